@@ -35,8 +35,6 @@ const HELIX_REPO_URL: &str = "https://github.com/helixdb/helix-db.git";
 const CARGO_MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
 
 pub async fn run(instance_name: String, metrics_sender: &MetricsSender) -> Result<MetricsData> {
-    let start_time = Instant::now();
-
     // Load project context
     let project = ProjectContext::find_and_load(None)?;
 
@@ -45,14 +43,42 @@ pub async fn run(instance_name: String, metrics_sender: &MetricsSender) -> Resul
 
     print_status("BUILD", &format!("Building instance '{instance_name}'"));
 
+    let metrics_data = compile_instance(&project, &instance_name, metrics_sender).await?;
+
+    // Generate Docker files
+    generate_docker_files(&project, &instance_name, instance_config.clone()).await?;
+
+    // For local instances, build Docker image
+    if instance_config.should_build_docker_image() {
+        let runtime = project.config.project.container_runtime;
+        DockerManager::check_runtime_available(runtime)?;
+        let docker = DockerManager::new(&project);
+
+        docker.build_image(&instance_name, instance_config.docker_build_target())?;
+    }
+
+    print_success(&format!("Instance '{instance_name}' built successfully"));
+
+    Ok(metrics_data)
+}
+
+/// Prepare the workspace of an instance and compile the project queries into it, without
+/// building an image.
+pub(crate) async fn compile_instance(
+    project: &ProjectContext,
+    instance_name: &str,
+    metrics_sender: &MetricsSender,
+) -> Result<MetricsData> {
+    let start_time = Instant::now();
+
     // Ensure Helix repo is cached
     ensure_helix_repo_cached().await?;
 
     // Prepare instance workspace
-    prepare_instance_workspace(&project, &instance_name).await?;
+    prepare_instance_workspace(project, instance_name).await?;
 
     // Compile project queries into the workspace
-    let compile_result = compile_project(&project, &instance_name).await;
+    let compile_result = compile_project(project, instance_name).await;
 
     // Collect metrics data
     let compile_time = start_time.elapsed().as_secs() as u32;
@@ -70,7 +96,7 @@ pub async fn run(instance_name: String, metrics_sender: &MetricsSender) -> Resul
 
     // Send compile metrics
     metrics_sender.send_compile_event(
-        instance_name.clone(),
+        instance_name.to_string(),
         metrics_data.queries_string.clone(),
         metrics_data.num_of_queries,
         compile_time,
@@ -81,21 +107,7 @@ pub async fn run(instance_name: String, metrics_sender: &MetricsSender) -> Resul
     // Propagate compilation error if any
     compile_result?;
 
-    // Generate Docker files
-    generate_docker_files(&project, &instance_name, instance_config.clone()).await?;
-
-    // For local instances, build Docker image
-    if instance_config.should_build_docker_image() {
-        let runtime = project.config.project.container_runtime;
-        DockerManager::check_runtime_available(runtime)?;
-        let docker = DockerManager::new(&project);
-
-        docker.build_image(&instance_name, instance_config.docker_build_target())?;
-    }
-
-    print_success(&format!("Instance '{instance_name}' built successfully"));
-
-    Ok(metrics_data.clone())
+    Ok(metrics_data)
 }
 
 async fn ensure_helix_repo_cached() -> Result<()> {
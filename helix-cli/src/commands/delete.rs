@@ -2,6 +2,7 @@ use crate::commands::integrations::ecr::EcrManager;
 use crate::commands::integrations::fly::FlyManager;
 use crate::config::InstanceInfo;
 use crate::docker::DockerManager;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
 use crate::utils::{
     print_confirm, print_lines, print_newline, print_status, print_success, print_warning,
@@ -48,6 +49,9 @@ pub async fn run(instance_name: String) -> Result<()> {
         docker.remove_instance_images(&instance_name)?;
     }
 
+    // Stop the native process, whose binary is in the workspace
+    NativeManager::new(&project).stop_instance(&instance_name)?;
+
     // Remove instance workspace
     let workspace = project.instance_workspace(&instance_name);
     if workspace.exists() {
@@ -0,0 +1,229 @@
+use crate::errors::CliError;
+use crate::metrics_sender::MetricsSender;
+use crate::native::NativeManager;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{
+    analyze_source, collect_hx_files, generate_content, parse_content,
+};
+use crate::utils::{print_error, print_line, print_status, print_success, print_warning};
+use eyre::Result;
+use helix_db::helix_engine::traversal_core::config::Config;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+/// Time between two scans of the queries directory
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Generated code of the queries of a project
+pub struct GeneratedQueries {
+    /// Content of `queries.rs`
+    pub code: String,
+    /// Generated code of each query, by name
+    pub queries: BTreeMap<String, String>,
+}
+
+/// Queries that changed between two generations
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct QueryChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl QueryChanges {
+    pub fn between(old: &GeneratedQueries, new: &GeneratedQueries) -> Self {
+        let mut changes = QueryChanges::default();
+        for (name, code) in &new.queries {
+            match old.queries.get(name) {
+                None => changes.added.push(name.clone()),
+                Some(old_code) if old_code != code => changes.modified.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        changes.removed = old
+            .queries
+            .keys()
+            .filter(|name| !new.queries.contains_key(*name))
+            .cloned()
+            .collect();
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Modification time and size of each .hx file of the project
+pub type QueriesSnapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+pub async fn run(instance_name: String, metrics_sender: &MetricsSender) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    // Get instance config
+    let instance_config = project.config.get_instance(&instance_name)?;
+    if !instance_config.is_local() {
+        return Err(CliError::new(format!(
+            "instance '{instance_name}' is not a local instance"
+        ))
+        .with_hint("only local instances can be run in watch mode")
+        .into());
+    }
+
+    // Taken before the first build, so edits made during it are picked up
+    let mut snapshot = snapshot_queries(&project);
+    crate::commands::run::run_native_instance(&project, &instance_name, metrics_sender).await?;
+    let mut current = generate_queries(&project, &instance_name)?;
+
+    print_status(
+        "DEV",
+        &format!(
+            "Watching {} for changes, press Ctrl+C to stop",
+            project.root.join(&project.config.project.queries).display()
+        ),
+    );
+
+    let mut build: Option<JoinHandle<Result<u32>>> = None;
+    let mut pending = false;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+
+        if let Some(finished) = build.take_if(|build| build.is_finished()) {
+            match finished.await? {
+                Ok(pid) => {
+                    print_success(&format!("Instance '{instance_name}' reloaded (pid {pid})"))
+                }
+                Err(e) => {
+                    print_error(&e.to_string());
+                    print_warning("The instance keeps running the previous build");
+                }
+            }
+        }
+
+        let latest = snapshot_queries(&project);
+        if latest != snapshot {
+            snapshot = latest;
+            print_status("DEV", "Change detected, analyzing queries...");
+            match generate_queries(&project, &instance_name) {
+                Ok(generated) if generated.code == current.code => {
+                    print_status("DEV", "Generated code is unchanged");
+                }
+                Ok(generated) => {
+                    write_queries(&project, &instance_name, &generated)?;
+                    print_changes(&QueryChanges::between(&current, &generated));
+                    current = generated;
+                    pending = true;
+                }
+                Err(e) => {
+                    print_error(&e.to_string());
+                    print_warning("The instance keeps running the last valid queries");
+                }
+            }
+        }
+
+        // changes made during a build are rebuilt together once it is done
+        if pending && build.is_none() {
+            pending = false;
+            let project = project.clone();
+            let instance_name = instance_name.clone();
+            build = Some(tokio::task::spawn_blocking(move || {
+                rebuild_instance(&project, &instance_name)
+            }));
+        }
+    }
+
+    // cargo is interrupted with us, wait for it before stopping the instance
+    if let Some(build) = build {
+        let _ = build.await;
+    }
+    print_line("");
+    NativeManager::new(&project).stop_instance(&instance_name)?;
+    print_success(&format!("Instance '{instance_name}' has been stopped"));
+    Ok(())
+}
+
+/// Take a snapshot of the .hx files, to detect when they are saved
+pub fn snapshot_queries(project: &ProjectContext) -> QueriesSnapshot {
+    collect_hx_files(&project.root, &project.config.project.queries)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|file| {
+            let metadata = file.metadata().ok()?;
+            Some((file.path(), (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+/// Parse and analyze the queries of the project and generate their code, without writing it.
+///
+/// Fails with the diagnostics of the analyzer if the queries are not valid.
+pub fn generate_queries(project: &ProjectContext, instance_name: &str) -> Result<GeneratedQueries> {
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+    let mut generated = analyze_source(source, &content.files)?;
+
+    // the config of the instance was written by the first build
+    let config_path = project
+        .container_dir(instance_name)
+        .join("src/config.hx.json");
+    generated.config =
+        Config::from_file(config_path).map_err(|e| eyre::eyre!("Failed to load config: {e}"))?;
+
+    let queries = generated
+        .queries
+        .iter()
+        .map(|query| (query.name.clone(), query.to_string()))
+        .collect();
+    Ok(GeneratedQueries {
+        code: generated.to_string(),
+        queries,
+    })
+}
+
+/// Write the generated code to the `queries.rs` of the instance workspace
+pub fn write_queries(
+    project: &ProjectContext,
+    instance_name: &str,
+    generated: &GeneratedQueries,
+) -> Result<()> {
+    let src_dir = project.container_dir(instance_name).join("src");
+    fs::write(src_dir.join("queries.rs"), &generated.code)?;
+    Ok(())
+}
+
+fn print_changes(changes: &QueryChanges) {
+    if changes.is_empty() {
+        print_status("DEV", "Schema changed, no query changed");
+        return;
+    }
+    print_status("DEV", "Queries changed:");
+    for name in &changes.added {
+        print_line(&format!("  + {name}"));
+    }
+    for name in &changes.modified {
+        print_line(&format!("  ~ {name}"));
+    }
+    for name in &changes.removed {
+        print_line(&format!("  - {name}"));
+    }
+}
+
+/// Rebuild the binary of the instance and restart it on the same data directory
+fn rebuild_instance(project: &ProjectContext, instance_name: &str) -> Result<u32> {
+    let native = NativeManager::new(project);
+    let build_mode = project.config.get_instance(instance_name)?.build_mode();
+    native.build_binary(instance_name, build_mode)?;
+    native.stop_instance(instance_name)?;
+    native.start_instance(instance_name)
+}
@@ -0,0 +1,71 @@
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::collect_hx_files;
+use crate::utils::{print_line, print_status, print_success};
+use eyre::Result;
+use helix_db::helixc::formatter::format_source;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub async fn run(check: bool) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let mut paths = hx_files
+        .iter()
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    if check {
+        print_status("FMT", "Checking formatting of Helix queries");
+    } else {
+        print_status("FMT", "Formatting Helix queries");
+    }
+    let changed = format_files(&paths, check)?;
+
+    for path in &changed {
+        let path = path.strip_prefix(&project.root).unwrap_or(path);
+        print_line(&format!("  {}", path.display()));
+    }
+    match (check, changed.len()) {
+        (true, 0) => print_success("All .hx files are formatted"),
+        (true, count) => {
+            return Err(
+                CliError::new(format!("{count} .hx file(s) are not formatted"))
+                    .with_hint("run 'helix fmt' to format them")
+                    .into(),
+            );
+        }
+        (false, count) => print_success(&format!("Formatted {count} .hx file(s)")),
+    }
+    Ok(())
+}
+
+/// Format the given files, returning the ones that were not formatted.
+///
+/// With `check`, files are left untouched.
+pub fn format_files(paths: &[PathBuf], check: bool) -> Result<Vec<PathBuf>> {
+    let mut changed = Vec::new();
+    for path in paths {
+        let content = fs::read_to_string(path)?;
+        let formatted = format_file(path, &content)?;
+        if formatted != content {
+            if !check {
+                fs::write(path, formatted)?;
+            }
+            changed.push(path.clone());
+        }
+    }
+    Ok(changed)
+}
+
+fn format_file(path: &Path, content: &str) -> Result<String> {
+    format_source(content).map_err(|e| {
+        CliError::new(format!("failed to format {}", path.display()))
+            .with_context(e.to_string())
+            .with_hint("fix the syntax errors reported by 'helix check' first")
+            .into()
+    })
+}
@@ -0,0 +1,63 @@
+use crate::GenerateAction;
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{
+    analyze_source, collect_hx_files, generate_content, parse_content,
+};
+use crate::utils::{print_status, print_success};
+use eyre::Result;
+use helix_db::helixc::generator::sdk::{Language, generate_sdk};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub async fn run(action: GenerateAction) -> Result<()> {
+    match action {
+        GenerateAction::Sdk { lang, output } => run_sdk(&lang, output).await,
+    }
+}
+
+async fn run_sdk(lang: &str, output: Option<String>) -> Result<()> {
+    let language = lang
+        .parse::<Language>()
+        .map_err(|e| CliError::new(e).with_hint("use --lang ts, --lang py or --lang rust"))?;
+
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    print_status("GENERATE", "Generating client for Helix queries");
+    let output_dir = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project.root.join("sdk"));
+    let path = generate_sdk_file(&project, language, &output_dir)?;
+
+    let path = path.strip_prefix(&project.root).unwrap_or(&path);
+    print_success(&format!("Client written to {}", path.display()));
+    Ok(())
+}
+
+/// Analyze the project queries and write their client to `output_dir`, returning its path.
+pub fn generate_sdk_file(
+    project: &ProjectContext,
+    language: Language,
+    output_dir: &Path,
+) -> Result<PathBuf> {
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+
+    if source.schema.is_empty() {
+        return Err(CliError::new("no schema definitions found in project")
+            .with_hint("add at least one schema definition like 'N::User { name: String }' to your .hx files")
+            .into());
+    }
+
+    // only clients for valid queries are generated
+    analyze_source(source.clone(), &content.files)?;
+    let client = generate_sdk(&source, language)
+        .map_err(|e| CliError::new("failed to generate client").with_context(e.to_string()))?;
+
+    fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(language.file_name());
+    fs::write(&path, client)?;
+    Ok(path)
+}
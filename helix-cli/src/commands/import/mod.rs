@@ -0,0 +1,612 @@
+//! `helix import` bulk-loads nodes, edges and vectors into a running local instance.
+//!
+//! The columns of each file are mapped onto the fields of the same name, and rows are sent
+//! in batches to the `/import` endpoint of the instance, which writes each batch in a single
+//! transaction. Nodes and vectors are imported first: their `id` column names them in the
+//! `from` and `to` columns of edges, which are mapped to the ids they were created with.
+
+pub mod reader;
+
+use crate::commands::import::reader::{Rows, read_rows};
+use crate::config::{BuildMode, InstanceInfo};
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{collect_hx_files, generate_content, parse_content};
+use crate::utils::{print_status, print_success, print_warning};
+use eyre::{Result, eyre};
+use helix_db::helixc::parser::types::{Field, FieldType, Schema};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Column naming a node or vector, for edges to point at it
+pub const ID_COLUMN: &str = "id";
+/// Column holding the id of the node or vector an edge starts from
+pub const FROM_COLUMN: &str = "from";
+/// Column holding the id of the node or vector an edge points to
+pub const TO_COLUMN: &str = "to";
+/// Column holding the data of a vector
+pub const VECTOR_COLUMN: &str = "vector";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Nodes,
+    Edges,
+    Vectors,
+}
+
+impl ImportKind {
+    /// Name of the kind in requests to the instance
+    fn as_str(&self) -> &'static str {
+        match self {
+            ImportKind::Nodes => "nodes",
+            ImportKind::Edges => "edges",
+            ImportKind::Vectors => "vectors",
+        }
+    }
+}
+
+/// A file to import, given as `Label=path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportSource {
+    pub kind: ImportKind,
+    pub label: String,
+    pub path: PathBuf,
+}
+
+impl ImportSource {
+    pub fn parse(kind: ImportKind, spec: &str) -> Result<Self> {
+        match spec.split_once('=') {
+            Some((label, path)) if !label.trim().is_empty() && !path.trim().is_empty() => {
+                Ok(Self {
+                    kind,
+                    label: label.trim().to_string(),
+                    path: PathBuf::from(path.trim()),
+                })
+            }
+            _ => Err(CliError::new(format!("invalid import '{spec}'"))
+                .with_hint(format!(
+                    "files are given as Label=path, e.g. --{} User=users.csv",
+                    kind.as_str()
+                ))
+                .into()),
+        }
+    }
+}
+
+/// Ids the nodes and vectors were created with, by label and by the value of their `id` column
+pub type IdMap = HashMap<String, HashMap<String, String>>;
+
+pub async fn run(
+    instance_name: String,
+    nodes: Vec<String>,
+    edges: Vec<String>,
+    vectors: Vec<String>,
+    batch_size: usize,
+) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    // Only local instances expose a port in helix.toml
+    let port = match project.config.get_instance(&instance_name)? {
+        InstanceInfo::Local(config) => {
+            if !matches!(config.build_mode, BuildMode::Dev) || !config.db_config.import {
+                print_warning(&format!(
+                    "instance '{instance_name}' does not enable imports, so it may not serve them"
+                ));
+            }
+            config.port.unwrap_or(6969)
+        }
+        _ => {
+            return Err(CliError::new(format!(
+                "instance '{instance_name}' is not a local instance"
+            ))
+            .with_hint("'helix import' can only load data into local instances")
+            .into());
+        }
+    };
+    if batch_size == 0 {
+        return Err(CliError::new("--batch-size must be at least 1").into());
+    }
+
+    // nodes and vectors first, so edges can point at them
+    let mut sources = Vec::new();
+    for (kind, specs) in [
+        (ImportKind::Nodes, nodes),
+        (ImportKind::Vectors, vectors),
+        (ImportKind::Edges, edges),
+    ] {
+        for spec in specs {
+            sources.push(ImportSource::parse(kind, &spec)?);
+        }
+    }
+    if sources.is_empty() {
+        return Err(CliError::new("nothing to import")
+            .with_hint("pass files with --nodes, --edges or --vectors, e.g. --nodes User=users.csv")
+            .into());
+    }
+
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+    let schema = source
+        .get_latest_schema()
+        .map_err(|e| eyre!("invalid schema: {e}"))?;
+
+    // every file is checked against the schema before anything is written
+    let mut files = Vec::new();
+    for source in sources {
+        let mapping = Mapping::new(schema, source.kind, &source.label)?;
+        let rows = read_rows(&source.path)?;
+        if let Some(columns) = &rows.columns {
+            mapping.check_columns(columns, &source.path.display().to_string())?;
+        }
+        files.push((source, mapping, rows));
+    }
+
+    let mut importer = Importer {
+        url: format!("http://localhost:{port}/import"),
+        instance_name,
+        schema: source.schema_source(),
+        client: reqwest::Client::new(),
+        batch_size,
+        ids: IdMap::new(),
+    };
+    for (source, mapping, rows) in files {
+        importer.import_file(&source, &mapping, rows).await?;
+    }
+    Ok(())
+}
+
+/// How the columns of a file map onto the fields of its type
+pub struct Mapping {
+    pub kind: ImportKind,
+    pub label: String,
+    pub fields: Vec<Field>,
+    /// Types of the endpoints of an edge
+    pub endpoints: Option<(String, String)>,
+}
+
+impl Mapping {
+    pub fn new(schema: &Schema, kind: ImportKind, label: &str) -> Result<Self> {
+        let (fields, endpoints, available) = match kind {
+            ImportKind::Nodes => (
+                schema
+                    .node_schemas
+                    .iter()
+                    .find(|node| node.name.1 == label)
+                    .map(|node| node.fields.clone()),
+                None,
+                schema
+                    .node_schemas
+                    .iter()
+                    .map(|node| node.name.1.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            ImportKind::Edges => {
+                let edge = schema.edge_schemas.iter().find(|edge| edge.name.1 == label);
+                (
+                    edge.map(|edge| edge.properties.clone().unwrap_or_default()),
+                    edge.map(|edge| (edge.from.1.clone(), edge.to.1.clone())),
+                    schema
+                        .edge_schemas
+                        .iter()
+                        .map(|edge| edge.name.1.clone())
+                        .collect(),
+                )
+            }
+            ImportKind::Vectors => (
+                schema
+                    .vector_schemas
+                    .iter()
+                    .find(|vector| vector.name == label)
+                    .map(|vector| vector.fields.clone()),
+                None,
+                schema
+                    .vector_schemas
+                    .iter()
+                    .map(|vector| vector.name.clone())
+                    .collect(),
+            ),
+        };
+
+        let kind_name = match kind {
+            ImportKind::Nodes => "node",
+            ImportKind::Edges => "edge",
+            ImportKind::Vectors => "vector",
+        };
+        let Some(fields) = fields else {
+            let hint = match available.is_empty() {
+                true => format!("the schema declares no {kind_name} types"),
+                false => format!("{kind_name} types: {}", available.join(", ")),
+            };
+            return Err(CliError::new(format!(
+                "the schema has no {kind_name} type named '{label}'"
+            ))
+            .with_hint(hint)
+            .into());
+        };
+        if let Some(field) = fields
+            .iter()
+            .find(|field| matches!(field.field_type, FieldType::Embedding(_)))
+        {
+            return Err(CliError::new(format!(
+                "'{label}' embeds a vector in '{}', which imports do not support",
+                field.name
+            ))
+            .into());
+        }
+
+        // struct values are read as the JSON object of their fields
+        let fields = fields
+            .into_iter()
+            .map(|field| Field {
+                field_type: schema.resolve_structs(&field.field_type),
+                ..field
+            })
+            .collect();
+
+        Ok(Self {
+            kind,
+            label: label.to_string(),
+            fields,
+            endpoints,
+        })
+    }
+
+    /// Columns a file of this type must have
+    fn required_columns(&self) -> Vec<&str> {
+        let mut columns = match self.kind {
+            ImportKind::Nodes => Vec::new(),
+            ImportKind::Edges => vec![FROM_COLUMN, TO_COLUMN],
+            ImportKind::Vectors => vec![VECTOR_COLUMN],
+        };
+        // fields that are neither optional nor defaulted are written on every insert
+        columns.extend(
+            self.fields
+                .iter()
+                .filter(|field| !field.is_optional && field.defaults.is_none())
+                .map(|field| field.name.as_str()),
+        );
+        columns
+    }
+
+    /// Whether a column is read by the import rather than written as a field
+    fn is_special(&self, column: &str) -> bool {
+        match self.kind {
+            ImportKind::Nodes => column == ID_COLUMN,
+            ImportKind::Edges => column == FROM_COLUMN || column == TO_COLUMN,
+            ImportKind::Vectors => column == ID_COLUMN || column == VECTOR_COLUMN,
+        }
+    }
+
+    /// Check that every column is a field of the type, and that no required field is missing
+    pub fn check_columns(&self, columns: &[String], file: &str) -> Result<()> {
+        if let Some(column) = columns.iter().find(|column| {
+            !self.is_special(column) && !self.fields.iter().any(|f| f.name == **column)
+        }) {
+            return Err(CliError::new(format!(
+                "column '{column}' of {file} is not a field of {}",
+                self.label
+            ))
+            .with_hint(self.columns_hint())
+            .into());
+        }
+        let missing = self
+            .required_columns()
+            .into_iter()
+            .filter(|required| !columns.iter().any(|column| column == required))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(CliError::new(format!(
+                "{file} has no column for {}",
+                missing
+                    .iter()
+                    .map(|column| format!("'{column}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .with_hint(self.columns_hint())
+            .into());
+        }
+        Ok(())
+    }
+
+    fn columns_hint(&self) -> String {
+        let special = match self.kind {
+            ImportKind::Nodes => format!("'{ID_COLUMN}' to name the nodes"),
+            ImportKind::Edges => format!("'{FROM_COLUMN}' and '{TO_COLUMN}' for the endpoints"),
+            ImportKind::Vectors => {
+                format!("'{VECTOR_COLUMN}' for the data and '{ID_COLUMN}' to name the vectors")
+            }
+        };
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        match fields.is_empty() {
+            true => format!("{} has no fields, columns: {special}", self.label),
+            false => format!(
+                "fields of {}: {}, and {special}",
+                self.label,
+                fields.join(", ")
+            ),
+        }
+    }
+
+    /// Turn a row into the row sent to the instance, along with the value of its `id` column
+    pub fn row(
+        &self,
+        mut values: Map<String, Value>,
+        ids: &IdMap,
+    ) -> Result<(Option<String>, Value)> {
+        let columns = values.keys().cloned().collect::<Vec<_>>();
+        if let Some(column) = columns.iter().find(|column| {
+            !self.is_special(column) && !self.fields.iter().any(|f| f.name == **column)
+        }) {
+            return Err(eyre!("'{column}' is not a field of {}", self.label));
+        }
+
+        let mut row = Map::new();
+        let id = match self.kind {
+            ImportKind::Nodes | ImportKind::Vectors => {
+                values.remove(ID_COLUMN).map(|id| key(&id)).transpose()?
+            }
+            ImportKind::Edges => None,
+        };
+        if let Some((from_label, to_label)) = &self.endpoints {
+            for (column, label) in [(FROM_COLUMN, from_label), (TO_COLUMN, to_label)] {
+                let value = values
+                    .remove(column)
+                    .ok_or_else(|| eyre!("'{column}' is empty"))?;
+                row.insert(
+                    column.to_string(),
+                    Value::String(resolve(ids, label, &key(&value)?)?),
+                );
+            }
+        }
+        if self.kind == ImportKind::Vectors {
+            let data = values
+                .remove(VECTOR_COLUMN)
+                .ok_or_else(|| eyre!("'{VECTOR_COLUMN}' is empty"))?;
+            row.insert(VECTOR_COLUMN.to_string(), vector(data)?);
+        }
+
+        let mut properties = Map::new();
+        for (name, value) in values {
+            if value.is_null() {
+                continue;
+            }
+            let field = self.fields.iter().find(|field| field.name == name);
+            let value = match field {
+                Some(field) => {
+                    cell(value, &field.field_type).map_err(|e| eyre!("'{name}': {e}"))?
+                }
+                None => value,
+            };
+            properties.insert(name, value);
+        }
+        row.insert("properties".to_string(), Value::Object(properties));
+        Ok((id, Value::Object(row)))
+    }
+}
+
+/// The value of an `id`, `from` or `to` column as a key of the id map
+fn key(value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(eyre!("{value} is not a valid id")),
+    }
+}
+
+/// The id of the node or vector an edge points at: one imported with this `id`, or one that
+/// already exists in the graph when it is written as its id
+pub fn resolve(ids: &IdMap, label: &str, key: &str) -> Result<String> {
+    if let Some(id) = ids.get(label).and_then(|ids| ids.get(key)) {
+        return Ok(id.clone());
+    }
+    match uuid::Uuid::parse_str(key) {
+        Ok(id) => Ok(id.to_string()),
+        Err(_) => Err(eyre!("no {label} was imported with id '{key}'")),
+    }
+}
+
+/// Cast a value read from a file to the JSON its field expects, CSV cells being strings
+pub fn cell(value: Value, ty: &FieldType) -> Result<Value> {
+    let Value::String(s) = &value else {
+        return Ok(value);
+    };
+    let text = s.trim();
+    match ty {
+        FieldType::F32
+        | FieldType::F64
+        | FieldType::I8
+        | FieldType::I16
+        | FieldType::I32
+        | FieldType::I64
+        | FieldType::U8
+        | FieldType::U16
+        | FieldType::U32
+        | FieldType::U64
+        | FieldType::U128 => match serde_json::from_str::<serde_json::Number>(text) {
+            Ok(n) => Ok(Value::Number(n)),
+            Err(_) => Err(eyre!("'{s}' is not a number")),
+        },
+        FieldType::Boolean => match text.to_lowercase().as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(eyre!("'{s}' is not a boolean")),
+        },
+        FieldType::Array(_) | FieldType::Object(_) => {
+            serde_json::from_str(text).map_err(|e| eyre!("'{s}' is not valid JSON: {e}"))
+        }
+        _ => Ok(value),
+    }
+}
+
+/// The data of a vector, written as a JSON array of numbers
+pub fn vector(value: Value) -> Result<Value> {
+    let value = match value {
+        Value::String(s) => serde_json::from_str(&s)
+            .map_err(|_| eyre!("'{VECTOR_COLUMN}' is not an array of numbers"))?,
+        value => value,
+    };
+    match &value {
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_number) => Ok(value),
+        _ => Err(eyre!("'{VECTOR_COLUMN}' is not an array of numbers")),
+    }
+}
+
+struct Importer {
+    url: String,
+    instance_name: String,
+    schema: String,
+    client: reqwest::Client,
+    batch_size: usize,
+    ids: IdMap,
+}
+
+impl Importer {
+    async fn import_file(
+        &mut self,
+        source: &ImportSource,
+        mapping: &Mapping,
+        rows: Rows,
+    ) -> Result<()> {
+        let file = source.path.display().to_string();
+        print_status(
+            "IMPORT",
+            &format!(
+                "Importing {} {} from {file}",
+                source.label,
+                source.kind.as_str()
+            ),
+        );
+        let start = Instant::now();
+
+        let mut imported = 0;
+        let mut batch = Vec::with_capacity(self.batch_size);
+        let mut keys = Vec::with_capacity(self.batch_size);
+        let mut seen = HashMap::new();
+        for (i, values) in rows.enumerate() {
+            let number = i + 1;
+            let row_error = |e: eyre::Report| {
+                CliError::new(format!("row {number} of {file} is invalid"))
+                    .with_caused_by(e.to_string())
+                    .with_context(format!("{imported} row(s) of {file} were imported"))
+            };
+            let (id, row) = values
+                .and_then(|values| mapping.row(values, &self.ids))
+                .map_err(row_error)?;
+            if let Some(id) = &id
+                && let Some(first) = seen.insert(id.clone(), number)
+            {
+                return Err(row_error(eyre!("id '{id}' is already used by row {first}")).into());
+            }
+            batch.push(row);
+            keys.push(id);
+
+            if batch.len() == self.batch_size {
+                let first_row = number + 1 - batch.len();
+                self.send(source, &file, first_row, imported, &mut batch, &mut keys)
+                    .await?;
+                imported = number;
+            }
+        }
+        if !batch.is_empty() {
+            let first_row = imported + 1;
+            imported += batch.len();
+            self.send(
+                source,
+                &file,
+                first_row,
+                first_row - 1,
+                &mut batch,
+                &mut keys,
+            )
+            .await?;
+        }
+
+        print_success(&format!(
+            "Imported {imported} {} {} from {file} in {:.1}s",
+            source.label,
+            source.kind.as_str(),
+            start.elapsed().as_secs_f64()
+        ));
+        Ok(())
+    }
+
+    /// Write a batch of rows in a single transaction, recording the ids of its nodes and vectors
+    async fn send(
+        &mut self,
+        source: &ImportSource,
+        file: &str,
+        first_row: usize,
+        imported: usize,
+        batch: &mut Vec<Value>,
+        keys: &mut Vec<Option<String>>,
+    ) -> Result<()> {
+        let body = json!({
+            "schema": self.schema,
+            "kind": source.kind.as_str(),
+            "label": source.label,
+            "rows": std::mem::take(batch),
+            "first_row": first_row,
+        });
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                CliError::new(format!(
+                    "could not reach instance '{}' at {}",
+                    self.instance_name, self.url
+                ))
+                .with_caused_by(e.to_string())
+                .with_hint(format!(
+                    "run 'helix start {}' to start the instance",
+                    self.instance_name
+                ))
+            })?;
+
+        let status = response.status();
+        let text = response.text().await?;
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(CliError::new(format!(
+                "instance '{}' does not serve imports",
+                self.instance_name
+            ))
+            .with_hint("set import = true and build_mode = \"dev\" for the instance and rebuild it")
+            .into());
+        }
+        if !status.is_success() {
+            return Err(CliError::new(format!("import of {file} failed"))
+                .with_caused_by(text)
+                .with_context(format!("{imported} row(s) of {file} were imported"))
+                .into());
+        }
+
+        let created = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|value| {
+                value["ids"].as_array().map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| id.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .ok_or_else(|| eyre!("unexpected response from instance: {text}"))?;
+        let ids = self.ids.entry(source.label.clone()).or_default();
+        for (key, id) in std::mem::take(keys).into_iter().zip(created) {
+            if let Some(key) = key {
+                ids.insert(key, id);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+//! Readers of the files `helix import` loads, yielding their rows as JSON objects.
+//!
+//! CSV cells are read as strings, and empty cells are left out of their row, so they are cast
+//! to the types of their fields once they are mapped onto the schema.
+
+use eyre::{Result, eyre};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Jsonl,
+    Parquet,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("jsonl" | "ndjson") => Ok(Format::Jsonl),
+            Some("parquet") => Ok(Format::Parquet),
+            _ => Err(eyre!(
+                "{}: unsupported file type, expected .csv, .jsonl or .parquet",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// The rows of a file
+pub struct Rows {
+    /// Columns of the file, if it declares them up front
+    pub columns: Option<Vec<String>>,
+    rows: Box<dyn Iterator<Item = Result<Map<String, Value>>>>,
+}
+
+impl Iterator for Rows {
+    type Item = Result<Map<String, Value>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next()
+    }
+}
+
+pub fn read_rows(path: &Path) -> Result<Rows> {
+    let file = File::open(path).map_err(|e| eyre!("failed to open {}: {e}", path.display()))?;
+    match Format::from_path(path)? {
+        Format::Csv => csv_rows(BufReader::new(file)),
+        Format::Jsonl => Ok(jsonl_rows(BufReader::new(file))),
+        Format::Parquet => parquet_rows(file),
+    }
+}
+
+/// Rows of a CSV file whose first record names its columns
+pub fn csv_rows(reader: impl BufRead + 'static) -> Result<Rows> {
+    let mut records = CsvRecords {
+        lines: reader.lines(),
+    };
+    let columns = match records.next() {
+        Some(header) => header?
+            .into_iter()
+            .map(|column| column.trim().to_string())
+            .collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+
+    let names = columns.clone();
+    let rows = records.enumerate().map(move |(i, record)| {
+        let record = record?;
+        if record.len() != names.len() {
+            return Err(eyre!(
+                "row {} has {} cells, the header names {} columns",
+                i + 1,
+                record.len(),
+                names.len()
+            ));
+        }
+        Ok(names
+            .iter()
+            .zip(record)
+            .filter(|(_, cell)| !cell.is_empty())
+            .map(|(name, cell)| (name.clone(), Value::String(cell)))
+            .collect())
+    });
+    Ok(Rows {
+        columns: Some(columns),
+        rows: Box::new(rows),
+    })
+}
+
+/// Records of a CSV file, as RFC 4180 quotes them: a quoted cell may hold commas, line
+/// breaks and quotes written twice
+struct CsvRecords<R> {
+    lines: std::io::Lines<R>,
+}
+
+impl<R: BufRead> Iterator for CsvRecords<R> {
+    type Item = Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e.into())),
+        };
+        // blank lines between records are skipped
+        while line.trim().is_empty() {
+            line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+        }
+
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut quoted = false;
+        loop {
+            let mut chars = line.trim_end_matches('\r').chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' if quoted && chars.peek() == Some(&'"') => {
+                        chars.next();
+                        cell.push('"');
+                    }
+                    '"' if quoted => quoted = false,
+                    '"' if cell.is_empty() => quoted = true,
+                    ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+                    c => cell.push(c),
+                }
+            }
+            if !quoted {
+                break;
+            }
+            // the quoted cell goes on on the next line
+            cell.push('\n');
+            line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => return Some(Err(eyre!("a quoted cell is not closed"))),
+            };
+        }
+        cells.push(cell);
+        Some(Ok(cells))
+    }
+}
+
+/// Rows of a file holding a JSON object per line
+pub fn jsonl_rows(reader: impl BufRead + 'static) -> Rows {
+    let rows = reader
+        .lines()
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .enumerate()
+        .map(|(i, line)| match serde_json::from_str(&line?) {
+            Ok(Value::Object(row)) => Ok(row),
+            Ok(_) => Err(eyre!("row {} is not a JSON object", i + 1)),
+            Err(e) => Err(eyre!("row {} is not valid JSON: {e}", i + 1)),
+        });
+    Rows {
+        columns: None,
+        rows: Box::new(rows),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_rows(file: File) -> Result<Rows> {
+    use polars::prelude::*;
+
+    let frame = ParquetReader::new(file).finish()?;
+    let columns = frame
+        .get_column_names()
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+
+    let names = columns.clone();
+    let rows = (0..frame.height()).map(move |i| {
+        let mut row = Map::new();
+        for (name, column) in names.iter().zip(frame.get_columns()) {
+            let value = any_value(column.get(i)?);
+            if !value.is_null() {
+                row.insert(name.clone(), value);
+            }
+        }
+        Ok(row)
+    });
+    Ok(Rows {
+        columns: Some(columns),
+        rows: Box::new(rows),
+    })
+}
+
+#[cfg(feature = "parquet")]
+fn any_value(value: polars::prelude::AnyValue) -> Value {
+    use polars::prelude::AnyValue;
+
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => Value::Bool(b),
+        AnyValue::String(s) => Value::String(s.to_string()),
+        AnyValue::StringOwned(s) => Value::String(s.to_string()),
+        AnyValue::UInt8(n) => n.into(),
+        AnyValue::UInt16(n) => n.into(),
+        AnyValue::UInt32(n) => n.into(),
+        AnyValue::UInt64(n) => n.into(),
+        AnyValue::Int8(n) => n.into(),
+        AnyValue::Int16(n) => n.into(),
+        AnyValue::Int32(n) => n.into(),
+        AnyValue::Int64(n) => n.into(),
+        AnyValue::Float32(n) => {
+            serde_json::Number::from_f64(n as f64).map_or(Value::Null, Value::Number)
+        }
+        AnyValue::Float64(n) => serde_json::Number::from_f64(n).map_or(Value::Null, Value::Number),
+        AnyValue::List(series) => Value::Array(series.iter().map(any_value).collect()),
+        value => Value::String(value.to_string()),
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+fn parquet_rows(_file: File) -> Result<Rows> {
+    Err(
+        crate::errors::CliError::new("this build of helix cannot read Parquet files")
+            .with_hint(
+                "reinstall the CLI with `--features parquet`, or convert the file to CSV or JSONL",
+            )
+            .into(),
+    )
+}
@@ -0,0 +1,726 @@
+use helix_db::helixc::{
+    analyzer::{
+        analyze_types,
+        diagnostic::{Diagnostic as HelixDiagnostic, DiagnosticSeverity},
+        fix::Fix,
+        types::Type,
+    },
+    parser::{
+        HelixParser,
+        location::{Loc, Span},
+        types::{
+            Content, Field, ForLoopVars, HxFile, Query, Schema, Source, Statement, StatementType,
+        },
+    },
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CompletionItem, CompletionItemKind,
+    Diagnostic, DiagnosticSeverity as Severity, NumberOrString, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+use regex::Regex;
+use std::{collections::HashMap, panic, sync::LazyLock};
+
+/// Steps that can follow `::` in a traversal, with a short description
+const STEPS: &[(&str, &str)] = &[
+    ("Out", "nodes reached through outgoing edges"),
+    ("In", "nodes reached through incoming edges"),
+    ("OutE", "outgoing edges"),
+    ("InE", "incoming edges"),
+    ("FromN", "node an edge starts from"),
+    ("ToN", "node an edge points to"),
+    ("FromV", "vector an edge starts from"),
+    ("ToV", "vector an edge points to"),
+    ("OutN", "nodes reached within a range of hops"),
+    ("ShortestPath", "shortest path to or from a node"),
+    ("WHERE", "keep the items matching a condition"),
+    ("COUNT", "number of items"),
+    ("RANGE", "items between two positions"),
+    ("ORDER", "sort the items"),
+    ("FIRST", "first item"),
+    ("UPDATE", "update properties of the items"),
+    ("ID", "ids of the items"),
+    ("AGGREGATE_BY", "group the items by properties"),
+    ("GROUP_BY", "count the items by properties"),
+    ("AFTER", "resume after a cursor"),
+    ("CURSOR", "cursor past the last item"),
+    ("RerankRRF", "rerank with reciprocal rank fusion"),
+    ("RerankMMR", "rerank with maximal marginal relevance"),
+    ("GT", "greater than"),
+    ("GTE", "greater than or equal"),
+    ("LT", "less than"),
+    ("LTE", "less than or equal"),
+    ("EQ", "equal to"),
+    ("NEQ", "not equal to"),
+    ("CONTAINS", "contains a value"),
+    ("IS_IN", "is one of the values"),
+    ("IS_NULL", "property is missing"),
+    ("ANY", "any element matches"),
+    ("ALL", "every element matches"),
+];
+
+/// The kind of schema item a label refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemKind {
+    Node,
+    Edge,
+    Vector,
+    Enum,
+    Struct,
+}
+
+/// Diagnostics, schema and inferred types of a set of `.hx` files
+pub struct Analysis {
+    files: Vec<HxFile>,
+    source: Option<Source>,
+    /// Diagnostics with the edit fixing them, by file name
+    diagnostics: Vec<(String, Diagnostic, Option<TextEdit>)>,
+    types: Vec<(Loc, Type)>,
+}
+
+impl Analysis {
+    /// Parse and analyze the files together, the way `helix check` does
+    pub fn new(files: Vec<HxFile>) -> Self {
+        let mut analysis = Analysis {
+            files,
+            source: None,
+            diagnostics: Vec::new(),
+            types: Vec::new(),
+        };
+
+        // parse the files one by one first, so syntax errors point at their file
+        for file in &analysis.files {
+            let content = Content {
+                content: file.content.clone(),
+                files: vec![file.clone()],
+                source: Source::default(),
+            };
+            if let Err(e) = HelixParser::parse_source(&content) {
+                analysis
+                    .diagnostics
+                    .push((file.name.clone(), parse_error(&e.to_string()), None));
+            }
+        }
+        if !analysis.diagnostics.is_empty() {
+            return analysis;
+        }
+
+        let content = Content {
+            content: String::new(),
+            files: analysis.files.clone(),
+            source: Source::default(),
+        };
+        let Ok(source) = HelixParser::parse_source(&content) else {
+            return analysis;
+        };
+        if source.schema.is_empty() {
+            analysis.source = Some(source);
+            return analysis;
+        }
+
+        // the analyzer panics on some invalid input, which must not take the server down
+        let result = panic::catch_unwind(|| analyze_types(&source));
+        if let Ok(Ok((diagnostics, types))) = result {
+            analysis.diagnostics = diagnostics
+                .iter()
+                .filter_map(|diagnostic| {
+                    let file = diagnostic.filepath.clone()?;
+                    let edit = diagnostic.fix.as_ref().and_then(fix_edit);
+                    Some((file, to_lsp_diagnostic(diagnostic), edit))
+                })
+                .collect();
+            analysis.types = types;
+        }
+        analysis.source = Some(source);
+        analysis
+    }
+
+    /// Whether the files parsed, so the schema and types can be used
+    pub fn is_parsed(&self) -> bool {
+        self.source.is_some()
+    }
+
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.files.iter().map(|file| file.name.as_str())
+    }
+
+    pub fn diagnostics(&self, file: &str) -> Vec<Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|(name, _, _)| name == file)
+            .map(|(_, diagnostic, _)| diagnostic.clone())
+            .collect()
+    }
+
+    /// Quick fixes for the diagnostics overlapping `range` that carry a `Fix`
+    pub fn code_actions(&self, file: &str, uri: &Url, range: Range) -> Vec<CodeActionOrCommand> {
+        self.diagnostics
+            .iter()
+            .filter(|(name, diagnostic, _)| {
+                name == file
+                    && diagnostic.range.start <= range.end
+                    && range.start <= diagnostic.range.end
+            })
+            .filter_map(|(_, diagnostic, edit)| {
+                let edit = edit.clone()?;
+                let title = match diagnostic.message.split_once("\n\nhint: ") {
+                    Some((_, hint)) => hint.to_string(),
+                    None => "Apply suggested fix".to_string(),
+                };
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect()
+    }
+
+    /// Markdown describing the schema item, variable, field or expression at the position
+    pub fn hover(&self, file: &str, position: Position) -> Option<String> {
+        let word = self.word_at(file, position)?;
+        let schema = self.schema()?;
+
+        if let Some((_, loc)) = schema_items(schema).find(|(name, _)| *name == word) {
+            return Some(code_block(&loc.span));
+        }
+        if let Some(query) = self.query_at(file, position)
+            && let Some(variable) = variables(query)
+                .into_iter()
+                .rfind(|variable| variable.name == word)
+        {
+            let ty = match &variable.ty {
+                VariableType::Param(ty) => ty.to_string(),
+                VariableType::Value(loc) => self
+                    .type_of(loc)
+                    .map_or_else(|| "unknown".to_string(), type_name),
+                VariableType::Unknown => "unknown".to_string(),
+            };
+            return Some(code_block(&format!("{word}: {ty}")));
+        }
+        if let Some(label) = self.label_at(file, position)
+            && let Some(field) = fields(schema, &label).find(|field| field.name == word)
+        {
+            return Some(code_block(&format!(
+                "{label}.{}: {}",
+                field.name, field.field_type
+            )));
+        }
+
+        let ty = self
+            .types_at(file, position)
+            .into_iter()
+            .map(|(_, ty)| ty)
+            .next()?;
+        Some(code_block(&type_name(ty)))
+    }
+
+    /// Location of the schema item, variable or field named at the position
+    pub fn definition(&self, file: &str, position: Position) -> Option<(String, Range)> {
+        let word = self.word_at(file, position)?;
+        let schema = self.schema()?;
+
+        if let Some((_, loc)) = schema_items(schema).find(|(name, _)| *name == word) {
+            return Some((loc.filepath.clone()?, name_range(loc, &word)));
+        }
+        if let Some(query) = self.query_at(file, position) {
+            let defined = variables(query)
+                .into_iter()
+                .filter(|variable| variable.name == word)
+                .collect::<Vec<_>>();
+            // the closest definition before the position, as later ones shadow earlier ones
+            if let Some(variable) = defined
+                .iter()
+                .rfind(|variable| span_position(variable.loc.start) <= position)
+                .or(defined.first())
+            {
+                return Some((file.to_string(), name_range(&variable.loc, &word)));
+            }
+        }
+        let label = self.label_at(file, position)?;
+        let (_, item) = schema_items(schema).find(|(name, _)| *name == label)?;
+        let field = fields(schema, &label).find(|field| field.name == word)?;
+        Some((item.filepath.clone()?, name_range(&field.loc, &word)))
+    }
+
+    /// Completion of labels, fields and steps for the text before the cursor
+    pub fn completions(&self, file: &str, position: Position, line: &str) -> Vec<CompletionItem> {
+        static LABEL: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(\w+)<\w*$").expect("valid regex"));
+        static FIELDS: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"(::|!)\{[\w\s,:]*$").expect("valid regex"));
+        static STEP: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"::\w*$").expect("valid regex"));
+
+        let prefix = line
+            .char_indices()
+            .nth(position.character as usize)
+            .map_or(line, |(i, _)| &line[..i]);
+        let Some(schema) = self.schema() else {
+            return Vec::new();
+        };
+
+        if let Some(captures) = LABEL.captures(prefix) {
+            let kinds: &[ItemKind] = match &captures[1] {
+                "N" | "AddN" | "UpsertN" => &[ItemKind::Node],
+                "E" | "AddE" | "UpsertE" | "Out" | "In" | "OutE" | "InE" | "OutN" => {
+                    &[ItemKind::Edge]
+                }
+                "V" | "AddV" | "SearchV" | "BatchAddV" => &[ItemKind::Vector],
+                "SearchBM25" => &[ItemKind::Node],
+                "ORDER" => {
+                    return ["Asc", "Desc"]
+                        .into_iter()
+                        .map(|order| completion(order, CompletionItemKind::KEYWORD, None))
+                        .collect();
+                }
+                _ => &[ItemKind::Node, ItemKind::Edge, ItemKind::Vector],
+            };
+            return label_completions(schema, kinds);
+        }
+        if FIELDS.is_match(prefix) {
+            let label = self
+                .label_at(file, position)
+                .or_else(|| last_label(schema, prefix));
+            return match label {
+                Some(label) => fields(schema, &label)
+                    .map(|field| {
+                        completion(
+                            &field.name,
+                            CompletionItemKind::FIELD,
+                            Some(field.field_type.to_string()),
+                        )
+                    })
+                    .collect(),
+                None => Vec::new(),
+            };
+        }
+        if STEP.is_match(prefix) {
+            return STEPS
+                .iter()
+                .map(|(step, detail)| {
+                    completion(step, CompletionItemKind::METHOD, Some(detail.to_string()))
+                })
+                .collect();
+        }
+
+        let mut items = label_completions(
+            schema,
+            &[
+                ItemKind::Node,
+                ItemKind::Edge,
+                ItemKind::Vector,
+                ItemKind::Enum,
+                ItemKind::Struct,
+            ],
+        );
+        if let Some(query) = self.query_at(file, position) {
+            let mut names = variables(query)
+                .into_iter()
+                .map(|variable| variable.name)
+                .collect::<Vec<_>>();
+            names.sort();
+            names.dedup();
+            items.extend(
+                names
+                    .iter()
+                    .map(|name| completion(name, CompletionItemKind::VARIABLE, None)),
+            );
+        }
+        items
+    }
+
+    fn schema(&self) -> Option<&Schema> {
+        self.source.as_ref()?.get_latest_schema().ok()
+    }
+
+    fn word_at(&self, file: &str, position: Position) -> Option<String> {
+        let content = &self.files.iter().find(|f| f.name == file)?.content;
+        let line = content.lines().nth(position.line as usize)?;
+        let chars = line.chars().collect::<Vec<_>>();
+        let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+        let at = (position.character as usize).min(chars.len());
+        let start = chars[..at]
+            .iter()
+            .rposition(|c| !is_word(c))
+            .map_or(0, |i| i + 1);
+        let end = chars[at..]
+            .iter()
+            .position(|c| !is_word(c))
+            .map_or(chars.len(), |i| at + i);
+        (start < end).then(|| chars[start..end].iter().collect())
+    }
+
+    fn query_at(&self, file: &str, position: Position) -> Option<&Query> {
+        self.source.as_ref()?.queries.iter().find(|query| {
+            query.loc.filepath.as_deref() == Some(file) && contains(&query.loc, position)
+        })
+    }
+
+    /// Types of the expressions containing the position, innermost first
+    fn types_at(&self, file: &str, position: Position) -> Vec<&(Loc, Type)> {
+        let mut types = self
+            .types
+            .iter()
+            .filter(|(loc, _)| loc.filepath.as_deref() == Some(file) && contains(loc, position))
+            .collect::<Vec<_>>();
+        types.sort_by_key(|(loc, _)| loc.span.len());
+        types
+    }
+
+    fn type_of(&self, loc: &Loc) -> Option<&Type> {
+        self.types
+            .iter()
+            .find(|(ty_loc, _)| {
+                span_position(ty_loc.start) == span_position(loc.start)
+                    && span_position(ty_loc.end) == span_position(loc.end)
+            })
+            .map(|(_, ty)| ty)
+    }
+
+    /// The node, edge or vector type of the innermost expression at the position
+    fn label_at(&self, file: &str, position: Position) -> Option<String> {
+        self.types_at(file, position)
+            .into_iter()
+            .find_map(|(_, ty)| item_label(ty))
+    }
+}
+
+/// Convert an analyzer diagnostic, appending its hint to the message
+fn to_lsp_diagnostic(diagnostic: &HelixDiagnostic) -> Diagnostic {
+    let severity = match diagnostic.severity {
+        DiagnosticSeverity::Error => Severity::ERROR,
+        DiagnosticSeverity::Warning => Severity::WARNING,
+        DiagnosticSeverity::Info => Severity::INFORMATION,
+        DiagnosticSeverity::Hint | DiagnosticSeverity::Empty => Severity::HINT,
+    };
+    let message = match &diagnostic.hint {
+        Some(hint) if !hint.is_empty() => format!("{}\n\nhint: {hint}", diagnostic.message),
+        _ => diagnostic.message.clone(),
+    };
+    Diagnostic {
+        range: loc_range(&diagnostic.location),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(diagnostic.error_code.to_string())),
+        source: Some("helix".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Turn a pest syntax error into a diagnostic at the position it reports
+fn parse_error(message: &str) -> Diagnostic {
+    static POSITION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"--> (\d+):(\d+)").expect("valid regex"));
+    let position = POSITION
+        .captures(message)
+        .map(|captures| {
+            let line = captures[1].parse::<u32>().unwrap_or(1);
+            let column = captures[2].parse::<u32>().unwrap_or(1);
+            Position::new(line.saturating_sub(1), column.saturating_sub(1))
+        })
+        .unwrap_or_default();
+    let expected = message
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("= "))
+        .unwrap_or(message);
+    Diagnostic {
+        range: Range::new(
+            position,
+            Position::new(position.line, position.character + 1),
+        ),
+        severity: Some(Severity::ERROR),
+        source: Some("helix".to_string()),
+        message: format!("syntax error: {expected}"),
+        ..Default::default()
+    }
+}
+
+fn fix_edit(fix: &Fix) -> Option<TextEdit> {
+    let (loc, new_text) = match (&fix.to_remove, &fix.span, &fix.to_add) {
+        (Some(loc), _, to_add) => (loc, to_add.clone().unwrap_or_default()),
+        (None, Some(loc), Some(to_add)) => (loc, to_add.clone()),
+        _ => return None,
+    };
+    Some(TextEdit {
+        range: loc_range(loc),
+        new_text,
+    })
+}
+
+/// Parser columns start at 2, LSP positions are zero based
+fn span_position(span: Span) -> Position {
+    Position::new(
+        span.line.saturating_sub(1) as u32,
+        span.column.saturating_sub(2) as u32,
+    )
+}
+
+fn loc_range(loc: &Loc) -> Range {
+    Range::new(span_position(loc.start), span_position(loc.end))
+}
+
+fn contains(loc: &Loc, position: Position) -> bool {
+    span_position(loc.start) <= position && position <= span_position(loc.end)
+}
+
+/// Range of the first occurrence of `name` in the source of `loc`
+fn name_range(loc: &Loc, name: &str) -> Range {
+    let start = span_position(loc.start);
+    let Some(offset) = loc.span.find(name) else {
+        return Range::new(start, start);
+    };
+    let before = &loc.span[..offset];
+    let line = start.line + before.matches('\n').count() as u32;
+    let character = match before.rfind('\n') {
+        Some(newline) => before[newline + 1..].chars().count() as u32,
+        None => start.character + before.chars().count() as u32,
+    };
+    Range::new(
+        Position::new(line, character),
+        Position::new(line, character + name.chars().count() as u32),
+    )
+}
+
+fn code_block(text: &str) -> String {
+    format!("```hql\n{text}\n```")
+}
+
+/// Names and locations of the labels, enums and structs declared in the schema
+fn schema_items(schema: &Schema) -> impl Iterator<Item = (&str, &Loc)> {
+    schema
+        .node_schemas
+        .iter()
+        .map(|item| (item.name.1.as_str(), &item.loc))
+        .chain(
+            schema
+                .edge_schemas
+                .iter()
+                .map(|item| (item.name.1.as_str(), &item.loc)),
+        )
+        .chain(
+            schema
+                .vector_schemas
+                .iter()
+                .map(|item| (item.name.as_str(), &item.loc)),
+        )
+        .chain(
+            schema
+                .enum_schemas
+                .iter()
+                .map(|item| (item.name.1.as_str(), &item.loc)),
+        )
+        .chain(
+            schema
+                .struct_schemas
+                .iter()
+                .map(|item| (item.name.1.as_str(), &item.loc)),
+        )
+}
+
+fn fields<'a>(schema: &'a Schema, label: &'a str) -> impl Iterator<Item = &'a Field> {
+    let node = schema
+        .node_schemas
+        .iter()
+        .filter(move |item| item.name.1 == label)
+        .flat_map(|item| &item.fields);
+    let edge = schema
+        .edge_schemas
+        .iter()
+        .filter(move |item| item.name.1 == label)
+        .flat_map(|item| item.properties.iter().flatten());
+    let vector = schema
+        .vector_schemas
+        .iter()
+        .filter(move |item| item.name == label)
+        .flat_map(|item| &item.fields);
+    node.chain(edge).chain(vector)
+}
+
+fn label_completions(schema: &Schema, kinds: &[ItemKind]) -> Vec<CompletionItem> {
+    let mut items = Vec::new();
+    if kinds.contains(&ItemKind::Node) {
+        items.extend(
+            schema.node_schemas.iter().map(|item| {
+                completion(&item.name.1, CompletionItemKind::CLASS, Some("node".into()))
+            }),
+        );
+    }
+    if kinds.contains(&ItemKind::Edge) {
+        items.extend(schema.edge_schemas.iter().map(|item| {
+            completion(
+                &item.name.1,
+                CompletionItemKind::INTERFACE,
+                Some(format!("edge {} -> {}", item.from.1, item.to.1)),
+            )
+        }));
+    }
+    if kinds.contains(&ItemKind::Vector) {
+        items.extend(schema.vector_schemas.iter().map(|item| {
+            completion(
+                &item.name,
+                CompletionItemKind::STRUCT,
+                Some("vector".into()),
+            )
+        }));
+    }
+    if kinds.contains(&ItemKind::Enum) {
+        items.extend(
+            schema.enum_schemas.iter().map(|item| {
+                completion(&item.name.1, CompletionItemKind::ENUM, Some("enum".into()))
+            }),
+        );
+    }
+    if kinds.contains(&ItemKind::Struct) {
+        items.extend(schema.struct_schemas.iter().map(|item| {
+            completion(
+                &item.name.1,
+                CompletionItemKind::STRUCT,
+                Some("struct".into()),
+            )
+        }));
+    }
+    items
+}
+
+fn completion(label: &str, kind: CompletionItemKind, detail: Option<String>) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        detail,
+        ..Default::default()
+    }
+}
+
+/// The last `<Label>` written before the cursor, for text the parser cannot read yet
+fn last_label(schema: &Schema, prefix: &str) -> Option<String> {
+    static LABELS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<(\w+)>").expect("valid regex"));
+    LABELS
+        .captures_iter(prefix)
+        .map(|captures| captures[1].to_string())
+        .filter(|label| schema_items(schema).any(|(name, _)| name == label))
+        .last()
+}
+
+fn item_label(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Node(Some(name))
+        | Type::Nodes(Some(name))
+        | Type::Edge(Some(name))
+        | Type::Edges(Some(name))
+        | Type::Vector(Some(name))
+        | Type::Vectors(Some(name)) => Some(name.clone()),
+        Type::Anonymous(ty) | Type::Computed(ty, _) => item_label(ty),
+        _ => None,
+    }
+}
+
+/// Render an inferred type the way it would be written in HQL
+pub fn type_name(ty: &Type) -> String {
+    let label = |prefix: &str, name: &Option<String>| match name {
+        Some(name) => format!("{prefix}<{name}>"),
+        None => prefix.to_string(),
+    };
+    match ty {
+        Type::Node(name) => label("N", name),
+        Type::Nodes(name) => format!("[{}]", label("N", name)),
+        Type::Edge(name) => label("E", name),
+        Type::Edges(name) => format!("[{}]", label("E", name)),
+        Type::Vector(name) => label("V", name),
+        Type::Vectors(name) => format!("[{}]", label("V", name)),
+        Type::Scalar(ty) => ty.to_string(),
+        Type::Object(fields) => {
+            let mut fields = fields
+                .iter()
+                .map(|(name, ty)| format!("{name}: {}", type_name(ty)))
+                .collect::<Vec<_>>();
+            fields.sort();
+            format!("{{{}}}", fields.join(", "))
+        }
+        Type::Array(ty) => format!("[{}]", type_name(ty)),
+        Type::Anonymous(ty) | Type::Computed(ty, _) => type_name(ty),
+        Type::Boolean => "Boolean".to_string(),
+        Type::Aggregate(_) => "Aggregate".to_string(),
+        Type::Unknown => "unknown".to_string(),
+    }
+}
+
+enum VariableType {
+    Param(String),
+    /// Bound to the value of the expression at this location
+    Value(Loc),
+    Unknown,
+}
+
+struct Variable {
+    name: String,
+    loc: Loc,
+    ty: VariableType,
+}
+
+/// Parameters and variables bound in a query, in the order they are bound
+fn variables(query: &Query) -> Vec<Variable> {
+    let mut variables = query
+        .parameters
+        .iter()
+        .map(|param| Variable {
+            name: param.name.1.clone(),
+            loc: param.name.0.clone(),
+            ty: VariableType::Param(param.param_type.1.to_string()),
+        })
+        .collect::<Vec<_>>();
+    statement_variables(&query.statements, &mut variables);
+    variables
+}
+
+fn statement_variables(statements: &[Statement], variables: &mut Vec<Variable>) {
+    for statement in statements {
+        match &statement.statement {
+            StatementType::Assignment(assignment) => {
+                variables.push(Variable {
+                    name: assignment.variable.clone(),
+                    loc: assignment.loc.clone(),
+                    ty: VariableType::Value(assignment.value.loc.clone()),
+                });
+                if let Some((loc, name)) = &assignment.created_variable {
+                    variables.push(Variable {
+                        name: name.clone(),
+                        loc: loc.clone(),
+                        ty: VariableType::Param("Boolean".to_string()),
+                    });
+                }
+            }
+            StatementType::ForLoop(for_loop) => {
+                match &for_loop.variable {
+                    ForLoopVars::Identifier { name, loc }
+                    | ForLoopVars::ObjectAccess { name, loc, .. } => variables.push(Variable {
+                        name: name.clone(),
+                        loc: loc.clone(),
+                        ty: VariableType::Unknown,
+                    }),
+                    ForLoopVars::ObjectDestructuring { fields, .. } => {
+                        variables.extend(fields.iter().map(|(loc, name)| Variable {
+                            name: name.clone(),
+                            loc: loc.clone(),
+                            ty: VariableType::Unknown,
+                        }))
+                    }
+                }
+                statement_variables(&for_loop.statements, variables);
+            }
+            StatementType::IfElse(if_else) => {
+                statement_variables(&if_else.then_statements, variables);
+                if let Some(statements) = &if_else.else_statements {
+                    statement_variables(statements, variables);
+                }
+            }
+            StatementType::Expression(_) | StatementType::Drop(_) => {}
+        }
+    }
+}
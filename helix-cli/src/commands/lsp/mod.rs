@@ -0,0 +1,321 @@
+//! Language server for `.hx` files, speaking the Language Server Protocol over stdio.
+//!
+//! Every change re-analyzes all the queries of the project the document belongs to,
+//! the same way `helix check` does.
+
+pub mod analysis;
+
+use crate::commands::lsp::analysis::Analysis;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{collect_hx_files, is_test_file};
+use eyre::Result;
+use helix_db::helixc::parser::types::HxFile;
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    CodeActionParams, CompletionOptions, CompletionParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{CodeActionRequest, Completion, GotoDefinition, HoverRequest, Request as _},
+};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(capabilities())?;
+    connection.initialize(capabilities)?;
+
+    let mut server = Server::default();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for notification in server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    // the writer thread stops once the connection is dropped
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(lsp_types::CodeActionProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["<".to_string(), ":".to_string(), "{".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// The files analyzed together, and their latest analysis
+struct Project {
+    analysis: Analysis,
+    /// Latest analysis of the project that parsed, used to complete text being typed
+    parsed: Option<Analysis>,
+}
+
+#[derive(Default)]
+struct Server {
+    /// Text of the open documents, which may differ from the files on disk
+    documents: HashMap<PathBuf, String>,
+    /// Projects by the directory holding their queries
+    projects: HashMap<PathBuf, Project>,
+}
+
+impl Server {
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            HoverRequest::METHOD => {
+                respond::<HoverRequest, _>(id, &request, |params: HoverParams| {
+                    let position = params.text_document_position_params;
+                    let (project, file) = self.project(&position.text_document.uri)?;
+                    let value = project.analysis.hover(&file, position.position)?;
+                    Some(Hover {
+                        contents: HoverContents::Markup(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value,
+                        }),
+                        range: None,
+                    })
+                })
+            }
+            GotoDefinition::METHOD => {
+                respond::<GotoDefinition, _>(id, &request, |params: GotoDefinitionParams| {
+                    let position = params.text_document_position_params;
+                    let (project, file) = self.project(&position.text_document.uri)?;
+                    let (file, range) = project.analysis.definition(&file, position.position)?;
+                    let uri = Url::from_file_path(&file).ok()?;
+                    Some(GotoDefinitionResponse::Scalar(Location::new(uri, range)))
+                })
+            }
+            Completion::METHOD => {
+                respond::<Completion, _>(id, &request, |params: CompletionParams| {
+                    let position = params.text_document_position;
+                    let (project, file) = self.project(&position.text_document.uri)?;
+                    let path = position.text_document.uri.to_file_path().ok()?;
+                    let line = self
+                        .documents
+                        .get(&path)?
+                        .lines()
+                        .nth(position.position.line as usize)
+                        .unwrap_or_default();
+                    let analysis = match &project.parsed {
+                        Some(parsed) if !project.analysis.is_parsed() => parsed,
+                        _ => &project.analysis,
+                    };
+                    Some(lsp_types::CompletionResponse::Array(analysis.completions(
+                        &file,
+                        position.position,
+                        line,
+                    )))
+                })
+            }
+            CodeActionRequest::METHOD => {
+                respond::<CodeActionRequest, _>(id, &request, |params: CodeActionParams| {
+                    let uri = params.text_document.uri;
+                    let (project, file) = self.project(&uri)?;
+                    Some(project.analysis.code_actions(&file, &uri, params.range))
+                })
+            }
+            _ => Response::new_err(
+                id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("unsupported request: {}", request.method),
+            ),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Notification> {
+        let path = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Some(params) = params::<DidOpenTextDocumentParams>(&notification) else {
+                    return Vec::new();
+                };
+                let path = params.text_document.uri.to_file_path().ok();
+                if let Some(path) = &path {
+                    self.documents
+                        .insert(path.clone(), params.text_document.text);
+                }
+                path
+            }
+            DidChangeTextDocument::METHOD => {
+                let Some(params) = params::<DidChangeTextDocumentParams>(&notification) else {
+                    return Vec::new();
+                };
+                let path = params.text_document.uri.to_file_path().ok();
+                // documents are synced in full, so the last change holds the whole text
+                if let (Some(path), Some(change)) =
+                    (&path, params.content_changes.into_iter().last())
+                {
+                    self.documents.insert(path.clone(), change.text);
+                }
+                path
+            }
+            DidSaveTextDocument::METHOD => params::<DidSaveTextDocumentParams>(&notification)
+                .and_then(|params| params.text_document.uri.to_file_path().ok()),
+            DidCloseTextDocument::METHOD => {
+                let Some(params) = params::<DidCloseTextDocumentParams>(&notification) else {
+                    return Vec::new();
+                };
+                let path = params.text_document.uri.to_file_path().ok();
+                if let Some(path) = &path {
+                    self.documents.remove(path);
+                }
+                path
+            }
+            _ => None,
+        };
+        match path {
+            Some(path) => self.analyze(&path),
+            None => Vec::new(),
+        }
+    }
+
+    /// Re-analyze the project of a document, publishing the diagnostics of all its files
+    fn analyze(&mut self, path: &Path) -> Vec<Notification> {
+        // tests are not HQL, they are checked by `helix test`
+        if is_test_file(path) {
+            return Vec::new();
+        }
+        let (dir, paths) = project_files(path);
+        let files = paths
+            .iter()
+            .filter_map(|path| {
+                let content = match self.documents.get(path) {
+                    Some(text) => text.clone(),
+                    None => fs::read_to_string(path).ok()?,
+                };
+                Some(HxFile {
+                    name: path.to_string_lossy().into_owned(),
+                    content,
+                })
+            })
+            .collect::<Vec<_>>();
+        let analysis = Analysis::new(files);
+
+        let notifications = analysis
+            .file_names()
+            .filter_map(|file| {
+                let params = PublishDiagnosticsParams::new(
+                    Url::from_file_path(file).ok()?,
+                    analysis.diagnostics(file),
+                    None,
+                );
+                Some(Notification::new(
+                    PublishDiagnostics::METHOD.to_string(),
+                    params,
+                ))
+            })
+            .collect();
+
+        let previous = self.projects.remove(&dir);
+        let parsed = match previous {
+            _ if analysis.is_parsed() => None,
+            Some(Project {
+                analysis: previous, ..
+            }) if previous.is_parsed() => Some(previous),
+            Some(Project { parsed, .. }) => parsed,
+            None => None,
+        };
+        self.projects.insert(dir, Project { analysis, parsed });
+        notifications
+    }
+
+    /// The project of a document, along with the document's file name in its analysis
+    fn project(&self, uri: &Url) -> Option<(&Project, String)> {
+        let path = uri.to_file_path().ok()?;
+        let (dir, _) = project_files(&path);
+        let project = self.projects.get(&dir)?;
+        Some((project, path.to_string_lossy().into_owned()))
+    }
+}
+
+/// The `.hx` files analyzed together with a document: the queries of its helix project,
+/// or the files next to it outside of a project
+fn project_files(path: &Path) -> (PathBuf, Vec<PathBuf>) {
+    let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    if let Ok(project) = ProjectContext::find_and_load(Some(&dir)) {
+        let queries = project.root.join(&project.config.project.queries);
+        if path.starts_with(&queries) {
+            let files = collect_hx_files(&project.root, &project.config.project.queries)
+                .map(|entries| entries.iter().map(|entry| entry.path()).collect())
+                .unwrap_or_default();
+            return (queries, with_document(files, path));
+        }
+    }
+
+    let files = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension().is_some_and(|ext| ext == "hx") && !is_test_file(path)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    (dir, with_document(files, path))
+}
+
+/// A document that is not saved yet is analyzed along with the files on disk
+fn with_document(mut files: Vec<PathBuf>, path: &Path) -> Vec<PathBuf> {
+    if !files.iter().any(|file| file == path) {
+        files.push(path.to_path_buf());
+    }
+    files.sort();
+    files
+}
+
+fn params<P: DeserializeOwned>(notification: &Notification) -> Option<P> {
+    serde_json::from_value(notification.params.clone()).ok()
+}
+
+/// Answer a request with the result of `handle`, or `null` when there is none
+fn respond<R, T>(
+    id: RequestId,
+    request: &Request,
+    handle: impl FnOnce(R::Params) -> Option<T>,
+) -> Response
+where
+    R: lsp_types::request::Request,
+    T: Serialize,
+{
+    match serde_json::from_value::<R::Params>(request.params.clone()) {
+        Ok(params) => Response::new_ok(id, handle(params)),
+        Err(e) => Response::new_err(
+            id,
+            lsp_server::ErrorCode::InvalidParams as i32,
+            e.to_string(),
+        ),
+    }
+}
@@ -414,6 +414,7 @@ fn create_v2_config(ctx: &MigrationContext) -> Result<()> {
     // Create graph config
     let graph_config = GraphConfig {
         secondary_indices: ctx.v1_config.graph_config.secondary_indices.clone(),
+        ..Default::default()
     };
 
     // Create db config
@@ -425,6 +426,9 @@ fn create_v2_config(ctx: &MigrationContext) -> Result<()> {
         schema: None,
         embedding_model: Some("text-embedding-ada-002".to_string()),
         graphvis_node_label: None,
+        interpret: false,
+        interpret_writes: false,
+        import: false,
     };
 
     // Create local instance config
@@ -0,0 +1,82 @@
+use crate::MigrationAction;
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{collect_hx_files, generate_content, parse_content};
+use crate::utils::{print_line, print_status, print_success, print_warning};
+use eyre::Result;
+use helix_db::helixc::migration::{MigrationDraft, draft_migration};
+use std::fs;
+use std::path::PathBuf;
+
+pub async fn run(action: MigrationAction) -> Result<()> {
+    match action {
+        MigrationAction::New { output } => run_new(output).await,
+    }
+}
+
+async fn run_new(output: Option<String>) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    print_status("MIGRATION", "Drafting migration from schema changes");
+    let (draft, path) = write_migration_draft(&project, output.map(PathBuf::from))?;
+
+    let path = path.strip_prefix(&project.root).unwrap_or(&path);
+    print_success(&format!(
+        "Migration from schema::{} to schema::{} written to {}",
+        draft.from_version,
+        draft.to_version,
+        path.display()
+    ));
+    if !draft.reviews.is_empty() {
+        print_warning(&format!(
+            "{} change(s) need a review, see the REVIEW comments:",
+            draft.reviews.len()
+        ));
+        for review in &draft.reviews {
+            print_line(&format!("  {review}"));
+        }
+    }
+    Ok(())
+}
+
+/// Draft the migration between the latest two schema versions of the project and write it,
+/// by default next to the queries, returning the draft and its path.
+pub fn write_migration_draft(
+    project: &ProjectContext,
+    output: Option<PathBuf>,
+) -> Result<(MigrationDraft, PathBuf)> {
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+
+    if source.schema.len() < 2 {
+        return Err(
+            CliError::new("at least two schema versions are needed to draft a migration")
+                .with_hint("keep the current schema in a 'schema::1 { ... }' block and write the new one in 'schema::2 { ... }'")
+                .into(),
+        );
+    }
+    let draft = draft_migration(&source)
+        .map_err(|e| CliError::new("failed to draft migration").with_context(e.to_string()))?;
+
+    let path = output.unwrap_or_else(|| {
+        project
+            .root
+            .join(&project.config.project.queries)
+            .join(format!(
+                "migration_{}_to_{}.hx",
+                draft.from_version, draft.to_version
+            ))
+    });
+    if path.exists() {
+        return Err(CliError::new(format!("{} already exists", path.display()))
+            .with_hint("remove it or pass another file with --output")
+            .into());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, &draft.source)?;
+    Ok((draft, path))
+}
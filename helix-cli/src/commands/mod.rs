@@ -4,14 +4,24 @@ pub mod build;
 pub mod check;
 pub mod compile;
 pub mod delete;
+pub mod dev;
+pub mod fmt;
+pub mod generate;
+pub mod import;
 pub mod init;
 pub mod integrations;
+pub mod lsp;
 pub mod metrics;
 pub mod migrate;
+pub mod migration;
 pub mod prune;
 pub mod pull;
 pub mod push;
+pub mod query;
+pub mod run;
+pub mod shell;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod test;
 pub mod update;
@@ -1,6 +1,7 @@
 use crate::config::ContainerRuntime;
 use crate::docker::DockerManager;
 use crate::errors::project_error;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
 use crate::utils::{
     print_confirm, print_lines, print_newline, print_status, print_success, print_warning,
@@ -50,6 +51,9 @@ async fn prune_instance(project: &ProjectContext, instance_name: &str) -> Result
         let _ = docker.remove_instance_images(instance_name);
     }
 
+    // Stop the native process, whose binary is in the workspace
+    let _ = NativeManager::new(project).stop_instance(instance_name);
+
     // Remove instance workspace directory
     let workspace = project.instance_workspace(instance_name);
     if workspace.exists() {
@@ -100,7 +104,9 @@ async fn prune_all_instances(project: &ProjectContext) -> Result<()> {
     }
 
     // Remove instance workspaces but keep volumes
+    let native = NativeManager::new(project);
     for instance_name in &instances {
+        let _ = native.stop_instance(instance_name);
         let workspace = project.instance_workspace(instance_name);
         if workspace.exists() {
             match std::fs::remove_dir_all(&workspace) {
@@ -5,6 +5,7 @@ use crate::commands::integrations::helix::HelixManager;
 use crate::config::{CloudConfig, InstanceInfo};
 use crate::docker::DockerManager;
 use crate::metrics_sender::MetricsSender;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
 use crate::utils::{print_status, print_success};
 use eyre::Result;
@@ -96,6 +97,9 @@ async fn push_local_instance(
     // Check Docker availability
     DockerManager::check_runtime_available(docker.runtime)?;
 
+    // An instance run natively is managed by the container runtime from now on
+    NativeManager::new(project).remove_instance(instance_name)?;
+
     // Build the instance first (this ensures it's up to date) and get metrics data
     let metrics_data =
         crate::commands::build::run(instance_name.to_string(), metrics_sender).await?;
@@ -0,0 +1,396 @@
+use crate::config::InstanceInfo;
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{collect_hx_files, generate_content, parse_content};
+use crate::utils::print_status;
+use eyre::Result;
+use helix_db::helixc::parser::types::{FieldType, Query, Schema};
+use serde_json::{Map, Value};
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Table,
+}
+
+impl OutputFormat {
+    pub fn from(value: &str) -> Result<Self> {
+        let format = match value {
+            "json" => OutputFormat::Json,
+            "table" => OutputFormat::Table,
+            _ => return Err(eyre::eyre!("Invalid output format: {value}")),
+        };
+        Ok(format)
+    }
+}
+
+pub async fn run(
+    instance_name: String,
+    query_name: String,
+    params: Vec<String>,
+    json: Option<String>,
+    format: String,
+) -> Result<()> {
+    let format = OutputFormat::from(&format)?;
+
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    // Only local instances expose a port in helix.toml
+    let port = match project.config.get_instance(&instance_name)? {
+        InstanceInfo::Local(config) => config.port.unwrap_or(6969),
+        _ => {
+            return Err(CliError::new(format!(
+                "instance '{instance_name}' is not a local instance"
+            ))
+            .with_hint("'helix query' can only run queries against local instances")
+            .into());
+        }
+    };
+
+    // Look up the query's parameters in the project's queries
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+    let schema = source
+        .get_latest_schema()
+        .map_err(|e| eyre::eyre!("invalid schema: {e}"))?;
+    let query = find_query(&source.queries, &query_name)?;
+
+    let json = match json {
+        Some(path) => {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| eyre::eyre!("Failed to read {path}: {e}"))?;
+            let value = serde_json::from_str(&contents)
+                .map_err(|e| eyre::eyre!("Failed to parse {path}: {e}"))?;
+            Some(value)
+        }
+        None => None,
+    };
+    let body = build_body(schema, query, &params, json)?;
+
+    print_status(
+        "QUERY",
+        &format!("Running '{query_name}' on instance '{instance_name}'"),
+    );
+
+    let url = format!("http://localhost:{port}/{query_name}");
+    let response = reqwest::Client::new()
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| {
+            CliError::new(format!(
+                "could not reach instance '{instance_name}' at {url}"
+            ))
+            .with_caused_by(e.to_string())
+            .with_hint(format!(
+                "run 'helix start {instance_name}' to start the instance"
+            ))
+        })?;
+
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        return Err(
+            CliError::new(format!("query '{query_name}' failed ({status})"))
+                .with_caused_by(text)
+                .into(),
+        );
+    }
+
+    match serde_json::from_str::<Value>(&text) {
+        Ok(value) => println!("{}", format_response(&value, format)?),
+        Err(_) => println!("{text}"),
+    }
+
+    Ok(())
+}
+
+/// Find a query by name, listing the available queries when it does not exist
+pub fn find_query<'a>(queries: &'a [Query], name: &str) -> Result<&'a Query> {
+    match queries.iter().find(|query| query.name == name) {
+        Some(query) => Ok(query),
+        None => {
+            let available = queries
+                .iter()
+                .map(|query| query.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(CliError::new(format!("query '{name}' not found"))
+                .with_hint(format!("available queries: {available}"))
+                .into())
+        }
+    }
+}
+
+/// Build the request body from a JSON object and `name=value` parameters.
+///
+/// Parameters override values from the JSON object and every value is checked against
+/// the type of the query parameter it is passed for.
+pub fn build_body(
+    schema: &Schema,
+    query: &Query,
+    params: &[String],
+    json: Option<Value>,
+) -> Result<Value> {
+    let mut body = match json {
+        Some(Value::Object(map)) => map,
+        Some(_) => return Err(eyre::eyre!("JSON parameters must be an object")),
+        None => Map::new(),
+    };
+
+    for param in params {
+        let Some((name, value)) = param.split_once('=') else {
+            return Err(CliError::new(format!("invalid parameter '{param}'"))
+                .with_hint("pass parameters as --param name=value")
+                .into());
+        };
+        let ty = param_type(schema, query, name)?;
+        body.insert(name.to_string(), parse_param(&ty, value)?);
+    }
+
+    for (name, value) in &body {
+        let ty = param_type(schema, query, name)?;
+        // optional parameters can be passed as an explicit `null`
+        let is_optional = query
+            .parameters
+            .iter()
+            .any(|param| param.name.1 == *name && param.is_optional);
+        if is_optional && value.is_null() {
+            continue;
+        }
+        if !matches_type(&ty, value) {
+            return Err(
+                CliError::new(format!("parameter '{name}' expects {ty}, got {value}")).into(),
+            );
+        }
+        let declared = query
+            .parameters
+            .iter()
+            .find(|param| param.name.1 == *name)
+            .map(|param| &param.param_type.1);
+        if let Some(field) = declared.and_then(|ty| missing_field(schema, ty, value)) {
+            return Err(CliError::new(format!(
+                "parameter '{name}' is missing the field '{field}'"
+            ))
+            .with_hint(format!("parameter '{name}' expects {ty}"))
+            .into());
+        }
+    }
+
+    for param in &query.parameters {
+        if !param.is_optional && !body.contains_key(&param.name.1) {
+            return Err(CliError::new(format!(
+                "missing parameter '{}' ({})",
+                param.name.1, param.param_type.1
+            ))
+            .with_hint(format!("pass it with --param {}=<value>", param.name.1))
+            .into());
+        }
+    }
+
+    Ok(Value::Object(body))
+}
+
+/// Type of a query parameter, with the structs it names replaced by their objects
+fn param_type(schema: &Schema, query: &Query, name: &str) -> Result<FieldType> {
+    match query.parameters.iter().find(|param| param.name.1 == name) {
+        Some(param) => Ok(schema.resolve_structs(&param.param_type.1)),
+        None => {
+            let expected = query
+                .parameters
+                .iter()
+                .map(|param| format!("{}: {}", param.name.1, param.param_type.1))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(
+                CliError::new(format!("query '{}' has no parameter '{name}'", query.name))
+                    .with_hint(format!("expected parameters: ({expected})"))
+                    .into(),
+            )
+        }
+    }
+}
+
+/// Parse a command line value for a parameter of the given type.
+///
+/// Strings, ids, dates and enum values are taken as they are, everything else is read as JSON.
+fn parse_param(ty: &FieldType, value: &str) -> Result<Value> {
+    match ty {
+        FieldType::String | FieldType::Uuid | FieldType::Date | FieldType::Identifier(_) => {
+            Ok(Value::String(value.to_string()))
+        }
+        _ => serde_json::from_str(value).map_err(|_| eyre::eyre!("'{value}' is not a valid {ty}")),
+    }
+}
+
+/// The first required field missing from the objects of a value passed for `ty`, as its
+/// dotted path. Struct fields declared with `?` may be left out, object fields may not.
+fn missing_field(schema: &Schema, ty: &FieldType, value: &Value) -> Option<String> {
+    let in_field = |name: &str, ty, value| {
+        missing_field(schema, ty, value).map(|path| format!("{name}.{path}"))
+    };
+    match (ty, value) {
+        (FieldType::Array(inner), Value::Array(items)) => items
+            .iter()
+            .find_map(|item| missing_field(schema, inner, item)),
+        (FieldType::Object(fields), Value::Object(map)) => {
+            let mut names = fields.keys().collect::<Vec<_>>();
+            names.sort();
+            names.into_iter().find_map(|name| match map.get(name) {
+                Some(item) => in_field(name, &fields[name], item),
+                None => Some(name.clone()),
+            })
+        }
+        (_, Value::Object(map)) => schema.get_struct(ty)?.fields.iter().find_map(|field| {
+            match map.get(&field.name) {
+                Some(item) => in_field(&field.name, &field.field_type, item),
+                None if field.is_optional => None,
+                None => Some(field.name.clone()),
+            }
+        }),
+        _ => None,
+    }
+}
+
+/// Check a JSON value against the type of a query parameter
+pub fn matches_type(ty: &FieldType, value: &Value) -> bool {
+    let in_range = |min: i128, max: i128| {
+        let n = value
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| value.as_u64().map(i128::from));
+        matches!(n, Some(n) if n >= min && n <= max)
+    };
+    match ty {
+        FieldType::String | FieldType::Date => value.is_string(),
+        FieldType::Uuid => value
+            .as_str()
+            .is_some_and(|id| uuid::Uuid::parse_str(id).is_ok()),
+        FieldType::F32 | FieldType::F64 => value.is_number(),
+        FieldType::I8 => in_range(i8::MIN.into(), i8::MAX.into()),
+        FieldType::I16 => in_range(i16::MIN.into(), i16::MAX.into()),
+        FieldType::I32 => in_range(i32::MIN.into(), i32::MAX.into()),
+        FieldType::I64 => in_range(i64::MIN.into(), i64::MAX.into()),
+        FieldType::U8 => in_range(0, u8::MAX.into()),
+        FieldType::U16 => in_range(0, u16::MAX.into()),
+        FieldType::U32 => in_range(0, u32::MAX.into()),
+        FieldType::U64 | FieldType::U128 => in_range(0, u64::MAX.into()),
+        FieldType::Boolean => value.is_boolean(),
+        FieldType::Array(inner) => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|item| matches_type(inner, item))),
+        FieldType::Object(fields) => value.as_object().is_some_and(|map| {
+            map.iter().all(|(key, item)| {
+                fields
+                    .get(key)
+                    .is_some_and(|field| matches_type(field, item))
+            })
+        }),
+        FieldType::Embedding(_) => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(Value::is_number)),
+        // enums and other named types are checked by the instance
+        FieldType::Identifier(_) => true,
+    }
+}
+
+/// Format a query response as pretty JSON or as one table per returned value
+pub fn format_response(value: &Value, format: OutputFormat) -> Result<String> {
+    match (format, value) {
+        (OutputFormat::Json, _) | (OutputFormat::Table, Value::Null) => {
+            Ok(serde_json::to_string_pretty(value)?)
+        }
+        (OutputFormat::Table, Value::Object(map)) => Ok(map
+            .iter()
+            .map(|(name, value)| format!("{name}:\n{}", format_table(value)))
+            .collect::<Vec<_>>()
+            .join("\n")),
+        (OutputFormat::Table, value) => Ok(format_table(value)),
+    }
+}
+
+/// Render a value as a table, with a row per object and a column per field
+pub fn format_table(value: &Value) -> String {
+    let rows = match value {
+        Value::Array(items) => items.iter().collect::<Vec<_>>(),
+        Value::Object(_) => vec![value],
+        _ => return format!("{}\n", cell(value)),
+    };
+    if !rows.iter().all(|row| row.is_object()) {
+        return format!(
+            "{}\n",
+            rows.iter()
+                .map(|row| cell(row))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    let mut columns: Vec<&str> = Vec::new();
+    for row in rows.iter().filter_map(|row| row.as_object()) {
+        for key in row.keys() {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    let cells = rows
+        .iter()
+        .filter_map(|row| row.as_object())
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| row.get(*column).map(cell).unwrap_or_default())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .fold(column.chars().count(), usize::max)
+        })
+        .collect::<Vec<_>>();
+
+    let line = |values: Vec<&str>| {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+    let mut table = String::new();
+    table.push_str(&line(columns.clone()));
+    table.push('\n');
+    table.push_str(
+        &widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-"),
+    );
+    table.push('\n');
+    for row in &cells {
+        table.push_str(&line(row.iter().map(String::as_str).collect()));
+        table.push('\n');
+    }
+    table
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,67 @@
+use crate::errors::CliError;
+use crate::metrics_sender::MetricsSender;
+use crate::native::NativeManager;
+use crate::project::ProjectContext;
+use crate::utils::{print_status, print_success};
+use eyre::Result;
+
+pub async fn run(
+    instance_name: String,
+    native: bool,
+    metrics_sender: &MetricsSender,
+) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    // Get instance config
+    let instance_config = project.config.get_instance(&instance_name)?;
+    if !instance_config.is_local() {
+        return Err(CliError::new(format!(
+            "instance '{instance_name}' is not a local instance"
+        ))
+        .with_hint(format!("run 'helix push {instance_name}' to deploy it"))
+        .into());
+    }
+
+    if !native {
+        return crate::commands::push::run(instance_name, metrics_sender).await;
+    }
+    run_native_instance(&project, &instance_name, metrics_sender).await
+}
+
+pub(crate) async fn run_native_instance(
+    project: &ProjectContext,
+    instance_name: &str,
+    metrics_sender: &MetricsSender,
+) -> Result<()> {
+    print_status(
+        "RUN",
+        &format!("Running local instance '{instance_name}' natively"),
+    );
+
+    NativeManager::check_cargo_available()?;
+    let native = NativeManager::new(project);
+
+    // Compile the queries into the workspace, then the workspace into a binary
+    crate::commands::build::compile_instance(project, instance_name, metrics_sender).await?;
+    let build_mode = project.config.get_instance(instance_name)?.build_mode();
+    native.build_binary(instance_name, build_mode)?;
+
+    // Restart the instance on the new binary
+    native.stop_instance(instance_name)?;
+    let pid = native.start_instance(instance_name)?;
+
+    let instance_config = project.config.get_instance(instance_name)?;
+    let port = instance_config.port().unwrap_or(6969);
+
+    print_success(&format!("Instance '{instance_name}' is now running"));
+    println!("  Local URL: http://localhost:{port}");
+    println!("  Process: {pid}");
+    println!("  Logs: {}", native.log_path(instance_name).display());
+    println!(
+        "  Data volume: {}",
+        project.instance_volume(instance_name).display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,298 @@
+use crate::commands::query::{OutputFormat, format_response};
+use crate::config::{BuildMode, InstanceInfo};
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{
+    analyze_source, collect_hx_files, generate_content, parse_content,
+};
+use crate::utils::{print_error, print_info, print_line, print_status, print_warning};
+use eyre::Result;
+use helix_db::helixc::parser::types::{Content, HxFile, Source};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde_json::{Value, json};
+
+/// Name of the query snippets are wrapped in
+const SHELL_QUERY: &str = "ShellQuery";
+
+const HELP: &[&str] = &[
+    "Enter HQL statements, e.g. `users <- N<User>::WHERE(_::{age}::GT(30))`.",
+    "The last statement is returned, or end the snippet with your own RETURN.",
+    "A full `QUERY Name() => ...` definition is run as it is.",
+    "End a line with `\\` to continue the snippet on the next line.",
+    "Commands: :help, :labels, :quit",
+];
+
+pub async fn run(instance_name: String, format: String) -> Result<()> {
+    let format = OutputFormat::from(&format)?;
+
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    // Only local instances expose a port in helix.toml
+    let (port, write) = match project.config.get_instance(&instance_name)? {
+        InstanceInfo::Local(config) => {
+            if !matches!(config.build_mode, BuildMode::Dev) || !config.db_config.interpret {
+                print_warning(&format!(
+                    "instance '{instance_name}' does not enable interpreted queries, so it may not serve them"
+                ));
+            }
+            (
+                config.port.unwrap_or(6969),
+                config.db_config.interpret_writes,
+            )
+        }
+        _ => {
+            return Err(CliError::new(format!(
+                "instance '{instance_name}' is not a local instance"
+            ))
+            .with_hint("'helix shell' can only run queries against local instances")
+            .into());
+        }
+    };
+
+    // Snippets are checked against the project's schema before the instance analyzes
+    // them against the schema it was deployed with
+    let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+    let content = generate_content(&hx_files)?;
+    let source = parse_content(&content)?;
+    let schema = source.schema_source();
+    let labels = schema_labels(&source);
+
+    let mut editor = Editor::<ShellHelper, DefaultHistory>::new()?;
+    editor.set_helper(Some(ShellHelper {
+        labels: labels.clone(),
+    }));
+    let history = project.helix_dir.join("shell_history");
+    // there is no history before the first session
+    let _ = editor.load_history(&history);
+
+    let mode = if write { "read-write" } else { "read-only" };
+    print_status(
+        "SHELL",
+        &format!("Connected to instance '{instance_name}' ({mode}), :help for help"),
+    );
+
+    let url = format!("http://localhost:{port}/interpret");
+    let client = reqwest::Client::new();
+    let mut snippet = String::new();
+    loop {
+        let prompt = if snippet.is_empty() {
+            "helix> "
+        } else {
+            "  ...> "
+        };
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                snippet.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(line) = line.strip_suffix('\\') {
+            snippet.push_str(line);
+            snippet.push('\n');
+            continue;
+        }
+        snippet.push_str(&line);
+        let input = std::mem::take(&mut snippet);
+        let input = input.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(input);
+
+        match input {
+            ":q" | ":quit" | "exit" | "quit" => break,
+            ":help" => {
+                HELP.iter().for_each(|line| print_line(line));
+                continue;
+            }
+            ":labels" => {
+                print_line(&labels.join(", "));
+                continue;
+            }
+            _ => {}
+        }
+
+        let query = wrap_snippet(input);
+        if let Err(e) = check_snippet(&schema, &query) {
+            print_line(&e.to_string());
+            continue;
+        }
+
+        let body = json!({ "query": query });
+        let response = match client.post(&url).json(&body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                print_error(&format!(
+                    "could not reach instance '{instance_name}' at {url}: {e}"
+                ));
+                continue;
+            }
+        };
+        let status = response.status();
+        let text = response.text().await?;
+        if status == reqwest::StatusCode::NOT_FOUND {
+            print_error(&format!(
+                "instance '{instance_name}' does not serve interpreted queries"
+            ));
+            print_info(
+                "set interpret = true and build_mode = \"dev\" for the instance and rebuild it",
+            );
+        } else if !status.is_success() {
+            print_error(&text);
+            if !write && text.contains("read-only") {
+                print_info(
+                    "set interpret_writes = true for the instance and rebuild it to allow writes",
+                );
+            }
+        } else {
+            match serde_json::from_str::<Value>(&text) {
+                Ok(value) => print_line(&format_response(&value, format)?),
+                Err(_) => print_line(&text),
+            }
+        }
+    }
+
+    if let Err(e) = editor.save_history(&history) {
+        print_warning(&format!("failed to save shell history: {e}"));
+    }
+    Ok(())
+}
+
+/// Node, edge and vector labels of the project's schema, for completion
+pub fn schema_labels(source: &Source) -> Vec<String> {
+    let Ok(schema) = source.get_latest_schema() else {
+        return Vec::new();
+    };
+    let mut labels = schema
+        .node_schemas
+        .iter()
+        .map(|item| item.name.1.clone())
+        .chain(schema.edge_schemas.iter().map(|item| item.name.1.clone()))
+        .chain(schema.vector_schemas.iter().map(|item| item.name.clone()))
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels
+}
+
+/// Wrap a snippet of statements into a query returning its last statement.
+///
+/// A bare traversal as the last statement is assigned to `result` so it can be returned.
+pub fn wrap_snippet(snippet: &str) -> String {
+    let snippet = snippet.trim();
+    if snippet.starts_with("QUERY ") {
+        return snippet.to_string();
+    }
+
+    let mut lines = snippet.lines().map(str::trim).collect::<Vec<_>>();
+    let has_return = lines.iter().any(|line| line.starts_with("RETURN"));
+    let mut body = String::new();
+    let mut returned = None;
+    if !has_return && let Some(last) = lines.pop() {
+        returned = Some(match last.split_once("<-") {
+            Some((name, _)) if is_identifier(name.trim()) => {
+                lines.push(last);
+                name.trim().to_string()
+            }
+            _ if last.starts_with("DROP ") => {
+                lines.push(last);
+                "NONE".to_string()
+            }
+            _ => {
+                body = format!("    result <- {last}\n");
+                "result".to_string()
+            }
+        });
+    }
+
+    let mut query = format!("QUERY {SHELL_QUERY}() =>\n");
+    for line in lines {
+        query.push_str(&format!("    {line}\n"));
+    }
+    query.push_str(&body);
+    if let Some(returned) = returned {
+        query.push_str(&format!("    RETURN {returned}\n"));
+    }
+    query
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_alphabetic)
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Analyze a wrapped snippet locally, so errors are reported with their source
+fn check_snippet(schema: &str, query: &str) -> Result<()> {
+    let files = vec![
+        HxFile {
+            name: "schema.hx".to_string(),
+            content: schema.to_string(),
+        },
+        HxFile {
+            name: "shell.hx".to_string(),
+            content: query.to_string(),
+        },
+    ];
+    let content = Content {
+        content: format!("{schema}\n{query}"),
+        files: files.clone(),
+        source: Source::default(),
+    };
+    let source = parse_content(&content)?;
+    analyze_source(source, &files)?;
+    Ok(())
+}
+
+/// Complete the label being typed at the cursor
+pub fn complete_label(labels: &[String], line: &str, pos: usize) -> (usize, Vec<String>) {
+    let start = line[..pos]
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+    let prefix = &line[start..pos];
+    if prefix.is_empty() {
+        return (pos, Vec::new());
+    }
+    let candidates = labels
+        .iter()
+        .filter(|label| label.starts_with(prefix))
+        .cloned()
+        .collect();
+    (start, candidates)
+}
+
+struct ShellHelper {
+    labels: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete_label(&self.labels, line, pos))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
@@ -1,6 +1,7 @@
 use crate::commands::integrations::fly::FlyManager;
 use crate::config::CloudConfig;
 use crate::docker::DockerManager;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
 use crate::utils::{print_status, print_success};
 use eyre::{OptionExt, Result};
@@ -25,6 +26,12 @@ async fn start_local_instance(project: &ProjectContext, instance_name: &str) ->
         &format!("Starting local instance '{instance_name}'"),
     );
 
+    // Instances last run natively are started as a process
+    let native = NativeManager::new(project);
+    if native.is_native(instance_name) {
+        return start_native_instance(project, &native, instance_name);
+    }
+
     let docker = DockerManager::new(project);
 
     // Check Docker availability
@@ -63,6 +70,28 @@ async fn start_local_instance(project: &ProjectContext, instance_name: &str) ->
     Ok(())
 }
 
+fn start_native_instance(
+    project: &ProjectContext,
+    native: &NativeManager,
+    instance_name: &str,
+) -> Result<()> {
+    let pid = native.start_instance(instance_name)?;
+
+    let instance_config = project.config.get_instance(instance_name)?;
+    let port = instance_config.port().unwrap_or(6969);
+
+    print_success(&format!("Instance '{instance_name}' is now running"));
+    println!("  Local URL: http://localhost:{port}");
+    println!("  Process: {pid}");
+    println!("  Logs: {}", native.log_path(instance_name).display());
+    println!(
+        "  Data volume: {}",
+        project.instance_volume(instance_name).display()
+    );
+
+    Ok(())
+}
+
 async fn start_cloud_instance(
     project: &ProjectContext,
     instance_name: &str,
@@ -1,4 +1,5 @@
 use crate::docker::DockerManager;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
 use crate::utils::{print_error, print_field, print_header, print_newline};
 use eyre::Result;
@@ -68,12 +69,34 @@ pub async fn run() -> Result<()> {
     }
     print_newline();
 
+    // Show native processes (for local instances run without a container)
+    show_native_status(&project);
+
     // Show running containers (for local instances)
     show_container_status(&project).await?;
 
     Ok(())
 }
 
+fn show_native_status(project: &ProjectContext) {
+    let statuses = NativeManager::new(project).get_project_status();
+    if statuses.is_empty() {
+        return;
+    }
+
+    print_header("Native Processes:");
+    for status in statuses {
+        match status.pid {
+            Some(pid) => print_field(
+                &format!("[UP] {}", status.instance_name),
+                &format!("pid {pid} (port {})", status.port),
+            ),
+            None => print_field(&format!("[DOWN] {}", status.instance_name), "stopped"),
+        }
+    }
+    print_newline();
+}
+
 async fn show_container_status(project: &ProjectContext) -> Result<()> {
     // Check if Docker is available
     let runtime = project.config.project.container_runtime;
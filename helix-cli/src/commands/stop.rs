@@ -1,8 +1,9 @@
 use crate::commands::integrations::fly::FlyManager;
 use crate::config::CloudConfig;
 use crate::docker::DockerManager;
+use crate::native::NativeManager;
 use crate::project::ProjectContext;
-use crate::utils::{print_status, print_success};
+use crate::utils::{print_status, print_success, print_warning};
 use eyre::{OptionExt, Result};
 
 pub async fn run(instance_name: String) -> Result<()> {
@@ -25,6 +26,17 @@ async fn stop_local_instance(project: &ProjectContext, instance_name: &str) -> R
         &format!("Stopping local instance '{instance_name}'"),
     );
 
+    // Instances last run natively are stopped as a process
+    let native = NativeManager::new(project);
+    if native.is_native(instance_name) {
+        if native.stop_instance(instance_name)? {
+            print_success(&format!("Instance '{instance_name}' has been stopped"));
+        } else {
+            print_warning(&format!("Instance '{instance_name}' is not running"));
+        }
+        return Ok(());
+    }
+
     let docker = DockerManager::new(project);
 
     // Check Docker availability
@@ -0,0 +1,288 @@
+//! Parser of `*.test.hx` files.
+//!
+//! A test file holds `TEST` blocks, each seeding an empty graph, calling a query of the project
+//! and checking what it returns:
+//!
+//! ```text
+//! TEST "lists the users older than the minimum age" {
+//!     SEED {
+//!         alice <- AddN<User>({name: "alice", age: 30})
+//!         AddN<User>({name: "bob", age: 20})
+//!     }
+//!     SEED addUser({"name": "carol", "age": 40})
+//!     CALL getUsersOlderThan({"min_age": 25})
+//!     EXPECT {"users": [{"name": "alice"}, {"name": "carol"}]}
+//! }
+//! ```
+//!
+//! `SEED` runs HQL statements, or a query of the project, before the call. `EXPECT` takes the
+//! JSON the call returns, where objects only need to list the keys that are checked, and
+//! `EXPECT SNAPSHOT` compares it to the snapshot of the test instead.
+
+use eyre::{Result, eyre};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    /// Line of the `TEST` keyword
+    pub line: usize,
+    pub seeds: Vec<Seed>,
+    pub call: Call,
+    pub expect: Expectation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Seed {
+    /// HQL statements, run as a query of their own
+    Statements {
+        hql: String,
+        /// Line of the `{` the statements start after
+        line: usize,
+    },
+    /// A query of the project
+    Call(Call),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub query: String,
+    pub params: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expectation {
+    Value(Value),
+    Snapshot,
+}
+
+/// Parse the tests of a file, `name` being the file they are reported in
+pub fn parse_test_file(name: &str, src: &str) -> Result<Vec<TestCase>> {
+    let mut cursor = Cursor { name, src, pos: 0 };
+    let mut tests: Vec<TestCase> = Vec::new();
+    loop {
+        cursor.skip_trivia();
+        if cursor.pos == src.len() {
+            return Ok(tests);
+        }
+        let line = cursor.line();
+        if !cursor.keyword("TEST") {
+            return Err(cursor.error("expected `TEST`"));
+        }
+        let test = cursor.test(line)?;
+        if tests.iter().any(|other| other.name == test.name) {
+            return Err(eyre!(
+                "{name}:{line}: a test named \"{}\" already exists",
+                test.name
+            ));
+        }
+        tests.push(test);
+    }
+}
+
+struct Cursor<'a> {
+    name: &'a str,
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn test(&mut self, line: usize) -> Result<TestCase> {
+        self.skip_trivia();
+        let name = self.string()?;
+        self.skip_trivia();
+        self.expect('{')?;
+
+        let mut seeds = Vec::new();
+        let mut call = None;
+        let mut expect = None;
+        loop {
+            self.skip_trivia();
+            if self.eat('}') {
+                break;
+            }
+            if self.keyword("SEED") {
+                self.skip_trivia();
+                match self.rest().starts_with('{') {
+                    true => seeds.push(Seed::Statements {
+                        line: self.line(),
+                        hql: self.delimited('{', '}')?.to_string(),
+                    }),
+                    false => seeds.push(Seed::Call(self.call()?)),
+                }
+            } else if self.keyword("CALL") {
+                if call.is_some() {
+                    return Err(self.error("a test calls a single query"));
+                }
+                self.skip_trivia();
+                call = Some(self.call()?);
+            } else if self.keyword("EXPECT") {
+                if expect.is_some() {
+                    return Err(self.error("a test has a single `EXPECT`"));
+                }
+                self.skip_trivia();
+                expect = Some(match self.keyword("SNAPSHOT") {
+                    true => Expectation::Snapshot,
+                    false => Expectation::Value(self.json()?),
+                });
+            } else if self.pos == self.src.len() {
+                return Err(self.error(&format!("test \"{name}\" is not closed")));
+            } else {
+                return Err(self.error("expected `SEED`, `CALL`, `EXPECT` or `}`"));
+            }
+        }
+
+        let missing = |what: &str| eyre!("{}:{line}: test \"{name}\" has no `{what}`", self.name);
+        Ok(TestCase {
+            call: call.ok_or_else(|| missing("CALL"))?,
+            expect: expect.ok_or_else(|| missing("EXPECT"))?,
+            name,
+            line,
+            seeds,
+        })
+    }
+
+    /// `query(params)`, the parameters being a JSON object that may be left out
+    fn call(&mut self) -> Result<Call> {
+        let query = self.identifier()?;
+        self.skip_trivia();
+        let params = self.delimited('(', ')')?.trim();
+        let params = match params.is_empty() {
+            true => Map::new(),
+            false => match serde_json::from_str(params) {
+                Ok(Value::Object(params)) => params,
+                Ok(_) => return Err(self.error("parameters are a JSON object")),
+                Err(e) => return Err(self.error(&format!("invalid parameters: {e}"))),
+            },
+        };
+        Ok(Call { query, params })
+    }
+
+    fn json(&mut self) -> Result<Value> {
+        let mut values = serde_json::Deserializer::from_str(self.rest()).into_iter::<Value>();
+        match values.next() {
+            Some(Ok(value)) => {
+                self.pos += values.byte_offset();
+                Ok(value)
+            }
+            Some(Err(e)) => Err(self.error(&format!("invalid JSON: {e}"))),
+            None => Err(self.error("expected a JSON value or `SNAPSHOT`")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        if !self.rest().starts_with('"') {
+            return Err(self.error("expected the name of the test as a string"));
+        }
+        let end = self.string_end(self.pos)?;
+        let string = serde_json::from_str(&self.src[self.pos..end])
+            .map_err(|e| self.error(&format!("invalid string: {e}")))?;
+        self.pos = end;
+        Ok(string)
+    }
+
+    /// End of the string literal starting at `start`
+    fn string_end(&self, start: usize) -> Result<usize> {
+        let mut escaped = false;
+        for (i, c) in self.src[start + 1..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => return Ok(start + 1 + i + 1),
+                '\n' => break,
+                _ => {}
+            }
+        }
+        Err(self.error("string is not closed"))
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expected the name of a query"));
+        }
+        let identifier = self.rest()[..len].to_string();
+        self.pos += len;
+        Ok(identifier)
+    }
+
+    /// The text between `open` and its matching `close`, skipping over string literals
+    fn delimited(&mut self, open: char, close: char) -> Result<&'a str> {
+        self.expect(open)?;
+        let start = self.pos;
+        let mut depth = 1;
+        let mut pos = start;
+        while let Some(c) = self.src[pos..].chars().next() {
+            match c {
+                '"' => {
+                    pos = self.string_end(pos)?;
+                    continue;
+                }
+                _ if c == open => depth += 1,
+                _ if c == close => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos = pos + 1;
+                        return Ok(&self.src[start..pos]);
+                    }
+                }
+                _ => {}
+            }
+            pos += c.len_utf8();
+        }
+        Err(self.error(&format!("`{open}` is not closed")))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        let matches = rest.starts_with(keyword)
+            && !rest[keyword.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if matches {
+            self.pos += keyword.len();
+        }
+        matches
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.rest().starts_with(c);
+        if matches {
+            self.pos += c.len_utf8();
+        }
+        matches
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{c}`"))),
+        }
+    }
+
+    /// Skip whitespace and `//` comments
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if !trimmed.starts_with("//") {
+                return;
+            }
+            self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn line(&self) -> usize {
+        self.src[..self.pos].matches('\n').count() + 1
+    }
+
+    fn error(&self, message: &str) -> eyre::Report {
+        eyre!("{}:{}: {message}", self.name, self.line())
+    }
+}
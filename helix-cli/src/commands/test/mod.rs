@@ -0,0 +1,312 @@
+//! `helix test` runs the tests of a project against an empty graph.
+//!
+//! The tests of `*.test.hx` files (see [`file`]) each get a graph of their own in a
+//! temporary directory. Their seeds and calls go through the interpreter, so the queries run
+//! in-process without compiling an instance.
+
+pub mod file;
+pub mod snapshot;
+
+use crate::commands::test::file::{Call, Expectation, Seed, TestCase, parse_test_file};
+use crate::commands::test::snapshot::{Snapshots, diff, matches, prune, redact_ids};
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{
+    analyze_source, collect_hx_files, collect_test_files, generate_content, parse_content,
+};
+use crate::utils::{print_line, print_status, print_success, print_warning};
+use color_eyre::owo_colors::OwoColorize;
+use eyre::{Result, eyre};
+use helix_db::helix_engine::storage_core::version_info::VersionInfo;
+use helix_db::helix_engine::traversal_core::config::{Config, GraphConfig};
+use helix_db::helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts};
+use helix_db::helixc::interpreter::interpret;
+use helix_db::helixc::parser::types::{Content, Field, HxFile, Source};
+use helix_db::protocol::value::Value as ProtocolValue;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Name of the query the statements of a `SEED` block are run as
+const SEED_QUERY: &str = "TestSeed";
+
+pub async fn run(filter: Option<String>, update: bool) -> Result<()> {
+    // Load project context
+    let project = ProjectContext::find_and_load(None)?;
+
+    let test_files = collect_test_files(&project.root, &project.config.project.queries)?;
+    if test_files.is_empty() {
+        print_warning(&format!(
+            "No test files found in {}",
+            project.root.join(&project.config.project.queries).display()
+        ));
+        print_line("  Tests are written in *.test.hx files next to the queries");
+        return Ok(());
+    }
+
+    print_status("TEST", "Analyzing queries...");
+    let suite = Suite::load(&project)?;
+
+    let mut summary = Summary::default();
+    for path in &test_files {
+        let name = path
+            .strip_prefix(&project.root)
+            .unwrap_or(path)
+            .display()
+            .to_string();
+        let results = run_test_file(&suite, path, &name, filter.as_deref(), update)?;
+        for (test, outcome) in results {
+            report(&name, &test, &outcome);
+            summary.add(&outcome);
+        }
+    }
+
+    summary.finish()
+}
+
+/// The queries of a project, analyzed once for all its tests
+pub struct Suite {
+    source: Source,
+    /// Schema definitions the statements of seeds are analyzed against
+    schema: String,
+    /// Queries that write to the graph
+    writes: HashSet<String>,
+    config: Config,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Passed,
+    /// The snapshot of the test was written, as it had none or snapshots are updated
+    Recorded,
+    Failed(String),
+}
+
+impl Suite {
+    pub fn load(project: &ProjectContext) -> Result<Self> {
+        let hx_files = collect_hx_files(&project.root, &project.config.project.queries)?;
+        let content = generate_content(&hx_files)?;
+        let source = parse_content(&content)?;
+        let generated = analyze_source(source.clone(), &content.files)?;
+
+        let writes = generated
+            .queries
+            .iter()
+            .filter(|query| query.is_mut)
+            .map(|query| query.name.clone())
+            .collect();
+
+        // the indices are created by the graph, the same way the analyzer configures instances
+        let indexed = |fields: &mut dyn Iterator<Item = &Field>| {
+            let mut names = fields
+                .filter(|field| field.is_indexed())
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            names.dedup();
+            names
+        };
+        let schema = source
+            .get_latest_schema()
+            .map_err(|e| eyre!("invalid schema: {e}"))?;
+        let graph_config = GraphConfig {
+            secondary_indices: Some(indexed(
+                &mut schema.node_schemas.iter().flat_map(|node| &node.fields),
+            )),
+            edge_secondary_indices: Some(indexed(
+                &mut schema
+                    .edge_schemas
+                    .iter()
+                    .flat_map(|edge| edge.properties.iter().flatten()),
+            )),
+        };
+        let config = Config {
+            graph_config: Some(graph_config),
+            db_max_size_gb: Some(1),
+            mcp: Some(false),
+            ..Config::default()
+        };
+
+        Ok(Self {
+            schema: source.schema_source(),
+            source,
+            writes,
+            config,
+        })
+    }
+
+    /// Run a test on a graph of its own, comparing what its call returns to its expectation
+    pub fn run_test(
+        &self,
+        test: &TestCase,
+        file: &str,
+        snapshots: &mut Snapshots,
+        update: bool,
+    ) -> Outcome {
+        let actual = match self.execute(test, file) {
+            Ok(actual) => actual,
+            Err(e) => return Outcome::Failed(e.to_string()),
+        };
+        match &test.expect {
+            Expectation::Value(expected) => match matches(expected, &actual) {
+                true => Outcome::Passed,
+                false => Outcome::Failed(diff(expected, &prune(expected, &actual))),
+            },
+            Expectation::Snapshot => {
+                let actual = redact_ids(&actual);
+                match snapshots.get(&test.name) {
+                    Some(snapshot) if *snapshot == actual => Outcome::Passed,
+                    Some(snapshot) if !update => Outcome::Failed(format!(
+                        "{}run 'helix test --update' to accept the new output",
+                        diff(snapshot, &actual)
+                    )),
+                    _ => {
+                        snapshots.set(&test.name, actual);
+                        Outcome::Recorded
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute(&self, test: &TestCase, file: &str) -> Result<Value> {
+        let dir = tempfile::TempDir::new()?;
+        let engine = HelixGraphEngine::new(HelixGraphEngineOpts {
+            path: dir.path().to_string_lossy().into_owned(),
+            config: self.config.clone(),
+            version_info: VersionInfo::default(),
+        })
+        .map_err(|e| eyre!("failed to create the graph of the test: {e}"))?;
+
+        for seed in &test.seeds {
+            match seed {
+                Seed::Statements { hql, line } => {
+                    let source = self.seed_source(hql, *line, file)?;
+                    let query = &source.queries[0];
+                    interpret(&engine.storage, &source, query, HashMap::new(), true)
+                        .map_err(|e| eyre!("seeding failed: {e}"))?;
+                }
+                Seed::Call(call) => {
+                    self.call(&engine, call, true)
+                        .map_err(|e| eyre!("seeding with `{}` failed: {e}", call.query))?;
+                }
+            }
+        }
+        let write = self.writes.contains(&test.call.query);
+        self.call(&engine, &test.call, write)
+    }
+
+    fn call(&self, engine: &HelixGraphEngine, call: &Call, write: bool) -> Result<Value> {
+        let query = self
+            .source
+            .queries
+            .iter()
+            .find(|query| query.name == call.query)
+            .ok_or_else(|| eyre!("the project has no query named `{}`", call.query))?;
+        let params = serde_json::from_value::<HashMap<String, ProtocolValue>>(Value::Object(
+            call.params.clone(),
+        ))
+        .map_err(|e| eyre!("invalid parameters: {e}"))?;
+        let result = interpret(&engine.storage, &self.source, query, params, write)?;
+        Ok(serde_json::to_value(&result)?)
+    }
+
+    /// Analyze the statements of a seed as a query of their own, placed at the same lines as in
+    /// the test file for diagnostics to point at them
+    fn seed_source(&self, hql: &str, line: usize, file: &str) -> Result<Source> {
+        let padding = "\n".repeat(line.saturating_sub(1));
+        let files = vec![
+            HxFile {
+                name: "schema.hx".to_string(),
+                content: self.schema.clone(),
+            },
+            HxFile {
+                name: file.to_string(),
+                content: format!("{padding}QUERY {SEED_QUERY}() =>{hql}\n    RETURN NONE\n"),
+            },
+        ];
+        let content = Content {
+            content: format!("{}\n{}", files[0].content, files[1].content),
+            files: files.clone(),
+            source: Source::default(),
+        };
+        let source = parse_content(&content)?;
+        analyze_source(source.clone(), &files)?;
+        Ok(source)
+    }
+}
+
+/// Run the tests of a file whose name contains `filter`, writing their new snapshots
+pub fn run_test_file(
+    suite: &Suite,
+    path: &Path,
+    name: &str,
+    filter: Option<&str>,
+    update: bool,
+) -> Result<Vec<(TestCase, Outcome)>> {
+    let tests = parse_test_file(name, &fs::read_to_string(path)?)?;
+    let mut snapshots = Snapshots::load(path)?;
+    let results = tests
+        .into_iter()
+        .filter(|test| filter.is_none_or(|filter| test.name.contains(filter)))
+        .map(|test| {
+            let outcome = suite.run_test(&test, name, &mut snapshots, update);
+            (test, outcome)
+        })
+        .collect();
+    snapshots.save()?;
+    Ok(results)
+}
+
+fn report(file: &str, test: &TestCase, outcome: &Outcome) {
+    let status = match outcome {
+        Outcome::Passed => "PASS".green().bold().to_string(),
+        Outcome::Recorded => "SNAP".yellow().bold().to_string(),
+        Outcome::Failed(_) => "FAIL".red().bold().to_string(),
+    };
+    print_line(&format!("  {status} {file}:{} {}", test.line, test.name));
+    if let Outcome::Failed(message) = outcome {
+        for line in message.lines() {
+            print_line(&format!("       {line}"));
+        }
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    recorded: usize,
+    failed: usize,
+}
+
+impl Summary {
+    fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Recorded => self.recorded += 1,
+            Outcome::Failed(_) => self.failed += 1,
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        let total = self.passed + self.recorded + self.failed;
+        if total == 0 {
+            print_warning("No test matches the filter");
+            return Ok(());
+        }
+        let mut counts = format!("{} passed", self.passed + self.recorded);
+        if self.recorded > 0 {
+            counts.push_str(&format!(", {} snapshot(s) written", self.recorded));
+        }
+        if self.failed > 0 {
+            return Err(
+                CliError::new(format!("{} of {total} test(s) failed", self.failed))
+                    .with_context(counts)
+                    .into(),
+            );
+        }
+        print_success(&counts);
+        Ok(())
+    }
+}
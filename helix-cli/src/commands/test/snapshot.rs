@@ -0,0 +1,163 @@
+//! Comparing the output of queries to what the tests expect.
+
+use color_eyre::owo_colors::OwoColorize;
+use eyre::Result;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether `actual` matches `expected`, where objects only need the keys `expected` lists and
+/// numbers are compared by value
+pub fn matches(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|actual| matches(value, actual))),
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().zip(actual).all(|(e, a)| matches(e, a))
+        }
+        (Value::Number(expected), Value::Number(actual)) => expected.as_f64() == actual.as_f64(),
+        _ => expected == actual,
+    }
+}
+
+/// Drop the keys of `actual` that `expected` does not list, so a diff only shows what is checked
+pub fn prune(expected: &Value, actual: &Value) -> Value {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => Value::Object(
+            actual
+                .iter()
+                .filter_map(|(key, value)| {
+                    let expected = expected.get(key)?;
+                    Some((key.clone(), prune(expected, value)))
+                })
+                .collect(),
+        ),
+        (Value::Array(expected), Value::Array(actual)) => Value::Array(
+            actual
+                .iter()
+                .enumerate()
+                .map(|(i, value)| match expected.get(i) {
+                    Some(expected) => prune(expected, value),
+                    None => value.clone(),
+                })
+                .collect(),
+        ),
+        _ => actual.clone(),
+    }
+}
+
+/// Replace the ids in `value`, which change on every run, with placeholders numbered in the
+/// order they appear, so the same id is always written the same way
+pub fn redact_ids(value: &Value) -> Value {
+    fn redact(value: &Value, ids: &mut HashMap<String, usize>) -> Value {
+        match value {
+            Value::String(s) if uuid::Uuid::parse_str(s).is_ok() => {
+                let next = ids.len() + 1;
+                Value::String(format!("<id {}>", ids.entry(s.clone()).or_insert(next)))
+            }
+            Value::Array(values) => Value::Array(values.iter().map(|v| redact(v, ids)).collect()),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), redact(value, ids)))
+                    .collect(),
+            ),
+            _ => value.clone(),
+        }
+    }
+    redact(value, &mut HashMap::new())
+}
+
+/// Line diff of the JSON of two values, `-` lines being expected and `+` lines actual
+pub fn diff(expected: &Value, actual: &Value) -> String {
+    let expected = serde_json::to_string_pretty(expected).unwrap_or_default();
+    let actual = serde_json::to_string_pretty(actual).unwrap_or_default();
+    let (old, new) = (
+        expected.lines().collect::<Vec<_>>(),
+        actual.lines().collect::<Vec<_>>(),
+    );
+
+    // longest common subsequence of the lines, from the end
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = match old[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("{}\n", format!("- {}", old[i]).red()));
+            i += 1;
+        } else {
+            out.push_str(&format!("{}\n", format!("+ {}", new[j]).green()));
+            j += 1;
+        }
+    }
+    out
+}
+
+/// Snapshots of the tests of a file, stored as a JSON object keyed by test name in a
+/// `__snapshots__` directory next to it
+pub struct Snapshots {
+    path: PathBuf,
+    values: Map<String, Value>,
+    changed: bool,
+}
+
+impl Snapshots {
+    pub fn load(test_file: &Path) -> Result<Self> {
+        let name = test_file
+            .file_name()
+            .map(|name| name.to_string_lossy().trim_end_matches(".hx").to_string())
+            .unwrap_or_default();
+        let path = test_file
+            .with_file_name("__snapshots__")
+            .join(format!("{name}.json"));
+        let values = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| eyre::eyre!("invalid snapshots in {}: {e}", path.display()))?,
+            Err(_) => Map::new(),
+        };
+        Ok(Self {
+            path,
+            values,
+            changed: false,
+        })
+    }
+
+    pub fn get(&self, test: &str) -> Option<&Value> {
+        self.values.get(test)
+    }
+
+    pub fn set(&mut self, test: &str, value: Value) {
+        self.values.insert(test.to_string(), value);
+        self.changed = true;
+    }
+
+    /// Write the snapshots back if any was set
+    pub fn save(&self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut content = serde_json::to_string_pretty(&self.values)?;
+        content.push('\n');
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
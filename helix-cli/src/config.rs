@@ -92,6 +92,8 @@ pub struct VectorConfig {
 pub struct GraphConfig {
     #[serde(default)]
     pub secondary_indices: Vec<String>,
+    #[serde(default)]
+    pub edge_secondary_indices: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,6 +115,15 @@ pub struct DbConfig {
     pub embedding_model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub graphvis_node_label: Option<String>,
+    /// Serves the `interpret` endpoint `helix shell` runs its queries through
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub interpret: bool,
+    /// Lets `helix shell` and other interpreted queries write to the instance
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub interpret_writes: bool,
+    /// Serves the `import` endpoint `helix import` loads its rows through
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub import: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -209,6 +220,10 @@ fn is_true(value: &bool) -> bool {
     *value
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 fn is_default_vector_config(value: &VectorConfig) -> bool {
     *value == VectorConfig::default()
 }
@@ -238,6 +253,9 @@ impl Default for DbConfig {
             schema: None,
             embedding_model: default_embedding_model(),
             graphvis_node_label: None,
+            interpret: false,
+            interpret_writes: false,
+            import: false,
         }
     }
 }
@@ -316,11 +334,15 @@ impl<'a> InstanceInfo<'a> {
                 "db_max_size": db_config.vector_config.db_max_size_gb
             },
             "graph_config": {
-                "secondary_indices": db_config.graph_config.secondary_indices
+                "secondary_indices": db_config.graph_config.secondary_indices,
+                "edge_secondary_indices": db_config.graph_config.edge_secondary_indices
             },
             "db_max_size_gb": db_config.vector_config.db_max_size_gb,
             "mcp": db_config.mcp,
-            "bm25": db_config.bm25
+            "bm25": db_config.bm25,
+            "interpret": db_config.interpret,
+            "interpret_writes": db_config.interpret_writes,
+            "import": db_config.import
         });
 
         // Add optional fields if they exist
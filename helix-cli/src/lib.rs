@@ -7,6 +7,7 @@ pub mod config;
 pub mod docker;
 pub mod errors;
 pub mod metrics_sender;
+pub mod native;
 pub mod project;
 pub mod update;
 pub mod utils;
@@ -36,6 +37,30 @@ pub enum MetricsAction {
     Status,
 }
 
+#[derive(Subcommand)]
+pub enum GenerateAction {
+    /// Generate a typed client for the project queries
+    Sdk {
+        /// Language of the client (ts, py or rust)
+        #[clap(short, long)]
+        lang: String,
+
+        /// Directory to write the client to (defaults to ./sdk/)
+        #[clap(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MigrationAction {
+    /// Draft a migration from the previous schema version to the latest one
+    New {
+        /// File to write the migration to (defaults to migration_<from>_to_<to>.hx in the queries directory)
+        #[clap(short, long)]
+        output: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum CloudDeploymentTypeCommand {
     /// Initialize Helix Cloud deployment
@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
 use eyre::Result;
-use helix_cli::{AuthAction, CloudDeploymentTypeCommand, MetricsAction};
+use helix_cli::{
+    AuthAction, CloudDeploymentTypeCommand, GenerateAction, MetricsAction, MigrationAction,
+};
 
 mod cleanup;
 mod commands;
@@ -8,6 +10,7 @@ mod config;
 mod docker;
 mod errors;
 mod metrics_sender;
+mod native;
 mod project;
 mod update;
 mod utils;
@@ -51,6 +54,23 @@ enum Commands {
         instance: Option<String>,
     },
 
+    /// Run the tests of the project queries
+    Test {
+        /// Only run the tests whose name contains this filter
+        filter: Option<String>,
+
+        /// Overwrite the snapshots that do not match
+        #[clap(long)]
+        update: bool,
+    },
+
+    /// Format the .hx files of the project
+    Fmt {
+        /// Check that files are formatted without changing them
+        #[clap(long)]
+        check: bool,
+    },
+
     /// Compile project queries into the workspace
     Compile {
         /// Directory containing helix.toml (defaults to current directory or project root)
@@ -74,6 +94,22 @@ enum Commands {
         instance: String,
     },
 
+    /// Build and run a local instance
+    Run {
+        /// Instance name to run
+        instance: String,
+
+        /// Build with cargo and run as a process instead of a container
+        #[clap(long)]
+        native: bool,
+    },
+
+    /// Run a local instance and rebuild it when its queries change
+    Dev {
+        /// Instance name to run
+        instance: String,
+    },
+
     /// Pull .hql files from instance back to local project
     Pull {
         /// Instance name to pull from
@@ -95,6 +131,74 @@ enum Commands {
     /// Show status of all instances
     Status,
 
+    /// Run a query against a running local instance
+    Query {
+        /// Instance name to query
+        instance: String,
+
+        /// Name of the query to run
+        query: String,
+
+        /// Query parameter as name=value (can be repeated)
+        #[clap(short, long = "param")]
+        params: Vec<String>,
+
+        /// JSON file containing the query parameters
+        #[clap(long)]
+        json: Option<String>,
+
+        /// Output format (json or table)
+        #[clap(short, long, default_value = "json")]
+        format: String,
+    },
+
+    /// Open an interactive HQL shell on a running local instance
+    Shell {
+        /// Instance name to connect to
+        instance: String,
+
+        /// Output format (json or table)
+        #[clap(short, long, default_value = "json")]
+        format: String,
+    },
+
+    /// Bulk-load nodes, edges and vectors from CSV, JSONL or Parquet files into a local instance
+    Import {
+        /// Instance name to import into
+        instance: String,
+
+        /// Nodes to import, as Label=path (repeatable)
+        #[clap(long = "nodes", value_name = "LABEL=FILE")]
+        nodes: Vec<String>,
+
+        /// Edges to import, as Label=path with `from` and `to` columns (repeatable)
+        #[clap(long = "edges", value_name = "LABEL=FILE")]
+        edges: Vec<String>,
+
+        /// Vectors to import, as Label=path with a `vector` column (repeatable)
+        #[clap(long = "vectors", value_name = "LABEL=FILE")]
+        vectors: Vec<String>,
+
+        /// Number of rows written per transaction
+        #[clap(long, default_value = "10000")]
+        batch_size: usize,
+    },
+
+    /// Generate code from the project queries
+    Generate {
+        #[clap(subcommand)]
+        action: GenerateAction,
+    },
+
+    /// Manage schema migrations
+    Migration {
+        #[clap(subcommand)]
+        action: MigrationAction,
+    },
+
+    /// Run the HQL language server over stdio
+    Lsp,
+
     /// Cloud operations (login, keys, etc.)
     Auth {
         #[clap(subcommand)]
@@ -183,15 +287,39 @@ async fn main() -> Result<()> {
         } => commands::init::run(path, template, queries_path, cloud).await,
         Commands::Add { cloud } => commands::add::run(cloud).await,
         Commands::Check { instance } => commands::check::run(instance).await,
+        Commands::Test { filter, update } => commands::test::run(filter, update).await,
+        Commands::Fmt { check } => commands::fmt::run(check).await,
         Commands::Compile { output, path } => commands::compile::run(output, path).await,
         Commands::Build { instance } => commands::build::run(instance, &metrics_sender)
             .await
             .map(|_| ()),
         Commands::Push { instance } => commands::push::run(instance, &metrics_sender).await,
+        Commands::Run { instance, native } => {
+            commands::run::run(instance, native, &metrics_sender).await
+        }
+        Commands::Dev { instance } => commands::dev::run(instance, &metrics_sender).await,
         Commands::Pull { instance } => commands::pull::run(instance).await,
         Commands::Start { instance } => commands::start::run(instance).await,
         Commands::Stop { instance } => commands::stop::run(instance).await,
         Commands::Status => commands::status::run().await,
+        Commands::Query {
+            instance,
+            query,
+            params,
+            json,
+            format,
+        } => commands::query::run(instance, query, params, json, format).await,
+        Commands::Shell { instance, format } => commands::shell::run(instance, format).await,
+        Commands::Import {
+            instance,
+            nodes,
+            edges,
+            vectors,
+            batch_size,
+        } => commands::import::run(instance, nodes, edges, vectors, batch_size).await,
+        Commands::Generate { action } => commands::generate::run(action).await,
+        Commands::Migration { action } => commands::migration::run(action).await,
+        Commands::Lsp => commands::lsp::run(),
         Commands::Auth { action } => commands::auth::run(action).await,
         Commands::Prune { instance, all } => commands::prune::run(instance, all).await,
         Commands::Delete { instance } => commands::delete::run(instance).await,
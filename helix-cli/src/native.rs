@@ -0,0 +1,394 @@
+//! Local instances run as native processes, built with cargo instead of a container runtime.
+//!
+//! The generated `helix-container` workspace of an instance is compiled with cargo directly and
+//! its binary is installed in `.helix/<instance>/native/`, next to the PID file and the logs of
+//! its process. An instance with an installed binary is started and stopped natively, until it
+//! is deployed with a container runtime again.
+
+use crate::config::BuildMode;
+use crate::docker::DockerManager;
+use crate::errors::CliError;
+use crate::project::ProjectContext;
+use crate::utils::print_status;
+use eyre::Result;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const BINARY_NAME: &str = if cfg!(windows) {
+    "helix-container.exe"
+} else {
+    "helix-container"
+};
+const PID_FILE: &str = "helix.pid";
+const LOG_FILE: &str = "helix.log";
+
+/// Time a process has to crash on startup before it is considered started
+const STARTUP_GRACE: Duration = Duration::from_millis(500);
+/// Time a process has to exit after being asked to, before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct NativeManager<'a> {
+    project: &'a ProjectContext,
+}
+
+#[derive(Debug, Clone)]
+pub struct NativeStatus {
+    pub instance_name: String,
+    /// PID of the running process, if any
+    pub pid: Option<u32>,
+    pub port: u16,
+}
+
+impl<'a> NativeManager<'a> {
+    pub fn new(project: &'a ProjectContext) -> Self {
+        Self { project }
+    }
+
+    /// Get the installed binary of an instance
+    pub fn binary_path(&self, instance_name: &str) -> PathBuf {
+        self.project.native_dir(instance_name).join(BINARY_NAME)
+    }
+
+    /// Get the file holding the PID of the running process of an instance
+    pub fn pid_path(&self, instance_name: &str) -> PathBuf {
+        self.project.native_dir(instance_name).join(PID_FILE)
+    }
+
+    /// Get the file the output of the process of an instance is appended to
+    pub fn log_path(&self, instance_name: &str) -> PathBuf {
+        self.project.native_dir(instance_name).join(LOG_FILE)
+    }
+
+    /// Cargo target directory of the native builds
+    fn target_dir(&self, instance_name: &str) -> PathBuf {
+        self.project.native_dir(instance_name).join("target")
+    }
+
+    /// Whether the instance was last deployed natively
+    pub fn is_native(&self, instance_name: &str) -> bool {
+        self.binary_path(instance_name).exists()
+    }
+
+    /// Check that cargo is installed
+    pub fn check_cargo_available() -> Result<()> {
+        let available = Command::new("cargo")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !available {
+            return Err(CliError::new("cargo is not available")
+                .with_hint("install Rust from https://rustup.rs to run instances natively")
+                .into());
+        }
+        Ok(())
+    }
+
+    /// Compile the instance workspace with cargo and install its binary.
+    ///
+    /// The workspace has to be prepared and compiled by `helix build` first.
+    pub fn build_binary(&self, instance_name: &str, build_mode: BuildMode) -> Result<()> {
+        let workspace = self.project.instance_workspace(instance_name);
+        let repo = workspace.join("helix-repo-copy");
+        if !repo.exists() {
+            return Err(CliError::new(format!(
+                "instance '{instance_name}' has not been built yet"
+            ))
+            .with_hint(format!(
+                "run 'helix build {instance_name}' first to build the instance"
+            ))
+            .into());
+        }
+
+        // the sources are synced into a workspace of their own, where unchanged files keep their
+        // modification time for cargo to only rebuild what changed
+        let sources = self.project.native_dir(instance_name).join("workspace");
+        sync_dir(&repo, &sources)?;
+        // overlay the generated queries and config, like the Dockerfile does
+        sync_dir(
+            &self.project.container_dir(instance_name),
+            &sources.join("helix-container"),
+        )?;
+
+        print_status(
+            "CARGO",
+            &format!(
+                "Compiling instance '{instance_name}' (the first build takes a few minutes)..."
+            ),
+        );
+        let mut args = vec!["build", "--package", "helix-container"];
+        match build_mode {
+            BuildMode::Debug => {}
+            BuildMode::Release => args.push("--release"),
+            BuildMode::Dev => args.extend(["--features", "dev"]),
+        }
+        let target_dir = self.target_dir(instance_name);
+        let output = Command::new("cargo")
+            .args(&args)
+            .current_dir(&sources)
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(CliError::new("cargo build failed")
+                .with_context(tail(&stderr, 30))
+                .into());
+        }
+
+        let profile = match build_mode {
+            BuildMode::Release => "release",
+            BuildMode::Debug | BuildMode::Dev => "debug",
+        };
+        let built = target_dir.join(profile).join(BINARY_NAME);
+        let binary = self.binary_path(instance_name);
+        fs::create_dir_all(self.project.native_dir(instance_name))?;
+        // renamed into place, as a running process may still be using the previous binary
+        let staged = binary.with_extension("new");
+        fs::copy(&built, &staged)?;
+        fs::rename(&staged, &binary)?;
+
+        print_status("CARGO", "Binary built successfully");
+        Ok(())
+    }
+
+    /// Get the PID of the running process of an instance
+    pub fn running_pid(&self, instance_name: &str) -> Option<u32> {
+        let pid = fs::read_to_string(self.pid_path(instance_name))
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        is_instance_process(pid, &self.binary_path(instance_name)).then_some(pid)
+    }
+
+    /// Start the installed binary of an instance in the background, returning its PID
+    pub fn start_instance(&self, instance_name: &str) -> Result<u32> {
+        if let Some(pid) = self.running_pid(instance_name) {
+            print_status(
+                "NATIVE",
+                &format!("Instance '{instance_name}' is already running (pid {pid})"),
+            );
+            return Ok(pid);
+        }
+        let binary = self.binary_path(instance_name);
+        if !binary.exists() {
+            return Err(CliError::new(format!(
+                "instance '{instance_name}' has not been built natively"
+            ))
+            .with_hint(format!(
+                "run 'helix run {instance_name} --native' to build and start it"
+            ))
+            .into());
+        }
+
+        print_status("NATIVE", &format!("Starting instance '{instance_name}'..."));
+        let volume = self.project.instance_volume(instance_name);
+        fs::create_dir_all(&volume)?;
+        let log_path = self.log_path(instance_name);
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+
+        let mut command = Command::new(&binary);
+        command
+            .current_dir(self.project.native_dir(instance_name))
+            .envs(self.environment_variables(instance_name))
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log);
+        // keep the process out of the terminal's process group, so it outlives the CLI
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command.spawn()?;
+        let pid = child.id();
+        fs::write(self.pid_path(instance_name), pid.to_string())?;
+
+        let started = Instant::now();
+        while started.elapsed() < STARTUP_GRACE {
+            if let Some(status) = child.try_wait()? {
+                let _ = fs::remove_file(self.pid_path(instance_name));
+                let logs = fs::read_to_string(&log_path).unwrap_or_default();
+                return Err(CliError::new(format!(
+                    "instance '{instance_name}' exited on startup ({status})"
+                ))
+                .with_context(tail(&logs, 20))
+                .with_hint(format!("see the full logs in {}", log_path.display()))
+                .into());
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        // reap the process if it exits while the CLI is still running
+        thread::spawn(move || child.wait());
+
+        print_status(
+            "NATIVE",
+            &format!("Instance '{instance_name}' started successfully"),
+        );
+        Ok(pid)
+    }
+
+    /// Stop the running process of an instance, returning whether one was running
+    pub fn stop_instance(&self, instance_name: &str) -> Result<bool> {
+        let pid_path = self.pid_path(instance_name);
+        let Some(pid) = self.running_pid(instance_name) else {
+            // the process is gone, only its PID file is left
+            if pid_path.exists() {
+                fs::remove_file(&pid_path)?;
+            }
+            return Ok(false);
+        };
+
+        print_status(
+            "NATIVE",
+            &format!("Stopping instance '{instance_name}' (pid {pid})..."),
+        );
+        signal(pid, false)?;
+        let stopping = Instant::now();
+        while process_alive(pid) {
+            if stopping.elapsed() > STOP_TIMEOUT {
+                signal(pid, true)?;
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        fs::remove_file(&pid_path)?;
+
+        print_status(
+            "NATIVE",
+            &format!("Instance '{instance_name}' stopped successfully"),
+        );
+        Ok(true)
+    }
+
+    /// Stop the process of an instance and remove its binary, so it is managed by the container
+    /// runtime again
+    pub fn remove_instance(&self, instance_name: &str) -> Result<()> {
+        if !self.is_native(instance_name) {
+            return Ok(());
+        }
+        self.stop_instance(instance_name)?;
+        fs::remove_file(self.binary_path(instance_name))?;
+        Ok(())
+    }
+
+    /// Get the status of the local instances of this project deployed natively
+    pub fn get_project_status(&self) -> Vec<NativeStatus> {
+        let mut statuses = self
+            .project
+            .config
+            .local
+            .iter()
+            .filter(|(name, _)| self.is_native(name))
+            .map(|(name, config)| NativeStatus {
+                instance_name: name.clone(),
+                pid: self.running_pid(name),
+                port: config.port.unwrap_or(6969),
+            })
+            .collect::<Vec<_>>();
+        statuses.sort_by(|a, b| a.instance_name.cmp(&b.instance_name));
+        statuses
+    }
+
+    /// Environment of the container, with the data directory in the instance volume
+    fn environment_variables(&self, instance_name: &str) -> Vec<(String, String)> {
+        let volume = self.project.instance_volume(instance_name);
+        DockerManager::new(self.project)
+            .environment_variables(instance_name)
+            .into_iter()
+            .filter_map(|var| {
+                let (key, value) = var.split_once('=')?;
+                Some(match key {
+                    "HELIX_DATA_DIR" => (key.to_string(), volume.display().to_string()),
+                    _ => (key.to_string(), value.to_string()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Copy a directory recursively, only writing the files whose content changed
+fn sync_dir(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if matches!(name.to_str(), Some("target" | ".git" | ".helix")) {
+            continue;
+        }
+        let (src_path, dst_path) = (entry.path(), dst.join(&name));
+        if src_path.is_dir() {
+            sync_dir(&src_path, &dst_path)?;
+        } else if fs::read(&dst_path).ok() != Some(fs::read(&src_path)?) {
+            fs::copy(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Last lines of a command output
+fn tail(output: &str, lines: usize) -> String {
+    let all = output.lines().collect::<Vec<_>>();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+/// Whether the PID belongs to a process running the binary, and not to another process the
+/// PID was reused for
+fn is_instance_process(pid: u32, binary: &Path) -> bool {
+    if !process_alive(pid) {
+        return false;
+    }
+    // the binary is the program, or the script run by the interpreter of its shebang
+    #[cfg(target_os = "linux")]
+    if let Ok(cmdline) = fs::read(format!("/proc/{pid}/cmdline")) {
+        let binary = binary.as_os_str().as_encoded_bytes();
+        return cmdline.split(|b| *b == 0).take(2).any(|arg| arg == binary);
+    }
+    let _ = binary;
+    true
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
+/// Ask the process to exit, or kill it when `force` is set
+#[cfg(unix)]
+fn signal(pid: u32, force: bool) -> Result<()> {
+    let signal = if force { "-KILL" } else { "-TERM" };
+    // the process leads its own group, which takes its children along
+    Command::new("kill")
+        .args([signal, "--", &format!("-{pid}")])
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn signal(pid: u32, force: bool) -> Result<()> {
+    let mut command = Command::new("taskkill");
+    command.args(["/PID", &pid.to_string()]);
+    if force {
+        command.arg("/F");
+    }
+    command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    Ok(())
+}
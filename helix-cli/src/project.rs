@@ -3,6 +3,7 @@ use eyre::{Result, eyre};
 use std::env;
 use std::path::{Path, PathBuf};
 
+#[derive(Clone)]
 pub struct ProjectContext {
     /// The root directory of the project
     pub root: PathBuf,
@@ -63,6 +64,11 @@ impl ProjectContext {
             .join("helix-container")
     }
 
+    /// Get the directory of the natively built binary, PID file and logs of an instance
+    pub fn native_dir(&self, instance_name: &str) -> PathBuf {
+        self.instance_workspace(instance_name).join("native")
+    }
+
     /// Ensure all necessary directories exist for an instance
     pub fn ensure_instance_dirs(&self, instance_name: &str) -> Result<()> {
         let workspace = self.instance_workspace(instance_name);
@@ -0,0 +1,191 @@
+use crate::commands::dev::{QueryChanges, generate_queries, snapshot_queries, write_queries};
+use crate::config::HelixConfig;
+use crate::project::ProjectContext;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const SCHEMA: &str = r#"
+N::User {
+    name: String,
+    age: U32,
+}
+"#;
+
+const QUERIES: &str = r#"
+QUERY getUsers() =>
+    users <- N<User>
+    RETURN users
+
+QUERY addUser(name: String, age: U32) =>
+    user <- AddN<User>({name: name, age: age})
+    RETURN user
+"#;
+
+/// Helper function to create a test project whose instance workspace has been prepared
+fn setup_project() -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+
+    let config = HelixConfig::default_config("test-project");
+    config
+        .save_to_file(&project_path.join("helix.toml"))
+        .expect("Failed to save config");
+
+    let queries_dir = project_path.join("db");
+    fs::create_dir_all(&queries_dir).expect("Failed to create queries directory");
+    fs::write(queries_dir.join("schema.hx"), SCHEMA).expect("Failed to write schema.hx");
+    fs::write(queries_dir.join("queries.hx"), QUERIES).expect("Failed to write queries.hx");
+
+    // the config is written to the workspace by the first build
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let src_dir = project.container_dir("dev").join("src");
+    fs::create_dir_all(&src_dir).expect("Failed to create workspace");
+    let instance_config = project.config.get_instance("dev").unwrap();
+    fs::write(
+        src_dir.join("config.hx.json"),
+        serde_json::to_string_pretty(&instance_config.to_legacy_json()).unwrap(),
+    )
+    .expect("Failed to write config.hx.json");
+
+    (temp_dir, project_path)
+}
+
+#[test]
+fn test_generate_queries() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    let generated = generate_queries(&project, "dev").expect("queries should be valid");
+    assert_eq!(
+        generated.queries.keys().collect::<Vec<_>>(),
+        ["addUser", "getUsers"]
+    );
+    assert!(generated.code.contains("pub fn getUsers"));
+
+    write_queries(&project, "dev", &generated).unwrap();
+    let written = fs::read_to_string(project.container_dir("dev").join("src/queries.rs")).unwrap();
+    assert_eq!(written, generated.code);
+}
+
+#[test]
+fn test_generate_queries_reports_diagnostics() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    fs::write(
+        project_path.join("db/queries.hx"),
+        "QUERY getPosts() =>\n    posts <- N<Post>\n    RETURN posts\n",
+    )
+    .unwrap();
+    let result = generate_queries(&project, "dev");
+    let error = result
+        .err()
+        .expect("unknown node type should fail")
+        .to_string();
+    assert!(
+        error.contains("Post"),
+        "diagnostic should name the type: {error}"
+    );
+}
+
+#[test]
+fn test_query_changes() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let before = generate_queries(&project, "dev").unwrap();
+
+    fs::write(
+        project_path.join("db/queries.hx"),
+        r#"
+QUERY getUsers() =>
+    users <- N<User>::RANGE(0, 10)
+    RETURN users
+
+QUERY countUsers() =>
+    count <- N<User>::COUNT
+    RETURN count
+"#,
+    )
+    .unwrap();
+    let after = generate_queries(&project, "dev").unwrap();
+
+    assert_eq!(
+        QueryChanges::between(&before, &after),
+        QueryChanges {
+            added: vec!["countUsers".to_string()],
+            removed: vec!["addUser".to_string()],
+            modified: vec!["getUsers".to_string()],
+        }
+    );
+    assert!(QueryChanges::between(&after, &after).is_empty());
+}
+
+#[test]
+fn test_snapshot_detects_saves() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    let snapshot = snapshot_queries(&project);
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot, snapshot_queries(&project));
+
+    fs::write(project_path.join("db/queries.hx"), format!("{QUERIES}\n")).unwrap();
+    assert_ne!(snapshot, snapshot_queries(&project));
+
+    fs::write(project_path.join("db/more.hx"), "").unwrap();
+    assert_eq!(snapshot_queries(&project).len(), 3);
+}
+
+#[test]
+fn test_generate_queries_is_stable() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    fs::write(
+        project_path.join("db/schema.hx"),
+        format!(
+            "{SCHEMA}
+N::Post {{ title: String, body: String, score: I32, views: I64 }}
+E::Wrote {{ From: User, To: Post, Properties: {{ at: I64, weight: F64, note: String }} }}
+V::Doc {{ content: String, tag: String, lang: String }}
+"
+        ),
+    )
+    .unwrap();
+    fs::write(
+        project_path.join("db/queries.hx"),
+        r#"
+QUERY write(user: ID, title: String, body: String, score: I32, views: I64, at: I64, weight: F64, note: String) =>
+    post <- AddN<Post>({title: title, body: body, score: score, views: views})
+    AddE<Wrote>({at: at, weight: weight, note: note})::From(user)::To(post)
+    RETURN post
+
+QUERY edit(post: ID, title: String, body: String) =>
+    updated <- N<Post>(post)::UPDATE({title: title, body: body})
+    RETURN updated::{title, body, score}
+
+QUERY addDoc(vector: [F64], content: String, tag: String, lang: String) =>
+    doc <- AddV<Doc>(vector, {content: content, tag: tag, lang: lang})
+    RETURN doc
+"#,
+    )
+    .unwrap();
+
+    // the same queries always generate the same code, so saving a file does not rebuild
+    // queries that did not change
+    let first = generate_queries(&project, "dev").unwrap();
+    for _ in 0..10 {
+        let again = generate_queries(&project, "dev").unwrap();
+        assert!(QueryChanges::between(&first, &again).is_empty());
+        assert!(first.code == again.code);
+    }
+
+    // return fields follow the schema
+    let post = first.code.find("pub struct WritePostReturnType").unwrap();
+    let fields = &first.code[post..];
+    let title = fields.find("pub title").unwrap();
+    let body = fields.find("pub body").unwrap();
+    let views = fields.find("pub views").unwrap();
+    assert!(title < body && body < views);
+}
@@ -0,0 +1,69 @@
+use crate::commands::fmt::format_files;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const UNFORMATTED: &str = r#"N::User{name:String, age: U8}
+
+QUERY GetUser (name: String) =>
+  user <- N<User>::WHERE(_::{name}::EQ(name))
+  RETURN user
+"#;
+
+const FORMATTED: &str = r#"N::User {
+    name: String,
+    age: U8,
+}
+
+QUERY GetUser(name: String) =>
+    user <- N<User>::WHERE(_::{name}::EQ(name))
+    RETURN user
+"#;
+
+/// Helper function to write .hx files into a temporary directory
+fn write_files(files: &[(&str, &str)]) -> (TempDir, Vec<PathBuf>) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let paths = files
+        .iter()
+        .map(|(name, content)| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, content).expect("Failed to write file");
+            path
+        })
+        .collect();
+    (temp_dir, paths)
+}
+
+#[test]
+fn test_fmt_rewrites_unformatted_files() {
+    let (_temp_dir, paths) = write_files(&[("queries.hx", UNFORMATTED), ("other.hx", FORMATTED)]);
+
+    let changed = format_files(&paths, false).expect("fmt should succeed");
+    assert_eq!(changed, vec![paths[0].clone()]);
+    assert_eq!(fs::read_to_string(&paths[0]).unwrap(), FORMATTED);
+    assert_eq!(fs::read_to_string(&paths[1]).unwrap(), FORMATTED);
+
+    // formatting again changes nothing
+    let changed = format_files(&paths, false).expect("fmt should succeed");
+    assert!(changed.is_empty());
+}
+
+#[test]
+fn test_fmt_check_leaves_files_untouched() {
+    let (_temp_dir, paths) = write_files(&[("queries.hx", UNFORMATTED)]);
+
+    let changed = format_files(&paths, true).expect("fmt --check should succeed");
+    assert_eq!(changed, paths);
+    assert_eq!(fs::read_to_string(&paths[0]).unwrap(), UNFORMATTED);
+}
+
+#[test]
+fn test_fmt_fails_on_invalid_files() {
+    let (_temp_dir, paths) = write_files(&[("broken.hx", "QUERY Broken( =>")]);
+
+    let result = format_files(&paths, false);
+    assert!(result.is_err());
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("failed to format"), "{error}");
+    assert_eq!(fs::read_to_string(&paths[0]).unwrap(), "QUERY Broken( =>");
+}
@@ -0,0 +1,107 @@
+use crate::commands::generate::generate_sdk_file;
+use crate::config::HelixConfig;
+use crate::project::ProjectContext;
+use helix_db::helixc::generator::sdk::Language;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const SCHEMA: &str = r#"
+N::User {
+    name: String,
+    age: U32,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+}
+"#;
+
+const QUERIES: &str = r#"
+QUERY GetUser(user_id: ID) =>
+    user <- N<User>(user_id)
+    RETURN user
+
+QUERY GetFollowers(user_id: ID) =>
+    followers <- N<User>(user_id)::In<Follows>
+    RETURN followers::{name}
+"#;
+
+/// Helper function to create a test project with the given queries
+fn setup_project(queries: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+
+    let config = HelixConfig::default_config("test-project");
+    config
+        .save_to_file(&project_path.join("helix.toml"))
+        .expect("Failed to save config");
+
+    let queries_dir = project_path.join("db");
+    fs::create_dir_all(&queries_dir).expect("Failed to create queries directory");
+    fs::write(queries_dir.join("schema.hx"), SCHEMA).expect("Failed to write schema.hx");
+    fs::write(queries_dir.join("queries.hx"), queries).expect("Failed to write queries.hx");
+
+    (temp_dir, project_path)
+}
+
+#[test]
+fn test_generate_typescript_sdk() {
+    let (_temp_dir, project_path) = setup_project(QUERIES);
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let output_dir = project_path.join("sdk");
+
+    let path = generate_sdk_file(&project, Language::TypeScript, &output_dir)
+        .expect("generate sdk should succeed");
+    assert_eq!(path, output_dir.join("client.ts"));
+
+    let client = fs::read_to_string(&path).unwrap();
+    assert!(client.contains("export interface User {"));
+    assert!(client.contains("export interface GetUserInput {\n  user_id: string;\n}"));
+    assert!(
+        client.contains("export interface GetFollowersOutput {\n  followers: Array<string>;\n}")
+    );
+    assert!(client.contains("async GetUser(input: GetUserInput): Promise<GetUserOutput> {"));
+    assert!(client.contains("headers[\"x-api-key\"] = this.apiKey;"));
+}
+
+#[test]
+fn test_generate_python_and_rust_sdks() {
+    let (_temp_dir, project_path) = setup_project(QUERIES);
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let output_dir = project_path.join("sdk");
+
+    let path = generate_sdk_file(&project, Language::Python, &output_dir).unwrap();
+    let client = fs::read_to_string(&path).unwrap();
+    assert!(client.contains("def get_user(self, *, user_id: str) -> GetUserOutput:"));
+
+    let path = generate_sdk_file(&project, Language::Rust, &output_dir).unwrap();
+    let client = fs::read_to_string(&path).unwrap();
+    assert!(client.contains(
+        "pub async fn get_followers(&self, input: &GetFollowersInput) -> Result<GetFollowersOutput, HelixError> {"
+    ));
+
+    // both clients sit next to each other
+    assert!(output_dir.join("client.py").exists());
+    assert!(output_dir.join("client.rs").exists());
+}
+
+#[test]
+fn test_generate_sdk_fails_with_invalid_queries() {
+    let queries = r#"
+QUERY GetUser(user_id: ID) =>
+    user <- N<Unknown>(user_id)
+    RETURN user
+"#;
+    let (_temp_dir, project_path) = setup_project(queries);
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let output_dir = project_path.join("sdk");
+
+    let result = generate_sdk_file(&project, Language::TypeScript, &output_dir);
+    assert!(
+        result.is_err(),
+        "generate sdk should fail with invalid queries"
+    );
+    assert!(!output_dir.join("client.ts").exists());
+}
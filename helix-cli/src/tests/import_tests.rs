@@ -0,0 +1,348 @@
+use crate::commands::import::reader::{Format, csv_rows, jsonl_rows};
+use crate::commands::import::{IdMap, ImportKind, ImportSource, Mapping, cell, resolve, vector};
+use helix_db::helixc::parser::{
+    HelixParser,
+    types::{Content, FieldType, HxFile, Source},
+};
+use serde_json::{Map, Value, json};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Helper function to parse a schema
+fn parse_source(source: &str) -> Source {
+    let content = Content {
+        content: source.to_string(),
+        files: vec![HxFile {
+            name: "schema.hx".to_string(),
+            content: source.to_string(),
+        }],
+        source: Source::default(),
+    };
+    HelixParser::parse_source(&content).expect("Failed to parse source")
+}
+
+const SCHEMA: &str = r#"
+STRUCT Address {
+    city: String,
+    zip: U32,
+}
+
+N::User {
+    INDEX name: String,
+    age: U32,
+    active: Boolean DEFAULT true,
+    nickname: String?,
+    home: Address?,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I64,
+    }
+}
+
+V::Doc {
+    title: String,
+}
+"#;
+
+fn mapping(kind: ImportKind, label: &str) -> Mapping {
+    let source = parse_source(SCHEMA);
+    let schema = source.get_latest_schema().unwrap();
+    Mapping::new(schema, kind, label).unwrap()
+}
+
+fn columns(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => panic!("not an object"),
+    }
+}
+
+#[test]
+fn test_parse_import_source() {
+    let source = ImportSource::parse(ImportKind::Nodes, "User=data/users.csv").unwrap();
+    assert_eq!(source.label, "User");
+    assert_eq!(source.path, PathBuf::from("data/users.csv"));
+
+    assert!(ImportSource::parse(ImportKind::Nodes, "users.csv").is_err());
+    assert!(ImportSource::parse(ImportKind::Edges, "=users.csv").is_err());
+    assert!(ImportSource::parse(ImportKind::Edges, "Follows=").is_err());
+}
+
+#[test]
+fn test_format_from_extension() {
+    assert_eq!(Format::from_path(Path::new("a.csv")).unwrap(), Format::Csv);
+    assert_eq!(
+        Format::from_path(Path::new("a.JSONL")).unwrap(),
+        Format::Jsonl
+    );
+    assert_eq!(
+        Format::from_path(Path::new("a.ndjson")).unwrap(),
+        Format::Jsonl
+    );
+    assert_eq!(
+        Format::from_path(Path::new("a.parquet")).unwrap(),
+        Format::Parquet
+    );
+    assert!(Format::from_path(Path::new("a.txt")).is_err());
+}
+
+#[test]
+fn test_csv_rows() {
+    let csv = "id,name,age\n1,alice,30\n\n2,\"smith, bob\",\n3,\"say \"\"hi\"\"\nthere\",40\r\n";
+    let rows = csv_rows(Cursor::new(csv)).unwrap();
+    assert_eq!(rows.columns, Some(columns(&["id", "name", "age"])));
+
+    let rows = rows.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        Value::Object(rows[0].clone()),
+        json!({"id": "1", "name": "alice", "age": "30"})
+    );
+    // empty cells are left out of their row
+    assert_eq!(
+        Value::Object(rows[1].clone()),
+        json!({"id": "2", "name": "smith, bob"})
+    );
+    assert_eq!(rows[2]["name"], json!("say \"hi\"\nthere"));
+}
+
+#[test]
+fn test_csv_rows_with_wrong_cell_count() {
+    let rows = csv_rows(Cursor::new("id,name\n1,alice,30\n")).unwrap();
+    let errors = rows.filter_map(Result::err).collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].to_string().contains("row 1 has 3 cells"));
+
+    let mut rows = csv_rows(Cursor::new("id,name\n1,\"alice\n")).unwrap();
+    assert!(rows.next().unwrap().is_err());
+}
+
+#[test]
+fn test_jsonl_rows() {
+    let jsonl = "{\"name\": \"alice\", \"age\": 30}\n\n[1, 2]\nnot json\n";
+    let rows = jsonl_rows(Cursor::new(jsonl)).collect::<Vec<_>>();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(
+        Value::Object(rows[0].as_ref().unwrap().clone()),
+        json!({"name": "alice", "age": 30})
+    );
+    assert!(
+        rows[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("not a JSON object")
+    );
+    assert!(
+        rows[2]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("not valid JSON")
+    );
+}
+
+#[test]
+fn test_mapping_of_unknown_label() {
+    let source = parse_source(SCHEMA);
+    let schema = source.get_latest_schema().unwrap();
+    let error = Mapping::new(schema, ImportKind::Nodes, "Post")
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(error.contains("no node type named 'Post'"));
+    assert!(error.contains("User"));
+}
+
+#[test]
+fn test_check_columns() {
+    let users = mapping(ImportKind::Nodes, "User");
+    assert!(
+        users
+            .check_columns(&columns(&["id", "name", "age"]), "users.csv")
+            .is_ok()
+    );
+
+    let error = users
+        .check_columns(&columns(&["name", "age", "email"]), "users.csv")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'email'"));
+
+    // fields without a default or `?` must be given
+    let error = users
+        .check_columns(&columns(&["name"]), "users.csv")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'age'"));
+
+    let follows = mapping(ImportKind::Edges, "Follows");
+    let error = follows
+        .check_columns(&columns(&["from", "since"]), "follows.csv")
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'to'"));
+
+    let docs = mapping(ImportKind::Vectors, "Doc");
+    assert!(
+        docs.check_columns(&columns(&["id", "vector", "title"]), "docs.csv")
+            .is_ok()
+    );
+    assert!(
+        docs.check_columns(&columns(&["id", "title"]), "docs.csv")
+            .is_err()
+    );
+}
+
+#[test]
+fn test_cell_conversion() {
+    assert_eq!(cell(json!("42"), &FieldType::U32).unwrap(), json!(42));
+    assert_eq!(cell(json!(" 1.5 "), &FieldType::F64).unwrap(), json!(1.5));
+    assert!(cell(json!("abc"), &FieldType::I64).is_err());
+    assert_eq!(
+        cell(json!("TRUE"), &FieldType::Boolean).unwrap(),
+        json!(true)
+    );
+    assert!(cell(json!("yes"), &FieldType::Boolean).is_err());
+    assert_eq!(
+        cell(json!("[1, 2]"), &FieldType::Array(Box::new(FieldType::I32))).unwrap(),
+        json!([1, 2])
+    );
+    assert_eq!(cell(json!("42"), &FieldType::String).unwrap(), json!("42"));
+    // values read from JSON are kept as they are
+    assert_eq!(cell(json!(42), &FieldType::U32).unwrap(), json!(42));
+}
+
+#[test]
+fn test_vector_column() {
+    assert_eq!(vector(json!("[0.1, 0.2]")).unwrap(), json!([0.1, 0.2]));
+    assert_eq!(vector(json!([1, 2])).unwrap(), json!([1, 2]));
+    assert!(vector(json!("0.1")).is_err());
+    assert!(vector(json!([])).is_err());
+    assert!(vector(json!(["a"])).is_err());
+}
+
+#[test]
+fn test_node_row() {
+    let users = mapping(ImportKind::Nodes, "User");
+    let (id, row) = users
+        .row(
+            object(json!({"id": 7, "name": "alice", "age": "30", "active": "false"})),
+            &IdMap::new(),
+        )
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("7"));
+    assert_eq!(
+        row,
+        json!({"properties": {"name": "alice", "age": 30, "active": false}})
+    );
+
+    let error = users
+        .row(
+            object(json!({"name": "alice", "age": "old"})),
+            &IdMap::new(),
+        )
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'age'"));
+    assert!(
+        users
+            .row(
+                object(json!({"name": "alice", "email": "a@b.c"})),
+                &IdMap::new()
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn test_node_row_with_struct() {
+    let users = mapping(ImportKind::Nodes, "User");
+    let (_, row) = users
+        .row(
+            object(json!({"name": "alice", "age": "30", "home": "{\"city\": \"Paris\", \"zip\": 75001}"})),
+            &IdMap::new(),
+        )
+        .unwrap();
+    assert_eq!(
+        row["properties"]["home"],
+        json!({"city": "Paris", "zip": 75001})
+    );
+
+    let error = users
+        .row(
+            object(json!({"name": "alice", "age": "30", "home": "Paris"})),
+            &IdMap::new(),
+        )
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'home'"));
+}
+
+#[test]
+fn test_edge_row_resolves_ids() {
+    let alice = "0192a3b4-c5d6-7e8f-9a0b-1c2d3e4f5a6b";
+    let bob = "0192a3b4-c5d6-7e8f-9a0b-1c2d3e4f5a6c";
+    let mut ids = IdMap::new();
+    ids.entry("User".to_string())
+        .or_default()
+        .insert("1".to_string(), alice.to_string());
+
+    let follows = mapping(ImportKind::Edges, "Follows");
+    let (id, row) = follows
+        .row(
+            object(json!({"from": "1", "to": bob, "since": "2020"})),
+            &ids,
+        )
+        .unwrap();
+    assert_eq!(id, None);
+    assert_eq!(
+        row,
+        json!({"from": alice, "to": bob, "properties": {"since": 2020}})
+    );
+
+    let error = follows
+        .row(object(json!({"from": "1", "to": "2"})), &ids)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("no User was imported with id '2'"));
+}
+
+#[test]
+fn test_resolve() {
+    let mut ids = IdMap::new();
+    ids.entry("Doc".to_string())
+        .or_default()
+        .insert("a".to_string(), "id-of-a".to_string());
+    assert_eq!(resolve(&ids, "Doc", "a").unwrap(), "id-of-a");
+    // ids are looked up by the label the edge points at
+    assert!(resolve(&ids, "User", "a").is_err());
+}
+
+#[test]
+fn test_vector_row() {
+    let docs = mapping(ImportKind::Vectors, "Doc");
+    let (id, row) = docs
+        .row(
+            object(json!({"id": "d1", "vector": "[0.5, 1]", "title": "intro"})),
+            &IdMap::new(),
+        )
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("d1"));
+    assert_eq!(
+        row,
+        json!({"vector": [0.5, 1], "properties": {"title": "intro"}})
+    );
+    assert!(
+        docs.row(object(json!({"title": "intro"})), &IdMap::new())
+            .is_err()
+    );
+}
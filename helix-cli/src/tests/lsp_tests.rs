@@ -0,0 +1,220 @@
+use crate::commands::lsp::analysis::{Analysis, type_name};
+use helix_db::helixc::{analyzer::types::Type, parser::types::HxFile};
+use lsp_types::{CodeActionOrCommand, CompletionItem, Position, Range, Url};
+
+const SCHEMA: &str = r#"N::User {
+    name: String,
+    age: U8,
+}
+
+N::Post {
+    title: String,
+}
+
+E::Wrote {
+    From: User,
+    To: Post,
+    Properties: {
+        at: Date,
+    }
+}
+"#;
+
+const QUERIES: &str = r#"QUERY GetPosts(name: String) =>
+    user <- N<User>::WHERE(_::{name}::EQ(name))::FIRST
+    posts <- user::Out<Wrote>
+    RETURN posts
+"#;
+
+fn analysis(queries: &str) -> Analysis {
+    Analysis::new(vec![
+        HxFile {
+            name: "/project/schema.hx".to_string(),
+            content: SCHEMA.to_string(),
+        },
+        HxFile {
+            name: "/project/queries.hx".to_string(),
+            content: queries.to_string(),
+        },
+    ])
+}
+
+fn labels(items: Vec<CompletionItem>) -> Vec<String> {
+    items.into_iter().map(|item| item.label).collect()
+}
+
+#[test]
+fn test_valid_project_has_no_diagnostics() {
+    let analysis = analysis(QUERIES);
+    assert!(analysis.is_parsed());
+    assert!(analysis.diagnostics("/project/schema.hx").is_empty());
+    assert!(analysis.diagnostics("/project/queries.hx").is_empty());
+}
+
+#[test]
+fn test_analyzer_diagnostics_point_at_their_file() {
+    let analysis =
+        analysis("QUERY GetComments() =>\n    comments <- N<Comment>\n    RETURN comments\n");
+
+    let diagnostics = analysis.diagnostics("/project/queries.hx");
+    let diagnostic = &diagnostics[0];
+    assert_eq!(
+        diagnostic.code,
+        Some(lsp_types::NumberOrString::String("E101".to_string()))
+    );
+    assert_eq!(diagnostic.range.start.line, 1);
+    assert!(diagnostic.message.contains("Comment"));
+    assert!(analysis.diagnostics("/project/schema.hx").is_empty());
+}
+
+#[test]
+fn test_syntax_errors_are_reported() {
+    let analysis = analysis("QUERY Broken() =>\n    users <- N<User>::\n    RETURN users\n");
+
+    assert!(!analysis.is_parsed());
+    let diagnostics = analysis.diagnostics("/project/queries.hx");
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.starts_with("syntax error"));
+    // the step after `::` is missing, so the parser stops at `RETURN`
+    assert_eq!(diagnostics[0].range.start, Position::new(2, 4));
+}
+
+#[test]
+fn test_code_action_applies_fix() {
+    let queries = "QUERY GetUsers() =>\n    users <- N<User>::!{age}::!{age}\n    RETURN users\n";
+    let analysis = analysis(queries);
+    let diagnostics = analysis.diagnostics("/project/queries.hx");
+    assert!(!diagnostics.is_empty());
+
+    let uri = Url::parse("file:///project/queries.hx").unwrap();
+    let actions = analysis.code_actions("/project/queries.hx", &uri, diagnostics[0].range);
+    let [CodeActionOrCommand::CodeAction(action)] = actions.as_slice() else {
+        panic!("expected a single code action, got {actions:?}");
+    };
+    let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].new_text, "");
+    assert_eq!(edits[0].range.start.line, 1);
+}
+
+#[test]
+fn test_hover_shows_inferred_types() {
+    let analysis = analysis(QUERIES);
+
+    // `posts` in `posts <- user::Out<Wrote>`
+    let hover = analysis
+        .hover("/project/queries.hx", Position::new(2, 6))
+        .unwrap();
+    assert_eq!(hover, "```hql\nposts: [N<Post>]\n```");
+
+    // `user` in `user <- N<User>...::FIRST`
+    let hover = analysis
+        .hover("/project/queries.hx", Position::new(1, 5))
+        .unwrap();
+    assert_eq!(hover, "```hql\nuser: N<User>\n```");
+
+    // the `name` parameter
+    let hover = analysis
+        .hover("/project/queries.hx", Position::new(0, 16))
+        .unwrap();
+    assert_eq!(hover, "```hql\nname: String\n```");
+}
+
+#[test]
+fn test_hover_shows_schema_items() {
+    let analysis = analysis(QUERIES);
+
+    // `Wrote` in `user::Out<Wrote>`
+    let hover = analysis
+        .hover("/project/queries.hx", Position::new(2, 24))
+        .unwrap();
+    assert!(hover.contains("E::Wrote {"));
+    assert!(hover.contains("at: Date"));
+}
+
+#[test]
+fn test_definition_of_schema_items_and_variables() {
+    let analysis = analysis(QUERIES);
+
+    // `User` in `N<User>` goes to `N::User`
+    let (file, range) = analysis
+        .definition("/project/queries.hx", Position::new(1, 15))
+        .unwrap();
+    assert_eq!(file, "/project/schema.hx");
+    assert_eq!(range, Range::new(Position::new(0, 3), Position::new(0, 7)));
+
+    // `user` in `user::Out<Wrote>` goes to its assignment
+    let (file, range) = analysis
+        .definition("/project/queries.hx", Position::new(2, 14))
+        .unwrap();
+    assert_eq!(file, "/project/queries.hx");
+    assert_eq!(range, Range::new(Position::new(1, 4), Position::new(1, 8)));
+
+    // `name` in `EQ(name)` goes to the parameter
+    let (_, range) = analysis
+        .definition("/project/queries.hx", Position::new(1, 44))
+        .unwrap();
+    assert_eq!(
+        range,
+        Range::new(Position::new(0, 15), Position::new(0, 19))
+    );
+}
+
+#[test]
+fn test_complete_labels_by_kind() {
+    let analysis = analysis(QUERIES);
+
+    let line = "    users <- N<";
+    let items = analysis.completions(
+        "/project/queries.hx",
+        Position::new(1, line.len() as u32),
+        line,
+    );
+    assert_eq!(labels(items), vec!["User", "Post"]);
+
+    let line = "    posts <- user::Out<";
+    let items = analysis.completions(
+        "/project/queries.hx",
+        Position::new(2, line.len() as u32),
+        line,
+    );
+    assert_eq!(labels(items), vec!["Wrote"]);
+}
+
+#[test]
+fn test_complete_fields_and_steps() {
+    let analysis = analysis(QUERIES);
+
+    let line = "    users <- N<User>::{";
+    let items = analysis.completions(
+        "/project/queries.hx",
+        Position::new(1, line.len() as u32),
+        line,
+    );
+    assert_eq!(labels(items), vec!["name", "age"]);
+
+    let line = "    users <- N<User>::";
+    let items = labels(analysis.completions(
+        "/project/queries.hx",
+        Position::new(1, line.len() as u32),
+        line,
+    ));
+    assert!(items.contains(&"WHERE".to_string()));
+    assert!(items.contains(&"OutE".to_string()));
+}
+
+#[test]
+fn test_type_name() {
+    assert_eq!(
+        type_name(&Type::Nodes(Some("User".to_string()))),
+        "[N<User>]"
+    );
+    assert_eq!(
+        type_name(&Type::Edge(Some("Wrote".to_string()))),
+        "E<Wrote>"
+    );
+    assert_eq!(
+        type_name(&Type::Array(Box::new(Type::Boolean))),
+        "[Boolean]"
+    );
+}
@@ -0,0 +1,87 @@
+use crate::commands::migration::write_migration_draft;
+use crate::config::HelixConfig;
+use crate::project::ProjectContext;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const SCHEMAS: &str = r#"
+schema::1 {
+    N::User {
+        name: String,
+        age: U32,
+    }
+}
+
+schema::2 {
+    N::User {
+        username: String,
+        age: U64,
+        joined: I64,
+    }
+}
+"#;
+
+/// Helper function to create a test project with the given schema
+fn setup_project(schema: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+
+    let config = HelixConfig::default_config("test-project");
+    config
+        .save_to_file(&project_path.join("helix.toml"))
+        .expect("Failed to save config");
+
+    let queries_dir = project_path.join("db");
+    fs::create_dir_all(&queries_dir).expect("Failed to create queries directory");
+    fs::write(queries_dir.join("schema.hx"), schema).expect("Failed to write schema.hx");
+
+    (temp_dir, project_path)
+}
+
+#[test]
+fn test_migration_new_writes_draft() {
+    let (_temp_dir, project_path) = setup_project(SCHEMAS);
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    let (draft, path) = write_migration_draft(&project, None).expect("draft should succeed");
+    assert_eq!(path, project_path.join("db/migration_1_to_2.hx"));
+    assert_eq!(draft.reviews.len(), 2);
+
+    let migration = fs::read_to_string(&path).unwrap();
+    assert!(migration.contains("MIGRATION schema::1 => schema::2 {"));
+    assert!(migration.contains("        username: name,\n"));
+    assert!(migration.contains("        age: age AS U64,\n"));
+    assert!(migration.contains("        // REVIEW: `joined` is a new required field"));
+    assert!(migration.contains("        joined: 0,\n"));
+
+    // the draft is picked up with the queries, so it is not drafted twice
+    let result = write_migration_draft(&project, None);
+    assert!(result.is_err(), "the migration already exists");
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("already exists"));
+}
+
+#[test]
+fn test_migration_new_to_output_file() {
+    let (_temp_dir, project_path) = setup_project(SCHEMAS);
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let output = project_path.join("drafts/users.hx");
+
+    let (_, path) =
+        write_migration_draft(&project, Some(output.clone())).expect("draft should succeed");
+    assert_eq!(path, output);
+    assert!(output.exists());
+    assert!(!project_path.join("db/migration_1_to_2.hx").exists());
+}
+
+#[test]
+fn test_migration_new_needs_two_schema_versions() {
+    let (_temp_dir, project_path) = setup_project("N::User {\n    name: String,\n}\n");
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+
+    let result = write_migration_draft(&project, None);
+    assert!(result.is_err(), "a single schema has nothing to migrate");
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("at least two schema versions"));
+}
@@ -5,6 +5,18 @@ pub mod init_tests;
 pub mod check_tests;
 #[cfg(test)]
 pub mod compile_tests;
+#[cfg(test)]
+pub mod dev_tests;
+#[cfg(test)]
+pub mod fmt_tests;
+#[cfg(test)]
+pub mod generate_tests;
+#[cfg(test)]
+pub mod import_tests;
+#[cfg(test)]
+pub mod migration_tests;
+#[cfg(all(test, unix))]
+pub mod native_tests;
 // #[cfg(test)]
 // pub mod build_tests;
 // #[cfg(test)]
@@ -13,3 +25,11 @@ pub mod compile_tests;
 // pub mod status_tests;
 #[cfg(test)]
 pub mod project_tests;
+#[cfg(test)]
+pub mod query_tests;
+#[cfg(test)]
+pub mod shell_tests;
+#[cfg(test)]
+pub mod test_runner_tests;
+#[cfg(test)]
+pub mod lsp_tests;
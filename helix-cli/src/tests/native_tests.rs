@@ -0,0 +1,112 @@
+use crate::config::HelixConfig;
+use crate::native::NativeManager;
+use crate::project::ProjectContext;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Helper function to create a test project with a `dev` local instance
+fn setup_project() -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+
+    let config = HelixConfig::default_config("test-project");
+    config
+        .save_to_file(&project_path.join("helix.toml"))
+        .expect("Failed to save config");
+
+    (temp_dir, project_path)
+}
+
+/// Install a script standing in for the binary built by cargo
+fn install_binary(native: &NativeManager, script: &str) {
+    let binary = native.binary_path("dev");
+    fs::create_dir_all(binary.parent().unwrap()).unwrap();
+    fs::write(&binary, format!("#!/bin/sh\n{script}\n")).unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn test_native_start_and_stop() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let native = NativeManager::new(&project);
+    assert!(!native.is_native("dev"));
+
+    install_binary(
+        &native,
+        "echo \"port $HELIX_PORT data $HELIX_DATA_DIR\"\nsleep 30",
+    );
+    assert!(native.is_native("dev"));
+    assert_eq!(native.running_pid("dev"), None);
+
+    let pid = native.start_instance("dev").expect("start should succeed");
+    assert_eq!(native.running_pid("dev"), Some(pid));
+    assert_eq!(
+        fs::read_to_string(native.pid_path("dev")).unwrap(),
+        pid.to_string()
+    );
+    // starting a running instance keeps its process
+    assert_eq!(native.start_instance("dev").unwrap(), pid);
+
+    let statuses = native.get_project_status();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].instance_name, "dev");
+    assert_eq!(statuses[0].pid, Some(pid));
+    assert_eq!(statuses[0].port, 6969);
+
+    assert!(native.stop_instance("dev").expect("stop should succeed"));
+    assert_eq!(native.running_pid("dev"), None);
+    assert!(!native.pid_path("dev").exists());
+    assert!(!native.stop_instance("dev").unwrap(), "already stopped");
+    assert_eq!(native.get_project_status()[0].pid, None);
+
+    let logs = fs::read_to_string(native.log_path("dev")).unwrap();
+    let volume = project.instance_volume("dev");
+    assert!(logs.contains(&format!("port 6969 data {}", volume.display())));
+}
+
+#[test]
+fn test_native_start_reports_crash() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let native = NativeManager::new(&project);
+    install_binary(&native, "echo 'failed to open database'\nexit 1");
+
+    let result = native.start_instance("dev");
+    assert!(result.is_err(), "the process exits on startup");
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("exited on startup"));
+    assert!(error.contains("failed to open database"));
+    assert!(!native.pid_path("dev").exists());
+}
+
+#[test]
+fn test_native_ignores_stale_pid() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let native = NativeManager::new(&project);
+    install_binary(&native, "sleep 30");
+
+    // the PID of this test process, which is not running the instance binary
+    fs::write(native.pid_path("dev"), std::process::id().to_string()).unwrap();
+    assert_eq!(native.running_pid("dev"), None);
+    assert!(!native.stop_instance("dev").unwrap());
+    assert!(!native.pid_path("dev").exists());
+
+    native.remove_instance("dev").unwrap();
+    assert!(!native.is_native("dev"));
+}
+
+#[test]
+fn test_native_build_needs_workspace() {
+    let (_temp_dir, project_path) = setup_project();
+    let project = ProjectContext::find_and_load(Some(&project_path)).unwrap();
+    let native = NativeManager::new(&project);
+
+    let result = native.build_binary("dev", crate::config::BuildMode::Debug);
+    assert!(result.is_err(), "the instance has not been built");
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("has not been built yet"));
+}
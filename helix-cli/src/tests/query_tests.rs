@@ -0,0 +1,238 @@
+use crate::commands::query::{OutputFormat, build_body, find_query, format_response};
+use helix_db::helixc::parser::{
+    HelixParser,
+    types::{Content, HxFile, Source},
+};
+use serde_json::json;
+
+/// Helper function to parse the queries of a schema and query source
+fn parse_source(source: &str) -> Source {
+    let content = Content {
+        content: source.to_string(),
+        files: vec![HxFile {
+            name: "queries.hx".to_string(),
+            content: source.to_string(),
+        }],
+        source: Source::default(),
+    };
+    HelixParser::parse_source(&content).expect("Failed to parse source")
+}
+
+fn test_source() -> Source {
+    parse_source(
+        r#"
+STRUCT Address {
+    city: String,
+    zip: U32,
+    country: String?,
+}
+
+N::User {
+    name: String,
+    age: U8,
+    home: Address?,
+}
+
+QUERY CreateUser(name: String, age: U8, tags: [String], nickname?: String) =>
+    user <- AddN<User>({name: name, age: age})
+    RETURN user
+
+QUERY GetUser(id: ID) =>
+    user <- N<User>(id)
+    RETURN user
+
+QUERY MoveUser(id: ID, home: Address) =>
+    user <- N<User>(id)::UPDATE({home: home})
+    RETURN user
+"#,
+    )
+}
+
+#[test]
+fn test_build_body_from_params() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "CreateUser").unwrap();
+
+    let params = vec![
+        "name=alice".to_string(),
+        "age=42".to_string(),
+        r#"tags=["a", "b"]"#.to_string(),
+    ];
+    let body = build_body(schema, query, &params, None).unwrap();
+
+    assert_eq!(
+        body,
+        json!({ "name": "alice", "age": 42, "tags": ["a", "b"] })
+    );
+}
+
+#[test]
+fn test_build_body_params_override_json() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "CreateUser").unwrap();
+
+    let json = json!({ "name": "alice", "age": 42, "tags": [] });
+    let body = build_body(schema, query, &["name=bob".to_string()], Some(json)).unwrap();
+
+    assert_eq!(body["name"], "bob");
+    assert_eq!(body["age"], 42);
+}
+
+#[test]
+fn test_build_body_rejects_mistyped_params() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "CreateUser").unwrap();
+
+    // 300 does not fit in a U8
+    let json = json!({ "name": "alice", "age": 300, "tags": [] });
+    assert!(build_body(schema, query, &[], Some(json)).is_err());
+
+    let params = vec![
+        "name=alice".to_string(),
+        "age=old".to_string(),
+        "tags=[]".to_string(),
+    ];
+    assert!(build_body(schema, query, &params, None).is_err());
+
+    let query = find_query(&source.queries, "GetUser").unwrap();
+    assert!(build_body(schema, query, &["id=not-a-uuid".to_string()], None).is_err());
+    assert!(
+        build_body(
+            schema,
+            query,
+            &["id=2b5b4a6e-5a6f-4c0e-9d43-4f1a3c5d2e10".to_string()],
+            None
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_build_body_struct_params() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "MoveUser").unwrap();
+    let id = "id=2b5b4a6e-5a6f-4c0e-9d43-4f1a3c5d2e10".to_string();
+
+    // structs are passed as the JSON object of their fields
+    let params = vec![
+        id.clone(),
+        r#"home={"city": "Paris", "zip": 75001}"#.to_string(),
+    ];
+    let body = build_body(schema, query, &params, None).unwrap();
+    assert_eq!(body["home"], json!({ "city": "Paris", "zip": 75001 }));
+
+    let params = vec![id.clone(), r#"home={"city": 1}"#.to_string()];
+    assert!(build_body(schema, query, &params, None).is_err());
+    let params = vec![id, "home=Paris".to_string()];
+    assert!(build_body(schema, query, &params, None).is_err());
+}
+
+#[test]
+fn test_build_body_struct_params_missing_fields() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "MoveUser").unwrap();
+    let id = "2b5b4a6e-5a6f-4c0e-9d43-4f1a3c5d2e10";
+
+    let json = json!({ "id": id, "home": { "city": "Paris" } });
+    let result = build_body(schema, query, &[], Some(json));
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("parameter 'home' is missing the field 'zip'")
+    );
+
+    // fields declared with `?` may be left out
+    let json = json!({ "id": id, "home": { "city": "Paris", "zip": 75001 } });
+    assert!(build_body(schema, query, &[], Some(json)).is_ok());
+}
+
+#[test]
+fn test_build_body_null_params() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "CreateUser").unwrap();
+
+    let json = json!({ "name": "alice", "age": 42, "tags": [], "nickname": null });
+    let body = build_body(schema, query, &[], Some(json)).unwrap();
+    assert!(body["nickname"].is_null());
+
+    let json = json!({ "name": null, "age": 42, "tags": [] });
+    let result = build_body(schema, query, &[], Some(json));
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("parameter 'name' expects String, got null")
+    );
+}
+
+#[test]
+fn test_build_body_missing_and_unknown_params() {
+    let source = test_source();
+    let schema = source.get_latest_schema().unwrap();
+    let query = find_query(&source.queries, "CreateUser").unwrap();
+
+    let result = build_body(schema, query, &["name=alice".to_string()], None);
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("missing parameter 'age'")
+    );
+
+    let params = vec![
+        "name=alice".to_string(),
+        "age=1".to_string(),
+        "tags=[]".to_string(),
+        "email=a@b.c".to_string(),
+    ];
+    let result = build_body(schema, query, &params, None);
+    assert!(
+        result
+            .unwrap_err()
+            .to_string()
+            .contains("no parameter 'email'")
+    );
+}
+
+#[test]
+fn test_find_unknown_query() {
+    let source = test_source();
+
+    let result = find_query(&source.queries, "DeleteUser");
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("query 'DeleteUser' not found"));
+    assert!(error.contains("CreateUser"));
+}
+
+#[test]
+fn test_format_response_as_table() {
+    let response = json!({
+        "users": [
+            { "name": "alice", "age": 42 },
+            { "name": "bob", "email": "bob@example.com" },
+        ]
+    });
+
+    let table = format_response(&response, OutputFormat::Table).unwrap();
+    let lines = table.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "users:");
+    assert_eq!(lines[1], "age | name  | email");
+    assert_eq!(lines[2], "----+-------+----------------");
+    assert_eq!(lines[3], "42  | alice |");
+    assert_eq!(lines[4], "    | bob   | bob@example.com");
+}
+
+#[test]
+fn test_format_response_as_json() {
+    let response = json!({ "user": { "name": "alice" } });
+
+    let output = format_response(&response, OutputFormat::Json).unwrap();
+    assert_eq!(output, serde_json::to_string_pretty(&response).unwrap());
+}
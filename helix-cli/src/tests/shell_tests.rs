@@ -0,0 +1,143 @@
+use crate::commands::shell::{complete_label, schema_labels, wrap_snippet};
+use helix_db::helixc::parser::{
+    HelixParser,
+    types::{Content, HxFile, Source},
+};
+
+/// Helper function to parse a schema and query source
+fn parse_source(source: &str) -> Source {
+    let content = Content {
+        content: source.to_string(),
+        files: vec![HxFile {
+            name: "queries.hx".to_string(),
+            content: source.to_string(),
+        }],
+        source: Source::default(),
+    };
+    HelixParser::parse_source(&content).expect("Failed to parse source")
+}
+
+const SOURCE: &str = r#"
+STRUCT Address {
+    city: String,
+}
+
+N::User {
+    INDEX name: String,
+    age: U8 DEFAULT 18,
+    home: Address?,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I32,
+    }
+}
+
+V::Document {
+    content: String,
+}
+
+QUERY GetUser(name: String) =>
+    user <- N<User>({name: name})
+    RETURN user
+"#;
+
+#[test]
+fn test_schema_source_keeps_definitions_only() {
+    let schema = parse_source(SOURCE).schema_source();
+
+    assert!(schema.contains("INDEX name: String"));
+    assert!(schema.contains("age: U8 DEFAULT 18"));
+    assert!(schema.contains("E::Follows"));
+    assert!(schema.contains("V::Document"));
+    assert!(schema.contains("STRUCT Address"));
+    assert!(!schema.contains("QUERY"));
+
+    // the reconstructed schema parses to the same labels
+    let reparsed = parse_source(&schema);
+    assert_eq!(
+        schema_labels(&reparsed),
+        schema_labels(&parse_source(SOURCE))
+    );
+}
+
+#[test]
+fn test_schema_labels_are_sorted() {
+    let labels = schema_labels(&parse_source(SOURCE));
+    assert_eq!(labels, vec!["Document", "Follows", "User"]);
+}
+
+#[test]
+fn test_wrap_snippet_returns_last_assignment() {
+    let query = wrap_snippet("users <- N<User>\ncount <- users::COUNT");
+    assert_eq!(
+        query,
+        "QUERY ShellQuery() =>\n    users <- N<User>\n    count <- users::COUNT\n    RETURN count\n"
+    );
+}
+
+#[test]
+fn test_wrap_snippet_assigns_bare_traversal() {
+    let query = wrap_snippet("N<User>::WHERE(_::{age}::GT(30))");
+    assert_eq!(
+        query,
+        "QUERY ShellQuery() =>\n    result <- N<User>::WHERE(_::{age}::GT(30))\n    RETURN result\n"
+    );
+}
+
+#[test]
+fn test_wrap_snippet_drop_returns_none() {
+    let query = wrap_snippet("DROP N<User>::WHERE(_::{age}::LT(18))");
+    assert_eq!(
+        query,
+        "QUERY ShellQuery() =>\n    DROP N<User>::WHERE(_::{age}::LT(18))\n    RETURN NONE\n"
+    );
+}
+
+#[test]
+fn test_wrap_snippet_keeps_explicit_return() {
+    let query = wrap_snippet("user <- N<User>::FIRST\nRETURN user::{name}");
+    assert_eq!(
+        query,
+        "QUERY ShellQuery() =>\n    user <- N<User>::FIRST\n    RETURN user::{name}\n"
+    );
+
+    let full = "QUERY Users() =>\n    users <- N<User>\n    RETURN users";
+    assert_eq!(wrap_snippet(full), full);
+}
+
+#[test]
+fn test_wrapped_snippets_parse() {
+    let schema = parse_source(SOURCE).schema_source();
+    for snippet in [
+        "N<User>::COUNT",
+        "user <- N<User>::FIRST\nfollowed <- user::Out<Follows>",
+        "DROP N<User>",
+    ] {
+        let source = parse_source(&format!("{schema}\n{}", wrap_snippet(snippet)));
+        assert_eq!(source.queries.len(), 1, "{snippet}");
+    }
+}
+
+#[test]
+fn test_complete_label() {
+    let labels = schema_labels(&parse_source(SOURCE));
+
+    let line = "users <- N<Us";
+    assert_eq!(
+        complete_label(&labels, line, line.len()),
+        (11, vec!["User".to_string()])
+    );
+
+    let line = "user::Out<F";
+    assert_eq!(
+        complete_label(&labels, line, line.len()),
+        (10, vec!["Follows".to_string()])
+    );
+
+    let line = "N<";
+    assert_eq!(complete_label(&labels, line, line.len()), (2, vec![]));
+}
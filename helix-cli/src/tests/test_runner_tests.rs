@@ -0,0 +1,231 @@
+use crate::commands::test::file::{Expectation, Seed, parse_test_file};
+use crate::commands::test::snapshot::{diff, matches, redact_ids};
+use crate::commands::test::{Outcome, Suite, run_test_file};
+use crate::config::HelixConfig;
+use crate::project::ProjectContext;
+use crate::utils::helixc_utils::{collect_hx_files, collect_test_files};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const SCHEMA: &str = r#"
+N::User {
+    INDEX name: String,
+    age: U32,
+}
+"#;
+
+const QUERIES: &str = r#"
+QUERY addUser(name: String, age: U32) =>
+    user <- AddN<User>({name: name, age: age})
+    RETURN user
+
+QUERY getUsers() =>
+    users <- N<User>
+    RETURN users
+"#;
+
+const TESTS: &str = r#"
+// seeded with statements
+TEST "lists the seeded users" {
+    SEED {
+        AddN<User>({name: "alice", age: 30})
+    }
+    CALL getUsers()
+    EXPECT {"users": [{"name": "alice", "age": 30}]}
+}
+
+TEST "adds a user" {
+    SEED addUser({"name": "bob", "age": 20})
+    CALL addUser({"name": "carol", "age": 40})
+    EXPECT SNAPSHOT
+}
+"#;
+
+/// Helper function to create a test project with a test file
+fn setup_project(tests: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+
+    let config = HelixConfig::default_config("test-project");
+    config
+        .save_to_file(&project_path.join("helix.toml"))
+        .expect("Failed to save config");
+
+    let queries_dir = project_path.join("db");
+    fs::create_dir_all(&queries_dir).expect("Failed to create queries directory");
+    fs::write(queries_dir.join("schema.hx"), SCHEMA).expect("Failed to write schema.hx");
+    fs::write(queries_dir.join("queries.hx"), QUERIES).expect("Failed to write queries.hx");
+    fs::write(queries_dir.join("users.test.hx"), tests).expect("Failed to write users.test.hx");
+
+    (temp_dir, project_path)
+}
+
+fn run_tests(project_path: &PathBuf, update: bool) -> Vec<(String, Outcome)> {
+    let project = ProjectContext::find_and_load(Some(project_path)).unwrap();
+    let suite = Suite::load(&project).expect("queries should be valid");
+    let path = project_path.join("db/users.test.hx");
+    run_test_file(&suite, &path, "db/users.test.hx", None, update)
+        .expect("test file should run")
+        .into_iter()
+        .map(|(test, outcome)| (test.name, outcome))
+        .collect()
+}
+
+#[test]
+fn test_parse_test_file() {
+    let tests = parse_test_file("users.test.hx", TESTS).expect("test file should parse");
+    assert_eq!(tests.len(), 2);
+
+    assert_eq!(tests[0].name, "lists the seeded users");
+    assert_eq!(tests[0].line, 3);
+    assert!(matches!(
+        &tests[0].seeds[..],
+        [Seed::Statements { line: 4, hql }] if hql.contains("AddN<User>")
+    ));
+    assert_eq!(tests[0].call.query, "getUsers");
+    assert!(tests[0].call.params.is_empty());
+
+    assert!(matches!(&tests[1].seeds[..], [Seed::Call(call)] if call.query == "addUser"));
+    assert_eq!(tests[1].call.params["name"], json!("carol"));
+    assert_eq!(tests[1].expect, Expectation::Snapshot);
+}
+
+#[test]
+fn test_parse_test_file_errors() {
+    let missing = parse_test_file("a.test.hx", "TEST \"t\" {\n    CALL getUsers()\n}\n");
+    assert_eq!(
+        missing.unwrap_err().to_string(),
+        "a.test.hx:1: test \"t\" has no `EXPECT`"
+    );
+
+    let duplicate = parse_test_file(
+        "a.test.hx",
+        "TEST \"t\" { CALL q() EXPECT 1 }\nTEST \"t\" { CALL q() EXPECT 1 }\n",
+    );
+    assert!(
+        duplicate
+            .unwrap_err()
+            .to_string()
+            .contains("already exists")
+    );
+
+    let params = parse_test_file("a.test.hx", "TEST \"t\" {\n    CALL q([1])\n}\n");
+    assert_eq!(
+        params.unwrap_err().to_string(),
+        "a.test.hx:2: parameters are a JSON object"
+    );
+
+    let unknown = parse_test_file("a.test.hx", "TEST \"t\" {\n    ASSERT 1\n}\n");
+    assert!(unknown.unwrap_err().to_string().starts_with("a.test.hx:2:"));
+}
+
+#[test]
+fn test_matches_checks_listed_keys() {
+    let actual = json!({"users": [{"id": "x", "name": "alice", "age": 30}]});
+    assert!(matches(&json!({"users": [{"name": "alice"}]}), &actual));
+    assert!(matches(&json!({"users": [{"age": 30.0}]}), &actual));
+    assert!(!matches(&json!({"users": []}), &actual));
+    assert!(!matches(&json!({"users": [{"email": "a@b.c"}]}), &actual));
+}
+
+#[test]
+fn test_redact_ids() {
+    let first = "0198c7b6-2b7e-7a41-9c5f-1f0e6f0d2a11";
+    let second = "0198c7b6-2b7e-7a41-9c5f-1f0e6f0d2a12";
+    let value = json!({"a": {"id": first}, "b": [{"id": second, "from": first}]});
+    assert_eq!(
+        redact_ids(&value),
+        json!({"a": {"id": "<id 1>"}, "b": [{"id": "<id 2>", "from": "<id 1>"}]})
+    );
+}
+
+#[test]
+fn test_diff_marks_changed_lines() {
+    let out = diff(&json!({"age": 30}), &json!({"age": 31}));
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[1].contains("- ") && lines[1].contains("30"));
+    assert!(lines[2].contains("+ ") && lines[2].contains("31"));
+}
+
+#[test]
+fn test_collect_hx_files_skips_test_files() {
+    let (_temp_dir, project_path) = setup_project(TESTS);
+
+    let hx_files = collect_hx_files(&project_path, &PathBuf::from("db")).unwrap();
+    let mut names = hx_files
+        .iter()
+        .map(|file| file.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["queries.hx", "schema.hx"]);
+
+    let test_files = collect_test_files(&project_path, &PathBuf::from("db")).unwrap();
+    assert_eq!(test_files, [project_path.join("db/users.test.hx")]);
+}
+
+#[test]
+fn test_run_test_file_records_then_checks_snapshots() {
+    let (_temp_dir, project_path) = setup_project(TESTS);
+
+    let results = run_tests(&project_path, false);
+    assert_eq!(
+        results[0],
+        ("lists the seeded users".to_string(), Outcome::Passed)
+    );
+    assert_eq!(results[1], ("adds a user".to_string(), Outcome::Recorded));
+
+    let snapshots = project_path.join("db/__snapshots__/users.test.json");
+    let content = fs::read_to_string(&snapshots).expect("snapshot should be written");
+    assert!(content.contains("\"carol\""));
+    assert!(content.contains("<id 1>"));
+
+    let results = run_tests(&project_path, false);
+    assert_eq!(results[1], ("adds a user".to_string(), Outcome::Passed));
+}
+
+#[test]
+fn test_run_test_file_reports_failures() {
+    let tests = r#"
+TEST "wrong age" {
+    SEED addUser({"name": "alice", "age": 30})
+    CALL getUsers()
+    EXPECT {"users": [{"age": 31}]}
+}
+
+TEST "unknown query" {
+    CALL getPosts()
+    EXPECT {}
+}
+"#;
+    let (_temp_dir, project_path) = setup_project(tests);
+
+    let results = run_tests(&project_path, false);
+    match &results[0].1 {
+        Outcome::Failed(message) => assert!(message.contains("31") && message.contains("30")),
+        outcome => panic!("expected a failure, got {outcome:?}"),
+    }
+    assert_eq!(
+        results[1].1,
+        Outcome::Failed("the project has no query named `getPosts`".to_string())
+    );
+}
+
+#[test]
+fn test_changed_snapshot_fails_until_updated() {
+    let (_temp_dir, project_path) = setup_project(TESTS);
+    run_tests(&project_path, false);
+
+    let changed = TESTS.replace("\"carol\", \"age\": 40", "\"carol\", \"age\": 41");
+    fs::write(project_path.join("db/users.test.hx"), changed).unwrap();
+
+    let results = run_tests(&project_path, false);
+    assert!(matches!(&results[1].1, Outcome::Failed(message) if message.contains("--update")));
+
+    let results = run_tests(&project_path, true);
+    assert_eq!(results[1].1, Outcome::Recorded);
+    let results = run_tests(&project_path, false);
+    assert_eq!(results[1].1, Outcome::Passed);
+}
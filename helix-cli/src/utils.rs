@@ -250,28 +250,43 @@ pub mod helixc_utils {
     };
     use std::{fs, path::Path};
 
+    /// Suffix of the files holding the tests of a project, which are not compiled with its queries
+    pub const TEST_FILE_SUFFIX: &str = ".test.hx";
+
+    /// Whether a file holds tests rather than queries
+    pub fn is_test_file(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().ends_with(TEST_FILE_SUFFIX))
+    }
+
+    fn collect_from_dir(
+        dir: &Path,
+        files: &mut Vec<std::fs::DirEntry>,
+        include: fn(&Path) -> bool,
+    ) -> Result<()> {
+        if dir.file_name().unwrap_or_default() == ".helix" {
+            return Ok(());
+        }
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() && include(&path) {
+                files.push(entry);
+            } else if path.is_dir() {
+                collect_from_dir(&path, files, include)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Collect all .hx files from queries directory and subdirectories
     pub fn collect_hx_files(root: &Path, queries_dir: &Path) -> Result<Vec<std::fs::DirEntry>> {
         let mut files = Vec::new();
         let queries_path = root.join(queries_dir);
 
-        fn collect_from_dir(dir: &Path, files: &mut Vec<std::fs::DirEntry>) -> Result<()> {
-            if dir.file_name().unwrap_or_default() == ".helix" {
-                return Ok(());
-            }
-            for entry in fs::read_dir(dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.is_file() && path.extension().map(|s| s == "hx").unwrap_or(false) {
-                    files.push(entry);
-                } else if path.is_dir() {
-                    collect_from_dir(&path, files)?;
-                }
-            }
-            Ok(())
-        }
-
-        collect_from_dir(&queries_path, &mut files)?;
+        collect_from_dir(&queries_path, &mut files, |path| {
+            path.extension().map(|s| s == "hx").unwrap_or(false) && !is_test_file(path)
+        })?;
 
         if files.is_empty() {
             return Err(eyre::eyre!(
@@ -283,6 +298,15 @@ pub mod helixc_utils {
         Ok(files)
     }
 
+    /// Collect the test files from queries directory and subdirectories, in path order
+    pub fn collect_test_files(root: &Path, queries_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+        let mut files = Vec::new();
+        collect_from_dir(&root.join(queries_dir), &mut files, is_test_file)?;
+        let mut paths = files.iter().map(|file| file.path()).collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+
     /// Generate content from .hx files (similar to build.rs)
     pub fn generate_content(files: &[std::fs::DirEntry]) -> Result<Content> {
         let hx_files: Vec<HxFile> = files
@@ -40,6 +40,10 @@ pub trait BM25 {
 
     fn insert_doc(&self, txn: &mut RwTxn, doc_id: u128, doc: &str) -> Result<(), GraphError>;
 
+    /// Inserts a batch of documents, writing each term's document frequency and the metadata
+    /// once for the whole batch
+    fn insert_docs(&self, txn: &mut RwTxn, docs: &[(u128, String)]) -> Result<(), GraphError>;
+
     fn delete_doc(&self, txn: &mut RwTxn, doc_id: u128) -> Result<(), GraphError>;
 
     fn update_doc(&self, txn: &mut RwTxn, doc_id: u128, doc: &str) -> Result<(), GraphError>;
@@ -217,6 +221,69 @@ impl BM25 for HBM25Config {
         Ok(())
     }
 
+    fn insert_docs(&self, txn: &mut RwTxn, docs: &[(u128, String)]) -> Result<(), GraphError> {
+        if docs.is_empty() {
+            return Ok(());
+        }
+
+        let mut doc_frequencies: HashMap<String, u32> = HashMap::new();
+        let mut total_length = 0u64;
+        for (doc_id, doc) in docs {
+            let tokens = self.tokenize::<true>(doc);
+            let doc_length = tokens.len() as u32;
+            total_length += doc_length as u64;
+
+            let mut term_counts: HashMap<String, u32> = HashMap::new();
+            for token in tokens {
+                *term_counts.entry(token).or_insert(0) += 1;
+            }
+
+            self.doc_lengths_db.put(txn, doc_id, &doc_length)?;
+
+            for (term, tf) in term_counts {
+                let posting_entry = PostingListEntry {
+                    doc_id: *doc_id,
+                    term_frequency: tf,
+                };
+                let posting_bytes = bincode::serialize(&posting_entry)?;
+                self.inverted_index_db
+                    .put(txn, term.as_bytes(), &posting_bytes)?;
+                *doc_frequencies.entry(term).or_insert(0) += 1;
+            }
+        }
+
+        // sorted, so the frequencies are written in key order
+        let mut doc_frequencies = doc_frequencies.into_iter().collect::<Vec<_>>();
+        doc_frequencies.sort_unstable();
+        for (term, added) in doc_frequencies {
+            let term_bytes = term.as_bytes();
+            let current_df = self.term_frequencies_db.get(txn, term_bytes)?.unwrap_or(0);
+            self.term_frequencies_db
+                .put(txn, term_bytes, &(current_df + added))?;
+        }
+
+        let mut metadata = if let Some(data) = self.metadata_db.get(txn, METADATA_KEY)? {
+            bincode::deserialize::<BM25Metadata>(data)?
+        } else {
+            BM25Metadata {
+                total_docs: 0,
+                avgdl: 0.0,
+                k1: 1.2,
+                b: 0.75,
+            }
+        };
+
+        let old_total_docs = metadata.total_docs;
+        metadata.total_docs += docs.len() as u64;
+        metadata.avgdl = (metadata.avgdl * old_total_docs as f64 + total_length as f64)
+            / metadata.total_docs as f64;
+
+        let metadata_bytes = bincode::serialize(&metadata)?;
+        self.metadata_db.put(txn, METADATA_KEY, &metadata_bytes)?;
+
+        Ok(())
+    }
+
     fn delete_doc(&self, txn: &mut RwTxn, doc_id: u128) -> Result<(), GraphError> {
         let terms_to_update = {
             let mut terms = Vec::new();
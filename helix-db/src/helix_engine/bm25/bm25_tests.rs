@@ -158,6 +158,49 @@ mod tests {
         wtxn.commit().unwrap();
     }
 
+    #[test]
+    fn test_insert_documents_in_batch() {
+        let (batched, _temp_dir) = setup_bm25_config();
+        let (single, _temp_dir2) = setup_bm25_config();
+
+        let docs = vec![
+            (1u128, "The quick brown fox".to_string()),
+            (2u128, "the lazy brown dog".to_string()),
+            (3u128, "machine learning algorithms".to_string()),
+        ];
+
+        let mut wtxn = batched.graph_env.write_txn().unwrap();
+        batched.insert_docs(&mut wtxn, &docs[..2]).unwrap();
+        batched.insert_docs(&mut wtxn, &docs[2..]).unwrap();
+        wtxn.commit().unwrap();
+
+        let mut wtxn = single.graph_env.write_txn().unwrap();
+        for (doc_id, doc) in &docs {
+            single.insert_doc(&mut wtxn, *doc_id, doc).unwrap();
+        }
+        wtxn.commit().unwrap();
+
+        let rtxn = batched.graph_env.read_txn().unwrap();
+        let metadata_bytes = batched.metadata_db.get(&rtxn, METADATA_KEY).unwrap().unwrap();
+        let metadata: BM25Metadata = bincode::deserialize(metadata_bytes).unwrap();
+        assert_eq!(metadata.total_docs, 3);
+        assert_eq!(
+            batched.term_frequencies_db.get(&rtxn, b"brown").unwrap(),
+            Some(2)
+        );
+
+        // the batch is indexed the same way as documents inserted one by one, documents with
+        // equal scores being returned in no particular order
+        let single_rtxn = single.graph_env.read_txn().unwrap();
+        for query in ["brown", "quick fox", "learning"] {
+            let mut batched_results = batched.search(&rtxn, query, 10).unwrap();
+            let mut single_results = single.search(&single_rtxn, query, 10).unwrap();
+            batched_results.sort_by_key(|(id, _)| *id);
+            single_results.sort_by_key(|(id, _)| *id);
+            assert_eq!(batched_results, single_results);
+        }
+    }
+
     #[test]
     fn test_search_single_term() {
         let (bm25, _temp_dir) = setup_bm25_config();
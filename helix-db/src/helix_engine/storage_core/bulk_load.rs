@@ -0,0 +1,202 @@
+//! Bulk loading of nodes, edges and vectors.
+//!
+//! Imports write a whole batch of items in the transaction they are given instead of going
+//! through `AddN`/`AddE` one item at a time: the index entries of the batch are collected
+//! and written in key order once its items are stored, and its BM25 documents are indexed
+//! together.
+
+use crate::{
+    helix_engine::{
+        bm25::bm25::{BM25, BM25Flatten},
+        storage_core::HelixGraphStorage,
+        types::GraphError,
+        vector_core::{hnsw::HNSW, vector::HVector},
+    },
+    utils::{
+        id::v6_uuid,
+        items::{Edge, Node},
+        label_hash::hash_label,
+        properties::ImmutablePropertiesMap,
+    },
+};
+use heed3::{Database, PutFlags, RoTxn, RwTxn, byteorder::BE, types::*};
+
+/// An edge to write, between two nodes or vectors that already exist
+pub struct BulkEdge<'arena> {
+    pub from_node: u128,
+    pub to_node: u128,
+    pub properties: Option<ImmutablePropertiesMap<'arena>>,
+}
+
+impl HelixGraphStorage {
+    /// Writes nodes of a single label, returning their ids in the order they were given.
+    ///
+    /// `secondary_indices` are the fields of the label that are indexed.
+    pub fn bulk_add_nodes<'arena>(
+        &self,
+        txn: &mut RwTxn,
+        label: &'arena str,
+        nodes: Vec<Option<ImmutablePropertiesMap<'arena>>>,
+        secondary_indices: &[&str],
+    ) -> Result<Vec<u128>, GraphError> {
+        let mut indices = secondary_indices
+            .iter()
+            .map(|index| match self.secondary_indices.get(*index) {
+                Some(db) => Ok((*index, db, Vec::new())),
+                None => Err(GraphError::New(format!(
+                    "Secondary Index {index} not found"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut ids = Vec::with_capacity(nodes.len());
+        let mut docs = Vec::new();
+
+        for properties in nodes {
+            let node = Node {
+                id: v6_uuid(),
+                label,
+                version: 1,
+                properties,
+            };
+            self.nodes_db.put_with_flags(
+                txn,
+                PutFlags::APPEND,
+                &node.id,
+                &bincode::serialize(&node)?,
+            )?;
+
+            for (index, _, entries) in &mut indices {
+                if let Some(value) = node.get_property(index) {
+                    entries.push((bincode::serialize(value)?, node.id));
+                }
+            }
+            if self.bm25.is_some()
+                && let Some(props) = node.properties.as_ref()
+            {
+                let mut data = props.flatten_bm25();
+                data.push_str(node.label);
+                docs.push((node.id, data));
+            }
+            ids.push(node.id);
+        }
+
+        for (_, db, entries) in indices {
+            Self::put_index_entries(txn, db, entries)?;
+        }
+        if let Some(bm25) = &self.bm25 {
+            bm25.insert_docs(txn, &docs)?;
+        }
+        Ok(ids)
+    }
+
+    /// Writes edges of a single label, returning their ids in the order they were given.
+    ///
+    /// Fails if any of their endpoints is neither a node nor a vector.
+    pub fn bulk_add_edges<'arena>(
+        &self,
+        txn: &mut RwTxn,
+        label: &'arena str,
+        edges: Vec<BulkEdge<'arena>>,
+    ) -> Result<Vec<u128>, GraphError> {
+        let version = self.version_info.get_latest(label);
+        let label_hash = hash_label(label, None);
+        let mut indices = self
+            .edge_secondary_indices
+            .iter()
+            .map(|(index, db)| (index.as_str(), db, Vec::new()))
+            .collect::<Vec<_>>();
+        let mut ids = Vec::with_capacity(edges.len());
+
+        for BulkEdge {
+            from_node,
+            to_node,
+            properties,
+        } in edges
+        {
+            for id in [from_node, to_node] {
+                if self.nodes_db.get(txn, Self::node_key(&id))?.is_none()
+                    && self.vectors.vector_properties_db.get(txn, &id)?.is_none()
+                {
+                    return Err(GraphError::New(format!(
+                        "item {} does not exist",
+                        uuid::Uuid::from_u128(id)
+                    )));
+                }
+            }
+
+            let edge = Edge {
+                id: v6_uuid(),
+                label,
+                version,
+                properties,
+                from_node,
+                to_node,
+            };
+            self.edges_db.put_with_flags(
+                txn,
+                PutFlags::APPEND,
+                Self::edge_key(&edge.id),
+                &edge.to_bincode_bytes()?,
+            )?;
+            self.out_edges_db.put_with_flags(
+                txn,
+                PutFlags::APPEND_DUP,
+                &Self::out_edge_key(&from_node, &label_hash),
+                &Self::pack_edge_data(&edge.id, &to_node),
+            )?;
+            self.in_edges_db.put_with_flags(
+                txn,
+                PutFlags::APPEND_DUP,
+                &Self::in_edge_key(&to_node, &label_hash),
+                &Self::pack_edge_data(&edge.id, &from_node),
+            )?;
+
+            for (index, _, entries) in &mut indices {
+                if let Some(value) = edge.get_property(index) {
+                    entries.push((bincode::serialize(value)?, edge.id));
+                }
+            }
+            ids.push(edge.id);
+        }
+
+        for (_, db, entries) in indices {
+            Self::put_index_entries(txn, db, entries)?;
+        }
+        Ok(ids)
+    }
+
+    /// Inserts vectors of a single label into its HNSW index, returning their ids in the order
+    /// they were given
+    pub fn bulk_add_vectors<'db, 'arena>(
+        &'db self,
+        txn: &mut RwTxn<'db>,
+        arena: &'arena bumpalo::Bump,
+        label: &'arena str,
+        vectors: Vec<(&'arena [f64], Option<ImmutablePropertiesMap<'arena>>)>,
+    ) -> Result<Vec<u128>, GraphError>
+    where
+        'db: 'arena,
+    {
+        let mut ids = Vec::with_capacity(vectors.len());
+        for (data, properties) in vectors {
+            let vector = self
+                .vectors
+                .insert::<fn(&HVector, &RoTxn) -> bool>(txn, label, data, properties, arena)?;
+            ids.push(vector.id);
+        }
+        Ok(ids)
+    }
+
+    /// Writes the entries of a secondary index sorted by key, the order LMDB writes fastest
+    fn put_index_entries(
+        txn: &mut RwTxn,
+        db: &Database<Bytes, U128<BE>>,
+        mut entries: Vec<(Vec<u8>, u128)>,
+    ) -> Result<(), GraphError> {
+        entries.sort_unstable();
+        for (key, id) in entries {
+            db.put(txn, &key, &id)?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+pub mod bulk_load;
 pub mod graph_visualization;
 pub mod metadata;
 pub mod storage_methods;
@@ -41,6 +42,7 @@ const DB_EDGES: &str = "edges"; // for edge data (e:)
 const DB_OUT_EDGES: &str = "out_edges"; // for outgoing edge indices (o:)
 const DB_IN_EDGES: &str = "in_edges"; // for incoming edge indices (i:)
 const DB_STORAGE_METADATA: &str = "storage_metadata"; // for storage metadata key/value pairs
+const DB_EDGE_INDEX_PREFIX: &str = "edge_index:"; // for edge property indices, followed by the property name
 
 pub type NodeId = u128;
 pub type EdgeId = u128;
@@ -49,6 +51,7 @@ pub struct StorageConfig {
     pub schema: Option<String>,
     pub graphvis_node_label: Option<String>,
     pub embedding_model: Option<String>,
+    pub interpret_writes: bool,
 }
 
 pub struct HelixGraphStorage {
@@ -59,6 +62,7 @@ pub struct HelixGraphStorage {
     pub out_edges_db: Database<Bytes, Bytes>,
     pub in_edges_db: Database<Bytes, Bytes>,
     pub secondary_indices: HashMap<String, Database<Bytes, U128<BE>>>,
+    pub edge_secondary_indices: HashMap<String, Database<Bytes, U128<BE>>>,
     pub vectors: VectorCore,
     pub bm25: Option<HBM25Config>,
     pub metadata_db: Database<Bytes, Bytes>,
@@ -155,6 +159,22 @@ impl HelixGraphStorage {
                 );
             }
         }
+        // Edge indices: [serialized property value]->[edge_id]
+        // named apart from the node indices so the same property can be indexed on both
+        let mut edge_secondary_indices = HashMap::new();
+        if let Some(indexes) = config.get_graph_config().edge_secondary_indices {
+            for index in indexes {
+                edge_secondary_indices.insert(
+                    index.clone(),
+                    graph_env
+                        .database_options()
+                        .types::<Bytes, U128<BE>>()
+                        .flags(DatabaseFlags::DUP_SORT)
+                        .name(&format!("{DB_EDGE_INDEX_PREFIX}{index}"))
+                        .create(&mut wtxn)?,
+                );
+            }
+        }
         let vector_config = config.get_vector_config();
         let vectors = VectorCore::new(
             &graph_env,
@@ -171,10 +191,12 @@ impl HelixGraphStorage {
             .then(|| HBM25Config::new(&graph_env, &mut wtxn))
             .transpose()?;
 
+        let interpret_writes = config.get_interpret_writes();
         let storage_config = StorageConfig::new(
             config.schema,
             config.graphvis_node_label,
             config.embedding_model,
+            interpret_writes,
         );
 
         wtxn.commit()?;
@@ -186,6 +208,7 @@ impl HelixGraphStorage {
             out_edges_db,
             in_edges_db,
             secondary_indices,
+            edge_secondary_indices,
             vectors,
             bm25,
             metadata_db,
@@ -273,6 +296,40 @@ impl HelixGraphStorage {
         );
         Ok((edge_id, node_id))
     }
+
+    /// Returns the `(edge_id, to_node)` pairs of the outgoing edges of `node_id`, only
+    /// following the edges with the given label when one is given.
+    pub fn out_neighbours(
+        &self,
+        txn: &RoTxn,
+        node_id: u128,
+        label_hash: Option<&[u8; 4]>,
+    ) -> Result<Vec<(EdgeId, NodeId)>, GraphError> {
+        let prefix = match label_hash {
+            Some(label_hash) => Self::out_edge_key(&node_id, label_hash).to_vec(),
+            None => node_id.to_be_bytes().to_vec(),
+        };
+        let mut neighbours = Vec::new();
+        for result in self.out_edges_db.prefix_iter(txn, &prefix)? {
+            let (_, value) = result?;
+            neighbours.push(Self::unpack_adj_edge_data(value)?);
+        }
+        Ok(neighbours)
+    }
+
+    /// Removes the edge from the edge property indices it is stored in.
+    pub fn delete_edge_index_entries(
+        &self,
+        txn: &mut RwTxn,
+        edge: &Edge,
+    ) -> Result<(), GraphError> {
+        for (index_name, db) in &self.edge_secondary_indices {
+            if let Some(value) = edge.get_property(index_name) {
+                db.delete_one_duplicate(txn, &bincode::serialize(value)?, &edge.id)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl StorageConfig {
@@ -280,11 +337,13 @@ impl StorageConfig {
         schema: Option<String>,
         graphvis_node_label: Option<String>,
         embedding_model: Option<String>,
+        interpret_writes: bool,
     ) -> StorageConfig {
         Self {
             schema,
             graphvis_node_label,
             embedding_model,
+            interpret_writes,
         }
     }
 }
@@ -389,8 +448,12 @@ impl StorageMethods for HelixGraphStorage {
 
         // println!("Deleting edges: {}", );
         // Delete all related data
-        for edge in edges {
-            self.edges_db.delete(txn, Self::edge_key(&edge))?;
+        for edge_id in edges {
+            if !self.edge_secondary_indices.is_empty() {
+                let edge = self.get_edge(txn, &edge_id, &arena)?;
+                self.delete_edge_index_entries(txn, &edge)?;
+            }
+            self.edges_db.delete(txn, Self::edge_key(&edge_id))?;
         }
         for label_bytes in out_edges.iter() {
             self.out_edges_db
@@ -454,6 +517,7 @@ impl StorageMethods for HelixGraphStorage {
         let out_edge_value = Self::pack_edge_data(edge_id, &edge.to_node);
         let in_edge_value = Self::pack_edge_data(edge_id, &edge.from_node);
         // Delete all edge-related data
+        self.delete_edge_index_entries(txn, &edge)?;
         self.edges_db.delete(txn, Self::edge_key(edge_id))?;
         self.out_edges_db.delete_one_duplicate(
             txn,
@@ -511,8 +575,12 @@ impl StorageMethods for HelixGraphStorage {
 
         // println!("Deleting edges: {}", );
         // Delete all related data
-        for edge in edges {
-            self.edges_db.delete(txn, Self::edge_key(&edge))?;
+        for edge_id in edges {
+            if !self.edge_secondary_indices.is_empty() {
+                let edge = self.get_edge(txn, &edge_id, &arena)?;
+                self.delete_edge_index_entries(txn, &edge)?;
+            }
+            self.edges_db.delete(txn, Self::edge_key(&edge_id))?;
         }
         for label_bytes in out_edges.iter() {
             self.out_edges_db
@@ -308,7 +308,17 @@ pub(crate) fn convert_old_vector_properties_to_new_format(
     new_vector.to_bincode_bytes().map_err(GraphError::from)
 }
 
-fn verify_vectors_and_repair(storage: &HelixGraphStorage) -> Result<(), GraphError> {
+/// Copies vectors stored at a level above 0 but missing at level 0 down to level 0,
+/// returning how many were repaired
+pub(crate) fn verify_vectors_and_repair(storage: &HelixGraphStorage) -> Result<usize, GraphError> {
+    // A graph without vectors, e.g. a new one, has nothing to verify
+    {
+        let txn = storage.graph_env.read_txn()?;
+        if storage.vectors.vectors_db.is_empty(&txn)? {
+            return Ok(0);
+        }
+    }
+
     // Verify that all vectors at level > 0 also exist at level 0 and collect ones that need repair
     println!("\nVerifying vector integrity after migration...");
     let vectors_to_repair: Vec<(u128, usize)> = {
@@ -399,7 +409,7 @@ fn verify_vectors_and_repair(storage: &HelixGraphStorage) -> Result<(), GraphErr
         println!("All vectors verified successfully!");
     }
 
-    Ok(())
+    Ok(vectors_to_repair.len())
 }
 
 fn remove_orphaned_vector_edges(storage: &HelixGraphStorage) -> Result<(), GraphError> {
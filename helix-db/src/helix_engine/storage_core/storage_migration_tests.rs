@@ -12,14 +12,14 @@ use super::{
     metadata::{StorageMetadata, VectorEndianness, NATIVE_VECTOR_ENDIANNESS},
     storage_migration::{
         convert_all_vector_properties, convert_old_vector_properties_to_new_format,
-        convert_vector_endianness, migrate,
+        convert_vector_endianness, migrate, verify_vectors_and_repair,
     },
     HelixGraphStorage,
 };
 use crate::{
     helix_engine::{
         storage_core::version_info::VersionInfo, traversal_core::config::Config,
-        types::GraphError,
+        types::GraphError, vector_core::vector_core::VectorCore,
     },
     protocol::value::Value,
 };
@@ -409,6 +409,37 @@ fn test_migrate_empty_database() {
     ));
 }
 
+#[test]
+fn test_verify_vectors_and_repair() {
+    let (storage, _temp_dir) = setup_test_storage();
+
+    // A graph without vectors is skipped
+    assert_eq!(verify_vectors_and_repair(&storage).unwrap(), 0);
+
+    // A vector stored at level 1 but not at level 0 is copied down
+    let vector_data = create_test_vector_bytes(&[1.0, 2.0], NATIVE_VECTOR_ENDIANNESS);
+    {
+        let mut txn = storage.graph_env.write_txn().unwrap();
+        storage
+            .vectors
+            .vectors_db
+            .put(&mut txn, &VectorCore::vector_key(7, 1), &vector_data)
+            .unwrap();
+        txn.commit().unwrap();
+    }
+    assert_eq!(verify_vectors_and_repair(&storage).unwrap(), 1);
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let repaired = storage
+        .vectors
+        .vectors_db
+        .get(&txn, &VectorCore::vector_key(7, 0))
+        .unwrap();
+    assert_eq!(repaired, Some(vector_data.as_slice()));
+    drop(txn);
+    assert_eq!(verify_vectors_and_repair(&storage).unwrap(), 0);
+}
+
 #[test]
 fn test_migrate_pre_metadata_to_native() {
     let (mut storage, _temp_dir) = setup_test_storage();
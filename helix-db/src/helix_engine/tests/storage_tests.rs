@@ -253,11 +253,12 @@ fn test_storage_config() {
     let graphvis = Some("name".to_string());
     let embedding = Some("openai".to_string());
 
-    let config = StorageConfig::new(schema.clone(), graphvis.clone(), embedding.clone());
+    let config = StorageConfig::new(schema.clone(), graphvis.clone(), embedding.clone(), false);
 
     assert_eq!(config.schema, schema);
     assert_eq!(config.graphvis_node_label, graphvis);
     assert_eq!(config.embedding_model, embedding);
+    assert!(!config.interpret_writes);
 }
 
 #[test]
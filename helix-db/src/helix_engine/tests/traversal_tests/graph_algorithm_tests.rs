@@ -0,0 +1,151 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            ops::{
+                g::G,
+                source::{add_e::AddEAdapter, add_n::AddNAdapter, n_from_type::NFromTypeAdapter},
+                util::graph_algorithms::{GraphAlgorithm, GraphAlgorithmAdapter},
+            },
+            traversal_value::TraversalValue,
+        },
+    },
+    props,
+    protocol::value::Value,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Builds the cycle `a -> b -> c -> a` with `d -> a` hanging off it, a separate
+/// `e -> f` pair, and a `likes` edge `d -> e` joining the two
+fn setup_graph(storage: &Arc<HelixGraphStorage>) {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ids = ["a", "b", "c", "d", "e", "f"]
+        .iter()
+        .map(|name| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("user", props_option(&arena, props!("name" => *name)), None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect::<Vec<_>>();
+    for (label, from, to) in [
+        ("follows", 0, 1),
+        ("follows", 1, 2),
+        ("follows", 2, 0),
+        ("follows", 3, 0),
+        ("follows", 4, 5),
+        ("likes", 3, 4),
+    ] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge(label, None, ids[from], ids[to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+fn run(
+    storage: &Arc<HelixGraphStorage>,
+    edge_labels: &[&str],
+    algorithm: GraphAlgorithm,
+) -> HashMap<String, f64> {
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    G::new(storage, &txn, &arena)
+        .n_from_type("user")
+        .run_graph_algorithm(edge_labels, algorithm)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .iter()
+        .map(|value: &TraversalValue| {
+            match (value.get_property("name"), value.get_property("score")) {
+                (Some(Value::String(name)), Some(Value::F64(score))) => (name.clone(), *score),
+                other => panic!("unexpected node {other:?}"),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_connected_components() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_graph(&storage);
+
+    let strong = run(&storage, &["follows"], GraphAlgorithm::StronglyConnectedComponents);
+    assert_eq!(strong["a"], strong["b"]);
+    assert_eq!(strong["b"], strong["c"]);
+    assert_ne!(strong["a"], strong["d"]);
+    assert_ne!(strong["e"], strong["f"]);
+
+    let weak = run(&storage, &["follows"], GraphAlgorithm::WeaklyConnectedComponents);
+    assert_eq!(weak["a"], weak["d"]);
+    assert_eq!(weak["e"], weak["f"]);
+    assert_ne!(weak["a"], weak["e"]);
+
+    // Without a label restriction the `likes` edge joins both components
+    let all = run(&storage, &[], GraphAlgorithm::WeaklyConnectedComponents);
+    assert_eq!(all["a"], all["f"]);
+}
+
+#[test]
+fn test_page_rank_and_triangles() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_graph(&storage);
+
+    let ranks = run(
+        &storage,
+        &["follows"],
+        GraphAlgorithm::PageRank {
+            damping: 0.85,
+            iterations: 30,
+        },
+    );
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(ranks["a"] > ranks["d"]);
+    assert!(ranks["f"] > ranks["e"]);
+
+    let triangles = run(&storage, &["follows"], GraphAlgorithm::TriangleCount);
+    assert_eq!(triangles["a"], 1.0);
+    assert_eq!(triangles["c"], 1.0);
+    assert_eq!(triangles["d"], 0.0);
+}
+
+#[test]
+fn test_betweenness_and_label_propagation() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_graph(&storage);
+
+    let betweenness = run(&storage, &[], GraphAlgorithm::Betweenness { samples: 0 });
+    assert_eq!(betweenness["f"], 0.0);
+    assert!(betweenness["e"] > 0.0);
+    assert!(betweenness["a"] > betweenness["d"]);
+
+    let communities = run(
+        &storage,
+        &["follows"],
+        GraphAlgorithm::LabelPropagation { iterations: 10 },
+    );
+    assert_eq!(communities["a"], communities["b"]);
+    assert_eq!(communities["b"], communities["c"]);
+    assert_ne!(communities["a"], communities["e"]);
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            source::{add_n::AddNAdapter, n_from_type::NFromTypeAdapter},
+            util::{aggregate::AggregateAdapter, group_by::GroupByAdapter},
+        },
+    },
+    props,
+    protocol::value::Value,
+    utils::group_by::{AggregateFunction, Aggregation, GroupRow},
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+fn setup_orders(storage: &Arc<HelixGraphStorage>) {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    for (region, customer, amount, qty) in [
+        ("eu", "ann", 10.0, 1),
+        ("us", "bob", 5.0, 2),
+        ("eu", "ann", 30.0, 3),
+        ("eu", "cat", 20.0, 4),
+        ("us", "dan", 15.0, 5),
+    ] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_n(
+                "order",
+                props_option(
+                    &arena,
+                    props!(
+                        "region" => region,
+                        "customer" => customer,
+                        "amount" => amount,
+                        "qty" => qty
+                    ),
+                ),
+                None,
+            )
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+fn aggregate<'a>(row: &'a GroupRow, name: &str) -> &'a Value {
+    &row.aggregates.iter().find(|(key, _)| key == name).unwrap().1
+}
+
+#[test]
+fn test_group_by_with_aggregations() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let rows = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .group_by_with(
+            &["region".to_string()],
+            &[
+                Aggregation {
+                    name: "total",
+                    function: AggregateFunction::Sum("amount"),
+                },
+                Aggregation {
+                    name: "items",
+                    function: AggregateFunction::Sum("qty"),
+                },
+                Aggregation {
+                    name: "average",
+                    function: AggregateFunction::Avg("amount"),
+                },
+                Aggregation {
+                    name: "smallest",
+                    function: AggregateFunction::Min("amount"),
+                },
+                Aggregation {
+                    name: "largest",
+                    function: AggregateFunction::Max("amount"),
+                },
+                Aggregation {
+                    name: "customers",
+                    function: AggregateFunction::CountDistinct("customer"),
+                },
+                Aggregation {
+                    name: "median",
+                    function: AggregateFunction::Percentile("amount", 0.5),
+                },
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    let eu = rows
+        .iter()
+        .find(|row| row.keys == vec![("region".to_string(), Value::from("eu"))])
+        .unwrap();
+    assert_eq!(eu.count, 3);
+    assert_eq!(aggregate(eu, "total"), &Value::F64(60.0));
+    assert!(matches!(aggregate(eu, "items"), Value::I64(8)));
+    assert_eq!(aggregate(eu, "average"), &Value::F64(20.0));
+    assert_eq!(aggregate(eu, "smallest"), &Value::F64(10.0));
+    assert_eq!(aggregate(eu, "largest"), &Value::F64(30.0));
+    assert!(matches!(aggregate(eu, "customers"), Value::I64(2)));
+    assert_eq!(aggregate(eu, "median"), &Value::F64(20.0));
+
+    let us = rows
+        .iter()
+        .find(|row| row.keys == vec![("region".to_string(), Value::from("us"))])
+        .unwrap();
+    assert_eq!(aggregate(us, "median"), &Value::F64(10.0));
+}
+
+#[test]
+fn test_aggregate_by_with_collects_items() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let rows = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .aggregate_by_with(
+            &["region".to_string(), "customer".to_string()],
+            &[Aggregation {
+                name: "amounts",
+                function: AggregateFunction::Collect("amount"),
+            }],
+        )
+        .unwrap();
+
+    assert_eq!(rows.len(), 4);
+    let ann = rows
+        .iter()
+        .find(|row| row.row.keys[1].1 == "ann")
+        .unwrap();
+    assert_eq!(ann.items.len(), 2);
+    match aggregate(&ann.row, "amounts") {
+        Value::Array(amounts) => {
+            let mut amounts = amounts.clone();
+            amounts.sort();
+            assert_eq!(amounts, vec![Value::F64(10.0), Value::F64(30.0)]);
+        }
+        other => panic!("unexpected amounts {other:?}"),
+    }
+
+    let json = sonic_rs::to_string(&ann).unwrap();
+    assert!(json.contains("\"customer\":\"ann\""));
+    assert!(json.contains("\"count\":2"));
+    assert!(json.contains("\"items\":["));
+}
@@ -2,11 +2,16 @@ pub mod count_tests;
 pub mod drop_tests;
 pub mod edge_traversal_tests;
 pub mod filter_tests;
+pub mod graph_algorithm_tests;
+pub mod group_by_tests;
 pub mod node_traversal_tests;
+pub mod out_n_tests;
+pub mod pagination_tests;
 pub mod range_tests;
 pub mod secondary_index_tests;
 pub mod shortest_path_tests;
 pub mod test_utils;
 pub mod update_tests;
+pub mod upsert_tests;
 pub mod util_tests;
 pub mod vector_traversal_tests;
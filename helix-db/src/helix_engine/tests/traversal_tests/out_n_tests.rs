@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            ops::{
+                g::G,
+                out::out_n::OutNAdapter,
+                source::{
+                    add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter,
+                    n_from_type::NFromTypeAdapter,
+                },
+            },
+            traversal_value::TraversalValue,
+        },
+    },
+    props,
+    protocol::value::Value,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Builds `a -> b -> c -> d` with a shortcut `a -> c` and a cycle `d -> a`,
+/// returning the node ids in order
+fn setup_chain(storage: &Arc<HelixGraphStorage>) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ids = ["a", "b", "c", "d"]
+        .iter()
+        .map(|name| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("person", props_option(&arena, props!("name" => *name)), None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect::<Vec<_>>();
+    for (from, to) in [(0, 1), (1, 2), (2, 3), (0, 2), (3, 0)] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge("knows", None, ids[from], ids[to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    ids
+}
+
+fn names(values: &[TraversalValue]) -> Vec<String> {
+    let mut names = values
+        .iter()
+        .map(|value| match value.get_property("name") {
+            Some(Value::String(name)) => name.clone(),
+            other => panic!("unexpected name {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_out_n_emits_distinct_nodes_within_range() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = setup_chain(&storage);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let reached = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n("knows", 1, 3, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // the cycle back to `a` is never followed
+    assert_eq!(names(&reached), vec!["b", "c", "d"]);
+
+    let second_hop = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n("knows", 2, 2, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // `c` is first reached at depth 1 through the shortcut
+    assert_eq!(names(&second_hop), vec!["d"]);
+
+    let with_start = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n("knows", 0, 1, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(names(&with_start), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_out_n_emits_shared_neighbour_once() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    // `x -> s` and `y -> s`, with `x -> y` so one source also reaches the other
+    let mut add = |label: &'static str, name: &str| {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_n(label, props_option(&arena, props!("name" => name)), None)
+            .collect_to_obj()
+            .unwrap()
+            .id()
+    };
+    let (x, y, shared) = (add("source", "x"), add("source", "y"), add("person", "s"));
+    for (from, to) in [(x, shared), (y, shared), (x, y)] {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_edge("knows", None, from, to, false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let reached = G::new(&storage, &txn, &arena)
+        .n_from_type("source")
+        .out_n("knows", 0, 2, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(names(&reached), vec!["s", "x", "y"]);
+}
+
+#[test]
+fn test_out_n_expands_overlapping_start_nodes_independently() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    // `a -> b -> c -> d`, where `a` and `b` are both start nodes
+    let mut add = |label: &'static str, name: &str| {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_n(label, props_option(&arena, props!("name" => name)), None)
+            .collect_to_obj()
+            .unwrap()
+            .id()
+    };
+    let ids = [
+        add("source", "a"),
+        add("source", "b"),
+        add("person", "c"),
+        add("person", "d"),
+    ];
+    for (from, to) in [(0, 1), (1, 2), (2, 3)] {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_edge("knows", None, ids[from], ids[to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let reached = G::new(&storage, &txn, &arena)
+        .n_from_type("source")
+        .out_n("knows", 1, 2, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // `d` is two hops from `b` even though `a` already reached `b` and `c`
+    assert_eq!(names(&reached), vec!["b", "c", "d"]);
+
+    let second_hop = G::new(&storage, &txn, &arena)
+        .n_from_type("source")
+        .out_n("knows", 2, 2, false)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // `c` is two hops from `a` and `d` two hops from `b`
+    assert_eq!(names(&second_hop), vec!["c", "d"]);
+
+}
+
+#[test]
+fn test_out_n_emits_simple_paths() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = setup_chain(&storage);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let paths = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n("knows", 1, 4, true)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut lengths = paths
+        .iter()
+        .map(|path| match path {
+            TraversalValue::Path((nodes, edges)) => {
+                assert_eq!(nodes.len(), edges.len() + 1);
+                assert_eq!(nodes[0].id, ids[0]);
+                edges.len()
+            }
+            other => panic!("expected path, got {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    lengths.sort();
+    // a-b, a-c, a-b-c, a-c-d, a-b-c-d
+    assert_eq!(lengths, vec![1, 1, 2, 2, 3]);
+}
+
+#[test]
+fn test_out_n_until_stops_expansion() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = setup_chain(&storage);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let reached = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n_until("knows", 1, 3, false, |val, _txn| {
+            Ok(matches!(val, Ok(v) if v.get_property("name") == Some(&Value::from("c"))))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(names(&reached), vec!["c"]);
+
+    let paths = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_n_until("knows", 1, 3, true, |val, _txn| {
+            Ok(matches!(val, Ok(v) if v.get_property("name") == Some(&Value::from("c"))))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // a-c and a-b-c, neither continues on to `d`
+    assert_eq!(paths.len(), 2);
+}
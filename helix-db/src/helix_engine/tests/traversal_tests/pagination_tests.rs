@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            in_::in_e::InEdgesAdapter,
+            out::{out::OutAdapter, out_e::OutEdgesAdapter},
+            source::{
+                add_e::AddEAdapter, add_n::AddNAdapter, e_from_type::EFromTypeAdapter,
+                n_from_id::NFromIdAdapter, n_from_index::NFromIndexAdapter,
+                n_from_type::NFromTypeAdapter,
+            },
+            util::{
+                drop::Drop,
+                paginate::{PaginateAdapter, decode_cursor, encode_cursor},
+                range::RangeAdapter,
+            },
+        },
+        types::GraphError,
+    },
+    props,
+    protocol::value::Value,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["group".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Adds a hub node following five users of the indexed group "a", returning the hub id
+fn setup_graph(storage: &Arc<HelixGraphStorage>) -> u128 {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let hub = G::new_mut(storage, &arena, &mut txn)
+        .add_n("hub", None, None)
+        .collect_to_obj()
+        .unwrap()
+        .id();
+    for i in 0..5 {
+        let user = G::new_mut(storage, &arena, &mut txn)
+            .add_n(
+                "user",
+                props_option(&arena, props!("index" => i, "group" => "a")),
+                Some(&["group"]),
+            )
+            .collect_to_obj()
+            .unwrap()
+            .id();
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge("follows", None, hub, user, false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    hub
+}
+
+fn cursor_of(value: Value) -> Option<u128> {
+    match value {
+        Value::String(cursor) => decode_cursor(&cursor).unwrap(),
+        Value::Empty => None,
+        other => panic!("unexpected cursor {other:?}"),
+    }
+}
+
+#[test]
+fn test_cursor_round_trip() {
+    let id = 0x1234_u128 << 64 | 42;
+    assert_eq!(decode_cursor(&encode_cursor(id)).unwrap(), Some(id));
+    assert_eq!(decode_cursor("").unwrap(), None);
+    assert!(matches!(
+        decode_cursor("not-a-cursor"),
+        Err(GraphError::DecodeError(_))
+    ));
+}
+
+#[test]
+fn test_label_scan_pages() {
+    let (_temp_dir, storage) = setup_test_db();
+    setup_graph(&storage);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let all = G::new(&storage, &txn, &arena)
+        .n_from_type("user")
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(all.len(), 5);
+
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_type_after("user", cursor)
+            .range(0, 2)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        if page.is_empty() {
+            break;
+        }
+        seen.extend(page.iter().map(|item| item.id()));
+        cursor = cursor_of(G::from_iter(&storage, &txn, page.into_iter(), &arena).next_cursor());
+    }
+    assert_eq!(seen, all);
+
+    let edges = G::new(&storage, &txn, &arena)
+        .e_from_type("follows")
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    let rest = G::new(&storage, &txn, &arena)
+        .e_from_type_after("follows", Some(edges[1]))
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(rest, edges[2..]);
+}
+
+/// Ids of the users followed by `hub`, in ascending order
+fn following(storage: &Arc<HelixGraphStorage>, hub: u128) -> Vec<u128> {
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let mut following = G::new(storage, &txn, &arena)
+        .n_from_id(&hub)
+        .out_node("follows")
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    following.sort();
+    following
+}
+
+#[test]
+fn test_adjacency_pages() {
+    let (_temp_dir, storage) = setup_test_db();
+    let hub = setup_graph(&storage);
+    let following = following(&storage, hub);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let first = G::new(&storage, &txn, &arena)
+        .n_from_id(&hub)
+        .out_node_after("follows", None)
+        .range(0, 3)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        first.iter().map(|item| item.id()).collect::<Vec<_>>(),
+        following[..3]
+    );
+    let cursor = cursor_of(G::from_iter(&storage, &txn, first.into_iter(), &arena).next_cursor());
+    assert_eq!(cursor, Some(following[2]));
+
+    let rest = G::new(&storage, &txn, &arena)
+        .n_from_id(&hub)
+        .out_node_after("follows", cursor)
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(rest, following[3..]);
+
+    let mut edges = G::new(&storage, &txn, &arena)
+        .e_from_type("follows")
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    edges.sort();
+    let out_edges = G::new(&storage, &txn, &arena)
+        .n_from_id(&hub)
+        .out_e_after("follows", Some(edges[1]))
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(out_edges, edges[2..]);
+    // the edges of every user are merged into one ascending page
+    let in_edges = G::new(&storage, &txn, &arena)
+        .n_from_type("user")
+        .in_e_after("follows", Some(edges[1]))
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(in_edges, edges[2..]);
+
+    let empty = G::new(&storage, &txn, &arena)
+        .n_from_id(&hub)
+        .out_node_after("follows", following.last().copied())
+        .next_cursor();
+    assert_eq!(empty, Value::Empty);
+}
+
+#[test]
+fn test_index_scan_pages() {
+    let (_temp_dir, storage) = setup_test_db();
+    let hub = setup_graph(&storage);
+    let following = following(&storage, hub);
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+
+    let group = "a".to_string();
+    let rest = G::new(&storage, &txn, &arena)
+        .n_from_index_after("user", "group", &group, Some(following[1]))
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(rest, following[2..]);
+}
+
+#[test]
+fn test_removed_cursor_resumes_at_next_id() {
+    let (_temp_dir, storage) = setup_test_db();
+    let hub = setup_graph(&storage);
+    let following = following(&storage, hub);
+    let removed = following[2];
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let node = G::new(&storage, &txn, &arena)
+        .n_from_id(&removed)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    drop(txn);
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    Drop::drop_traversal(node.into_iter().map(Ok), storage.as_ref(), &mut txn).unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let group = "a".to_string();
+    let pages = [
+        G::new(&storage, &txn, &arena)
+            .n_from_type("user")
+            .after(Some(removed))
+            .map(|item| item.unwrap().id())
+            .collect::<Vec<_>>(),
+        G::new(&storage, &txn, &arena)
+            .n_from_type_after("user", Some(removed))
+            .map(|item| item.unwrap().id())
+            .collect::<Vec<_>>(),
+        G::new(&storage, &txn, &arena)
+            .n_from_index_after("user", "group", &group, Some(removed))
+            .map(|item| item.unwrap().id())
+            .collect::<Vec<_>>(),
+        G::new(&storage, &txn, &arena)
+            .n_from_id(&hub)
+            .out_node_after("follows", Some(removed))
+            .map(|item| item.unwrap().id())
+            .collect::<Vec<_>>(),
+    ];
+    for page in pages {
+        assert_eq!(page, following[3..]);
+    }
+}
@@ -11,7 +11,9 @@ use crate::{
             ops::{
                 g::G,
                 source::{
-                    add_n::AddNAdapter, n_from_id::NFromIdAdapter, n_from_index::NFromIndexAdapter,
+                    add_e::AddEAdapter, add_n::AddNAdapter, e_between::EBetweenAdapter,
+                    e_from_index::EFromIndexAdapter, n_from_id::NFromIdAdapter,
+                    n_from_index::NFromIndexAdapter,
                 },
                 util::{drop::Drop, update::UpdateAdapter},
             },
@@ -32,6 +34,16 @@ fn setup_indexed_db() -> (TempDir, Arc<HelixGraphStorage>) {
     (temp_dir, Arc::new(storage))
 }
 
+fn setup_edge_indexed_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().edge_secondary_indices =
+        Some(vec!["since".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
 fn to_result_iter(
     values: Vec<TraversalValue>,
 ) -> impl Iterator<Item = Result<TraversalValue, GraphError>> {
@@ -133,3 +145,202 @@ fn test_update_of_secondary_indices() {
         .collect::<Result<Vec<_>,_>>().unwrap();
     assert!(john_nodes.is_empty());
 }
+
+#[test]
+fn test_update_indexes_property_the_node_did_not_have() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let unnamed = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props! { "age" => 30 }), Some(&["name"]))
+        .collect_to_obj().unwrap();
+    let john = G::new_mut(&storage, &arena, &mut txn)
+        .add_n(
+            "person",
+            props_option(&arena, props! { "name" => "John" }),
+            Some(&["name"]),
+        )
+        .collect_to_obj().unwrap();
+    let (unnamed_id, john_id) = (unnamed.id(), john.id());
+
+    // both nodes end up sharing the value
+    G::new_mut_from_iter(&storage, &mut txn, std::iter::once(unnamed), &arena)
+        .update(&[("name", Value::from("John"))])
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let mut ids = G::new(&storage, &txn, &arena)
+        .n_from_index("person", "name", &"John".to_string())
+        .map(|node| node.unwrap().id())
+        .collect::<Vec<_>>();
+    ids.sort();
+    let mut expected = vec![unnamed_id, john_id];
+    expected.sort();
+    assert_eq!(ids, expected);
+    drop(txn);
+
+    // renaming one of them only moves its own entry
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut_from_iter(&storage, &mut txn, std::iter::once(john), &arena)
+        .update(&[("name", Value::from("Jane"))])
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let johns = G::new(&storage, &txn, &arena)
+        .n_from_index("person", "name", &"John".to_string())
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert_eq!(johns.len(), 1);
+    assert_eq!(johns[0].id(), unnamed_id);
+    let janes = G::new(&storage, &txn, &arena)
+        .n_from_index("person", "name", &"Jane".to_string())
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert_eq!(janes.len(), 1);
+    assert_eq!(janes[0].id(), john_id);
+}
+
+#[test]
+fn test_edge_secondary_index_lifecycle() {
+    let (_temp_dir, storage) = setup_edge_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let edge = G::new_mut(&storage, &arena, &mut txn)
+        .add_edge(
+            "follows",
+            props_option(&arena, props! { "since" => 2020 }),
+            a.id(),
+            b.id(),
+            false,
+        )
+        .collect_to_obj().unwrap();
+    let edge_id = edge.id();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::new(&storage, &txn, &arena)
+        .e_from_index("follows", "since", &2020)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].id(), edge_id);
+
+    let other_label = G::new(&storage, &txn, &arena)
+        .e_from_index("likes", "since", &2020)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(other_label.is_empty());
+    drop(txn);
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let edge = G::new_mut_from_iter(&storage, &mut txn, std::iter::once(edge), &arena)
+        .update(&[("since", Value::from(2021))])
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let old = G::new(&storage, &txn, &arena)
+        .e_from_index("follows", "since", &2020)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(old.is_empty());
+    let new = G::new(&storage, &txn, &arena)
+        .e_from_index("follows", "since", &2021)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert_eq!(new.len(), 1);
+    drop(txn);
+
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    Drop::drop_traversal(to_result_iter(vec![edge]), storage.as_ref(), &mut txn).unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::new(&storage, &txn, &arena)
+        .e_from_index("follows", "since", &2021)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(edges.is_empty());
+}
+
+#[test]
+fn test_edge_index_cleared_when_node_dropped() {
+    let (_temp_dir, storage) = setup_edge_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge(
+            "follows",
+            props_option(&arena, props! { "since" => 2020 }),
+            a.id(),
+            b.id(),
+            false,
+        )
+        .collect_to_obj().unwrap();
+    Drop::drop_traversal(to_result_iter(vec![a]), storage.as_ref(), &mut txn).unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::new(&storage, &txn, &arena)
+        .e_from_index("follows", "since", &2020)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(edges.is_empty());
+}
+
+#[test]
+fn test_e_between() {
+    let (_temp_dir, storage) = setup_edge_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let c = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", None, None)
+        .collect_to_obj().unwrap();
+    let edge = G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("follows", None, a.id(), b.id(), false)
+        .collect_to_obj().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("follows", None, a.id(), c.id(), false)
+        .collect_to_obj().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("likes", None, a.id(), b.id(), false)
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::new(&storage, &txn, &arena)
+        .e_between("follows", a.id(), b.id())
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].id(), edge.id());
+
+    let reversed = G::new(&storage, &txn, &arena)
+        .e_between("follows", b.id(), a.id())
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(reversed.is_empty());
+}
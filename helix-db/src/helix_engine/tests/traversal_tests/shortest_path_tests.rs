@@ -11,12 +11,16 @@ use crate::{
             ops::{
                 g::G,
                 source::{add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter},
-                util::paths::{PathAlgorithm, ShortestPathAdapter, default_weight_fn},
+                util::{
+                    paths::{PathAlgorithm, ShortestPathAdapter, default_weight_fn},
+                    shortest_paths_from::{ShortestPathsFromAdapter, hop_count_weight_fn},
+                },
             },
             traversal_value::TraversalValue,
         },
     },
     props,
+    protocol::value::Value,
 };
 
 fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
@@ -905,3 +909,214 @@ fn test_astar_custom_weight_and_heuristic() {
         panic!("expected path");
     }
 }
+
+/// Builds a weighted road graph with three routes from `start` to `end`:
+/// `start -> end` (10), `start -> a -> end` (2 + 2) and `start -> a -> b -> end` (2 + 1 + 2)
+fn setup_routes(storage: &Arc<HelixGraphStorage>) -> (u128, u128) {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ids: Vec<_> = ["start", "a", "b", "end"]
+        .into_iter()
+        .map(|name| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("city", props_option(&arena, props!("name" => name)), None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    for (from, to, weight) in [(0, 3, 10.0), (0, 1, 2.0), (1, 3, 2.0), (1, 2, 1.0), (2, 3, 2.0)] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge(
+                "road",
+                props_option(&arena, props!("weight" => weight)),
+                ids[from],
+                ids[to],
+                false,
+            )
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    (ids[0], ids[3])
+}
+
+fn path_lengths(paths: &[TraversalValue]) -> Vec<usize> {
+    paths
+        .iter()
+        .map(|path| match path {
+            TraversalValue::Path((nodes, edges)) => {
+                assert_eq!(nodes.len(), edges.len() + 1);
+                edges.len()
+            }
+            _ => panic!("expected path"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_k_shortest_paths_ordered_by_weight() {
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, end) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let paths = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .k_shortest_paths(Some("road"), None, Some(&end), 3, default_weight_fn)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    // 4.0, 5.0 and then the direct road at 10.0
+    assert_eq!(path_lengths(&paths), vec![2, 3, 1]);
+
+    let fewer = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .k_shortest_paths(Some("road"), None, Some(&end), 10, default_weight_fn)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(fewer.len(), 3);
+}
+
+#[test]
+fn test_k_shortest_paths_negative_k() {
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, end) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    // `k` comes from a parameter, so it is only checked when the query runs
+    let k: i64 = -1;
+    let mut paths = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .k_shortest_paths(Some("road"), None, Some(&end), k, default_weight_fn);
+    assert!(matches!(
+        paths.next(),
+        Some(Err(crate::helix_engine::types::GraphError::TraversalError(message)))
+            if message.contains("non-negative")
+    ));
+    assert!(paths.next().is_none());
+}
+
+#[test]
+fn test_all_paths_bounded_by_depth() {
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, end) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let paths = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .all_paths(Some("road"), None, Some(&end), 3)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(path_lengths(&paths), vec![1, 2, 3]);
+
+    let short = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .all_paths(Some("road"), None, Some(&end), 2)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(path_lengths(&short), vec![1, 2]);
+
+    let none = G::new(&storage, &txn, &arena)
+        .n_from_id(&end)
+        .all_paths(Some("road"), None, Some(&start), 3)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+fn names_and_distances(nodes: &[TraversalValue]) -> Vec<(String, f64)> {
+    nodes
+        .iter()
+        .map(|node| match (node.get_property("name"), node.get_property("distance")) {
+            (Some(Value::String(name)), Some(Value::F64(distance))) => (name.clone(), *distance),
+            _ => panic!("expected a node with a name and a distance"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_shortest_paths_from_weighted_within_radius() {
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, _) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let reached = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .shortest_paths_from(Some("road"), Some(3.0), default_weight_fn)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(
+        names_and_distances(&reached),
+        vec![("a".to_string(), 2.0), ("b".to_string(), 3.0)]
+    );
+}
+
+#[test]
+fn test_shortest_paths_from_invalid_weights() {
+    use crate::{
+        helix_engine::types::GraphError,
+        utils::items::{Edge, Node},
+    };
+
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, _) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let is_rejected = |result: Option<Result<TraversalValue, GraphError>>| {
+        matches!(
+            result,
+            Some(Err(GraphError::TraversalError(message))) if message.contains("non-negative")
+        )
+    };
+
+    let nan_weight = |_: &Edge, _: &Node, _: &Node| Ok(f64::NAN);
+    let mut reached = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .shortest_paths_from(Some("road"), None, nan_weight);
+    assert!(is_rejected(reached.next()));
+
+    let negative_weight = |_: &Edge, _: &Node, _: &Node| Ok(-1.0);
+    let mut reached = G::new(&storage, &txn, &arena)
+        .n_from_id(&start)
+        .shortest_paths_from(Some("road"), None, negative_weight);
+    assert!(is_rejected(reached.next()));
+
+    for max_distance in [f64::NAN, -1.0] {
+        let mut reached = G::new(&storage, &txn, &arena)
+            .n_from_id(&start)
+            .shortest_paths_from(Some("road"), Some(max_distance), default_weight_fn);
+        assert!(is_rejected(reached.next()));
+    }
+}
+
+#[test]
+fn test_shortest_paths_from_hop_count() {
+    let (_temp_dir, storage) = setup_test_db();
+    let (start, _) = setup_routes(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let mut reached = names_and_distances(
+        &G::new(&storage, &txn, &arena)
+            .n_from_id(&start)
+            .shortest_paths_from(Some("road"), None, hop_count_weight_fn)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+    );
+    reached.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        reached,
+        vec![
+            ("a".to_string(), 1.0),
+            ("b".to_string(), 2.0),
+            ("end".to_string(), 1.0)
+        ]
+    );
+}
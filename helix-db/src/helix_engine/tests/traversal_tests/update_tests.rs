@@ -16,7 +16,7 @@ use crate::{
             traversal_value::TraversalValue,
         },
     },
-    protocol::value::Value,
+    protocol::{functions, value::Value},
     props,
 };
 
@@ -77,3 +77,39 @@ fn test_update_node() {
         other => panic!("unexpected traversal value: {other:?}"),
     }
 }
+
+#[test]
+fn test_update_node_with_own_properties() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let tags = Value::Array(vec![Value::from("a")]);
+    let node = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props!("tags" => tags)), None)
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let traversal = G::new(&storage, &txn, &arena)
+        .n_from_id(&node.id())
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    G::new_mut_from_iter(&storage, &mut txn, traversal.into_iter(), &arena)
+        .update_with(|val| {
+            let tags = val.get_property("tags").unwrap_or(&Value::Empty);
+            vec![("tags", functions::append(tags, &Value::from("b")))]
+        })
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let updated = G::new(&storage, &txn, &arena)
+        .n_from_id(&node.id())
+        .collect_to_obj().unwrap();
+    assert_eq!(
+        updated.get_property("tags"),
+        Some(&Value::Array(vec![Value::from("a"), Value::from("b")]))
+    );
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            out::out_e::OutEdgesAdapter,
+            source::{
+                add_n::AddNAdapter, n_from_id::NFromIdAdapter, n_from_index::NFromIndexAdapter,
+                upsert_e::UpsertEAdapter, upsert_n::UpsertNAdapter,
+            },
+        },
+    },
+    props,
+    protocol::value::Value,
+};
+
+fn setup_indexed_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["email".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+#[test]
+fn test_upsert_n_inserts_then_updates() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let key = Value::from("a@example.com");
+    let first = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            "email",
+            &key,
+            props_option(
+                &arena,
+                props! { "email" => "a@example.com", "name" => "Alice" },
+            ),
+            &[("name", Value::from("Alice"))],
+            Some(&["email"]),
+        )
+        .unwrap();
+    assert!(first.created);
+
+    let second = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            "email",
+            &key,
+            props_option(
+                &arena,
+                props! { "email" => "a@example.com", "name" => "Alicia" },
+            ),
+            &[("name", Value::from("Alicia"))],
+            Some(&["email"]),
+        )
+        .unwrap();
+    assert!(!second.created);
+    assert_eq!(second.value.id(), first.value.id());
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let users = G::new(&storage, &txn, &arena)
+        .n_from_index("user", "email", &"a@example.com".to_string())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].get_property("name"), Some(&Value::from("Alicia")));
+}
+
+#[test]
+fn test_upsert_n_ignores_other_labels() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_n(
+            "admin",
+            props_option(&arena, props! { "email" => "a@example.com" }),
+            Some(&["email"]),
+        )
+        .collect_to_obj()
+        .unwrap();
+
+    let upserted = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            "email",
+            &Value::from("a@example.com"),
+            props_option(&arena, props! { "email" => "a@example.com" }),
+            &[],
+            Some(&["email"]),
+        )
+        .unwrap();
+    assert!(upserted.created);
+    assert_eq!(upserted.value.label(), "user");
+}
+
+#[test]
+fn test_upsert_e_inserts_then_updates() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+
+    let first = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e(
+            "follows",
+            props_option(&arena, props! { "weight" => 1.0 }),
+            &[("weight", Value::from(1.0))],
+            a.id(),
+            b.id(),
+        )
+        .unwrap();
+    assert!(first.created);
+
+    let second = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e(
+            "follows",
+            props_option(&arena, props! { "weight" => 2.0 }),
+            &[("weight", Value::from(2.0))],
+            a.id(),
+            b.id(),
+        )
+        .unwrap();
+    assert!(!second.created);
+    assert_eq!(second.value.id(), first.value.id());
+
+    let reverse = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e("follows", None, &[], b.id(), a.id())
+        .unwrap();
+    assert!(reverse.created);
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let out = G::new(&storage, &txn, &arena)
+        .n_from_id(&a.id())
+        .out_e("follows")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].get_property("weight"), Some(&Value::from(2.0)));
+}
@@ -13,7 +13,11 @@ use crate::{
                     add_n::AddNAdapter,
                     n_from_type::NFromTypeAdapter,
                 },
-                util::{dedup::DedupAdapter, order::OrderByAdapter},
+                util::{
+                    dedup::DedupAdapter,
+                    order::{OrderByAdapter, SortKey},
+                    range::RangeAdapter,
+                },
                 vectors::{insert::InsertVAdapter, search::SearchVAdapter},
             },
         },
@@ -271,6 +275,100 @@ fn test_order_vector_by_desc() {
     assert_eq!(traversal[2].id(), vector3.id());
 }
 
+#[test]
+fn test_order_by_multiple_keys() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ann = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props! { "last" => "smith", "age" => 30 }), None)
+        .collect_to_obj().unwrap();
+
+    let bob = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props! { "last" => "smith", "age" => 40 }), None)
+        .collect_to_obj().unwrap();
+
+    let cat = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props! { "last" => "jones", "age" => 20 }), None)
+        .collect_to_obj().unwrap();
+
+    let dan = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("person", props_option(&arena, props! { "last" => "smith" }), None)
+        .collect_to_obj().unwrap();
+
+    txn.commit().unwrap();
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let traversal = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&[SortKey::asc("last"), SortKey::desc("age")])
+        .collect::<Result<Vec<_>,_>>().unwrap();
+
+    let ids = traversal.iter().map(|item| item.id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec![cat.id(), bob.id(), ann.id(), dan.id()]);
+
+    let traversal = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&[SortKey::asc("last"), SortKey::desc("age").nulls_first()])
+        .collect::<Result<Vec<_>,_>>().unwrap();
+
+    let ids = traversal.iter().map(|item| item.id()).collect::<Vec<_>>();
+    assert_eq!(ids, vec![cat.id(), dan.id(), bob.id(), ann.id()]);
+}
+
+#[test]
+fn test_order_by_top_k() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    for age in [42, 7, 19, 88, 3, 61, 19, 25] {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_n("person", props_option(&arena, props! { "age" => age }), None)
+            .collect_to_obj().unwrap();
+    }
+
+    txn.commit().unwrap();
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let keys = [SortKey::desc("age")];
+    let sorted = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&keys)
+        .range(1, 4)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    let top_k = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by_top_k(&keys, 4)
+        .range(1, 4)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+
+    assert_eq!(top_k.len(), 3);
+    assert_eq!(
+        top_k.iter().map(|item| item.id()).collect::<Vec<_>>(),
+        sorted.iter().map(|item| item.id()).collect::<Vec<_>>()
+    );
+
+    let none = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by_top_k(&keys, 0)
+        .collect::<Result<Vec<_>,_>>().unwrap();
+    assert!(none.is_empty());
+
+    // `k` comes from a parameter, so it is only checked when the query runs
+    let k: i64 = -1;
+    let mut negative = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by_top_k(&keys, k);
+    assert!(matches!(
+        negative.next(),
+        Some(Err(crate::helix_engine::types::GraphError::TraversalError(message)))
+            if message.contains("non-negative")
+    ));
+    assert!(negative.next().is_none());
+}
+
 #[test]
 fn test_dedup() {
     let (_temp_dir, storage) = setup_test_db();
@@ -4,26 +4,34 @@ use bumpalo::Bump;
 use heed3::RoTxn;
 use tempfile::TempDir;
 
+use super::test_utils::props_option;
 use crate::{
     helix_engine::{
         storage_core::HelixGraphStorage,
-        traversal_core::ops::{
-            g::G,
-            in_::to_v::ToVAdapter,
-            out::{out::OutAdapter, out_e::OutEdgesAdapter},
-            source::{
-                add_e::AddEAdapter, add_n::AddNAdapter, e_from_type::EFromTypeAdapter,
-                n_from_id::NFromIdAdapter, v_from_id::VFromIdAdapter, v_from_type::VFromTypeAdapter,
-            },
-            util::drop::Drop,
-            vectors::{
-                brute_force_search::BruteForceSearchVAdapter, insert::InsertVAdapter,
-                search::SearchVAdapter,
+        traversal_core::{
+            ops::{
+                g::G,
+                in_::to_v::ToVAdapter,
+                out::{out::OutAdapter, out_e::OutEdgesAdapter},
+                source::{
+                    add_e::AddEAdapter, add_n::AddNAdapter, e_from_type::EFromTypeAdapter,
+                    n_from_id::NFromIdAdapter, v_from_id::VFromIdAdapter,
+                    v_from_type::VFromTypeAdapter,
+                },
+                util::drop::Drop,
+                vectors::{
+                    brute_force_search::BruteForceSearchVAdapter, insert::InsertVAdapter,
+                    owner_n::OwnerNAdapter, search::SearchVAdapter,
+                    set_embedding::SetEmbeddingAdapter,
+                },
             },
+            traversal_value::TraversalValue,
         },
         types::GraphError,
         vector_core::vector::HVector,
     },
+    props,
+    protocol::value::Value,
     utils::properties::ImmutablePropertiesMap,
 };
 
@@ -41,6 +49,22 @@ fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
     (temp_dir, Arc::new(storage))
 }
 
+fn setup_embedding_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["embedding".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+fn embedding_id(node: &TraversalValue) -> u128 {
+    match node.get_property("embedding") {
+        Some(Value::Id(id)) => id.inner(),
+        other => panic!("expected an embedded vector id, got {other:?}"),
+    }
+}
+
 #[test]
 fn test_insert_and_fetch_vector() {
     let (_temp_dir, storage) = setup_test_db();
@@ -867,3 +891,132 @@ fn test_v_from_id_with_max_id() {
         result
     );
 }
+
+#[test]
+fn test_set_embedding_and_owner_n() {
+    let (_temp_dir, storage) = setup_embedding_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let doc = G::new_mut(&storage, &arena, &mut txn)
+        .add_n(
+            "doc",
+            props_option(&arena, props! { "title" => "first" }),
+            Some(&["embedding"]),
+        )
+        .set_embedding::<Filter>("embedding", "doc_emb", &[1.0, 0.0, 0.0])
+        .collect_to_obj()
+        .unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_n(
+            "doc",
+            props_option(&arena, props! { "title" => "second" }),
+            Some(&["embedding"]),
+        )
+        .set_embedding::<Filter>("embedding", "doc_emb", &[0.0, 1.0, 0.0])
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+    let first_vector = embedding_id(&doc);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let results = G::new(&storage, &txn, &arena)
+        .search_v::<Filter, _>(&[1.0, 0.0, 0.0], 1, "doc_emb", None)
+        .owner_n("doc", "embedding")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id(), doc.id());
+    assert!(matches!(
+        results[0].get_property("score"),
+        Some(Value::F64(_))
+    ));
+
+    // a different node label is not an owner
+    let results = G::new(&storage, &txn, &arena)
+        .search_v::<Filter, _>(&[1.0, 0.0, 0.0], 2, "doc_emb", None)
+        .owner_n("person", "embedding")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(results.is_empty());
+    drop(txn);
+
+    // re-embedding replaces the previous vector
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let current = G::new(&storage, &txn, &arena)
+        .n_from_id(&doc.id())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let doc = G::new_mut_from_iter(&storage, &mut txn, current.into_iter(), &arena)
+        .set_embedding::<Filter>("embedding", "doc_emb", &[0.0, 0.0, 1.0])
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+    assert_ne!(embedding_id(&doc), first_vector);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    assert!(
+        G::new(&storage, &txn, &arena)
+            .v_from_id(&first_vector, false)
+            .collect_to_obj()
+            .is_err()
+    );
+    let results = G::new(&storage, &txn, &arena)
+        .search_v::<Filter, _>(&[0.0, 0.0, 1.0], 1, "doc_emb", None)
+        .owner_n("doc", "embedding")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id(), doc.id());
+}
+
+#[test]
+fn test_drop_traversal_with_embeddings() {
+    let (_temp_dir, storage) = setup_embedding_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let doc = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("doc", None, Some(&["embedding"]))
+        .set_embedding::<Filter>("embedding", "doc_emb", &[1.0, 2.0, 3.0])
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+    let vector_id = embedding_id(&doc);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let docs = G::new(&storage, &txn, &arena)
+        .n_from_id(&doc.id())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    drop(txn);
+
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    Drop::drop_traversal_with_embeddings(
+        docs.into_iter().map(Ok::<_, GraphError>),
+        &["embedding"],
+        storage.as_ref(),
+        &mut txn,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    assert!(
+        G::new(&storage, &txn, &arena)
+            .n_from_id(&doc.id())
+            .collect_to_obj()
+            .is_err()
+    );
+    assert!(matches!(
+        G::new(&storage, &txn, &arena)
+            .v_from_id(&vector_id, false)
+            .collect_to_obj(),
+        Err(GraphError::VectorError(_))
+    ));
+}
@@ -1,6 +1,6 @@
 use crate::{
     helix_engine::types::GraphError,
-    helixc::analyzer::{INTROSPECTION_DATA, SECONDARY_INDICES},
+    helixc::analyzer::{EDGE_SECONDARY_INDICES, INTROSPECTION_DATA, SECONDARY_INDICES},
 };
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
@@ -25,6 +25,7 @@ impl Default for VectorConfig {
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GraphConfig {
     pub secondary_indices: Option<Vec<String>>,
+    pub edge_secondary_indices: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -37,6 +38,12 @@ pub struct Config {
     pub schema: Option<String>,
     pub embedding_model: Option<String>,
     pub graphvis_node_label: Option<String>,
+    /// Serves the `interpret` endpoint running ad-hoc queries against the graph
+    pub interpret: Option<bool>,
+    /// Allows queries sent to the `interpret` endpoint to write to the graph
+    pub interpret_writes: Option<bool>,
+    /// Serves the `import` endpoint bulk-loading rows into the graph
+    pub import: Option<bool>,
 }
 
 impl Config {
@@ -59,6 +66,7 @@ impl Config {
             }),
             graph_config: Some(GraphConfig {
                 secondary_indices: None,
+                edge_secondary_indices: None,
             }),
             db_max_size_gb: Some(db_max_size_gb),
             mcp: Some(mcp),
@@ -66,6 +74,9 @@ impl Config {
             schema,
             embedding_model,
             graphvis_node_label,
+            interpret: None,
+            interpret_writes: None,
+            import: None,
         }
     }
 
@@ -112,7 +123,8 @@ impl Config {
 		"ef_search": 768
 	},
 	"graph_config": {
-		"secondary_indices": []
+		"secondary_indices": [],
+		"edge_secondary_indices": []
 	},
 	"db_max_size_gb": 10,
 	"mcp": true,
@@ -149,6 +161,18 @@ impl Config {
         self.bm25.unwrap_or(true)
     }
 
+    pub fn get_interpret(&self) -> bool {
+        self.interpret.unwrap_or(false)
+    }
+
+    pub fn get_interpret_writes(&self) -> bool {
+        self.interpret_writes.unwrap_or(false)
+    }
+
+    pub fn get_import(&self) -> bool {
+        self.import.unwrap_or(false)
+    }
+
     pub fn get_schema(&self) -> Option<String> {
         self.schema.clone()
     }
@@ -164,6 +188,7 @@ impl Default for Config {
             }),
             graph_config: Some(GraphConfig {
                 secondary_indices: None,
+                edge_secondary_indices: None,
             }),
             db_max_size_gb: Some(10),
             mcp: Some(true),
@@ -171,6 +196,9 @@ impl Default for Config {
             schema: None,
             embedding_model: Some("text-embedding-ada-002".to_string()),
             graphvis_node_label: None,
+            interpret: None,
+            interpret_writes: None,
+            import: None,
         }
     }
 }
@@ -212,19 +240,12 @@ impl fmt::Display for Config {
         writeln!(
             f,
             "secondary_indices: {},",
-            match SECONDARY_INDICES.get() {
-                Some(indices) => {
-                    format!(
-                        "Some(vec![{}])",
-                        indices
-                            .iter()
-                            .map(|i| format!("\"{i}\".to_string()"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    )
-                }
-                None => "None".to_string(),
-            }
+            write_indices(SECONDARY_INDICES.get())
+        )?;
+        writeln!(
+            f,
+            "edge_secondary_indices: {},",
+            write_indices(EDGE_SECONDARY_INDICES.get())
         )?;
         writeln!(f, "}}),")?;
         writeln!(
@@ -257,8 +278,35 @@ impl fmt::Display for Config {
                 None => "None".to_string(),
             }
         )?;
+        writeln!(
+            f,
+            "interpret: Some({}),",
+            self.interpret.unwrap_or(false)
+        )?;
+        writeln!(
+            f,
+            "interpret_writes: Some({}),",
+            self.interpret_writes.unwrap_or(false)
+        )?;
+        writeln!(f, "import: Some({}),", self.import.unwrap_or(false))?;
         writeln!(f, "}})")?;
         writeln!(f, "}}")?;
         Ok(())
     }
 }
+
+fn write_indices(indices: Option<&Vec<String>>) -> String {
+    match indices {
+        Some(indices) => {
+            format!(
+                "Some(vec![{}])",
+                indices
+                    .iter()
+                    .map(|i| format!("\"{i}\".to_string()"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        None => "None".to_string(),
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
     helix_engine::{
         storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
-        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        traversal_core::{
+            ops::util::paginate::{Adjacent, adjacent_ids_after},
+            traversal_iter::RoTraversalIterator,
+            traversal_value::TraversalValue,
+        },
         types::GraphError,
     },
     utils::label_hash::hash_label,
@@ -25,6 +29,19 @@ pub trait InEdgesAdapter<'db, 'arena, 'txn, 's, I>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Same as `in_e`, but returns the edges in ascending id order starting right after the
+    /// edge with id `after`, so a page can pick up where the previous one ended
+    fn in_e_after(
+        self,
+        edge_label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -95,4 +112,41 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
             inner: iter,
         }
     }
+
+    #[inline]
+    fn in_e_after(
+        self,
+        edge_label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let (ids, error) = match adjacent_ids_after(
+            &self.storage.in_edges_db,
+            self.txn,
+            self.inner,
+            HelixGraphStorage::in_edge_key,
+            edge_label,
+            Adjacent::Edge,
+            after,
+        ) {
+            Ok(ids) => (ids, None),
+            Err(e) => (Vec::new(), Some(Err(e))),
+        };
+        let iter = error.into_iter().chain(ids.into_iter().map(move |id| {
+            self.storage
+                .get_edge(self.txn, &id, self.arena)
+                .map(TraversalValue::Edge)
+        }));
+
+        RoTraversalIterator {
+            inner: iter,
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+        }
+    }
 }
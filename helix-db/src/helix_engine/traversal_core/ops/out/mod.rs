@@ -2,3 +2,4 @@ pub mod from_n;
 pub mod from_v;
 pub mod out;
 pub mod out_e;
+pub mod out_n;
@@ -0,0 +1,256 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    utils::{
+        items::{Edge, Node},
+        label_hash::hash_label,
+    },
+};
+use heed3::RoTxn;
+use std::collections::{HashSet, VecDeque};
+
+/// Condition checked against every reached node to decide where the expansion stops
+pub type UntilFn<'arena> =
+    fn(&Result<TraversalValue<'arena>, GraphError>, &RoTxn) -> Result<bool, GraphError>;
+
+pub struct OutNIterator<'db, 'arena, 'txn, I, F> {
+    iter: I,
+    storage: &'db HelixGraphStorage,
+    txn: &'txn RoTxn<'db>,
+    arena: &'arena bumpalo::Bump,
+    label_hash: [u8; 4],
+    min_depth: usize,
+    max_depth: usize,
+    emit_paths: bool,
+    until: Option<F>,
+    /// Nodes already emitted, so a node reached from several start nodes is returned once
+    emitted: HashSet<u128>,
+    buffer: VecDeque<TraversalValue<'arena>>,
+}
+
+impl<'db, 'arena, 'txn, I, F> Iterator for OutNIterator<'db, 'arena, 'txn, I, F>
+where
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    F: Fn(&Result<TraversalValue<'arena>, GraphError>, &RoTxn) -> Result<bool, GraphError>,
+{
+    type Item = Result<TraversalValue<'arena>, GraphError>;
+
+    /// Expands each incoming node in full before yielding what it reached
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.buffer.pop_front() {
+                return Some(Ok(value));
+            }
+            match self.iter.next()? {
+                Ok(TraversalValue::Node(node)) => {
+                    let expanded = match self.emit_paths {
+                        true => self.expand_paths(node),
+                        false => self.expand_nodes(node),
+                    };
+                    if let Err(e) = expanded {
+                        return Some(Err(e));
+                    }
+                }
+                other => return Some(other),
+            }
+        }
+    }
+}
+
+impl<'db, 'arena, 'txn, I, F> OutNIterator<'db, 'arena, 'txn, I, F>
+where
+    F: Fn(&Result<TraversalValue<'arena>, GraphError>, &RoTxn) -> Result<bool, GraphError>,
+{
+    fn neighbours(&self, node_id: u128) -> Result<Vec<(u128, u128)>, GraphError> {
+        self.storage
+            .out_neighbours(self.txn, node_id, Some(&self.label_hash))
+    }
+
+    fn reached_until(&self, node: &Node<'arena>) -> Result<bool, GraphError> {
+        match &self.until {
+            Some(until) => until(&Ok(TraversalValue::Node(*node)), self.txn),
+            None => Ok(false),
+        }
+    }
+
+    /// Without an `UNTIL` condition every node within the depth range is emitted,
+    /// otherwise only the nodes that satisfy it are
+    fn should_emit(&self, depth: usize, reached_until: bool) -> bool {
+        depth >= self.min_depth && (self.until.is_none() || reached_until)
+    }
+
+    /// Breadth first expansion from a single start node, where each node is emitted
+    /// at the depth it is first reached from that start and at most once across all
+    /// the start nodes
+    fn expand_nodes(&mut self, start: Node<'arena>) -> Result<(), GraphError> {
+        let mut visited = HashSet::from([start.id]);
+        let stop = self.reached_until(&start)?;
+        if self.should_emit(0, stop) && self.emitted.insert(start.id) {
+            self.buffer.push_back(TraversalValue::Node(start));
+        }
+        if stop {
+            return Ok(());
+        }
+
+        let mut frontier = vec![start.id];
+        for depth in 1..=self.max_depth {
+            let mut next = Vec::new();
+            for node_id in frontier {
+                for (_, to_node) in self.neighbours(node_id)? {
+                    if !visited.insert(to_node) {
+                        continue;
+                    }
+                    let node = self.storage.get_node(self.txn, &to_node, self.arena)?;
+                    let stop = self.reached_until(&node)?;
+                    if self.should_emit(depth, stop) && self.emitted.insert(to_node) {
+                        self.buffer.push_back(TraversalValue::Node(node));
+                    }
+                    if !stop {
+                        next.push(to_node);
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        Ok(())
+    }
+
+    /// Breadth first expansion over simple paths, so no node appears twice in a path
+    fn expand_paths(&mut self, start: Node<'arena>) -> Result<(), GraphError> {
+        let mut queue: VecDeque<(Vec<Node<'arena>>, Vec<Edge<'arena>>)> = VecDeque::new();
+        queue.push_back((vec![start], Vec::new()));
+
+        while let Some((nodes, edges)) = queue.pop_front() {
+            let depth = edges.len();
+            let last = nodes[nodes.len() - 1];
+            let stop = self.reached_until(&last)?;
+            if self.should_emit(depth, stop) {
+                self.buffer
+                    .push_back(TraversalValue::Path((nodes.clone(), edges.clone())));
+            }
+            if stop || depth == self.max_depth {
+                continue;
+            }
+            for (edge_id, to_node) in self.neighbours(last.id)? {
+                if nodes.iter().any(|n| n.id == to_node) {
+                    continue;
+                }
+                let mut next_nodes = nodes.clone();
+                next_nodes.push(self.storage.get_node(self.txn, &to_node, self.arena)?);
+                let mut next_edges = edges.clone();
+                next_edges.push(self.storage.get_edge(self.txn, &edge_id, self.arena)?);
+                queue.push_back((next_nodes, next_edges));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub trait OutNAdapter<'db, 'arena, 'txn, I>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Follows outgoing `edge_label` edges between `min_depth` and `max_depth` hops
+    /// from each node, never revisiting a node.
+    ///
+    /// With `emit_paths` the full paths are returned instead of the distinct nodes
+    /// that were reached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let friends_of_friends = G::new(storage, &txn, &arena)
+    ///     .n_from_id(&user_id)
+    ///     .out_n("knows", 2, 2, false);
+    /// ```
+    fn out_n(
+        self,
+        edge_label: &'arena str,
+        min_depth: usize,
+        max_depth: usize,
+        emit_paths: bool,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OutNIterator<'db, 'arena, 'txn, I, UntilFn<'arena>>>;
+
+    /// Same as `out_n`, but stops expanding at the nodes for which `until` holds and
+    /// only emits those nodes (or the paths ending at them)
+    fn out_n_until<F>(
+        self,
+        edge_label: &'arena str,
+        min_depth: usize,
+        max_depth: usize,
+        emit_paths: bool,
+        until: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OutNIterator<'db, 'arena, 'txn, I, F>>
+    where
+        F: Fn(&Result<TraversalValue<'arena>, GraphError>, &RoTxn) -> Result<bool, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    OutNAdapter<'db, 'arena, 'txn, I> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn out_n(
+        self,
+        edge_label: &'arena str,
+        min_depth: usize,
+        max_depth: usize,
+        emit_paths: bool,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OutNIterator<'db, 'arena, 'txn, I, UntilFn<'arena>>>
+    {
+        RoTraversalIterator {
+            arena: self.arena,
+            inner: OutNIterator {
+                iter: self.inner,
+                storage: self.storage,
+                txn: self.txn,
+                arena: self.arena,
+                label_hash: hash_label(edge_label, None),
+                min_depth,
+                max_depth,
+                emit_paths,
+                until: None,
+                emitted: HashSet::new(),
+                buffer: VecDeque::new(),
+            },
+            storage: self.storage,
+            txn: self.txn,
+        }
+    }
+
+    #[inline]
+    fn out_n_until<F>(
+        self,
+        edge_label: &'arena str,
+        min_depth: usize,
+        max_depth: usize,
+        emit_paths: bool,
+        until: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OutNIterator<'db, 'arena, 'txn, I, F>>
+    where
+        F: Fn(&Result<TraversalValue<'arena>, GraphError>, &RoTxn) -> Result<bool, GraphError>,
+    {
+        RoTraversalIterator {
+            arena: self.arena,
+            inner: OutNIterator {
+                iter: self.inner,
+                storage: self.storage,
+                txn: self.txn,
+                arena: self.arena,
+                label_hash: hash_label(edge_label, None),
+                min_depth,
+                max_depth,
+                emit_paths,
+                until: Some(until),
+                emitted: HashSet::new(),
+                buffer: VecDeque::new(),
+            },
+            storage: self.storage,
+            txn: self.txn,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    utils::label_hash::hash_label,
+};
+
+pub trait EBetweenAdapter<'db, 'arena, 'txn, 's>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns an iterator containing the `label` edges going from `from_node` to `to_node`.
+    ///
+    /// The edges are read from the out edge entry of `from_node` for `label`, so only the
+    /// adjacency of that label is scanned and only the matching edges are decoded.
+    fn e_between(
+        self,
+        label: &'s str,
+        from_node: u128,
+        to_node: u128,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    EBetweenAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn e_between(
+        self,
+        label: &'s str,
+        from_node: u128,
+        to_node: u128,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let out_key = HelixGraphStorage::out_edge_key(&from_node, &hash_label(label, None));
+        let edges = match self.storage.out_edges_db.get_duplicates(self.txn, &out_key) {
+            Ok(Some(iter)) => iter
+                .filter_map(|item| {
+                    let (edge_id, node_id) = match item {
+                        Ok((_, data)) => match HelixGraphStorage::unpack_adj_edge_data(data) {
+                            Ok(ids) => ids,
+                            Err(e) => return Some(Err(e)),
+                        },
+                        Err(e) => return Some(Err(GraphError::from(e))),
+                    };
+                    (node_id == to_node).then(|| {
+                        self.storage
+                            .get_edge(self.txn, &edge_id, self.arena)
+                            .map(TraversalValue::Edge)
+                    })
+                })
+                .collect(),
+            Ok(None) => Vec::new(),
+            Err(e) => vec![Err(GraphError::from(e))],
+        };
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: edges.into_iter(),
+        }
+    }
+}
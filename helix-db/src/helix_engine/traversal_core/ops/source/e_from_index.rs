@@ -0,0 +1,149 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    protocol::value::Value,
+};
+use heed3::RoTxn;
+use serde::Serialize;
+
+/// Reads the `label` edges stored under `key` in the edge index `index` with an id greater
+/// than `after`. The ids of a key are sorted, so they are compared before any edge is read.
+fn index_edges<'arena, K: Into<Value> + Serialize + Clone>(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    arena: &'arena bumpalo::Bump,
+    label: &str,
+    index: &str,
+    key: &K,
+    after: Option<u128>,
+) -> Vec<Result<TraversalValue<'arena>, GraphError>> {
+    let edge_ids = match storage.edge_secondary_indices.get(index) {
+        Some(db) => bincode::serialize(&Value::from(key))
+            .map_err(GraphError::from)
+            .and_then(|key| {
+                let ids = match db.get_duplicates(txn, &key)? {
+                    Some(ids) => ids.map(|item| item.map(|(_, id)| id)).collect(),
+                    None => Ok(Vec::new()),
+                };
+                ids.map_err(GraphError::from)
+            }),
+        None => Err(GraphError::New(format!(
+            "Edge Secondary Index {index} not found"
+        ))),
+    };
+
+    match edge_ids {
+        Ok(ids) => ids
+            .into_iter()
+            .filter(|id| after.is_none_or(|after| *id > after))
+            .filter_map(|id| match storage.get_edge(txn, &id, arena) {
+                Ok(edge) if edge.label == label => Some(Ok(TraversalValue::Edge(edge))),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect(),
+        Err(e) => vec![Err(e)],
+    }
+}
+
+pub trait EFromIndexAdapter<'db, 'arena, 'txn, 's, K: Into<Value> + Serialize>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns an iterator containing the `label` edges whose `index` property equals `key`,
+    /// read from the edge secondary index of that property.
+    ///
+    /// The index must be an existing edge secondary index and the key should match its type.
+    fn e_from_index(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &'s K,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone;
+
+    /// Same as `e_from_index`, but starts right after the edge with id `after`
+    fn e_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &'s K,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone;
+}
+
+impl<
+    'db,
+    'arena,
+    'txn,
+    's,
+    K: Into<Value> + Serialize,
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+> EFromIndexAdapter<'db, 'arena, 'txn, 's, K> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn e_from_index(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &K,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone,
+    {
+        let iter = index_edges(self.storage, self.txn, self.arena, label, index, key, None);
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: iter.into_iter(),
+        }
+    }
+
+    #[inline]
+    fn e_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &K,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone,
+    {
+        let iter = index_edges(self.storage, self.txn, self.arena, label, index, key, after);
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: iter.into_iter(),
+        }
+    }
+}
@@ -8,21 +8,19 @@ use crate::{
     },
     utils::items::Edge,
 };
-use heed3::{
-    byteorder::BE,
-    types::{Bytes, U128},
-};
+use heed3::types::{Bytes, Lazy};
+use std::ops::Bound;
 
-pub struct EFromType<'arena, 'txn, 's>
-where
-    'arena: 'txn,
-{
+pub struct EFromType<'arena, 's, I> {
     pub arena: &'arena bumpalo::Bump,
-    pub iter: heed3::RoIter<'txn, U128<BE>, heed3::types::LazyDecode<Bytes>>,
+    pub iter: I,
     pub label: &'s [u8],
 }
 
-impl<'arena, 'txn, 's> Iterator for EFromType<'arena, 'txn, 's> {
+impl<'arena, 'txn, 's, I> Iterator for EFromType<'arena, 's, I>
+where
+    I: Iterator<Item = heed3::Result<(u128, Lazy<'txn, Bytes>)>>,
+{
     type Item = Result<TraversalValue<'arena>, GraphError>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -80,6 +78,19 @@ pub trait EFromTypeAdapter<'db, 'arena, 'txn, 's>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Same as `e_from_type`, but starts right after the edge with id `after`, so a
+    /// page can pick up where the previous one ended without rescanning it
+    fn e_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     EFromTypeAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
@@ -111,4 +122,34 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
             },
         }
     }
+
+    #[inline]
+    fn e_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let iter = self
+            .storage
+            .edges_db
+            .lazily_decode_data()
+            .range(self.txn, &(start, Bound::Unbounded))
+            .unwrap();
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: EFromType {
+                arena: self.arena,
+                iter,
+                label: label.as_bytes(),
+            },
+        }
+    }
 }
@@ -5,6 +5,7 @@ use crate::{
     },
     protocol::value::Value, utils::items::Node,
 };
+use super::n_from_type::node_with_label;
 use serde::Serialize;
 
 pub trait NFromIndexAdapter<'db, 'arena, 'txn, 's, K: Into<Value> + Serialize>:
@@ -32,6 +33,25 @@ pub trait NFromIndexAdapter<'db, 'arena, 'txn, 's, K: Into<Value> + Serialize>:
     >
     where
         K: Into<Value> + Serialize + Clone;
+
+    /// Same as `n_from_index`, but starts right after the node with id `after`.
+    ///
+    /// The node ids stored under a key are sorted, so the ids before the cursor are
+    /// skipped without reading their nodes.
+    fn n_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &'s K,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone;
 }
 
 impl<
@@ -118,4 +138,48 @@ impl<
             inner: res,
         }
     }
+
+    #[inline]
+    fn n_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &K,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone,
+    {
+        let db = self
+            .storage
+            .secondary_indices
+            .get(index)
+            .ok_or(GraphError::New(format!(
+                "Secondary Index {index} not found"
+            )))
+            .unwrap();
+        let res = db
+            .prefix_iter(self.txn, &bincode::serialize(&Value::from(key)).unwrap())
+            .unwrap()
+            .filter_map(move |item| {
+                let (_, node_id) = item.ok()?;
+                if after.is_some_and(|after| node_id <= after) {
+                    return None;
+                }
+                let value = self.storage.nodes_db.get(self.txn, &node_id).ok()??;
+                node_with_label(node_id, value, label, self.arena)
+            });
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: res,
+        }
+    }
 }
@@ -8,6 +8,44 @@ use crate::{
     },
     utils::items::Node,
 };
+use std::ops::Bound;
+
+/// Decodes the node stored under `id` if it has the given label
+pub(super) fn node_with_label<'arena>(
+    id: u128,
+    value: &[u8],
+    label: &str,
+    arena: &'arena bumpalo::Bump,
+) -> Option<Result<TraversalValue<'arena>, GraphError>> {
+    assert!(
+        value.len() >= LMDB_STRING_HEADER_LENGTH,
+        "value length does not contain header which means the `label` field was missing from the node on insertion"
+    );
+    let length_of_label_in_lmdb =
+        u64::from_le_bytes(value[..LMDB_STRING_HEADER_LENGTH].try_into().unwrap()) as usize;
+
+    if length_of_label_in_lmdb != label.len() {
+        return None;
+    }
+
+    assert!(
+        value.len() >= length_of_label_in_lmdb + LMDB_STRING_HEADER_LENGTH,
+        "value length is not at least the header length plus the label length meaning there has been a corruption on node insertion"
+    );
+    let label_in_lmdb =
+        &value[LMDB_STRING_HEADER_LENGTH..LMDB_STRING_HEADER_LENGTH + length_of_label_in_lmdb];
+
+    if label_in_lmdb != label.as_bytes() {
+        return None;
+    }
+    match Node::<'arena>::from_bincode_bytes(id, value, arena) {
+        Ok(node) => Some(Ok(TraversalValue::Node(node))),
+        Err(e) => {
+            println!("{} Error decoding node: {:?}", line!(), e);
+            Some(Err(GraphError::ConversionError(e.to_string())))
+        }
+    }
+}
 
 pub trait NFromTypeAdapter<'db, 'arena, 'txn, 's>:
     Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
@@ -35,6 +73,19 @@ pub trait NFromTypeAdapter<'db, 'arena, 'txn, 's>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Same as `n_from_type`, but starts right after the node with id `after`, so a
+    /// page can pick up where the previous one ended without rescanning it
+    fn n_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     NFromTypeAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
@@ -49,44 +100,45 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
-        let label_as_bytes = label.as_bytes();
-        let iter = self.storage.nodes_db.iter(self.txn).unwrap().filter_map(move |item| {
-            if let Ok((id, value)) = item {
-                assert!(
-                    value.len() >= LMDB_STRING_HEADER_LENGTH,
-                    "value length does not contain header which means the `label` field was missing from the node on insertion"
-                );
-                let length_of_label_in_lmdb =
-                    u64::from_le_bytes(value[..LMDB_STRING_HEADER_LENGTH].try_into().unwrap()) as usize;
-    
-                if length_of_label_in_lmdb != label.len() {
-                    return None;
-                }
-    
-                assert!(
-                    value.len() >= length_of_label_in_lmdb + LMDB_STRING_HEADER_LENGTH,
-                    "value length is not at least the header length plus the label length meaning there has been a corruption on node insertion"
-                );
-                let label_in_lmdb = &value[LMDB_STRING_HEADER_LENGTH
-                    ..LMDB_STRING_HEADER_LENGTH + length_of_label_in_lmdb];
-    
-                if label_in_lmdb == label_as_bytes {
-                    match Node::<'arena>::from_bincode_bytes(id, value, self.arena) {
-                        Ok(node) => {
-                            return Some(Ok(TraversalValue::Node(node)));
-                        }
-                        Err(e) => {
-                            println!("{} Error decoding node: {:?}", line!(), e);
-                            return Some(Err(GraphError::ConversionError(e.to_string())));
-                        }
-                    }
-                } else {
-                    return None;
-                }
-            }
-            None
+        let iter = self
+            .storage
+            .nodes_db
+            .iter(self.txn)
+            .unwrap()
+            .filter_map(move |item| match item {
+                Ok((id, value)) => node_with_label(id, value, label, self.arena),
+                Err(_) => None,
+            }); // should be handled because label may be variable in the future
 
-        }); // should be handled because label may be variable in the future
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: iter,
+        }
+    }
+
+    #[inline]
+    fn n_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let iter = self
+            .storage
+            .nodes_db
+            .range(self.txn, &(start, Bound::Unbounded))
+            .unwrap()
+            .filter_map(move |item| match item {
+                Ok((id, value)) => node_with_label(id, value, label, self.arena),
+                Err(_) => None,
+            });
 
         RoTraversalIterator {
             storage: self.storage,
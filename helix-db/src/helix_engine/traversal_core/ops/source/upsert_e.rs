@@ -0,0 +1,88 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            ops::{
+                source::{add_e::AddEAdapter, upsert_n::Upserted},
+                util::update::UpdateAdapter,
+            },
+            traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{label_hash::hash_label, properties::ImmutablePropertiesMap},
+};
+
+pub trait UpsertEAdapter<'db, 'arena, 'txn, 's>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Finds the `label` edge from `from_node` to `to_node` through the out edge
+    /// adjacency table and updates it with `update_props`, or inserts a new edge with
+    /// `properties` if there is none.
+    fn upsert_e(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        update_props: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> Result<Upserted<'arena>, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    UpsertEAdapter<'db, 'arena, 'txn, 's> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn upsert_e(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        update_props: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> Result<Upserted<'arena>, GraphError> {
+        let out_key = HelixGraphStorage::out_edge_key(&from_node, &hash_label(label, None));
+        let mut existing = None;
+        if let Some(edges) = self.storage.out_edges_db.get_duplicates(self.txn, &out_key)? {
+            for item in edges {
+                let (_, data) = item?;
+                let (edge_id, node_id) = HelixGraphStorage::unpack_adj_edge_data(data)?;
+                if node_id == to_node {
+                    existing = Some(self.storage.get_edge(self.txn, &edge_id, self.arena)?);
+                    break;
+                }
+            }
+        }
+
+        match existing {
+            Some(edge) if update_props.is_empty() => Ok(Upserted {
+                value: TraversalValue::Edge(edge),
+                created: false,
+            }),
+            Some(edge) => {
+                let value = RwTraversalIterator::new(
+                    self.storage,
+                    self.txn,
+                    self.arena,
+                    std::iter::once(Ok(TraversalValue::Edge(edge))),
+                )
+                .update(update_props)
+                .collect_to_obj()?;
+                Ok(Upserted {
+                    value,
+                    created: false,
+                })
+            }
+            None => {
+                let value = self
+                    .add_edge(label, properties, from_node, to_node, false)
+                    .collect_to_obj()?;
+                Ok(Upserted {
+                    value,
+                    created: true,
+                })
+            }
+        }
+    }
+}
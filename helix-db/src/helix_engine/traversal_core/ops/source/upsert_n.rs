@@ -0,0 +1,102 @@
+use crate::{
+    helix_engine::{
+        storage_core::storage_methods::StorageMethods,
+        traversal_core::{
+            ops::{source::add_n::AddNAdapter, util::update::UpdateAdapter},
+            traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::properties::ImmutablePropertiesMap,
+};
+
+/// The item written by an upsert and whether it was inserted rather than updated.
+pub struct Upserted<'arena> {
+    pub value: TraversalValue<'arena>,
+    pub created: bool,
+}
+
+pub trait UpsertNAdapter<'db, 'arena, 'txn, 's>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Finds the `label` node whose secondary `index` holds `key` and updates it with
+    /// `update_props`, or inserts a new node with `properties` if there is none.
+    ///
+    /// The lookup and the write share the caller's write transaction, so no other
+    /// writer can insert the same key in between.
+    fn upsert_n(
+        self,
+        label: &'arena str,
+        index: &'s str,
+        key: &Value,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        update_props: &[(&'static str, Value)],
+        secondary_indices: Option<&'s [&str]>,
+    ) -> Result<Upserted<'arena>, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    UpsertNAdapter<'db, 'arena, 'txn, 's> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn upsert_n(
+        self,
+        label: &'arena str,
+        index: &'s str,
+        key: &Value,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        update_props: &[(&'static str, Value)],
+        secondary_indices: Option<&'s [&str]>,
+    ) -> Result<Upserted<'arena>, GraphError> {
+        let db = self
+            .storage
+            .secondary_indices
+            .get(index)
+            .ok_or_else(|| GraphError::New(format!("Secondary Index {index} not found")))?;
+
+        // the index is shared by every label that has the field, so check the label
+        let key = bincode::serialize(key)?;
+        let mut existing = None;
+        if let Some(node_ids) = db.get_duplicates(self.txn, &key)? {
+            for item in node_ids {
+                let (_, node_id) = item?;
+                let node = self.storage.get_node(self.txn, &node_id, self.arena)?;
+                if node.label == label {
+                    existing = Some(node);
+                    break;
+                }
+            }
+        }
+
+        match existing {
+            Some(node) if update_props.is_empty() => Ok(Upserted {
+                value: TraversalValue::Node(node),
+                created: false,
+            }),
+            Some(node) => {
+                let value = RwTraversalIterator::new(
+                    self.storage,
+                    self.txn,
+                    self.arena,
+                    std::iter::once(Ok(TraversalValue::Node(node))),
+                )
+                .update(update_props)
+                .collect_to_obj()?;
+                Ok(Upserted {
+                    value,
+                    created: false,
+                })
+            }
+            None => {
+                let value = self
+                    .add_n(label, properties, secondary_indices)
+                    .collect_to_obj()?;
+                Ok(Upserted {
+                    value,
+                    created: true,
+                })
+            }
+        }
+    }
+}
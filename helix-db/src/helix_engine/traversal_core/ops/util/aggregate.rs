@@ -2,10 +2,16 @@ use std::collections::HashMap;
 
 use crate::{
     helix_engine::{
-        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        traversal_core::{
+            ops::util::group_by::group_rows, traversal_iter::RoTraversalIterator,
+            traversal_value::TraversalValue,
+        },
         types::GraphError,
     },
-    utils::aggregate::{Aggregate, AggregateItem},
+    utils::{
+        aggregate::{Aggregate, AggregateItem, AggregateRow},
+        group_by::Aggregation,
+    },
 };
 
 pub trait AggregateAdapter<'arena>: Iterator {
@@ -14,6 +20,13 @@ pub trait AggregateAdapter<'arena>: Iterator {
         properties: &[String],
         should_count: bool,
     ) -> Result<Aggregate<'arena>, GraphError>;
+
+    /// Same as `group_by_with`, but each group also keeps the items that fell into it
+    fn aggregate_by_with(
+        self,
+        properties: &[String],
+        aggregations: &[Aggregation],
+    ) -> Result<Vec<AggregateRow<'arena>>, GraphError>;
 }
 
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -56,4 +69,20 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             Ok(Aggregate::Group(groups))
         }
     }
+
+    fn aggregate_by_with(
+        self,
+        properties: &[String],
+        aggregations: &[Aggregation],
+    ) -> Result<Vec<AggregateRow<'arena>>, GraphError> {
+        Ok(group_rows(
+            self.inner,
+            properties,
+            aggregations,
+            |items: &mut Vec<_>, item| items.push(item),
+        )?
+        .into_iter()
+        .map(|(row, items)| AggregateRow { row, items })
+        .collect())
+    }
 }
@@ -4,6 +4,7 @@ use crate::helix_engine::{
     traversal_core::traversal_value::TraversalValue,
     types::GraphError,
 };
+use crate::protocol::value::Value;
 use heed3::RwTxn;
 
 pub struct Drop<I> {
@@ -56,4 +57,25 @@ where
             },
         )
     }
+
+    /// Drops the traversal along with the vectors its nodes are embedded with
+    /// under the `embeddings` properties.
+    pub fn drop_traversal_with_embeddings(
+        iter: I,
+        embeddings: &[&str],
+        storage: &'db HelixGraphStorage,
+        txn: &'txn mut RwTxn<'db>,
+    ) -> Result<(), GraphError> {
+        let items = iter.collect::<Vec<_>>();
+        for item in &items {
+            if let Ok(TraversalValue::Node(node)) = item {
+                for field in embeddings {
+                    if let Some(Value::Id(id)) = node.get_property(field) {
+                        storage.drop_vector(txn, &id.inner())?;
+                    }
+                }
+            }
+        }
+        Drop::drop_traversal(items.into_iter(), storage, txn)
+    }
 }
@@ -0,0 +1,416 @@
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{items::Node, label_hash::hash_label},
+};
+use heed3::RoTxn;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Property under which each algorithm returns its result on every node
+pub const SCORE_PROPERTY: &str = "score";
+
+/// The analytics algorithms that can be run over the nodes of a traversal
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphAlgorithm {
+    /// PageRank with the given damping factor and number of iterations
+    PageRank { damping: f64, iterations: usize },
+    /// Components of the graph when edge direction is ignored
+    WeaklyConnectedComponents,
+    /// Components in which every node can reach every other node
+    StronglyConnectedComponents,
+    /// Community detection that stops after `iterations` rounds or once labels settle
+    LabelPropagation { iterations: usize },
+    /// Number of triangles each node is part of, ignoring edge direction
+    TriangleCount,
+    /// Betweenness centrality estimated from `samples` evenly spread source nodes,
+    /// or computed exactly when `samples` is 0 or covers every node
+    Betweenness { samples: usize },
+}
+
+/// The nodes of a traversal and the edges between them, indexed by position
+struct Subgraph<'arena> {
+    nodes: Vec<Node<'arena>>,
+    out: Vec<Vec<usize>>,
+    inc: Vec<Vec<usize>>,
+}
+
+impl<'arena> Subgraph<'arena> {
+    /// Loads the edges with one of `edge_labels` (or any label when empty) whose
+    /// endpoints are both among `nodes`
+    fn load(
+        storage: &HelixGraphStorage,
+        txn: &RoTxn,
+        nodes: Vec<Node<'arena>>,
+        edge_labels: &[&str],
+    ) -> Result<Self, GraphError> {
+        let index: HashMap<u128, usize> =
+            nodes.iter().enumerate().map(|(i, node)| (node.id, i)).collect();
+        let mut out = vec![Vec::new(); nodes.len()];
+        let mut inc = vec![Vec::new(); nodes.len()];
+
+        for (from, node) in nodes.iter().enumerate() {
+            let prefixes = match edge_labels.is_empty() {
+                true => vec![node.id.to_be_bytes().to_vec()],
+                false => edge_labels
+                    .iter()
+                    .map(|label| {
+                        HelixGraphStorage::out_edge_key(&node.id, &hash_label(label, None)).to_vec()
+                    })
+                    .collect(),
+            };
+            for prefix in prefixes {
+                for result in storage.out_edges_db.prefix_iter(txn, &prefix)? {
+                    let (_, value) = result?;
+                    let (_, to_node) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+                    if let Some(&to) = index.get(&to_node) {
+                        out[from].push(to);
+                        inc[to].push(from);
+                    }
+                }
+            }
+        }
+
+        Ok(Self { nodes, out, inc })
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Distinct neighbours of each node when edge direction is ignored, without self loops
+    fn undirected(&self) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|i| {
+                let mut neighbours: Vec<usize> = self.out[i]
+                    .iter()
+                    .chain(self.inc[i].iter())
+                    .copied()
+                    .filter(|&j| j != i)
+                    .collect();
+                neighbours.sort_unstable();
+                neighbours.dedup();
+                neighbours
+            })
+            .collect()
+    }
+
+    fn page_rank(&self, damping: f64, iterations: usize) -> Vec<f64> {
+        let n = self.len() as f64;
+        let mut ranks = vec![1.0 / n; self.len()];
+        for _ in 0..iterations {
+            // Nodes without outgoing edges spread their rank over every node
+            let dangling: f64 = (0..self.len())
+                .filter(|&i| self.out[i].is_empty())
+                .map(|i| ranks[i])
+                .sum();
+            let mut next = vec![(1.0 - damping) / n + damping * dangling / n; self.len()];
+            for (from, targets) in self.out.iter().enumerate() {
+                let share = damping * ranks[from] / targets.len().max(1) as f64;
+                for &to in targets {
+                    next[to] += share;
+                }
+            }
+            ranks = next;
+        }
+        ranks
+    }
+
+    /// Numbers the groups of `labels` in the order they first appear
+    fn number_groups(labels: &[usize]) -> Vec<f64> {
+        let mut numbers = HashMap::new();
+        labels
+            .iter()
+            .map(|label| {
+                let next = numbers.len();
+                *numbers.entry(*label).or_insert(next) as f64
+            })
+            .collect()
+    }
+
+    fn weakly_connected_components(&self) -> Vec<f64> {
+        fn find(parents: &mut [usize], i: usize) -> usize {
+            let mut root = i;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            let mut i = i;
+            while parents[i] != root {
+                let next = parents[i];
+                parents[i] = root;
+                i = next;
+            }
+            root
+        }
+
+        let mut parents: Vec<usize> = (0..self.len()).collect();
+        for (from, targets) in self.out.iter().enumerate() {
+            for &to in targets {
+                let (a, b) = (find(&mut parents, from), find(&mut parents, to));
+                if a != b {
+                    parents[a.max(b)] = a.min(b);
+                }
+            }
+        }
+        let roots: Vec<usize> = (0..self.len()).map(|i| find(&mut parents, i)).collect();
+        Self::number_groups(&roots)
+    }
+
+    /// Tarjan's algorithm, written iteratively so deep graphs cannot overflow the stack
+    fn strongly_connected_components(&self) -> Vec<f64> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut low_link = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = vec![0; self.len()];
+        let mut next_index = 0;
+        let mut next_component = 0;
+
+        for start in 0..self.len() {
+            if index[start] != usize::MAX {
+                continue;
+            }
+            let mut work = vec![(start, 0)];
+            while let Some((node, edge)) = work.pop() {
+                if edge == 0 {
+                    index[node] = next_index;
+                    low_link[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+                if let Some(&to) = self.out[node].get(edge) {
+                    work.push((node, edge + 1));
+                    if index[to] == usize::MAX {
+                        work.push((to, 0));
+                    } else if on_stack[to] {
+                        low_link[node] = low_link[node].min(index[to]);
+                    }
+                    continue;
+                }
+                if low_link[node] == index[node] {
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        components[member] = next_component;
+                        if member == node {
+                            break;
+                        }
+                    }
+                    next_component += 1;
+                }
+                if let Some(&(parent, _)) = work.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+            }
+        }
+        Self::number_groups(&components)
+    }
+
+    /// Each node repeatedly takes the most common label among its neighbours, preferring
+    /// the smallest label on ties so results are deterministic
+    fn label_propagation(&self, iterations: usize) -> Vec<f64> {
+        let neighbours = self.undirected();
+        let mut labels: Vec<usize> = (0..self.len()).collect();
+        for _ in 0..iterations {
+            let mut changed = false;
+            for node in 0..self.len() {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for &neighbour in &neighbours[node] {
+                    *counts.entry(labels[neighbour]).or_default() += 1;
+                }
+                let best = counts
+                    .into_iter()
+                    .max_by(|(a_label, a_count), (b_label, b_count)| {
+                        a_count.cmp(b_count).then(b_label.cmp(a_label))
+                    })
+                    .map(|(label, _)| label);
+                if let Some(best) = best
+                    && best != labels[node]
+                {
+                    labels[node] = best;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        Self::number_groups(&labels)
+    }
+
+    fn triangle_count(&self) -> Vec<f64> {
+        let neighbours = self.undirected();
+        let sets: Vec<HashSet<usize>> = neighbours
+            .iter()
+            .map(|n| n.iter().copied().collect())
+            .collect();
+        neighbours
+            .iter()
+            .map(|n| {
+                let mut triangles = 0;
+                for (i, a) in n.iter().enumerate() {
+                    for b in &n[i + 1..] {
+                        if sets[*a].contains(b) {
+                            triangles += 1;
+                        }
+                    }
+                }
+                triangles as f64
+            })
+            .collect()
+    }
+
+    /// Brandes' algorithm over unweighted directed edges
+    fn betweenness(&self, samples: usize) -> Vec<f64> {
+        let n = self.len();
+        let sources: Vec<usize> = match samples {
+            0 => (0..n).collect(),
+            s if s >= n => (0..n).collect(),
+            s => (0..s).map(|i| i * n / s).collect(),
+        };
+        let mut centrality = vec![0.0; n];
+
+        for &source in &sources {
+            let mut order = Vec::with_capacity(n);
+            let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+            let mut paths = vec![0.0; n];
+            let mut depth = vec![usize::MAX; n];
+            paths[source] = 1.0;
+            depth[source] = 0;
+
+            let mut queue = VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                order.push(node);
+                for &to in &self.out[node] {
+                    if depth[to] == usize::MAX {
+                        depth[to] = depth[node] + 1;
+                        queue.push_back(to);
+                    }
+                    if depth[to] == depth[node] + 1 {
+                        paths[to] += paths[node];
+                        predecessors[to].push(node);
+                    }
+                }
+            }
+
+            let mut dependency = vec![0.0; n];
+            for &node in order.iter().rev() {
+                for &pred in &predecessors[node] {
+                    dependency[pred] += paths[pred] / paths[node] * (1.0 + dependency[node]);
+                }
+                if node != source {
+                    centrality[node] += dependency[node];
+                }
+            }
+        }
+
+        // Scale sampled estimates up to the whole graph
+        let scale = n as f64 / sources.len().max(1) as f64;
+        centrality.iter().map(|c| c * scale).collect()
+    }
+
+    fn run(&self, algorithm: &GraphAlgorithm) -> Vec<f64> {
+        match algorithm {
+            GraphAlgorithm::PageRank {
+                damping,
+                iterations,
+            } => self.page_rank(*damping, *iterations),
+            GraphAlgorithm::WeaklyConnectedComponents => self.weakly_connected_components(),
+            GraphAlgorithm::StronglyConnectedComponents => self.strongly_connected_components(),
+            GraphAlgorithm::LabelPropagation { iterations } => self.label_propagation(*iterations),
+            GraphAlgorithm::TriangleCount => self.triangle_count(),
+            GraphAlgorithm::Betweenness { samples } => self.betweenness(*samples),
+        }
+    }
+}
+
+pub trait GraphAlgorithmAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Runs `algorithm` over the graph formed by the incoming nodes and the edges
+    /// between them, returning every node with its result under the `score` property.
+    ///
+    /// Only edges with one of `edge_labels` are followed, or every edge when it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let ranked = G::new(storage, &txn, &arena)
+    ///     .n_from_type("user")
+    ///     .run_graph_algorithm(
+    ///         &["follows"],
+    ///         GraphAlgorithm::PageRank { damping: 0.85, iterations: 20 },
+    ///     );
+    /// ```
+    fn run_graph_algorithm(
+        self,
+        edge_labels: &[&str],
+        algorithm: GraphAlgorithm,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    GraphAlgorithmAdapter<'db, 'arena, 'txn> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn run_graph_algorithm(
+        self,
+        edge_labels: &[&str],
+        algorithm: GraphAlgorithm,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let arena = self.arena;
+        let (storage, txn) = (self.storage, self.txn);
+
+        let scored = (|| {
+            let mut seen = HashSet::new();
+            let mut nodes = Vec::new();
+            for item in self.inner {
+                match item? {
+                    TraversalValue::Node(node) => {
+                        if seen.insert(node.id) {
+                            nodes.push(node);
+                        }
+                    }
+                    _ => {
+                        return Err(GraphError::TraversalError(
+                            "Graph algorithms can only be run over nodes".to_string(),
+                        ));
+                    }
+                }
+            }
+            let graph = Subgraph::load(storage, txn, nodes, edge_labels)?;
+            let scores = graph.run(&algorithm);
+            Ok(graph
+                .nodes
+                .into_iter()
+                .zip(scores)
+                .map(|(node, score)| {
+                    Ok(TraversalValue::Node(node.with_property(
+                        SCORE_PROPERTY,
+                        Value::F64(score),
+                        arena,
+                    )))
+                })
+                .collect::<Vec<_>>())
+        })();
+
+        RoTraversalIterator {
+            arena,
+            storage,
+            txn,
+            inner: scored.unwrap_or_else(|e| vec![Err(e)]).into_iter(),
+        }
+    }
+}
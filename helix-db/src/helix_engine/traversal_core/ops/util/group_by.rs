@@ -3,12 +3,34 @@ use crate::{
         traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
     },
-    utils::group_by::{GroupBy, GroupByItem},
+    protocol::value::Value,
+    utils::group_by::{AggregateFunction, Aggregation, GroupBy, GroupByItem, GroupRow},
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub trait GroupByAdapter: Iterator {
     fn group_by(self, properties: &[String], should_count: bool) -> Result<GroupBy, GraphError>;
+
+    /// Groups the items by the values of `properties` and computes each aggregation
+    /// per group in a single pass.
+    ///
+    /// Groups are returned in the order they were first seen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let totals = G::new(storage, &txn, &arena)
+    ///     .n_from_type("Order")
+    ///     .group_by_with(
+    ///         &["region".to_string()],
+    ///         &[Aggregation { name: "total", function: AggregateFunction::Sum("amount") }],
+    ///     )?;
+    /// ```
+    fn group_by_with(
+        self,
+        properties: &[String],
+        aggregations: &[Aggregation],
+    ) -> Result<Vec<GroupRow>, GraphError>;
 }
 
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -48,4 +70,227 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             Ok(GroupBy::Group(groups))
         }
     }
+
+    fn group_by_with(
+        self,
+        properties: &[String],
+        aggregations: &[Aggregation],
+    ) -> Result<Vec<GroupRow>, GraphError> {
+        Ok(group_rows(self.inner, properties, aggregations, |_: &mut (), _| {})?
+            .into_iter()
+            .map(|(row, _)| row)
+            .collect())
+    }
+}
+
+/// Groups the items of `iter` and folds every aggregation as the items go by,
+/// handing each item to `on_item` along with extra state kept for its group
+pub(crate) fn group_rows<'arena, S: Default>(
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    properties: &[String],
+    aggregations: &[Aggregation],
+    mut on_item: impl FnMut(&mut S, TraversalValue<'arena>),
+) -> Result<Vec<(GroupRow, S)>, GraphError> {
+    // The key values are kept apart from their string forms so the rows stay typed
+    let mut index: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+    let mut groups: Vec<(GroupRow, Vec<Accumulator>, S)> = Vec::new();
+
+    for item in iter {
+        let item = item?;
+
+        let values = properties
+            .iter()
+            .map(|property| item.get_property(property).cloned())
+            .collect::<Vec<_>>();
+        let key = values
+            .iter()
+            .map(|value| value.as_ref().map(Value::inner_stringify))
+            .collect::<Vec<_>>();
+
+        let group_index = *index.entry(key).or_insert_with(|| {
+            let row = GroupRow {
+                keys: properties
+                    .iter()
+                    .cloned()
+                    .zip(values.into_iter().map(Option::unwrap_or_default))
+                    .collect(),
+                ..Default::default()
+            };
+            let accumulators = aggregations
+                .iter()
+                .map(|aggregation| Accumulator::new(&aggregation.function))
+                .collect();
+            groups.push((row, accumulators, S::default()));
+            groups.len() - 1
+        });
+
+        let (row, accumulators, state) = &mut groups[group_index];
+        for (accumulator, aggregation) in accumulators.iter_mut().zip(aggregations) {
+            let value = aggregation
+                .function
+                .property()
+                .and_then(|property| item.get_property(property));
+            accumulator.update(value);
+        }
+        row.count += 1;
+        on_item(state, item);
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(mut row, accumulators, state)| {
+            row.aggregates = aggregations
+                .iter()
+                .zip(accumulators)
+                .map(|(aggregation, accumulator)| {
+                    (aggregation.name.to_string(), accumulator.finish(row.count))
+                })
+                .collect();
+            (row, state)
+        })
+        .collect())
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::F64(f) => Some(*f),
+        Value::F32(f) => Some(*f as f64),
+        Value::I8(i) => Some(*i as f64),
+        Value::I16(i) => Some(*i as f64),
+        Value::I32(i) => Some(*i as f64),
+        Value::I64(i) => Some(*i as f64),
+        Value::U8(i) => Some(*i as f64),
+        Value::U16(i) => Some(*i as f64),
+        Value::U32(i) => Some(*i as f64),
+        Value::U64(i) => Some(*i as f64),
+        Value::U128(i) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::I8(i) => Some(*i as i64),
+        Value::I16(i) => Some(*i as i64),
+        Value::I32(i) => Some(*i as i64),
+        Value::I64(i) => Some(*i),
+        Value::U8(i) => Some(*i as i64),
+        Value::U16(i) => Some(*i as i64),
+        Value::U32(i) => Some(*i as i64),
+        Value::U64(i) => i64::try_from(*i).ok(),
+        Value::U128(i) => i64::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+/// Running state of one aggregation within one group.
+///
+/// Missing and non-numeric values are skipped by the numeric aggregations.
+enum Accumulator {
+    Count,
+    Distinct(HashSet<String>),
+    /// Integer sums stay integers until a float or an overflow is seen
+    Sum { int: Option<i64>, float: f64 },
+    Avg { sum: f64, count: usize },
+    Min(Option<Value>),
+    Max(Option<Value>),
+    Percentile { values: Vec<f64>, rank: f64 },
+    Collect(Vec<Value>),
+}
+
+impl Accumulator {
+    fn new(function: &AggregateFunction) -> Self {
+        match function {
+            AggregateFunction::Count => Self::Count,
+            AggregateFunction::CountDistinct(_) => Self::Distinct(HashSet::new()),
+            AggregateFunction::Sum(_) => Self::Sum {
+                int: Some(0),
+                float: 0.0,
+            },
+            AggregateFunction::Avg(_) => Self::Avg { sum: 0.0, count: 0 },
+            AggregateFunction::Min(_) => Self::Min(None),
+            AggregateFunction::Max(_) => Self::Max(None),
+            AggregateFunction::Percentile(_, rank) => Self::Percentile {
+                values: Vec::new(),
+                rank: *rank,
+            },
+            AggregateFunction::Collect(_) => Self::Collect(Vec::new()),
+        }
+    }
+
+    fn update(&mut self, value: Option<&Value>) {
+        match self {
+            Self::Count => {}
+            Self::Collect(values) => values.push(value.cloned().unwrap_or_default()),
+            _ => {
+                if let Some(value) = value.filter(|value| !matches!(value, Value::Empty)) {
+                    self.update_present(value);
+                }
+            }
+        }
+    }
+
+    fn update_present(&mut self, value: &Value) {
+        match self {
+            Self::Distinct(seen) => {
+                seen.insert(value.inner_stringify());
+            }
+            Self::Sum { int, float } => {
+                if let Some(f) = as_f64(value) {
+                    *float += f;
+                    *int = match (*int, as_i64(value)) {
+                        (Some(sum), Some(i)) => sum.checked_add(i),
+                        _ => None,
+                    };
+                }
+            }
+            Self::Avg { sum, count } => {
+                if let Some(f) = as_f64(value) {
+                    *sum += f;
+                    *count += 1;
+                }
+            }
+            Self::Min(min) => {
+                if min.as_ref().is_none_or(|min| value < min) {
+                    *min = Some(value.clone());
+                }
+            }
+            Self::Max(max) => {
+                if max.as_ref().is_none_or(|max| value > max) {
+                    *max = Some(value.clone());
+                }
+            }
+            Self::Percentile { values, .. } => {
+                if let Some(f) = as_f64(value) {
+                    values.push(f);
+                }
+            }
+            Self::Count | Self::Collect(_) => {}
+        }
+    }
+
+    fn finish(self, count: i32) -> Value {
+        match self {
+            Self::Count => Value::I64(count as i64),
+            Self::Distinct(seen) => Value::I64(seen.len() as i64),
+            Self::Sum {
+                int: Some(int), ..
+            } => Value::I64(int),
+            Self::Sum { float, .. } => Value::F64(float),
+            Self::Avg { count: 0, .. } => Value::Empty,
+            Self::Avg { sum, count } => Value::F64(sum / count as f64),
+            Self::Min(value) | Self::Max(value) => value.unwrap_or_default(),
+            Self::Percentile { mut values, rank } => {
+                if values.is_empty() {
+                    return Value::Empty;
+                }
+                values.sort_by(|a, b| a.total_cmp(b));
+                let position = rank.clamp(0.0, 1.0) * (values.len() - 1) as f64;
+                let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+                let fraction = position - lower as f64;
+                Value::F64(values[lower] + (values[upper] - values[lower]) * fraction)
+            }
+            Self::Collect(values) => Value::Array(values),
+        }
+    }
 }
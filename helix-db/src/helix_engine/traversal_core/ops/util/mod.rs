@@ -5,9 +5,12 @@ pub mod drop;
 pub mod exist;
 pub mod filter_mut;
 pub mod filter_ref;
+pub mod graph_algorithms;
 pub mod group_by;
 pub mod map;
 pub mod order;
+pub mod paginate;
 pub mod paths;
 pub mod range;
+pub mod shortest_paths_from;
 pub mod update;
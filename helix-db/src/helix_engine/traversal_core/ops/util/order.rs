@@ -1,10 +1,13 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BinaryHeap};
 
 use itertools::Itertools;
 
-use crate::helix_engine::{
-    traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
-    types::GraphError,
+use crate::{
+    helix_engine::{
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    protocol::value::Value,
 };
 
 pub struct OrderByAsc<I> {
@@ -37,7 +40,199 @@ where
     }
 }
 
+/// A property to sort by, along with its direction and where missing values go
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey<'a> {
+    pub property: &'a str,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl<'a> SortKey<'a> {
+    pub const fn asc(property: &'a str) -> Self {
+        Self {
+            property,
+            descending: false,
+            nulls_first: false,
+        }
+    }
+
+    pub const fn desc(property: &'a str) -> Self {
+        Self {
+            property,
+            descending: true,
+            nulls_first: false,
+        }
+    }
+
+    pub const fn nulls_first(self) -> Self {
+        Self {
+            nulls_first: true,
+            ..self
+        }
+    }
+
+    pub const fn nulls_last(self) -> Self {
+        Self {
+            nulls_first: false,
+            ..self
+        }
+    }
+}
+
+/// Returns the value of `property` on `item`, with plain values sorting by themselves
+fn sort_value<'v>(item: &'v TraversalValue<'_>, property: &str) -> Option<&'v Value> {
+    match item {
+        TraversalValue::Value(value) => Some(value),
+        _ => item.get_property(property),
+    }
+    .filter(|value| !matches!(value, Value::Empty))
+}
+
+fn compare_by_keys(keys: &[SortKey], a: &TraversalValue, b: &TraversalValue) -> Ordering {
+    keys.iter()
+        .map(
+            |key| match (sort_value(a, key.property), sort_value(b, key.property)) {
+                (Some(a), Some(b)) if key.descending => b.cmp(a),
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) if key.nulls_first => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) if key.nulls_first => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        )
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Heap entry ordered by the sort keys, with the traversal position breaking ties so
+/// the top-k matches what a full stable sort would return
+struct TopKEntry<'k, 'arena> {
+    keys: &'k [SortKey<'k>],
+    position: usize,
+    item: TraversalValue<'arena>,
+}
+
+impl Ord for TopKEntry<'_, '_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_by_keys(self.keys, &self.item, &other.item)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for TopKEntry<'_, '_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for TopKEntry<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for TopKEntry<'_, '_> {}
+
+pub struct OrderByKeys<'arena> {
+    iter: std::vec::IntoIter<Result<TraversalValue<'arena>, GraphError>>,
+}
+
+impl<'arena> Iterator for OrderByKeys<'arena> {
+    type Item = Result<TraversalValue<'arena>, GraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'arena> OrderByKeys<'arena> {
+    fn sorted(
+        iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+        keys: &[SortKey],
+    ) -> Self {
+        let items = match iter.collect::<Result<Vec<_>, _>>() {
+            Ok(mut items) => {
+                items.sort_by(|a, b| compare_by_keys(keys, a, b));
+                items.into_iter().map(Ok).collect()
+            }
+            Err(e) => vec![Err(e)],
+        };
+        Self {
+            iter: items.into_iter(),
+        }
+    }
+
+    /// Keeps the `k` first items in a max-heap, so only `k` items are held at once
+    fn top_k(
+        iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+        keys: &[SortKey],
+        k: usize,
+    ) -> Self {
+        let mut heap: BinaryHeap<TopKEntry> = BinaryHeap::with_capacity(k + 1);
+        for (position, item) in iter.enumerate() {
+            let item = match item {
+                Ok(item) => item,
+                Err(e) => {
+                    return Self {
+                        iter: vec![Err(e)].into_iter(),
+                    };
+                }
+            };
+            if k == 0 {
+                continue;
+            }
+            let entry = TopKEntry {
+                keys,
+                position,
+                item,
+            };
+            if heap.len() < k {
+                heap.push(entry);
+            } else if heap.peek().is_some_and(|largest| entry < *largest) {
+                heap.pop();
+                heap.push(entry);
+            }
+        }
+        Self {
+            iter: heap
+                .into_sorted_vec()
+                .into_iter()
+                .map(|entry| Ok(entry.item))
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+}
+
 pub trait OrderByAdapter<'db, 'arena, 'txn>: Iterator {
+    /// Sorts by each key in turn, with later keys breaking the ties of earlier ones.
+    ///
+    /// Missing values sort last unless the key asks for `nulls_first`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let users = G::new(storage, &txn, &arena)
+    ///     .n_from_type("User")
+    ///     .order_by(&[SortKey::asc("last"), SortKey::desc("age").nulls_first()]);
+    /// ```
+    fn order_by(
+        self,
+        keys: &[SortKey],
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OrderByKeys<'arena>>;
+
+    /// Same as `order_by`, but only returns the first `k` items, which are found with a
+    /// bounded heap instead of sorting everything
+    fn order_by_top_k<K>(
+        self,
+        keys: &[SortKey],
+        k: K,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OrderByKeys<'arena>>
+    where
+        K: TryInto<usize>;
+
     fn order_by_asc(
         self,
         property: &str,
@@ -62,6 +257,44 @@ pub trait OrderByAdapter<'db, 'arena, 'txn>: Iterator {
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     OrderByAdapter<'db, 'arena, 'txn> for RoTraversalIterator<'db, 'arena, 'txn, I>
 {
+    fn order_by(
+        self,
+        keys: &[SortKey],
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OrderByKeys<'arena>> {
+        RoTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner: OrderByKeys::sorted(self.inner, keys),
+        }
+    }
+
+    fn order_by_top_k<K>(
+        self,
+        keys: &[SortKey],
+        k: K,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, OrderByKeys<'arena>>
+    where
+        K: TryInto<usize>,
+    {
+        // a negative `k` can only come from a parameter, literals are checked by the analyzer
+        let inner = match k.try_into() {
+            Ok(k) => OrderByKeys::top_k(self.inner, keys, k),
+            Err(_) => OrderByKeys {
+                iter: vec![Err(GraphError::TraversalError(
+                    "RANGE expects a non-negative number of items".to_string(),
+                ))]
+                .into_iter(),
+            },
+        };
+        RoTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner,
+        }
+    }
+
     fn order_by_asc(
         self,
         property: &str,
@@ -0,0 +1,152 @@
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::label_hash::hash_label,
+};
+use heed3::{Database, RoTxn, types::Bytes};
+
+/// Encodes the id of the last item on a page as an opaque cursor for the next page
+pub fn encode_cursor(id: u128) -> String {
+    format!("{id:032x}")
+}
+
+/// Decodes a cursor produced by `encode_cursor`, where an empty cursor means the first page
+pub fn decode_cursor(cursor: &str) -> Result<Option<u128>, GraphError> {
+    if cursor.is_empty() {
+        return Ok(None);
+    }
+    match cursor.len() == 32 {
+        true => u128::from_str_radix(cursor, 16).map(Some).ok(),
+        false => None,
+    }
+    .ok_or_else(|| GraphError::DecodeError(format!("invalid cursor `{cursor}`")))
+}
+
+pub struct After<I> {
+    iter: I,
+    after: Option<u128>,
+}
+
+impl<'arena, I> Iterator for After<I>
+where
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(after) = self.after else {
+            return self.iter.next();
+        };
+        for item in self.iter.by_ref() {
+            match item {
+                Ok(item) if item.id() <= after => continue,
+                item => {
+                    self.after = None;
+                    return Some(item);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Which end of an adjacency entry a cursor is compared against
+#[derive(Clone, Copy)]
+pub enum Adjacent {
+    /// The node on the other side of the edge
+    Node,
+    /// The edge itself
+    Edge,
+}
+
+/// Reads the adjacency entries of `items` under `edge_label` and returns the ids of the
+/// adjacent nodes or edges greater than `after`, in ascending order.
+///
+/// Only the packed entries are compared, so the items before the cursor are never loaded,
+/// and a cursor whose item has since been removed still resumes at the next id.
+pub fn adjacent_ids_after<'arena>(
+    db: &Database<Bytes, Bytes>,
+    txn: &RoTxn,
+    items: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_key: fn(&u128, &[u8; 4]) -> [u8; 20],
+    edge_label: &str,
+    adjacent: Adjacent,
+    after: Option<u128>,
+) -> Result<Vec<u128>, GraphError> {
+    let edge_label_hash = hash_label(edge_label, None);
+    let mut ids = Vec::new();
+    for item in items {
+        let prefix = edge_key(&item?.id(), &edge_label_hash);
+        let Some(entries) = db.get_duplicates(txn, &prefix)? else {
+            continue;
+        };
+        for entry in entries {
+            let (_, data) = entry?;
+            let (edge_id, node_id) = HelixGraphStorage::unpack_adj_edge_data(data)?;
+            let id = match adjacent {
+                Adjacent::Node => node_id,
+                Adjacent::Edge => edge_id,
+            };
+            if after.is_none_or(|after| id > after) {
+                ids.push(id);
+            }
+        }
+    }
+    ids.sort_unstable();
+    Ok(ids)
+}
+
+pub trait PaginateAdapter<'db, 'arena, 'txn, I>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Skips every item up to and including the id `after`, so the items must come in
+    /// ascending id order. The page resumes at the next id even if the cursor's item was
+    /// removed in the meantime.
+    ///
+    /// Label and index scans and adjacency steps should use their `_after` variants
+    /// instead (`n_from_type_after`, `n_from_index_after`, `out_node_after`, ...), which
+    /// seek to the cursor rather than walking up to it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let page = G::new(storage, &txn, &arena)
+    ///     .n_from_type("user")
+    ///     .dedup()
+    ///     .after(decode_cursor(&cursor)?)
+    ///     .range(0, 50);
+    /// ```
+    fn after(self, after: Option<u128>) -> RoTraversalIterator<'db, 'arena, 'txn, After<I>>;
+
+    /// Returns the cursor pointing past the last item, or `Value::Empty` when there are
+    /// no items
+    fn next_cursor(self) -> Value;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    PaginateAdapter<'db, 'arena, 'txn, I> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn after(self, after: Option<u128>) -> RoTraversalIterator<'db, 'arena, 'txn, After<I>> {
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: After {
+                iter: self.inner,
+                after,
+            },
+        }
+    }
+
+    fn next_cursor(self) -> Value {
+        match self.inner.filter_map(Result::ok).last() {
+            Some(item) => Value::from(encode_cursor(item.id())),
+            None => Value::Empty,
+        }
+    }
+}
@@ -104,9 +104,9 @@ where
 }
 
 #[derive(Debug, Clone)]
-struct DijkstraState {
-    node_id: u128,
-    distance: f64,
+pub(crate) struct DijkstraState {
+    pub(crate) node_id: u128,
+    pub(crate) distance: f64,
 }
 
 impl Eq for DijkstraState {}
@@ -508,6 +508,333 @@ where
     }
 }
 
+/// The endpoint paths are enumerated from, of which steps are given exactly one
+fn path_type(from: Option<&u128>, to: Option<&u128>, step: &str) -> Result<PathType, GraphError> {
+    match (from, to) {
+        (Some(from), None) => Ok(PathType::From(*from)),
+        (None, Some(to)) => Ok(PathType::To(*to)),
+        _ => Err(GraphError::TraversalError(format!(
+            "{step} expects exactly one of a start or an end node"
+        ))),
+    }
+}
+
+/// Splits the checked arguments of a step into the fields of `PathsIterator`
+fn split_args(
+    args: Result<(PathType, PathEnumeration), GraphError>,
+) -> (Option<(PathType, PathEnumeration)>, Option<GraphError>) {
+    match args {
+        Ok(args) => (Some(args), None),
+        Err(e) => (None, Some(e)),
+    }
+}
+
+/// Which paths `PathsIterator` enumerates between two nodes
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathEnumeration {
+    /// The `k` cheapest loopless paths, found with Yen's algorithm
+    KShortest(usize),
+    /// Every loopless path with at most `max_depth` edges
+    All(usize),
+}
+
+pub struct PathsIterator<'db, 'arena, 'txn, I, F>
+where
+    'db: 'arena,
+    'arena: 'txn,
+    F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+{
+    pub arena: &'arena bumpalo::Bump,
+    pub iter: I,
+    edge_label: Option<&'arena str>,
+    storage: &'db HelixGraphStorage,
+    txn: &'txn RoTxn<'db>,
+    /// The endpoint and the paths to enumerate, unless the arguments of the step were invalid
+    args: Option<(PathType, PathEnumeration)>,
+    /// Why the arguments were invalid, returned once in place of any path
+    error: Option<GraphError>,
+    weight_fn: F,
+    buffer: VecDeque<TraversalValue<'arena>>,
+}
+
+/// A path as node and edge ids, along with the weight of each edge when paths are ranked
+#[derive(Debug, Clone)]
+struct WeightedPath {
+    nodes: Vec<u128>,
+    edges: Vec<u128>,
+    weights: Vec<f64>,
+}
+
+impl WeightedPath {
+    fn cost(&self) -> f64 {
+        self.weights.iter().sum()
+    }
+}
+
+impl<
+    'db: 'arena,
+    'arena: 'txn,
+    'txn,
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+> Iterator for PathsIterator<'db, 'arena, 'txn, I, F>
+{
+    type Item = Result<TraversalValue<'arena>, GraphError>;
+
+    /// Returns the paths found for each incoming node one by one, cheapest or shortest first
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(path) = self.buffer.pop_front() {
+                return Some(Ok(path));
+            }
+            let Some((path_type, enumeration)) = self.args.clone() else {
+                return self.error.take().map(Err);
+            };
+            match self.iter.next()? {
+                Ok(TraversalValue::Node(node)) => {
+                    let (from, to) = match path_type {
+                        PathType::From(from) => (from, node.id),
+                        PathType::To(to) => (node.id, to),
+                    };
+                    let paths = match enumeration {
+                        PathEnumeration::KShortest(k) => self.k_shortest_paths(from, to, k),
+                        PathEnumeration::All(max_depth) => self.all_paths(from, to, max_depth),
+                    };
+                    let values = paths.and_then(|paths| {
+                        paths
+                            .iter()
+                            .map(|path| self.to_traversal_value(path))
+                            .collect::<Result<Vec<_>, _>>()
+                    });
+                    match values {
+                        Ok(values) => self.buffer.extend(values),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                other => return Some(other),
+            }
+        }
+    }
+}
+
+impl<'db, 'arena, 'txn, I, F> PathsIterator<'db, 'arena, 'txn, I, F>
+where
+    F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+{
+    fn out_edges(&self, node_id: u128) -> Result<Vec<(u128, u128)>, GraphError> {
+        let label_hash = self.edge_label.map(|label| hash_label(label, None));
+        self.storage
+            .out_neighbours(self.txn, node_id, label_hash.as_ref())
+    }
+
+    fn edge_weight(&self, edge_id: u128, from: u128, to: u128) -> Result<f64, GraphError> {
+        let edge = self.storage.get_edge(self.txn, &edge_id, self.arena)?;
+        let src_node = self.storage.get_node(self.txn, &from, self.arena)?;
+        let dst_node = self.storage.get_node(self.txn, &to, self.arena)?;
+        let weight = (self.weight_fn)(&edge, &src_node, &dst_node)?;
+        if weight < 0.0 {
+            return Err(GraphError::TraversalError(
+                "Negative edge weights are not supported for k shortest paths".to_string(),
+            ));
+        }
+        Ok(weight)
+    }
+
+    fn to_traversal_value(&self, path: &WeightedPath) -> Result<TraversalValue<'arena>, GraphError> {
+        let nodes = path
+            .nodes
+            .iter()
+            .map(|id| self.storage.get_node(self.txn, id, self.arena))
+            .collect::<Result<Vec<_>, _>>()?;
+        let edges = path
+            .edges
+            .iter()
+            .map(|id| self.storage.get_edge(self.txn, id, self.arena))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TraversalValue::Path((nodes, edges)))
+    }
+
+    /// Dijkstra's algorithm that ignores the given edges and nodes, used for the spur
+    /// paths of Yen's algorithm
+    fn dijkstra_excluding(
+        &self,
+        from: u128,
+        to: u128,
+        removed_edges: &HashSet<u128>,
+        removed_nodes: &HashSet<u128>,
+    ) -> Result<Option<WeightedPath>, GraphError> {
+        let mut heap = BinaryHeap::new();
+        let mut distances = HashMap::with_capacity(64);
+        let mut parent: HashMap<u128, (u128, u128, f64)> = HashMap::with_capacity(32);
+
+        distances.insert(from, 0.0);
+        heap.push(DijkstraState {
+            node_id: from,
+            distance: 0.0,
+        });
+
+        while let Some(DijkstraState {
+            node_id: current_id,
+            distance: current_dist,
+        }) = heap.pop()
+        {
+            if let Some(&best_dist) = distances.get(&current_id)
+                && current_dist > best_dist
+            {
+                continue;
+            }
+
+            if current_id == to {
+                let mut path = WeightedPath {
+                    nodes: vec![to],
+                    edges: Vec::new(),
+                    weights: Vec::new(),
+                };
+                let mut current = to;
+                while current != from {
+                    let (prev_node, edge_id, weight) = parent[&current];
+                    path.nodes.push(prev_node);
+                    path.edges.push(edge_id);
+                    path.weights.push(weight);
+                    current = prev_node;
+                }
+                path.nodes.reverse();
+                path.edges.reverse();
+                path.weights.reverse();
+                return Ok(Some(path));
+            }
+
+            for (edge_id, to_node) in self.out_edges(current_id)? {
+                if removed_edges.contains(&edge_id) || removed_nodes.contains(&to_node) {
+                    continue;
+                }
+                let weight = self.edge_weight(edge_id, current_id, to_node)?;
+                let new_dist = current_dist + weight;
+                if distances
+                    .get(&to_node)
+                    .is_none_or(|&existing_dist| new_dist < existing_dist)
+                {
+                    distances.insert(to_node, new_dist);
+                    parent.insert(to_node, (current_id, edge_id, weight));
+                    heap.push(DijkstraState {
+                        node_id: to_node,
+                        distance: new_dist,
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Yen's algorithm: every next path branches off ("spurs") from a node of the
+    /// previous one while avoiding the edges already used by paths sharing that prefix
+    fn k_shortest_paths(
+        &self,
+        from: u128,
+        to: u128,
+        k: usize,
+    ) -> Result<Vec<WeightedPath>, GraphError> {
+        let mut found: Vec<WeightedPath> = Vec::with_capacity(k);
+        if k == 0 {
+            return Ok(found);
+        }
+        match self.dijkstra_excluding(from, to, &HashSet::new(), &HashSet::new())? {
+            Some(path) => found.push(path),
+            None => return Ok(found),
+        }
+
+        let mut candidates: Vec<WeightedPath> = Vec::new();
+        while found.len() < k {
+            let previous = found[found.len() - 1].clone();
+            for i in 0..previous.edges.len() {
+                let spur_node = previous.nodes[i];
+                let root_nodes = &previous.nodes[..=i];
+                let root_edges = &previous.edges[..i];
+
+                let removed_edges = found
+                    .iter()
+                    .filter(|p| {
+                        p.edges.len() > i
+                            && p.nodes[..=i] == *root_nodes
+                            && p.edges[..i] == *root_edges
+                    })
+                    .map(|p| p.edges[i])
+                    .collect::<HashSet<_>>();
+                let removed_nodes = previous.nodes[..i].iter().copied().collect::<HashSet<_>>();
+
+                if let Some(spur) =
+                    self.dijkstra_excluding(spur_node, to, &removed_edges, &removed_nodes)?
+                {
+                    let mut candidate = WeightedPath {
+                        nodes: previous.nodes[..i].to_vec(),
+                        edges: root_edges.to_vec(),
+                        weights: previous.weights[..i].to_vec(),
+                    };
+                    candidate.nodes.extend(spur.nodes);
+                    candidate.edges.extend(spur.edges);
+                    candidate.weights.extend(spur.weights);
+                    if !candidates.iter().any(|c| c.edges == candidate.edges) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+
+            // cheapest candidate next, preferring fewer hops on ties
+            let best = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    a.cost()
+                        .partial_cmp(&b.cost())
+                        .unwrap_or(Ordering::Equal)
+                        .then_with(|| a.edges.len().cmp(&b.edges.len()))
+                })
+                .map(|(i, _)| i);
+            match best {
+                Some(best) => found.push(candidates.swap_remove(best)),
+                None => break,
+            }
+        }
+        Ok(found)
+    }
+
+    /// Breadth first enumeration of the loopless paths, so shorter paths come first
+    fn all_paths(
+        &self,
+        from: u128,
+        to: u128,
+        max_depth: usize,
+    ) -> Result<Vec<WeightedPath>, GraphError> {
+        let mut paths = Vec::new();
+        let mut queue = VecDeque::from([WeightedPath {
+            nodes: vec![from],
+            edges: Vec::new(),
+            weights: Vec::new(),
+        }]);
+
+        while let Some(path) = queue.pop_front() {
+            let last = path.nodes[path.nodes.len() - 1];
+            if last == to {
+                paths.push(path);
+                continue;
+            }
+            if path.edges.len() == max_depth {
+                continue;
+            }
+            for (edge_id, to_node) in self.out_edges(last)? {
+                if path.nodes.contains(&to_node) {
+                    continue;
+                }
+                let mut next = path.clone();
+                next.nodes.push(to_node);
+                next.edges.push(edge_id);
+                queue.push_back(next);
+            }
+        }
+        Ok(paths)
+    }
+}
+
 pub trait ShortestPathAdapter<'db, 'arena, 'txn, 's, I>:
     Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
 {
@@ -567,6 +894,48 @@ pub trait ShortestPathAdapter<'db, 'arena, 'txn, 's, I>:
     where
         F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
         H: Fn(&Node<'arena>) -> Result<f64, GraphError>;
+
+    /// KShortestPaths finds up to `k` loopless paths between two nodes, cheapest first
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let traversal = G::new(storage, &txn, &arena)
+    ///     .n_from_id(&node1.id)
+    ///     .k_shortest_paths(Some("knows"), None, Some(&node2.id), 3, default_weight_fn);
+    /// ```
+    fn k_shortest_paths<K, F>(
+        self,
+        edge_label: Option<&'arena str>,
+        from: Option<&'s u128>,
+        to: Option<&'s u128>,
+        k: K,
+        weight_fn: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, PathsIterator<'db, 'arena, 'txn, I, F>>
+    where
+        K: TryInto<usize>,
+        F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>;
+
+    /// AllPaths finds every loopless path between two nodes with at most `max_depth` edges
+    #[allow(clippy::type_complexity)]
+    fn all_paths(
+        self,
+        edge_label: Option<&'arena str>,
+        from: Option<&'s u128>,
+        to: Option<&'s u128>,
+        max_depth: usize,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        PathsIterator<
+            'db,
+            'arena,
+            'txn,
+            I,
+            fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+        >,
+    >;
 }
 
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -661,4 +1030,85 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
             txn: self.txn,
         }
     }
+
+    #[inline]
+    fn k_shortest_paths<K, F>(
+        self,
+        edge_label: Option<&'arena str>,
+        from: Option<&'s u128>,
+        to: Option<&'s u128>,
+        k: K,
+        weight_fn: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, PathsIterator<'db, 'arena, 'txn, I, F>>
+    where
+        K: TryInto<usize>,
+        F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+    {
+        let args = path_type(from, to, "KShortestPaths").and_then(|path_type| {
+            // a negative `k` can only come from a parameter, literals are checked by the analyzer
+            let k = k.try_into().map_err(|_| {
+                GraphError::TraversalError(
+                    "KShortestPaths expects a non-negative number of paths".to_string(),
+                )
+            })?;
+            Ok((path_type, PathEnumeration::KShortest(k)))
+        });
+        let (args, error) = split_args(args);
+        RoTraversalIterator {
+            arena: self.arena,
+            inner: PathsIterator {
+                arena: self.arena,
+                iter: self.inner,
+                edge_label,
+                storage: self.storage,
+                txn: self.txn,
+                args,
+                error,
+                weight_fn,
+                buffer: VecDeque::new(),
+            },
+            storage: self.storage,
+            txn: self.txn,
+        }
+    }
+
+    #[inline]
+    fn all_paths(
+        self,
+        edge_label: Option<&'arena str>,
+        from: Option<&'s u128>,
+        to: Option<&'s u128>,
+        max_depth: usize,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        PathsIterator<
+            'db,
+            'arena,
+            'txn,
+            I,
+            fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+        >,
+    > {
+        let args = path_type(from, to, "AllPaths")
+            .map(|path_type| (path_type, PathEnumeration::All(max_depth)));
+        let (args, error) = split_args(args);
+        RoTraversalIterator {
+            arena: self.arena,
+            inner: PathsIterator {
+                arena: self.arena,
+                iter: self.inner,
+                edge_label,
+                storage: self.storage,
+                txn: self.txn,
+                args,
+                error,
+                weight_fn: default_weight_fn,
+                buffer: VecDeque::new(),
+            },
+            storage: self.storage,
+            txn: self.txn,
+        }
+    }
 }
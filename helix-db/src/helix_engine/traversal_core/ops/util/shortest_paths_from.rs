@@ -0,0 +1,195 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            ops::util::paths::DijkstraState, traversal_iter::RoTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{
+        items::{Edge, Node},
+        label_hash::hash_label,
+    },
+};
+use heed3::RoTxn;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Property under which the distance from the source is returned on each reached node
+pub const DISTANCE_PROPERTY: &str = "distance";
+
+/// Weight function that counts every edge as a single hop
+pub fn hop_count_weight_fn<'arena>(
+    _edge: &Edge<'arena>,
+    _src_node: &Node<'arena>,
+    _dst_node: &Node<'arena>,
+) -> Result<f64, GraphError> {
+    Ok(1.0)
+}
+
+pub struct ShortestPathsFromIterator<'db, 'arena, 'txn, I, F> {
+    iter: I,
+    storage: &'db HelixGraphStorage,
+    txn: &'txn RoTxn<'db>,
+    arena: &'arena bumpalo::Bump,
+    edge_label: Option<&'arena str>,
+    max_distance: Option<f64>,
+    weight_fn: F,
+    buffer: VecDeque<TraversalValue<'arena>>,
+}
+
+impl<'db, 'arena, 'txn, I, F> Iterator for ShortestPathsFromIterator<'db, 'arena, 'txn, I, F>
+where
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+{
+    type Item = Result<TraversalValue<'arena>, GraphError>;
+
+    /// Returns the nodes reached from each incoming node, closest first
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.buffer.pop_front() {
+                return Some(Ok(value));
+            }
+            match self.iter.next()? {
+                Ok(TraversalValue::Node(node)) => {
+                    if let Err(e) = self.expand(node) {
+                        return Some(Err(e));
+                    }
+                }
+                other => return Some(other),
+            }
+        }
+    }
+}
+
+impl<'db, 'arena, 'txn, I, F> ShortestPathsFromIterator<'db, 'arena, 'txn, I, F>
+where
+    F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+{
+    fn out_edges(&self, node_id: u128) -> Result<Vec<(u128, u128)>, GraphError> {
+        let label_hash = self.edge_label.map(|label| hash_label(label, None));
+        self.storage
+            .out_neighbours(self.txn, node_id, label_hash.as_ref())
+    }
+
+    /// Dijkstra's algorithm from `source`, stopping once the closest unsettled node is
+    /// further away than `max_distance`
+    fn expand(&mut self, source: Node<'arena>) -> Result<(), GraphError> {
+        // `max_distance` can come from a parameter, so it is only checked when the query runs
+        if let Some(max) = self.max_distance
+            && (max.is_nan() || max < 0.0)
+        {
+            return Err(GraphError::TraversalError(format!(
+                "ShortestPathsFrom expects a non-negative max distance, got {max}"
+            )));
+        }
+        let mut distances: HashMap<u128, f64> = HashMap::from([(source.id, 0.0)]);
+        let mut nodes: HashMap<u128, Node<'arena>> = HashMap::from([(source.id, source)]);
+        let mut heap = BinaryHeap::from([DijkstraState {
+            node_id: source.id,
+            distance: 0.0,
+        }]);
+
+        while let Some(DijkstraState { node_id, distance }) = heap.pop() {
+            if distance > distances.get(&node_id).copied().unwrap_or(f64::INFINITY) {
+                continue;
+            }
+            if self.max_distance.is_some_and(|max| distance > max) {
+                break;
+            }
+            let node = nodes[&node_id];
+            if node_id != source.id {
+                self.buffer.push_back(TraversalValue::Node(node.with_property(
+                    DISTANCE_PROPERTY,
+                    Value::F64(distance),
+                    self.arena,
+                )));
+            }
+
+            for (edge_id, to_node) in self.out_edges(node_id)? {
+                let edge = self.storage.get_edge(self.txn, &edge_id, self.arena)?;
+                let dst_node = match nodes.get(&to_node) {
+                    Some(dst_node) => *dst_node,
+                    None => self.storage.get_node(self.txn, &to_node, self.arena)?,
+                };
+                let weight = (self.weight_fn)(&edge, &node, &dst_node)?;
+                // a NaN weight would break both the heap order and the `max_distance` cutoff
+                if weight.is_nan() || weight < 0.0 {
+                    return Err(GraphError::TraversalError(format!(
+                        "ShortestPathsFrom expects non-negative edge weights, got {weight}"
+                    )));
+                }
+
+                let next_distance = distance + weight;
+                if next_distance < distances.get(&to_node).copied().unwrap_or(f64::INFINITY) {
+                    distances.insert(to_node, next_distance);
+                    nodes.insert(to_node, dst_node);
+                    heap.push(DijkstraState {
+                        node_id: to_node,
+                        distance: next_distance,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub trait ShortestPathsFromAdapter<'db, 'arena, 'txn, I>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Finds the distance from each node to every node reachable over `edge_label`
+    /// edges, returning the reached nodes closest first with their distance stored
+    /// under the `distance` property.
+    ///
+    /// Nodes further away than `max_distance` are left out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let nearby = G::new(storage, &txn, &arena)
+    ///     .n_from_id(&city_id)
+    ///     .shortest_paths_from(Some("road"), Some(10.0), hop_count_weight_fn);
+    /// ```
+    fn shortest_paths_from<F>(
+        self,
+        edge_label: Option<&'arena str>,
+        max_distance: Option<f64>,
+        weight_fn: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, ShortestPathsFromIterator<'db, 'arena, 'txn, I, F>>
+    where
+        F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    ShortestPathsFromAdapter<'db, 'arena, 'txn, I> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn shortest_paths_from<F>(
+        self,
+        edge_label: Option<&'arena str>,
+        max_distance: Option<f64>,
+        weight_fn: F,
+    ) -> RoTraversalIterator<'db, 'arena, 'txn, ShortestPathsFromIterator<'db, 'arena, 'txn, I, F>>
+    where
+        F: Fn(&Edge<'arena>, &Node<'arena>, &Node<'arena>) -> Result<f64, GraphError>,
+    {
+        RoTraversalIterator {
+            arena: self.arena,
+            inner: ShortestPathsFromIterator {
+                iter: self.inner,
+                storage: self.storage,
+                txn: self.txn,
+                arena: self.arena,
+                edge_label,
+                max_distance,
+                weight_fn,
+                buffer: VecDeque::new(),
+            },
+            storage: self.storage,
+            txn: self.txn,
+        }
+    }
+}
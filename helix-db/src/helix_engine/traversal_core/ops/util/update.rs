@@ -1,4 +1,6 @@
-use heed3::PutFlags;
+use std::{borrow::Cow, collections::HashMap};
+
+use heed3::{Database, RwTxn, byteorder::BE, types::*};
 use itertools::Itertools;
 
 use crate::{
@@ -28,13 +30,27 @@ where
 pub trait UpdateAdapter<'db, 'arena, 'txn>: Iterator {
     fn update(
         self,
-        props: &[(&'static str, Value)],
+        props: &[(&'arena str, Value)],
     ) -> RwTraversalIterator<
         'db,
         'arena,
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Updates each item with the properties computed from the item itself,
+    /// e.g. to append to an array it already holds
+    fn update_with<F>(
+        self,
+        props: F,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        F: Fn(&TraversalValue<'arena>) -> Vec<(&'arena str, Value)>;
 }
 
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -42,37 +58,71 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
 {
     fn update(
         self,
-        props: &[(&'static str, Value)],
+        props: &[(&'arena str, Value)],
     ) -> RwTraversalIterator<
         'db,
         'arena,
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
-        // TODO: use a non-contiguous arena vec to avoid copying stuff
-        // around when we run out of capacity
-        let mut results = bumpalo::collections::Vec::new_in(self.arena);
+        update_items(self, |_| Cow::Borrowed(props))
+    }
 
-        for item in self.inner {
-            match item {
-                Ok(value) => match value {
+    fn update_with<F>(
+        self,
+        props: F,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        F: Fn(&TraversalValue<'arena>) -> Vec<(&'arena str, Value)>,
+    {
+        update_items(self, move |item| Cow::Owned(props(item)))
+    }
+}
+
+fn update_items<'db, 'arena, 'txn, 'p, I>(
+    traversal: RwTraversalIterator<'db, 'arena, 'txn, I>,
+    props_for: impl Fn(&TraversalValue<'arena>) -> Cow<'p, [(&'arena str, Value)]>,
+) -> RwTraversalIterator<
+    'db,
+    'arena,
+    'txn,
+    Update<bumpalo::collections::vec::IntoIter<'arena, Result<TraversalValue<'arena>, GraphError>>>,
+>
+where
+    'arena: 'p,
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+{
+    // TODO: use a non-contiguous arena vec to avoid copying stuff
+    // around when we run out of capacity
+    let mut results = bumpalo::collections::Vec::new_in(traversal.arena);
+
+    for item in traversal.inner {
+        match item {
+            Ok(value) => {
+                let props = props_for(&value);
+                match value {
                     TraversalValue::Node(mut node) => {
                         match node.properties {
                             None => {
                                 // Insert secondary indices
                                 for (k, v) in props.iter() {
-                                    let Some(db) = self.storage.secondary_indices.get(*k) else {
+                                    let Some(db) = traversal.storage.secondary_indices.get(*k)
+                                    else {
                                         continue;
                                     };
 
                                     match bincode::serialize(v) {
+                                        // updated nodes are not in id order, so the
+                                        // entry can't be appended to the value's ids
                                         Ok(v_serialized) => {
-                                            if let Err(e) = db.put_with_flags(
-                                                self.txn,
-                                                PutFlags::APPEND_DUP,
-                                                &v_serialized,
-                                                &node.id,
-                                            ) {
+                                            if let Err(e) =
+                                                db.put(traversal.txn, &v_serialized, &node.id)
+                                            {
                                                 results.push(Err(GraphError::from(e)));
                                             }
                                         }
@@ -84,48 +134,47 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                 let map = ImmutablePropertiesMap::new(
                                     props.len(),
                                     props.iter().map(|(k, v)| (*k, v.clone())),
-                                    self.arena,
+                                    traversal.arena,
                                 );
 
                                 node.properties = Some(map);
                             }
                             Some(old) => {
                                 for (k, v) in props.iter() {
-                                    let Some(db) = self.storage.secondary_indices.get(*k) else {
+                                    let Some(db) = traversal.storage.secondary_indices.get(*k)
+                                    else {
                                         continue;
                                     };
 
-                                    // delete secondary indexes for the props changed
-                                    let Some(old_value) = old.get(k) else {
-                                        continue;
-                                    };
-
-                                    match bincode::serialize(old_value) {
-                                        Ok(old_serialized) => {
-                                            if let Err(e) = db.delete_one_duplicate(
-                                                self.txn,
-                                                &old_serialized,
-                                                &node.id,
-                                            ) {
+                                    // delete secondary indexes for the props changed,
+                                    // props the node did not have yet have no entry
+                                    if let Some(old_value) = old.get(k) {
+                                        match bincode::serialize(old_value) {
+                                            Ok(old_serialized) => {
+                                                if let Err(e) = db.delete_one_duplicate(
+                                                    traversal.txn,
+                                                    &old_serialized,
+                                                    &node.id,
+                                                ) {
+                                                    results.push(Err(GraphError::from(e)));
+                                                    continue;
+                                                }
+                                            }
+                                            Err(e) => {
                                                 results.push(Err(GraphError::from(e)));
                                                 continue;
                                             }
                                         }
-                                        Err(e) => {
-                                            results.push(Err(GraphError::from(e)));
-                                            continue;
-                                        }
                                     }
 
                                     // create new secondary indexes for the props changed
                                     match bincode::serialize(v) {
+                                        // updated nodes are not in id order, so the
+                                        // entry can't be appended to the value's ids
                                         Ok(v_serialized) => {
-                                            if let Err(e) = db.put_with_flags(
-                                                self.txn,
-                                                PutFlags::APPEND_DUP,
-                                                &v_serialized,
-                                                &node.id,
-                                            ) {
+                                            if let Err(e) =
+                                                db.put(traversal.txn, &v_serialized, &node.id)
+                                            {
                                                 results.push(Err(GraphError::from(e)));
                                             }
                                         }
@@ -157,7 +206,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                 let new_map = ImmutablePropertiesMap::new(
                                     old.len() + len_diff,
                                     merged,
-                                    self.arena,
+                                    traversal.arena,
                                 );
 
                                 node.properties = Some(new_map);
@@ -166,8 +215,8 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
 
                         match bincode::serialize(&node) {
                             Ok(serialized_node) => {
-                                match self.storage.nodes_db.put(
-                                    self.txn,
+                                match traversal.storage.nodes_db.put(
+                                    traversal.txn,
                                     &node.id,
                                     &serialized_node,
                                 ) {
@@ -181,16 +230,36 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                     TraversalValue::Edge(mut edge) => {
                         match edge.properties {
                             None => {
+                                if let Err(e) = update_edge_indices(
+                                    &traversal.storage.edge_secondary_indices,
+                                    traversal.txn,
+                                    edge.id,
+                                    None,
+                                    &props,
+                                ) {
+                                    results.push(Err(e));
+                                }
+
                                 // Create properties map and insert edge
                                 let map = ImmutablePropertiesMap::new(
                                     props.len(),
                                     props.iter().map(|(k, v)| (*k, v.clone())),
-                                    self.arena,
+                                    traversal.arena,
                                 );
 
                                 edge.properties = Some(map);
                             }
                             Some(old) => {
+                                if let Err(e) = update_edge_indices(
+                                    &traversal.storage.edge_secondary_indices,
+                                    traversal.txn,
+                                    edge.id,
+                                    Some(&old),
+                                    &props,
+                                ) {
+                                    results.push(Err(e));
+                                }
+
                                 let diff = props.iter().filter(|(k, _)| {
                                     !old.iter().map(|(old_k, _)| old_k).contains(k)
                                 });
@@ -215,7 +284,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                 let new_map = ImmutablePropertiesMap::new(
                                     old.len() + len_diff,
                                     merged,
-                                    self.arena,
+                                    traversal.arena,
                                 );
 
                                 edge.properties = Some(new_map);
@@ -224,8 +293,8 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
 
                         match bincode::serialize(&edge) {
                             Ok(serialized_edge) => {
-                                match self.storage.edges_db.put(
-                                    self.txn,
+                                match traversal.storage.edges_db.put(
+                                    traversal.txn,
                                     &edge.id,
                                     &serialized_edge,
                                 ) {
@@ -240,18 +309,39 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                     // TraversalValue::Vector(hvector) => todo!(),
                     // TraversalValue::VectorNodeWithoutVectorData(vector_without_data) => todo!(),
                     _ => results.push(Err(GraphError::New("Unsupported value type".to_string()))),
-                },
-                Err(e) => results.push(Err(e)),
+                }
             }
+            Err(e) => results.push(Err(e)),
         }
+    }
+
+    RwTraversalIterator {
+        inner: Update {
+            iter: results.into_iter(),
+        },
+        storage: traversal.storage,
+        arena: traversal.arena,
+        txn: traversal.txn,
+    }
+}
 
-        RwTraversalIterator {
-            inner: Update {
-                iter: results.into_iter(),
-            },
-            storage: self.storage,
-            arena: self.arena,
-            txn: self.txn,
+/// Moves the edge's entries in the edge property indices of the changed properties
+/// from their old values to the new ones.
+fn update_edge_indices(
+    indices: &HashMap<String, Database<Bytes, U128<BE>>>,
+    txn: &mut RwTxn,
+    id: u128,
+    old: Option<&ImmutablePropertiesMap>,
+    props: &[(&str, Value)],
+) -> Result<(), GraphError> {
+    for (k, v) in props {
+        let Some(db) = indices.get(*k) else {
+            continue;
+        };
+        if let Some(old_value) = old.and_then(|old| old.get(k)) {
+            db.delete_one_duplicate(txn, &bincode::serialize(old_value)?, &id)?;
         }
+        db.put(txn, &bincode::serialize(v)?, &id)?;
     }
+    Ok(())
 }
@@ -1,3 +1,5 @@
 pub mod brute_force_search;
 pub mod insert;
+pub mod owner_n;
 pub mod search;
+pub mod set_embedding;
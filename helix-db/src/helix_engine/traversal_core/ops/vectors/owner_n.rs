@@ -0,0 +1,82 @@
+use crate::{
+    helix_engine::{
+        storage_core::storage_methods::StorageMethods,
+        traversal_core::{
+            ops::util::graph_algorithms::SCORE_PROPERTY, traversal_iter::RoTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::id::ID,
+};
+
+pub trait OwnerNAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns the `label` node each vector is embedded on under `field`, with the score
+    /// of the vector under the `score` property.
+    ///
+    /// Vectors that are not embedded on a `label` node are skipped.
+    fn owner_n(
+        self,
+        label: &'arena str,
+        field: &'arena str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    OwnerNAdapter<'db, 'arena, 'txn> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn owner_n(
+        self,
+        label: &'arena str,
+        field: &'arena str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let db = self.storage.secondary_indices.get(field);
+
+        let iter = self.inner.filter_map(move |item| {
+            let vector = match item {
+                Ok(TraversalValue::Vector(vector)) => vector,
+                Ok(_) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+            let Some(db) = db else {
+                return Some(Err(GraphError::New(format!(
+                    "Secondary Index {field} not found"
+                ))));
+            };
+            let owner = bincode::serialize(&Value::Id(ID::from(vector.id)))
+                .map_err(GraphError::from)
+                .and_then(|key| db.get(self.txn, &key).map_err(GraphError::from));
+            match owner {
+                Ok(Some(node_id)) => match self.storage.get_node(self.txn, &node_id, self.arena) {
+                    Ok(node) if node.label == label => Some(Ok(TraversalValue::Node(
+                        node.with_property(SCORE_PROPERTY, Value::F64(vector.score()), self.arena),
+                    ))),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(e)),
+                },
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            }
+        });
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: iter,
+        }
+    }
+}
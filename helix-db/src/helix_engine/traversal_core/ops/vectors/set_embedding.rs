@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use crate::{
+    helix_engine::{
+        storage_core::storage_methods::StorageMethods,
+        traversal_core::{
+            ops::util::update::UpdateAdapter, traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+        vector_core::{hnsw::HNSW, vector::HVector},
+    },
+    protocol::value::Value,
+    utils::id::ID,
+};
+use heed3::RoTxn;
+
+pub trait SetEmbeddingAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Inserts `data` as a `label` vector for each node and stores the id of the vector
+    /// under the node's `field` property.
+    ///
+    /// The vector the node was previously embedded with is dropped.
+    fn set_embedding<F>(
+        self,
+        field: &'static str,
+        label: &'arena str,
+        data: &'arena [f64],
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        F: Fn(&HVector<'arena>, &RoTxn<'db>) -> bool;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    SetEmbeddingAdapter<'db, 'arena, 'txn> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn set_embedding<F>(
+        self,
+        field: &'static str,
+        label: &'arena str,
+        data: &'arena [f64],
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        F: Fn(&HVector<'arena>, &RoTxn<'db>) -> bool,
+    {
+        let mut items = Vec::new();
+        let mut vector_ids = HashMap::new();
+
+        for item in self.inner {
+            let node = match item {
+                Ok(TraversalValue::Node(node)) => node,
+                other => {
+                    items.push(other);
+                    continue;
+                }
+            };
+            if let Some(Value::Id(old)) = node.get_property(field)
+                && let Err(e) = self.storage.drop_vector(self.txn, &old.inner())
+            {
+                items.push(Err(e));
+                continue;
+            }
+            match self
+                .storage
+                .vectors
+                .insert::<F>(self.txn, label, data, None, self.arena)
+            {
+                Ok(vector) => {
+                    vector_ids.insert(node.id, vector.id);
+                    items.push(Ok(TraversalValue::Node(node)));
+                }
+                Err(e) => items.push(Err(GraphError::from(e))),
+            }
+        }
+
+        RwTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: items.into_iter(),
+        }
+        .update_with(move |item| match vector_ids.get(&item.id()) {
+            Some(id) => vec![(field, Value::Id(ID::from(*id)))],
+            None => Vec::new(),
+        })
+    }
+}
@@ -81,6 +81,7 @@ impl<'arena> TraversalValue<'arena> {
         match self {
             TraversalValue::Vector(vector) => vector.score(),
             TraversalValue::VectorNodeWithoutVectorData(_) => 2f64,
+            TraversalValue::NodeWithScore { score, .. } => *score,
             _ => unimplemented!(),
         }
     }
@@ -97,6 +98,12 @@ impl<'arena> TraversalValue<'arena> {
     }
 
     pub fn get_property(&self, property: &str) -> Option<&'arena Value> {
+        // `address.city` reads `city` from the object stored in the `address` property
+        if let Some((field, path)) = property.split_once('.') {
+            return self
+                .get_property(field)
+                .and_then(|value| value.get_path(path));
+        }
         match self {
             TraversalValue::Node(node) => node.get_property(property),
             TraversalValue::Edge(edge) => edge.get_property(property),
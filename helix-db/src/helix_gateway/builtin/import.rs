@@ -0,0 +1,536 @@
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use serde::Deserialize;
+
+use crate::helix_engine::{storage_core::bulk_load::BulkEdge, types::GraphError};
+use crate::helix_gateway::router::router::{Handler, HandlerInput, HandlerSubmission};
+use crate::helixc::{
+    interpreter::values::{default_value, parse_id, stored},
+    parser::{
+        HelixParser,
+        types::{Content, Field, FieldType, HxFile, Schema, Source},
+    },
+};
+use crate::protocol::{self, value::Value};
+use crate::utils::properties::ImmutablePropertiesMap;
+
+// load a batch of rows into the graph in a single transaction, e.g. from `helix import`
+// curl -X POST "http://localhost:PORT/import" \
+//   -d '{"schema": "N::User { name: String }", "kind": "nodes", "label": "User", "rows": [{"properties": {"name": "alice"}}]}'
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    Nodes,
+    Edges,
+    Vectors,
+}
+
+#[derive(Deserialize)]
+pub struct ImportRequest {
+    /// HQL schema definitions the rows are checked against
+    schema: String,
+    kind: ImportKind,
+    /// Node, edge or vector type of the rows
+    label: String,
+    rows: Vec<ImportRow>,
+    /// Number of the first row, errors count rows from it
+    #[serde(default)]
+    first_row: usize,
+}
+
+#[derive(Deserialize)]
+pub struct ImportRow {
+    #[serde(default)]
+    properties: HashMap<String, Value>,
+    /// Id of the node or vector an edge starts from
+    from: Option<String>,
+    /// Id of the node or vector an edge points to
+    to: Option<String>,
+    /// Data of a vector
+    vector: Option<Vec<f64>>,
+}
+
+pub fn import_inner(input: HandlerInput) -> Result<protocol::Response, GraphError> {
+    let request = sonic_rs::from_slice::<ImportRequest>(&input.request.body)
+        .map_err(|e| GraphError::New(format!("invalid request: {e}")))?;
+
+    let content = Content {
+        content: String::new(),
+        files: vec![HxFile {
+            name: "schema.hx".to_string(),
+            content: request.schema,
+        }],
+        source: Source::default(),
+    };
+    let source = HelixParser::parse_source(&content).map_err(|e| GraphError::New(e.to_string()))?;
+    let schema = source
+        .get_latest_schema()
+        .map_err(|e| GraphError::New(e.to_string()))?;
+
+    let label = request.label.as_str();
+    let fields = match request.kind {
+        ImportKind::Nodes => schema
+            .node_schemas
+            .iter()
+            .find(|node| node.name.1 == label)
+            .map(|node| node.fields.as_slice()),
+        ImportKind::Edges => schema
+            .edge_schemas
+            .iter()
+            .find(|edge| edge.name.1 == label)
+            .map(|edge| edge.properties.as_deref().unwrap_or_default()),
+        ImportKind::Vectors => schema
+            .vector_schemas
+            .iter()
+            .find(|vector| vector.name == label)
+            .map(|vector| vector.fields.as_slice()),
+    };
+    let Some(fields) = fields else {
+        let kind = match request.kind {
+            ImportKind::Nodes => "node",
+            ImportKind::Edges => "edge",
+            ImportKind::Vectors => "vector",
+        };
+        return Err(GraphError::New(format!(
+            "the schema has no {kind} type named `{label}`"
+        )));
+    };
+
+    let storage = &input.graph.storage;
+    let arena = Bump::new();
+    let label = arena.alloc_str(label);
+    let first_row = request.first_row;
+    let at_row = |i: usize| {
+        move |e: GraphError| {
+            let message = match e {
+                GraphError::New(message) => message,
+                e => e.to_string(),
+            };
+            GraphError::New(format!("row {}: {message}", first_row + i))
+        }
+    };
+
+    let mut txn = storage.graph_env.write_txn()?;
+    let ids = match request.kind {
+        ImportKind::Nodes => {
+            let nodes = request
+                .rows
+                .into_iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    properties(&arena, schema, label, fields, row.properties).map_err(at_row(i))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let indices = fields
+                .iter()
+                .filter(|field| field.is_indexed())
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>();
+            storage.bulk_add_nodes(&mut txn, label, nodes, &indices)?
+        }
+        ImportKind::Edges => {
+            let edges = request
+                .rows
+                .into_iter()
+                .enumerate()
+                .map(|(i, row)| edge(&arena, schema, label, fields, row).map_err(at_row(i)))
+                .collect::<Result<Vec<_>, _>>()?;
+            storage.bulk_add_edges(&mut txn, label, edges)?
+        }
+        ImportKind::Vectors => {
+            let vectors = request
+                .rows
+                .into_iter()
+                .enumerate()
+                .map(|(i, row)| vector(&arena, schema, label, fields, row).map_err(at_row(i)))
+                .collect::<Result<Vec<_>, _>>()?;
+            storage.bulk_add_vectors(&mut txn, &arena, label, vectors)?
+        }
+    };
+    txn.commit()?;
+
+    let ids = ids
+        .into_iter()
+        .map(|id| uuid::Uuid::from_u128(id).to_string())
+        .collect::<Vec<_>>();
+    Ok(protocol::Response {
+        body: sonic_rs::to_vec(&sonic_rs::json!({ "ids": ids }))
+            .map_err(|e| GraphError::New(e.to_string()))?,
+        fmt: Default::default(),
+    })
+}
+
+fn edge<'arena>(
+    arena: &'arena Bump,
+    schema: &Schema,
+    label: &str,
+    fields: &[Field],
+    row: ImportRow,
+) -> Result<BulkEdge<'arena>, GraphError> {
+    let endpoint = |id: Option<&String>, name: &str| match id {
+        Some(id) => parse_id(id),
+        None => Err(GraphError::New(format!("missing `{name}`"))),
+    };
+    Ok(BulkEdge {
+        from_node: endpoint(row.from.as_ref(), "from")?,
+        to_node: endpoint(row.to.as_ref(), "to")?,
+        properties: properties(arena, schema, label, fields, row.properties)?,
+    })
+}
+
+type VectorRow<'arena> = (&'arena [f64], Option<ImmutablePropertiesMap<'arena>>);
+
+fn vector<'arena>(
+    arena: &'arena Bump,
+    schema: &Schema,
+    label: &str,
+    fields: &[Field],
+    row: ImportRow,
+) -> Result<VectorRow<'arena>, GraphError> {
+    let data = match row.vector {
+        Some(data) if !data.is_empty() => data,
+        _ => return Err(GraphError::New("missing `vector`".to_string())),
+    };
+    let properties = properties(arena, schema, label, fields, row.properties)?;
+    Ok((arena.alloc_slice_copy(&data), properties))
+}
+
+/// Casts the properties of a row to the types of its fields, filling in the defaults of
+/// the missing ones the same way `AddN` does
+fn properties<'arena>(
+    arena: &'arena Bump,
+    schema: &Schema,
+    label: &str,
+    fields: &[Field],
+    values: HashMap<String, Value>,
+) -> Result<Option<ImmutablePropertiesMap<'arena>>, GraphError> {
+    let mut props = Vec::with_capacity(fields.len());
+    for (key, value) in values {
+        let Some(field) = fields.iter().find(|field| field.name == key) else {
+            return Err(GraphError::New(format!(
+                "`{key}` is not a field of {label}"
+            )));
+        };
+        if matches!(value, Value::Empty) {
+            continue;
+        }
+        if matches!(field.field_type, FieldType::Embedding(_)) {
+            return Err(GraphError::New(format!(
+                "`{key}` is an embedded vector, which imports do not support"
+            )));
+        }
+        let value = stored(schema, value, &field.field_type, &key)?;
+        props.push((&*arena.alloc_str(&key), value));
+    }
+    for field in fields {
+        if let Some(default) = &field.defaults
+            && !props.iter().any(|(key, _)| *key == field.name)
+        {
+            let value = stored(
+                schema,
+                default_value(default),
+                &field.field_type,
+                &field.name,
+            )?;
+            props.push((&*arena.alloc_str(&field.name), value));
+        }
+    }
+    Ok((!props.is_empty())
+        .then(|| ImmutablePropertiesMap::new(props.len(), props.into_iter(), arena)))
+}
+
+inventory::submit! {
+    HandlerSubmission(
+        Handler::new("import", import_inner)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helix_engine::{
+            bm25::bm25::BM25,
+            storage_core::version_info::VersionInfo,
+            traversal_core::{
+                HelixGraphEngine, HelixGraphEngineOpts,
+                config::{Config, GraphConfig},
+            },
+        },
+        helixc::interpreter::interpret,
+        protocol::{Format, request::Request, request::RequestType},
+    };
+    use axum::body::Bytes;
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait, json};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const SCHEMA: &str = r#"
+N::User {
+    INDEX name: String,
+    age: U8 DEFAULT 18,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I32,
+    }
+}
+
+V::Doc {
+    title: String,
+}
+
+E::Wrote {
+    From: User,
+    To: Doc,
+}
+"#;
+
+    fn setup_test_engine() -> (Arc<HelixGraphEngine>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config {
+            graph_config: Some(GraphConfig {
+                secondary_indices: Some(vec!["name".to_string()]),
+                edge_secondary_indices: None,
+            }),
+            ..Config::default()
+        };
+        let opts = HelixGraphEngineOpts {
+            path: temp_dir.path().to_str().unwrap().to_string(),
+            config,
+            version_info: VersionInfo::default(),
+        };
+        (Arc::new(HelixGraphEngine::new(opts).unwrap()), temp_dir)
+    }
+
+    fn handler_input(
+        engine: &Arc<HelixGraphEngine>,
+        name: &str,
+        body: sonic_rs::Value,
+    ) -> HandlerInput {
+        HandlerInput {
+            graph: Arc::clone(engine),
+            request: Request {
+                name: name.to_string(),
+                req_type: RequestType::Query,
+                api_key_hash: None,
+                body: Bytes::from(sonic_rs::to_vec(&body).unwrap()),
+                in_fmt: Format::Json,
+                out_fmt: Format::Json,
+            },
+        }
+    }
+
+    /// Imports the rows, returning the ids of the created items
+    fn import(
+        engine: &Arc<HelixGraphEngine>,
+        kind: &str,
+        label: &str,
+        rows: sonic_rs::Value,
+    ) -> Result<Vec<String>, GraphError> {
+        let body = json!({
+            "schema": SCHEMA,
+            "kind": kind,
+            "label": label,
+            "rows": rows,
+            "first_row": 1,
+        });
+        let response = import_inner(handler_input(engine, "import", body))?;
+        let response: sonic_rs::Value = sonic_rs::from_slice(&response.body).unwrap();
+        Ok(response["ids"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_str().unwrap().to_string())
+            .collect())
+    }
+
+    fn query(
+        engine: &Arc<HelixGraphEngine>,
+        query: &str,
+        params: HashMap<String, Value>,
+    ) -> sonic_rs::Value {
+        let content = Content {
+            content: String::new(),
+            files: vec![
+                HxFile {
+                    name: "schema.hx".to_string(),
+                    content: SCHEMA.to_string(),
+                },
+                HxFile {
+                    name: "query.hx".to_string(),
+                    content: query.to_string(),
+                },
+            ],
+            source: Source::default(),
+        };
+        let source = HelixParser::parse_source(&content).unwrap();
+        interpret(&engine.storage, &source, &source.queries[0], params, false).unwrap()
+    }
+
+    #[test]
+    fn test_import_nodes_and_edges() {
+        let (engine, _temp_dir) = setup_test_engine();
+
+        let ids = import(
+            &engine,
+            "nodes",
+            "User",
+            json!([
+                { "properties": { "name": "alice", "age": 30 } },
+                { "properties": { "name": "bob" } },
+            ]),
+        )
+        .unwrap();
+        assert_eq!(ids.len(), 2);
+
+        let edges = import(
+            &engine,
+            "edges",
+            "Follows",
+            json!([{ "from": ids[0], "to": ids[1], "properties": { "since": 2020 } }]),
+        )
+        .unwrap();
+        assert_eq!(edges.len(), 1);
+
+        let result = query(
+            &engine,
+            r#"
+QUERY Followed() =>
+    alice <- N<User>({name: "alice"})
+    followed <- alice::Out<Follows>
+    edges <- alice::OutE<Follows>
+    RETURN alice, followed::{name, age}, edges::{since}
+"#,
+            HashMap::new(),
+        );
+        assert_eq!(result["alice"]["id"].as_str(), Some(ids[0].as_str()));
+        // the default is applied to the fields a row leaves out
+        assert_eq!(result["followed"], json!([{ "name": "bob", "age": 18 }]));
+        assert_eq!(result["edges"], json!([{ "since": 2020 }]));
+    }
+
+    #[test]
+    fn test_import_indexes_bm25_documents() {
+        let (engine, _temp_dir) = setup_test_engine();
+        import(
+            &engine,
+            "nodes",
+            "User",
+            json!([
+                { "properties": { "name": "alice" } },
+                { "properties": { "name": "bob" } },
+            ]),
+        )
+        .unwrap();
+
+        let bm25 = engine.storage.bm25.as_ref().unwrap();
+        let txn = engine.storage.graph_env.read_txn().unwrap();
+        assert_eq!(bm25.search(&txn, "alice", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_vectors() {
+        let (engine, _temp_dir) = setup_test_engine();
+        let users = import(
+            &engine,
+            "nodes",
+            "User",
+            json!([{ "properties": { "name": "alice" } }]),
+        )
+        .unwrap();
+        let docs = import(
+            &engine,
+            "vectors",
+            "Doc",
+            json!([
+                { "vector": [1.0, 0.0], "properties": { "title": "first" } },
+                { "vector": [0.0, 1.0], "properties": { "title": "second" } },
+            ]),
+        )
+        .unwrap();
+        import(
+            &engine,
+            "edges",
+            "Wrote",
+            json!([{ "from": users[0], "to": docs[1] }]),
+        )
+        .unwrap();
+
+        let result = query(
+            &engine,
+            r#"
+QUERY Search(vec: [F64]) =>
+    docs <- SearchV<Doc>(vec, 1)
+    wrote <- N<User>::OutE<Wrote>::ToV
+    RETURN docs::{title}, wrote::{title}
+"#,
+            HashMap::from([(
+                "vec".to_string(),
+                Value::Array(vec![Value::F64(0.1), Value::F64(0.9)]),
+            )]),
+        );
+        assert_eq!(result["docs"], json!([{ "title": "second" }]));
+        assert_eq!(result["wrote"], json!([{ "title": "second" }]));
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_rows() {
+        let (engine, _temp_dir) = setup_test_engine();
+
+        let result = import(&engine, "nodes", "Post", json!([]));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("the schema has no node type named `Post`")
+        );
+
+        let result = import(
+            &engine,
+            "nodes",
+            "User",
+            json!([
+                { "properties": { "name": "alice" } },
+                { "properties": { "name": "bob", "age": 300 } },
+            ]),
+        );
+        let err = result.unwrap_err().to_string();
+        assert!(err.ends_with("row 2: `age` expects U8, got 300"), "{err}");
+
+        let result = import(
+            &engine,
+            "nodes",
+            "User",
+            json!([{ "properties": { "email": "a@b.c" } }]),
+        );
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("`email` is not a field of User")
+        );
+
+        let missing = "0198c7b6-2b7e-7a41-9c5f-1f0e6f0d2a11";
+        let result = import(
+            &engine,
+            "edges",
+            "Follows",
+            json!([{ "from": missing, "to": missing, "properties": { "since": 1 } }]),
+        );
+        assert!(result.unwrap_err().to_string().contains("does not exist"));
+
+        // a failed batch writes none of its rows
+        let result = query(
+            &engine,
+            "QUERY Count() =>\n    count <- N<User>::COUNT\n    RETURN count\n",
+            HashMap::new(),
+        );
+        assert_eq!(result["count"].as_u64(), Some(0));
+    }
+}
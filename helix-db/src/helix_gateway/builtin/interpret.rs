@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::helix_engine::types::GraphError;
+use crate::helix_gateway::router::router::{Handler, HandlerInput, HandlerSubmission};
+use crate::helixc::{
+    analyzer::{analyze, diagnostic::DiagnosticSeverity},
+    interpreter::interpret,
+    parser::{
+        HelixParser,
+        types::{Content, HxFile, Source},
+    },
+};
+use crate::protocol::{self, value::Value};
+
+// run an ad-hoc query against the graph without compiling it into the instance
+// curl -X POST "http://localhost:PORT/interpret" \
+//   -d '{"query": "QUERY Q() => u <- N<User> RETURN u"}'
+
+#[derive(Deserialize)]
+pub struct InterpretRequest {
+    /// A single HQL query
+    query: String,
+    #[serde(default)]
+    params: HashMap<String, Value>,
+}
+
+/// The part of the introspection data the query is analyzed against
+#[derive(Deserialize)]
+struct DeployedSchema {
+    schema: DeployedSchemaData,
+}
+
+#[derive(Deserialize)]
+struct DeployedSchemaData {
+    hql: String,
+}
+
+pub fn interpret_inner(input: HandlerInput) -> Result<protocol::Response, GraphError> {
+    let request = sonic_rs::from_slice::<InterpretRequest>(&input.request.body)
+        .map_err(|e| GraphError::New(format!("invalid request: {e}")))?;
+
+    // queries are analyzed against the schema the instance was deployed with
+    let storage_config = &input.graph.storage.storage_config;
+    let schema = storage_config
+        .schema
+        .as_deref()
+        .ok_or_else(|| GraphError::New("instance has no deployed schema".to_string()))?;
+    let schema = sonic_rs::from_str::<DeployedSchema>(schema)
+        .map_err(|e| GraphError::New(format!("invalid deployed schema: {e}")))?
+        .schema
+        .hql;
+
+    let content = Content {
+        content: String::new(),
+        files: vec![
+            HxFile {
+                name: "schema.hx".to_string(),
+                content: schema,
+            },
+            HxFile {
+                name: "query.hx".to_string(),
+                content: request.query,
+            },
+        ],
+        source: Source::default(),
+    };
+    let source = HelixParser::parse_source(&content).map_err(|e| GraphError::New(e.to_string()))?;
+    let (diagnostics, generated) =
+        analyze(&source).map_err(|e| GraphError::New(e.to_string()))?;
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| matches!(diagnostic.severity, DiagnosticSeverity::Error))
+        .map(|diagnostic| format!("{}: {}", diagnostic.error_code, diagnostic.message))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(GraphError::New(errors.join("\n")));
+    }
+
+    let [query] = source.queries.as_slice() else {
+        return Err(GraphError::New("expected a single query".to_string()));
+    };
+    // only queries changing the graph take the write lock, reads don't queue behind writers
+    let write =
+        storage_config.interpret_writes && generated.queries.iter().any(|query| query.is_mut);
+    let result = interpret(&input.graph.storage, &source, query, request.params, write)?;
+
+    Ok(protocol::Response {
+        body: sonic_rs::to_vec(&result).map_err(|e| GraphError::New(e.to_string()))?,
+        fmt: Default::default(),
+    })
+}
+
+inventory::submit! {
+    HandlerSubmission(
+        Handler::new("interpret", interpret_inner)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helix_engine::{
+            storage_core::version_info::VersionInfo,
+            traversal_core::{HelixGraphEngine, HelixGraphEngineOpts, config::Config},
+        },
+        protocol::{Format, request::Request, request::RequestType},
+        utils::items::Node,
+    };
+    use axum::body::Bytes;
+    use sonic_rs::{JsonContainerTrait, JsonValueTrait, json};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    const SCHEMA: &str = r#"
+ENUM Tier { Free, Pro }
+
+N::Account {
+    owner: String,
+    tier: Tier,
+}
+
+N::User {
+    name: String,
+    age: U8 DEFAULT 18,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I32,
+    }
+}
+"#;
+
+    /// Engine deployed with `SCHEMA`, accepting interpreted writes when `writes` is set
+    fn setup_test_engine(writes: bool) -> (Arc<HelixGraphEngine>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config {
+            schema: Some(json!({ "schema": { "hql": SCHEMA } }).to_string()),
+            interpret_writes: Some(writes),
+            ..Config::default()
+        };
+        let opts = HelixGraphEngineOpts {
+            path: temp_dir.path().to_str().unwrap().to_string(),
+            config,
+            version_info: VersionInfo::default(),
+        };
+        (Arc::new(HelixGraphEngine::new(opts).unwrap()), temp_dir)
+    }
+
+    fn run(
+        engine: &Arc<HelixGraphEngine>,
+        query: &str,
+        params: sonic_rs::Value,
+    ) -> Result<sonic_rs::Value, GraphError> {
+        send(engine, json!({ "query": query, "params": params }))
+    }
+
+    fn send(
+        engine: &Arc<HelixGraphEngine>,
+        body: sonic_rs::Value,
+    ) -> Result<sonic_rs::Value, GraphError> {
+        let input = HandlerInput {
+            graph: Arc::clone(engine),
+            request: Request {
+                name: "interpret".to_string(),
+                req_type: RequestType::Query,
+                api_key_hash: None,
+                body: Bytes::from(sonic_rs::to_vec(&body).unwrap()),
+                in_fmt: Format::Json,
+                out_fmt: Format::Json,
+            },
+        };
+        let response = interpret_inner(input)?;
+        Ok(sonic_rs::from_slice(&response.body).unwrap())
+    }
+
+    /// Adds alice (30) following bob (20)
+    fn add_users(engine: &Arc<HelixGraphEngine>) {
+        let query = r#"
+QUERY AddUsers() =>
+    alice <- AddN<User>({name: "alice", age: 30})
+    bob <- AddN<User>({name: "bob", age: 20})
+    AddE<Follows>({since: 2020})::From(alice)::To(bob)
+    RETURN alice
+"#;
+        run(engine, query, json!({})).unwrap();
+    }
+
+    #[test]
+    fn test_interpret_writes_and_reads() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+        add_users(&engine);
+
+        let query = r#"
+QUERY GetUsers(min_age: U8) =>
+    users <- N<User>::WHERE(_::{age}::GT(min_age))
+    count <- N<User>::COUNT
+    RETURN users, count
+"#;
+        let result = run(&engine, query, json!({ "min_age": 25 })).unwrap();
+        let users = result["users"].as_array().unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0]["name"].as_str(), Some("alice"));
+        assert_eq!(users[0]["label"].as_str(), Some("User"));
+        assert_eq!(result["count"].as_u64(), Some(2));
+    }
+
+    #[test]
+    fn test_interpret_traversal_steps() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+        add_users(&engine);
+
+        let query = r#"
+QUERY Followed(name: String) =>
+    user <- N<User>::WHERE(_::{name}::EQ(name))::FIRST
+    followed <- user::Out<Follows>
+    edges <- user::OutE<Follows>
+    oldest <- N<User>::ORDER<Desc>(_::{age})::RANGE(0, 1)
+    RETURN followed::{name, age}, edges::{since}, oldest
+"#;
+        let result = run(&engine, query, json!({ "name": "alice" })).unwrap();
+        assert_eq!(result["followed"], json!([{ "name": "bob", "age": 20 }]));
+        assert_eq!(result["edges"], json!([{ "since": 2020 }]));
+        assert_eq!(result["oldest"][0]["name"].as_str(), Some("alice"));
+    }
+
+    #[test]
+    fn test_interpret_reads_alongside_a_writer() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+        add_users(&engine);
+
+        let query = r#"
+QUERY CountUsers() =>
+    count <- N<User>::COUNT
+    RETURN count
+"#;
+        // a read doesn't wait for the write lock held here
+        let wtxn = engine.storage.graph_env.write_txn().unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let reader = Arc::clone(&engine);
+        std::thread::spawn(move || {
+            let result = run(&reader, query, json!({})).unwrap();
+            sender.send(result["count"].as_u64()).unwrap();
+        });
+        let count = receiver.recv_timeout(std::time::Duration::from_secs(5));
+        drop(wtxn);
+        assert_eq!(count, Ok(Some(2)));
+    }
+
+    #[test]
+    fn test_interpret_is_read_only_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let opts = HelixGraphEngineOpts {
+            path: temp_dir.path().to_str().unwrap().to_string(),
+            config: Config {
+                schema: Some(json!({ "schema": { "hql": SCHEMA } }).to_string()),
+                ..Config::default()
+            },
+            version_info: VersionInfo::default(),
+        };
+        let engine = Arc::new(HelixGraphEngine::new(opts).unwrap());
+
+        let query = r#"
+QUERY AddUser() =>
+    user <- AddN<User>({name: "carol"})
+    RETURN user
+"#;
+        let result = run(&engine, query, json!({}));
+        assert!(result.unwrap_err().to_string().contains("read-only"));
+
+        let count = r#"
+QUERY CountUsers() =>
+    count <- N<User>::COUNT
+    RETURN count
+"#;
+        let result = run(&engine, count, json!({})).unwrap();
+        assert_eq!(result["count"].as_u64(), Some(0));
+
+        // writes are only allowed by the instance's config, whatever the request says
+        let (engine, _temp_dir) = setup_test_engine(true);
+        let body = json!({ "query": query, "write": false });
+        let result = send(&engine, body).unwrap();
+        // the default is applied to fields left out on insert
+        assert_eq!(result["user"]["age"].as_u64(), Some(18));
+    }
+
+    #[test]
+    fn test_interpret_ignores_schema_sent_by_the_client() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+
+        let body = json!({
+            "schema": "N::Post { title: String }",
+            "query": "QUERY AddPost() =>\n    post <- AddN<Post>({title: \"hi\"})\n    RETURN post",
+        });
+        let err = send(&engine, body).unwrap_err().to_string();
+        assert!(err.contains("E101"), "{err}");
+    }
+
+    #[test]
+    fn test_interpret_update_and_drop() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+        add_users(&engine);
+
+        let update = r#"
+QUERY Birthday(name: String) =>
+    user <- N<User>::WHERE(_::{name}::EQ(name))::UPDATE({age: 31})
+    RETURN user
+"#;
+        let result = run(&engine, update, json!({ "name": "alice" })).unwrap();
+        assert_eq!(result["user"][0]["age"].as_u64(), Some(31));
+
+        let drop = r#"
+QUERY RemoveUser(name: String) =>
+    DROP N<User>::WHERE(_::{name}::EQ(name))
+    RETURN NONE
+"#;
+        run(&engine, drop, json!({ "name": "bob" })).unwrap();
+
+        let query = r#"
+QUERY GetUsers() =>
+    users <- N<User>::{name, age}
+    RETURN users
+"#;
+        let result = run(&engine, query, json!({})).unwrap();
+        assert_eq!(result["users"], json!([{ "name": "alice", "age": 31 }]));
+    }
+
+    #[test]
+    fn test_interpret_enum_variants() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+
+        let add = r#"
+QUERY AddAccounts(tier: Tier) =>
+    AddN<Account>({owner: "alice", tier: "Free"})
+    AddN<Account>({owner: "bob", tier: tier})
+    RETURN NONE
+"#;
+        run(&engine, add, json!({ "tier": "Pro" })).unwrap();
+
+        let query = r#"
+QUERY GetAccounts(tier: Tier) =>
+    free <- N<Account>::WHERE(_::{tier}::EQ("Free"))
+    pro <- N<Account>::WHERE(_::{tier}::EQ(tier))
+    RETURN free::{owner, tier}, pro::{owner, tier}
+"#;
+        let result = run(&engine, query, json!({ "tier": "Pro" })).unwrap();
+        assert_eq!(
+            result["free"],
+            json!([{ "owner": "alice", "tier": "Free" }])
+        );
+        assert_eq!(result["pro"], json!([{ "owner": "bob", "tier": "Pro" }]));
+
+        // variants are stored as their index
+        let arena = bumpalo::Bump::new();
+        let txn = engine.storage.graph_env.read_txn().unwrap();
+        let tiers = engine
+            .storage
+            .nodes_db
+            .iter(&txn)
+            .unwrap()
+            .filter_map(|entry| {
+                let (id, bytes) = entry.unwrap();
+                let node = Node::from_bincode_bytes(id, bytes, &arena).unwrap();
+                node.get_property("tier").cloned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(tiers.len(), 2);
+        assert!(tiers.contains(&Value::U8(0)) && tiers.contains(&Value::U8(1)));
+
+        let err = run(&engine, add, json!({ "tier": "Team" }))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expects a variant of Tier"), "{err}");
+    }
+
+    #[test]
+    fn test_interpret_rejects_invalid_queries() {
+        let (engine, _temp_dir) = setup_test_engine(true);
+
+        let unknown_type = r#"
+QUERY GetPosts() =>
+    posts <- N<Post>
+    RETURN posts
+"#;
+        let result = run(&engine, unknown_type, json!({}));
+        assert!(result.unwrap_err().to_string().contains("E101"));
+
+        let missing_param = r#"
+QUERY GetUser(name: String) =>
+    users <- N<User>::WHERE(_::{name}::EQ(name))
+    RETURN users
+"#;
+        let result = run(&engine, missing_param, json!({}));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("missing parameter `name`")
+        );
+
+        let unsupported = r#"
+QUERY AddUsers(names: [String]) =>
+    FOR name IN names {
+        AddN<User>({name: name})
+    }
+    RETURN NONE
+"#;
+        let result = run(&engine, unsupported, json!({ "names": ["carol"] }));
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("not supported"), "{err}");
+    }
+}
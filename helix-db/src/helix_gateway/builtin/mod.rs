@@ -1,4 +1,8 @@
 pub mod all_nodes_and_edges;
+#[cfg(feature = "compiler")]
+pub mod import;
+#[cfg(feature = "compiler")]
+pub mod interpret;
 pub mod node_by_id;
 pub mod node_connections;
 pub mod nodes_by_label;
@@ -22,7 +22,7 @@ use crate::helix_gateway::introspect_schema::introspect_schema_handler;
 use crate::helix_gateway::worker_pool::WorkerPool;
 use crate::protocol;
 use crate::{
-    helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts},
+    helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts, config::Config},
     helix_gateway::mcp::mcp::MCPHandlerFn,
 };
 
@@ -50,7 +50,16 @@ impl HelixGateway {
         mcp_routes: Option<HashMap<String, MCPHandlerFn>>,
         opts: Option<HelixGraphEngineOpts>,
     ) -> HelixGateway {
-        let router = Arc::new(HelixRouter::new(routes, mcp_routes));
+        let mut routes = routes.unwrap_or_default();
+        // ad-hoc queries and bulk loads are only served when the instance's config turns them on
+        let config = opts.as_ref().map(|opts| &opts.config);
+        if !config.is_some_and(Config::get_interpret) {
+            routes.remove("interpret");
+        }
+        if !config.is_some_and(Config::get_import) {
+            routes.remove("import");
+        }
+        let router = Arc::new(HelixRouter::new(Some(routes), mcp_routes));
         let cluster_id = std::env::var("CLUSTER_ID").ok();
         HelixGateway {
             address: address.to_string(),
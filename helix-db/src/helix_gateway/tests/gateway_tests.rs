@@ -1,7 +1,8 @@
 use crate::helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts};
 use crate::helix_gateway::gateway::{AppState, CoreSetter, GatewayOpts, HelixGateway};
-use crate::helix_gateway::router::router::HelixRouter;
+use crate::helix_gateway::router::router::{HandlerFn, HelixRouter};
 use crate::helix_gateway::worker_pool::WorkerPool;
+use crate::protocol::Response;
 use core_affinity::CoreId;
 use std::sync::atomic;
 use std::{collections::HashMap, sync::Arc};
@@ -67,6 +68,73 @@ fn test_gateway_new_with_opts() {
     assert!(gateway.opts.is_some());
 }
 
+#[test]
+fn test_gateway_serves_interpret_only_when_enabled() {
+    let (graph, temp_dir) = create_test_graph();
+    let routes = || {
+        let handler: HandlerFn = Arc::new(|_| {
+            Ok(Response {
+                body: vec![],
+                fmt: Default::default(),
+            })
+        });
+        HashMap::from([("interpret".to_string(), handler)])
+    };
+    let opts = |interpret| HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config: Config {
+            interpret,
+            ..Config::default()
+        },
+        version_info: Default::default(),
+    };
+
+    let gateway = HelixGateway::new("127.0.0.1:8080", graph.clone(), 8, Some(routes()), None, None);
+    assert!(!gateway.router.routes.contains_key("interpret"));
+
+    let disabled = Some(opts(None));
+    let gateway =
+        HelixGateway::new("127.0.0.1:8080", graph.clone(), 8, Some(routes()), None, disabled);
+    assert!(!gateway.router.routes.contains_key("interpret"));
+
+    let enabled = Some(opts(Some(true)));
+    let gateway = HelixGateway::new("127.0.0.1:8080", graph, 8, Some(routes()), None, enabled);
+    assert!(gateway.router.routes.contains_key("interpret"));
+}
+
+#[test]
+fn test_gateway_serves_import_only_when_enabled() {
+    let (graph, temp_dir) = create_test_graph();
+    let routes = || {
+        let handler: HandlerFn = Arc::new(|_| {
+            Ok(Response {
+                body: vec![],
+                fmt: Default::default(),
+            })
+        });
+        HashMap::from([("import".to_string(), handler)])
+    };
+    let opts = |interpret, import| HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config: Config {
+            interpret,
+            import,
+            ..Config::default()
+        },
+        version_info: Default::default(),
+    };
+
+    // interpreted queries don't turn on bulk loads
+    let interpret = Some(opts(Some(true), None));
+    let gateway =
+        HelixGateway::new("127.0.0.1:8080", graph.clone(), 8, Some(routes()), None, interpret);
+    assert!(!gateway.router.routes.contains_key("import"));
+
+    let enabled = Some(opts(None, Some(true)));
+    let gateway = HelixGateway::new("127.0.0.1:8080", graph, 8, Some(routes()), None, enabled);
+    assert!(gateway.router.routes.contains_key("import"));
+}
+
 #[test]
 fn test_gateway_new_with_cluster_id() {
     unsafe {
@@ -42,6 +42,36 @@ pub enum ErrorCode {
     E209,
     /// `E210` – `identifier was expected to be of type ID, but got {}`
     E210,
+    /// `E211` – `optional parameter cannot be assigned to a required field`
+    E211,
+    /// `E212` – `value is not a variant of the enum`
+    E212,
+    /// `E213` – `upsert matches on a field that is not indexed`
+    E213,
+    /// `E214` – `variable-length step follows an edge between different node types`
+    E214,
+    /// `E215` – `invalid depth range for a variable-length step`
+    E215,
+    /// `E216` – `too many arguments for a graph algorithm`
+    E216,
+    /// `E217` – `aggregate function requires a numeric field`
+    E217,
+    /// `E218` – `percentile rank out of range`
+    E218,
+    /// `E219` – `embedding declared outside a node type`
+    E219,
+    /// `E220` – `searched node type does not embed exactly one vector type`
+    E220,
+    /// `E221` – `struct contains itself`
+    E221,
+    /// `E222` – `struct field declared with INDEX or DEFAULT`
+    E222,
+    /// `E223` – `KShortestPaths asks for less than one path`
+    E223,
+    /// `E224` – `aggregate named like another key of its group`
+    E224,
+    /// `E225` – `optional field returned without a null check`
+    E225,
     // QUERY ERRORS
     /// `E301` – `variable not in scope`
     E301,
@@ -55,6 +85,14 @@ pub enum ErrorCode {
     E305,
     /// `E306` – `expression is not a boolean`
     E306,
+    /// `E307` – `COALESCE arguments have mismatched types`
+    E307,
+    /// `E308` – `created variable bound to something other than an upsert`
+    E308,
+    /// `E309` – `upserted edge connects plural nodes`
+    E309,
+    /// `E310` – `edge lookup between plural nodes`
+    E310,
 
     // MCP ERRORS
     /// `E401` – `MCP query must return a single value`
@@ -98,6 +136,10 @@ pub enum ErrorCode {
     E632,
     /// `E633` - `index of range must be an integer`
     E633,
+    /// `E634` - `cursor must be a string parameter`
+    E634,
+    /// `E635` - `AFTER must follow a step that can seek to the cursor`
+    E635,
 
     /// `E641` - `closure is only valid as the last step in a traversal`
     E641,
@@ -119,6 +161,19 @@ pub enum ErrorCode {
     /// `E653` - `inner type of in variable is not an object`
     E653,
 
+    /// `E661` - `function argument has the wrong type`
+    E661,
+    /// `E662` - `invalid literal argument to function`
+    E662,
+    /// `E663` - `unsupported function argument`
+    E663,
+    /// `E664` - `array function is missing its array argument`
+    E664,
+    /// `E665` - `array function used to update a non-array field`
+    E665,
+    /// `E666` - `ANY/ALL applied to a non-array property`
+    E666,
+
 
     /// `W101` - `query has no return`
     W101,
@@ -144,12 +199,31 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E208 => write!(f, "E208"),
             ErrorCode::E209 => write!(f, "E209"),
             ErrorCode::E210 => write!(f, "E210"),
+            ErrorCode::E211 => write!(f, "E211"),
+            ErrorCode::E212 => write!(f, "E212"),
+            ErrorCode::E213 => write!(f, "E213"),
+            ErrorCode::E214 => write!(f, "E214"),
+            ErrorCode::E215 => write!(f, "E215"),
+            ErrorCode::E216 => write!(f, "E216"),
+            ErrorCode::E217 => write!(f, "E217"),
+            ErrorCode::E218 => write!(f, "E218"),
+            ErrorCode::E219 => write!(f, "E219"),
+            ErrorCode::E220 => write!(f, "E220"),
+            ErrorCode::E221 => write!(f, "E221"),
+            ErrorCode::E222 => write!(f, "E222"),
+            ErrorCode::E223 => write!(f, "E223"),
+            ErrorCode::E224 => write!(f, "E224"),
+            ErrorCode::E225 => write!(f, "E225"),
             ErrorCode::E301 => write!(f, "E301"),
             ErrorCode::E302 => write!(f, "E302"),
             ErrorCode::E303 => write!(f, "E303"),
             ErrorCode::E304 => write!(f, "E304"),
             ErrorCode::E305 => write!(f, "E305"),
             ErrorCode::E306 => write!(f, "E306"),
+            ErrorCode::E307 => write!(f, "E307"),
+            ErrorCode::E308 => write!(f, "E308"),
+            ErrorCode::E309 => write!(f, "E309"),
+            ErrorCode::E310 => write!(f, "E310"),
             ErrorCode::E401 => write!(f, "E401"),
             ErrorCode::E501 => write!(f, "E501"),
             ErrorCode::E601 => write!(f, "E601"),
@@ -167,6 +241,8 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E631 => write!(f, "E631"),
             ErrorCode::E632 => write!(f, "E632"),
             ErrorCode::E633 => write!(f, "E633"),
+            ErrorCode::E634 => write!(f, "E634"),
+            ErrorCode::E635 => write!(f, "E635"),
             ErrorCode::E641 => write!(f, "E641"),
             ErrorCode::E642 => write!(f, "E642"),
             ErrorCode::E643 => write!(f, "E643"),
@@ -176,6 +252,12 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E651 => write!(f, "E651"),
             ErrorCode::E652 => write!(f, "E652"),
             ErrorCode::E653 => write!(f, "E653"),
+            ErrorCode::E661 => write!(f, "E661"),
+            ErrorCode::E662 => write!(f, "E662"),
+            ErrorCode::E663 => write!(f, "E663"),
+            ErrorCode::E664 => write!(f, "E664"),
+            ErrorCode::E665 => write!(f, "E665"),
+            ErrorCode::E666 => write!(f, "E666"),
             ErrorCode::W101 => write!(f, "W101"),
         }
     }
@@ -222,9 +304,24 @@ implement_error_code!(E205,
     "change the value type to match the field type defined in the schema" => {});
 implement_error_code!(E206, "invalid value type `{}`" => { value_type }, "use a literal or an identifier" => {});
 implement_error_code!(E207, "edge type `{}` exists but it is not a valid edge type for the given {} type `{}`" => { edge_type, item_type, item_type_name }, "check the schema field names" => {});
-implement_error_code!(E208, "field `{}` has not been indexed for node type `{}`" => { field_name, node_type }, "use a field that has been indexed with `INDEX` in the schema for node type `{}`" => { node_type });
+implement_error_code!(E208, "field `{}` has not been indexed for {} type `{}`" => { field_name, item_kind, item_type }, "use a field that has been indexed with `INDEX` in the schema for {} type `{}`" => { item_kind, item_type });
 implement_error_code!(E209, "unknown type `{}` for parameter `{}`" => { parameter_type, parameter_name }, "declare or use a matching schema object or use a primitive type" => {});
 implement_error_code!(E210, "identifier `{}` was expected to be of type ID, but got {}" => { identifier, value_type_name }, "ensure the identifier is of type ID" => {});
+implement_error_code!(E211, "optional parameter `{}` cannot be assigned to required field `{}` for {} type `{}`" => { parameter_name, field_name, item_type, item_type_name }, "mark `{}` as optional with `?`, give it a `DEFAULT`, or make the parameter required" => { field_name });
+implement_error_code!(E212, "`{}` is not a variant of enum `{}`" => { value, enum_name }, "use one of the variants of `{}`: {}" => { enum_name, variants });
+implement_error_code!(E213, "field `{}` of node `{}` is not indexed, so `UpsertN` cannot match on it" => { field_name, node_type }, "mark `{}` with `INDEX` in the schema" => { field_name });
+implement_error_code!(E214, "edge `{}` goes from `{}` to `{}`, so `OutN` cannot follow it repeatedly" => { edge_type, from_type, to_type }, "`OutN` needs an edge whose `From` and `To` are the same node type" => {});
+implement_error_code!(E215, "invalid depth range `{}..{}`" => { min_depth, max_depth }, "the maximum depth must be at least 1 and not below the minimum depth" => {});
+implement_error_code!(E216, "`{}` takes at most {} argument(s), but got {}" => { algorithm, max_arguments, arguments }, "the arguments of `{}` are {}" => { algorithm, expected });
+implement_error_code!(E217, "`{}` requires a numeric field, but `{}` is of type `{}`" => { function, field_name, field_type }, "use `MIN`, `MAX`, `COUNT(DISTINCT ..)` or `COLLECT` for non-numeric fields" => {});
+implement_error_code!(E218, "percentile rank `{}` is out of range" => { rank }, "the rank must be between 0 and 1, e.g. `0.95` for the 95th percentile" => {});
+implement_error_code!(E219, "field `{}` embeds `V::{}`, but vectors can only be embedded on node types" => { field_name, vector_type }, "move the field to a node type or use a `[F64]` field" => {});
+implement_error_code!(E220, "`SearchV<{}>` searches the vector embedded on the node type, but it embeds {}" => { node_type, embedded }, "embed a single vector type on `{}` or search the vector type directly" => { node_type });
+implement_error_code!(E221, "struct `{}` contains itself through field `{}`" => { struct_name, field_name }, "store the nested value in a separate node type connected by an edge" => {});
+implement_error_code!(E222, "field `{}` of struct `{}` cannot be declared with `{}`" => { field_name, struct_name, modifier }, "remove `{}` from the field" => { modifier });
+implement_error_code!(E223, "`KShortestPaths` asks for {} paths" => { k }, "ask for at least 1 path" => {});
+implement_error_code!(E224, "aggregate `{}` has the same name as {}" => { name, used_by }, "rename the aggregate" => {});
+implement_error_code!(E225, "optional field `{}` is returned without a null check" => { field }, "check it with `IS_NULL` or `EXISTS` in a `WHERE` of the traversal returning `{}`, or give it a `DEFAULT` in the schema" => { field });
 
 // Query errors
 implement_error_code!(E301, "variable `{}` not in scope" => { variable }, "check the variable" => {});
@@ -232,6 +329,10 @@ implement_error_code!(E302, "variable `{}` previously declared" => { variable },
 implement_error_code!(E304, "missing {} type" => { item_type }, "add an {} type" => { item_type });
 implement_error_code!(E305, "missing parameter `{}` for method `{}`" => { parameter_name, method_name }, "add the parameter `{}`" => { parameter_name });
 implement_error_code!(E306, "expression should result in a boolean, instead got `{}`" => { expression_type }, "ensure the expression is a boolean" => {});
+implement_error_code!(E307, "`COALESCE` arguments must be scalars of the same type, got `{}` and `{}`" => { value_type, fallback_type }, "make the fallback the same type as the value" => {});
+implement_error_code!(E308, "`{}` can only be bound to the result of `UpsertN` or `UpsertE`" => { variable }, "remove `, {}` from the assignment" => { variable });
+implement_error_code!(E309, "`UpsertE` connects a single pair of nodes, but `{}` holds many" => { variable }, "use `FOR` to upsert one edge per node" => {});
+implement_error_code!(E310, "`Between` looks up the edges of a single pair of nodes, but `{}` holds many" => { variable }, "use `FOR` to look up the edges of each node" => {});
 
 // MCP errors
 implement_error_code!(E401, "MCP query must return a single value, but got `{}`" => { number_of_values }, "return a single value" => {});
@@ -261,6 +362,8 @@ implement_error_code!(E626, "edge type `{}` does not have a vector type as its `
 implement_error_code!(E631, "range must have a start and end, missing the `{}` value" => { start_or_end }, "add a `{}` value to the range" => { start_or_end });
 implement_error_code!(E632, "range start must be less than range end, got `{}` which is larger than `{}`" => { start, end }, "change the range start to be less than the range end" => {});
 implement_error_code!(E633, "index of range must be an integer, got `{}` which is of type `{}`" => { index, index_type }, "change {} to be an integer" => { index_type });
+implement_error_code!(E634, "cursor `{}` must be a `String` parameter, got `{}`" => { cursor, cursor_type }, "pass the cursor in as a `String` parameter" => {});
+implement_error_code!(E635, "`AFTER` must follow a label scan, an index lookup or an `Out`, `In`, `OutE` or `InE` step" => {}, "move `AFTER` right after the step the cursor pages through, only `WHERE` steps may come between them" => {});
 
 // Object remapping errors
 implement_error_code!(E641, "closure is only valid as the last step in a traversal" => {}, "move the closure to the end of the traversal" => {});
@@ -275,6 +378,13 @@ implement_error_code!(E651, "`IN` variable `{}` is not iterable" => { in_variabl
 implement_error_code!(E652, "variable `{}` is not a field of the inner object of the `IN` variable `{}`" => { variable, in_variable }, "ensure `{}` is a field of `{}`" => { variable, in_variable });
 implement_error_code!(E653, "inner object of `IN` variable `{}` is not an object" => { in_variable }, "ensure the inner type of `{}` is an object" => { in_variable });
 
+implement_error_code!(E661, "argument {} of `{}` must be {}, got `{}`" => { position, function, expected, got }, "pass {} as argument {} of `{}`" => { expected, position, function });
+implement_error_code!(E662, "invalid {} `{}` passed to `{}`" => { kind, value, function }, "expected {}" => { expected });
+implement_error_code!(E663, "`{}` cannot be used as an argument to `{}` here" => { argument, function }, "item properties (`_::{{field}}`) can only be read inside `WHERE`, object remappings and `UPDATE` values; other arguments must be literals, parameters, variables or function calls" => {});
+implement_error_code!(E664, "`{}` is missing the array to change" => { function }, "pass the array first, e.g. `{}(_::{{tags}}, value)`, or use it as an `UPDATE` value to change the updated field" => { function });
+implement_error_code!(E665, "`{}` can only update array fields, but `{}` is `{}`" => { function, field, field_type }, "declare `{}` as an array, e.g. `[String]`" => { field });
+implement_error_code!(E666, "`{}` can only be applied to an array property, got `{}`" => { quantifier, property_type }, "select a field declared as an array before `::{}`" => { quantifier });
+
 #[macro_export]
 macro_rules! generate_error {
     ($ctx:ident, $original_query:ident, $loc:expr, $error_code:ident, [$($message_args:expr),*], [$($hint_args:expr),*]) => {
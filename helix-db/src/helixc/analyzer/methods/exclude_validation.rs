@@ -122,7 +122,7 @@ pub(crate) fn validate_exclude<'a>(
                 );
             }
         }
-        Type::Anonymous(ty) => {
+        Type::Anonymous(ty) | Type::Computed(ty, _) => {
             // validates the exclude on the inner type of the anonymous type
             validate_exclude(ctx, ty, tr, ex, excluded, original_query);
         }
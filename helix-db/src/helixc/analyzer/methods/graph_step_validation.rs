@@ -9,6 +9,7 @@ use crate::{
         analyzer::{
             Ctx,
             errors::push_query_err,
+            methods::{infer_expr_type::infer_expr_type, traversal_validation::where_ref_from},
             types::Type,
             utils::{gen_identifier_or_param, is_valid_identifier},
         },
@@ -16,11 +17,12 @@ use crate::{
             math_functions::{generate_math_expr, ExpressionContext},
             queries::Query as GeneratedQuery,
             traversal_steps::{
-                FromV as GeneratedFromV, In as GeneratedIn, InE as GeneratedInE,
-                Out as GeneratedOut, OutE as GeneratedOutE, SearchVectorStep,
+                AllPaths as GeneratedAllPaths, FromV as GeneratedFromV, GraphAlgorithmStep, In as GeneratedIn,
+                InE as GeneratedInE, KShortestPaths as GeneratedKShortestPaths, Out as GeneratedOut, OutE as GeneratedOutE, OutN as GeneratedOutN, SearchVectorStep,
                 ShortestPath as GeneratedShortestPath, ShortestPathAStar as GeneratedShortestPathAStar,
                 ShortestPathBFS as GeneratedShortestPathBFS,
-                ShortestPathDijkstras as GeneratedShortestPathDijkstras, ShouldCollect,
+                ShortestPathDijkstras as GeneratedShortestPathDijkstras,
+                ShortestPathsFrom as GeneratedShortestPathsFrom, ShouldCollect,
                 Step as GeneratedStep, ToV as GeneratedToV, Traversal as GeneratedTraversal,
                 WeightCalculation,
             },
@@ -57,7 +59,8 @@ pub(crate) fn apply_graph_step<'a>(
     gen_query: &mut GeneratedQuery,
 ) -> Option<Type> {
     use GraphStepType::*;
-    match (&gs.step, cur_ty.base()) {
+    let cur_ty = cur_ty.base();
+    match (&gs.step, cur_ty) {
         // Node‑to‑Edge
         (
             OutE(label),
@@ -70,6 +73,7 @@ pub(crate) fn apply_graph_step<'a>(
                 .steps
                 .push(Separator::Period(GeneratedStep::OutE(GeneratedOutE {
                     label: GenRef::Literal(label.clone()),
+                    after: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -106,6 +110,7 @@ pub(crate) fn apply_graph_step<'a>(
                 .steps
                 .push(Separator::Period(GeneratedStep::InE(GeneratedInE {
                     label: GenRef::Literal(label.clone()),
+                    after: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -155,6 +160,7 @@ pub(crate) fn apply_graph_step<'a>(
                     edge_type: edge_type.clone(),
                     label: GenRef::Literal(label.clone()),
                     get_vector_data: false, // Will be updated if 'data' field is accessed
+                    after: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -189,6 +195,82 @@ pub(crate) fn apply_graph_step<'a>(
             }
         }
 
+        (OutN(out_n), Type::Nodes(Some(node_label)) | Type::Node(Some(node_label))) => {
+            let (from_label, to_label) = match ctx.edge_map.get(out_n.label.as_str()) {
+                Some(e) => (e.from.1.clone(), e.to.1.clone()),
+                None => {
+                    generate_error!(ctx, original_query, gs.loc.clone(), E102, out_n.label.as_str());
+                    return None;
+                }
+            };
+            if from_label != *node_label {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    gs.loc.clone(),
+                    E207,
+                    out_n.label.as_str(),
+                    "node",
+                    node_label.as_str()
+                );
+                return None;
+            }
+            if to_label != from_label {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    gs.loc.clone(),
+                    E214,
+                    out_n.label.as_str(),
+                    from_label.as_str(),
+                    to_label.as_str()
+                );
+                return None;
+            }
+            if out_n.max_depth == 0 || out_n.min_depth > out_n.max_depth {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    gs.loc.clone(),
+                    E215,
+                    &out_n.min_depth.to_string(),
+                    &out_n.max_depth.to_string()
+                );
+            }
+            let node_ty = Type::Node(Some(to_label.clone()));
+
+            // the condition is checked against every reached node, like a `WHERE` on them
+            let until = match &out_n.until {
+                Some(expr) => {
+                    let (_, stmt) = infer_expr_type(
+                        ctx,
+                        expr,
+                        scope,
+                        original_query,
+                        Some(node_ty.clone()),
+                        gen_query,
+                    );
+                    Some(where_ref_from(stmt?))
+                }
+                None => None,
+            };
+
+            traversal
+                .steps
+                .push(Separator::Period(GeneratedStep::OutN(GeneratedOutN {
+                    label: GenRef::Literal(out_n.label.clone()),
+                    min_depth: out_n.min_depth,
+                    max_depth: out_n.max_depth,
+                    emit_paths: out_n.emit_paths,
+                    until,
+                })));
+            traversal.should_collect = ShouldCollect::ToVec;
+            match out_n.emit_paths {
+                true => Some(Type::Unknown),
+                false => Some(Type::Nodes(Some(to_label))),
+            }
+        }
+
         (
             In(label),
             Type::Nodes(Some(node_label))
@@ -219,6 +301,7 @@ pub(crate) fn apply_graph_step<'a>(
                     edge_type: edge_type.clone(),
                     label: GenRef::Literal(label.clone()),
                     get_vector_data: false, // Will be updated if 'data' field is accessed
+                    after: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -608,6 +691,252 @@ pub(crate) fn apply_graph_step<'a>(
             traversal.should_collect = ShouldCollect::ToVec;
             Some(Type::Unknown)
         }
+        (KShortestPaths(sp), Type::Nodes(_) | Type::Node(_)) => {
+            if let Some(ref edge_type) = sp.type_arg
+                && !ctx.edge_map.contains_key(edge_type.as_str())
+            {
+                generate_error!(ctx, original_query, sp.loc.clone(), E102, edge_type.as_str());
+            }
+
+            let weight_calculation = match &sp.weight_expr {
+                Some(WeightExpression::Property(prop)) => {
+                    WeightCalculation::Property(GenRef::Literal(prop.clone()))
+                }
+                Some(WeightExpression::Expression(expr)) => {
+                    match generate_math_expr(expr, ExpressionContext::WeightCalculation) {
+                        Ok(math_expr) => WeightCalculation::Expression(format!("{}", math_expr)),
+                        Err(e) => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                sp.loc.clone(),
+                                E202,
+                                &format!("Failed to generate weight expression: {}", e),
+                                "valid math expression",
+                                "KShortestPaths"
+                            );
+                            WeightCalculation::Default
+                        }
+                    }
+                }
+                Some(WeightExpression::Default) | None => WeightCalculation::Default,
+            };
+
+            let k = match &sp.k.value {
+                EvaluatesToNumberType::I32(i) => {
+                    // parameters are checked when the query runs
+                    if *i < 1 {
+                        generate_error!(ctx, original_query, sp.loc.clone(), E223, &i.to_string());
+                    }
+                    GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                }
+                EvaluatesToNumberType::Identifier(i) => {
+                    is_valid_identifier(ctx, original_query, sp.loc.clone(), i.as_str());
+                    gen_identifier_or_param(original_query, i, false, true)
+                }
+                _ => {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        sp.loc.clone(),
+                        E305,
+                        ["k", "KShortestPaths"],
+                        ["k"]
+                    );
+                    GeneratedValue::Unknown
+                }
+            };
+
+            traversal
+                .steps
+                .push(Separator::Period(GeneratedStep::KShortestPaths(
+                    GeneratedKShortestPaths {
+                        label: sp.type_arg.clone().map(GenRef::Literal),
+                        from: sp.from.clone().map(GenRef::from),
+                        to: sp.to.clone().map(GenRef::from),
+                        k,
+                        weight_calculation,
+                    },
+                )));
+            traversal.should_collect = ShouldCollect::ToVec;
+            Some(Type::Unknown)
+        }
+        (AllPaths(sp), Type::Nodes(_) | Type::Node(_)) => {
+            if let Some(ref edge_type) = sp.type_arg
+                && !ctx.edge_map.contains_key(edge_type.as_str())
+            {
+                generate_error!(ctx, original_query, sp.loc.clone(), E102, edge_type.as_str());
+            }
+            if sp.max_depth == 0 {
+                generate_error!(ctx, original_query, sp.loc.clone(), E215, "1", "0");
+            }
+
+            traversal
+                .steps
+                .push(Separator::Period(GeneratedStep::AllPaths(GeneratedAllPaths {
+                    label: sp.type_arg.clone().map(GenRef::Literal),
+                    from: sp.from.clone().map(GenRef::from),
+                    to: sp.to.clone().map(GenRef::from),
+                    max_depth: sp.max_depth,
+                })));
+            traversal.should_collect = ShouldCollect::ToVec;
+            Some(Type::Unknown)
+        }
+        (ShortestPathsFrom(sp), Type::Nodes(_) | Type::Node(_)) => {
+            let edge_type = sp.type_arg.as_deref();
+            let to_type = match edge_type {
+                Some(edge_type) => match ctx.edge_map.get(edge_type) {
+                    Some(edge) => Some(edge.to.1.clone()),
+                    None => {
+                        generate_error!(ctx, original_query, sp.loc.clone(), E102, edge_type);
+                        None
+                    }
+                },
+                None => match cur_ty {
+                    Type::Node(label) | Type::Nodes(label) => label.clone(),
+                    _ => None,
+                },
+            };
+
+            // A bare field name weighs each edge by that property, e.g. `ShortestPathsFrom<Road>(weight)`
+            let weight_calculation = match &sp.weight_expr {
+                Some(WeightExpression::Expression(expr)) => match &expr.expr {
+                    ExpressionType::Identifier(field) => {
+                        if let Some(edge_type) = edge_type
+                            && ctx
+                                .edge_fields
+                                .get(edge_type)
+                                .is_some_and(|fields| !fields.contains_key(field.as_str()))
+                        {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                E202,
+                                field.as_str(),
+                                "edge",
+                                edge_type
+                            );
+                        }
+                        Some(WeightCalculation::Property(GenRef::Literal(field.clone())))
+                    }
+                    _ => match generate_math_expr(expr, ExpressionContext::WeightCalculation) {
+                        Ok(math_expr) => {
+                            Some(WeightCalculation::Expression(format!("{}", math_expr)))
+                        }
+                        Err(e) => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                sp.loc.clone(),
+                                E202,
+                                &format!("Failed to generate weight expression: {}", e),
+                                "valid math expression",
+                                "ShortestPathsFrom"
+                            );
+                            None
+                        }
+                    },
+                },
+                Some(WeightExpression::Property(prop)) => {
+                    Some(WeightCalculation::Property(GenRef::Literal(prop.clone())))
+                }
+                Some(WeightExpression::Default) => Some(WeightCalculation::Default),
+                None => None,
+            };
+
+            let max_distance = sp.max_distance.as_ref().map(|max| match &max.expr {
+                ExpressionType::Identifier(id) => {
+                    is_valid_identifier(ctx, original_query, max.loc.clone(), id.as_str());
+                    gen_identifier_or_param(original_query, id.as_str(), false, true)
+                }
+                ExpressionType::IntegerLiteral(val) => {
+                    GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+                }
+                ExpressionType::FloatLiteral(val) => {
+                    GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+                }
+                _ => {
+                    generate_error!(ctx, original_query, max.loc.clone(), E206, &max.expr.to_string());
+                    GeneratedValue::Unknown
+                }
+            });
+
+            traversal
+                .steps
+                .push(Separator::Period(GeneratedStep::ShortestPathsFrom(
+                    GeneratedShortestPathsFrom {
+                        label: sp.type_arg.clone().map(GenRef::Literal),
+                        max_distance,
+                        weight_calculation,
+                    },
+                )));
+            traversal.should_collect = ShouldCollect::ToVec;
+            Some(Type::Nodes(to_type).with_computed_property("distance", FieldType::F64))
+        }
+        (GraphAlgorithm(ga), Type::Nodes(label) | Type::Node(label)) => {
+            for edge_type in &ga.edge_types {
+                if !ctx.edge_map.contains_key(edge_type.as_str()) {
+                    generate_error!(ctx, original_query, ga.loc.clone(), E102, edge_type.as_str());
+                }
+            }
+
+            // Parameters each algorithm accepts, in order, with their defaults
+            let (params, defaults): (&str, &[&str]) = match ga.algorithm {
+                GraphAlgorithmType::PageRank => ("`damping` and `iterations`", &["0.85", "20"]),
+                GraphAlgorithmType::LabelPropagation => ("`iterations`", &["10"]),
+                GraphAlgorithmType::Betweenness => ("`samples`", &["0"]),
+                GraphAlgorithmType::ConnectedComponents
+                | GraphAlgorithmType::StronglyConnectedComponents
+                | GraphAlgorithmType::TriangleCount => ("none", &[]),
+            };
+            if ga.args.len() > defaults.len() {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    ga.loc.clone(),
+                    E216,
+                    [
+                        &ga.algorithm.to_string(),
+                        &defaults.len().to_string(),
+                        &ga.args.len().to_string()
+                    ],
+                    [&ga.algorithm.to_string(), params]
+                );
+            }
+
+            let args = defaults
+                .iter()
+                .enumerate()
+                .map(|(i, default)| match ga.args.get(i).map(|arg| (arg, &arg.expr)) {
+                    Some((arg, ExpressionType::Identifier(id))) => {
+                        is_valid_identifier(ctx, original_query, arg.loc.clone(), id.as_str());
+                        gen_identifier_or_param(original_query, id.as_str(), false, true)
+                    }
+                    Some((_, ExpressionType::IntegerLiteral(val))) => {
+                        GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+                    }
+                    Some((_, ExpressionType::FloatLiteral(val))) => {
+                        GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+                    }
+                    Some((arg, _)) => {
+                        generate_error!(ctx, original_query, arg.loc.clone(), E206, &arg.expr.to_string());
+                        GeneratedValue::Unknown
+                    }
+                    None => GeneratedValue::Primitive(GenRef::Std(default.to_string())),
+                })
+                .collect();
+
+            traversal
+                .steps
+                .push(Separator::Period(GeneratedStep::GraphAlgorithm(GraphAlgorithmStep {
+                    edge_labels: ga.edge_types.iter().cloned().map(GenRef::Literal).collect(),
+                    algorithm: ga.algorithm.clone(),
+                    args,
+                })));
+            traversal.should_collect = ShouldCollect::ToVec;
+            Some(Type::Nodes(label.clone()).with_computed_property("score", FieldType::F64))
+        }
         (SearchVector(sv), Type::Vectors(Some(vector_ty)) | Type::Vector(Some(vector_ty))) => {
             if !(matches!(cur_ty, Type::Vector(_)) || matches!(cur_ty, Type::Vectors(_))) {
                 generate_error!(
@@ -1021,4 +1350,307 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(diagnostics.is_empty());
     }
+
+    // ============================================================================
+    // Variable-Length Traversal Tests
+    // ============================================================================
+
+    #[test]
+    fn test_out_n_with_until_and_paths() {
+        let source = r#"
+            N::Person { name: String, age: U32 }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID, name: String) =>
+                reached <- N<Person>(id)::OutN<Knows>(1..3, UNTIL(_::{name}::EQ(name)))
+                paths <- N<Person>(id)::OutN<Knows>(0..2, PATHS)
+                RETURN reached, paths
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_out_n_between_different_node_types() {
+        let source = r#"
+            N::Person { name: String }
+            N::Company { name: String }
+            E::WorksAt { From: Person, To: Company }
+
+            QUERY test(id: ID) =>
+                companies <- N<Person>(id)::OutN<WorksAt>(1..2)
+                RETURN companies
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E214));
+    }
+
+    #[test]
+    fn test_out_n_invalid_depth_range() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                people <- N<Person>(id)::OutN<Knows>(3..1)
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E215));
+    }
+
+    // ============================================================================
+    // Path Enumeration Tests
+    // ============================================================================
+
+    #[test]
+    fn test_k_shortest_paths_and_all_paths() {
+        let source = r#"
+            N::City { name: String }
+            E::Road { From: City, To: City, Properties: { distance: F64 } }
+
+            QUERY test(from: ID, to: ID, k: I64) =>
+                cheapest <- N<City>(from)::KShortestPaths<Road>(k, _::{distance})::To(to)
+                routes <- N<City>(from)::AllPaths<Road>(4)::To(to)
+                RETURN cheapest, routes
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_k_shortest_paths_unknown_edge_type() {
+        let source = r#"
+            N::City { name: String }
+
+            QUERY test(from: ID, to: ID) =>
+                cheapest <- N<City>(from)::KShortestPaths<Road>(3)::To(to)
+                RETURN cheapest
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E102));
+    }
+
+    #[test]
+    fn test_k_shortest_paths_zero_k() {
+        let source = r#"
+            N::City { name: String }
+            E::Road { From: City, To: City }
+
+            QUERY test(from: ID, to: ID) =>
+                cheapest <- N<City>(from)::KShortestPaths<Road>(0)::To(to)
+                RETURN cheapest
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E223));
+    }
+
+    #[test]
+    fn test_all_paths_zero_depth() {
+        let source = r#"
+            N::City { name: String }
+            E::Road { From: City, To: City }
+
+            QUERY test(from: ID, to: ID) =>
+                routes <- N<City>(from)::AllPaths<Road>(0)::To(to)
+                RETURN routes
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E215));
+    }
+
+    #[test]
+    fn test_shortest_paths_from_distance_is_filterable() {
+        let source = r#"
+            N::City { name: String }
+            E::Road { From: City, To: City, Properties: { km: F64 } }
+
+            QUERY test(from: ID, radius: F64) =>
+                near <- N<City>(from)::ShortestPathsFrom<Road>(km)::WITHIN(radius)::WHERE(_::{distance}::GT(1.0))::ORDER<Asc>(_::{distance})
+                hops <- N<City>(from)::ShortestPathsFrom<Road>()::WITHIN(2)
+                RETURN near::{name, distance}, hops
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_shortest_paths_from_unknown_weight_field() {
+        let source = r#"
+            N::City { name: String }
+            E::Road { From: City, To: City, Properties: { km: F64 } }
+
+            QUERY test(from: ID) =>
+                near <- N<City>(from)::ShortestPathsFrom<Road>(miles)
+                RETURN near
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
+    }
+
+    // ============================================================================
+    // Graph Algorithm Tests
+    // ============================================================================
+
+    #[test]
+    fn test_graph_algorithms_return_scored_nodes() {
+        let source = r#"
+            N::User { name: String }
+            E::Follows { From: User, To: User }
+            E::Likes { From: User, To: User }
+
+            QUERY test(iterations: I64) =>
+                ranked <- N<User>::PageRank<Follows>(0.85, iterations)::ORDER<Desc>(_::{score})
+                components <- N<User>::ConnectedComponents<Follows, Likes>
+                triangles <- N<User>::TriangleCount::WHERE(_::{score}::GT(0.0))
+                central <- N<User>::Betweenness<Follows>(50)
+                RETURN ranked::{name, score}, components, triangles, central
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_computed_properties_only_exist_on_step_output() {
+        let source = r#"
+            N::City { name: String }
+
+            QUERY test() =>
+                far <- N<City>::WHERE(_::{distance}::GT(1.0))
+                ranked <- N<City>::ORDER<Desc>(_::{score})
+                RETURN far, ranked
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let unknown_fields = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E202)
+            .count();
+        assert_eq!(unknown_fields, 2, "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_schema_field_named_like_computed_property_keeps_its_type() {
+        let source = r#"
+            N::User { name: String, score: I64 }
+            E::Follows { From: User, To: User }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(_::{score}::GT(1))
+                ranked <- N<User>::PageRank<Follows>::WHERE(_::{score}::GT(0.5))
+                RETURN users::{name, score}, ranked
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_graph_algorithm_too_many_arguments() {
+        let source = r#"
+            N::User { name: String }
+            E::Follows { From: User, To: User }
+
+            QUERY test() =>
+                communities <- N<User>::LabelPropagation<Follows>(10, 20)
+                RETURN communities
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E216));
+    }
+
+    #[test]
+    fn test_graph_algorithm_unknown_edge_type() {
+        let source = r#"
+            N::User { name: String }
+
+            QUERY test() =>
+                components <- N<User>::StronglyConnectedComponents<Follows>
+                RETURN components
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E102));
+    }
 }
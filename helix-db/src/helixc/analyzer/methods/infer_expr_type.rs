@@ -11,28 +11,34 @@ use crate::{
             methods::traversal_validation::validate_traversal,
             types::Type,
             utils::{
-                gen_id_access_or_param, gen_identifier_or_param, is_valid_identifier, type_in_scope,
+                check_enum_variant, gen_enum_variant, gen_id_access_or_param,
+                gen_identifier_or_param, gen_optional_param_for_field,
+                get_field_type_from_item_fields, is_valid_identifier, resolve_search_type,
+                type_in_scope, validate_field_name_existence_for_item_type,
             },
         },
         generator::{
             bool_ops::BoExp,
             queries::Query as GeneratedQuery,
+            scalar_functions::{ScalarArg, ScalarFunctionCallGen},
             source_steps::{
                 AddE, AddN, AddV, SearchBM25, SearchVector as GeneratedSearchVector, SourceStep,
+                UpsertE, UpsertN,
             },
-            statements::Statement as GeneratedStatement,
+            statements::{Coalesce as GeneratedCoalesce, Statement as GeneratedStatement},
             traversal_steps::{
-                ShouldCollect, Step as GeneratedStep, Traversal as GeneratedTraversal,
-                TraversalType, Where, WhereRef,
+                SetEmbedding, ShouldCollect, Step as GeneratedStep,
+                Traversal as GeneratedTraversal, TraversalType, Where, WhereRef,
             },
             utils::{GenRef, GeneratedValue, Separator, VecData},
         },
-        parser::types::*,
+        parser::{location::Loc, types::*},
     },
-    protocol::date::Date,
+    protocol::{date::Date, functions::DATE_UNITS},
 };
 use paste::paste;
-use std::collections::HashMap;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 
 /// Infer the end type of an expression and returns the statement to generate from the expression
 ///
@@ -57,6 +63,28 @@ pub(crate) fn infer_expr_type<'a>(
     original_query: &'a Query,
     parent_ty: Option<Type>,
     gen_query: &mut GeneratedQuery,
+) -> (Type, Option<GeneratedStatement>) {
+    let (ty, stmt) = infer_type(ctx, expression, scope, original_query, parent_ty, gen_query);
+    // recorded for editor tooling, e.g. hovers in the language server
+    ctx.expr_types.push((
+        Loc::new(
+            original_query.loc.filepath.clone(),
+            expression.loc.start,
+            expression.loc.end,
+            expression.loc.span.clone(),
+        ),
+        ty.clone(),
+    ));
+    (ty, stmt)
+}
+
+fn infer_type<'a>(
+    ctx: &mut Ctx<'a>,
+    expression: &'a Expression,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    parent_ty: Option<Type>,
+    gen_query: &mut GeneratedQuery,
 ) -> (Type, Option<GeneratedStatement>) {
     use ExpressionType::*;
     let expr: &ExpressionType = &expression.expr;
@@ -179,7 +207,7 @@ pub(crate) fn infer_expr_type<'a>(
 
                 // Validate fields of add node by traversing the fields
                 // checking they exist in the schema, then checking their types
-                let (properties, secondary_indices) = match &add.fields {
+                let (mut properties, secondary_indices) = match &add.fields {
                     Some(fields_to_add) => {
                         let field_set_from_schema = ctx.node_fields.get(ty.as_str()).cloned();
                         if let Some(field_set) = field_set_from_schema {
@@ -253,7 +281,17 @@ pub(crate) fn infer_expr_type<'a>(
                                             Some(fields) => match fields.get(field_name.as_str()) {
                                                 Some(field) => {
                                                     let field_type = field.field_type.clone();
-                                                    if field_type != *value {
+                                                    if let Some(enum_schema) =
+                                                        ctx.get_enum(&field_type)
+                                                    {
+                                                        check_enum_variant(
+                                                            ctx,
+                                                            original_query,
+                                                            loc.clone(),
+                                                            enum_schema,
+                                                            &value.inner_stringify(),
+                                                        );
+                                                    } else if field_type != *value {
                                                         generate_error!(
                                                             ctx,
                                                             original_query,
@@ -309,9 +347,19 @@ pub(crate) fn infer_expr_type<'a>(
                                                                     GeneratedValue::Unknown
                                                                 }
                                                             },
-                                                            false => GeneratedValue::Literal(
-                                                                GenRef::from(value.clone()),
-                                                            ),
+                                                            false => match ctx
+                                                                .get_enum(&field.field_type)
+                                                            {
+                                                                Some(enum_schema) => {
+                                                                    gen_enum_variant(
+                                                                        enum_schema,
+                                                                        &value.inner_stringify(),
+                                                                    )
+                                                                }
+                                                                None => GeneratedValue::Literal(
+                                                                    GenRef::from(value.clone()),
+                                                                ),
+                                                            },
                                                         }
                                                     }
                                                     None => {
@@ -325,13 +373,32 @@ pub(crate) fn infer_expr_type<'a>(
                                                 }
                                             }
                                         }
-                                        ValueType::Identifier { value, .. } => {
-                                            gen_identifier_or_param(
-                                                original_query,
-                                                value,
-                                                true,
-                                                false,
-                                            )
+                                        ValueType::Identifier { value, loc } => {
+                                            let field = ctx
+                                                .node_fields
+                                                .get(ty.as_str())
+                                                .and_then(|fields| fields.get(field_name.as_str()))
+                                                .map(|field| field.as_ref().clone());
+                                            field
+                                                .and_then(|field| {
+                                                    gen_optional_param_for_field(
+                                                        ctx,
+                                                        original_query,
+                                                        loc.clone(),
+                                                        value,
+                                                        &field,
+                                                        "node",
+                                                        ty.as_str(),
+                                                    )
+                                                })
+                                                .unwrap_or_else(|| {
+                                                    gen_identifier_or_param(
+                                                        original_query,
+                                                        value,
+                                                        true,
+                                                        false,
+                                                    )
+                                                })
                                         }
                                         v => {
                                             generate_error!(
@@ -346,7 +413,7 @@ pub(crate) fn infer_expr_type<'a>(
                                     },
                                 )
                             })
-                            .collect::<HashMap<String, GeneratedValue>>();
+                            .collect::<BTreeMap<String, GeneratedValue>>();
 
                         for (field_name, default_value) in default_properties {
                             if !properties.contains_key(field_name.as_str()) {
@@ -373,7 +440,7 @@ pub(crate) fn infer_expr_type<'a>(
                     }
                     None => (
                         default_properties.into_iter().fold(
-                            HashMap::new(),
+                            BTreeMap::new(),
                             |mut acc, (field_name, default_value)| {
                                 acc.insert(field_name, default_value);
                                 acc
@@ -383,6 +450,19 @@ pub(crate) fn infer_expr_type<'a>(
                     ),
                 };
 
+                // embedded vectors are inserted once the node exists
+                let embeddings = ctx
+                    .get_embeddings(ty)
+                    .into_iter()
+                    .filter_map(|(field, vector_type)| {
+                        properties.remove(&field).map(|value| SetEmbedding {
+                            field: GenRef::Literal(field),
+                            label: GenRef::Literal(vector_type),
+                            vec: VecData::Standard(value),
+                        })
+                    })
+                    .collect();
+
                 let add_n = AddN {
                     label,
                     properties: Some(properties.into_iter().collect()),
@@ -394,6 +474,7 @@ pub(crate) fn infer_expr_type<'a>(
                     steps: vec![],
                     traversal_type: TraversalType::Mut,
                     should_collect: ShouldCollect::ToObj,
+                    embeddings,
                     ..Default::default()
                 });
                 gen_query.is_mut = true;
@@ -507,7 +588,17 @@ pub(crate) fn infer_expr_type<'a>(
                                             Some(fields) => match fields.get(field_name.as_str()) {
                                                 Some(field) => {
                                                     let field_type = field.field_type.clone();
-                                                    if field_type != *value {
+                                                    if let Some(enum_schema) =
+                                                        ctx.get_enum(&field_type)
+                                                    {
+                                                        check_enum_variant(
+                                                            ctx,
+                                                            original_query,
+                                                            loc.clone(),
+                                                            enum_schema,
+                                                            &value.inner_stringify(),
+                                                        );
+                                                    } else if field_type != *value {
                                                         generate_error!(
                                                             ctx,
                                                             original_query,
@@ -563,9 +654,19 @@ pub(crate) fn infer_expr_type<'a>(
                                                                     GeneratedValue::Unknown
                                                                 }
                                                             },
-                                                            false => GeneratedValue::Literal(
-                                                                GenRef::from(value.clone()),
-                                                            ),
+                                                            false => match ctx
+                                                                .get_enum(&field.field_type)
+                                                            {
+                                                                Some(enum_schema) => {
+                                                                    gen_enum_variant(
+                                                                        enum_schema,
+                                                                        &value.inner_stringify(),
+                                                                    )
+                                                                }
+                                                                None => GeneratedValue::Literal(
+                                                                    GenRef::from(value.clone()),
+                                                                ),
+                                                            },
                                                         }
                                                     }
                                                     None => {
@@ -586,12 +687,31 @@ pub(crate) fn infer_expr_type<'a>(
                                                 loc.clone(),
                                                 value.as_str(),
                                             );
-                                            gen_identifier_or_param(
-                                                original_query,
-                                                value.as_str(),
-                                                false,
-                                                true,
-                                            )
+                                            let field = ctx
+                                                .edge_fields
+                                                .get(ty.as_str())
+                                                .and_then(|fields| fields.get(field_name.as_str()))
+                                                .map(|field| field.as_ref().clone());
+                                            field
+                                                .and_then(|field| {
+                                                    gen_optional_param_for_field(
+                                                        ctx,
+                                                        original_query,
+                                                        loc.clone(),
+                                                        value,
+                                                        &field,
+                                                        "edge",
+                                                        ty.as_str(),
+                                                    )
+                                                })
+                                                .unwrap_or_else(|| {
+                                                    gen_identifier_or_param(
+                                                        original_query,
+                                                        value.as_str(),
+                                                        false,
+                                                        true,
+                                                    )
+                                                })
                                         }
                                         v => {
                                             generate_error!(
@@ -606,7 +726,7 @@ pub(crate) fn infer_expr_type<'a>(
                                     },
                                 )
                             })
-                            .collect::<HashMap<String, GeneratedValue>>();
+                            .collect::<BTreeMap<String, GeneratedValue>>();
 
                         for (field_name, default_value) in default_properties.clone() {
                             if !properties.contains_key(field_name.as_str()) {
@@ -862,7 +982,17 @@ pub(crate) fn infer_expr_type<'a>(
                                             Some(fields) => match fields.get(field_name.as_str()) {
                                                 Some(field) => {
                                                     let field_type = field.field_type.clone();
-                                                    if field_type != *value {
+                                                    if let Some(enum_schema) =
+                                                        ctx.get_enum(&field_type)
+                                                    {
+                                                        check_enum_variant(
+                                                            ctx,
+                                                            original_query,
+                                                            loc.clone(),
+                                                            enum_schema,
+                                                            &value.inner_stringify(),
+                                                        );
+                                                    } else if field_type != *value {
                                                         generate_error!(
                                                             ctx,
                                                             original_query,
@@ -918,9 +1048,19 @@ pub(crate) fn infer_expr_type<'a>(
                                                                     GeneratedValue::Unknown
                                                                 }
                                                             },
-                                                            false => GeneratedValue::Literal(
-                                                                GenRef::from(value.clone()),
-                                                            ),
+                                                            false => match ctx
+                                                                .get_enum(&field.field_type)
+                                                            {
+                                                                Some(enum_schema) => {
+                                                                    gen_enum_variant(
+                                                                        enum_schema,
+                                                                        &value.inner_stringify(),
+                                                                    )
+                                                                }
+                                                                None => GeneratedValue::Literal(
+                                                                    GenRef::from(value.clone()),
+                                                                ),
+                                                            },
                                                         }
                                                     }
                                                     None => {
@@ -941,12 +1081,31 @@ pub(crate) fn infer_expr_type<'a>(
                                                 loc.clone(),
                                                 value.as_str(),
                                             );
-                                            gen_identifier_or_param(
-                                                original_query,
-                                                value.as_str(),
-                                                false,
-                                                true,
-                                            )
+                                            let field = ctx
+                                                .vector_fields
+                                                .get(ty.as_str())
+                                                .and_then(|fields| fields.get(field_name.as_str()))
+                                                .map(|field| field.as_ref().clone());
+                                            field
+                                                .and_then(|field| {
+                                                    gen_optional_param_for_field(
+                                                        ctx,
+                                                        original_query,
+                                                        loc.clone(),
+                                                        value,
+                                                        &field,
+                                                        "vector",
+                                                        ty.as_str(),
+                                                    )
+                                                })
+                                                .unwrap_or_else(|| {
+                                                    gen_identifier_or_param(
+                                                        original_query,
+                                                        value.as_str(),
+                                                        false,
+                                                        true,
+                                                    )
+                                                })
                                         }
                                         v => {
                                             generate_error!(
@@ -961,7 +1120,7 @@ pub(crate) fn infer_expr_type<'a>(
                                     },
                                 )
                             })
-                            .collect::<HashMap<String, GeneratedValue>>();
+                            .collect::<BTreeMap<String, GeneratedValue>>();
 
                         for (field_name, default_value) in default_properties.clone() {
                             if !properties.contains_key(field_name.as_str()) {
@@ -973,7 +1132,7 @@ pub(crate) fn infer_expr_type<'a>(
                     }
                     None => {
                         default_properties.into_iter().fold(
-                            HashMap::new(),
+                            BTreeMap::new(),
                             |mut acc, (field_name, default_value)| {
                                 acc.insert(field_name, default_value);
                                 acc
@@ -1058,11 +1217,14 @@ pub(crate) fn infer_expr_type<'a>(
         //     Type::Vector(add.vector_type.as_deref())
         // }
         SearchVector(sv) => {
-            if let Some(ref ty) = sv.vector_type
-                && !ctx.vector_set.contains(ty.as_str())
-            {
-                generate_error!(ctx, original_query, sv.loc.clone(), E103, ty.as_str());
-            }
+            let (vector_type, owner) = match &sv.vector_type {
+                Some(ty) => {
+                    let (vector_type, owner) =
+                        resolve_search_type(ctx, original_query, sv.loc.clone(), ty);
+                    (Some(vector_type), owner)
+                }
+                None => (None, None),
+            };
             let vec: VecData = match &sv.data {
                 Some(VectorData::Vector(v)) => {
                     VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
@@ -1169,13 +1331,13 @@ pub(crate) fn infer_expr_type<'a>(
                         expr,
                         scope,
                         original_query,
-                        Some(Type::Vector(sv.vector_type.clone())),
+                        Some(Type::Vector(vector_type.clone())),
                         gen_query,
                     );
                     // Where/boolean ops don't change the element type,
                     // so `cur_ty` stays the same.
                     if stmt.is_none() {
-                        return (Type::Vector(sv.vector_type.clone()), None);
+                        return (Type::Vector(vector_type.clone()), None);
                     }
                     let stmt = stmt.unwrap();
                     let mut gen_traversal = GeneratedTraversal {
@@ -1215,16 +1377,23 @@ pub(crate) fn infer_expr_type<'a>(
                 None => None,
             };
 
-            // Search returns nodes that contain the vectors
+            // Searching a node type joins the vectors back to the nodes embedding them
+            let (ty, steps) = match owner {
+                Some(owner) => (
+                    Type::Nodes(sv.vector_type.clone()).with_computed_property("score", FieldType::F64),
+                    vec![Separator::Period(GeneratedStep::OwnerN(owner))],
+                ),
+                None => (Type::Vectors(vector_type.clone()), vec![]),
+            };
             (
-                Type::Vectors(sv.vector_type.clone()),
+                ty,
                 Some(GeneratedStatement::Traversal(GeneratedTraversal {
                     traversal_type: TraversalType::Ref,
-                    steps: vec![],
+                    steps,
                     should_collect: ShouldCollect::ToVec,
                     source_step: Separator::Period(SourceStep::SearchVector(
                         GeneratedSearchVector {
-                            label: GenRef::Literal(sv.vector_type.clone().unwrap()),
+                            label: GenRef::Literal(vector_type.unwrap()),
                             vec,
                             k,
                             pre_filter,
@@ -1346,6 +1515,13 @@ pub(crate) fn infer_expr_type<'a>(
             }
         }
         Exists(expr) => {
+            // `EXISTS(_::{field})` checks the field for null like `IS_NULL` does
+            if let Traversal(traversal) = &expr.expr.expr
+                && let Some(StepType::Object(obj)) = traversal.steps.last().map(|step| &step.step)
+                && let Some(field) = obj.fields.first()
+            {
+                ctx.null_checked_fields.insert(field.key.clone());
+            }
             let (_, stmt) =
                 infer_expr_type(ctx, &expr.expr, scope, original_query, parent_ty, gen_query);
             if stmt.is_none() {
@@ -1387,6 +1563,26 @@ pub(crate) fn infer_expr_type<'a>(
                 Some(GeneratedStatement::BoExp(BoExp::Exists(traversal))),
             )
         }
+        ScalarFunctionCall(call) => {
+            let Some((ty, gen_call)) = gen_scalar_function_call(
+                ctx,
+                call,
+                scope,
+                original_query,
+                parent_ty,
+                None,
+                gen_query,
+            ) else {
+                return (Type::Unknown, None);
+            };
+            match ty {
+                Type::Boolean => (
+                    Type::Boolean,
+                    Some(GeneratedStatement::BoExp(BoExp::ScalarFunction(gen_call))),
+                ),
+                ty => (ty, Some(GeneratedStatement::ScalarFunction(gen_call))),
+            }
+        }
         MathFunctionCall(_math_call) => {
             // Math function calls always return f64
             // TODO: Add proper type inference and validation for math function arguments
@@ -1395,6 +1591,163 @@ pub(crate) fn infer_expr_type<'a>(
                 None, // Will be handled by generator
             )
         }
+        UpsertNode(upsert) => {
+            let (ty, stmt) = infer_expr_type(
+                ctx,
+                &upsert.insert,
+                scope,
+                original_query,
+                parent_ty,
+                gen_query,
+            );
+            let Some(GeneratedStatement::Traversal(tr)) = stmt else {
+                return (ty, None);
+            };
+            let SourceStep::AddN(add_n) = tr.source_step.inner() else {
+                return (ty, None);
+            };
+            let node_type = ty.get_type_name();
+            let match_field = upsert.match_field.1.as_str();
+
+            let is_indexed = ctx
+                .node_fields
+                .get(node_type.as_str())
+                .and_then(|fields| fields.get(match_field))
+                .map(|field| field.prefix.is_indexed());
+            if is_indexed == Some(false) {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    upsert.match_field.0.clone(),
+                    E213,
+                    [match_field, &node_type],
+                    [match_field]
+                );
+            }
+
+            let properties = add_n.properties.clone().unwrap_or_default();
+            let key = properties
+                .iter()
+                .find_map(|(name, value)| (name == match_field).then(|| value.clone()))
+                .unwrap_or(GeneratedValue::Unknown);
+            let update_properties = properties
+                .iter()
+                .filter(|(name, _)| upsert.update_fields.contains(name))
+                .cloned()
+                .collect();
+
+            let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
+                source_step: Separator::Period(SourceStep::UpsertN(UpsertN {
+                    label: add_n.label.clone(),
+                    index: GenRef::Literal(match_field.to_string()),
+                    key,
+                    properties: Some(properties),
+                    update_properties,
+                    secondary_indices: add_n.secondary_indices.clone(),
+                })),
+                steps: vec![],
+                traversal_type: TraversalType::Mut,
+                should_collect: ShouldCollect::Try,
+                ..Default::default()
+            });
+            (ty, Some(stmt))
+        }
+        UpsertEdge(upsert) => {
+            let (ty, stmt) = infer_expr_type(
+                ctx,
+                &upsert.insert,
+                scope,
+                original_query,
+                parent_ty,
+                gen_query,
+            );
+            let Some(GeneratedStatement::Traversal(tr)) = stmt else {
+                return (ty, None);
+            };
+            let SourceStep::AddE(add_e) = tr.source_step.inner() else {
+                return (ty, None);
+            };
+
+            if add_e.from_is_plural || add_e.to_is_plural {
+                if let ExpressionType::AddEdge(add) = &upsert.insert.expr {
+                    let plural = match add_e.from_is_plural {
+                        true => add.connection.from_id.as_ref(),
+                        false => add.connection.to_id.as_ref(),
+                    };
+                    if let Some(IdType::Identifier { value, loc }) = plural {
+                        generate_error!(ctx, original_query, loc.clone(), E309, value.as_str());
+                    }
+                }
+                return (Type::Unknown, None);
+            }
+
+            // only the fields written in the query overwrite an existing edge
+            let update_properties = match (&upsert.insert.expr, &add_e.properties) {
+                (ExpressionType::AddEdge(add), Some(properties)) => properties
+                    .iter()
+                    .filter(|(name, _)| {
+                        add.fields
+                            .as_ref()
+                            .is_some_and(|fields| fields.contains_key(name.as_str()))
+                    })
+                    .cloned()
+                    .collect(),
+                _ => vec![],
+            };
+
+            let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
+                source_step: Separator::Period(SourceStep::UpsertE(UpsertE {
+                    label: add_e.label.clone(),
+                    properties: add_e.properties.clone(),
+                    update_properties,
+                    from: add_e.from.clone(),
+                    to: add_e.to.clone(),
+                })),
+                steps: vec![],
+                traversal_type: TraversalType::Mut,
+                should_collect: ShouldCollect::Try,
+                ..Default::default()
+            });
+            (ty, Some(stmt))
+        }
+        Coalesce(coalesce) => {
+            let (value_ty, value) =
+                infer_coalesce_arg(ctx, &coalesce.value, scope, original_query, gen_query);
+            let (fallback_ty, fallback) =
+                infer_coalesce_arg(ctx, &coalesce.fallback, scope, original_query, gen_query);
+
+            // both sides end up as a `Value`, so any two numbers are interchangeable
+            let compatible = match (&value_ty, &fallback_ty) {
+                (Type::Unknown, _) | (_, Type::Unknown) => true,
+                (a, b) if a.is_numeric() && b.is_numeric() => true,
+                (Type::Scalar(a), Type::Scalar(b)) => a == b,
+                (Type::Boolean | Type::Scalar(FieldType::Boolean), Type::Boolean)
+                | (Type::Boolean, Type::Scalar(FieldType::Boolean)) => true,
+                _ => false,
+            };
+            if !compatible {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    coalesce.loc.clone(),
+                    E307,
+                    value_ty.kind_str(),
+                    fallback_ty.kind_str()
+                );
+                return (Type::Unknown, None);
+            }
+
+            match (value, fallback) {
+                (Some(value), Some(fallback)) => (
+                    value_ty,
+                    Some(GeneratedStatement::Coalesce(GeneratedCoalesce {
+                        value: Box::new(value),
+                        fallback: Box::new(fallback),
+                    })),
+                ),
+                _ => (Type::Unknown, None),
+            }
+        }
         Empty => (Type::Unknown, Some(GeneratedStatement::Empty)),
         BM25Search(bm25_search) => {
             if let Some(ref ty) = bm25_search.type_arg
@@ -1522,6 +1875,420 @@ pub(crate) fn infer_expr_type<'a>(
     }
 }
 
+/// Infers an argument of `COALESCE`, reading parameters straight off `data` so that
+/// an optional parameter keeps its `Option` and a missing value can fall through.
+fn infer_coalesce_arg<'a>(
+    ctx: &mut Ctx<'a>,
+    expr: &'a Expression,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    gen_query: &mut GeneratedQuery,
+) -> (Type, Option<GeneratedStatement>) {
+    let (ty, stmt) = infer_expr_type(ctx, expr, scope, original_query, None, gen_query);
+    let stmt = match (&expr.expr, stmt) {
+        (ExpressionType::Identifier(name), Some(_)) => {
+            Some(GeneratedStatement::Identifier(GenRef::Std(
+                match is_param(original_query, name) {
+                    Some(_) => format!("data.{name}.clone()"),
+                    None => format!("{name}.clone()"),
+                },
+            )))
+        }
+        (_, stmt) => stmt,
+    };
+    match ty {
+        Type::Scalar(_) | Type::Boolean | Type::Unknown => (ty, stmt),
+        _ => {
+            generate_error!(
+                ctx,
+                original_query,
+                expr.loc.clone(),
+                E307,
+                ty.kind_str(),
+                "scalar"
+            );
+            (Type::Unknown, None)
+        }
+    }
+}
+
+/// The type a string or date function evaluates to.
+/// `APPEND` and `REMOVE` evaluate to the type of the array they change.
+fn scalar_function_type(function: ScalarFunction) -> Type {
+    match function {
+        ScalarFunction::Lower
+        | ScalarFunction::Upper
+        | ScalarFunction::Concat
+        | ScalarFunction::Substring => Type::Scalar(FieldType::String),
+        ScalarFunction::StartsWith | ScalarFunction::EndsWith | ScalarFunction::RegexMatch => {
+            Type::Boolean
+        }
+        ScalarFunction::Length | ScalarFunction::DateDiff => Type::Scalar(FieldType::I64),
+        ScalarFunction::DateTrunc | ScalarFunction::DateAdd | ScalarFunction::Now => {
+            Type::Scalar(FieldType::Date)
+        }
+        ScalarFunction::Append | ScalarFunction::Remove => Type::Unknown,
+    }
+}
+
+/// What a string or date function accepts at a given argument position
+#[derive(Clone, Copy, PartialEq)]
+enum ScalarArgKind {
+    String,
+    Integer,
+    /// A `Date`, or a `String` parsed as one at runtime
+    Date,
+    /// A date unit such as `"day"`, validated when given as a literal
+    Unit,
+    /// A regular expression, validated when given as a literal
+    Pattern,
+    Array,
+    /// An element of an array, or an array of them
+    Element,
+    StringOrArray,
+    Any,
+}
+
+impl ScalarArgKind {
+    fn of(function: ScalarFunction, idx: usize) -> Self {
+        use ScalarArgKind::*;
+        let kinds: &[ScalarArgKind] = match function {
+            ScalarFunction::Lower | ScalarFunction::Upper => &[String],
+            ScalarFunction::Concat | ScalarFunction::Now => &[],
+            ScalarFunction::Substring => &[String, Integer, Integer],
+            ScalarFunction::StartsWith | ScalarFunction::EndsWith => &[String, String],
+            ScalarFunction::RegexMatch => &[String, Pattern],
+            ScalarFunction::Length => &[StringOrArray],
+            ScalarFunction::DateTrunc => &[Date, Unit],
+            ScalarFunction::DateAdd => &[Date, Integer, Unit],
+            ScalarFunction::DateDiff => &[Date, Date, Unit],
+            // the value is checked against the element type once the array is known
+            ScalarFunction::Append | ScalarFunction::Remove => &[Array, Element],
+        };
+        kinds.get(idx).copied().unwrap_or(Any)
+    }
+
+    fn accepts(&self, ty: &Type) -> bool {
+        match self {
+            ScalarArgKind::String | ScalarArgKind::Unit | ScalarArgKind::Pattern => {
+                matches!(ty, Type::Scalar(FieldType::String))
+            }
+            ScalarArgKind::Integer => ty.is_integer(),
+            ScalarArgKind::Date => {
+                matches!(ty, Type::Scalar(FieldType::Date | FieldType::String))
+            }
+            ScalarArgKind::Array => {
+                matches!(ty, Type::Scalar(FieldType::Array(_)) | Type::Array(_))
+            }
+            ScalarArgKind::Element => {
+                matches!(ty, Type::Scalar(_) | Type::Boolean | Type::Array(_))
+            }
+            ScalarArgKind::StringOrArray => matches!(
+                ty,
+                Type::Scalar(FieldType::String | FieldType::Array(_)) | Type::Array(_)
+            ),
+            ScalarArgKind::Any => matches!(ty, Type::Scalar(_) | Type::Boolean),
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            ScalarArgKind::String => "a `String`",
+            ScalarArgKind::Integer => "an integer",
+            ScalarArgKind::Date => "a `Date`",
+            ScalarArgKind::Unit => "a `String` date unit",
+            ScalarArgKind::Pattern => "a `String` regular expression",
+            ScalarArgKind::Array => "an array",
+            ScalarArgKind::Element => "an array element or an array",
+            ScalarArgKind::StringOrArray => "a `String` or an array",
+            ScalarArgKind::Any => "a scalar value",
+        }
+    }
+}
+
+/// Validates the arguments of a string or date function and generates the call.
+///
+/// Arguments can be literals, parameters, variables, nested calls or, when `parent_ty` is
+/// an item, one of its properties (`_::{field}`). Literal date units and regular
+/// expressions are checked here so they can't fail at runtime.
+///
+/// `updated_field` is the field an `UPDATE` value is assigned to, which `APPEND(value)`
+/// and `REMOVE(value)` change when they're given no array. Returns the type the call
+/// evaluates to alongside it.
+pub(crate) fn gen_scalar_function_call<'a>(
+    ctx: &mut Ctx<'a>,
+    call: &'a ScalarFunctionCall,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    parent_ty: Option<Type>,
+    updated_field: Option<(&str, &FieldType)>,
+    gen_query: &mut GeneratedQuery,
+) -> Option<(Type, ScalarFunctionCallGen)> {
+    let function = call.function;
+    let mut valid = true;
+    let mut args = Vec::with_capacity(call.args.len() + 1);
+    let mut arg_types = Vec::with_capacity(call.args.len() + 1);
+    let is_array_function = matches!(function, ScalarFunction::Append | ScalarFunction::Remove);
+    let offset = usize::from(is_array_function && call.args.len() == 1);
+    if offset == 1 {
+        match updated_field {
+            Some((field, field_type @ FieldType::Array(_))) => {
+                args.push(ScalarArg::Property(field.to_string()));
+                arg_types.push(Type::Scalar(field_type.clone()));
+            }
+            Some((field, field_type)) => {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    call.loc.clone(),
+                    E665,
+                    [function.name(), field, &field_type.to_string()],
+                    [field]
+                );
+                valid = false;
+            }
+            None => {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    call.loc.clone(),
+                    E664,
+                    [function.name()],
+                    [function.name()]
+                );
+                valid = false;
+            }
+        }
+    }
+    for (idx, arg) in call.args.iter().enumerate() {
+        let idx = idx + offset;
+        let kind = ScalarArgKind::of(function, idx);
+        let (ty, gen_arg) = match &arg.expr {
+            ExpressionType::Traversal(tr) => {
+                let property = match (&tr.start, tr.steps.as_slice(), &parent_ty) {
+                    (StartNode::Anonymous, [step], Some(item_ty))
+                        if matches!(
+                            item_ty.base(),
+                            Type::Node(_)
+                                | Type::Nodes(_)
+                                | Type::Edge(_)
+                                | Type::Edges(_)
+                                | Type::Vector(_)
+                                | Type::Vectors(_)
+                        ) =>
+                    {
+                        match &step.step {
+                            StepType::Object(obj) => match obj.fields.as_slice() {
+                                [field] => match &field.value.value {
+                                    FieldValueType::Identifier(name) => Some((item_ty, name)),
+                                    _ => None,
+                                },
+                                _ => None,
+                            },
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                let Some((item_ty, name)) = property else {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        arg.loc.clone(),
+                        E663,
+                        &arg.loc.span,
+                        function.name()
+                    );
+                    valid = false;
+                    continue;
+                };
+                let Some(field_ty) = get_field_type_from_item_fields(ctx, item_ty, name) else {
+                    validate_field_name_existence_for_item_type(
+                        ctx,
+                        original_query,
+                        arg.loc.clone(),
+                        item_ty,
+                        name,
+                    );
+                    valid = false;
+                    continue;
+                };
+                // implicit fields aren't stored as properties
+                if matches!(field_ty, FieldType::Uuid) || name == "label" {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        arg.loc.clone(),
+                        E663,
+                        &arg.loc.span,
+                        function.name()
+                    );
+                    valid = false;
+                    continue;
+                }
+                (Type::Scalar(field_ty), ScalarArg::Property(name.clone()))
+            }
+            ExpressionType::Identifier(name) => {
+                let (ty, stmt) = infer_expr_type(ctx, arg, scope, original_query, None, gen_query);
+                if stmt.is_none() {
+                    valid = false;
+                    continue;
+                }
+                let value = match is_param(original_query, name) {
+                    Some(_) => format!("&data.{name}"),
+                    None => format!("&{name}"),
+                };
+                (ty, ScalarArg::Value(value))
+            }
+            ExpressionType::StringLiteral(s) => {
+                let literal = s.replace("\\\\", "\\").replace("\\\"", "\"");
+                let invalid = match kind {
+                    ScalarArgKind::Unit => (!DATE_UNITS.contains(&literal.as_str())).then(|| {
+                        (
+                            "date unit",
+                            format!("one of {}", DATE_UNITS.map(|u| format!("`{u}`")).join(", ")),
+                        )
+                    }),
+                    ScalarArgKind::Pattern => Regex::new(&literal).err().map(|_| {
+                        (
+                            "regular expression",
+                            "a valid regular expression".to_string(),
+                        )
+                    }),
+                    _ => None,
+                };
+                if let Some((what, expected)) = invalid {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        arg.loc.clone(),
+                        E662,
+                        [what, s, function.name()],
+                        [&expected]
+                    );
+                    valid = false;
+                }
+                (
+                    Type::Scalar(FieldType::String),
+                    ScalarArg::Value(format!("\"{s}\"")),
+                )
+            }
+            ExpressionType::IntegerLiteral(i) => (
+                Type::Scalar(FieldType::I32),
+                ScalarArg::Value(i.to_string()),
+            ),
+            ExpressionType::FloatLiteral(f) => (
+                Type::Scalar(FieldType::F64),
+                ScalarArg::Value(format!("{f}_f64")),
+            ),
+            ExpressionType::BooleanLiteral(b) => (Type::Boolean, ScalarArg::Value(b.to_string())),
+            ExpressionType::ScalarFunctionCall(_) => {
+                match infer_expr_type(
+                    ctx,
+                    arg,
+                    scope,
+                    original_query,
+                    parent_ty.clone(),
+                    gen_query,
+                ) {
+                    (
+                        ty,
+                        Some(
+                            GeneratedStatement::ScalarFunction(call)
+                            | GeneratedStatement::BoExp(BoExp::ScalarFunction(call)),
+                        ),
+                    ) => (ty, ScalarArg::Call(Box::new(call))),
+                    _ => {
+                        valid = false;
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    arg.loc.clone(),
+                    E663,
+                    &arg.loc.span,
+                    function.name()
+                );
+                valid = false;
+                continue;
+            }
+        };
+        if !matches!(ty, Type::Unknown) && !kind.accepts(&ty) {
+            generate_error!(
+                ctx,
+                original_query,
+                arg.loc.clone(),
+                E661,
+                [
+                    &(idx + 1).to_string(),
+                    function.name(),
+                    kind.describe(),
+                    &ty.get_type_name()
+                ],
+                [kind.describe(), &(idx + 1).to_string(), function.name()]
+            );
+            valid = false;
+        }
+        args.push(gen_arg);
+        arg_types.push(ty);
+    }
+    // the length of `SUBSTRING` is optional
+    if function == ScalarFunction::Substring && args.len() == 2 {
+        args.push(ScalarArg::Empty);
+    }
+    let ty = match function {
+        ScalarFunction::Append | ScalarFunction::Remove if valid => {
+            // the value must be an element of the array, or an array of them
+            let element = match &arg_types[0] {
+                Type::Scalar(FieldType::Array(element)) => Some(element.as_ref()),
+                Type::Array(element) => match element.as_ref() {
+                    Type::Scalar(element) => Some(element),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let value_ty = &arg_types[1];
+            let accepted = match (element, value_ty) {
+                (None, _) | (_, Type::Unknown) => true,
+                (Some(element), Type::Scalar(FieldType::Array(value))) => element == value.as_ref(),
+                (Some(element), Type::Array(value)) => {
+                    matches!(value.as_ref(), Type::Scalar(value) if element == value)
+                }
+                (Some(element), Type::Scalar(value)) => element == value,
+                (Some(element), Type::Boolean) => element == &FieldType::Boolean,
+                _ => false,
+            };
+            if let Some(element) = element
+                && !accepted
+            {
+                let expected = format!("a `{element}` or `[{element}]`");
+                let position = (2 - offset).to_string();
+                generate_error!(
+                    ctx,
+                    original_query,
+                    call.args[1 - offset].loc.clone(),
+                    E661,
+                    [
+                        &position,
+                        function.name(),
+                        &expected,
+                        &value_ty.get_type_name()
+                    ],
+                    [&expected, &position, function.name()]
+                );
+                valid = false;
+            }
+            arg_types.swap_remove(0)
+        }
+        function => scalar_function_type(function),
+    };
+    valid.then_some((ty, ScalarFunctionCallGen { function, args }))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::helixc::analyzer::error_codes::ErrorCode;
@@ -1569,6 +2336,92 @@ mod tests {
         assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E101));
     }
 
+    #[test]
+    fn test_add_node_with_enum_field() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY createUser(name: String, status: Status) =>
+                active <- AddN<User>({name: name, status: "Active"})
+                user <- AddN<User>({name: name, status: status})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("(\"status\", Value::from(Status::Active))"));
+        assert!(output.contains("#[serde(serialize_with = \"Status::serialize_property\")]"));
+    }
+
+    #[test]
+    fn test_add_node_with_unknown_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY createUser(name: String) =>
+                user <- AddN<User>({name: name, status: "Deleted"})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E212));
+        assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E205));
+    }
+
+    #[test]
+    fn test_add_node_optional_param_to_optional_field() {
+        let source = r#"
+            N::Person { name: String, nickname: String?, age: U32? DEFAULT 0 }
+
+            QUERY test(personName: String, nickname?: String, age?: U32) =>
+                person <- AddN<Person>({name: personName, nickname: nickname, age: age})
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("Value::from(&data.nickname)"));
+        assert!(output.contains("data.age.as_ref().map_or_else(|| Value::from(0u32), Value::from)"));
+    }
+
+    #[test]
+    fn test_add_node_optional_param_to_required_field() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(personName?: String) =>
+                person <- AddN<Person>({name: personName})
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E211));
+    }
+
     // ============================================================================
     // AddEdge Expression Tests
     // ============================================================================
@@ -1845,4 +2698,183 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
     }
+
+    // ============================================================================
+    // String and Date Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_scalar_functions_valid() {
+        let source = r#"
+            N::User { name: String, created_at: Date }
+
+            QUERY test(id: ID, prefix: String, days: I64) =>
+                users <- N<User>::WHERE(STARTS_WITH(LOWER(_::{name}), prefix))
+                recent <- N<User>::WHERE(_::{created_at}::GT(DATE_ADD(NOW(), days, "day")))
+                user <- N<User>(id)::UPDATE({name: CONCAT(UPPER(prefix), "-", SUBSTRING(prefix, 1))})
+                RETURN users::{ name, shout: UPPER(_::{name}), joined: DATE_TRUNC(_::{created_at}, "week") }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_scalar_function_argument_type_mismatch() {
+        let source = r#"
+            N::User { name: String, age: U32 }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(STARTS_WITH(_::{age}, "1"))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E661));
+    }
+
+    #[test]
+    fn test_scalar_function_invalid_literals() {
+        let source = r#"
+            N::User { name: String, created_at: Date }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(REGEX_MATCH(_::{name}, "(unclosed"))
+                RETURN users::{ joined: DATE_TRUNC(_::{created_at}, "fortnight") }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .filter(|d| d.error_code == ErrorCode::E662)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_scalar_function_property_outside_item() {
+        let source = r#"
+            N::User { name: String }
+
+            QUERY test(id: ID) =>
+                lower <- LOWER(_::{name})
+                RETURN lower
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E663));
+    }
+
+    // ============================================================================
+    // Array and Object Operator Tests
+    // ============================================================================
+
+    #[test]
+    fn test_array_operators_valid() {
+        let source = r#"
+            N::User { name: String, tags: [String], address: { city: String } }
+
+            QUERY tagged(tag: String, city: String) =>
+                users <- N<User>::WHERE(
+                    AND(_::{tags}::ANY(EQ(tag)), _::{address.city}::EQ(city))
+                )::{name, address.city, tag_count: LENGTH(_::{tags})}
+                RETURN users
+
+            QUERY tag(id: ID, tag: String, old: [String]) =>
+                user <- N<User>(id)::UPDATE({tags: APPEND(tag)})
+                user2 <- N<User>(id)::UPDATE({tags: REMOVE(old)})
+                RETURN user, user2
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_array_predicate_on_non_array() {
+        let source = r#"
+            N::User { name: String }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(_::{name}::ANY(EQ("a")))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E666));
+    }
+
+    #[test]
+    fn test_array_update_errors() {
+        let source = r#"
+            N::User { name: String, scores: [I64] }
+
+            QUERY test(id: ID) =>
+                named <- N<User>(id)::UPDATE({name: APPEND("x")})
+                scored <- N<User>(id)::UPDATE({scores: APPEND("x")})
+                appended <- APPEND("x")
+                RETURN named, scored, appended
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E665));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E661));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E664));
+    }
+
+    #[test]
+    fn test_nested_field_not_in_schema() {
+        let source = r#"
+            N::User { address: { city: String } }
+
+            QUERY test() =>
+                users <- N<User>::{street: address.street}
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
+    }
 }
@@ -5,6 +5,7 @@ use crate::helixc::analyzer::{
 };
 use crate::helixc::generator::source_steps::SourceStep;
 use crate::helixc::parser::errors::ParserError;
+use crate::helixc::parser::location::Loc;
 use crate::{
     generate_error,
     helixc::{
@@ -27,6 +28,27 @@ use crate::{
 use paste::paste;
 use std::{borrow::Cow, collections::HashMap};
 
+/// Records the type of a traversal nested in an object, like the expressions checked by
+/// `infer_expr_type`, so tooling can look it up
+fn record_nested_type(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    traversal: &Traversal,
+    ty: &Option<Type>,
+) {
+    if let Some(ty) = ty {
+        ctx.expr_types.push((
+            Loc::new(
+                original_query.loc.filepath.clone(),
+                traversal.loc.start,
+                traversal.loc.end,
+                traversal.loc.span.clone(),
+            ),
+            ty.clone(),
+        ));
+    }
+}
+
 /// Marks all Out/In steps with EdgeType::Vec in the traversal to fetch vector data
 /// This should be called when the 'data' field is accessed on a Vector type
 fn mark_vector_steps_for_data_fetch(gen_traversal: &mut GeneratedTraversal) {
@@ -97,46 +119,49 @@ pub(crate) fn validate_object<'a>(
     scope: &mut std::collections::HashMap<&'a str, crate::helixc::analyzer::utils::VariableInfo>,
     gen_query: &mut crate::helixc::generator::queries::Query,
 ) -> Result<Type, ParserError> {
-    match &cur_ty {
-        Type::Node(Some(node_ty)) | Type::Nodes(Some(node_ty)) => validate_property_access(
+    if let Type::Anonymous(ty) = cur_ty {
+        return validate_object(
             ctx,
+            ty,
             obj,
             original_query,
             gen_traversal,
-            cur_ty,
-            ctx.node_fields.get(node_ty.as_str()).cloned(),
             fields_out,
             scope,
             gen_query,
-        ),
-        Type::Edge(Some(edge_ty)) | Type::Edges(Some(edge_ty)) => validate_property_access(
+        );
+    }
+    // the properties set by the step producing the items are looked up on `cur_ty` itself
+    match cur_ty.base() {
+        Type::Node(Some(node_ty)) | Type::Nodes(Some(node_ty)) => validate_property_access(
             ctx,
             obj,
             original_query,
             gen_traversal,
             cur_ty,
-            ctx.edge_fields.get(edge_ty.as_str()).cloned(),
+            ctx.node_fields.get(node_ty.as_str()).cloned(),
             fields_out,
             scope,
             gen_query,
         ),
-        Type::Vector(Some(vector_ty)) | Type::Vectors(Some(vector_ty)) => validate_property_access(
+        Type::Edge(Some(edge_ty)) | Type::Edges(Some(edge_ty)) => validate_property_access(
             ctx,
             obj,
             original_query,
             gen_traversal,
             cur_ty,
-            ctx.vector_fields.get(vector_ty.as_str()).cloned(),
+            ctx.edge_fields.get(edge_ty.as_str()).cloned(),
             fields_out,
             scope,
             gen_query,
         ),
-        Type::Anonymous(ty) => validate_object(
+        Type::Vector(Some(vector_ty)) | Type::Vectors(Some(vector_ty)) => validate_property_access(
             ctx,
-            ty,
             obj,
             original_query,
             gen_traversal,
+            cur_ty,
+            ctx.vector_fields.get(vector_ty.as_str()).cloned(),
             fields_out,
             scope,
             gen_query,
@@ -222,7 +247,7 @@ fn validate_property_access<'a>(
     scope: &mut std::collections::HashMap<&'a str, crate::helixc::analyzer::utils::VariableInfo>,
     gen_query: &mut crate::helixc::generator::queries::Query,
 ) -> Result<Type, ParserError> {
-    match fields {
+    match &fields {
         Some(_) => {
             // if there is only one field then it is a single property access
             // e.g. N<User>::{name}
@@ -247,7 +272,7 @@ fn validate_property_access<'a>(
                         // Check if we're accessing the 'data' field on a Vector type
                         // If so, we need to mark vector traversal steps to fetch the data
                         if lit.as_str() == "data"
-                            && matches!(cur_ty, Type::Vector(_) | Type::Vectors(_))
+                            && matches!(cur_ty.base(), Type::Vector(_) | Type::Vectors(_))
                         {
                             mark_vector_steps_for_data_fetch(gen_traversal);
                         }
@@ -258,8 +283,15 @@ fn validate_property_access<'a>(
 
                         // Store the field name so nested traversal code generation can access it
                         gen_traversal.object_fields.push(lit.as_str().to_string());
+                        if fields
+                            .as_ref()
+                            .and_then(|fields| fields.get(lit.as_str()))
+                            .is_some_and(|field| field.is_nullable())
+                        {
+                            gen_traversal.nullable_fields.push(lit.as_str().to_string());
+                        }
 
-                        match cur_ty {
+                        match cur_ty.base() {
                             Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_) => {
                                 gen_traversal.should_collect = ShouldCollect::ToVec;
                             }
@@ -289,7 +321,7 @@ fn validate_property_access<'a>(
                 );
 
                 // If accessing 'data' field on Vector type, mark vector steps to fetch data
-                if data_field_accessed && matches!(cur_ty, Type::Vector(_) | Type::Vectors(_)) {
+                if data_field_accessed && matches!(cur_ty.base(), Type::Vector(_) | Type::Vectors(_)) {
                     mark_vector_steps_for_data_fetch(gen_traversal);
                 }
 
@@ -300,8 +332,28 @@ fn validate_property_access<'a>(
                 // Collect field names and nested traversals
                 for field_addition in &obj.fields {
                     match &field_addition.value.value {
+                        FieldValueType::Identifier(path) if path.contains('.') => {
+                            validate_field_name_existence_for_item_type(
+                                ctx,
+                                original_query,
+                                field_addition.value.loc.clone(),
+                                cur_ty,
+                                path,
+                            );
+                            gen_traversal
+                                .property_paths
+                                .insert(field_addition.key.clone(), path.clone());
+                            gen_traversal.object_fields.push(field_addition.key.clone());
+                        }
                         FieldValueType::Identifier(id) => {
                             gen_traversal.object_fields.push(id.clone());
+                            if fields
+                                .as_ref()
+                                .and_then(|fields| fields.get(id.as_str()))
+                                .is_some_and(|field| field.is_nullable())
+                            {
+                                gen_traversal.nullable_fields.push(id.clone());
+                            }
                         }
                         FieldValueType::Traversal(tr) => {
                             // Nested traversal - validate it now to get the type
@@ -343,6 +395,7 @@ fn validate_property_access<'a>(
                                 &mut nested_gen_traversal,
                                 gen_query,
                             );
+                            record_nested_type(ctx, original_query, tr, &nested_type);
 
                             // Check if this nested traversal ends with a Closure step
                             let own_closure_param = tr.steps.last()
@@ -384,6 +437,7 @@ fn validate_property_access<'a>(
                                     &mut nested_gen_traversal,
                                     gen_query,
                                 );
+                                record_nested_type(ctx, original_query, tr, &nested_type);
 
                                 // Check if this nested traversal ends with a Closure step
                                 let own_closure_param = tr.steps.last()
@@ -405,6 +459,31 @@ fn validate_property_access<'a>(
                                     .nested_traversals
                                     .insert(field_addition.key.clone(), nested_info);
                                 gen_traversal.object_fields.push(field_addition.key.clone());
+                            } else if let ExpressionType::ScalarFunctionCall(_) = &expr.expr {
+                                // String and date functions are evaluated per item when building the return value
+                                use crate::helixc::analyzer::methods::infer_expr_type::infer_expr_type;
+                                use crate::helixc::generator::statements::Statement as GeneratedStatement;
+                                use crate::helixc::generator::bool_ops::BoExp;
+
+                                if let (
+                                    _,
+                                    Some(
+                                        GeneratedStatement::ScalarFunction(call)
+                                        | GeneratedStatement::BoExp(BoExp::ScalarFunction(call)),
+                                    ),
+                                ) = infer_expr_type(
+                                    ctx,
+                                    expr,
+                                    scope,
+                                    original_query,
+                                    Some(cur_ty.clone()),
+                                    gen_query,
+                                ) {
+                                    gen_traversal
+                                        .computed_fields
+                                        .insert(field_addition.key.clone(), call);
+                                }
+                                gen_traversal.object_fields.push(field_addition.key.clone());
                             } else {
                                 // Other expression types (identifiers, literals, etc.)
                                 gen_traversal.object_fields.push(field_addition.key.clone());
@@ -418,7 +497,7 @@ fn validate_property_access<'a>(
                 }
 
                 // Set collection behavior based on current type
-                match cur_ty {
+                match cur_ty.base() {
                     Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_) => {
                         gen_traversal.should_collect = ShouldCollect::ToVec;
                     }
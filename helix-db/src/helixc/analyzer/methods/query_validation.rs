@@ -24,6 +24,7 @@ use crate::helixc::{
     parser::{location::Loc, types::*},
 };
 use paste::paste;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Helper to capitalize first letter of a string
@@ -35,6 +36,35 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+/// Names of the fields of a schema in the order they are declared, for the return structs to
+/// be generated the same way every time
+fn declared_field_names<'b>(fields: &HashMap<&'b str, Cow<'_, Field>>) -> Vec<&'b str> {
+    let mut names = fields
+        .iter()
+        .map(|(name, field)| (field.loc.start.line, field.loc.start.column, *name))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.into_iter().map(|(_, _, name)| name).collect()
+}
+
+/// The return field of a schema property. Enum properties hold the index of their variant, so
+/// they are serialized as the variant name
+fn schema_return_field(
+    ctx: &Ctx,
+    schema_fields: Option<&HashMap<&str, Cow<'_, Field>>>,
+    name: &str,
+) -> ReturnFieldInfo {
+    match schema_fields
+        .and_then(|fields| fields.get(name))
+        .and_then(|field| ctx.get_enum(&field.field_type))
+    {
+        Some(enum_schema) => {
+            ReturnFieldInfo::new_enum(name.to_string(), enum_schema.name.1.clone())
+        }
+        None => ReturnFieldInfo::new_schema(name.to_string(), "Option<&'a Value>".to_string()),
+    }
+}
+
 /// Build unified field list for return types
 /// This handles all cases: simple schema, projections, spread, nested traversals
 fn build_return_fields(
@@ -47,15 +77,18 @@ fn build_return_fields(
 
     // Handle aggregate types specially
     if let Type::Aggregate(info) = inferred_type {
-        // All aggregates have a key field (the grouping key from HashMap)
-        fields.push(ReturnFieldInfo::new_implicit(
-            "key".to_string(),
-            "String".to_string(),
-        ));
+        // Plain aggregates have a key field (the grouping key from HashMap), rows with
+        // named aggregates only carry the grouped values
+        if info.aggregates.is_empty() {
+            fields.push(ReturnFieldInfo::new_implicit(
+                "key".to_string(),
+                "String".to_string(),
+            ));
+        }
 
         // Add fields for each grouped property
         // We need to get the source type's schema to determine property types
-        let (_schema_fields, _item_type) = match info.source_type.as_ref() {
+        let (schema_fields, _item_type) = match info.source_type.as_ref() {
             Type::Node(Some(label)) | Type::Nodes(Some(label)) => {
                 (ctx.node_fields.get(label.as_str()), "node")
             }
@@ -70,10 +103,17 @@ fn build_return_fields(
 
         // Add each grouped property as a field
         for prop_name in &info.properties {
-            fields.push(ReturnFieldInfo::new_schema(
-                prop_name.clone(),
-                "Option<&'a Value>".to_string(),
-            ));
+            fields.push(schema_return_field(ctx, schema_fields, prop_name));
+        }
+
+        for (name, ty) in &info.aggregates {
+            let (rust_type, _) =
+                type_to_rust_string_and_fields(ty, &ShouldCollect::No, ctx, name);
+            let rust_type = match rust_type.is_empty() {
+                true => "Value".to_string(),
+                false => rust_type,
+            };
+            fields.push(ReturnFieldInfo::new_implicit(name.clone(), rust_type));
         }
 
         // Add count field
@@ -82,8 +122,9 @@ fn build_return_fields(
             "i32".to_string(),
         ));
 
-        // For non-COUNT aggregates, add items field with nested struct
-        if !info.is_count {
+        // For non-COUNT aggregates, add items field with nested struct. Rows with named
+        // aggregates only keep their items for AGGREGATE_BY
+        if !info.is_count && (info.aggregates.is_empty() || !info.is_group_by) {
             // Build nested struct for the items
             let items_struct_name = format!("{}Items", struct_name_prefix);
             // Recursively build fields for the source type
@@ -115,7 +156,7 @@ fn build_return_fields(
     }
 
     // Get schema type name if this is a schema type
-    let schema_type = match inferred_type {
+    let schema_type = match inferred_type.base() {
         Type::Node(Some(label)) | Type::Nodes(Some(label)) => Some((label.as_str(), "node")),
         Type::Edge(Some(label)) | Type::Edges(Some(label)) => Some((label.as_str(), "edge")),
         Type::Vector(Some(label)) | Type::Vectors(Some(label)) => Some((label.as_str(), "vector")),
@@ -195,30 +236,53 @@ fn build_return_fields(
                         continue;
                     }
 
+                    if let Some(call) = traversal.computed_fields.get(field_name) {
+                        fields.push(ReturnFieldInfo::new_computed(
+                            field_name.clone(),
+                            call.clone(),
+                        ));
+                        continue;
+                    }
+
+                    if let Some(path) = traversal.property_paths.get(field_name) {
+                        fields.push(ReturnFieldInfo::new_property_path(
+                            field_name.clone(),
+                            path.clone(),
+                        ));
+                        continue;
+                    }
+
+                    // Properties set by the step producing the items, e.g. the `distance`
+                    // of `ShortestPathsFrom`, are read like schema fields
+                    if inferred_type.computed_property(field_name).is_some() {
+                        fields.push(ReturnFieldInfo::new_schema(
+                            field_name.clone(),
+                            "Option<&'a Value>".to_string(),
+                        ));
+                        continue;
+                    }
+
                     // Skip implicit fields (already added)
                     if field_name == "id"
                         || field_name == "label"
                         || field_name == "from_node"
                         || field_name == "to_node"
                         || field_name == "data"
-                        || field_name == "score"
+                        || (field_name == "score" && item_type == "vector")
                     {
                         continue;
                     }
 
-                    if let Some(_field) = schema_fields.get(field_name.as_str()) {
-                        fields.push(ReturnFieldInfo::new_schema(
-                            field_name.clone(),
-                            "Option<&'a Value>".to_string(),
-                        ));
+                    if schema_fields.get(field_name.as_str()).is_some() {
+                        fields.push(schema_return_field(ctx, Some(schema_fields), field_name));
                     }
                 }
 
                 // If has_spread, add all remaining schema fields
                 if traversal.has_spread {
-                    for (field_name, _field) in schema_fields.iter() {
+                    for field_name in declared_field_names(schema_fields) {
                         // Skip if already added
-                        let already_exists = fields.iter().any(|f| f.name == *field_name);
+                        let already_exists = fields.iter().any(|f| f.name == field_name);
                         if already_exists {
                             continue;
                         }
@@ -229,12 +293,12 @@ fn build_return_fields(
 
                         // Check if this is an implicit field - if so, use the correct type
                         let is_implicit_field = matches!(
-                            *field_name,
-                            "id" | "label" | "from_node" | "to_node" | "data" | "score"
-                        );
+                            field_name,
+                            "id" | "label" | "from_node" | "to_node" | "data"
+                        ) || (field_name == "score" && item_type == "vector");
 
                         if is_implicit_field {
-                            let rust_type = match *field_name {
+                            let rust_type = match field_name {
                                 "data" => "&'a [f64]".to_string(),
                                 "score" => "f64".to_string(),
                                 _ => "&'a str".to_string(),
@@ -244,23 +308,20 @@ fn build_return_fields(
                                 rust_type,
                             ));
                         } else {
-                            fields.push(ReturnFieldInfo::new_schema(
-                                field_name.to_string(),
-                                "Option<&'a Value>".to_string(),
-                            ));
+                            fields.push(schema_return_field(ctx, Some(schema_fields), field_name));
                         }
                     }
                 }
             } else {
                 // No projection - include all schema fields except excluded ones
-                for (field_name, _field) in schema_fields.iter() {
+                for field_name in declared_field_names(schema_fields) {
                     // Skip implicit fields (already added)
-                    if *field_name == "id"
-                        || *field_name == "label"
-                        || *field_name == "from_node"
-                        || *field_name == "to_node"
-                        || *field_name == "data"
-                        || *field_name == "score"
+                    if field_name == "id"
+                        || field_name == "label"
+                        || field_name == "from_node"
+                        || field_name == "to_node"
+                        || field_name == "data"
+                        || (field_name == "score" && item_type == "vector")
                     {
                         continue;
                     }
@@ -268,10 +329,7 @@ fn build_return_fields(
                     if traversal.excluded_fields.contains(&field_name.to_string()) {
                         continue;
                     }
-                    fields.push(ReturnFieldInfo::new_schema(
-                        field_name.to_string(),
-                        "Option<&'a Value>".to_string(),
-                    ));
+                    fields.push(schema_return_field(ctx, Some(schema_fields), field_name));
                 }
             }
         }
@@ -282,7 +340,7 @@ fn build_return_fields(
         // For nested traversals, extract the return type and build nested fields
         if let Some(ref return_type) = nested_info.return_type {
             // Check if this is a scalar type or needs a struct
-            match return_type {
+            match return_type.base() {
                 Type::Scalar(_scalar_ty) => {
                     // Check if the traversal is accessing an implicit field
                     // For nested traversals like usr::ID, we need to check what field is actually accessed
@@ -557,7 +615,7 @@ fn process_object_literal<'a>(
 
     // Helper function to build JSON for an identifier based on its type
     fn build_identifier_json(ctx: &Ctx, var_name: &str, ty: &Type) -> String {
-        match ty {
+        match ty.base() {
             Type::Node(Some(label)) => {
                 // Look up the node schema to get its properties
                 if let Some(node_fields) = ctx.node_fields.get(label.as_str()) {
@@ -650,7 +708,7 @@ fn type_to_rust_string_and_fields(
     String,
     Vec<crate::helixc::generator::return_values::ReturnValueField>,
 ) {
-    match (ty, should_collect) {
+    match (ty.base(), should_collect) {
         // For single nodes/vectors/edges, generate a proper struct based on schema
         (Type::Node(Some(label)), ShouldCollect::ToObj | ShouldCollect::No) => {
             let type_name = format!("{}ReturnType", label);
@@ -674,7 +732,10 @@ fn type_to_rust_string_and_fields(
                         fields.push(
                             crate::helixc::generator::return_values::ReturnValueField::new(
                                 prop_name.to_string(),
-                                format!("{}", field.field_type),
+                                match field.is_nullable() {
+                                    true => format!("Option<{}>", field.field_type),
+                                    false => format!("{}", field.field_type),
+                                },
                             ),
                         );
                     }
@@ -703,7 +764,10 @@ fn type_to_rust_string_and_fields(
                         fields.push(
                             crate::helixc::generator::return_values::ReturnValueField::new(
                                 prop_name.to_string(),
-                                format!("{}", field.field_type),
+                                match field.is_nullable() {
+                                    true => format!("Option<{}>", field.field_type),
+                                    false => format!("{}", field.field_type),
+                                },
                             ),
                         );
                     }
@@ -732,7 +796,10 @@ fn type_to_rust_string_and_fields(
                         fields.push(
                             crate::helixc::generator::return_values::ReturnValueField::new(
                                 prop_name.to_string(),
-                                format!("{}", field.field_type),
+                                match field.is_nullable() {
+                                    true => format!("Option<{}>", field.field_type),
+                                    false => format!("{}", field.field_type),
+                                },
                             ),
                         );
                     }
@@ -770,11 +837,32 @@ fn type_to_rust_string_and_fields(
     }
 }
 
+/// Reports returned fields that are optional without a default and were not checked with
+/// `IS_NULL` or `EXISTS`, either in the returned expression or for the variable it starts
+/// from, as they would be returned as `null` when missing.
+fn check_nullable_fields(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    loc: Loc,
+    nullable_fields: &[String],
+    source: Option<&VariableInfo>,
+) {
+    for field_name in nullable_fields {
+        if ctx.null_checked_fields.contains(field_name)
+            || source.is_some_and(|var| var.null_checked_fields.contains(field_name))
+        {
+            continue;
+        }
+        generate_error!(ctx, original_query, loc.clone(), E225, [field_name], [field_name]);
+    }
+}
+
 pub(crate) fn validate_query<'a>(ctx: &mut Ctx<'a>, original_query: &'a Query) {
     let mut query = GeneratedQuery {
         name: original_query.name.clone(),
         ..Default::default()
     };
+    ctx.null_checked_fields.clear();
 
     if let Some(BuiltInMacro::Model(model_name)) = &original_query.built_in_macro {
         // handle model macro
@@ -790,6 +878,8 @@ pub(crate) fn validate_query<'a>(ctx: &mut Ctx<'a>, original_query: &'a Query) {
             && !ctx.node_set.contains(id.as_str())
             && !ctx.edge_map.contains_key(id.as_str())
             && !ctx.vector_set.contains(id.as_str())
+            && !ctx.enum_map.contains_key(id.as_str())
+            && !ctx.struct_map.contains_key(id.as_str())
         {
             generate_error!(
                 ctx,
@@ -884,6 +974,7 @@ fn analyze_return_expr<'a>(
 ) {
     match ret {
         ReturnType::Expression(expr) => {
+            ctx.null_checked_fields.clear();
             let (inferred_type, stmt) =
                 infer_expr_type(ctx, expr, scope, original_query, None, query);
 
@@ -903,6 +994,13 @@ fn analyze_return_expr<'a>(
                             );
 
                             let field_name = v.inner().clone();
+                            check_nullable_fields(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                &traversal.nullable_fields,
+                                scope.get(v.inner().as_str()),
+                            );
 
                             // Legacy approach
                             let (rust_type, fields) = type_to_rust_string_and_fields(
@@ -963,8 +1061,11 @@ fn analyze_return_expr<'a>(
                             ));
 
                             // New unified approach
-                            // Skip struct generation for primitive types (Boolean, Scalar) - they use legacy path only
-                            if !matches!(inferred_type, Type::Boolean | Type::Scalar(_)) {
+                            // Skip struct generation for primitive types (Boolean, Scalar) and
+                            // untyped values such as paths - they are serialized directly
+                            if matches!(inferred_type, Type::Boolean | Type::Scalar(_) | Type::Unknown) {
+                                query.primitive_returns.push(field_name.clone());
+                            } else {
                                 let struct_name_prefix = format!(
                                     "{}{}",
                                     capitalize_first(&query.name),
@@ -978,7 +1079,7 @@ fn analyze_return_expr<'a>(
                                 );
                                 let struct_name = format!("{}ReturnType", struct_name_prefix);
                                 let is_collection = matches!(
-                                    inferred_type,
+                                    inferred_type.base(),
                                     Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_)
                                 );
                                 let (
@@ -1015,6 +1116,13 @@ fn analyze_return_expr<'a>(
                         }
                         _ => {
                             let field_name = "data".to_string();
+                            check_nullable_fields(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                &traversal.nullable_fields,
+                                None,
+                            );
 
                             // Legacy approach
                             let (rust_type, fields) = type_to_rust_string_and_fields(
@@ -1046,7 +1154,7 @@ fn analyze_return_expr<'a>(
                             );
                             let struct_name = format!("{}ReturnType", struct_name_prefix);
                             let is_collection = matches!(
-                                inferred_type,
+                                inferred_type.base(),
                                 Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_)
                             );
                             let (
@@ -1099,6 +1207,17 @@ fn analyze_return_expr<'a>(
                     };
 
                     let field_name = id.inner().clone();
+                    let var_info = scope.get(id.inner().as_str());
+                    let nullable_fields = var_info
+                        .map(|var_info| var_info.nullable_fields.clone())
+                        .unwrap_or_default();
+                    check_nullable_fields(
+                        ctx,
+                        original_query,
+                        expr.loc.clone(),
+                        &nullable_fields,
+                        var_info,
+                    );
 
                     // Legacy approach
                     let (rust_type, fields) = type_to_rust_string_and_fields(
@@ -1117,8 +1236,11 @@ fn analyze_return_expr<'a>(
                     ));
 
                     // New unified approach
-                    // Skip struct generation for primitive types (Boolean, Scalar) - they use legacy path only
-                    if !matches!(identifier_end_type, Type::Boolean | Type::Scalar(_)) {
+                    // Skip struct generation for primitive types (Boolean, Scalar) and
+                    // untyped values such as paths - they are serialized directly
+                    if matches!(identifier_end_type, Type::Boolean | Type::Scalar(_) | Type::Unknown) {
+                        query.primitive_returns.push(field_name.clone());
+                    } else {
                         // For identifier returns, we need to create a traversal to build fields from
                         let var_info = scope.get(id.inner().as_str());
                         let is_reused = var_info.is_some_and(|v| v.reference_count > 1);
@@ -1504,6 +1626,117 @@ mod tests {
         assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E301));
     }
 
+    #[test]
+    fn test_return_optional_field_without_null_check() {
+        let source = r#"
+            N::Person { name: String, nickname: String? }
+
+            QUERY test(id: ID) =>
+                person <- N<Person>(id)
+                RETURN person::{name, nickname}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.error_code == ErrorCode::E225 && d.message.contains("nickname"))
+        );
+    }
+
+    #[test]
+    fn test_return_optional_field_with_null_check() {
+        let source = r#"
+            N::Person { name: String, nickname: String?, age: U32? DEFAULT 0 }
+
+            QUERY test() =>
+                people <- N<Person>::WHERE(_::{nickname}::IS_NULL)
+                names <- people::{nickname, age}
+                RETURN names
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E225));
+    }
+
+    #[test]
+    fn test_return_optional_field_with_exists_check() {
+        let source = r#"
+            N::Person { name: String, nickname: String? }
+
+            QUERY test() =>
+                people <- N<Person>::WHERE(EXISTS(_::{nickname}))
+                RETURN people::{nickname}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_null_check_only_covers_its_own_variable() {
+        let source = r#"
+            N::Person { name: String, nickname: String? }
+
+            QUERY test() =>
+                checked <- N<Person>::WHERE(_::{nickname}::IS_NULL)
+                unchecked <- N<Person>
+                RETURN checked::{nickname}, unchecked::{nickname}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let unchecked = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E225)
+            .collect::<Vec<_>>();
+        assert_eq!(unchecked.len(), 1, "{diagnostics:?}");
+        assert_eq!(unchecked[0].location.span.trim(), "unchecked::{nickname}");
+    }
+
+    #[test]
+    fn test_return_primitive_next_to_struct() {
+        let source = r#"
+            N::Person { INDEX name: String, age: U32 }
+
+            QUERY test(name: String) =>
+                person, created <- UpsertN<Person>({name: name}, {age: 1})
+                total <- N<Person>::COUNT
+                RETURN person, created, total
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("\"person\": TestPersonReturnType {"), "{output}");
+        assert!(output.contains("\"created\": created"), "{output}");
+        assert!(output.contains("\"total\": total"), "{output}");
+    }
+
     // ============================================================================
     // Model Macro Tests
     // ============================================================================
@@ -1607,4 +1840,31 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E301));
     }
+
+    #[test]
+    fn test_return_computed_properties_read_from_the_items() {
+        let source = r#"
+            N::User { name: String, score: I64 }
+            E::Follows { From: User, To: User }
+
+            QUERY test(id: ID) =>
+                ranked <- N<User>::PageRank<Follows>
+                near <- N<User>(id)::ShortestPathsFrom<Follows>()
+                users <- N<User>
+                RETURN ranked::{name, score}, near::{name, distance}, users::{name, score}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("score: ranked.get_property(\"score\")"), "{output}");
+        assert!(output.contains("distance: near.get_property(\"distance\")"), "{output}");
+        assert!(output.contains("score: user.get_property(\"score\")"), "{output}");
+        assert!(!output.contains(".score()"), "{output}");
+    }
 }
@@ -1,11 +1,14 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
 
 use crate::helixc::{
     analyzer::{Ctx, error_codes::ErrorCode, errors::push_schema_err},
     parser::{
         errors::ParserError,
         location::Loc,
-        types::{Field, FieldPrefix, FieldType, Source},
+        types::{Field, FieldPrefix, FieldType, Source, StructSchema},
     },
 };
 
@@ -49,6 +52,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -59,6 +63,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -86,6 +91,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -96,6 +102,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -106,6 +113,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "from_node".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -116,6 +124,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "to_node".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -139,6 +148,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -149,6 +159,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -159,6 +170,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "data".to_string(),
                                 field_type: FieldType::Array(Box::new(FieldType::F64)),
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -169,6 +181,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "score".to_string(),
                                 field_type: FieldType::F64,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -248,6 +261,53 @@ fn check_duplicate_schema_definitions(ctx: &mut Ctx) -> Result<(), ParserError>
             );
         }
     }
+
+    // Check duplicate enums and duplicate variants within an enum
+    let mut seen_enums: HashSet<&str> = HashSet::new();
+    for enum_schema in &schema.enum_schemas {
+        if !seen_enums.insert(enum_schema.name.1.as_str()) {
+            push_schema_err(
+                ctx,
+                enum_schema.name.0.clone(),
+                ErrorCode::E107,
+                format!("duplicate enum definition `{}`", enum_schema.name.1),
+                Some("rename the enum or remove the duplicate definition".to_string()),
+            );
+        }
+        let mut seen_variants: HashSet<&str> = HashSet::new();
+        for (loc, variant) in &enum_schema.variants {
+            if !seen_variants.insert(variant.as_str()) {
+                push_schema_err(
+                    ctx,
+                    loc.clone(),
+                    ErrorCode::E107,
+                    format!("duplicate enum variant definition `{variant}`"),
+                    Some("rename the enum variant or remove the duplicate definition".to_string()),
+                );
+            }
+        }
+    }
+
+    // Check duplicate structs, which share their generated type names with the other
+    // schema items
+    let mut seen_structs: HashSet<&str> = HashSet::new();
+    for struct_schema in &schema.struct_schemas {
+        let name = struct_schema.name.1.as_str();
+        if !seen_structs.insert(name)
+            || seen_nodes.contains_key(name)
+            || seen_edges.contains_key(name)
+            || seen_vectors.contains_key(name)
+            || seen_enums.contains(name)
+        {
+            push_schema_err(
+                ctx,
+                struct_schema.name.0.clone(),
+                ErrorCode::E107,
+                format!("duplicate struct definition `{name}`"),
+                Some("rename the struct or remove the duplicate definition".to_string()),
+            );
+        }
+    }
     Ok(())
 }
 
@@ -255,6 +315,15 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
     // Check for duplicate schema definitions
     check_duplicate_schema_definitions(ctx)?;
 
+    for enum_schema in &ctx.src.get_latest_schema()?.enum_schemas {
+        ctx.output.enums.push(enum_schema.clone().into());
+    }
+
+    for struct_schema in &ctx.src.get_latest_schema()?.struct_schemas {
+        check_struct(ctx, struct_schema);
+        ctx.output.structs.push(struct_schema.clone().into());
+    }
+
     for edge in &ctx.src.get_latest_schema()?.edge_schemas {
         if !ctx.node_set.contains(edge.from.1.as_str())
             && !ctx.vector_set.contains(edge.from.1.as_str())
@@ -301,13 +370,18 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                         Some("rename the field".to_string()),
                     );
                 }
-                if !is_valid_schema_field_type(&f.field_type) {
+                if let FieldType::Embedding(vector_type) = &f.field_type {
+                    push_embedding_outside_node_err(ctx, f, vector_type);
+                } else if !is_valid_schema_field_type(ctx, &f.field_type) {
                     push_schema_err(
                         ctx,
                         f.loc.clone(),
                         ErrorCode::E209,
                         format!("invalid type in schema field: `{}`", f.name),
-                        Some("use built-in types only (String, U32, etc.)".to_string()),
+                        Some(
+                            "use built-in types (String, U32, etc.) or a declared `ENUM` or `STRUCT`"
+                                .to_string(),
+                        ),
                     );
                 }
             })
@@ -325,13 +399,26 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("rename the field".to_string()),
                 );
             }
-            if !is_valid_schema_field_type(&f.field_type) {
+            if let FieldType::Embedding(vector_type) = &f.field_type {
+                if !ctx.vector_set.contains(vector_type.as_str()) {
+                    push_schema_err(
+                        ctx,
+                        f.loc.clone(),
+                        ErrorCode::E103,
+                        format!("unknown vector type `{vector_type}`"),
+                        Some(format!("declare `V::{vector_type}` in the schema")),
+                    );
+                }
+            } else if !is_valid_schema_field_type(ctx, &f.field_type) {
                 push_schema_err(
                     ctx,
                     f.loc.clone(),
                     ErrorCode::E209,
                     format!("invalid type in schema field: `{}`", f.name),
-                    Some("use built-in types only (String, U32, etc.)".to_string()),
+                    Some(
+                        "use built-in types (String, U32, etc.) or a declared `ENUM` or `STRUCT`"
+                            .to_string(),
+                    ),
                 );
             }
         });
@@ -348,13 +435,18 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("rename the field".to_string()),
                 );
             }
-            if !is_valid_schema_field_type(&f.field_type) {
+            if let FieldType::Embedding(vector_type) = &f.field_type {
+                push_embedding_outside_node_err(ctx, f, vector_type);
+            } else if !is_valid_schema_field_type(ctx, &f.field_type) {
                 push_schema_err(
                     ctx,
                     f.loc.clone(),
                     ErrorCode::E209,
                     format!("invalid type in schema field: `{}`", f.name),
-                    Some("use built-in types only (String, U32, etc.)".to_string()),
+                    Some(
+                        "use built-in types (String, U32, etc.) or a declared `ENUM` or `STRUCT`"
+                            .to_string(),
+                    ),
                 );
             }
         });
@@ -363,11 +455,113 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
     Ok(())
 }
 
-fn is_valid_schema_field_type(ft: &FieldType) -> bool {
+fn push_embedding_outside_node_err(ctx: &mut Ctx, field: &Field, vector_type: &str) {
+    push_schema_err(
+        ctx,
+        field.loc.clone(),
+        ErrorCode::E219,
+        format!(
+            "field `{}` embeds `V::{vector_type}`, but vectors can only be embedded on node types",
+            field.name
+        ),
+        Some("move the field to a node type or use a `[F64]` field".to_string()),
+    );
+}
+
+fn check_struct(ctx: &mut Ctx, struct_schema: &StructSchema) {
+    for f in &struct_schema.fields {
+        let modifier = match (f.is_indexed(), &f.defaults) {
+            (true, _) => Some("INDEX"),
+            (false, Some(_)) => Some("DEFAULT"),
+            (false, None) => None,
+        };
+        if let Some(modifier) = modifier {
+            push_schema_err(
+                ctx,
+                f.loc.clone(),
+                ErrorCode::E222,
+                format!(
+                    "field `{}` of struct `{}` cannot be declared with `{modifier}`",
+                    f.name, struct_schema.name.1
+                ),
+                Some(format!("remove `{modifier}` from the field")),
+            );
+        }
+        if ctx.get_enum(&f.field_type).is_some() || !is_valid_schema_field_type(ctx, &f.field_type)
+        {
+            push_schema_err(
+                ctx,
+                f.loc.clone(),
+                ErrorCode::E209,
+                format!("invalid type in struct field: `{}`", f.name),
+                Some("use built-in types (String, U32, etc.) or a declared `STRUCT`".to_string()),
+            );
+        } else if contains_struct(
+            ctx,
+            &f.field_type,
+            &struct_schema.name.1,
+            &mut HashSet::new(),
+        ) {
+            push_schema_err(
+                ctx,
+                f.loc.clone(),
+                ErrorCode::E221,
+                format!(
+                    "struct `{}` contains itself through field `{}`",
+                    struct_schema.name.1, f.name
+                ),
+                Some(
+                    "store the nested value in a separate node type connected by an edge"
+                        .to_string(),
+                ),
+            );
+        }
+    }
+}
+
+/// Whether values of `ft` hold a value of the struct `name`, at any depth
+fn contains_struct<'a>(
+    ctx: &Ctx<'a>,
+    ft: &FieldType,
+    name: &str,
+    seen: &mut HashSet<&'a str>,
+) -> bool {
+    match ft {
+        FieldType::Object(fields) => fields
+            .values()
+            .any(|ft| contains_struct(ctx, ft, name, seen)),
+        FieldType::Array(inner) => contains_struct(ctx, inner, name, seen),
+        _ => match ctx.get_struct(ft) {
+            Some(struct_schema) if struct_schema.name.1 == name => true,
+            Some(struct_schema) => {
+                seen.insert(struct_schema.name.1.as_str())
+                    && struct_schema
+                        .fields
+                        .iter()
+                        .any(|f| contains_struct(ctx, &f.field_type, name, seen))
+            }
+            None => false,
+        },
+    }
+}
+
+fn is_valid_schema_field_type(ctx: &Ctx, ft: &FieldType) -> bool {
     match ft {
-        FieldType::Identifier(_) => false,
-        FieldType::Object(_) => false,
-        FieldType::Array(inner) => is_valid_schema_field_type(inner),
+        FieldType::Identifier(name) => {
+            ctx.enum_map.contains_key(name.as_str()) || ctx.struct_map.contains_key(name.as_str())
+        }
+        // enum values convert into `Value` one at a time, so nested ones are not supported
+        FieldType::Object(fields) => fields
+            .values()
+            .all(|ft| ctx.get_enum(ft).is_none() && is_valid_schema_field_type(ctx, ft)),
+        // neither do arrays of structs, which convert as a whole
+        FieldType::Array(inner) => {
+            ctx.get_enum(inner).is_none()
+                && ctx.get_struct(inner).is_none()
+                && is_valid_schema_field_type(ctx, inner)
+        }
+        // embeddings are only valid as top level node fields, which are checked separately
+        FieldType::Embedding(_) => false,
         _ => true,
     }
 }
@@ -729,6 +923,172 @@ mod tests {
         assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E209));
     }
 
+    #[test]
+    fn test_valid_object_field_types() {
+        let source = r#"
+            N::Person {
+                address: { city: String, zip: { code: String } },
+                contacts: [{ email: String }]
+            }
+
+            QUERY test() =>
+                p <- N<Person>
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E209));
+    }
+
+    #[test]
+    fn test_enum_field_type() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+
+            N::Person {
+                status: Status
+            }
+
+            N::Company {
+                kind: Kind,
+                history: [Status]
+            }
+
+            QUERY test() =>
+                p <- N<Person>
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        let e209: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E209)
+            .collect();
+        assert_eq!(e209.len(), 2);
+        assert!(e209.iter().any(|d| d.message.contains("kind")));
+        assert!(e209.iter().any(|d| d.message.contains("history")));
+        assert_eq!(generated.enums.len(), 1);
+        assert_eq!(generated.enums[0].variants, vec!["Active", "Suspended"]);
+    }
+
+    #[test]
+    fn test_duplicate_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Active }
+
+            QUERY test() =>
+                p <- N
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| {
+            d.message
+                .contains("duplicate enum variant definition `Active`")
+        }));
+    }
+
+    #[test]
+    fn test_struct_field_type() {
+        let source = r#"
+            STRUCT Address { city: String, zip: U32? }
+            STRUCT Location { address: Address, tags: [String] }
+
+            N::Person {
+                home: Address,
+                work: Location?,
+                past: [Address]
+            }
+
+            QUERY test() =>
+                p <- N<Person>
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        let e209: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E209)
+            .collect();
+        // arrays of structs are not supported
+        assert_eq!(e209.len(), 1);
+        assert!(e209[0].message.contains("past"));
+        assert_eq!(generated.structs.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_struct_fields() {
+        let source = r#"
+            ENUM Status { Active }
+
+            STRUCT Address {
+                INDEX city: String,
+                zip: U32 DEFAULT 0,
+                status: Status,
+                next: Address
+            }
+
+            QUERY test() =>
+                p <- N
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let count = |code| diagnostics.iter().filter(|d| d.error_code == code).count();
+        assert_eq!(count(ErrorCode::E222), 2);
+        assert_eq!(count(ErrorCode::E221), 1);
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.error_code == ErrorCode::E209 && d.message.contains("status"))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_struct_definition() {
+        let source = r#"
+            N::Address { city: String }
+            STRUCT Address { city: String }
+
+            QUERY test() =>
+                p <- N
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E107));
+    }
+
     // ============================================================================
     // Field Lookup Tests
     // ============================================================================
@@ -849,4 +1209,44 @@ mod tests {
         assert!(result.is_ok());
         // Schema with only vectors should be valid
     }
+
+    #[test]
+    fn test_embedding_on_edge_property() {
+        let source = r#"
+            V::DocEmb {}
+            N::Doc { title: String }
+            E::Cites { From: Doc, To: Doc, Properties: { embedding: V::DocEmb } }
+
+            QUERY test() =>
+                d <- N<Doc>
+                RETURN d
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E219));
+    }
+
+    #[test]
+    fn test_embedding_of_undeclared_vector() {
+        let source = r#"
+            N::Doc { title: String, embedding: V::Missing }
+
+            QUERY test() =>
+                d <- N<Doc>
+                RETURN d
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E103));
+    }
 }
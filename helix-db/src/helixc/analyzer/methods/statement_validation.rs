@@ -6,16 +6,19 @@ use crate::{
     helixc::{
         analyzer::{
             Ctx, errors::push_query_err, methods::infer_expr_type::infer_expr_type, types::Type,
-            utils::{is_valid_identifier, VariableInfo},
+            utils::{is_param, is_valid_identifier, VariableInfo},
         },
         generator::{
+            bool_ops::BoExp,
             queries::Query as GeneratedQuery,
+            source_steps::SourceStep,
             statements::Statement as GeneratedStatement,
             statements::{
                 Assignment as GeneratedAssignment, Drop as GeneratedDrop,
                 ForEach as GeneratedForEach, ForLoopInVariable, ForVariable,
+                IfElse as GeneratedIfElse,
             },
-            traversal_steps::ShouldCollect,
+            traversal_steps::{ShouldCollect, TraversalType},
             utils::GenRef,
         },
         parser::types::*,
@@ -57,6 +60,7 @@ pub(crate) fn validate_statements<'a>(
                 );
             }
 
+            ctx.null_checked_fields.clear();
             let (rhs_ty, stmt) =
                 infer_expr_type(ctx, &assign.value, scope, original_query, None, query);
 
@@ -64,32 +68,78 @@ pub(crate) fn validate_statements<'a>(
             let is_single = if let Some(GeneratedStatement::Traversal(ref tr)) = stmt {
                 // Check if should_collect is ToObj, or if the type is a single value
                 matches!(tr.should_collect, ShouldCollect::ToObj) ||
-                matches!(rhs_ty, Type::Node(_) | Type::Edge(_) | Type::Vector(_))
+                matches!(rhs_ty.base(), Type::Node(_) | Type::Edge(_) | Type::Vector(_))
             } else {
                 // Non-traversal: check if type is single
-                matches!(rhs_ty, Type::Node(_) | Type::Edge(_) | Type::Vector(_))
+                matches!(rhs_ty.base(), Type::Node(_) | Type::Edge(_) | Type::Vector(_))
             };
 
-            scope.insert(assign.variable.as_str(), VariableInfo::new(rhs_ty, is_single));
+            let mut var_info = VariableInfo::new(rhs_ty, is_single);
+            var_info.null_checked_fields = ctx.null_checked_fields.drain().collect();
+            if let Some(GeneratedStatement::Traversal(ref tr)) = stmt {
+                var_info.nullable_fields = tr.nullable_fields.clone();
+                // checks made for the variable a traversal starts from hold for its items
+                if let SourceStep::Identifier(source) = tr.source_step.inner()
+                    && let Some(source) = scope.get(source.inner().as_str())
+                {
+                    var_info
+                        .null_checked_fields
+                        .extend(source.null_checked_fields.iter().cloned());
+                }
+            }
+            scope.insert(assign.variable.as_str(), var_info);
+
+            let is_upsert = matches!(
+                assign.value.expr,
+                ExpressionType::UpsertNode(_) | ExpressionType::UpsertEdge(_)
+            );
+            if let Some((loc, created)) = &assign.created_variable {
+                if !is_upsert {
+                    generate_error!(ctx, original_query, loc.clone(), E308, [created], [created]);
+                } else if scope.contains_key(created.as_str()) {
+                    generate_error!(ctx, original_query, loc.clone(), E302, created);
+                }
+                scope.insert(created.as_str(), VariableInfo::new(Type::Boolean, true));
+            }
 
             stmt.as_ref()?;
 
+            // upserts return the written item alongside whether it was inserted
+            let variable = match (is_upsert, &assign.created_variable) {
+                (true, Some((_, created))) => {
+                    format!("Upserted {{ value: {}, created: {created} }}", assign.variable)
+                }
+                (true, None) => format!("Upserted {{ value: {}, .. }}", assign.variable),
+                (false, _) => assign.variable.clone(),
+            };
+
             let assignment = GeneratedStatement::Assignment(GeneratedAssignment {
-                variable: GenRef::Std(assign.variable.clone()),
+                variable: GenRef::Std(variable),
                 value: Box::new(stmt.unwrap()),
             });
             Some(assignment)
         }
 
         Drop(expr) => {
-            let (_, stmt) = infer_expr_type(ctx, expr, scope, original_query, None, query);
+            let (ty, stmt) = infer_expr_type(ctx, expr, scope, original_query, None, query);
             stmt.as_ref()?;
 
             query.is_mut = true;
             if let Some(GeneratedStatement::Traversal(mut tr)) = stmt {
                 // Drop should not collect - it needs the iterator
                 tr.should_collect = ShouldCollect::No;
-                Some(GeneratedStatement::Drop(GeneratedDrop { expression: tr }))
+                let embeddings = match ty.base() {
+                    Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => ctx
+                        .get_embeddings(node_type)
+                        .into_iter()
+                        .map(|(field, _)| field)
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                Some(GeneratedStatement::Drop(GeneratedDrop {
+                    expression: tr,
+                    embeddings,
+                }))
             } else {
                 panic!("Drop should only be applied to traversals");
             }
@@ -305,6 +355,91 @@ pub(crate) fn validate_statements<'a>(
             });
             Some(stmt)
         }
+
+        IfElse(if_else) => {
+            let condition = validate_if_condition(ctx, scope, original_query, query, &if_else.condition);
+
+            // each branch gets its own scope, so variables assigned inside a branch
+            // are not visible after the IF, matching the generated rust blocks
+            let mut then_scope = scope.clone();
+            let then_statements = if_else
+                .then_statements
+                .iter()
+                .filter_map(|stmt| {
+                    validate_statements(ctx, &mut then_scope, original_query, query, stmt)
+                })
+                .collect();
+            let else_statements = if_else.else_statements.as_ref().map(|else_statements| {
+                let mut else_scope = scope.clone();
+                else_statements
+                    .iter()
+                    .filter_map(|stmt| {
+                        validate_statements(ctx, &mut else_scope, original_query, query, stmt)
+                    })
+                    .collect()
+            });
+
+            Some(GeneratedStatement::IfElse(GeneratedIfElse {
+                condition: Box::new(condition?),
+                then_statements,
+                else_statements,
+            }))
+        }
+    }
+}
+
+/// Validates the condition of an `IF` statement and returns the rust expression to branch on
+fn validate_if_condition<'a>(
+    ctx: &mut Ctx<'a>,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    query: &mut GeneratedQuery,
+    condition: &'a Expression,
+) -> Option<GeneratedStatement> {
+    let (ty, stmt) = infer_expr_type(ctx, condition, scope, original_query, None, query);
+    let stmt = stmt?;
+
+    let ends_in_bool_op = |tr: &Traversal| {
+        matches!(
+            tr.steps.last().map(|step| &step.step),
+            Some(StepType::BooleanOperation(_))
+        )
+    };
+    let is_bool = match &condition.expr {
+        ExpressionType::Traversal(tr) => ends_in_bool_op(tr),
+        _ => matches!(ty, Type::Boolean | Type::Scalar(FieldType::Boolean)),
+    };
+    if !is_bool {
+        generate_error!(ctx, original_query, condition.loc.clone(), E306, ty.kind_str());
+        return None;
+    }
+
+    match &condition.expr {
+        ExpressionType::BooleanLiteral(b) => {
+            Some(GeneratedStatement::Identifier(GenRef::Std(b.to_string())))
+        }
+        ExpressionType::Identifier(name) => Some(GeneratedStatement::Identifier(GenRef::Std(
+            match is_param(original_query, name) {
+                Some(param) if param.is_optional => format!("data.{name}.unwrap_or(false)"),
+                Some(_) => format!("data.{name}"),
+                None => name.clone(),
+            },
+        ))),
+        // a comparison on a single bound item is checked like a WHERE predicate,
+        // anything wider has to go through EXISTS
+        ExpressionType::Traversal(_) => match stmt {
+            GeneratedStatement::Traversal(mut tr)
+                if matches!(tr.traversal_type, TraversalType::FromSingle(_)) =>
+            {
+                tr.should_collect = ShouldCollect::No;
+                Some(GeneratedStatement::BoExp(BoExp::Expr(tr)))
+            }
+            _ => {
+                generate_error!(ctx, original_query, condition.loc.clone(), E306, "traversal");
+                None
+            }
+        },
+        _ => Some(stmt),
     }
 }
 
@@ -448,6 +583,210 @@ mod tests {
         // This tests the for loop with object destructuring works
     }
 
+    // ============================================================================
+    // If Statement Validation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_if_else_inside_for_loop() {
+        let source = r#"
+            N::Person { name: String, age: U32 }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(from: ID, ids: [ID], flag: Boolean) =>
+                person <- N<Person>(from)
+                FOR id IN ids {
+                    IF !EXISTS(person::Out<Knows>::WHERE(_::ID::EQ(id))) {
+                        AddE<Knows>::From(from)::To(id)
+                    } ELSE IF person::{age}::GT(18) {
+                        other <- N<Person>(id)
+                    } ELSE {
+                        IF flag {
+                            AddN<Person>({name: "new", age: 1})
+                        }
+                    }
+                }
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_if_condition_not_boolean() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(name: String) =>
+                IF name {
+                    AddN<Person>({name: name})
+                }
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E306));
+    }
+
+    #[test]
+    fn test_if_branch_variable_not_in_scope_after() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(id: ID, flag: Boolean) =>
+                IF flag {
+                    person <- N<Person>(id)
+                }
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E301));
+    }
+
+    #[test]
+    fn test_coalesce_valid() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(nickname?: String) =>
+                name <- COALESCE(nickname, "anonymous")
+                RETURN name
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_coalesce_mismatched_types() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(nickname?: String) =>
+                name <- COALESCE(nickname, 5)
+                RETURN name
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E307));
+    }
+
+    // ============================================================================
+    // Upsert Tests
+    // ============================================================================
+
+    #[test]
+    fn test_upsert_node_with_created_binding() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+            E::Follows { From: User, To: User, Properties: { weight: F64 } }
+
+            QUERY upsertUser(email: String, name: String) =>
+                user, created <- UpsertN<User>({email: email}, {name: name})
+                IF created {
+                    AddN<User>({email: "audit", name: name})
+                }
+                RETURN user, created
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_upsert_node_on_unindexed_field() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+            E::Follows { From: User, To: User, Properties: { weight: F64 } }
+
+            QUERY upsertUser(name: String) =>
+                user <- UpsertN<User>({name: name})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E213));
+    }
+
+    #[test]
+    fn test_created_binding_requires_upsert() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+            E::Follows { From: User, To: User, Properties: { weight: F64 } }
+
+            QUERY addUser(email: String) =>
+                user, created <- AddN<User>({email: email})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E308));
+    }
+
+    #[test]
+    fn test_upsert_edge_between_collections() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+            E::Follows { From: User, To: User, Properties: { weight: F64 } }
+
+            QUERY followAll(from: ID) =>
+                a <- N<User>(from)
+                b <- N<User>
+                UpsertE<Follows>({weight: 1.0})::From(a)::To(b)
+                RETURN a
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E309));
+    }
+
     // ============================================================================
     // Drop Statement Tests
     // ============================================================================
@@ -1,10 +1,11 @@
 use crate::helixc::analyzer::error_codes::*;
 use crate::helixc::analyzer::utils::{
-    DEFAULT_VAR_NAME, VariableInfo, check_identifier_is_fieldtype,
+    DEFAULT_VAR_NAME, VariableInfo, check_enum_variant, check_identifier_is_fieldtype,
+    gen_enum_variant, is_param, resolve_search_type,
 };
-use crate::helixc::generator::bool_ops::{Contains, IsIn};
+use crate::helixc::generator::bool_ops::{All, Any, Contains, IsIn, IsNull};
 use crate::helixc::generator::source_steps::{SearchVector, VFromID, VFromType};
-use crate::helixc::generator::traversal_steps::{AggregateBy, GroupBy};
+use crate::helixc::generator::traversal_steps::{AggregateBy, GroupBy, SetEmbedding};
 use crate::helixc::generator::utils::{EmbedData, VecData};
 use crate::{
     generate_error,
@@ -13,23 +14,30 @@ use crate::{
             Ctx,
             errors::push_query_err,
             methods::{
-                exclude_validation::validate_exclude, graph_step_validation::apply_graph_step,
-                infer_expr_type::infer_expr_type, object_validation::validate_object,
+                exclude_validation::validate_exclude,
+                graph_step_validation::apply_graph_step,
+                infer_expr_type::{gen_scalar_function_call, infer_expr_type},
+                object_validation::validate_object,
             },
             types::{AggregateInfo, Type},
             utils::{
-                field_exists_on_item_type, gen_identifier_or_param, is_valid_identifier,
-                type_in_scope,
+                FieldLookup, field_exists_on_item_type, gen_id_access_or_param,
+                gen_identifier_or_param, is_valid_identifier, type_in_scope,
             },
         },
         generator::{
             bool_ops::{BoExp, BoolOp, Eq, Gt, Gte, Lt, Lte, Neq},
             queries::Query as GeneratedQuery,
-            source_steps::{EFromID, EFromType, NFromID, NFromIndex, NFromType, SourceStep},
+            source_steps::{
+                EBetween, EFromID, EFromIndex, EFromType, NFromID, NFromIndex, NFromType,
+                SourceStep,
+            },
             statements::Statement as GeneratedStatement,
             traversal_steps::{
-                OrderBy, Range, ShouldCollect, Step as GeneratedStep,
-                Traversal as GeneratedTraversal, TraversalType, Where, WhereRef,
+                EdgeType, In as GeneratedIn, InE as GeneratedInE, OrderBy, OrderKey,
+                Out as GeneratedOut, OutE as GeneratedOutE, Range, ShouldCollect,
+                Step as GeneratedStep, Traversal as GeneratedTraversal, TraversalType, Where,
+                WhereRef,
             },
             utils::{GenRef, GeneratedValue, Order, Separator},
         },
@@ -42,6 +50,10 @@ use std::collections::HashMap;
 
 /// Check if a property name is a reserved property and return its expected type
 fn get_reserved_property_type(prop_name: &str, item_type: &Type) -> Option<FieldType> {
+    if let Some(field_type) = item_type.computed_property(prop_name) {
+        return Some(field_type.clone());
+    }
+    let item_type = item_type.base();
     match prop_name {
         "id" | "ID" | "Id" => Some(FieldType::Uuid),
         "label" | "Label" => Some(FieldType::String),
@@ -94,6 +106,288 @@ fn get_reserved_property_type(prop_name: &str, item_type: &Type) -> Option<Field
     }
 }
 
+/// Checks the names and fields of the aggregates of a `GROUP_BY` or `AGGREGATE_BY` step and
+/// returns the name and result type of each aggregate.
+///
+/// Each group is returned as one flat object, so an aggregate may not be named like one of
+/// the grouping `keys`, one of the `reserved` keys the step adds, or another aggregate.
+fn validate_aggregations(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    item_type: &Type,
+    keys: &[String],
+    reserved: &[&str],
+    aggregations: &[AggregateField],
+) -> Vec<(String, Type)> {
+    for (i, aggregation) in aggregations.iter().enumerate() {
+        let name = aggregation.name.as_str();
+        let used_by = if reserved.contains(&name) {
+            format!("the `{name}` of each group")
+        } else if keys.iter().any(|key| key == name) {
+            "a grouping key".to_string()
+        } else if aggregations[..i].iter().any(|other| other.name == name) {
+            "another aggregate".to_string()
+        } else {
+            continue;
+        };
+        generate_error!(ctx, original_query, aggregation.loc.clone(), E224, name, &used_by);
+    }
+
+    let is_schema_item = matches!(
+        item_type.base(),
+        Type::Node(Some(_))
+            | Type::Nodes(Some(_))
+            | Type::Edge(Some(_))
+            | Type::Edges(Some(_))
+            | Type::Vector(Some(_))
+            | Type::Vectors(Some(_))
+    );
+    aggregations
+        .iter()
+        .map(|aggregation| {
+            let field_type = match aggregation.function.field() {
+                Some(field) if is_schema_item => {
+                    if !item_type.item_fields_contains_key(ctx, field) {
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            aggregation.loc.clone(),
+                            E202,
+                            field,
+                            item_type.kind_str(),
+                            &item_type.get_type_name()
+                        );
+                    }
+                    item_type.get_field_type_from_item_fields(ctx, field)
+                }
+                _ => None,
+            };
+            let field_type = field_type.map(Type::Scalar);
+
+            let requires_number = matches!(
+                aggregation.function,
+                AggregateFunction::Sum(_)
+                    | AggregateFunction::Avg(_)
+                    | AggregateFunction::Percentile(_, _)
+            );
+            if let Some(ty) = &field_type
+                && requires_number
+                && !ty.is_numeric()
+            {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    aggregation.loc.clone(),
+                    E217,
+                    &aggregation.function.to_string(),
+                    aggregation.function.field().unwrap_or_default(),
+                    &ty.get_type_name()
+                );
+            }
+            if let AggregateFunction::Percentile(_, rank) = aggregation.function
+                && !(0.0..=1.0).contains(&rank)
+            {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    aggregation.loc.clone(),
+                    E218,
+                    &rank.to_string()
+                );
+            }
+
+            let result_type = match &aggregation.function {
+                AggregateFunction::Count | AggregateFunction::CountDistinct(_) => {
+                    Type::Scalar(FieldType::I64)
+                }
+                AggregateFunction::Sum(_) => match &field_type {
+                    Some(ty) if ty.is_integer() => Type::Scalar(FieldType::I64),
+                    _ => Type::Scalar(FieldType::F64),
+                },
+                AggregateFunction::Avg(_) | AggregateFunction::Percentile(_, _) => {
+                    Type::Scalar(FieldType::F64)
+                }
+                AggregateFunction::Min(_) | AggregateFunction::Max(_) => {
+                    field_type.unwrap_or(Type::Unknown)
+                }
+                AggregateFunction::Collect(_) => field_type
+                    .map(|ty| Type::Array(Box::new(ty)))
+                    .unwrap_or(Type::Unknown),
+            };
+            (aggregation.name.clone(), result_type)
+        })
+        .collect()
+}
+
+/// Turns the statement generated for a `WHERE` condition into the predicate used by
+/// `filter_ref`, making `EXISTS` traversals inside it stop at the first item.
+pub(crate) fn where_ref_from(stmt: GeneratedStatement) -> WhereRef {
+    match stmt {
+        GeneratedStatement::Traversal(tr) => WhereRef {
+            expr: BoExp::Expr(tr),
+        },
+        GeneratedStatement::BoExp(expr) => match expr {
+            BoExp::Not(inner_expr) => {
+                if let BoExp::Exists(mut traversal) = *inner_expr {
+                    traversal.should_collect = ShouldCollect::No;
+                    WhereRef {
+                        expr: BoExp::Not(Box::new(BoExp::Exists(traversal))),
+                    }
+                } else {
+                    WhereRef {
+                        // expr gets moved at start of match to allow for box dereference so need to move back
+                        expr: BoExp::Not(inner_expr),
+                    }
+                }
+            }
+            BoExp::Exists(mut traversal) => {
+                traversal.should_collect = ShouldCollect::No;
+                WhereRef {
+                    expr: BoExp::Exists(traversal),
+                }
+            }
+            _ => WhereRef { expr },
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Validates an index lookup such as `N<User>({email: email})` against the indexed fields
+/// of the item type, and returns the name of the index with the key to look up.
+fn gen_index_lookup(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    scope: &HashMap<&str, VariableInfo>,
+    item_kind: &str,
+    item_type: &str,
+    lookup: IdType,
+) -> (GenRef<String>, GeneratedValue) {
+    let IdType::ByIndex { index, value, loc } = lookup else {
+        unreachable!()
+    };
+    is_valid_identifier(ctx, original_query, loc.clone(), index.to_string().as_str());
+    let item_fields = match item_kind {
+        "edge" => ctx.edge_fields.get(item_type),
+        _ => ctx.node_fields.get(item_type),
+    };
+    let corresponding_field = match item_fields.cloned() {
+        Some(fields) => fields,
+        None => {
+            generate_error!(ctx, original_query, loc.clone(), E201, item_type);
+            HashMap::default()
+        }
+    };
+
+    match corresponding_field
+        .iter()
+        .find(|(name, _)| name.to_string() == *index.to_string())
+    {
+        Some((_, field)) => {
+            if !field.is_indexed() {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    loc.clone(),
+                    E208,
+                    [&index.to_string(), item_kind, item_type],
+                    [item_kind, item_type]
+                );
+            } else if let ValueType::Literal { ref value, ref loc } = *value
+                && let Some(enum_schema) = ctx.get_enum(&field.field_type)
+            {
+                check_enum_variant(
+                    ctx,
+                    original_query,
+                    loc.clone(),
+                    enum_schema,
+                    &value.inner_stringify(),
+                );
+            } else if let ValueType::Literal { ref value, ref loc } = *value
+                && !field.field_type.eq(value)
+            {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    loc.clone(),
+                    E205,
+                    &value.inner_stringify(),
+                    &value.to_variant_string(),
+                    &field.field_type.to_string(),
+                    item_kind,
+                    item_type
+                );
+            }
+        }
+        None => {
+            generate_error!(
+                ctx,
+                original_query,
+                loc.clone(),
+                E208,
+                [&index.to_string(), item_kind, item_type],
+                [item_kind, item_type]
+            );
+        }
+    };
+    let enum_schema = corresponding_field
+        .get(index.to_string().as_str())
+        .and_then(|field| ctx.get_enum(&field.field_type));
+    let index = GenRef::Literal(match *index {
+        IdType::Identifier { value, loc: _ } => value,
+        // would be caught by the parser
+        _ => unreachable!(),
+    });
+    let key = match *value {
+        ValueType::Identifier { value, loc } => {
+            if is_valid_identifier(ctx, original_query, loc.clone(), value.as_str())
+                && !scope.contains_key(value.as_str())
+            {
+                generate_error!(ctx, original_query, loc.clone(), E301, value.as_str());
+            }
+            gen_identifier_or_param(original_query, value.as_str(), true, false)
+        }
+        ValueType::Literal { value, loc: _ } => {
+            GeneratedValue::Primitive(GenRef::Ref(match (value, enum_schema) {
+                // enums are looked up by the index their variant is stored as
+                (Value::String(s), Some(enum_schema)) => format!("{}::{s}", enum_schema.name.1),
+                (Value::String(s), None) => format!("\"{s}\""),
+                (other, _) => other.inner_stringify(),
+            }))
+        }
+        _ => unreachable!(),
+    };
+    (index, key)
+}
+
+/// Validates a node passed to `E<Type>::Between(from, to)` and generates its id.
+fn gen_between_endpoint(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    scope: &HashMap<&str, VariableInfo>,
+    endpoint: &IdType,
+) -> GeneratedValue {
+    match endpoint {
+        IdType::Identifier { value, loc } => {
+            is_valid_identifier(ctx, original_query, loc.clone(), value.as_str());
+            match scope.get(value.as_str()) {
+                Some(var_info) if !var_info.is_single => {
+                    generate_error!(ctx, original_query, loc.clone(), E310, value.as_str());
+                }
+                None if is_param(original_query, value.as_str()).is_none() => {
+                    generate_error!(ctx, original_query, loc.clone(), E301, value.as_str());
+                }
+                _ => {}
+            }
+            gen_id_access_or_param(original_query, value.as_str())
+        }
+        IdType::Literal { value, loc: _ } => {
+            GeneratedValue::Literal(GenRef::Literal(value.clone()))
+        }
+        // would be caught by the parser
+        IdType::ByIndex { .. } => unreachable!(),
+    }
+}
+
 /// Validates the traversal and returns the end type of the traversal
 ///
 /// This method also builds the generated traversal (`gen_traversal`) as it analyzes the traversal
@@ -122,179 +416,77 @@ pub(crate) fn validate_traversal<'a>(
                 assert!(ids.len() == 1, "multiple ids not supported yet");
                 // check id exists in scope
                 match ids.first().cloned() {
-                    Some(id) => {
-                        match id {
-                            IdType::ByIndex { index, value, loc } => {
-                                is_valid_identifier(
-                                    ctx,
-                                    original_query,
-                                    loc.clone(),
-                                    index.to_string().as_str(),
-                                );
-                                let corresponding_field = ctx
-                                    .node_fields
-                                    .get(node_type.as_str())
-                                    .cloned()
-                                    .ok_or_else(|| {
-                                        generate_error!(
+                    Some(id) => match id {
+                        lookup @ IdType::ByIndex { .. } => {
+                            let (index, key) = gen_index_lookup(
+                                ctx,
+                                original_query,
+                                scope,
+                                "node",
+                                node_type,
+                                lookup,
+                            );
+                            gen_traversal.source_step =
+                                Separator::Period(SourceStep::NFromIndex(NFromIndex {
+                                    label: GenRef::Literal(node_type.clone()),
+                                    index,
+                                    key,
+                                    after: None,
+                                }));
+                            gen_traversal.should_collect = ShouldCollect::ToObj;
+                            gen_traversal.traversal_type = TraversalType::Ref;
+                            Type::Node(Some(node_type.to_string()))
+                        }
+                        IdType::Identifier { value: i, loc } => {
+                            gen_traversal.source_step =
+                                Separator::Period(SourceStep::NFromID(NFromID {
+                                    id: {
+                                        is_valid_identifier(
                                             ctx,
                                             original_query,
                                             loc.clone(),
-                                            E201,
-                                            node_type
+                                            i.as_str(),
                                         );
-                                    })
-                                    .unwrap_or_else(|_| {
-                                        generate_error!(
+                                        let _ = type_in_scope(
                                             ctx,
                                             original_query,
                                             loc.clone(),
-                                            E201,
-                                            node_type
+                                            scope,
+                                            i.as_str(),
                                         );
-                                        HashMap::default()
-                                    });
-
-                                match corresponding_field
-                                    .iter()
-                                    .find(|(name, _)| name.to_string() == *index.to_string())
-                                {
-                                    Some((_, field)) => {
-                                        if !field.is_indexed() {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E208,
-                                                [&index.to_string(), node_type],
-                                                [node_type]
-                                            );
-                                        } else if let ValueType::Literal { ref value, ref loc } =
-                                            *value
-                                            && !field.field_type.eq(value)
-                                        {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E205,
-                                                &value.inner_stringify(),
-                                                &value.to_variant_string(),
-                                                &field.field_type.to_string(),
-                                                "node",
-                                                node_type
-                                            );
-                                        }
-                                    }
-                                    None => {
-                                        generate_error!(
+                                        let value = gen_identifier_or_param(
+                                            original_query,
+                                            i.as_str(),
+                                            true,
+                                            false,
+                                        );
+                                        check_identifier_is_fieldtype(
                                             ctx,
                                             original_query,
                                             loc.clone(),
-                                            E208,
-                                            [&index.to_string(), node_type],
-                                            [node_type]
-                                        );
-                                    }
-                                };
-                                gen_traversal.source_step =
-                                    Separator::Period(SourceStep::NFromIndex(NFromIndex {
-                                        label: GenRef::Literal(node_type.clone()),
-                                        index: GenRef::Literal(match *index {
-                                            IdType::Identifier { value, loc: _ } => value,
-                                            // would be caught by the parser
-                                            _ => unreachable!(),
-                                        }),
-                                        key: match *value {
-                                            ValueType::Identifier { value, loc } => {
-                                                if is_valid_identifier(
-                                                    ctx,
-                                                    original_query,
-                                                    loc.clone(),
-                                                    value.as_str(),
-                                                ) && !scope.contains_key(value.as_str())
-                                                {
-                                                    generate_error!(
-                                                        ctx,
-                                                        original_query,
-                                                        loc.clone(),
-                                                        E301,
-                                                        value.as_str()
-                                                    );
-                                                }
-                                                gen_identifier_or_param(
-                                                    original_query,
-                                                    value.as_str(),
-                                                    true,
-                                                    false,
-                                                )
-                                            }
-                                            ValueType::Literal { value, loc: _ } => {
-                                                GeneratedValue::Primitive(GenRef::Ref(
-                                                    match value {
-                                                        Value::String(s) => format!("\"{s}\""),
-                                                        other => other.inner_stringify(),
-                                                    },
-                                                ))
-                                            }
-                                            _ => unreachable!(),
-                                        },
-                                    }));
-                                gen_traversal.should_collect = ShouldCollect::ToObj;
-                                gen_traversal.traversal_type = TraversalType::Ref;
-                                Type::Node(Some(node_type.to_string()))
-                            }
-                            IdType::Identifier { value: i, loc } => {
-                                gen_traversal.source_step =
-                                    Separator::Period(SourceStep::NFromID(NFromID {
-                                        id: {
-                                            is_valid_identifier(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                i.as_str(),
-                                            );
-                                            let _ = type_in_scope(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                scope,
-                                                i.as_str(),
-                                            );
-                                            let value = gen_identifier_or_param(
-                                                original_query,
-                                                i.as_str(),
-                                                true,
-                                                false,
-                                            );
-                                            check_identifier_is_fieldtype(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                scope,
-                                                i.as_str(),
-                                                FieldType::Uuid,
-                                            )?;
-                                            value.inner().clone()
-                                        },
-                                        label: GenRef::Literal(node_type.clone()),
-                                    }));
-                                gen_traversal.traversal_type = TraversalType::Ref;
-                                gen_traversal.should_collect = ShouldCollect::ToObj;
-                                Type::Node(Some(node_type.to_string()))
-                            }
-                            IdType::Literal { value: s, loc: _ } => {
-                                gen_traversal.source_step =
-                                    Separator::Period(SourceStep::NFromID(NFromID {
-                                        id: GenRef::Ref(s.clone()),
-                                        label: GenRef::Literal(node_type.clone()),
-                                    }));
-                                gen_traversal.traversal_type = TraversalType::Ref;
-                                gen_traversal.should_collect = ShouldCollect::ToObj;
-                                Type::Node(Some(node_type.to_string()))
-                            }
+                                            scope,
+                                            i.as_str(),
+                                            FieldType::Uuid,
+                                        )?;
+                                        value.inner().clone()
+                                    },
+                                    label: GenRef::Literal(node_type.clone()),
+                                }));
+                            gen_traversal.traversal_type = TraversalType::Ref;
+                            gen_traversal.should_collect = ShouldCollect::ToObj;
+                            Type::Node(Some(node_type.to_string()))
                         }
-                    }
+                        IdType::Literal { value: s, loc: _ } => {
+                            gen_traversal.source_step =
+                                Separator::Period(SourceStep::NFromID(NFromID {
+                                    id: GenRef::Ref(s.clone()),
+                                    label: GenRef::Literal(node_type.clone()),
+                                }));
+                            gen_traversal.traversal_type = TraversalType::Ref;
+                            gen_traversal.should_collect = ShouldCollect::ToObj;
+                            Type::Node(Some(node_type.to_string()))
+                        }
+                    },
                     None => {
                         generate_error!(ctx, original_query, tr.loc.clone(), E601, "missing id");
                         Type::Unknown
@@ -303,6 +495,7 @@ pub(crate) fn validate_traversal<'a>(
             } else {
                 gen_traversal.source_step = Separator::Period(SourceStep::NFromType(NFromType {
                     label: GenRef::Literal(node_type.clone()),
+                    after: None,
                 }));
                 gen_traversal.traversal_type = TraversalType::Ref;
                 Type::Nodes(Some(node_type.to_string()))
@@ -312,7 +505,24 @@ pub(crate) fn validate_traversal<'a>(
             if !ctx.edge_map.contains_key(edge_type.as_str()) {
                 generate_error!(ctx, original_query, tr.loc.clone(), E102, edge_type);
             }
-            if let Some(ids) = ids {
+            if let Some([lookup @ IdType::ByIndex { .. }]) = ids.as_deref() {
+                let (index, key) = gen_index_lookup(
+                    ctx,
+                    original_query,
+                    scope,
+                    "edge",
+                    edge_type,
+                    lookup.clone(),
+                );
+                gen_traversal.source_step = Separator::Period(SourceStep::EFromIndex(EFromIndex {
+                    label: GenRef::Literal(edge_type.clone()),
+                    index,
+                    key,
+                    after: None,
+                }));
+                gen_traversal.traversal_type = TraversalType::Ref;
+                Type::Edges(Some(edge_type.to_string()))
+            } else if let Some(ids) = ids {
                 assert!(ids.len() == 1, "multiple ids not supported yet");
                 gen_traversal.source_step = Separator::Period(SourceStep::EFromID(EFromID {
                     id: match ids.first().cloned() {
@@ -356,11 +566,30 @@ pub(crate) fn validate_traversal<'a>(
             } else {
                 gen_traversal.source_step = Separator::Period(SourceStep::EFromType(EFromType {
                     label: GenRef::Literal(edge_type.clone()),
+                    after: None,
                 }));
                 gen_traversal.traversal_type = TraversalType::Ref;
                 Type::Edges(Some(edge_type.to_string()))
             }
         }
+        StartNode::EdgesBetween {
+            edge_type,
+            from,
+            to,
+        } => {
+            if !ctx.edge_map.contains_key(edge_type.as_str()) {
+                generate_error!(ctx, original_query, tr.loc.clone(), E102, edge_type);
+            }
+            let from = gen_between_endpoint(ctx, original_query, scope, from);
+            let to = gen_between_endpoint(ctx, original_query, scope, to);
+            gen_traversal.source_step = Separator::Period(SourceStep::EBetween(EBetween {
+                label: GenRef::Literal(edge_type.clone()),
+                from,
+                to,
+            }));
+            gen_traversal.traversal_type = TraversalType::Ref;
+            Type::Edges(Some(edge_type.to_string()))
+        }
         StartNode::Vector { vector_type, ids } => {
             if !ctx.vector_set.contains(vector_type.as_str()) {
                 generate_error!(ctx, original_query, tr.loc.clone(), E103, vector_type);
@@ -461,11 +690,14 @@ pub(crate) fn validate_traversal<'a>(
             parent
         }
         StartNode::SearchVector(sv) => {
-            if let Some(ref ty) = sv.vector_type
-                && !ctx.vector_set.contains(ty.as_str())
-            {
-                generate_error!(ctx, original_query, sv.loc.clone(), E103, ty.as_str());
-            }
+            let (vector_type, owner) = match &sv.vector_type {
+                Some(ty) => {
+                    let (vector_type, owner) =
+                        resolve_search_type(ctx, original_query, sv.loc.clone(), ty);
+                    (Some(vector_type), owner)
+                }
+                None => (None, None),
+            };
             let vec: VecData = match &sv.data {
                 Some(VectorData::Vector(v)) => {
                     VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
@@ -619,13 +851,21 @@ pub(crate) fn validate_traversal<'a>(
             gen_traversal.traversal_type = TraversalType::Ref;
             gen_traversal.should_collect = ShouldCollect::ToVec;
             gen_traversal.source_step = Separator::Period(SourceStep::SearchVector(SearchVector {
-                label: GenRef::Literal(sv.vector_type.clone().unwrap()),
+                label: GenRef::Literal(vector_type.clone().unwrap()),
                 vec,
                 k,
                 pre_filter,
             }));
-            // Search returns nodes that contain the vectors
-            Type::Vectors(sv.vector_type.clone())
+            // Searching a node type joins the vectors back to the nodes embedding them
+            match owner {
+                Some(owner) => {
+                    gen_traversal
+                        .steps
+                        .push(Separator::Period(GeneratedStep::OwnerN(owner)));
+                    Type::Nodes(sv.vector_type.clone()).with_computed_property("score", FieldType::F64)
+                }
+                None => Type::Vectors(vector_type),
+            }
         }
     };
 
@@ -691,6 +931,15 @@ pub(crate) fn validate_traversal<'a>(
             }
 
             StepType::Object(obj) => {
+                ctx.object_types.push((
+                    Loc::new(
+                        original_query.loc.filepath.clone(),
+                        obj.loc.start,
+                        obj.loc.end,
+                        obj.loc.span.clone(),
+                    ),
+                    cur_ty.clone(),
+                ));
                 // For intermediate object steps, we don't track fields for return values
                 // Fields are only tracked when this traversal is used in a RETURN statement
                 let mut fields_out = vec![];
@@ -708,7 +957,7 @@ pub(crate) fn validate_traversal<'a>(
             }
 
             StepType::Where(expr) => {
-                let (_, stmt) = infer_expr_type(
+                let (ty, stmt) = infer_expr_type(
                     ctx,
                     expr,
                     scope,
@@ -721,52 +970,68 @@ pub(crate) fn validate_traversal<'a>(
                 if stmt.is_none() {
                     return Some(cur_ty.clone());
                 }
-                let stmt = stmt.unwrap();
-                match stmt {
-                    GeneratedStatement::Traversal(tr) => {
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::Where(Where::Ref(
-                                WhereRef {
-                                    expr: BoExp::Expr(tr),
-                                },
-                            ))));
-                    }
-                    GeneratedStatement::BoExp(expr) => {
-                        // if Not(Exists()) or Exits() need to modify the traversal to not collect
-                        // else return where as normal
-                        let where_expr = match expr {
-                            BoExp::Not(inner_expr) => {
-                                if let BoExp::Exists(mut traversal) = *inner_expr {
-                                    traversal.should_collect = ShouldCollect::No;
-                                    Where::Ref(WhereRef {
-                                        expr: BoExp::Not(Box::new(BoExp::Exists(traversal))),
-                                    })
-                                } else {
-                                    Where::Ref(WhereRef {
-                                        // expr gets moved at start of match to allow for box dereference so need to move back
-                                        expr: BoExp::Not(inner_expr),
-                                    })
-                                }
-                            }
-                            BoExp::Exists(mut traversal) => {
-                                traversal.should_collect = ShouldCollect::No;
-                                Where::Ref(WhereRef {
-                                    expr: BoExp::Exists(traversal),
-                                })
-                            }
-                            _ => Where::Ref(WhereRef { expr }),
-                        };
-
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::Where(where_expr)));
-                    }
-                    _ => unreachable!(),
+                // only the boolean string functions can be used as a filter
+                if let Some(GeneratedStatement::ScalarFunction(_)) = stmt {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        expr.loc.clone(),
+                        E621,
+                        ty.kind_str(),
+                        &ty.get_type_name()
+                    );
+                    return Some(cur_ty.clone());
                 }
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::Where(Where::Ref(where_ref_from(
+                        stmt.unwrap(),
+                    )))));
             }
             StepType::BooleanOperation(b_op) => {
                 let step = previous_step.unwrap();
+                // `ANY`/`ALL` check their op against the elements of the array property
+                let quantified = b_op;
+                let (b_op, cur_ty) = match &quantified.op {
+                    BooleanOpType::Any(op) | BooleanOpType::All(op) => match &cur_ty {
+                        Type::Scalar(FieldType::Array(element)) => {
+                            (op.as_ref(), Type::Scalar(*element.clone()))
+                        }
+                        _ => {
+                            let quantifier = match quantified.op {
+                                BooleanOpType::Any(_) => "ANY",
+                                _ => "ALL",
+                            };
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                quantified.loc.clone(),
+                                E666,
+                                [quantifier, &cur_ty.get_type_name()],
+                                [quantifier]
+                            );
+                            return Some(cur_ty.clone());
+                        }
+                    },
+                    _ => (quantified, cur_ty.clone()),
+                };
+                if let BooleanOpType::IsNull = b_op.op {
+                    // IS_NULL takes no value to compare against, it only checks whether
+                    // the property fetched by the previous step is present
+                    if let StepType::Object(obj) = &step
+                        && let Some(field) = obj.fields.first()
+                    {
+                        ctx.null_checked_fields.insert(field.key.clone());
+                    }
+                    gen_traversal
+                        .steps
+                        .push(Separator::Period(GeneratedStep::BoolOp(BoolOp::IsNull(IsNull))));
+                    gen_traversal.should_collect = ShouldCollect::No;
+                    previous_step = Some(graph_step.step.clone());
+                    continue;
+                }
+                // string and date functions compared against are generated alongside the type check
+                let mut compared_call = None;
                 let property_type = match &b_op.op {
                     BooleanOpType::LessThanOrEqual(expr)
                     | BooleanOpType::LessThan(expr)
@@ -783,6 +1048,20 @@ pub(crate) fn validate_traversal<'a>(
                             Some(cur_ty.clone()),
                             gen_query,
                         ) {
+                            (
+                                ty,
+                                Some(
+                                    GeneratedStatement::ScalarFunction(call)
+                                    | GeneratedStatement::BoExp(BoExp::ScalarFunction(call)),
+                                ),
+                            ) => {
+                                compared_call =
+                                    Some(GeneratedValue::Primitive(GenRef::Std(call.to_string())));
+                                match ty {
+                                    Type::Scalar(ft) => ft,
+                                    _ => FieldType::Boolean,
+                                }
+                            }
                             (Type::Scalar(ft), _) => ft.clone(),
                             (Type::Boolean, _) => FieldType::Boolean,
                             (field_type, _) => {
@@ -847,9 +1126,38 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     _ => None,
                 };
+                // a string literal compared with an enum field has to name one of its variants
+                let property_type = match (&b_op.op, &field_name) {
+                    (
+                        BooleanOpType::Equal(expr) | BooleanOpType::NotEqual(expr),
+                        Some(FieldValueType::Identifier(field_name)),
+                    ) => {
+                        let enum_schema = match &cur_ty {
+                            Type::Scalar(ft) => ctx.get_enum(ft),
+                            _ => ctx
+                                .get_item_fields(&cur_ty)
+                                .and_then(|fields| fields.get(field_name.as_str()))
+                                .and_then(|field| ctx.get_enum(&field.field_type)),
+                        };
+                        match (&expr.expr, enum_schema) {
+                            (ExpressionType::StringLiteral(s), Some(enum_schema)) => {
+                                check_enum_variant(
+                                    ctx,
+                                    original_query,
+                                    expr.loc.clone(),
+                                    enum_schema,
+                                    s,
+                                );
+                                FieldType::Identifier(enum_schema.name.1.clone())
+                            }
+                            _ => property_type,
+                        }
+                    }
+                    _ => property_type,
+                };
                 if let Some(FieldValueType::Identifier(field_name)) = &field_name {
                     is_valid_identifier(ctx, original_query, b_op.loc.clone(), field_name.as_str());
-                    match &cur_ty {
+                    match cur_ty.base() {
                         Type::Scalar(ft) => {
                             if ft != &property_type {
                                 generate_error!(
@@ -1075,6 +1383,9 @@ pub(crate) fn validate_traversal<'a>(
                     BooleanOpType::LessThanOrEqual(expr) => {
                         // assert!()
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::IntegerLiteral(i) => {
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
@@ -1099,6 +1410,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::LessThan(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::IntegerLiteral(i) => {
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
@@ -1123,6 +1437,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::GreaterThanOrEqual(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::IntegerLiteral(i) => {
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
@@ -1147,6 +1464,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::GreaterThan(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::IntegerLiteral(i) => {
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
@@ -1171,6 +1491,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::Equal(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::BooleanLiteral(b) => {
                                 GeneratedValue::Primitive(GenRef::Std(b.to_string()))
                             }
@@ -1181,7 +1504,12 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(f.to_string()))
                             }
                             ExpressionType::StringLiteral(s) => {
-                                GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
+                                match ctx.get_enum(&property_type) {
+                                    Some(enum_schema) => gen_enum_variant(enum_schema, s),
+                                    None => {
+                                        GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
+                                    }
+                                }
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1218,6 +1546,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::NotEqual(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::BooleanLiteral(b) => {
                                 GeneratedValue::Primitive(GenRef::Std(b.to_string()))
                             }
@@ -1228,7 +1559,12 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(f.to_string()))
                             }
                             ExpressionType::StringLiteral(s) => {
-                                GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
+                                match ctx.get_enum(&property_type) {
+                                    Some(enum_schema) => gen_enum_variant(enum_schema, s),
+                                    None => {
+                                        GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
+                                    }
+                                }
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1263,6 +1599,9 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     BooleanOpType::Contains(expr) => {
                         let v = match &expr.expr {
+                            ExpressionType::ScalarFunctionCall(_) => {
+                                compared_call.clone().unwrap_or(GeneratedValue::Unknown)
+                            }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
                                     ctx,
@@ -1336,6 +1675,11 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     _ => unreachable!("shouldve been caught earlier"),
                 };
+                let op = match &quantified.op {
+                    BooleanOpType::Any(_) => BoolOp::Any(Any { op: Box::new(op) }),
+                    BooleanOpType::All(_) => BoolOp::All(All { op: Box::new(op) }),
+                    _ => op,
+                };
                 gen_traversal
                     .steps
                     .push(Separator::Period(GeneratedStep::BoolOp(op)));
@@ -1352,11 +1696,21 @@ pub(crate) fn validate_traversal<'a>(
 
                 // Capture aggregate metadata before replacing cur_ty
                 let property_names = aggr.properties.clone();
+                let enum_fields = ctx.get_enum_fields(&cur_ty);
+                let aggregates = validate_aggregations(
+                    ctx,
+                    original_query,
+                    &cur_ty,
+                    &aggr.properties,
+                    &["count", "items"],
+                    &aggr.aggregations,
+                );
                 cur_ty = Type::Aggregate(AggregateInfo {
                     source_type: Box::new(cur_ty.clone()),
                     properties: property_names,
                     is_count: should_count,
                     is_group_by: false, // This is AGGREGATE_BY
+                    aggregates,
                 });
 
                 gen_traversal.should_collect = ShouldCollect::Try;
@@ -1365,6 +1719,12 @@ pub(crate) fn validate_traversal<'a>(
                     .push(Separator::Period(GeneratedStep::AggregateBy(AggregateBy {
                         properties,
                         should_count,
+                        aggregations: aggr
+                            .aggregations
+                            .iter()
+                            .map(|a| (a.name.clone(), a.function.clone()))
+                            .collect(),
+                        enum_fields,
                     })))
             }
             StepType::GroupBy(gb) => {
@@ -1378,11 +1738,21 @@ pub(crate) fn validate_traversal<'a>(
 
                 // Capture aggregate metadata before replacing cur_ty
                 let property_names = gb.properties.clone();
+                let enum_fields = ctx.get_enum_fields(&cur_ty);
+                let aggregates = validate_aggregations(
+                    ctx,
+                    original_query,
+                    &cur_ty,
+                    &gb.properties,
+                    &["count"],
+                    &gb.aggregations,
+                );
                 cur_ty = Type::Aggregate(AggregateInfo {
                     source_type: Box::new(cur_ty.clone()),
                     properties: property_names,
                     is_count: should_count,
                     is_group_by: true, // This is GROUP_BY
+                    aggregates,
                 });
 
                 gen_traversal.should_collect = ShouldCollect::Try;
@@ -1391,6 +1761,12 @@ pub(crate) fn validate_traversal<'a>(
                     .push(Separator::Period(GeneratedStep::GroupBy(GroupBy {
                         properties,
                         should_count,
+                        aggregations: gb
+                            .aggregations
+                            .iter()
+                            .map(|a| (a.name.clone(), a.function.clone()))
+                            .collect(),
+                        enum_fields,
                     })))
             }
             StepType::Update(update) => {
@@ -1399,7 +1775,7 @@ pub(crate) fn validate_traversal<'a>(
 
                 // Update returns the same type (nodes/edges) it started with.
 
-                match &cur_ty {
+                match cur_ty.base() {
                     Type::Node(Some(ty))
                     | Type::Nodes(Some(ty))
                     | Type::Edge(Some(ty))
@@ -1414,6 +1790,33 @@ pub(crate) fn validate_traversal<'a>(
                                 .map(|field| (field.key.as_str(), &field.loc))
                                 .collect(),
                         );
+                        // literals written to enum fields have to name one of the variants
+                        for field in &update.fields {
+                            let enum_schema = ctx
+                                .get_item_fields(&cur_ty)
+                                .and_then(|fields| fields.get(field.key.as_str()))
+                                .and_then(|f| ctx.get_enum(&f.field_type));
+                            let literal = match &field.value.value {
+                                FieldValueType::Literal(l) => Some(l.inner_stringify()),
+                                FieldValueType::Expression(e) => match &e.expr {
+                                    ExpressionType::StringLiteral(s) => Some(s.clone()),
+                                    ExpressionType::IntegerLiteral(i) => Some(i.to_string()),
+                                    ExpressionType::FloatLiteral(f) => Some(f.to_string()),
+                                    ExpressionType::BooleanLiteral(b) => Some(b.to_string()),
+                                    _ => None,
+                                },
+                                _ => None,
+                            };
+                            if let (Some(enum_schema), Some(literal)) = (enum_schema, literal) {
+                                check_enum_variant(
+                                    ctx,
+                                    original_query,
+                                    field.value.loc.clone(),
+                                    enum_schema,
+                                    &literal,
+                                );
+                            }
+                        }
                     }
                     other => {
                         generate_error!(
@@ -1426,76 +1829,171 @@ pub(crate) fn validate_traversal<'a>(
                         return Some(cur_ty.clone());
                     }
                 }
-                gen_traversal.traversal_type = TraversalType::Update(Some(
-                    update
-                        .fields
-                        .iter()
-                        .map(|field| {
-                            (
-                                field.key.clone(),
-                                match &field.value.value {
-                                    FieldValueType::Identifier(i) => {
-                                        is_valid_identifier(
-                                            ctx,
-                                            original_query,
-                                            field.value.loc.clone(),
-                                            i.as_str(),
-                                        );
-                                        gen_identifier_or_param(
-                                            original_query,
-                                            i.as_str(),
-                                            true,
-                                            true,
-                                        )
-                                    }
-                                    FieldValueType::Literal(l) => match l {
-                                        Value::String(s) => {
-                                            GeneratedValue::Literal(GenRef::Literal(s.clone()))
-                                        }
-                                        other => GeneratedValue::Primitive(GenRef::Std(
-                                            other.inner_stringify(),
-                                        )),
-                                    },
-                                    FieldValueType::Expression(e) => match &e.expr {
-                                        ExpressionType::Identifier(i) => {
-                                            is_valid_identifier(
-                                                ctx,
-                                                original_query,
-                                                e.loc.clone(),
-                                                i.as_str(),
-                                            );
-                                            gen_identifier_or_param(
-                                                original_query,
-                                                i.as_str(),
-                                                true,
-                                                true,
-                                            )
-                                        }
-                                        ExpressionType::StringLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                                        }
-
-                                        ExpressionType::IntegerLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                                        }
-                                        ExpressionType::FloatLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                                        }
-                                        ExpressionType::BooleanLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                                        }
-                                        _ => {
-                                            panic!("expr be primitive or value")
+                // embedded vectors are replaced once the node is updated
+                let (node_type, embedded) = match cur_ty.base() {
+                    Type::Node(Some(ty)) | Type::Nodes(Some(ty)) => {
+                        (ty.clone(), ctx.get_embeddings(ty))
+                    }
+                    _ => (String::new(), Vec::new()),
+                };
+                for field in &update.fields {
+                    let Some((_, vector_type)) =
+                        embedded.iter().find(|(name, _)| *name == field.key)
+                    else {
+                        continue;
+                    };
+                    let value_type = match &field.value.value {
+                        FieldValueType::Identifier(i) => Ok(i),
+                        FieldValueType::Expression(e) => match &e.expr {
+                            ExpressionType::Identifier(i) => Ok(i),
+                            ExpressionType::StringLiteral(_) => Err("String"),
+                            ExpressionType::IntegerLiteral(_) => Err("I32"),
+                            ExpressionType::FloatLiteral(_) => Err("F64"),
+                            ExpressionType::BooleanLiteral(_) => Err("Boolean"),
+                            _ => Err("expression"),
+                        },
+                        FieldValueType::Literal(l) => Err(l.to_variant_string()),
+                        _ => Err("expression"),
+                    };
+                    match value_type {
+                        Ok(i) => {
+                            is_valid_identifier(
+                                ctx,
+                                original_query,
+                                field.value.loc.clone(),
+                                i.as_str(),
+                            );
+                            gen_traversal.embeddings.push(SetEmbedding {
+                                field: GenRef::Literal(field.key.clone()),
+                                label: GenRef::Literal(vector_type.clone()),
+                                vec: VecData::Standard(gen_identifier_or_param(
+                                    original_query,
+                                    i.as_str(),
+                                    true,
+                                    false,
+                                )),
+                            });
+                        }
+                        Err(value_type) => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                field.value.loc.clone(),
+                                E205,
+                                &field.value.loc.span,
+                                value_type,
+                                &format!("V::{vector_type}"),
+                                "node",
+                                &node_type
+                            );
+                        }
+                    }
+                }
+                // values that read the item's properties are computed per item
+                let mut reads_item = false;
+                let properties = update
+                    .fields
+                    .iter()
+                    .filter(|field| !embedded.iter().any(|(name, _)| *name == field.key))
+                    .map(|field| {
+                        let enum_schema = ctx
+                            .get_item_fields(&cur_ty)
+                            .and_then(|fields| fields.get(field.key.as_str()))
+                            .and_then(|f| ctx.get_enum(&f.field_type));
+                        (
+                            field.key.clone(),
+                            match &field.value.value {
+                                FieldValueType::Identifier(i) => {
+                                    is_valid_identifier(
+                                        ctx,
+                                        original_query,
+                                        field.value.loc.clone(),
+                                        i.as_str(),
+                                    );
+                                    gen_identifier_or_param(original_query, i.as_str(), true, true)
+                                }
+                                FieldValueType::Literal(l) => match l {
+                                    Value::String(s) => match enum_schema {
+                                        Some(enum_schema) => gen_enum_variant(enum_schema, s),
+                                        None => GeneratedValue::Literal(GenRef::Literal(s.clone())),
+                                    },
+                                    other => GeneratedValue::Primitive(GenRef::Std(
+                                        other.inner_stringify(),
+                                    )),
+                                },
+                                FieldValueType::Expression(e) => match &e.expr {
+                                    ExpressionType::Identifier(i) => {
+                                        is_valid_identifier(
+                                            ctx,
+                                            original_query,
+                                            e.loc.clone(),
+                                            i.as_str(),
+                                        );
+                                        gen_identifier_or_param(
+                                            original_query,
+                                            i.as_str(),
+                                            true,
+                                            true,
+                                        )
+                                    }
+                                    ExpressionType::StringLiteral(i) => match enum_schema {
+                                        Some(enum_schema) => gen_enum_variant(enum_schema, i),
+                                        None => {
+                                            GeneratedValue::Literal(GenRef::Literal(i.to_string()))
                                         }
                                     },
+
+                                    ExpressionType::IntegerLiteral(i) => {
+                                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                                    }
+                                    ExpressionType::FloatLiteral(i) => {
+                                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                                    }
+                                    ExpressionType::BooleanLiteral(i) => {
+                                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                                    }
+                                    // `APPEND(value)` and `REMOVE(value)` change the field
+                                    // they're assigned to
+                                    ExpressionType::ScalarFunctionCall(call) => {
+                                        let field_type = ctx
+                                            .get_item_fields(&cur_ty)
+                                            .and_then(|fields| {
+                                                fields.get(field.key.as_str()).cloned()
+                                            })
+                                            .map(|f| f.field_type.clone());
+                                        match gen_scalar_function_call(
+                                            ctx,
+                                            call,
+                                            scope,
+                                            original_query,
+                                            Some(cur_ty.clone()),
+                                            field_type.as_ref().map(|ty| (field.key.as_str(), ty)),
+                                            gen_query,
+                                        ) {
+                                            Some((_, gen_call)) => {
+                                                reads_item |= gen_call.uses_item();
+                                                GeneratedValue::Primitive(GenRef::Std(
+                                                    gen_call.to_string(),
+                                                ))
+                                            }
+                                            None => GeneratedValue::Unknown,
+                                        }
+                                    }
                                     _ => {
-                                        panic!("Should be primitive or value")
+                                        panic!("expr be primitive or value")
                                     }
                                 },
-                            )
-                        })
-                        .collect(),
-                ));
+                                _ => {
+                                    panic!("Should be primitive or value")
+                                }
+                            },
+                        )
+                    })
+                    .collect();
+                gen_traversal.traversal_type = match reads_item {
+                    true => TraversalType::UpdateWith(properties),
+                    false => TraversalType::Update(Some(properties)),
+                };
                 cur_ty = cur_ty.into_single();
                 gen_traversal.should_collect = ShouldCollect::No;
                 excluded.clear();
@@ -1636,6 +2134,12 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     _ => unreachable!("shouldve been caught eariler"),
                 };
+                // An ORDER right before the range only needs to keep the items up to its end
+                if let Some(Separator::Period(GeneratedStep::OrderBy(order_by))) =
+                    gen_traversal.steps.last_mut()
+                {
+                    order_by.top_k = Some(end.clone());
+                }
                 gen_traversal
                     .steps
                     .push(Separator::Period(GeneratedStep::Range(Range {
@@ -1643,56 +2147,154 @@ pub(crate) fn validate_traversal<'a>(
                         end,
                     })));
             }
+            StepType::After(expr) => {
+                let after = match &expr.expr {
+                    ExpressionType::StringLiteral(s) => GenRef::Literal(s.clone()),
+                    ExpressionType::Identifier(i) => {
+                        is_valid_identifier(ctx, original_query, expr.loc.clone(), i.as_str());
+                        let ty =
+                            type_in_scope(ctx, original_query, expr.loc.clone(), scope, i.as_str());
+                        // Cursors come from the client, so only `String` parameters are accepted
+                        match ty {
+                            Some(Type::Scalar(FieldType::String))
+                                if is_param(original_query, i.as_str()).is_some() => {}
+                            Some(ty) => {
+                                generate_error!(
+                                    ctx,
+                                    original_query,
+                                    expr.loc.clone(),
+                                    E634,
+                                    i.as_str(),
+                                    &ty.get_type_name()
+                                );
+                                return Some(cur_ty.clone());
+                            }
+                            None => return Some(cur_ty.clone()),
+                        }
+                        gen_identifier_or_param(original_query, i.as_str(), true, false)
+                            .inner()
+                            .clone()
+                    }
+                    _ => unreachable!("shouldve been caught eariler"),
+                };
+                // The cursor is the last id of the previous page, so it can only resume
+                // label and index scans and adjacency steps, which seek straight to it.
+                // `WHERE` steps only drop items, so the cursor can be applied before them.
+                let filters = gen_traversal
+                    .steps
+                    .iter()
+                    .rev()
+                    .take_while(|step| matches!(step.inner(), GeneratedStep::Where(_)))
+                    .count();
+                let seek_step = gen_traversal.steps.len() - filters;
+                let cursor = match seek_step.checked_sub(1) {
+                    None => match &mut gen_traversal.source_step {
+                        Separator::Period(SourceStep::NFromType(NFromType { after, .. }))
+                        | Separator::Period(SourceStep::EFromType(EFromType { after, .. }))
+                        | Separator::Period(SourceStep::NFromIndex(NFromIndex {
+                            after, ..
+                        }))
+                        | Separator::Period(SourceStep::EFromIndex(EFromIndex {
+                            after, ..
+                        })) => Some(after),
+                        _ => None,
+                    },
+                    Some(i) => match &mut gen_traversal.steps[i] {
+                        Separator::Period(GeneratedStep::Out(GeneratedOut {
+                            edge_type: EdgeType::Node,
+                            after,
+                            ..
+                        }))
+                        | Separator::Period(GeneratedStep::In(GeneratedIn {
+                            edge_type: EdgeType::Node,
+                            after,
+                            ..
+                        }))
+                        | Separator::Period(GeneratedStep::OutE(GeneratedOutE { after, .. }))
+                        | Separator::Period(GeneratedStep::InE(GeneratedInE { after, .. })) => {
+                            Some(after)
+                        }
+                        _ => None,
+                    },
+                };
+                match cursor {
+                    Some(cursor) if cursor.is_none() => *cursor = Some(after),
+                    _ => generate_error!(ctx, original_query, graph_step.loc.clone(), E635),
+                }
+            }
+            StepType::Cursor => {
+                cur_ty = Type::Scalar(FieldType::String);
+                excluded.clear();
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::Cursor));
+                gen_traversal.should_collect = ShouldCollect::No;
+            }
             StepType::OrderBy(order_by) => {
-                // verify property access
-                let (_, stmt) = infer_expr_type(
-                    ctx,
-                    &order_by.expression,
-                    scope,
-                    original_query,
-                    Some(cur_ty.clone()),
-                    gen_query,
-                );
+                let mut keys = Vec::with_capacity(order_by.keys.len());
+                for key in &order_by.keys {
+                    // verify property access
+                    let (_, stmt) = infer_expr_type(
+                        ctx,
+                        &key.expression,
+                        scope,
+                        original_query,
+                        Some(cur_ty.clone()),
+                        gen_query,
+                    );
 
-                if stmt.is_none() {
-                    return Some(cur_ty.clone());
-                }
-                match stmt.unwrap() {
-                    GeneratedStatement::Traversal(traversal) => {
-                        let property = match &traversal.steps.last() {
-                            Some(step) => match &step.inner() {
-                                GeneratedStep::PropertyFetch(property) => property.clone(),
-                                _ => unreachable!("Cannot reach here"),
-                            },
-                            None => unreachable!("Cannot reach here"),
-                        };
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::OrderBy(OrderBy {
+                    if stmt.is_none() {
+                        return Some(cur_ty.clone());
+                    }
+                    match stmt.unwrap() {
+                        GeneratedStatement::Traversal(traversal) => {
+                            let property = match &traversal.steps.last() {
+                                Some(step) => match &step.inner() {
+                                    GeneratedStep::PropertyFetch(property) => property.clone(),
+                                    _ => unreachable!("Cannot reach here"),
+                                },
+                                None => unreachable!("Cannot reach here"),
+                            };
+                            keys.push(OrderKey {
                                 property,
-                                order: match order_by.order_by_type {
+                                order: match key.order_by_type {
                                     OrderByType::Asc => Order::Asc,
                                     OrderByType::Desc => Order::Desc,
                                 },
-                            })));
-                        gen_traversal.should_collect = ShouldCollect::ToVec;
+                                nulls_first: key.nulls == Some(NullsOrder::First),
+                            });
+                        }
+                        _ => unreachable!("Cannot reach here"),
                     }
-                    _ => unreachable!("Cannot reach here"),
                 }
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::OrderBy(OrderBy {
+                        keys,
+                        top_k: None,
+                    })));
+                gen_traversal.should_collect = ShouldCollect::ToVec;
             }
             StepType::Closure(cl) => {
                 if i != number_of_steps {
                     generate_error!(ctx, original_query, cl.loc.clone(), E641);
                 }
+                ctx.object_types.push((
+                    Loc::new(
+                        original_query.loc.filepath.clone(),
+                        cl.object.loc.start,
+                        cl.object.loc.end,
+                        cl.object.loc.span.clone(),
+                    ),
+                    cur_ty.clone(),
+                ));
                 // Add identifier to a temporary scope so inner uses pass
                 // For closures iterating over collections, singularize the type
                 let was_collection =
-                    matches!(cur_ty, Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_));
-                let closure_param_type = match &cur_ty {
-                    Type::Nodes(label) => Type::Node(label.clone()),
-                    Type::Edges(label) => Type::Edge(label.clone()),
-                    Type::Vectors(label) => Type::Vector(label.clone()),
-                    other => other.clone(),
+                    matches!(cur_ty.base(), Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_));
+                let closure_param_type = match was_collection {
+                    true => cur_ty.clone().into_single(),
+                    false => cur_ty.clone(),
                 };
 
                 // Extract the source variable name from the current traversal
@@ -1747,12 +2349,7 @@ pub(crate) fn validate_traversal<'a>(
                     gen_traversal.should_collect = ShouldCollect::ToVec;
                     // Also convert the return type back to collection type
                     // This ensures is_collection flag is set correctly in query_validation.rs
-                    cur_ty = match cur_ty {
-                        Type::Node(label) => Type::Nodes(label),
-                        Type::Edge(label) => Type::Edges(label),
-                        Type::Vector(label) => Type::Vectors(label),
-                        other => other,
-                    };
+                    cur_ty = cur_ty.into_collection();
                 }
 
                 scope.remove(cl.identifier.as_str());
@@ -1856,7 +2453,7 @@ pub(crate) fn validate_traversal<'a>(
         previous_step = Some(step.clone());
     }
     match gen_traversal.traversal_type {
-        TraversalType::Mut | TraversalType::Update(_) => {
+        TraversalType::Mut | TraversalType::Update(_) | TraversalType::UpdateWith(_) => {
             gen_query.is_mut = true;
         }
         _ => {}
@@ -2032,6 +2629,120 @@ mod tests {
     // Traversal Step Tests
     // ============================================================================
 
+    #[test]
+    fn test_edge_index_and_between_lookups() {
+        let source = r#"
+            N::Person { name: String }
+            E::Follows { From: Person, To: Person, Properties: { INDEX since: I64 } }
+
+            QUERY test(a: ID, b: ID, since: I64) =>
+                indexed <- E<Follows>({since: since})
+                between <- E<Follows>::Between(a, b)
+                RETURN indexed, between
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_edge_lookup_on_unindexed_field() {
+        let source = r#"
+            N::Person { name: String }
+            E::Follows { From: Person, To: Person, Properties: { since: I64 } }
+
+            QUERY test(since: I64) =>
+                edges <- E<Follows>({since: since})
+                RETURN edges
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E208));
+    }
+
+    #[test]
+    fn test_between_plural_nodes() {
+        let source = r#"
+            N::Person { name: String }
+            E::Follows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                people <- N<Person>
+                person <- N<Person>(id)
+                edges <- E<Follows>::Between(people, person)
+                RETURN edges
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E310));
+    }
+
+    #[test]
+    fn test_embedded_vector_queries() {
+        let source = r#"
+            V::DocEmb {}
+            N::Doc { title: String, embedding: V::DocEmb }
+
+            QUERY add(title: String, vec: [F64]) =>
+                doc <- AddN<Doc>({title: title, embedding: vec})
+                RETURN doc
+
+            QUERY reembed(id: ID, vec: [F64]) =>
+                doc <- N<Doc>(id)::UPDATE({embedding: vec})
+                RETURN doc
+
+            QUERY search(vec: [F64]) =>
+                docs <- SearchV<Doc>(vec, 10)
+                RETURN docs::{title, score}
+
+            QUERY remove(id: ID) =>
+                DROP N<Doc>(id)
+                RETURN "ok"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_search_node_without_embedding() {
+        let source = r#"
+            N::Doc { title: String }
+
+            QUERY search(vec: [F64]) =>
+                docs <- SearchV<Doc>(vec, 10)
+                RETURN docs
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E220));
+    }
+
     #[test]
     fn test_valid_out_traversal() {
         let source = r#"
@@ -2076,6 +2787,156 @@ mod tests {
     // Note: Property errors are caught during object validation, not traversal validation
     // Removing test_property_not_exists as it requires different assertion approach
 
+    #[test]
+    fn test_order_by_multiple_keys() {
+        let source = r#"
+            N::Person { last: String, age: U32 }
+
+            QUERY test(limit: U32) =>
+                people <- N<Person>::ORDER<Asc>(_::{last}, Desc: _::{age} NULLS FIRST)::RANGE(0, limit)
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_order_by_single_key_uses_sort_keys() {
+        let source = r#"
+            N::Person { name: String, nickname: String? }
+
+            QUERY test() =>
+                people <- N<Person>::ORDER<Asc>(_::{nickname})
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        // missing values of the optional field sort last, as with any other key
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("order_by(&[SortKey::asc(\"nickname\")])"), "{output}");
+        assert!(!output.contains("order_by_asc"));
+    }
+
+    #[test]
+    fn test_order_by_unknown_second_key() {
+        let source = r#"
+            N::Person { last: String, age: U32 }
+
+            QUERY test() =>
+                people <- N<Person>::ORDER<Asc>(_::{last}, _::{height})
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
+    }
+
+    #[test]
+    fn test_after_cursor_pagination() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(cursor: String) =>
+                people <- N<Person>::AFTER(cursor)::RANGE(0, 20)
+                next <- people::CURSOR
+                RETURN people, next
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_after_non_string_cursor() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(cursor: U32) =>
+                people <- N<Person>::AFTER(cursor)::RANGE(0, 20)
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E634));
+    }
+
+    #[test]
+    fn test_after_seeks_in_index_and_adjacency_steps() {
+        let source = r#"
+            N::Person { INDEX name: String, age: U32 }
+            E::Follows { From: Person, To: Person }
+
+            QUERY test(id: ID, name: String, cursor: String) =>
+                named <- N<Person>({name: name})::AFTER(cursor)
+                followed <- N<Person>(id)::Out<Follows>::WHERE(_::{age}::GT(18))::AFTER(cursor)
+                follows <- N<Person>(id)::OutE<Follows>::AFTER(cursor)
+                RETURN named, followed, follows
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        let output = format!("{}", generated.queries[0]);
+        assert!(
+            output.contains("n_from_index_after(\"Person\", \"name\", &data.name, decode_cursor(&data.cursor)?)"),
+            "{output}"
+        );
+        // the cursor is applied before the filter, which only drops items
+        assert!(output.contains("out_node_after(\"Follows\", decode_cursor(&data.cursor)?)"));
+        assert!(output.contains("out_e_after(\"Follows\", decode_cursor(&data.cursor)?)"));
+    }
+
+    #[test]
+    fn test_after_step_that_cannot_seek() {
+        let source = r#"
+            N::Person { name: String, age: U32 }
+
+            QUERY test(cursor: String) =>
+                people <- N<Person>::ORDER<Asc>(_::{age})::AFTER(cursor)
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].error_code, ErrorCode::E635);
+    }
+
     // ============================================================================
     // Where Clause Tests
     // ============================================================================
@@ -2118,6 +2979,163 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_where_with_property_is_null() {
+        let source = r#"
+            N::Person { name: String, nickname: String? }
+
+            QUERY test() =>
+                people <- N<Person>::WHERE(_::{nickname}::IS_NULL)
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains(".map_or(true, |v| matches!(v, Value::Empty))"));
+    }
+
+    #[test]
+    fn test_where_eq_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(_::{status}::EQ("Active"))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains("*v == Status::Active"));
+    }
+
+    #[test]
+    fn test_where_eq_unknown_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY test() =>
+                users <- N<User>::WHERE(_::{status}::EQ("Deleted"))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E212));
+        assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E622));
+    }
+
+    #[test]
+    fn test_update_unknown_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY test(id: ID) =>
+                user <- N<User>(id)::UPDATE({status: "Deleted"})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E212));
+    }
+
+    #[test]
+    fn test_update_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY test(id: ID) =>
+                user <- N<User>(id)::UPDATE({status: "Suspended"})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains(".update(&[(\"status\", Value::from(Status::Suspended))])"));
+    }
+
+    #[test]
+    fn test_index_lookup_by_enum_variant() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::Account { INDEX tier: Status, owner: String }
+
+            QUERY active() =>
+                accounts <- N<Account>({tier: "Active"})
+                RETURN accounts
+
+            QUERY deleted() =>
+                accounts <- N<Account>({tier: "Deleted"})
+                RETURN accounts
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].error_code, ErrorCode::E212);
+        let output = format!("{}", generated.queries[0]);
+        assert!(output.contains(".n_from_index(\"Account\", \"tier\", &Status::Active)"));
+    }
+
+    #[test]
+    fn test_group_by_enum_field_decodes_variants() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { name: String, status: Status }
+
+            QUERY test() =>
+                users <- N<User>::GROUP_BY(status)
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let output = format!("{}", generated.queries[0]);
+        assert!(
+            output.contains("enums::decode_item(item, &[(\"status\", Status::VARIANTS)], &arena)")
+        );
+    }
+
     // Note: Removed tests for UPDATE, Range, and property errors as they require
     // different syntax or validation approaches than initially assumed
 
@@ -2168,6 +3186,108 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    // ============================================================================
+    // Aggregation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_group_by_with_aggregates() {
+        let source = r#"
+            N::Order { region: String, customer: String, amount: F64, qty: I32 }
+
+            QUERY test() =>
+                totals <- N<Order>::GROUP_BY(region, total: SUM(amount), n: COUNT, buyers: COUNT(DISTINCT customer), p90: PERCENTILE(amount, 0.9))
+                orders <- N<Order>::AGGREGATE_BY(customer, largest: MAX(amount), units: COLLECT(qty))
+                RETURN totals, orders
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_of_unknown_field() {
+        let source = r#"
+            N::Order { region: String, amount: F64 }
+
+            QUERY test() =>
+                totals <- N<Order>::GROUP_BY(region, total: SUM(price))
+                RETURN totals
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
+    }
+
+    #[test]
+    fn test_numeric_aggregate_of_string_field() {
+        let source = r#"
+            N::Order { region: String, customer: String }
+
+            QUERY test() =>
+                totals <- N<Order>::GROUP_BY(region, avg: AVG(customer))
+                RETURN totals
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E217));
+    }
+
+    #[test]
+    fn test_percentile_rank_out_of_range() {
+        let source = r#"
+            N::Order { region: String, amount: F64 }
+
+            QUERY test() =>
+                totals <- N<Order>::GROUP_BY(region, p: PERCENTILE(amount, 95))
+                RETURN totals
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E218));
+    }
+
+    #[test]
+    fn test_aggregate_named_like_group_key() {
+        let source = r#"
+            N::Order { region: String, amount: F64 }
+
+            QUERY test() =>
+                totals <- N<Order>::GROUP_BY(region, count: SUM(amount), region: MAX(amount))
+                orders <- N<Order>::AGGREGATE_BY(region, items: COUNT, top: MAX(amount), top: SUM(amount))
+                RETURN totals, orders
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert_eq!(diagnostics.len(), 4, "{diagnostics:?}");
+        assert!(diagnostics.iter().all(|d| d.error_code == ErrorCode::E224));
+    }
+
     // ============================================================================
     // Complex Query Tests
     // ============================================================================
@@ -17,7 +17,14 @@ use crate::helixc::{
         types::Type,
     },
     generator::Source as GeneratedSource,
-    parser::{errors::ParserError, types::{EdgeSchema, ExpressionType, Field, Query, ReturnType, Source}},
+    parser::{
+        errors::ParserError,
+        location::Loc,
+        types::{
+            EdgeSchema, EnumSchema, ExpressionType, Field, FieldType, Query, ReturnType, Source,
+            StructSchema,
+        },
+    },
 };
 use itertools::Itertools;
 use serde::Serialize;
@@ -35,6 +42,32 @@ pub fn analyze(src: &Source) -> Result<(Vec<Diagnostic>, GeneratedSource), Parse
     Ok((ctx.diagnostics, ctx.output))
 }
 
+/// Type inferred for an expression, along with the expression's location
+pub type ExprType = (Loc, Type);
+
+/// Analyzes the source like [`analyze`], returning the type inferred for every expression
+/// in the queries instead of the generated source.
+pub fn analyze_types(src: &Source) -> Result<(Vec<Diagnostic>, Vec<ExprType>), ParserError> {
+    let mut ctx = Ctx::new(src)?;
+    ctx.check_schema()?;
+    ctx.check_schema_migrations();
+    ctx.check_queries();
+    Ok((ctx.diagnostics, ctx.expr_types))
+}
+
+/// Diagnostics, expression types and object step types of a source
+pub type ObjectTypes = (Vec<Diagnostic>, Vec<ExprType>, Vec<ExprType>);
+
+/// Analyzes the source like [`analyze_types`], also returning the type of the items each
+/// object step reads its fields from, e.g. `Nodes<User>` for `users::{name}`.
+pub fn analyze_object_types(src: &Source) -> Result<ObjectTypes, ParserError> {
+    let mut ctx = Ctx::new(src)?;
+    ctx.check_schema()?;
+    ctx.check_schema_migrations();
+    ctx.check_queries();
+    Ok((ctx.diagnostics, ctx.expr_types, ctx.object_types))
+}
+
 pub mod diagnostic;
 pub mod error_codes;
 pub mod errors;
@@ -52,16 +85,25 @@ pub(crate) struct Ctx<'a> {
     pub(super) node_set: HashSet<&'a str>,
     pub(super) vector_set: HashSet<&'a str>,
     pub(super) edge_map: HashMap<&'a str, &'a EdgeSchema>,
+    pub(super) enum_map: HashMap<&'a str, &'a EnumSchema>,
+    pub(super) struct_map: HashMap<&'a str, &'a StructSchema>,
     pub(super) node_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) edge_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) vector_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) all_schemas: SchemaVersionMap<'a>,
+    /// Fields checked with `IS_NULL` or `EXISTS` in the statement currently being validated
+    pub(super) null_checked_fields: HashSet<String>,
     pub(super) diagnostics: Vec<Diagnostic>,
+    /// Type inferred for each expression, in the order they were checked
+    pub(super) expr_types: Vec<ExprType>,
+    /// Type of the items read by each object step, located at the step
+    pub(super) object_types: Vec<ExprType>,
     pub(super) output: GeneratedSource,
 }
 
 pub static INTROSPECTION_DATA: OnceLock<IntrospectionData> = OnceLock::new();
 pub static SECONDARY_INDICES: OnceLock<Vec<String>> = OnceLock::new();
+pub static EDGE_SECONDARY_INDICES: OnceLock<Vec<String>> = OnceLock::new();
 
 impl<'a> Ctx<'a> {
     pub(super) fn new(src: &'a Source) -> Result<Self, ParserError> {
@@ -93,12 +135,27 @@ impl<'a> Ctx<'a> {
                 .iter()
                 .map(|e| (e.name.1.as_str(), e))
                 .collect(),
+            enum_map: src
+                .get_latest_schema()?
+                .enum_schemas
+                .iter()
+                .map(|e| (e.name.1.as_str(), e))
+                .collect(),
+            struct_map: src
+                .get_latest_schema()?
+                .struct_schemas
+                .iter()
+                .map(|s| (s.name.1.as_str(), s))
+                .collect(),
             node_fields,
             edge_fields,
             vector_fields,
             all_schemas,
+            null_checked_fields: HashSet::new(),
             src,
             diagnostics: Vec::new(),
+            expr_types: Vec::new(),
+            object_types: Vec::new(),
             output,
         };
 
@@ -122,6 +179,19 @@ impl<'a> Ctx<'a> {
                     .collect(),
             )
             .ok();
+
+        EDGE_SECONDARY_INDICES
+            .set(
+                src.get_latest_schema()?
+                    .edge_schemas
+                    .iter()
+                    .flat_map(|schema| schema.properties.iter().flatten())
+                    .filter(|f| f.is_indexed())
+                    .map(|f| f.name.clone())
+                    .dedup()
+                    .collect(),
+            )
+            .ok();
         Ok(ctx)
     }
 
@@ -130,7 +200,7 @@ impl<'a> Ctx<'a> {
         &self,
         item_type: &Type,
     ) -> Option<&HashMap<&str, Cow<'_, Field>>> {
-        match item_type {
+        match item_type.base() {
             Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => {
                 self.node_fields.get(node_type.as_str())
             }
@@ -144,6 +214,62 @@ impl<'a> Ctx<'a> {
         }
     }
 
+    /// Returns the enum declaration when `field_type` names one.
+    pub(super) fn get_enum(&self, field_type: &FieldType) -> Option<&'a EnumSchema> {
+        match field_type {
+            FieldType::Identifier(name) => self.enum_map.get(name.as_str()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the struct declaration when `field_type` names one.
+    pub(super) fn get_struct(&self, field_type: &FieldType) -> Option<&'a StructSchema> {
+        match field_type {
+            FieldType::Identifier(name) => self.struct_map.get(name.as_str()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the `(field, enum)` of every enum field of the item type, ordered by field name.
+    pub(super) fn get_enum_fields(&self, item_type: &Type) -> Vec<(String, String)> {
+        let mut enum_fields = self
+            .get_item_fields(item_type)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|(name, field)| {
+                        self.get_enum(&field.field_type)
+                            .map(|enum_schema| (name.to_string(), enum_schema.name.1.clone()))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        enum_fields.sort();
+        enum_fields
+    }
+
+    /// Returns the `(field, vector type)` of every vector embedded on the node type,
+    /// ordered by field name.
+    pub(super) fn get_embeddings(&self, node_type: &str) -> Vec<(String, String)> {
+        let mut embeddings = self
+            .node_fields
+            .get(node_type)
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter_map(|(name, field)| match &field.field_type {
+                        FieldType::Embedding(vector_type) => {
+                            Some((name.to_string(), vector_type.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        embeddings.sort();
+        embeddings
+    }
+
     // ---------- Pass #1: schema --------------------------
     /// Validate that every edge references declared node types.
     pub(super) fn check_schema(&mut self) -> Result<(), ParserError> {
@@ -186,6 +312,10 @@ pub struct SchemaData {
     nodes: Vec<NodeData>,
     vectors: Vec<NodeData>,
     edges: Vec<EdgeData>,
+    enums: Vec<EnumData>,
+    structs: Vec<NodeData>,
+    /// HQL definitions of the schema, for analyzing queries against a running instance
+    hql: String,
 }
 
 impl SchemaData {
@@ -193,11 +323,16 @@ impl SchemaData {
         let nodes = ctx.node_fields.iter().map(NodeData::from_entry).collect();
         let vectors = ctx.vector_fields.iter().map(NodeData::from_entry).collect();
         let edges = ctx.edge_map.iter().map(EdgeData::from_entry).collect();
+        let enums = ctx.enum_map.values().map(|e| EnumData::from_schema(e)).collect();
+        let structs = ctx.struct_map.values().map(|s| NodeData::from_struct(s)).collect();
 
         SchemaData {
             nodes,
             vectors,
             edges,
+            enums,
+            structs,
+            hql: ctx.src.schema_source(),
         }
     }
 }
@@ -220,6 +355,18 @@ impl NodeData {
             properties,
         }
     }
+
+    fn from_struct(ss: &StructSchema) -> Self {
+        let properties = ss
+            .fields
+            .iter()
+            .map(|f| (f.name.clone(), f.field_type.to_string()))
+            .collect();
+        NodeData {
+            name: ss.name.1.clone(),
+            properties,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -248,6 +395,21 @@ impl EdgeData {
     }
 }
 
+#[derive(Serialize)]
+pub struct EnumData {
+    name: String,
+    variants: Vec<String>,
+}
+
+impl EnumData {
+    fn from_schema(es: &EnumSchema) -> Self {
+        EnumData {
+            name: es.name.1.clone(),
+            variants: es.variants.iter().map(|(_, v)| v.clone()).collect(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct QueryData {
     name: String,
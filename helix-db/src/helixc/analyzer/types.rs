@@ -4,12 +4,16 @@ use crate::helixc::{
     generator::{
         queries::Parameter as GeneratedParameter,
         schemas::{
-            EdgeSchema as GeneratedEdgeSchema, NodeSchema as GeneratedNodeSchema, SchemaProperty,
+            EdgeSchema as GeneratedEdgeSchema, EnumSchema as GeneratedEnumSchema,
+            NodeSchema as GeneratedNodeSchema, SchemaProperty, StructSchema as GeneratedStructSchema,
             VectorSchema as GeneratedVectorSchema,
         },
         utils::{GenRef, GeneratedType, GeneratedValue, RustType as GeneratedRustType},
     },
-    parser::types::{DefaultValue, EdgeSchema, FieldType, NodeSchema, Parameter, VectorSchema},
+    parser::types::{
+        DefaultValue, EdgeSchema, EnumSchema, FieldType, NodeSchema, Parameter, StructSchema,
+        VectorSchema,
+    },
 };
 
 impl From<NodeSchema> for GeneratedNodeSchema {
@@ -23,6 +27,7 @@ impl From<NodeSchema> for GeneratedNodeSchema {
                     name: f.name,
                     field_type: f.field_type.into(),
                     default_value: f.defaults.map(|d| d.into()),
+                    is_optional: f.is_optional,
                     is_index: f.prefix,
                 })
                 .collect(),
@@ -43,6 +48,7 @@ impl From<EdgeSchema> for GeneratedEdgeSchema {
                         name: f.name,
                         field_type: f.field_type.into(),
                         default_value: f.defaults.map(|d| d.into()),
+                        is_optional: f.is_optional,
                         is_index: f.prefix,
                     })
                     .collect()
@@ -51,6 +57,34 @@ impl From<EdgeSchema> for GeneratedEdgeSchema {
     }
 }
 
+impl From<EnumSchema> for GeneratedEnumSchema {
+    fn from(generated: EnumSchema) -> Self {
+        GeneratedEnumSchema {
+            name: generated.name.1,
+            variants: generated.variants.into_iter().map(|(_, v)| v).collect(),
+        }
+    }
+}
+
+impl From<StructSchema> for GeneratedStructSchema {
+    fn from(generated: StructSchema) -> Self {
+        GeneratedStructSchema {
+            name: generated.name.1,
+            properties: generated
+                .fields
+                .into_iter()
+                .map(|f| SchemaProperty {
+                    name: f.name,
+                    field_type: f.field_type.into(),
+                    default_value: None,
+                    is_optional: f.is_optional,
+                    is_index: f.prefix,
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<VectorSchema> for GeneratedVectorSchema {
     fn from(generated: VectorSchema) -> Self {
         GeneratedVectorSchema {
@@ -62,6 +96,7 @@ impl From<VectorSchema> for GeneratedVectorSchema {
                     name: f.name,
                     field_type: f.field_type.into(),
                     default_value: f.defaults.map(|d| d.into()),
+                    is_optional: f.is_optional,
                     is_index: f.prefix,
                 })
                 .collect(),
@@ -188,16 +223,10 @@ impl From<FieldType> for GeneratedType {
             FieldType::Date => GeneratedType::RustType(GeneratedRustType::Date),
             FieldType::Array(inner) => GeneratedType::Vec(Box::new(GeneratedType::from(*inner))),
             FieldType::Identifier(ref id) => GeneratedType::Variable(GenRef::Std(id.clone())),
-            // FieldType::Object(obj) => GeneratedType::Object(
-            //     obj.iter()
-            //         .map(|(name, field_type)| {
-            //             (name.clone(), GeneratedType::from(field_type.clone()))
-            //         })
-            //         .collect(),
-            // ),
-            _ => {
-                unimplemented!()
-            }
+            // object fields are stored as a nested `Value::Object`
+            FieldType::Object(_) => GeneratedType::Object(GenRef::Std("Value".to_string())),
+            // the node stores the id of its vector
+            FieldType::Embedding(_) => GeneratedType::RustType(GeneratedRustType::Uuid),
         }
     }
 }
@@ -233,6 +262,7 @@ pub struct AggregateInfo {
     pub properties: Vec<String>,  // Properties being grouped by
     pub is_count: bool,           // true for COUNT mode
     pub is_group_by: bool,        // true for GROUP_BY, false for AGGREGATE_BY
+    pub aggregates: Vec<(String, Type)>, // Named aggregates and the type of their result
 }
 
 #[derive(Debug, Clone)]
@@ -249,6 +279,9 @@ pub enum Type {
     Object(HashMap<String, Type>),
     Array(Box<Type>),
     Anonymous(Box<Type>),
+    /// Items carrying properties set by the step that produced them,
+    /// e.g. the `distance` of the nodes returned by `ShortestPathsFrom`
+    Computed(Box<Type>, Vec<(String, FieldType)>),
     Boolean,
     Unknown,
 }
@@ -268,7 +301,7 @@ impl Type {
             Type::Array(_) => "array",
             Type::Boolean => "boolean",
             Type::Unknown => "unknown",
-            Type::Anonymous(ty) => ty.kind_str(),
+            Type::Anonymous(ty) | Type::Computed(ty, _) => ty.kind_str(),
         }
     }
 
@@ -282,7 +315,7 @@ impl Type {
             Type::Vector(Some(name)) => name.clone(),
             Type::Vectors(Some(name)) => name.clone(),
             Type::Scalar(ft) => ft.to_string(),
-            Type::Anonymous(ty) => ty.get_type_name(),
+            Type::Anonymous(ty) | Type::Computed(ty, _) => ty.get_type_name(),
             Type::Array(ty) => ty.get_type_name(),
             Type::Boolean => "boolean".to_string(),
             Type::Unknown => "unknown".to_string(),
@@ -294,10 +327,10 @@ impl Type {
         }
     }
 
-    /// Recursively strip <code>Anonymous</code> layers and return the base type.
+    /// Recursively strip <code>Anonymous</code> and <code>Computed</code> layers and return the base type.
     pub fn base(&self) -> &Type {
         match self {
-            Type::Anonymous(inner) => inner.base(),
+            Type::Anonymous(inner) | Type::Computed(inner, _) => inner.base(),
             _ => self,
         }
     }
@@ -306,7 +339,7 @@ impl Type {
     /// Same, but returns an owned clone for convenience.
     pub fn cloned_base(&self) -> Type {
         match self {
-            Type::Anonymous(inner) => inner.cloned_base(),
+            Type::Anonymous(inner) | Type::Computed(inner, _) => inner.cloned_base(),
             _ => self.clone(),
         }
     }
@@ -355,6 +388,9 @@ impl Type {
             Type::Boolean => Type::Boolean,
             Type::Unknown => Type::Unknown,
             Type::Anonymous(inner) => Type::Anonymous(Box::new(inner.into_single())),
+            Type::Computed(inner, properties) => {
+                Type::Computed(Box::new(inner.into_single()), properties)
+            }
             Type::Aggregate(info) => Type::Aggregate(info),
             Type::Node(name) => Type::Node(name),
             Type::Nodes(name) => Type::Node(name),
@@ -365,6 +401,45 @@ impl Type {
             Type::Array(inner) => *inner,
         }
     }
+
+    /// The collection of the items of a single node, edge or vector type
+    pub fn into_collection(self) -> Type {
+        match self {
+            Type::Node(name) => Type::Nodes(name),
+            Type::Edge(name) => Type::Edges(name),
+            Type::Vector(name) => Type::Vectors(name),
+            Type::Computed(inner, properties) => {
+                Type::Computed(Box::new(inner.into_collection()), properties)
+            }
+            other => other,
+        }
+    }
+
+    /// Properties set on the items by the step that produced them, with their types
+    pub fn computed_properties(&self) -> Vec<(&str, &FieldType)> {
+        match self {
+            Type::Computed(inner, properties) => properties
+                .iter()
+                .map(|(name, field_type)| (name.as_str(), field_type))
+                .chain(inner.computed_properties())
+                .collect(),
+            Type::Anonymous(inner) => inner.computed_properties(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The type of a property set on the items by the step that produced them
+    pub fn computed_property(&self, key: &str) -> Option<&FieldType> {
+        self.computed_properties()
+            .into_iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, field_type)| field_type)
+    }
+
+    /// Wrap the type so its items carry an extra property
+    pub fn with_computed_property(self, key: &str, field_type: FieldType) -> Type {
+        Type::Computed(Box::new(self), vec![(key.to_string(), field_type)])
+    }
 }
 
 impl PartialEq for Type {
@@ -375,6 +450,10 @@ impl PartialEq for Type {
             (Type::Boolean, Type::Boolean) => true,
             (Type::Unknown, Type::Unknown) => true,
             (Type::Anonymous(inner), Type::Anonymous(other_inner)) => inner == other_inner,
+            // computed properties don't change which items a type holds
+            (Type::Computed(inner, _), other) | (other, Type::Computed(inner, _)) => {
+                inner.as_ref() == other
+            }
             (Type::Node(name), Type::Node(other_name)) => name == other_name,
             (Type::Nodes(name), Type::Nodes(other_name)) => name == other_name,
             (Type::Edge(name), Type::Edge(other_name)) => name == other_name,
@@ -397,6 +476,8 @@ impl From<FieldType> for Type {
             Array(inner_ft) => Type::Array(Box::new(Type::from(*inner_ft))),
             Object(obj) => Type::Object(obj.into_iter().map(|(k, v)| (k, Type::from(v))).collect()),
             Identifier(id) => Type::Scalar(FieldType::Identifier(id)),
+            // an embedding is assigned the data of its vector
+            Embedding(_) => Type::Array(Box::new(Type::Scalar(F64))),
         }
     }
 }
@@ -410,6 +491,7 @@ impl From<&FieldType> for Type {
             Array(inner_ft) => Type::Array(Box::new(Type::from(*inner_ft.clone()))),
             Object(obj) => Type::Object(obj.iter().map(|(k, v)| (k.clone(), Type::from(v))).collect()),
             Identifier(id) => Type::Scalar(FieldType::Identifier(id.clone())),
+            Embedding(_) => Type::Array(Box::new(Type::Scalar(F64))),
         }
     }
 }
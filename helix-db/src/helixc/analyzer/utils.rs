@@ -5,7 +5,7 @@ use crate::{
     helixc::{
         analyzer::{Ctx, errors::push_query_err, types::Type},
         generator::{
-            traversal_steps::{Step, ReservedProp},
+            traversal_steps::{OwnerN, Step, ReservedProp},
             utils::{GenRef, GeneratedValue},
         },
         parser::{location::Loc, types::*},
@@ -88,6 +88,101 @@ pub(super) fn gen_identifier_or_param(
     }
 }
 
+/// Generates the value written to a schema field from an optional parameter.
+///
+/// A missing parameter is stored as `Value::Empty` on nullable fields and falls back
+/// to the `DEFAULT` on defaulted fields. Assigning one to a required field is rejected,
+/// since the item would be written without it.
+///
+/// Returns `None` if `name` is not an optional parameter.
+pub(super) fn gen_optional_param_for_field(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    loc: Loc,
+    name: &str,
+    field: &Field,
+    item_type: &str,
+    item_type_name: &str,
+) -> Option<GeneratedValue> {
+    if !is_param(original_query, name)?.is_optional {
+        return None;
+    }
+    Some(match (&field.defaults, field.is_optional) {
+        (Some(default), _) => {
+            let default = default_literal(default);
+            GeneratedValue::Parameter(GenRef::Std(format!(
+                "data.{name}.as_ref().map_or_else(|| Value::from({default}), Value::from)"
+            )))
+        }
+        (None, true) => GeneratedValue::Parameter(GenRef::Ref(format!("data.{name}"))),
+        (None, false) => {
+            generate_error!(
+                ctx,
+                original_query,
+                loc,
+                E211,
+                [name, field.name.as_str(), item_type, item_type_name],
+                [field.name.as_str()]
+            );
+            GeneratedValue::Unknown
+        }
+    })
+}
+
+/// Rust literal of a field's `DEFAULT`, suffixed with the field's type so that e.g. a `U32`
+/// default is stored as `Value::U32` rather than as an untyped integer
+fn default_literal(default: &DefaultValue) -> String {
+    match default {
+        DefaultValue::F32(f) => format!("{f}f32"),
+        DefaultValue::F64(f) => format!("{f}f64"),
+        DefaultValue::I8(i) => format!("{i}i8"),
+        DefaultValue::I16(i) => format!("{i}i16"),
+        DefaultValue::I32(i) => format!("{i}i32"),
+        DefaultValue::I64(i) => format!("{i}i64"),
+        DefaultValue::U8(i) => format!("{i}u8"),
+        DefaultValue::U16(i) => format!("{i}u16"),
+        DefaultValue::U32(i) => format!("{i}u32"),
+        DefaultValue::U64(i) => format!("{i}u64"),
+        DefaultValue::U128(i) => format!("{i}u128"),
+        _ => GeneratedValue::from(default.clone()).to_string(),
+    }
+}
+
+/// Checks that a literal written to or compared with an enum-typed field names one of
+/// the enum's variants. Returns `true` when the value is a valid variant.
+pub(super) fn check_enum_variant(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    loc: Loc,
+    enum_schema: &EnumSchema,
+    value: &str,
+) -> bool {
+    if enum_schema.has_variant(value) {
+        return true;
+    }
+    let variants = enum_schema
+        .variants
+        .iter()
+        .map(|(_, v)| format!("`{v}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    generate_error!(
+        ctx,
+        original_query,
+        loc,
+        E212,
+        [value, enum_schema.name.1.as_str()],
+        [enum_schema.name.1.as_str(), variants.as_str()]
+    );
+    false
+}
+
+/// The generated value of the `enum_schema` variant a string literal names. Enums are stored as
+/// the index of their variant, so literals are written as the variant itself.
+pub(super) fn gen_enum_variant(enum_schema: &EnumSchema, variant: &str) -> GeneratedValue {
+    GeneratedValue::Primitive(GenRef::Std(format!("{}::{variant}", enum_schema.name.1)))
+}
+
 pub(super) fn gen_id_access_or_param(original_query: &Query, name: &str) -> GeneratedValue {
     if let Some(param) = is_param(original_query, name) {
         GeneratedValue::Parameter(match param.is_optional {
@@ -101,6 +196,52 @@ pub(super) fn gen_id_access_or_param(original_query: &Query, name: &str) -> Gene
     }
 }
 
+/// Resolves `SearchV<Type>` to the vector type to search.
+///
+/// A node type searches the vector embedded on it, and the step joining the results
+/// back to the nodes is returned with it.
+pub(super) fn resolve_search_type(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    loc: Loc,
+    search_type: &str,
+) -> (String, Option<OwnerN>) {
+    if !ctx.node_set.contains(search_type) {
+        if !ctx.vector_set.contains(search_type) {
+            generate_error!(ctx, original_query, loc, E103, search_type);
+        }
+        return (search_type.to_string(), None);
+    }
+    match ctx.get_embeddings(search_type).as_slice() {
+        [(field, vector_type)] => (
+            vector_type.clone(),
+            Some(OwnerN {
+                label: GenRef::Literal(search_type.to_string()),
+                field: GenRef::Literal(field.clone()),
+            }),
+        ),
+        embeddings => {
+            let embedded = match embeddings.is_empty() {
+                true => "no vectors".to_string(),
+                false => embeddings
+                    .iter()
+                    .map(|(_, vector_type)| format!("`V::{vector_type}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            generate_error!(
+                ctx,
+                original_query,
+                loc,
+                E220,
+                [search_type, &embedded],
+                [search_type]
+            );
+            (search_type.to_string(), None)
+        }
+    }
+}
+
 pub(super) fn is_in_scope(scope: &HashMap<&str, VariableInfo>, name: &str) -> bool {
     scope.contains_key(name)
 }
@@ -219,6 +360,8 @@ pub(super) struct VariableInfo {
     pub is_single: bool,            // true if ToObj, false if ToVec
     pub reference_count: usize,     // How many times this variable is referenced
     pub source_var: Option<String>, // For closure parameters, the actual variable they refer to
+    pub nullable_fields: Vec<String>, // Projected fields that may be missing on the item
+    pub null_checked_fields: Vec<String>, // Fields checked with `IS_NULL` or `EXISTS` for the items
 }
 
 impl VariableInfo {
@@ -228,6 +371,8 @@ impl VariableInfo {
             is_single,
             reference_count: 0,
             source_var: None,
+            nullable_fields: Vec::new(),
+            null_checked_fields: Vec::new(),
         }
     }
 
@@ -237,6 +382,8 @@ impl VariableInfo {
             is_single,
             reference_count: 0,
             source_var: Some(source_var),
+            nullable_fields: Vec::new(),
+            null_checked_fields: Vec::new(),
         }
     }
 
@@ -276,7 +423,13 @@ pub(super) trait FieldLookup {
 
 impl FieldLookup for Type {
     fn item_fields_contains_key(&self, ctx: &Ctx, key: &str) -> bool {
-        match self {
+        if key.contains('.') {
+            return self.get_field_type_from_item_fields(ctx, key).is_some();
+        }
+        if self.computed_property(key).is_some() {
+            return true;
+        }
+        match self.base() {
             Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => ctx
                 .node_fields
                 .get(node_type.as_str())
@@ -306,7 +459,7 @@ impl FieldLookup for Type {
     }
 
     fn item_fields_contains_key_with_type(&self, ctx: &Ctx, key: &str) -> (bool, String) {
-        let (is_valid_field, item_type) = match self {
+        let (is_valid_field, item_type) = match self.base() {
             Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => (
                 ctx.node_fields
                     .get(node_type.as_str())
@@ -340,11 +493,30 @@ impl FieldLookup for Type {
             _ => unreachable!("shouldve been caught eariler"),
         };
 
+        let is_valid_field = is_valid_field || self.computed_property(key).is_some();
         (is_valid_field, item_type.to_string())
     }
 
     fn get_field_type_from_item_fields(&self, ctx: &Ctx, key: &str) -> Option<FieldType> {
-        match self {
+        // `address.city` is the `city` field of the object or struct stored in `address`
+        if let Some((field, path)) = key.split_once('.') {
+            return path.split('.').try_fold(
+                self.get_field_type_from_item_fields(ctx, field)?,
+                |field_type, key| match (ctx.get_struct(&field_type), field_type) {
+                    (Some(struct_schema), _) => struct_schema
+                        .fields
+                        .iter()
+                        .find(|f| f.name == key)
+                        .map(|f| f.field_type.clone()),
+                    (None, FieldType::Object(mut fields)) => fields.remove(key),
+                    (None, _) => None,
+                },
+            );
+        }
+        if let Some(field_type) = self.computed_property(key) {
+            return Some(field_type.clone());
+        }
+        match self.base() {
             Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => ctx
                 .node_fields
                 .get(node_type.as_str())
@@ -0,0 +1,122 @@
+//! Layout of expressions that are broken over several lines only when they do not fit.
+
+/// Maximum width of a formatted line
+pub const WIDTH: usize = 100;
+/// Indentation of each nested level
+pub const INDENT: usize = 4;
+
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    /// A space, or a line break when the enclosing group is broken
+    Line,
+    /// Nothing, or a line break when the enclosing group is broken
+    SoftLine,
+    /// Text printed only when the enclosing group is broken
+    IfBreak(&'static str),
+    /// Line breaks inside are indented one level further
+    Nest(Vec<Doc>),
+    /// Printed on one line when it fits, otherwise its lines are broken
+    Group(Vec<Doc>),
+    Concat(Vec<Doc>),
+}
+
+impl Doc {
+    pub fn text(text: impl Into<String>) -> Doc {
+        Doc::Text(text.into())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Flat,
+    Break,
+}
+
+/// Print a document whose first line starts at column `indent`.
+///
+/// The indentation of the first line itself is left to the caller.
+pub fn print(doc: &Doc, indent: usize) -> String {
+    let mut out = String::new();
+    let mut column = indent;
+    let mut stack = vec![(indent, Mode::Break, doc)];
+    while let Some((indent, mode, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(text) => {
+                out.push_str(text);
+                column += text.chars().count();
+            }
+            Doc::Line | Doc::SoftLine if mode == Mode::Break => {
+                out.push('\n');
+                out.push_str(&" ".repeat(indent));
+                column = indent;
+            }
+            Doc::Line => {
+                out.push(' ');
+                column += 1;
+            }
+            Doc::SoftLine => {}
+            Doc::IfBreak(text) => {
+                if mode == Mode::Break {
+                    out.push_str(text);
+                    column += text.len();
+                }
+            }
+            Doc::Nest(docs) => {
+                stack.extend(docs.iter().rev().map(|doc| (indent + INDENT, mode, doc)))
+            }
+            Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc))),
+            Doc::Group(docs) => {
+                let mode = match mode {
+                    Mode::Flat => Mode::Flat,
+                    Mode::Break if fits(WIDTH.saturating_sub(column), docs, &stack) => Mode::Flat,
+                    Mode::Break => Mode::Break,
+                };
+                stack.extend(docs.iter().rev().map(|doc| (indent, mode, doc)));
+            }
+        }
+    }
+    out
+}
+
+/// Whether a group fits on the rest of the line, along with what follows it up to the next line break
+fn fits(mut width: usize, group: &[Doc], rest: &[(usize, Mode, &Doc)]) -> bool {
+    let mut flat = group
+        .iter()
+        .rev()
+        .map(|doc| (Mode::Flat, doc))
+        .collect::<Vec<_>>();
+    let mut rest = rest.iter().rev();
+    loop {
+        let (mode, doc) = match flat.pop() {
+            Some(next) => next,
+            None => match rest.next() {
+                Some((_, mode, doc)) => (*mode, *doc),
+                None => return true,
+            },
+        };
+        match doc {
+            Doc::Text(text) => match width.checked_sub(text.chars().count()) {
+                Some(left) => width = left,
+                None => return false,
+            },
+            Doc::Line | Doc::SoftLine if mode == Mode::Break => return true,
+            Doc::Line => match width.checked_sub(1) {
+                Some(left) => width = left,
+                None => return false,
+            },
+            Doc::SoftLine => {}
+            Doc::IfBreak(text) => {
+                if mode == Mode::Break {
+                    match width.checked_sub(text.len()) {
+                        Some(left) => width = left,
+                        None => return false,
+                    }
+                }
+            }
+            Doc::Nest(docs) | Doc::Concat(docs) | Doc::Group(docs) => {
+                flat.extend(docs.iter().rev().map(|doc| (mode, doc)))
+            }
+        }
+    }
+}
@@ -0,0 +1,1077 @@
+// Copyright 2025 HelixDB Inc.
+// SPDX-License-Identifier: AGPL-3.0
+
+//! Canonical formatter for HelixQL source.
+//!
+//! Files are parsed with the HelixQL grammar and printed back in a single style:
+//! - blocks are indented by four spaces, and top level items are separated by a blank line
+//! - fields, enum variants and migrated fields get one line each, with a trailing comma
+//! - schema items are grouped by declaration (enums, nodes, vectors, edges), keeping their order
+//! - traversal chains and lists are kept on one line when they fit, otherwise each step or
+//!   element gets its own line, one level deeper
+//!
+//! Comments are kept on the line they were written on, or above the item that follows them.
+//! An expression with comments inside it is kept as written, apart from its indentation.
+
+mod doc;
+
+use crate::helixc::parser::{HelixParser, Rule, errors::ParserError};
+use doc::{Doc, INDENT};
+use pest::{Parser as PestParser, iterators::Pair};
+
+/// Format a HelixQL source file
+pub fn format_source(src: &str) -> Result<String, ParserError> {
+    let source = HelixParser::parse(Rule::source, src)?
+        .next()
+        .ok_or_else(|| ParserError::from("Empty input"))?;
+
+    let mut formatter = Formatter::new(src);
+    formatter.source(source);
+    let formatted = formatter.out;
+
+    // the formatter only moves whitespace and comments, so this can only fail on a bug
+    HelixParser::parse(Rule::source, &formatted)
+        .map_err(|e| ParserError::from(format!("formatted source does not parse: {e}")))?;
+    Ok(formatted)
+}
+
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+struct Formatter<'a> {
+    src: &'a str,
+    comments: Vec<Comment>,
+    /// Index of the first comment not printed yet
+    next_comment: usize,
+    /// End of the source printed last, to find trailing comments and blank lines
+    last: usize,
+    /// Set at the start of a block, where blank lines are not kept
+    fresh: bool,
+    out: String,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(src: &'a str) -> Self {
+        Formatter {
+            src,
+            comments: comments(src),
+            next_comment: 0,
+            last: 0,
+            fresh: true,
+            out: String::new(),
+        }
+    }
+
+    fn source(&mut self, source: Pair<'a, Rule>) {
+        let mut chunks = Vec::new();
+        for item in source.into_inner() {
+            if item.as_rule() == Rule::EOI {
+                continue;
+            }
+            let kind = match item.as_rule() {
+                Rule::schema_def => item
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .and_then(|item| schema_kind(&item)),
+                _ => None,
+            };
+            self.fresh = true;
+            let chunk = self.chunk(|f| f.item(item));
+            chunks.push((kind, chunk));
+        }
+        // only sort schema items declared next to each other, queries stay where they are
+        for run in chunks.chunk_by_mut(|(a, _), (b, _)| a.is_some() && b.is_some()) {
+            run.sort_by_key(|(kind, _)| *kind);
+        }
+
+        self.out = chunks
+            .into_iter()
+            .map(|(_, chunk)| chunk)
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.fresh = self.out.is_empty();
+        self.comments_before(self.src.len(), 0);
+    }
+
+    fn item(&mut self, item: Pair<'a, Rule>) {
+        match item.as_rule() {
+            Rule::schema_def => self.schema_def(item),
+            Rule::migration_def => self.migration_def(item),
+            Rule::query_def => self.query_def(item),
+            _ => {}
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // Schemas
+    // -----------------------------------------------------------------
+
+    fn schema_def(&mut self, pair: Pair<'a, Rule>) {
+        let mut inner = pair.clone().into_inner().peekable();
+        if inner.peek().map(|pair| pair.as_rule()) != Some(Rule::schema_version) {
+            inner.for_each(|item| self.schema_item(item, 0));
+            return;
+        }
+
+        let version = inner.next().unwrap();
+        let items = inner.collect::<Vec<_>>();
+        let header = format!("{} {{", self.inline(&version));
+        let open = self.after(version.as_span().end(), "{");
+        let close = pair.as_span().end() - 1;
+        self.leading(pair.as_span().start(), 0);
+        self.block(0, &header, open, close, items.is_empty(), |f, indent| {
+            let mut chunks = items
+                .into_iter()
+                .map(|item| {
+                    f.fresh = true;
+                    (schema_kind(&item), f.chunk(|f| f.schema_item(item, indent)))
+                })
+                .collect::<Vec<_>>();
+            chunks.sort_by_key(|(kind, _)| *kind);
+            let chunks = chunks
+                .into_iter()
+                .map(|(_, chunk)| chunk)
+                .collect::<Vec<_>>();
+            f.out.push_str(&chunks.join("\n"));
+        });
+    }
+
+    fn schema_item(&mut self, pair: Pair<'a, Rule>, indent: usize) {
+        let span = pair.as_span();
+        self.leading(span.start(), indent);
+        let rule = pair.as_rule();
+        let mut inner = pair.into_inner();
+        let Some(name) = inner.next() else {
+            return;
+        };
+        match rule {
+            Rule::node_def | Rule::vector_def | Rule::struct_def => {
+                let prefix = match rule {
+                    Rule::node_def => "N::",
+                    Rule::vector_def => "V::",
+                    _ => "STRUCT ",
+                };
+                let header = format!("{prefix}{}", name.as_str());
+                let Some(body) = inner.next() else {
+                    self.line(indent, &header, self.end(&name));
+                    return;
+                };
+                let fields = body.clone().into_inner().next();
+                let empty = fields
+                    .as_ref()
+                    .is_none_or(|fields| fields.as_str().is_empty());
+                let (open, close) = (body.as_span().start() + 1, body.as_span().end() - 1);
+                self.block(
+                    indent,
+                    &format!("{header} {{"),
+                    open,
+                    close,
+                    empty,
+                    |f, indent| {
+                        if let Some(fields) = fields {
+                            f.fields(fields, indent);
+                        }
+                    },
+                );
+            }
+            Rule::edge_def => {
+                let Some(body) = inner.next() else {
+                    return;
+                };
+                let header = format!("E::{} {{", name.as_str());
+                let (open, close) = (body.as_span().start() + 1, body.as_span().end() - 1);
+                let mut inner = body.into_inner();
+                let (from, to, properties) = (inner.next(), inner.next(), inner.next());
+                self.block(indent, &header, open, close, false, |f, indent| {
+                    for (key, item) in [("From", from), ("To", to)] {
+                        if let Some(item) = item {
+                            let doc = Doc::text(format!("{key}: {}", item.as_str()));
+                            f.element(indent, item.as_span().start(), f.end(&item), doc, ",");
+                        }
+                    }
+                    if let Some(properties) = properties {
+                        f.properties(properties, indent);
+                    }
+                });
+            }
+            Rule::enum_def => {
+                let header = format!("ENUM {} {{", name.as_str());
+                let open = self.after(name.as_span().end(), "{");
+                let close = span.end() - 1;
+                let variants = inner.collect::<Vec<_>>();
+                self.block(
+                    indent,
+                    &header,
+                    open,
+                    close,
+                    variants.is_empty(),
+                    |f, indent| {
+                        for variant in variants {
+                            let (start, end) = (variant.as_span().start(), f.end(&variant));
+                            f.element(indent, start, end, Doc::text(variant.as_str()), ",");
+                        }
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Edge properties, of a schema or a migration
+    fn properties(&mut self, pair: Pair<'a, Rule>, indent: usize) {
+        let span = pair.as_span();
+        self.leading(span.start(), indent);
+        let open = self.after(span.start(), "{");
+        let fields = pair.into_inner().next();
+        let empty = fields
+            .as_ref()
+            .is_none_or(|fields| fields.as_str().is_empty());
+        let close = match &fields {
+            Some(fields) => self.after(fields.as_span().end(), "}") - 1,
+            None => self.after(open, "}") - 1,
+        };
+        self.block(indent, "Properties: {", open, close, empty, |f, indent| {
+            if let Some(fields) = fields {
+                f.fields(fields, indent);
+            }
+        });
+    }
+
+    /// Field definitions or field migrations, one per line
+    fn fields(&mut self, pair: Pair<'a, Rule>, indent: usize) {
+        for field in pair.into_inner() {
+            let (start, end) = (field.as_span().start(), self.end(&field));
+            let doc = self.doc(&field);
+            self.element(indent, start, end, doc, ",");
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // Migrations
+    // -----------------------------------------------------------------
+
+    fn migration_def(&mut self, pair: Pair<'a, Rule>) {
+        let span = pair.as_span();
+        self.leading(span.start(), 0);
+        let mut inner = pair.into_inner();
+        let (Some(from), Some(to), Some(body)) = (inner.next(), inner.next(), inner.next()) else {
+            return;
+        };
+        let header = format!(
+            "MIGRATION {} => {} {{",
+            self.inline(&from),
+            self.inline(&to)
+        );
+        let open = self.after(to.as_span().end(), "{");
+        let mappings = body.into_inner().collect::<Vec<_>>();
+        self.block(
+            0,
+            &header,
+            open,
+            span.end() - 1,
+            mappings.is_empty(),
+            |f, indent| {
+                for (i, mapping) in mappings.into_iter().enumerate() {
+                    if i > 0 {
+                        f.out.push('\n');
+                        f.fresh = true;
+                    }
+                    f.mapping(mapping, indent);
+                }
+            },
+        );
+    }
+
+    fn mapping(&mut self, pair: Pair<'a, Rule>, indent: usize) {
+        self.leading(pair.as_span().start(), indent);
+        let mut inner = pair.into_inner();
+        let (Some(from), Some(to), Some(fields)) = (inner.next(), inner.next(), inner.next())
+        else {
+            return;
+        };
+        let separator = if to.as_rule() == Rule::anon_decl {
+            ""
+        } else {
+            " "
+        };
+        let header = format!(
+            "{} => {}{separator}{{",
+            self.inline(&from),
+            self.inline(&to)
+        );
+        let (open, close) = (fields.as_span().start() + 1, fields.as_span().end() - 1);
+        match fields.as_rule() {
+            Rule::edge_migration => {
+                self.block(indent, &header, open, close, false, |f, indent| {
+                    if let Some(fields) = fields.into_inner().next() {
+                        f.migrated_properties(fields, open, indent);
+                    }
+                });
+            }
+            _ => {
+                let fields = fields.into_inner().next();
+                let empty = fields
+                    .as_ref()
+                    .is_none_or(|fields| fields.as_str().is_empty());
+                self.block(indent, &header, open, close, empty, |f, indent| {
+                    if let Some(fields) = fields {
+                        f.fields(fields, indent);
+                    }
+                });
+            }
+        }
+    }
+
+    /// The `Properties` of an edge migration, whose fields start after `from`
+    fn migrated_properties(&mut self, fields: Pair<'a, Rule>, from: usize, indent: usize) {
+        self.leading(self.after(from, "Properties") - "Properties".len(), indent);
+        let open = self.after(from, "{");
+        let close = self.after(fields.as_span().end().max(open), "}") - 1;
+        let empty = fields.as_str().is_empty();
+        self.block(indent, "Properties: {", open, close, empty, |f, indent| {
+            f.fields(fields, indent)
+        });
+    }
+
+    // -----------------------------------------------------------------
+    // Queries
+    // -----------------------------------------------------------------
+
+    fn query_def(&mut self, pair: Pair<'a, Rule>) {
+        let span = pair.as_span();
+        self.leading(span.start(), 0);
+        let mut start = span.start();
+        let mut header = Vec::new();
+        let mut body = None;
+        let mut returns = None;
+        for child in pair.into_inner() {
+            match child.as_rule() {
+                Rule::built_in_macro => {
+                    let child_span = child.as_span();
+                    let doc = self.doc(&child);
+                    self.element(0, child_span.start(), child_span.end(), doc, "");
+                    start = self.after(child_span.end(), "QUERY") - "QUERY".len();
+                }
+                Rule::identifier => {
+                    header.push(Doc::text("QUERY "));
+                    header.push(self.doc(&child));
+                }
+                Rule::query_params => header.push(self.doc(&child)),
+                Rule::query_body => body = Some(child),
+                Rule::return_stmt => returns = Some(child),
+                _ => {}
+            }
+        }
+        header.push(Doc::text(" =>"));
+        let Some(body) = body else {
+            return;
+        };
+        let end = self.after(start, "=>");
+        self.element(0, start, end, Doc::Concat(header), "");
+
+        self.fresh = true;
+        self.statements(body, INDENT);
+        if let Some(returns) = returns {
+            let (start, end) = (returns.as_span().start(), self.end(&returns));
+            let doc = self.doc(&returns);
+            self.element(INDENT, start, end, doc, "");
+        }
+    }
+
+    fn statements(&mut self, body: Pair<'a, Rule>, indent: usize) {
+        for statement in body.into_inner() {
+            match statement.as_rule() {
+                Rule::for_loop => self.for_loop(statement, indent),
+                Rule::if_stmt => {
+                    self.leading(statement.as_span().start(), indent);
+                    self.if_stmt(statement, indent, "");
+                }
+                _ => {
+                    let (start, end) = (statement.as_span().start(), self.end(&statement));
+                    let doc = self.doc(&statement);
+                    self.element(indent, start, end, doc, "");
+                }
+            }
+        }
+    }
+
+    fn for_loop(&mut self, pair: Pair<'a, Rule>, indent: usize) {
+        let span = pair.as_span();
+        self.leading(span.start(), indent);
+        let mut inner = pair.into_inner();
+        let (Some(argument), Some(iterable), Some(body)) =
+            (inner.next(), inner.next(), inner.next())
+        else {
+            return;
+        };
+        let header = Doc::Concat(vec![
+            Doc::text("FOR "),
+            self.doc(&argument),
+            Doc::text(format!(" IN {} {{", iterable.as_str())),
+        ]);
+        let header = doc::print(&header, indent);
+        let open = self.after(iterable.as_span().end(), "{");
+        let empty = body.as_str().is_empty();
+        self.block(indent, &header, open, span.end() - 1, empty, |f, indent| {
+            f.statements(body, indent)
+        });
+    }
+
+    /// An `IF` statement, following the closing brace of the previous branch when `prefix` is set
+    fn if_stmt(&mut self, pair: Pair<'a, Rule>, indent: usize, prefix: &str) {
+        let mut inner = pair.into_inner();
+        let (Some(condition), Some(body)) = (inner.next(), inner.next()) else {
+            return;
+        };
+        let header = Doc::Concat(vec![
+            Doc::text(format!("{prefix}IF ")),
+            self.doc(&condition),
+            Doc::text(" {"),
+        ]);
+        let header = doc::print(&header, indent);
+        let open = self.after(condition.as_span().end(), "{");
+        let close = self.after(body.as_span().end().max(open), "}") - 1;
+        self.line(indent, &header, open);
+        self.fresh = true;
+        self.statements(body, indent + INDENT);
+        self.comments_before(close, indent + INDENT);
+
+        let Some(otherwise) = inner.next() else {
+            self.line(indent, "}", close + 1);
+            return;
+        };
+        let end = otherwise.as_span().end();
+        match otherwise.into_inner().next() {
+            Some(branch) if branch.as_rule() == Rule::if_stmt => {
+                self.if_stmt(branch, indent, "} ELSE ")
+            }
+            branch => {
+                let open = self.after(close + 1, "{");
+                self.line(indent, "} ELSE {", open);
+                self.fresh = true;
+                if let Some(branch) = branch {
+                    self.statements(branch, indent + INDENT);
+                }
+                self.comments_before(end - 1, indent + INDENT);
+                self.line(indent, "}", end);
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // Expressions
+    // -----------------------------------------------------------------
+
+    fn doc(&self, pair: &Pair<'a, Rule>) -> Doc {
+        match pair.as_rule() {
+            Rule::identifier
+            | Rule::identifier_upper
+            | Rule::string_literal
+            | Rule::integer
+            | Rule::float
+            | Rule::field_path => Doc::text(pair.as_str()),
+            Rule::spread_object => Doc::text(".."),
+            Rule::traversal | Rule::id_traversal | Rule::anonymous_traversal => self.chain(pair),
+            Rule::create_field
+            | Rule::object_step
+            | Rule::object_creation
+            | Rule::array_creation => self.list(pair, pair.clone().into_inner().collect(), true),
+            Rule::object => {
+                let fields = pair
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .map(|fields| fields.into_inner().collect())
+                    .unwrap_or_default();
+                self.list(pair, fields, true)
+            }
+            Rule::update
+            | Rule::array_literal
+            | Rule::exclude_field
+            | Rule::query_params
+            | Rule::object_destructuring => {
+                self.list(pair, pair.clone().into_inner().collect(), false)
+            }
+            _ => self.tokens(pair),
+        }
+    }
+
+    /// A pair printed on one line
+    fn inline(&self, pair: &Pair<'a, Rule>) -> String {
+        doc::print(&self.doc(pair), 0)
+    }
+
+    /// The tokens of a pair, with the literals between its children
+    fn tokens(&self, pair: &Pair<'a, Rule>) -> Doc {
+        let mut pieces = Vec::new();
+        let mut pos = pair.as_span().start();
+        for child in pair.clone().into_inner() {
+            let span = child.as_span();
+            for token in tokens(&self.src[pos..span.start()]) {
+                pieces.push((Doc::text(token), token, token));
+            }
+            pos = span.end();
+            let child_tokens = tokens(child.as_str());
+            if let (Some(first), Some(last)) = (child_tokens.first(), child_tokens.last()) {
+                pieces.push((self.doc(&child), *first, *last));
+            }
+        }
+        for token in tokens(&self.src[pos..pair.as_span().end()]) {
+            pieces.push((Doc::text(token), token, token));
+        }
+
+        let mut docs = Vec::new();
+        let mut previous: Option<&str> = None;
+        for (doc, first, last) in pieces {
+            if previous.is_some_and(|previous| spaced(previous, first)) {
+                docs.push(Doc::text(" "));
+            }
+            docs.push(doc);
+            previous = Some(last);
+        }
+        Doc::Concat(docs)
+    }
+
+    /// A traversal, with one step per line when it does not fit on one
+    fn chain(&self, pair: &Pair<'a, Rule>) -> Doc {
+        let mut head = Vec::new();
+        let mut steps = Vec::new();
+        for child in pair.clone().into_inner() {
+            match child.as_rule() {
+                Rule::step | Rule::last_step => {
+                    steps.push(Doc::SoftLine);
+                    steps.push(self.doc(&child));
+                }
+                _ => head.push(self.doc(&child)),
+            }
+        }
+        if pair.as_rule() == Rule::anonymous_traversal {
+            head.push(Doc::text("_"));
+        }
+        Doc::Group(vec![Doc::Concat(head), Doc::Nest(steps)])
+    }
+
+    /// A bracketed list, with one item per line when it does not fit on one
+    fn list(&self, pair: &Pair<'a, Rule>, items: Vec<Pair<'a, Rule>>, trailing_comma: bool) -> Doc {
+        let (Some(first), Some(last)) = (items.first(), items.last()) else {
+            return self.tokens(pair);
+        };
+        let span = pair.as_span();
+        let before = tokens(&self.src[span.start()..first.as_span().start()]);
+        let after = tokens(&self.src[last.as_span().end()..span.end()]);
+        let after = after.strip_prefix(&[","]).unwrap_or(&after);
+        let (Some((open, prefix)), Some((close, suffix))) =
+            (before.split_last(), after.split_first())
+        else {
+            return self.tokens(pair);
+        };
+
+        let mut elements = vec![Doc::SoftLine];
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                elements.push(Doc::text(","));
+                elements.push(Doc::Line);
+            }
+            elements.push(self.doc(item));
+        }
+        if trailing_comma {
+            elements.push(Doc::IfBreak(","));
+        }
+        Doc::Concat(vec![
+            Doc::Group(vec![
+                Doc::text(format!("{}{open}", join(prefix))),
+                Doc::Nest(elements),
+                Doc::SoftLine,
+                Doc::text(*close),
+            ]),
+            Doc::text(join(suffix)),
+        ])
+    }
+
+    // -----------------------------------------------------------------
+    // Lines and comments
+    // -----------------------------------------------------------------
+
+    /// Render the output of `f` on its own, to be placed by the caller
+    fn chunk(&mut self, f: impl FnOnce(&mut Self)) -> String {
+        let out = std::mem::take(&mut self.out);
+        f(self);
+        std::mem::replace(&mut self.out, out)
+    }
+
+    /// A block of lines between `header` (ending with its opening brace) and a closing brace
+    fn block(
+        &mut self,
+        indent: usize,
+        header: &str,
+        open: usize,
+        close: usize,
+        empty: bool,
+        body: impl FnOnce(&mut Self, usize),
+    ) {
+        if empty && !self.has_comment(open, close) {
+            self.line(indent, &format!("{header}}}"), close + 1);
+            return;
+        }
+        self.line(indent, header, open);
+        self.fresh = true;
+        body(self, indent + INDENT);
+        self.comments_before(close, indent + INDENT);
+        self.line(indent, "}", close + 1);
+    }
+
+    /// An expression on its own line, kept as written when there are comments inside it
+    fn element(&mut self, indent: usize, start: usize, end: usize, doc: Doc, suffix: &str) {
+        self.leading(start, indent);
+        let text = if self.has_comment(start, end) {
+            self.verbatim(start, end, indent)
+        } else {
+            doc::print(&doc, indent)
+        };
+        self.line(indent, &format!("{text}{suffix}"), end);
+    }
+
+    /// The source between `start` and `end`, moved to `indent` along with the lines after the
+    /// first, which keep their indentation relative to it
+    fn verbatim(&mut self, start: usize, end: usize, indent: usize) -> String {
+        while self
+            .comments
+            .get(self.next_comment)
+            .is_some_and(|comment| comment.start < end)
+        {
+            self.next_comment += 1;
+        }
+        let line_start = self.src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let base = self.src[line_start..start].chars().count();
+
+        let mut lines = self.src[start..end].lines();
+        let mut text = lines.next().unwrap_or_default().trim_end().to_string();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let content = line.trim_start();
+            let depth = line.len() - content.len();
+            text.push('\n');
+            text.push_str(&" ".repeat(indent + depth.saturating_sub(base)));
+            text.push_str(content.trim_end());
+        }
+        text
+    }
+
+    /// Print a line, followed by the comment written after `end` on the same line
+    fn line(&mut self, indent: usize, text: &str, end: usize) {
+        self.out.push_str(&" ".repeat(indent));
+        self.out.push_str(text);
+        self.last = end;
+        if let Some(comment) = self.comments.get(self.next_comment)
+            && comment.start >= end
+            && !self.src[end..comment.start].contains('\n')
+        {
+            self.out.push(' ');
+            self.out
+                .push_str(self.src[comment.start..comment.end].trim_end());
+            self.last = comment.end;
+            self.next_comment += 1;
+        }
+        self.out.push('\n');
+        self.fresh = false;
+    }
+
+    /// Comments before `pos`, followed by a blank line if the source has one before `pos`
+    fn leading(&mut self, pos: usize, indent: usize) {
+        self.comments_before(pos, indent);
+        self.blank_line(pos);
+    }
+
+    /// Comments before `pos`, each on its own line
+    fn comments_before(&mut self, pos: usize, indent: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment)
+            && comment.start < pos
+        {
+            let (start, end) = (comment.start, comment.end);
+            self.blank_line(start);
+            self.out.push_str(&" ".repeat(indent));
+            self.out.push_str(self.src[start..end].trim_end());
+            self.out.push('\n');
+            self.fresh = false;
+            self.last = end;
+            self.next_comment += 1;
+        }
+    }
+
+    /// Keep a blank line between the source printed last and `pos`
+    fn blank_line(&mut self, pos: usize) {
+        let gap = &self.src[self.last.min(pos)..pos];
+        if !self.fresh && gap.matches('\n').count() > 1 {
+            self.out.push('\n');
+        }
+        self.last = self.last.max(pos);
+    }
+
+    fn has_comment(&self, start: usize, end: usize) -> bool {
+        self.comments[self.next_comment..]
+            .iter()
+            .any(|comment| comment.start >= start && comment.start < end)
+    }
+
+    /// End of the last token of a pair, as pest spans can run over the whitespace and comments
+    /// following an optional part at the end of a rule
+    fn end(&self, pair: &Pair<'a, Rule>) -> usize {
+        let start = pair.as_span().start();
+        token_offsets(pair.as_str())
+            .last()
+            .map_or(start, |(offset, token)| start + offset + token.len())
+    }
+
+    /// Position just after the next `token` from `from`, skipping comments
+    fn after(&self, from: usize, token: &str) -> usize {
+        let mut pos = from;
+        while pos < self.src.len() {
+            let rest = &self.src[pos..];
+            if rest.starts_with(token) {
+                return pos + token.len();
+            }
+            pos += match rest.starts_with("//") {
+                true => rest.find('\n').unwrap_or(rest.len()),
+                false => rest.chars().next().map_or(1, char::len_utf8),
+            };
+        }
+        self.src.len()
+    }
+}
+
+/// Order schema items are grouped in
+fn schema_kind(pair: &Pair<Rule>) -> Option<usize> {
+    match pair.as_rule() {
+        Rule::enum_def => Some(0),
+        Rule::struct_def => Some(1),
+        Rule::node_def => Some(2),
+        Rule::vector_def => Some(3),
+        Rule::edge_def => Some(4),
+        _ => None,
+    }
+}
+
+/// Comments of the source, skipping string literals
+fn comments(src: &str) -> Vec<Comment> {
+    let mut comments = Vec::new();
+    let mut chars = src.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                chars.by_ref().find(|(_, c)| *c == '"');
+            }
+            '/' if src[i..].starts_with("//") => {
+                let end = src[i..].find('\n').map_or(src.len(), |len| i + len);
+                comments.push(Comment { start: i, end });
+                chars.by_ref().find(|(j, _)| *j + 1 >= end);
+            }
+            _ => {}
+        }
+    }
+    comments
+}
+
+/// Split source into tokens, skipping whitespace and comments
+fn tokens(text: &str) -> Vec<&str> {
+    token_offsets(text)
+        .into_iter()
+        .map(|(_, token)| token)
+        .collect()
+}
+
+/// Tokens of the source, with their offset in it
+fn token_offsets(text: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        let rest = &text[pos..];
+        let len = if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        } else if rest.starts_with("//") {
+            pos += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        } else if c == '"' {
+            rest[1..].find('"').map_or(rest.len(), |end| end + 2)
+        } else if c.is_alphanumeric() || c == '_' {
+            rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len())
+        } else if ["::", "<-", "=>", "..", "#["]
+            .iter()
+            .any(|token| rest.starts_with(token))
+        {
+            2
+        } else {
+            c.len_utf8()
+        };
+        tokens.push((pos, &rest[..len]));
+        pos += len;
+    }
+    tokens
+}
+
+/// Whether two tokens are separated by a space
+fn spaced(left: &str, right: &str) -> bool {
+    let tight_left = matches!(
+        left,
+        "(" | "[" | "{" | "<" | "::" | "." | ".." | "!" | "|" | "#["
+    );
+    let tight_right = matches!(
+        right,
+        ")" | "]" | "}" | ">" | "," | ":" | "::" | "." | ".." | "?" | "|" | "(" | "<"
+    );
+    !(tight_left || tight_right)
+}
+
+fn join(tokens: &[&str]) -> String {
+    let mut out = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && spaced(tokens[i - 1], token) {
+            out.push(' ');
+        }
+        out.push_str(token);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Format a source, checking that formatting it again changes nothing
+    fn format(src: &str) -> String {
+        let formatted = format_source(src).unwrap();
+        assert_eq!(format_source(&formatted).unwrap(), formatted);
+        formatted
+    }
+
+    #[test]
+    fn test_format_query() {
+        let src = r#"
+QUERY   GetUser (name : String,age:U8) =>
+  user<-N<User>::WHERE( AND(_::{name}::EQ(name),_::{age}::GT( age )) )
+     friends <- user::Out<Follows>::{ name, age }
+  RETURN user,friends
+"#;
+        let expected = r#"QUERY GetUser(name: String, age: U8) =>
+    user <- N<User>::WHERE(AND(_::{name}::EQ(name), _::{age}::GT(age)))
+    friends <- user::Out<Follows>::{name, age}
+    RETURN user, friends
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_schema() {
+        let src = r#"
+E::Follows { From: User, To: User, Properties: { since: I32 } }
+N::User{INDEX name:String, age : U8 DEFAULT 18, nickname: String?}
+V::Document { content: String }
+ENUM Status { Active, Inactive }
+N::Marker {}
+STRUCT Address { city: String, zip: U32? }
+"#;
+        // items are grouped by declaration, keeping their order within a group
+        let expected = r#"ENUM Status {
+    Active,
+    Inactive,
+}
+
+STRUCT Address {
+    city: String,
+    zip: U32?,
+}
+
+N::User {
+    INDEX name: String,
+    age: U8 DEFAULT 18,
+    nickname: String?,
+}
+
+N::Marker {}
+
+V::Document {
+    content: String,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I32,
+    }
+}
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_versioned_schema_and_migration() {
+        let src = r#"
+schema::1 { N::User { name: String } }
+schema::2 { E::Knows { From: User, To: User } N::User { username: String } }
+MIGRATION schema::1 => schema::2 {
+    N::User => _::{ username: name }
+    E::Knows => _::{ Properties: { since: 0 } }
+}
+"#;
+        let expected = r#"schema::1 {
+    N::User {
+        name: String,
+    }
+}
+
+schema::2 {
+    N::User {
+        username: String,
+    }
+
+    E::Knows {
+        From: User,
+        To: User,
+    }
+}
+
+MIGRATION schema::1 => schema::2 {
+    N::User => _::{
+        username: name,
+    }
+
+    E::Knows => _::{
+        Properties: {
+            since: 0,
+        }
+    }
+}
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_long_chains_and_lists() {
+        let src = r#"
+QUERY AddUser(name: String, email: String, bio: String, location: String) =>
+    user <- AddN<User>({name: name, email: email, bio: bio, location: location, verified: false, score: 0})
+    posts <- N<User>::WHERE(_::{email}::EQ(email))::Out<Authored>::WHERE(_::{published}::EQ(true))::RANGE(0, 10)
+    RETURN user, posts
+"#;
+        let expected = r#"QUERY AddUser(name: String, email: String, bio: String, location: String) =>
+    user <- AddN<User>({
+        name: name,
+        email: email,
+        bio: bio,
+        location: location,
+        verified: false,
+        score: 0,
+    })
+    posts <- N<User>
+        ::WHERE(_::{email}::EQ(email))
+        ::Out<Authored>
+        ::WHERE(_::{published}::EQ(true))
+        ::RANGE(0, 10)
+    RETURN user, posts
+"#;
+        assert_eq!(format(src), expected);
+
+        // chains that fit are joined back on one line
+        let joined = r#"
+QUERY GetPosts() =>
+    posts <- N<User>
+        ::Out<Authored>
+    RETURN posts
+"#;
+        assert!(format(joined).contains("    posts <- N<User>::Out<Authored>\n"));
+    }
+
+    #[test]
+    fn test_format_nested_blocks() {
+        let src = r#"
+#[mcp]
+QUERY Import(flag: Boolean, names: [String]) =>
+  FOR name IN names { AddN<User>({name: name}) }
+  IF flag { x <- N<User> } ELSE IF AND(flag, flag) {
+  } ELSE { y <- N<User>::|u|{name: u::{name}, ..} }
+  RETURN NONE
+"#;
+        let expected = r#"#[mcp]
+QUERY Import(flag: Boolean, names: [String]) =>
+    FOR name IN names {
+        AddN<User>({name: name})
+    }
+    IF flag {
+        x <- N<User>
+    } ELSE IF AND(flag, flag) {
+    } ELSE {
+        y <- N<User>::|u|{name: u::{name}, ..}
+    }
+    RETURN NONE
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let src = r#"// Schema
+N::User {
+    name: String, // display name
+
+    // the age in years
+    age: U8
+}
+
+
+// Queries
+
+QUERY GetUser(name: String) =>
+    // find the user
+    user <- N<User>::WHERE(_::{name}::EQ(name)) // by name
+    RETURN user // done
+// end
+"#;
+        let expected = r#"// Schema
+N::User {
+    name: String, // display name
+
+    // the age in years
+    age: U8,
+}
+
+// Queries
+
+QUERY GetUser(name: String) =>
+    // find the user
+    user <- N<User>::WHERE(_::{name}::EQ(name)) // by name
+    RETURN user // done
+// end
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_keeps_expressions_with_comments() {
+        let src = r#"
+QUERY GetUsers() =>
+  users <- N<User>::{
+      name, // the name
+      age,
+  }
+  RETURN users
+"#;
+        let expected = r#"QUERY GetUsers() =>
+    users <- N<User>::{
+        name, // the name
+        age,
+    }
+    RETURN users
+"#;
+        assert_eq!(format(src), expected);
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_source() {
+        assert!(format_source("QUERY Broken( =>").is_err());
+        assert_eq!(format_source("").unwrap(), "");
+    }
+}
@@ -2,6 +2,7 @@ use core::fmt;
 use std::fmt::Display;
 
 use crate::helixc::generator::{
+    scalar_functions::ScalarFunctionCallGen,
     source_steps::SourceStep,
     traversal_steps::{Step, Traversal, TraversalType},
 };
@@ -18,10 +19,20 @@ pub enum BoolOp {
     Neq(Neq),
     Contains(Contains),
     IsIn(IsIn),
+    IsNull(IsNull),
+    Any(Any),
+    All(All),
 }
-impl Display for BoolOp {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
+impl BoolOp {
+    /// Result of the op when the property is missing from the item.
+    /// Only `IS_NULL` holds for a missing property, every comparison fails.
+    pub fn value_if_missing(&self) -> bool {
+        matches!(self, BoolOp::IsNull(_))
+    }
+
+    /// The condition on the fetched property `v`
+    pub fn predicate(&self) -> String {
+        match self {
             BoolOp::Gt(gt) => format!("{gt}"),
             BoolOp::Gte(gte) => format!("{gte}"),
             BoolOp::Lt(lt) => format!("{lt}"),
@@ -30,8 +41,20 @@ impl Display for BoolOp {
             BoolOp::Neq(neq) => format!("{neq}"),
             BoolOp::Contains(contains) => format!("v{contains}"),
             BoolOp::IsIn(is_in) => format!("v{is_in}"),
-        };
-        write!(f, "map_value_or(false, |v| {s})?")
+            BoolOp::IsNull(is_null) => format!("{is_null}"),
+            BoolOp::Any(any) => format!("{any}"),
+            BoolOp::All(all) => format!("{all}"),
+        }
+    }
+}
+impl Display for BoolOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "map_value_or({}, |v| {})?",
+            self.value_if_missing(),
+            self.predicate()
+        )
     }
 }
 #[derive(Clone, Debug)]
@@ -120,6 +143,44 @@ impl Display for IsIn {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct IsNull;
+impl Display for IsNull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "matches!(v, Value::Empty)")
+    }
+}
+
+/// Holds when the op holds for at least one element of an array property
+#[derive(Clone, Debug)]
+pub struct Any {
+    pub op: Box<BoolOp>,
+}
+impl Display for Any {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "matches!(v, Value::Array(items) if items.iter().any(|v| {}))",
+            self.op.predicate()
+        )
+    }
+}
+
+/// Holds when the op holds for every element of an array property
+#[derive(Clone, Debug)]
+pub struct All {
+    pub op: Box<BoolOp>,
+}
+impl Display for All {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "matches!(v, Value::Array(items) if items.iter().all(|v| {}))",
+            self.op.predicate()
+        )
+    }
+}
+
 /// Boolean expression is used for a traversal or set of traversals wrapped in AND/OR
 /// that resolve to a boolean value
 #[derive(Clone, Debug)]
@@ -129,6 +190,7 @@ pub enum BoExp {
     Or(Vec<BoExp>),
     Exists(Traversal),
     Expr(Traversal),
+    ScalarFunction(ScalarFunctionCallGen),
     Empty,
 }
 
@@ -187,15 +249,21 @@ impl Display for BoExp {
             BoExp::Expr(traversal) => {
                 // Optimize simple property checks in filters to avoid unnecessary cloning and traversal creation
                 // Check if this is a FromVar("val") or FromSingle("val") traversal with just property fetch + bool op
-                let is_val_traversal = match &traversal.traversal_type {
+                // A single item bound to a variable (e.g. in an `IF` condition) is checked the same way
+                let single_var = match &traversal.traversal_type {
                     TraversalType::FromIter(var) | TraversalType::FromSingle(var) => match var {
-                        GenRef::Std(s) | GenRef::Literal(s) => s == "val",
-                        _ => false,
+                        GenRef::Std(s) | GenRef::Literal(s) if s == "val" => Some(s.as_str()),
+                        GenRef::Std(s) | GenRef::Literal(s)
+                            if matches!(traversal.traversal_type, TraversalType::FromSingle(_)) =>
+                        {
+                            Some(s.as_str())
+                        }
+                        _ => None,
                     },
-                    _ => false,
+                    _ => None,
                 };
 
-                if is_val_traversal {
+                if let Some(var) = single_var {
                     // Look for PropertyFetch followed by BoolOp pattern (in any Separator type)
                     let mut prop_info: Option<&GenRef<String>> = None;
                     let mut bool_op_info: Option<&BoolOp> = None;
@@ -232,26 +300,21 @@ impl Display for BoExp {
                         && other_steps == 0
                     {
                         // Generate optimized code: val.get_property("prop").map_or(false, |v| ...)
-                        let bool_expr = match bool_op {
-                            BoolOp::Gt(gt) => format!("{gt}"),
-                            BoolOp::Gte(gte) => format!("{gte}"),
-                            BoolOp::Lt(lt) => format!("{lt}"),
-                            BoolOp::Lte(lte) => format!("{lte}"),
-                            BoolOp::Eq(eq) => format!("{eq}"),
-                            BoolOp::Neq(neq) => format!("{neq}"),
-                            BoolOp::Contains(contains) => format!("v{contains}"),
-                            BoolOp::IsIn(is_in) => format!("v{is_in}"),
-                        };
+                        let bool_expr = bool_op.predicate();
                         return write!(
                             f,
-                            "val\n                    .get_property({})\n                    .map_or(false, |v| {})",
-                            prop, bool_expr
+                            "{}\n                    .get_property({})\n                    .map_or({}, |v| {})",
+                            var,
+                            prop,
+                            bool_op.value_if_missing(),
+                            bool_expr
                         );
                     }
                 }
                 // Fall back to full traversal for complex expressions
                 write!(f, "{traversal}")
             }
+            BoExp::ScalarFunction(call) => write!(f, "matches!({call}, Value::Boolean(true))"),
             BoExp::Empty => write!(f, ""),
         }
     }
@@ -369,6 +432,39 @@ mod tests {
         assert!(output.contains("map_value_or(false, |v| v.contains(\"text\"))"));
     }
 
+    #[test]
+    fn test_boolop_is_null_wrapped() {
+        let bool_op = BoolOp::IsNull(IsNull);
+        let output = format!("{}", bool_op);
+        assert!(output.contains("map_value_or(true, |v| matches!(v, Value::Empty))"));
+    }
+
+    #[test]
+    fn test_boolop_any_wrapped() {
+        let bool_op = BoolOp::Any(Any {
+            op: Box::new(BoolOp::Eq(Eq {
+                left: GeneratedValue::Primitive(GenRef::Std("*v".to_string())),
+                right: GeneratedValue::Literal(GenRef::Literal("admin".to_string())),
+            })),
+        });
+        let output = format!("{}", bool_op);
+        assert!(output.contains(
+            "map_value_or(false, |v| matches!(v, Value::Array(items) if items.iter().any(|v| *v == \"admin\")))"
+        ));
+    }
+
+    #[test]
+    fn test_boolop_all_wrapped() {
+        let bool_op = BoolOp::All(All {
+            op: Box::new(BoolOp::Gt(Gt {
+                left: GeneratedValue::Primitive(GenRef::Std("*v".to_string())),
+                right: GeneratedValue::Primitive(GenRef::Std("5".to_string())),
+            })),
+        });
+        let output = format!("{}", bool_op);
+        assert!(output.contains("items.iter().all(|v| *v > 5)"));
+    }
+
     // ============================================================================
     // BoExp Tests
     // ============================================================================
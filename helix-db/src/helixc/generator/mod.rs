@@ -10,7 +10,7 @@ use crate::{
     helixc::generator::{
         migrations::GeneratedMigration,
         queries::Query,
-        schemas::{EdgeSchema, NodeSchema, VectorSchema},
+        schemas::{EdgeSchema, EnumSchema, NodeSchema, StructSchema, VectorSchema},
         utils::write_headers,
     },
 };
@@ -23,7 +23,9 @@ pub mod math_functions;
 pub mod migrations;
 pub mod queries;
 pub mod return_values;
+pub mod scalar_functions;
 pub mod schemas;
+pub mod sdk;
 pub mod source_steps;
 pub mod statements;
 pub mod traversal_steps;
@@ -39,6 +41,8 @@ pub fn generate(source: Source, path: &Path) -> Result<()> {
 }
 
 pub struct Source {
+    pub enums: Vec<EnumSchema>,
+    pub structs: Vec<StructSchema>,
     pub nodes: Vec<NodeSchema>,
     pub edges: Vec<EdgeSchema>,
     pub vectors: Vec<VectorSchema>,
@@ -50,6 +54,8 @@ pub struct Source {
 impl Default for Source {
     fn default() -> Self {
         Self {
+            enums: vec![],
+            structs: vec![],
             nodes: vec![],
             edges: vec![],
             vectors: vec![],
@@ -64,6 +70,26 @@ impl Display for Source {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{}", write_headers())?;
         writeln!(f, "{}", self.config)?;
+        write!(
+            f,
+            "{}",
+            self.enums
+                .iter()
+                .map(|e| format!("{e}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )?;
+        writeln!(f)?;
+        write!(
+            f,
+            "{}",
+            self.structs
+                .iter()
+                .map(|s| format!("{s}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )?;
+        writeln!(f)?;
         write!(
             f,
             "{}",
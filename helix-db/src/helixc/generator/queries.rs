@@ -15,6 +15,7 @@ pub struct Query {
     pub sub_parameters: Vec<(String, Vec<Parameter>)>,
     pub return_values: Vec<(String, ReturnValue)>, // Legacy approach
     pub return_structs: Vec<ReturnValueStruct>,    // New struct-based approach
+    pub primitive_returns: Vec<String>,            // Returned primitives, which have no struct
     pub use_struct_returns: bool,                  // Flag to use new vs old approach
     pub is_mut: bool,
     pub hoisted_embedding_calls: Vec<EmbedData>,
@@ -497,6 +498,14 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::Computed(call) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            call.render(singular_var)
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::PropertyPath(path) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            format!("{}.get_property(\"{}\")", singular_var, path)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", singular_var)
                         } else if field.name == "label" {
@@ -507,7 +516,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", singular_var)
                         } else if field.name == "data" {
                             format!("{}.data()", singular_var)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", singular_var)
                         } else {
                             // Regular schema field
@@ -708,6 +717,14 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::Computed(call) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            call.render(&struct_def.source_variable)
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::PropertyPath(path) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            format!("{}.get_property(\"{}\")", struct_def.source_variable, path)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", struct_def.source_variable)
                         } else if field.name == "label" {
@@ -721,7 +738,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", struct_def.source_variable)
                         } else if field.name == "data" {
                             format!("{}.data()", struct_def.source_variable)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", struct_def.source_variable)
                         } else {
                             format!(
@@ -735,6 +752,18 @@ impl Query {
                     write!(f, "    }}")?;
                 }
             }
+            // Primitive values (bools, scalars) have no return struct, so serialize them directly
+            for (field_name, ret_val) in self
+                .return_values
+                .iter()
+                .filter(|(name, _)| self.primitive_returns.contains(name))
+            {
+                writeln!(f, ",")?;
+                match &ret_val.literal_value {
+                    Some(lit) => write!(f, "    \"{}\": {}", field_name, lit)?,
+                    None => write!(f, "    \"{}\": {}", field_name, field_name)?,
+                }
+            }
             writeln!(f)?;
             writeln!(f, "}});")?;
             self.print_txn_commit(f)?;
@@ -1113,6 +1142,14 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::Computed(call) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            call.render(singular_var)
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::PropertyPath(path) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            format!("{}.get_property(\"{}\")", singular_var, path)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", singular_var)
                         } else if field.name == "label" {
@@ -1123,7 +1160,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", singular_var)
                         } else if field.name == "data" {
                             format!("{}.data()", singular_var)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", singular_var)
                         } else {
                             // Regular schema field
@@ -1324,6 +1361,14 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::Computed(call) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            call.render(&struct_def.source_variable)
+                        } else if let crate::helixc::generator::return_values::ReturnFieldSource::PropertyPath(path) =
+                            &struct_def.field_infos[field_idx].source
+                        {
+                            format!("{}.get_property(\"{}\")", struct_def.source_variable, path)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", struct_def.source_variable)
                         } else if field.name == "label" {
@@ -1337,7 +1382,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", struct_def.source_variable)
                         } else if field.name == "data" {
                             format!("{}.data()", struct_def.source_variable)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", struct_def.source_variable)
                         } else {
                             format!(
@@ -1452,6 +1497,7 @@ impl Default for Query {
             sub_parameters: vec![],
             return_values: vec![],
             return_structs: vec![],
+            primitive_returns: vec![],
             use_struct_returns: true, // Enable new struct-based returns
             is_mut: false,
             hoisted_embedding_calls: vec![],
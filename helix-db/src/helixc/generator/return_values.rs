@@ -1,7 +1,7 @@
 use core::fmt;
 use std::fmt::Display;
 
-use super::utils::GenRef;
+use super::{scalar_functions::ScalarFunctionCallGen, utils::GenRef};
 
 /// Represents a return value field with enhanced metadata
 #[derive(Clone, Debug)]
@@ -11,6 +11,7 @@ pub struct ReturnValueField {
     pub is_implicit: bool,         // id, label, from_node, to_node, data, score
     pub is_nested_traversal: bool, // Whether this field contains a nested traversal
     pub nested_struct_name: Option<String>, // Name of nested struct type if applicable
+    pub serialize_with: Option<String>, // Function serializing the field, e.g. for enum variants
 }
 
 impl ReturnValueField {
@@ -21,6 +22,7 @@ impl ReturnValueField {
             is_implicit: false,
             is_nested_traversal: false,
             nested_struct_name: None,
+            serialize_with: None,
         }
     }
 
@@ -117,6 +119,11 @@ impl ReturnValueStruct {
 
         // Generate fields
         for field in &self.fields {
+            if let Some(serialize_with) = &field.serialize_with {
+                output.push_str(&format!(
+                    "    #[serde(serialize_with = \"{serialize_with}\")]\n"
+                ));
+            }
             if self.has_lifetime {
                 output.push_str(&format!("    pub {}: {},\n", field.name, field.field_type));
             } else {
@@ -148,7 +155,7 @@ impl ReturnValueStruct {
                 format!("uuid_str({}.to_node(), &arena)", singular_var)
             } else if field.name == "data" {
                 format!("{}.data()", singular_var)
-            } else if field.name == "score" {
+            } else if field.name == "score" && field.is_implicit {
                 format!("{}.score()", singular_var)
             } else if field.is_nested_traversal {
                 // Nested traversal - will be populated by nested G::new() call
@@ -261,6 +268,12 @@ impl ReturnValueStruct {
                         ReturnFieldSource::NestedTraversal { .. }
                     ),
                     nested_struct_name: nested_name,
+                    serialize_with: match &field_info.source {
+                        ReturnFieldSource::EnumField(enum_name) => {
+                            Some(format!("{enum_name}::serialize_property"))
+                        }
+                        _ => None,
+                    },
                 }
             })
             .collect::<Vec<_>>();
@@ -352,7 +365,7 @@ pub fn generate_map_closure_body(
             "uuid_str(val.to_node(), &arena)".to_string()
         } else if field.name == "data" {
             "val.data()".to_string()
-        } else if field.name == "score" {
+        } else if field.name == "score" && field.is_implicit {
             "val.score()".to_string()
         } else if field.is_nested_traversal {
             // Nested traversal - will be populated by nested G::new() call
@@ -432,6 +445,8 @@ pub enum ReturnFieldType {
 pub enum ReturnFieldSource {
     /// Field from the schema (node/edge/vector properties)
     SchemaField,
+    /// Field from the schema holding a variant of the named enum
+    EnumField(String),
     /// Implicit field (id, label, from_node, to_node, data, score)
     ImplicitField,
     /// User-defined field in custom object
@@ -447,6 +462,10 @@ pub enum ReturnFieldSource {
         accessed_field_name: Option<String>, // For simple property access, the field being accessed (e.g., "name" for usr::{name})
         own_closure_param: Option<String>, // This traversal's own closure parameter if it ends with a Closure step
     },
+    /// Result of a string or date function evaluated on each item
+    Computed(ScalarFunctionCallGen),
+    /// Field inside an object property, read by its dotted path
+    PropertyPath(String),
 }
 
 impl ReturnFieldInfo {
@@ -466,6 +485,14 @@ impl ReturnFieldInfo {
         }
     }
 
+    pub fn new_enum(name: String, enum_name: String) -> Self {
+        Self {
+            name,
+            field_type: ReturnFieldType::Simple("Option<&'a Value>".to_string()),
+            source: ReturnFieldSource::EnumField(enum_name),
+        }
+    }
+
     pub fn new_nested(name: String, fields: Vec<ReturnFieldInfo>, traversal_expr: String) -> Self {
         Self {
             name,
@@ -490,6 +517,22 @@ impl ReturnFieldInfo {
             source: ReturnFieldSource::UserDefined,
         }
     }
+
+    pub fn new_computed(name: String, call: ScalarFunctionCallGen) -> Self {
+        Self {
+            name,
+            field_type: ReturnFieldType::Simple("Value".to_string()),
+            source: ReturnFieldSource::Computed(call),
+        }
+    }
+
+    pub fn new_property_path(name: String, path: String) -> Self {
+        Self {
+            name,
+            field_type: ReturnFieldType::Simple("Option<&'a Value>".to_string()),
+            source: ReturnFieldSource::PropertyPath(path),
+        }
+    }
 }
 
 /// Legacy ReturnValue structure for backwards compatibility
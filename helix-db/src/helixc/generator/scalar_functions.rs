@@ -0,0 +1,76 @@
+use std::fmt::{self, Display};
+
+use crate::helixc::parser::types::ScalarFunction;
+
+/// Generated argument to a string, date or array function
+#[derive(Debug, Clone)]
+pub enum ScalarArg {
+    /// Literal, parameter or variable, converted with `Value::from`
+    Value(String),
+    /// Property of the current item (e.g. `_::{name}`)
+    Property(String),
+    /// Nested function call
+    Call(Box<ScalarFunctionCallGen>),
+    /// Omitted optional argument
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScalarFunctionCallGen {
+    pub function: ScalarFunction,
+    pub args: Vec<ScalarArg>,
+}
+
+impl ScalarFunctionCallGen {
+    /// Whether any argument reads a property of the current item
+    pub fn uses_item(&self) -> bool {
+        self.args.iter().any(|arg| match arg {
+            ScalarArg::Property(_) => true,
+            ScalarArg::Call(call) => call.uses_item(),
+            _ => false,
+        })
+    }
+
+    /// Renders the call with properties read from `item`
+    pub fn render(&self, item: &str) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| match arg {
+                ScalarArg::Value(value) => format!("&Value::from({value})"),
+                ScalarArg::Property(name) => {
+                    format!("{item}.get_property(\"{name}\").unwrap_or(&Value::Empty)")
+                }
+                ScalarArg::Call(call) => format!("&{}", call.render(item)),
+                ScalarArg::Empty => "&Value::Empty".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let name = match self.function {
+            ScalarFunction::Lower => "lower",
+            ScalarFunction::Upper => "upper",
+            ScalarFunction::Concat => "concat",
+            ScalarFunction::Substring => "substring",
+            ScalarFunction::StartsWith => "starts_with",
+            ScalarFunction::EndsWith => "ends_with",
+            ScalarFunction::RegexMatch => "regex_match",
+            ScalarFunction::Length => "length",
+            ScalarFunction::DateTrunc => "date_trunc",
+            ScalarFunction::DateAdd => "date_add",
+            ScalarFunction::DateDiff => "date_diff",
+            ScalarFunction::Now => "now",
+            ScalarFunction::Append => "append",
+            ScalarFunction::Remove => "remove",
+        };
+        match self.function {
+            ScalarFunction::Concat => format!("functions::{name}(&[{}])", args.join(", ")),
+            _ => format!("functions::{name}({})", args.join(", ")),
+        }
+    }
+}
+
+/// Displays the call inside a `WHERE` closure, where the current item is `val`
+impl Display for ScalarFunctionCallGen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render("val"))
+    }
+}
@@ -18,7 +18,7 @@ impl Display for NodeSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "pub struct {} {{", self.name)?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    pub {}: {},", property.name, property.rust_type())?;
         }
         writeln!(f, "}}")
     }
@@ -29,14 +29,7 @@ impl ToTypeScript for NodeSchema {
         result.push_str("  id: string;\n");
 
         for property in &self.properties {
-            result.push_str(&format!(
-                "  {}: {};\n",
-                property.name,
-                match &property.field_type {
-                    GeneratedType::RustType(t) => t.to_ts(),
-                    _ => unreachable!(),
-                }
-            ));
+            result.push_str(&format!("  {};\n", property.ts_member()));
         }
 
         result.push_str("}\n");
@@ -57,7 +50,7 @@ impl Display for EdgeSchema {
         writeln!(f, "    pub from: {},", self.from)?;
         writeln!(f, "    pub to: {},", self.to)?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    pub {}: {},", property.name, property.rust_type())?;
         }
         writeln!(f, "}}")
     }
@@ -69,14 +62,7 @@ impl ToTypeScript for VectorSchema {
         result.push_str("  data: Array<number>;\n");
 
         for property in &self.properties {
-            result.push_str(&format!(
-                "  {}: {};\n",
-                property.name,
-                match &property.field_type {
-                    GeneratedType::RustType(t) => t.to_ts(),
-                    _ => unreachable!(),
-                }
-            ));
+            result.push_str(&format!("  {};\n", property.ts_member()));
         }
 
         result.push_str("}\n");
@@ -92,7 +78,7 @@ impl Display for VectorSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "pub struct {} {{", self.name)?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    pub {}: {},", property.name, property.rust_type())?;
         }
         writeln!(f, "}}")
     }
@@ -102,16 +88,7 @@ impl ToTypeScript for EdgeSchema {
         let properties_str = self
             .properties
             .iter()
-            .map(|p| {
-                format!(
-                    "    {}: {}",
-                    p.name,
-                    match &p.field_type {
-                        GeneratedType::RustType(t) => t.to_ts(),
-                        _ => unreachable!(),
-                    }
-                )
-            })
+            .map(|p| format!("    {}", p.ts_member()))
             .collect::<Vec<_>>()
             .join(";");
 
@@ -122,15 +99,157 @@ impl ToTypeScript for EdgeSchema {
     }
 }
 
+#[derive(Clone)]
+pub struct EnumSchema {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+impl Display for EnumSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]"
+        )?;
+        writeln!(f, "pub enum {} {{", self.name)?;
+        for variant in &self.variants {
+            writeln!(f, "    {variant},")?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "impl {} {{", self.name)?;
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| format!("\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            f,
+            "    pub const VARIANTS: &'static [&'static str] = &[{variants}];"
+        )?;
+        writeln!(f, "    pub fn as_str(&self) -> &'static str {{")?;
+        writeln!(f, "        Self::VARIANTS[*self as usize]")?;
+        writeln!(f, "    }}")?;
+        writeln!(
+            f,
+            "    pub fn serialize_property<S: enums::Serializer>(value: &Option<&Value>, serializer: S) -> Result<S::Ok, S::Error> {{"
+        )?;
+        writeln!(
+            f,
+            "        enums::serialize_variant(value, Self::VARIANTS, serializer)"
+        )?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")?;
+        // stored as the index of the variant, and read back as its name
+        writeln!(f, "impl From<{}> for Value {{", self.name)?;
+        writeln!(f, "    fn from(v: {}) -> Self {{", self.name)?;
+        writeln!(
+            f,
+            "        enums::variant_value(v as usize, {}::VARIANTS.len())",
+            self.name
+        )?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "impl PartialEq<{}> for Value {{", self.name)?;
+        writeln!(f, "    fn eq(&self, other: &{}) -> bool {{", self.name)?;
+        writeln!(
+            f,
+            "        enums::variant_index(self) == Some(*other as usize)"
+        )?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")
+    }
+}
+impl ToTypeScript for EnumSchema {
+    fn to_typescript(&self) -> String {
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| format!("\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        format!("type {} = {};\n", self.name, variants)
+    }
+}
+
+#[derive(Clone)]
+pub struct StructSchema {
+    pub name: String,
+    pub properties: Vec<SchemaProperty>,
+}
+impl Display for StructSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "#[derive(Serialize, Deserialize, Debug, Clone)]")?;
+        writeln!(f, "pub struct {} {{", self.name)?;
+        for property in &self.properties {
+            writeln!(f, "    pub {}: {},", property.name, property.rust_type())?;
+        }
+        writeln!(f, "}}")?;
+        // stored as an object of its fields
+        writeln!(f, "impl From<{}> for Value {{", self.name)?;
+        writeln!(f, "    fn from(v: {}) -> Self {{", self.name)?;
+        writeln!(f, "        Value::Object(HashMap::from([")?;
+        for property in &self.properties {
+            writeln!(
+                f,
+                "            (\"{0}\".to_string(), Value::from(v.{0})),",
+                property.name
+            )?;
+        }
+        writeln!(f, "        ]))")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")
+    }
+}
+impl ToTypeScript for StructSchema {
+    fn to_typescript(&self) -> String {
+        let mut result = format!("interface {} {{\n", self.name);
+        for property in &self.properties {
+            result.push_str(&format!("  {};\n", property.ts_member()));
+        }
+        result.push_str("}\n");
+        result
+    }
+}
+
 #[derive(Clone)]
 pub struct SchemaProperty {
     pub name: String,
     pub field_type: GeneratedType,
     pub default_value: Option<GeneratedValue>,
-    // pub is_optional: bool,
+    pub is_optional: bool,
     pub is_index: FieldPrefix,
 }
 
+impl SchemaProperty {
+    /// Rust type of the property, wrapped in `Option` when the field may be missing.
+    pub fn rust_type(&self) -> String {
+        match self.is_optional && self.default_value.is_none() {
+            true => format!("Option<{}>", self.field_type),
+            false => self.field_type.to_string(),
+        }
+    }
+
+    /// TypeScript member declaration, marked with `?` when the field may be missing.
+    pub fn ts_member(&self) -> String {
+        let ts_type = ts_type(&self.field_type);
+        match self.is_optional && self.default_value.is_none() {
+            true => format!("{}?: {}", self.name, ts_type),
+            false => format!("{}: {}", self.name, ts_type),
+        }
+    }
+}
+
+fn ts_type(field_type: &GeneratedType) -> String {
+    match field_type {
+        GeneratedType::RustType(t) => t.to_ts(),
+        // enum and struct fields refer to the generated type by name
+        GeneratedType::Variable(name) => name.to_string(),
+        GeneratedType::Vec(inner) => format!("Array<{}>", ts_type(inner)),
+        // object fields are stored as a nested `Value::Object`
+        GeneratedType::Object(_) => "Record<string, any>".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,12 +268,14 @@ mod tests {
                     name: "name".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "age".to_string(),
                     field_type: GeneratedType::RustType(RustType::U32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -187,12 +308,14 @@ mod tests {
                     name: "email".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "active".to_string(),
                     field_type: GeneratedType::RustType(RustType::Bool),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -214,12 +337,14 @@ mod tests {
                     name: "count".to_string(),
                     field_type: GeneratedType::RustType(RustType::I32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "score".to_string(),
                     field_type: GeneratedType::RustType(RustType::F64),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -230,6 +355,98 @@ mod tests {
         assert!(output.contains("pub score: f64,"));
     }
 
+    #[test]
+    fn test_enum_schema_display() {
+        let schema = EnumSchema {
+            name: "Status".to_string(),
+            variants: vec!["Active".to_string(), "Suspended".to_string()],
+        };
+
+        let output = format!("{}", schema);
+        assert!(output.contains("pub enum Status {"));
+        assert!(output.contains("    Active,"));
+        assert!(output.contains(
+            "pub const VARIANTS: &'static [&'static str] = &[\"Active\", \"Suspended\"];"
+        ));
+        assert!(output.contains("impl From<Status> for Value {"));
+        assert!(output.contains("enums::variant_value(v as usize, Status::VARIANTS.len())"));
+        assert!(output.contains("impl PartialEq<Status> for Value {"));
+        assert!(output.contains("enums::variant_index(self) == Some(*other as usize)"));
+        assert_eq!(
+            schema.to_typescript(),
+            "type Status = \"Active\" | \"Suspended\";\n"
+        );
+    }
+
+    #[test]
+    fn test_struct_schema_display() {
+        let schema = StructSchema {
+            name: "Address".to_string(),
+            properties: vec![
+                SchemaProperty {
+                    name: "city".to_string(),
+                    field_type: GeneratedType::RustType(RustType::String),
+                    default_value: None,
+                    is_optional: false,
+                    is_index: FieldPrefix::Empty,
+                },
+                SchemaProperty {
+                    name: "zip".to_string(),
+                    field_type: GeneratedType::RustType(RustType::U32),
+                    default_value: None,
+                    is_optional: true,
+                    is_index: FieldPrefix::Empty,
+                },
+            ],
+        };
+
+        let output = format!("{}", schema);
+        assert!(
+            output
+                .contains("#[derive(Serialize, Deserialize, Debug, Clone)]\npub struct Address {")
+        );
+        assert!(output.contains("pub zip: Option<u32>,"));
+        assert!(output.contains("impl From<Address> for Value {"));
+        assert!(output.contains("(\"city\".to_string(), Value::from(v.city)),"));
+
+        let output = schema.to_typescript();
+        assert!(output.contains("interface Address {"));
+        assert!(output.contains("zip?: number;"));
+    }
+
+    #[test]
+    fn test_node_schema_with_optional_property() {
+        let schema = NodeSchema {
+            name: "User".to_string(),
+            properties: vec![
+                SchemaProperty {
+                    name: "nickname".to_string(),
+                    field_type: GeneratedType::RustType(RustType::String),
+                    default_value: None,
+                    is_optional: true,
+                    is_index: FieldPrefix::Empty,
+                },
+                SchemaProperty {
+                    name: "age".to_string(),
+                    field_type: GeneratedType::RustType(RustType::U32),
+                    default_value: Some(GeneratedValue::Primitive(
+                        crate::helixc::generator::utils::GenRef::Std("0".to_string()),
+                    )),
+                    is_optional: true,
+                    is_index: FieldPrefix::Empty,
+                },
+            ],
+        };
+
+        let output = format!("{}", schema);
+        assert!(output.contains("pub nickname: Option<String>,"));
+        assert!(output.contains("pub age: u32,"));
+
+        let output = schema.to_typescript();
+        assert!(output.contains("nickname?: string;"));
+        assert!(output.contains("age: number;"));
+    }
+
     // ============================================================================
     // EdgeSchema Tests
     // ============================================================================
@@ -244,6 +461,7 @@ mod tests {
                 name: "since".to_string(),
                 field_type: GeneratedType::RustType(RustType::U32),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -280,6 +498,7 @@ mod tests {
                 name: "role".to_string(),
                 field_type: GeneratedType::RustType(RustType::String),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -302,12 +521,14 @@ mod tests {
                     name: "rating".to_string(),
                     field_type: GeneratedType::RustType(RustType::F32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "comment".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -330,6 +551,7 @@ mod tests {
                 name: "metadata".to_string(),
                 field_type: GeneratedType::RustType(RustType::String),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -360,12 +582,14 @@ mod tests {
                     name: "source".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "chunk_index".to_string(),
                     field_type: GeneratedType::RustType(RustType::U32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -387,6 +611,7 @@ mod tests {
                 name: "is_normalized".to_string(),
                 field_type: GeneratedType::RustType(RustType::Bool),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
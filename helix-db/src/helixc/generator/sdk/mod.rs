@@ -0,0 +1,979 @@
+//! Typed clients for the queries of a project.
+//!
+//! The latest schema and the analyzed queries are first described independently of the
+//! target language: one model per schema item and per query input and output, with the
+//! anonymous objects of projections hoisted into models of their own. Each language then
+//! prints that description as a client with one function per query.
+//!
+//! Queries are called like the gateway expects: a `POST` to `/{query_name}` with a JSON body,
+//! sending the API key of the instance, when there is one, in the `x-api-key` header.
+
+use crate::{
+    helixc::{
+        analyzer::{ExprType, analyze_object_types, types::Type},
+        parser::{
+            errors::ParserError,
+            location::Loc,
+            types::{
+                Expression, ExpressionType, FieldAddition, FieldType, FieldValueType, Object,
+                ReturnType, Schema, Source, StartNode, StepType, Traversal,
+            },
+        },
+    },
+    protocol::value::Value,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+mod python;
+mod rust;
+mod typescript;
+
+/// URL of an instance running locally with the default port
+pub const DEFAULT_URL: &str = "http://localhost:6969";
+/// Header carrying the API key of an instance
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// Format of the request and response bodies, sent as both `Content-Type` and `Accept`
+pub const CONTENT_TYPE: &str = "application/json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    TypeScript,
+    Python,
+    Rust,
+}
+
+impl Language {
+    /// Name of the file the client is written to
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Language::TypeScript => "client.ts",
+            Language::Python => "client.py",
+            Language::Rust => "client.rs",
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ts" | "typescript" => Ok(Language::TypeScript),
+            "py" | "python" => Ok(Language::Python),
+            "rs" | "rust" => Ok(Language::Rust),
+            _ => Err(format!(
+                "unsupported language '{s}', expected ts, py or rust"
+            )),
+        }
+    }
+}
+
+/// Type of a value sent to or returned by a query
+#[derive(Debug, Clone, PartialEq)]
+pub enum SdkType {
+    String,
+    Boolean,
+    /// Integer, with the name of its Rust type, e.g. `i32`
+    Integer(&'static str),
+    /// Floating point number, with the name of its Rust type
+    Float(&'static str),
+    /// RFC 3339 date, sent as a string
+    Date,
+    /// UUID of an item, sent as a string
+    Id,
+    Array(Box<SdkType>),
+    /// Anonymous object, only found before the models are hoisted
+    Object(Vec<SdkField>),
+    /// Model or enum declared by the client
+    Named(String),
+    /// Value whose shape is only known at runtime
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdkField {
+    pub name: String,
+    pub ty: SdkType,
+    /// Whether an input field may be left out, or an output field may be null
+    pub optional: bool,
+}
+
+impl SdkField {
+    fn new(name: impl Into<String>, ty: SdkType) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            optional: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SdkModel {
+    pub name: String,
+    pub fields: Vec<SdkField>,
+    /// Whether the model is sent to the instance rather than returned by it
+    pub is_input: bool,
+    /// Whether the model is both sent to and returned by the instance, like the structs of the
+    /// schema
+    pub is_shared: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SdkEnum {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SdkQuery {
+    /// Name of the query, which is also its route
+    pub name: String,
+    /// Model of the request body, if the query takes parameters
+    pub input: Option<String>,
+    /// Model of the response body
+    pub output: String,
+}
+
+/// Language independent description of a client
+#[derive(Debug, Clone, Default)]
+pub struct Sdk {
+    pub enums: Vec<SdkEnum>,
+    /// Models in dependency order, every model coming after the ones it refers to
+    pub models: Vec<SdkModel>,
+    pub queries: Vec<SdkQuery>,
+}
+
+/// Generates the client for the queries of the source in the given language.
+///
+/// The source is expected to have been analyzed without errors.
+pub fn generate_sdk(src: &Source, language: Language) -> Result<String, ParserError> {
+    let sdk = Sdk::from_source(src)?;
+    Ok(match language {
+        Language::TypeScript => typescript::print(&sdk),
+        Language::Python => python::print(&sdk),
+        Language::Rust => rust::print(&sdk),
+    })
+}
+
+impl Sdk {
+    /// Describes the latest schema and the queries of the source
+    pub fn from_source(src: &Source) -> Result<Sdk, ParserError> {
+        let (_, expr_types, object_types) = analyze_object_types(src)?;
+        let mut builder = Builder {
+            schema: src.get_latest_schema()?,
+            expr_types: index_types(expr_types),
+            object_types: index_types(object_types),
+            taken: HashSet::new(),
+            sdk: Sdk::default(),
+        };
+        builder.schema_models();
+        // in the order they are written, for the client to be stable between runs
+        let mut queries = src.queries.iter().collect::<Vec<_>>();
+        queries.sort_by_key(|q| (q.loc.filepath.clone(), q.loc.start.line, q.loc.start.column));
+        for query in queries {
+            builder.query(query);
+        }
+        Ok(builder.sdk)
+    }
+}
+
+/// Location of an expression or step, as recorded by the analyzer
+type LocKey = (Option<String>, usize, usize, usize, usize);
+
+fn loc_key(filepath: &Option<String>, loc: &Loc) -> LocKey {
+    (
+        filepath.clone(),
+        loc.start.line,
+        loc.start.column,
+        loc.end.line,
+        loc.end.column,
+    )
+}
+
+fn index_types(types: Vec<ExprType>) -> HashMap<LocKey, Type> {
+    types
+        .into_iter()
+        .map(|(loc, ty)| (loc_key(&loc.filepath, &loc), ty))
+        .collect()
+}
+
+struct Builder<'a> {
+    schema: &'a Schema,
+    expr_types: HashMap<LocKey, Type>,
+    object_types: HashMap<LocKey, Type>,
+    /// Names of the enums and models declared so far
+    taken: HashSet<String>,
+    sdk: Sdk,
+}
+
+impl<'a> Builder<'a> {
+    fn schema_models(&mut self) {
+        for schema in &self.schema.enum_schemas {
+            self.taken.insert(schema.name.1.clone());
+            self.sdk.enums.push(SdkEnum {
+                name: schema.name.1.clone(),
+                variants: schema.variants.iter().map(|(_, v)| v.clone()).collect(),
+            });
+        }
+        for schema in &self.schema.struct_schemas {
+            self.struct_model(&schema.name.1, &mut Vec::new());
+        }
+        for schema in &self.schema.node_schemas {
+            let fields = self.item_fields(&schema.name.1).unwrap_or_default();
+            self.add_model(&schema.name.1, fields, false);
+        }
+        for schema in &self.schema.edge_schemas {
+            let fields = self.item_fields(&schema.name.1).unwrap_or_default();
+            self.add_model(&schema.name.1, fields, false);
+        }
+        for schema in &self.schema.vector_schemas {
+            let fields = self.item_fields(&schema.name).unwrap_or_default();
+            self.add_model(&schema.name, fields, false);
+        }
+    }
+
+    /// Declares the model of a struct after the structs its fields refer to
+    fn struct_model(&mut self, name: &str, seen: &mut Vec<String>) {
+        let Some(schema) = self.schema.struct_schemas.iter().find(|s| s.name.1 == name) else {
+            return;
+        };
+        if self.taken.contains(name) || seen.iter().any(|s| s == name) {
+            return;
+        }
+        seen.push(name.to_string());
+        for field in &schema.fields {
+            let mut ty = &field.field_type;
+            while let FieldType::Array(inner) = ty {
+                ty = inner;
+            }
+            if let FieldType::Identifier(nested) = ty {
+                self.struct_model(nested, seen);
+            }
+        }
+        let fields = schema
+            .fields
+            .iter()
+            .map(|f| SdkField {
+                name: f.name.clone(),
+                ty: self.field_type(&f.field_type, false),
+                optional: f.is_optional,
+            })
+            .collect();
+        self.add_model(name, fields, false);
+        if let Some(model) = self.sdk.models.last_mut() {
+            model.is_shared = true;
+        }
+    }
+
+    fn query(&mut self, query: &crate::helixc::parser::types::Query) {
+        let name = pascal_case(&query.name);
+        let input = match query.parameters.is_empty() {
+            true => None,
+            false => {
+                let fields = query
+                    .parameters
+                    .iter()
+                    .map(|param| SdkField {
+                        name: param.name.1.clone(),
+                        ty: self.field_type(&param.param_type.1, true),
+                        optional: param.is_optional,
+                    })
+                    .collect();
+                Some(self.add_model(&format!("{name}Input"), fields, true))
+            }
+        };
+
+        let file = &query.loc.filepath;
+        let mut fields: Vec<SdkField> = Vec::new();
+        for ret in &query.return_values {
+            for field in self.return_fields(ret, file) {
+                // a later value under the same key replaces the earlier one in the response
+                fields.retain(|f| f.name != field.name);
+                fields.push(field);
+            }
+        }
+        let output = self.add_model(&format!("{name}Output"), fields, false);
+
+        self.sdk.queries.push(SdkQuery {
+            name: query.name.clone(),
+            input,
+            output,
+        });
+    }
+
+    /// Fields an item of the schema is returned with, the implicit ones first
+    fn item_fields(&self, label: &str) -> Option<Vec<SdkField>> {
+        let (mut fields, properties) = if let Some(node) =
+            self.schema.node_schemas.iter().find(|n| n.name.1 == label)
+        {
+            (implicit_fields(&["id", "label"]), &node.fields)
+        } else if let Some(edge) = self.schema.edge_schemas.iter().find(|e| e.name.1 == label) {
+            let properties = match &edge.properties {
+                Some(properties) => properties,
+                None => return Some(implicit_fields(&["id", "label", "from_node", "to_node"])),
+            };
+            (
+                implicit_fields(&["id", "label", "from_node", "to_node"]),
+                properties,
+            )
+        } else if let Some(vector) = self.schema.vector_schemas.iter().find(|v| v.name == label) {
+            (
+                implicit_fields(&["id", "label", "data", "score"]),
+                &vector.fields,
+            )
+        } else {
+            return None;
+        };
+        for field in properties {
+            if fields.iter().any(|f| f.name == field.name) {
+                continue;
+            }
+            fields.push(SdkField {
+                name: field.name.clone(),
+                ty: self.field_type(&field.field_type, false),
+                optional: field.is_nullable(),
+            });
+        }
+        Some(fields)
+    }
+
+    /// Fields the items of a type are returned with, including the properties set by the
+    /// step that produced them
+    fn type_fields(&self, ty: &Type) -> Option<Vec<SdkField>> {
+        let mut fields = self.item_fields(item_label(ty)?)?;
+        for (name, field_type) in ty.computed_properties() {
+            // the step overwrites a schema field of the same name
+            fields.retain(|f| f.name != name);
+            fields.push(SdkField::new(name, self.field_type(field_type, false)));
+        }
+        Some(fields)
+    }
+
+    fn field_type(&self, field_type: &FieldType, is_input: bool) -> SdkType {
+        match field_type {
+            FieldType::String => SdkType::String,
+            FieldType::F32 => SdkType::Float("f32"),
+            FieldType::F64 => SdkType::Float("f64"),
+            FieldType::I8 => SdkType::Integer("i8"),
+            FieldType::I16 => SdkType::Integer("i16"),
+            FieldType::I32 => SdkType::Integer("i32"),
+            FieldType::I64 => SdkType::Integer("i64"),
+            FieldType::U8 => SdkType::Integer("u8"),
+            FieldType::U16 => SdkType::Integer("u16"),
+            FieldType::U32 => SdkType::Integer("u32"),
+            FieldType::U64 => SdkType::Integer("u64"),
+            FieldType::U128 => SdkType::Integer("u128"),
+            FieldType::Boolean => SdkType::Boolean,
+            FieldType::Uuid => SdkType::Id,
+            FieldType::Date => SdkType::Date,
+            FieldType::Array(inner) => SdkType::Array(Box::new(self.field_type(inner, is_input))),
+            FieldType::Identifier(name) => match self.taken.contains(name) {
+                true => SdkType::Named(name.clone()),
+                false => SdkType::Any,
+            },
+            FieldType::Object(fields) => SdkType::Object(sorted_fields(
+                fields
+                    .iter()
+                    .map(|(name, ty)| SdkField::new(name, self.field_type(ty, is_input))),
+            )),
+            // embeddings are given as the vector, and stored as the id of the vector item
+            FieldType::Embedding(_) => match is_input {
+                true => SdkType::Array(Box::new(SdkType::Float("f64"))),
+                false => SdkType::Id,
+            },
+        }
+    }
+
+    /// Fields a return value adds to the response, named like the generated handlers do
+    fn return_fields(&self, ret: &ReturnType, file: &Option<String>) -> Vec<SdkField> {
+        match ret {
+            ReturnType::Expression(expr) => {
+                let name = match &expr.expr {
+                    ExpressionType::Empty => return Vec::new(),
+                    ExpressionType::Identifier(name) => name.clone(),
+                    ExpressionType::Traversal(tr) => match &tr.start {
+                        StartNode::Identifier(name) => name.clone(),
+                        _ => "data".to_string(),
+                    },
+                    _ => "data".to_string(),
+                };
+                vec![SdkField::new(name, self.expr_type(expr, file))]
+            }
+            // arrays and objects of plain expressions are flattened into the response
+            ReturnType::Array(values)
+                if values
+                    .iter()
+                    .all(|v| matches!(v, ReturnType::Expression(_))) =>
+            {
+                values
+                    .iter()
+                    .flat_map(|value| self.return_fields(value, file))
+                    .collect()
+            }
+            ReturnType::Object(values)
+                if values
+                    .values()
+                    .all(|v| matches!(v, ReturnType::Expression(_))) =>
+            {
+                let mut values = values.iter().collect::<Vec<_>>();
+                values.sort_by_key(|(key, _)| key.as_str());
+                values
+                    .into_iter()
+                    .flat_map(|(_, value)| self.return_fields(value, file))
+                    .collect()
+            }
+            // nested ones are returned whole, arrays under a `data` key
+            ReturnType::Array(_) => vec![SdkField::new(
+                "response",
+                SdkType::Object(vec![SdkField::new("data", self.literal_type(ret, file))]),
+            )],
+            ReturnType::Object(_) => vec![SdkField::new("response", self.literal_type(ret, file))],
+            ReturnType::Empty => Vec::new(),
+        }
+    }
+
+    /// Type of a value nested in a returned object or array literal
+    fn literal_type(&self, ret: &ReturnType, file: &Option<String>) -> SdkType {
+        match ret {
+            ReturnType::Expression(expr) => match &expr.expr {
+                ExpressionType::Identifier(_) | ExpressionType::Traversal(_) => {
+                    self.expr_type(expr, file)
+                }
+                _ => SdkType::Any,
+            },
+            ReturnType::Object(values) => SdkType::Object(sorted_fields(
+                values
+                    .iter()
+                    .map(|(key, value)| SdkField::new(key, self.literal_type(value, file))),
+            )),
+            ReturnType::Array(values) => {
+                let types = values
+                    .iter()
+                    .map(|value| self.literal_type(value, file))
+                    .collect::<Vec<_>>();
+                match types.split_first() {
+                    Some((first, rest)) if rest.iter().all(|ty| ty == first) => {
+                        SdkType::Array(Box::new(first.clone()))
+                    }
+                    _ => SdkType::Array(Box::new(SdkType::Any)),
+                }
+            }
+            ReturnType::Empty => SdkType::Any,
+        }
+    }
+
+    fn expr_type(&self, expr: &Expression, file: &Option<String>) -> SdkType {
+        let ty = self.expr_types.get(&loc_key(file, &expr.loc));
+        match &expr.expr {
+            ExpressionType::Traversal(tr) => self.traversal_type(tr, ty, file),
+            _ => ty.map_or(SdkType::Any, |ty| self.sdk_type(ty)),
+        }
+    }
+
+    /// Type of a traversal, given the type the analyzer inferred for it
+    fn traversal_type(&self, tr: &Traversal, ty: Option<&Type>, file: &Option<String>) -> SdkType {
+        let projected = match tr.steps.last().map(|step| &step.step) {
+            Some(StepType::Object(obj)) => self.projection(obj, false, file),
+            Some(StepType::Closure(closure)) => self.projection(&closure.object, true, file),
+            Some(StepType::Exclude(exclude)) => ty.and_then(|ty| {
+                let fields = self.type_fields(ty)?;
+                let fields = fields
+                    .into_iter()
+                    .filter(|f| !exclude.fields.iter().any(|(_, key)| *key == f.name))
+                    .collect();
+                Some(collect_like(ty, SdkType::Object(fields)))
+            }),
+            _ => None,
+        };
+        match projected {
+            Some(projected) => projected,
+            None => ty.map_or(SdkType::Any, |ty| self.sdk_type(ty)),
+        }
+    }
+
+    /// Type of the items an object step builds, collected like the items it reads from
+    fn projection(&self, obj: &Object, is_closure: bool, file: &Option<String>) -> Option<SdkType> {
+        let source = self.object_types.get(&loc_key(file, &obj.loc))?;
+        let fields = self.type_fields(source)?;
+        // a single property is returned as is rather than in an object
+        if let [field] = obj.fields.as_slice()
+            && let FieldValueType::Identifier(name) = &field.value.value
+            && !is_closure
+            && !obj.should_spread
+            && !name.contains('.')
+        {
+            let ty = property_type(&fields, name).ty;
+            return Some(collect_like(source, ty));
+        }
+
+        let mut projected = obj
+            .fields
+            .iter()
+            .map(|field| self.projected_field(field, &fields, file))
+            .collect::<Vec<_>>();
+        if obj.should_spread {
+            for field in fields {
+                if !projected.iter().any(|f| f.name == field.name) {
+                    projected.push(field);
+                }
+            }
+        }
+        Some(collect_like(source, SdkType::Object(projected)))
+    }
+
+    fn projected_field(
+        &self,
+        field: &FieldAddition,
+        item_fields: &[SdkField],
+        file: &Option<String>,
+    ) -> SdkField {
+        let (ty, optional) = match &field.value.value {
+            FieldValueType::Identifier(path) if path.contains('.') => (SdkType::Any, true),
+            FieldValueType::Identifier(name) => {
+                let field = property_type(item_fields, name);
+                (field.ty, field.optional)
+            }
+            FieldValueType::Traversal(tr) => {
+                let ty = self.expr_types.get(&loc_key(file, &tr.loc));
+                (self.traversal_type(tr, ty, file), false)
+            }
+            FieldValueType::Expression(expr) => match &expr.expr {
+                // `{years: age}` renames a property of the item
+                ExpressionType::Identifier(name) if item_fields.iter().any(|f| f.name == *name) => {
+                    let field = property_type(item_fields, name);
+                    (field.ty, field.optional)
+                }
+                ExpressionType::Traversal(tr) => {
+                    let ty = self.expr_types.get(&loc_key(file, &tr.loc));
+                    (self.traversal_type(tr, ty, file), false)
+                }
+                _ => (self.expr_type(expr, file), false),
+            },
+            FieldValueType::Literal(value) => (literal_value_type(value), false),
+            FieldValueType::Fields(_) | FieldValueType::Empty => (SdkType::Any, false),
+        };
+        SdkField {
+            name: field.key.clone(),
+            ty,
+            optional,
+        }
+    }
+
+    fn sdk_type(&self, ty: &Type) -> SdkType {
+        match ty {
+            Type::Node(label) | Type::Edge(label) | Type::Vector(label) => match label {
+                Some(label) if self.taken.contains(label) => SdkType::Named(label.clone()),
+                _ => SdkType::Any,
+            },
+            Type::Nodes(label) | Type::Edges(label) | Type::Vectors(label) => {
+                let item = match label {
+                    Some(label) if self.taken.contains(label) => SdkType::Named(label.clone()),
+                    _ => SdkType::Any,
+                };
+                SdkType::Array(Box::new(item))
+            }
+            Type::Scalar(field_type) => self.field_type(field_type, false),
+            Type::Object(fields) => SdkType::Object(sorted_fields(
+                fields
+                    .iter()
+                    .map(|(name, ty)| SdkField::new(name, self.sdk_type(ty))),
+            )),
+            Type::Array(inner) => SdkType::Array(Box::new(self.sdk_type(inner))),
+            Type::Anonymous(inner) | Type::Computed(inner, _) => self.sdk_type(inner),
+            Type::Boolean => SdkType::Boolean,
+            Type::Aggregate(_) | Type::Unknown => SdkType::Any,
+        }
+    }
+
+    /// Declares a model, hoisting the anonymous objects of its fields into models named
+    /// after it, and returns the name it was declared under
+    fn add_model(&mut self, name: &str, fields: Vec<SdkField>, is_input: bool) -> String {
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let nested = format!("{name}{}", pascal_case(&field.name));
+                SdkField {
+                    ty: self.hoist(&nested, field.ty, is_input),
+                    ..field
+                }
+            })
+            .collect();
+
+        let mut unique = name.to_string();
+        let mut n = 1;
+        while self.taken.contains(&unique) {
+            n += 1;
+            unique = format!("{name}{n}");
+        }
+        self.taken.insert(unique.clone());
+        self.sdk.models.push(SdkModel {
+            name: unique.clone(),
+            fields,
+            is_input,
+            is_shared: false,
+        });
+        unique
+    }
+
+    fn hoist(&mut self, name: &str, ty: SdkType, is_input: bool) -> SdkType {
+        match ty {
+            SdkType::Object(fields) => SdkType::Named(self.add_model(name, fields, is_input)),
+            SdkType::Array(inner) => SdkType::Array(Box::new(self.hoist(name, *inner, is_input))),
+            ty => ty,
+        }
+    }
+}
+
+fn implicit_fields(names: &[&str]) -> Vec<SdkField> {
+    names
+        .iter()
+        .map(|name| {
+            let ty = match *name {
+                "id" | "from_node" | "to_node" => SdkType::Id,
+                "data" => SdkType::Array(Box::new(SdkType::Float("f64"))),
+                "score" => SdkType::Float("f64"),
+                _ => SdkType::String,
+            };
+            SdkField::new(*name, ty)
+        })
+        .collect()
+}
+
+/// Field an item is returned with
+fn property_type(item_fields: &[SdkField], name: &str) -> SdkField {
+    match item_fields.iter().find(|f| f.name == name) {
+        Some(field) => field.clone(),
+        None => SdkField::new(name, SdkType::Any),
+    }
+}
+
+fn item_label(ty: &Type) -> Option<&str> {
+    match ty {
+        Type::Node(label)
+        | Type::Nodes(label)
+        | Type::Edge(label)
+        | Type::Edges(label)
+        | Type::Vector(label)
+        | Type::Vectors(label) => label.as_deref(),
+        Type::Anonymous(inner) | Type::Computed(inner, _) => item_label(inner),
+        _ => None,
+    }
+}
+
+/// Wraps the type of an item in an array when the items are read from a collection
+fn collect_like(source: &Type, item: SdkType) -> SdkType {
+    match source {
+        Type::Nodes(_) | Type::Edges(_) | Type::Vectors(_) => SdkType::Array(Box::new(item)),
+        Type::Anonymous(inner) | Type::Computed(inner, _) => collect_like(inner, item),
+        _ => item,
+    }
+}
+
+fn literal_value_type(value: &Value) -> SdkType {
+    match value {
+        Value::String(_) => SdkType::String,
+        Value::F32(_) => SdkType::Float("f32"),
+        Value::F64(_) => SdkType::Float("f64"),
+        Value::I8(_) => SdkType::Integer("i8"),
+        Value::I16(_) => SdkType::Integer("i16"),
+        Value::I32(_) => SdkType::Integer("i32"),
+        Value::I64(_) => SdkType::Integer("i64"),
+        Value::U8(_) => SdkType::Integer("u8"),
+        Value::U16(_) => SdkType::Integer("u16"),
+        Value::U32(_) => SdkType::Integer("u32"),
+        Value::U64(_) => SdkType::Integer("u64"),
+        Value::U128(_) => SdkType::Integer("u128"),
+        Value::Date(_) => SdkType::Date,
+        Value::Boolean(_) => SdkType::Boolean,
+        Value::Id(_) => SdkType::Id,
+        Value::Array(_) | Value::Object(_) | Value::Empty => SdkType::Any,
+    }
+}
+
+fn sorted_fields(fields: impl Iterator<Item = SdkField>) -> Vec<SdkField> {
+    let mut fields = fields.collect::<Vec<_>>();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
+
+/// `getUser` and `get_user` both become `GetUser`
+pub(super) fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// `getUserByID` becomes `get_user_by_id`
+pub(super) fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = match prev {
+                Some(prev) => {
+                    prev.is_lowercase()
+                        || prev.is_ascii_digit()
+                        || (prev.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+                }
+                None => false,
+            };
+            if starts_word && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(*c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helixc::parser::{HelixParser, write_to_temp_file};
+
+    fn sdk(source: &str) -> Sdk {
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        Sdk::from_source(&parsed).unwrap()
+    }
+
+    fn model<'a>(sdk: &'a Sdk, name: &str) -> &'a SdkModel {
+        sdk.models.iter().find(|m| m.name == name).unwrap()
+    }
+
+    fn field<'a>(model: &'a SdkModel, name: &str) -> &'a SdkField {
+        model.fields.iter().find(|f| f.name == name).unwrap()
+    }
+
+    const SCHEMA: &str = r#"
+        N::User { name: String, age: U32, bio: String DEFAULT "", nickname: String? }
+        E::Follows { From: User, To: User, Properties: { since: Date } }
+        V::Doc { title: String }
+    "#;
+
+    #[test]
+    fn test_schema_models() {
+        let sdk = sdk(&format!(
+            "{SCHEMA}\nQUERY getUser(id: ID) =>\n    u <- N<User>(id)\n    RETURN u"
+        ));
+
+        let user = model(&sdk, "User");
+        let names = user
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["id", "label", "name", "age", "bio", "nickname"]);
+        assert_eq!(field(user, "age").ty, SdkType::Integer("u32"));
+        assert!(field(user, "nickname").optional);
+        assert!(!field(user, "bio").optional);
+
+        let follows = model(&sdk, "Follows");
+        assert_eq!(field(follows, "from_node").ty, SdkType::Id);
+        assert_eq!(field(follows, "since").ty, SdkType::Date);
+
+        let doc = model(&sdk, "Doc");
+        assert_eq!(
+            field(doc, "data").ty,
+            SdkType::Array(Box::new(SdkType::Float("f64")))
+        );
+    }
+
+    #[test]
+    fn test_query_input_and_output() {
+        let sdk = sdk(&format!(
+            "{SCHEMA}\nQUERY getUser(id: ID, limit?: I64) =>\n    u <- N<User>(id)\n    followers <- u::In<Follows>\n    RETURN u, followers"
+        ));
+
+        let query = &sdk.queries[0];
+        assert_eq!(query.name, "getUser");
+        assert_eq!(query.input.as_deref(), Some("GetUserInput"));
+        assert_eq!(query.output, "GetUserOutput");
+
+        let input = model(&sdk, "GetUserInput");
+        assert!(input.is_input);
+        assert_eq!(field(input, "id").ty, SdkType::Id);
+        assert!(field(input, "limit").optional);
+
+        let output = model(&sdk, "GetUserOutput");
+        assert_eq!(field(output, "u").ty, SdkType::Named("User".to_string()));
+        assert_eq!(
+            field(output, "followers").ty,
+            SdkType::Array(Box::new(SdkType::Named("User".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_projections_are_hoisted() {
+        let sdk = sdk(&format!(
+            "{SCHEMA}\nQUERY listUsers() =>\n    users <- N<User>\n    RETURN users::{{name, years: age}}, users::{{name}}"
+        ));
+
+        let query = &sdk.queries[0];
+        assert_eq!(query.input, None);
+        let output = model(&sdk, "ListUsersOutput");
+        assert_eq!(output.fields.len(), 1);
+        // the later value under the same key wins
+        assert_eq!(
+            field(output, "users").ty,
+            SdkType::Array(Box::new(SdkType::String))
+        );
+
+        let sdk = self::sdk(&format!(
+            "{SCHEMA}\nQUERY listUsers() =>\n    users <- N<User>\n    RETURN users::{{name, years: age}}"
+        ));
+        let output = model(&sdk, "ListUsersOutput");
+        assert_eq!(
+            field(output, "users").ty,
+            SdkType::Array(Box::new(SdkType::Named("ListUsersOutputUsers".to_string())))
+        );
+        let projected = model(&sdk, "ListUsersOutputUsers");
+        assert_eq!(field(projected, "years").ty, SdkType::Integer("u32"));
+        // nested models come before the models using them
+        let position = |name: &str| sdk.models.iter().position(|m| m.name == name).unwrap();
+        assert!(position("ListUsersOutputUsers") < position("ListUsersOutput"));
+    }
+
+    #[test]
+    fn test_return_value_names() {
+        let sdk = sdk(&format!(
+            "{SCHEMA}\nQUERY counts() =>\n    users <- N<User>\n    RETURN N<User>::COUNT, users::{{name}}"
+        ));
+        let output = model(&sdk, "CountsOutput");
+        let names = output
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["data", "users"]);
+    }
+
+    #[test]
+    fn test_struct_models() {
+        let sdk = sdk(r#"
+            STRUCT Location { address: Address, floor: U8? }
+            STRUCT Address { city: String }
+            N::User { name: String, work: Location? }
+
+            QUERY moveUser(id: ID, work: Location) =>
+                u <- N<User>(id)::UPDATE({work: work})
+                RETURN u
+        "#);
+
+        let location = model(&sdk, "Location");
+        assert!(location.is_shared);
+        assert_eq!(
+            field(location, "address").ty,
+            SdkType::Named("Address".to_string())
+        );
+        assert!(field(location, "floor").optional);
+        assert_eq!(
+            field(model(&sdk, "User"), "work").ty,
+            SdkType::Named("Location".to_string())
+        );
+        assert_eq!(
+            field(model(&sdk, "MoveUserInput"), "work").ty,
+            SdkType::Named("Location".to_string())
+        );
+        // structs come before the models using them, whatever their declaration order
+        let position = |name: &str| sdk.models.iter().position(|m| m.name == name).unwrap();
+        assert!(position("Address") < position("Location"));
+        assert!(position("Location") < position("User"));
+    }
+
+    fn client(source: &str, language: Language) -> String {
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        generate_sdk(&parsed, language).unwrap()
+    }
+
+    const QUERIES: &str = r#"
+        QUERY getUser(id: ID, limit?: I64) =>
+            u <- N<User>(id)
+            RETURN u
+
+        QUERY follow(from: ID, to: ID) =>
+            e <- AddE<Follows>({since: "2024-01-01"})::From(from)::To(to)
+            RETURN e
+    "#;
+
+    #[test]
+    fn test_typescript_client() {
+        let client = client(&format!("{SCHEMA}{QUERIES}"), Language::TypeScript);
+        assert!(client.contains("  nickname: string | null;\n"));
+        assert!(
+            client.contains("export interface GetUserInput {\n  id: string;\n  limit?: number;\n}")
+        );
+        assert!(client.contains("  async getUser(input: GetUserInput): Promise<GetUserOutput> {\n    return this.request(\"getUser\", input);"));
+        assert!(client.contains("\"Content-Type\": \"application/json\""));
+    }
+
+    #[test]
+    fn test_struct_clients() {
+        let source = "STRUCT Address { city: String, zip: U32? }\nN::User { home: Address }";
+        let client = self::client(source, Language::TypeScript);
+        assert!(
+            client
+                .contains("export interface Address {\n  city: string;\n  zip?: number | null;\n}")
+        );
+        let client = self::client(source, Language::Rust);
+        assert!(
+            client
+                .contains("#[derive(Serialize, Deserialize, Debug, Clone)]\npub struct Address {")
+        );
+    }
+
+    #[test]
+    fn test_python_client() {
+        let client = client(&format!("{SCHEMA}{QUERIES}"), Language::Python);
+        assert!(client.contains("class User(TypedDict):\n"));
+        assert!(client.contains("    nickname: Optional[str]\n"));
+        // keywords are suffixed in arguments but sent under their own name
+        assert!(client.contains("    def follow(self, *, from_: str, to: str) -> FollowOutput:\n        return self._request(\"follow\", {\"from\": from_, \"to\": to})"));
+        assert!(!client.contains("class GetUserInput"));
+    }
+
+    #[test]
+    fn test_rust_client() {
+        let client = client(&format!("{SCHEMA}{QUERIES}"), Language::Rust);
+        assert!(client.contains("#[derive(Serialize, Debug, Clone)]\npub struct GetUserInput {"));
+        assert!(client.contains(
+            "    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub limit: Option<i64>,"
+        ));
+        assert!(client.contains("#[derive(Deserialize, Debug, Clone)]\npub struct User {"));
+        assert!(client.contains("    pub async fn get_user(&self, input: &GetUserInput) -> Result<GetUserOutput, HelixError> {"));
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(pascal_case("getUser"), "GetUser");
+        assert_eq!(pascal_case("get_user"), "GetUser");
+        assert_eq!(snake_case("getUser"), "get_user");
+        assert_eq!(snake_case("getUserByID"), "get_user_by_id");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("get_user"), "get_user");
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        assert_eq!("ts".parse(), Ok(Language::TypeScript));
+        assert_eq!("python".parse(), Ok(Language::Python));
+        assert_eq!("rs".parse(), Ok(Language::Rust));
+        assert!("go".parse::<Language>().is_err());
+    }
+}
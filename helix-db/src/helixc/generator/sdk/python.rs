@@ -0,0 +1,200 @@
+//! Python client, using only the standard library.
+
+use super::{API_KEY_HEADER, CONTENT_TYPE, DEFAULT_URL, Sdk, SdkType, snake_case};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+pub(super) fn print(sdk: &Sdk) -> String {
+    let mut out = String::new();
+    write!(
+        out,
+        r#""""Client for the queries of a Helix project.
+
+Generated by `helix generate sdk`, do not edit.
+"""
+
+from __future__ import annotations
+
+import json
+import urllib.error
+import urllib.request
+from typing import Any, List, Literal, Optional, TypedDict
+"#
+    )
+    .unwrap();
+
+    for schema in &sdk.enums {
+        let variants = schema
+            .variants
+            .iter()
+            .map(|v| format!("\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(out, "\n{} = Literal[{variants}]\n", schema.name).unwrap();
+    }
+
+    // the parameters of a query are taken as keyword arguments instead of its input model
+    let query_inputs = sdk
+        .queries
+        .iter()
+        .filter_map(|query| query.input.as_deref())
+        .collect::<Vec<_>>();
+    for model in &sdk.models {
+        if query_inputs.contains(&model.name.as_str()) {
+            continue;
+        }
+        let field_type = |ty: &SdkType, optional: bool| match optional {
+            true => format!("Optional[{}]", py_type(ty)),
+            false => py_type(ty),
+        };
+        if model.fields.iter().any(|f| !is_identifier(&f.name)) {
+            // names that are not identifiers need the functional syntax
+            writeln!(out, "\n\n{} = TypedDict(\"{}\", {{", model.name, model.name).unwrap();
+            for field in &model.fields {
+                writeln!(
+                    out,
+                    "    \"{}\": {},",
+                    field.name,
+                    field_type(&field.ty, field.optional)
+                )
+                .unwrap();
+            }
+            writeln!(out, "}})").unwrap();
+        } else {
+            writeln!(out, "\n\nclass {}(TypedDict):", model.name).unwrap();
+            for field in &model.fields {
+                writeln!(
+                    out,
+                    "    {}: {}",
+                    field.name,
+                    field_type(&field.ty, field.optional)
+                )
+                .unwrap();
+            }
+            if model.fields.is_empty() {
+                writeln!(out, "    pass").unwrap();
+            }
+        }
+    }
+
+    write!(
+        out,
+        r#"
+
+class HelixError(Exception):
+    """Error returned by the instance for a query."""
+
+    def __init__(self, status: int, message: str) -> None:
+        super().__init__(f"{{status}}: {{message}}")
+        self.status = status
+        self.message = message
+
+
+class HelixClient:
+    """Client for the queries of a Helix instance."""
+
+    def __init__(
+        self,
+        url: str = "{DEFAULT_URL}",
+        api_key: Optional[str] = None,
+        timeout: float = 60,
+    ) -> None:
+        self.url = url.rstrip("/")
+        self.api_key = api_key
+        self.timeout = timeout
+
+    def _request(self, query: str, body: Any) -> Any:
+        headers = {{"Content-Type": "{CONTENT_TYPE}", "Accept": "{CONTENT_TYPE}"}}
+        if self.api_key is not None:
+            headers["{API_KEY_HEADER}"] = self.api_key
+        request = urllib.request.Request(
+            f"{{self.url}}/{{query}}",
+            data=json.dumps(body).encode(),
+            headers=headers,
+            method="POST",
+        )
+        try:
+            with urllib.request.urlopen(request, timeout=self.timeout) as response:
+                return json.loads(response.read())
+        except urllib.error.HTTPError as e:
+            raise HelixError(e.code, e.read().decode(errors="replace")) from None
+"#
+    )
+    .unwrap();
+
+    for query in &sdk.queries {
+        let params = query
+            .input
+            .as_deref()
+            .and_then(|input| sdk.models.iter().find(|m| m.name == input))
+            .map(|model| model.fields.as_slice())
+            .unwrap_or_default();
+        let args = params
+            .iter()
+            .map(|param| match param.optional {
+                true => format!(
+                    ", {}: Optional[{}] = None",
+                    argument(&param.name),
+                    py_type(&param.ty)
+                ),
+                false => format!(", {}: {}", argument(&param.name), py_type(&param.ty)),
+            })
+            .collect::<String>();
+        let body = params
+            .iter()
+            .map(|param| format!("\"{}\": {}", param.name, argument(&param.name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let star = match params.is_empty() {
+            true => "",
+            false => ", *",
+        };
+        writeln!(
+            out,
+            "\n    def {}(self{star}{args}) -> {}:",
+            argument(&snake_case(&query.name)),
+            query.output
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        return self._request(\"{}\", {{{body}}})",
+            query.name
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn py_type(ty: &SdkType) -> String {
+    match ty {
+        SdkType::String | SdkType::Date | SdkType::Id => "str".to_string(),
+        SdkType::Integer(_) => "int".to_string(),
+        SdkType::Float(_) => "float".to_string(),
+        SdkType::Boolean => "bool".to_string(),
+        SdkType::Array(inner) => format!("List[{}]", py_type(inner)),
+        SdkType::Object(_) | SdkType::Any => "Any".to_string(),
+        SdkType::Named(name) => name.clone(),
+    }
+}
+
+/// Name of a keyword argument, with keywords suffixed by `_`
+fn argument(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("{name}_"),
+        false => name.to_string(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    !KEYWORDS.contains(&name)
+        && chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
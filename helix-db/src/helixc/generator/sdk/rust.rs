@@ -0,0 +1,210 @@
+//! Rust client, using `reqwest` with its `json` feature, `serde` and `serde_json`.
+
+use super::{API_KEY_HEADER, CONTENT_TYPE, DEFAULT_URL, Sdk, SdkType, snake_case};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+pub(super) fn print(sdk: &Sdk) -> String {
+    let mut out = String::new();
+    write!(
+        out,
+        r#"//! Client for the queries of a Helix project.
+//!
+//! Generated by `helix generate sdk`, do not edit.
+
+// types are named after the schema
+#![allow(non_camel_case_types)]
+
+use serde::{{Deserialize, Serialize, de::DeserializeOwned}};
+"#
+    )
+    .unwrap();
+
+    for schema in &sdk.enums {
+        writeln!(
+            out,
+            "\n#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]"
+        )
+        .unwrap();
+        writeln!(out, "pub enum {} {{", schema.name).unwrap();
+        for variant in &schema.variants {
+            writeln!(out, "    {variant},").unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    for model in &sdk.models {
+        let derive = match (model.is_shared, model.is_input) {
+            (true, _) => "Serialize, Deserialize",
+            (false, true) => "Serialize",
+            (false, false) => "Deserialize",
+        };
+        writeln!(out, "\n#[derive({derive}, Debug, Clone)]").unwrap();
+        writeln!(out, "pub struct {} {{", model.name).unwrap();
+        for field in &model.fields {
+            let name = snake_case(&field.name);
+            if name != field.name {
+                writeln!(out, "    #[serde(rename = \"{}\")]", field.name).unwrap();
+            }
+            let ty = rs_type(&field.ty);
+            match (field.optional, model.is_input || model.is_shared) {
+                (true, true) => {
+                    writeln!(
+                        out,
+                        "    #[serde(skip_serializing_if = \"Option::is_none\")]"
+                    )
+                    .unwrap();
+                    writeln!(out, "    pub {}: Option<{ty}>,", identifier(&name)).unwrap();
+                }
+                (true, false) => {
+                    writeln!(out, "    pub {}: Option<{ty}>,", identifier(&name)).unwrap()
+                }
+                (false, _) => writeln!(out, "    pub {}: {ty},", identifier(&name)).unwrap(),
+            }
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    write!(
+        out,
+        r#"
+#[derive(Debug)]
+pub enum HelixError {{
+    /// The request could not be sent or its response could not be read
+    Http(reqwest::Error),
+    /// The instance answered with an error
+    Status {{ status: u16, message: String }},
+}}
+
+impl std::fmt::Display for HelixError {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+            HelixError::Http(e) => write!(f, "{{e}}"),
+            HelixError::Status {{ status, message }} => write!(f, "{{status}}: {{message}}"),
+        }}
+    }}
+}}
+
+impl std::error::Error for HelixError {{}}
+
+impl From<reqwest::Error> for HelixError {{
+    fn from(e: reqwest::Error) -> Self {{
+        HelixError::Http(e)
+    }}
+}}
+
+/// Client for the queries of a Helix instance
+#[derive(Debug, Clone)]
+pub struct HelixClient {{
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}}
+
+impl Default for HelixClient {{
+    fn default() -> Self {{
+        Self::new("{DEFAULT_URL}")
+    }}
+}}
+
+impl HelixClient {{
+    pub fn new(url: impl Into<String>) -> Self {{
+        Self {{
+            client: reqwest::Client::new(),
+            url: url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+        }}
+    }}
+
+    /// Sends the API key of the instance in the `{API_KEY_HEADER}` header
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {{
+        self.api_key = Some(api_key.into());
+        self
+    }}
+
+    async fn request<I: Serialize + ?Sized, O: DeserializeOwned>(
+        &self,
+        query: &str,
+        input: &I,
+    ) -> Result<O, HelixError> {{
+        let mut request = self
+            .client
+            .post(format!("{{}}/{{query}}", self.url))
+            .header(reqwest::header::CONTENT_TYPE, "{CONTENT_TYPE}")
+            .header(reqwest::header::ACCEPT, "{CONTENT_TYPE}")
+            .body(serde_json::to_vec(input).expect("inputs serialize to JSON"));
+        if let Some(api_key) = &self.api_key {{
+            request = request.header("{API_KEY_HEADER}", api_key);
+        }}
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {{
+            return Err(HelixError::Status {{
+                status: status.as_u16(),
+                message: response.text().await?,
+            }});
+        }}
+        Ok(response.json().await?)
+    }}
+"#
+    )
+    .unwrap();
+
+    for query in &sdk.queries {
+        let function = identifier(&snake_case(&query.name));
+        match &query.input {
+            Some(input) => {
+                writeln!(
+                    out,
+                    "\n    pub async fn {function}(&self, input: &{input}) -> Result<{}, HelixError> {{",
+                    query.output
+                )
+                .unwrap();
+                writeln!(out, "        self.request(\"{}\", input).await", query.name).unwrap();
+            }
+            None => {
+                writeln!(
+                    out,
+                    "\n    pub async fn {function}(&self) -> Result<{}, HelixError> {{",
+                    query.output
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "        self.request(\"{}\", &serde_json::json!({{}})).await",
+                    query.name
+                )
+                .unwrap();
+            }
+        }
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+fn rs_type(ty: &SdkType) -> String {
+    match ty {
+        SdkType::String | SdkType::Date | SdkType::Id => "String".to_string(),
+        SdkType::Integer(name) | SdkType::Float(name) => name.to_string(),
+        SdkType::Boolean => "bool".to_string(),
+        SdkType::Array(inner) => format!("Vec<{}>", rs_type(inner)),
+        SdkType::Object(_) | SdkType::Any => "serde_json::Value".to_string(),
+        SdkType::Named(name) => name.clone(),
+    }
+}
+
+/// Keywords are written as raw identifiers, which serde names without the `r#`
+fn identifier(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("r#{name}"),
+        false => name.to_string(),
+    }
+}
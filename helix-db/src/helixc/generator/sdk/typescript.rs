@@ -0,0 +1,149 @@
+//! TypeScript client, using `fetch`.
+
+use super::{API_KEY_HEADER, CONTENT_TYPE, DEFAULT_URL, Sdk, SdkField, SdkType};
+use std::fmt::Write;
+
+pub(super) fn print(sdk: &Sdk) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Client for the queries of a Helix project.").unwrap();
+    writeln!(out, "// Generated by `helix generate sdk`, do not edit.").unwrap();
+
+    for schema in &sdk.enums {
+        let variants = schema
+            .variants
+            .iter()
+            .map(|v| format!("\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        write!(out, "\nexport type {} = {variants};\n", schema.name).unwrap();
+    }
+
+    for model in &sdk.models {
+        write!(out, "\nexport interface {} {{\n", model.name).unwrap();
+        for field in &model.fields {
+            writeln!(out, "  {};", member(field, model.is_input, model.is_shared)).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+
+    write!(
+        out,
+        r#"
+export class HelixError extends Error {{
+  constructor(
+    public readonly status: number,
+    message: string,
+  ) {{
+    super(message);
+    this.name = "HelixError";
+  }}
+}}
+
+export interface HelixClientOptions {{
+  /** URL of the instance, `{DEFAULT_URL}` by default */
+  url?: string;
+  /** API key of the instance, sent in the `{API_KEY_HEADER}` header */
+  apiKey?: string;
+}}
+
+export class HelixClient {{
+  private readonly url: string;
+  private readonly apiKey?: string;
+
+  constructor(options: HelixClientOptions = {{}}) {{
+    this.url = (options.url ?? "{DEFAULT_URL}").replace(/\/+$/, "");
+    this.apiKey = options.apiKey;
+  }}
+
+  private async request<T>(query: string, input: unknown): Promise<T> {{
+    const headers: Record<string, string> = {{
+      "Content-Type": "{CONTENT_TYPE}",
+      Accept: "{CONTENT_TYPE}",
+    }};
+    if (this.apiKey !== undefined) {{
+      headers["{API_KEY_HEADER}"] = this.apiKey;
+    }}
+    const response = await fetch(`${{this.url}}/${{query}}`, {{
+      method: "POST",
+      headers,
+      body: JSON.stringify(input),
+    }});
+    if (!response.ok) {{
+      throw new HelixError(response.status, await response.text());
+    }}
+    return (await response.json()) as T;
+  }}
+"#
+    )
+    .unwrap();
+
+    for query in &sdk.queries {
+        writeln!(out).unwrap();
+        match &query.input {
+            Some(input) => {
+                writeln!(
+                    out,
+                    "  async {}(input: {input}): Promise<{}> {{",
+                    query.name, query.output
+                )
+                .unwrap();
+                writeln!(out, "    return this.request(\"{}\", input);", query.name).unwrap();
+            }
+            None => {
+                writeln!(
+                    out,
+                    "  async {}(): Promise<{}> {{",
+                    query.name, query.output
+                )
+                .unwrap();
+                writeln!(out, "    return this.request(\"{}\", {{}});", query.name).unwrap();
+            }
+        }
+        writeln!(out, "  }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// Optional inputs may be left out, optional outputs are null, and optional fields of models
+/// going both ways may be either
+fn member(field: &SdkField, is_input: bool, is_shared: bool) -> String {
+    let name = match is_identifier(&field.name) {
+        true => field.name.clone(),
+        false => format!("\"{}\"", field.name),
+    };
+    let ty = ts_type(&field.ty);
+    match (field.optional, is_shared, is_input) {
+        (false, _, _) => format!("{name}: {ty}"),
+        (true, true, _) => format!("{name}?: {ty} | null"),
+        (true, false, true) => format!("{name}?: {ty}"),
+        (true, false, false) => format!("{name}: {ty} | null"),
+    }
+}
+
+fn ts_type(ty: &SdkType) -> String {
+    match ty {
+        SdkType::String | SdkType::Date | SdkType::Id => "string".to_string(),
+        SdkType::Integer(_) | SdkType::Float(_) => "number".to_string(),
+        SdkType::Boolean => "boolean".to_string(),
+        SdkType::Array(inner) => format!("Array<{}>", ts_type(inner)),
+        SdkType::Object(fields) => {
+            let members = fields
+                .iter()
+                .map(|field| member(field, false, false))
+                .collect::<Vec<_>>()
+                .join("; ");
+            format!("{{ {members} }}")
+        }
+        SdkType::Named(name) => name.clone(),
+        SdkType::Any => "unknown".to_string(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
@@ -1,7 +1,9 @@
 use core::fmt;
 use std::fmt::Display;
 
-use crate::helixc::generator::utils::{VecData, write_properties, write_secondary_indices};
+use crate::helixc::generator::utils::{
+    VecData, write_properties, write_properties_slice, write_secondary_indices,
+};
 
 use super::{
     bool_ops::BoExp,
@@ -18,6 +20,10 @@ pub enum SourceStep {
     AddE(AddE),
     /// Insert a vector
     AddV(AddV),
+    /// Update or insert a node matched by a secondary index
+    UpsertN(UpsertN),
+    /// Update or insert an edge between two nodes
+    UpsertE(UpsertE),
     /// Lookup a node by ID
     NFromID(NFromID),
     /// Lookup a node by index
@@ -26,6 +32,10 @@ pub enum SourceStep {
     NFromType(NFromType),
     /// Lookup an edge by ID
     EFromID(EFromID),
+    /// Lookup an edge by index
+    EFromIndex(EFromIndex),
+    /// Lookup the edges between two nodes
+    EBetween(EBetween),
     /// Lookup an edge by type
     EFromType(EFromType),
     /// Lookup a vector by ID
@@ -128,6 +138,63 @@ impl Display for AddE {
         }
     }
 }
+#[derive(Clone, Debug)]
+pub struct UpsertN {
+    /// Label of node
+    pub label: GenRef<String>,
+    /// Name of the secondary index to match on
+    pub index: GenRef<String>,
+    /// Value looked up in the index
+    pub key: GeneratedValue,
+    /// Properties of the node if it is inserted
+    pub properties: Option<Vec<(String, GeneratedValue)>>,
+    /// Properties written to the node if it already exists
+    pub update_properties: Vec<(String, GeneratedValue)>,
+    /// Names of properties to index on
+    pub secondary_indices: Option<Vec<String>>,
+}
+impl Display for UpsertN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "upsert_n({}, {}, &Value::from({}), {}, {}, {})",
+            self.label,
+            self.index,
+            self.key,
+            write_properties(&self.properties),
+            write_properties_slice(&Some(self.update_properties.clone())),
+            write_secondary_indices(&self.secondary_indices)
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UpsertE {
+    /// Label of edge
+    pub label: GenRef<String>,
+    /// Properties of the edge if it is inserted
+    pub properties: Option<Vec<(String, GeneratedValue)>>,
+    /// Properties written to the edge if it already exists
+    pub update_properties: Vec<(String, GeneratedValue)>,
+    /// From node ID
+    pub from: GeneratedValue,
+    /// To node ID
+    pub to: GeneratedValue,
+}
+impl Display for UpsertE {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "upsert_e({}, {}, {}, {}, {})",
+            self.label,
+            write_properties(&self.properties),
+            write_properties_slice(&Some(self.update_properties.clone())),
+            self.from,
+            self.to
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AddV {
     /// Vector to add
@@ -168,10 +235,19 @@ impl Display for NFromID {
 pub struct NFromType {
     /// Label of nodes to lookup
     pub label: GenRef<String>,
+    /// Cursor to resume the scan after
+    pub after: Option<GenRef<String>>,
 }
 impl Display for NFromType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "n_from_type({})", self.label)
+        match &self.after {
+            Some(after) => write!(
+                f,
+                "n_from_type_after({}, decode_cursor({after})?)",
+                self.label
+            ),
+            None => write!(f, "n_from_type({})", self.label),
+        }
     }
 }
 
@@ -190,14 +266,66 @@ impl Display for EFromID {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct EFromIndex {
+    /// Index to search against
+    pub index: GenRef<String>,
+    /// Key to search for in the index
+    pub key: GeneratedValue,
+    /// Label of edges to lookup - used for post filtering
+    pub label: GenRef<String>,
+    /// Cursor to resume the lookup after
+    pub after: Option<GenRef<String>>,
+}
+impl Display for EFromIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.after {
+            Some(after) => write!(
+                f,
+                "e_from_index_after({}, {}, {}, decode_cursor({after})?)",
+                self.label, self.index, self.key
+            ),
+            None => write!(
+                f,
+                "e_from_index({}, {}, {})",
+                self.label, self.index, self.key
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EBetween {
+    /// Label of edges to lookup
+    pub label: GenRef<String>,
+    /// From node ID
+    pub from: GeneratedValue,
+    /// To node ID
+    pub to: GeneratedValue,
+}
+impl Display for EBetween {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "e_between({}, {}, {})", self.label, self.from, self.to)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EFromType {
     /// Label of edges to lookup
     pub label: GenRef<String>,
+    /// Cursor to resume the scan after
+    pub after: Option<GenRef<String>>,
 }
 impl Display for EFromType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "e_from_type({})", self.label)
+        match &self.after {
+            Some(after) => write!(
+                f,
+                "e_from_type_after({}, decode_cursor({after})?)",
+                self.label
+            ),
+            None => write!(f, "e_from_type({})", self.label),
+        }
     }
 }
 
@@ -259,10 +387,14 @@ impl Display for SourceStep {
             SourceStep::AddN(add_n) => write!(f, "{add_n}"),
             SourceStep::AddE(add_e) => write!(f, "{add_e}"),
             SourceStep::AddV(add_v) => write!(f, "{add_v}"),
+            SourceStep::UpsertN(upsert_n) => write!(f, "{upsert_n}"),
+            SourceStep::UpsertE(upsert_e) => write!(f, "{upsert_e}"),
             SourceStep::NFromID(n_from_id) => write!(f, "{n_from_id}"),
             SourceStep::NFromIndex(n_from_index) => write!(f, "{n_from_index}"),
             SourceStep::NFromType(n_from_type) => write!(f, "{n_from_type}"),
             SourceStep::EFromID(e_from_id) => write!(f, "{e_from_id}"),
+            SourceStep::EFromIndex(e_from_index) => write!(f, "{e_from_index}"),
+            SourceStep::EBetween(e_between) => write!(f, "{e_between}"),
             SourceStep::EFromType(e_from_type) => write!(f, "{e_from_type}"),
             SourceStep::SearchVector(search_vector) => write!(f, "{search_vector}"),
             SourceStep::SearchBM25(search_bm25) => write!(f, "{search_bm25}"),
@@ -318,14 +450,23 @@ pub struct NFromIndex {
     pub key: GeneratedValue,
     /// Label of nodes to lookup - used for post filtering
     pub label: GenRef<String>,
+    /// Cursor to resume the lookup after
+    pub after: Option<GenRef<String>>,
 }
 
 impl Display for NFromIndex {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "n_from_index({}, {}, {})",
-            self.label, self.index, self.key
-        )
+        match &self.after {
+            Some(after) => write!(
+                f,
+                "n_from_index_after({}, {}, {}, decode_cursor({after})?)",
+                self.label, self.index, self.key
+            ),
+            None => write!(
+                f,
+                "n_from_index({}, {}, {})",
+                self.label, self.index, self.key
+            ),
+        }
     }
 }
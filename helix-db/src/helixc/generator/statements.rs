@@ -1,7 +1,10 @@
 use core::fmt;
 use std::fmt::Display;
 
-use crate::helixc::generator::{bool_ops::BoExp, traversal_steps::Traversal, utils::GenRef};
+use crate::helixc::generator::{
+    bool_ops::BoExp, scalar_functions::ScalarFunctionCallGen, traversal_steps::Traversal,
+    utils::GenRef,
+};
 
 
 
@@ -11,9 +14,12 @@ pub enum Statement {
     Drop(Drop),
     Traversal(Traversal),
     ForEach(ForEach),
+    IfElse(IfElse),
+    Coalesce(Coalesce),
     Literal(GenRef<String>),
     Identifier(GenRef<String>),
     BoExp(BoExp),
+    ScalarFunction(ScalarFunctionCallGen),
     Array(Vec<Statement>),
     Empty,
 }
@@ -24,9 +30,12 @@ impl Display for Statement {
             Statement::Drop(drop) => write!(f, "{drop}"),
             Statement::Traversal(traversal) => write!(f, "{traversal}"),
             Statement::ForEach(foreach) => write!(f, "{foreach}"),
+            Statement::IfElse(if_else) => write!(f, "{if_else}"),
+            Statement::Coalesce(coalesce) => write!(f, "{coalesce}"),
             Statement::Literal(literal) => write!(f, "{literal}"),
             Statement::Identifier(identifier) => write!(f, "{identifier}"),
             Statement::BoExp(bo) => write!(f, "{bo}"),
+            Statement::ScalarFunction(call) => write!(f, "{call}"),
             Statement::Array(array) => write!(f, "[{}]", array.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")),
             Statement::Empty => write!(f, ""),
         }
@@ -89,6 +98,50 @@ impl Display for ForEach {
     }
 }
 
+#[derive(Clone)]
+pub struct IfElse {
+    pub condition: Box<Statement>,
+    pub then_statements: Vec<Statement>,
+    pub else_statements: Option<Vec<Statement>>,
+}
+impl Display for IfElse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "if {} {{", self.condition)?;
+        for statement in &self.then_statements {
+            writeln!(f, "    {statement};")?;
+        }
+        match &self.else_statements {
+            // a lone nested if is an `ELSE IF` chain
+            Some(statements) if matches!(statements.as_slice(), [Statement::IfElse(_)]) => {
+                write!(f, "}} else {}", statements[0])
+            }
+            Some(statements) => {
+                writeln!(f, "}} else {{")?;
+                for statement in statements {
+                    writeln!(f, "    {statement};")?;
+                }
+                write!(f, "}}")
+            }
+            None => write!(f, "}}"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Coalesce {
+    pub value: Box<Statement>,
+    pub fallback: Box<Statement>,
+}
+impl Display for Coalesce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "match Value::from({}) {{ Value::Empty => Value::from({}), value => value }}",
+            self.value, self.fallback
+        )
+    }
+}
+
 #[derive(Clone)]
 pub enum ForVariable {
     ObjectDestructure(Vec<GenRef<String>>),
@@ -124,9 +177,29 @@ impl Display for ForLoopInVariable {
 #[derive(Clone)]
 pub struct Drop {
     pub expression: Traversal,
+    /// Fields of the dropped nodes holding embedded vectors, which are dropped with them
+    pub embeddings: Vec<String>,
 }
 impl Display for Drop {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.embeddings.is_empty() {
+            let embeddings = self
+                .embeddings
+                .iter()
+                .map(|field| format!("\"{field}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return write!(
+                f,
+                "Drop::drop_traversal_with_embeddings(
+                {}.collect::<Vec<_>>().into_iter(),
+                &[{embeddings}],
+                &db,
+                &mut txn,
+            )?;",
+                self.expression
+            );
+        }
         write!(
             f,
             "Drop::drop_traversal(
@@ -203,6 +276,55 @@ mod tests {
         assert!(output.contains("let result = computation"));
     }
 
+    // ============================================================================
+    // IfElse / Coalesce Tests
+    // ============================================================================
+
+    #[test]
+    fn test_if_else_display() {
+        let stmt = Statement::IfElse(IfElse {
+            condition: Box::new(Statement::Identifier(GenRef::Std("data.flag".to_string()))),
+            then_statements: vec![Statement::Identifier(GenRef::Std("a".to_string()))],
+            else_statements: Some(vec![Statement::Identifier(GenRef::Std("b".to_string()))]),
+        });
+        assert_eq!(
+            format!("{}", stmt),
+            "if data.flag {\n    a;\n} else {\n    b;\n}"
+        );
+    }
+
+    #[test]
+    fn test_if_else_if_chain_display() {
+        let nested = Statement::IfElse(IfElse {
+            condition: Box::new(Statement::Identifier(GenRef::Std("other".to_string()))),
+            then_statements: vec![Statement::Identifier(GenRef::Std("b".to_string()))],
+            else_statements: None,
+        });
+        let stmt = Statement::IfElse(IfElse {
+            condition: Box::new(Statement::Identifier(GenRef::Std("flag".to_string()))),
+            then_statements: vec![Statement::Identifier(GenRef::Std("a".to_string()))],
+            else_statements: Some(vec![nested]),
+        });
+        assert_eq!(
+            format!("{}", stmt),
+            "if flag {\n    a;\n} else if other {\n    b;\n}"
+        );
+    }
+
+    #[test]
+    fn test_coalesce_display() {
+        let stmt = Statement::Coalesce(Coalesce {
+            value: Box::new(Statement::Identifier(GenRef::Std(
+                "data.nickname.clone()".to_string(),
+            ))),
+            fallback: Box::new(Statement::Literal(GenRef::Literal("anon".to_string()))),
+        });
+        assert_eq!(
+            format!("{}", stmt),
+            "match Value::from(data.nickname.clone()) { Value::Empty => Value::from(\"anon\"), value => value }"
+        );
+    }
+
     // ============================================================================
     // ForLoopInVariable Tests
     // ============================================================================
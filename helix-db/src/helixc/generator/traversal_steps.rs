@@ -1,10 +1,12 @@
 use crate::helixc::{
     analyzer::types::Type,
     generator::utils::{VecData, write_properties_slice},
+    parser::types::{AggregateFunction, GraphAlgorithmType},
 };
 
 use super::{
     bool_ops::{BoExp, BoolOp},
+    scalar_functions::ScalarFunctionCallGen,
     source_steps::SourceStep,
     utils::{GenRef, GeneratedValue, Order, Separator},
 };
@@ -31,6 +33,8 @@ pub enum TraversalType {
     Mut,
     Empty,
     Update(Option<Vec<(String, GeneratedValue)>>),
+    /// Update whose values read the current properties of each updated item as `val`
+    UpdateWith(Vec<(String, GeneratedValue)>),
     /// Standalone - no G::new wrapper, just the source step (used for plural AddE)
     Standalone,
 }
@@ -91,9 +95,17 @@ pub struct Traversal {
     // Projection tracking
     pub has_object_step: bool,
     pub object_fields: Vec<String>,
+    /// Selected fields that are optional without a default, so may be missing
+    pub nullable_fields: Vec<String>,
     pub has_spread: bool,
     pub excluded_fields: Vec<String>,
     pub nested_traversals: std::collections::HashMap<String, NestedTraversalInfo>,
+    /// Remapped fields computed by a string or date function
+    pub computed_fields: std::collections::HashMap<String, ScalarFunctionCallGen>,
+    /// Remapped fields read from inside object properties, e.g. `city` for `address.city`
+    pub property_paths: std::collections::HashMap<String, String>,
+    /// Vectors embedded on the nodes written by an `AddN` or `UPDATE`
+    pub embeddings: Vec<SetEmbedding>,
     pub is_reused_variable: bool,
 }
 
@@ -131,6 +143,9 @@ impl Display for Traversal {
                 for step in &self.steps {
                     write!(f, "\n{step}")?;
                 }
+                for embedding in &self.embeddings {
+                    write!(f, "\n.{embedding}")?;
+                }
             }
 
             TraversalType::Standalone => {
@@ -155,6 +170,33 @@ impl Display for Traversal {
                     "G::new_mut_from_iter(&db, &mut txn, update_tr.iter().cloned(), &arena)",
                 )?;
                 write!(f, "\n    .update({})", write_properties_slice(properties))?;
+                for embedding in &self.embeddings {
+                    write!(f, "\n    .{embedding}")?;
+                }
+                write!(f, "\n    .collect_to_obj()?")?;
+                write!(f, "}}")?;
+            }
+            TraversalType::UpdateWith(properties) => {
+                write!(f, "{{")?;
+                write!(f, "let update_tr = G::new(&db, &txn, &arena)")?;
+                write!(f, "{}", self.source_step)?;
+                for step in &self.steps {
+                    write!(f, "\n{step}")?;
+                }
+                write!(f, "\n    .collect::<Result<Vec<_>, _>>()?;")?;
+                write!(
+                    f,
+                    "G::new_mut_from_iter(&db, &mut txn, update_tr.iter().cloned(), &arena)",
+                )?;
+                let values = properties
+                    .iter()
+                    .map(|(name, value)| format!("(\"{name}\", Value::from({value}))"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "\n    .update_with(|val| vec![{values}])")?;
+                for embedding in &self.embeddings {
+                    write!(f, "\n    .{embedding}")?;
+                }
                 write!(f, "\n    .collect_to_obj()?")?;
                 write!(f, "}}")?;
             }
@@ -173,9 +215,13 @@ impl Default for Traversal {
             should_collect: ShouldCollect::ToVec,
             has_object_step: false,
             object_fields: vec![],
+            nullable_fields: vec![],
             has_spread: false,
             excluded_fields: vec![],
             nested_traversals: std::collections::HashMap::new(),
+            computed_fields: std::collections::HashMap::new(),
+            property_paths: std::collections::HashMap::new(),
+            embeddings: vec![],
             is_reused_variable: false,
         }
     }
@@ -212,6 +258,7 @@ pub enum ReservedProp {
 pub enum Step {
     // graph steps
     Out(Out),
+    OutN(OutN),
     In(In),
     OutE(OutE),
     InE(InE),
@@ -225,6 +272,7 @@ pub enum Step {
 
     Where(Where),
     Range(Range),
+    Cursor,
     OrderBy(OrderBy),
     Dedup,
 
@@ -243,9 +291,16 @@ pub enum Step {
     ShortestPathDijkstras(ShortestPathDijkstras),
     ShortestPathBFS(ShortestPathBFS),
     ShortestPathAStar(ShortestPathAStar),
+    KShortestPaths(KShortestPaths),
+    AllPaths(AllPaths),
+    ShortestPathsFrom(ShortestPathsFrom),
+
+    // analytics
+    GraphAlgorithm(GraphAlgorithmStep),
 
     // search vector
     SearchVector(SearchVectorStep),
+    OwnerN(OwnerN),
 
     GroupBy(GroupBy),
 
@@ -278,11 +333,13 @@ impl Display for Step {
             },
 
             Step::Out(out) => write!(f, "{out}"),
+            Step::OutN(out_n) => write!(f, "{out_n}"),
             Step::In(in_) => write!(f, "{in_}"),
             Step::OutE(out_e) => write!(f, "{out_e}"),
             Step::InE(in_e) => write!(f, "{in_e}"),
             Step::Where(where_) => write!(f, "{where_}"),
             Step::Range(range) => write!(f, "{range}"),
+            Step::Cursor => write!(f, "next_cursor()"),
             Step::OrderBy(order_by) => write!(f, "{order_by}"),
             Step::BoolOp(bool_op) => write!(f, "{bool_op}"),
             Step::ShortestPath(shortest_path) => write!(f, "{shortest_path}"),
@@ -291,7 +348,12 @@ impl Display for Step {
             }
             Step::ShortestPathBFS(shortest_path_bfs) => write!(f, "{shortest_path_bfs}"),
             Step::ShortestPathAStar(shortest_path_astar) => write!(f, "{shortest_path_astar}"),
+            Step::KShortestPaths(k_shortest_paths) => write!(f, "{k_shortest_paths}"),
+            Step::AllPaths(all_paths) => write!(f, "{all_paths}"),
+            Step::ShortestPathsFrom(shortest_paths_from) => write!(f, "{shortest_paths_from}"),
+            Step::GraphAlgorithm(graph_algorithm) => write!(f, "{graph_algorithm}"),
             Step::SearchVector(search_vector) => write!(f, "{search_vector}"),
+            Step::OwnerN(owner_n) => write!(f, "{owner_n}"),
             Step::GroupBy(group_by) => write!(f, "{group_by}"),
             Step::AggregateBy(aggregate_by) => write!(f, "{aggregate_by}"),
             Step::RerankRRF(rerank_rrf) => write!(f, "{rerank_rrf}"),
@@ -311,18 +373,25 @@ impl Debug for Step {
             Step::FromV(_) => write!(f, "FromV"),
             Step::ToV(_) => write!(f, "ToV"),
             Step::Out(_) => write!(f, "Out"),
+            Step::OutN(_) => write!(f, "OutN"),
             Step::In(_) => write!(f, "In"),
             Step::OutE(_) => write!(f, "OutE"),
             Step::InE(_) => write!(f, "InE"),
             Step::Where(_) => write!(f, "Where"),
             Step::Range(_) => write!(f, "Range"),
+            Step::Cursor => write!(f, "Cursor"),
             Step::OrderBy(_) => write!(f, "OrderBy"),
             Step::BoolOp(_) => write!(f, "Bool"),
             Step::ShortestPath(_) => write!(f, "ShortestPath"),
             Step::ShortestPathDijkstras(_) => write!(f, "ShortestPathDijkstras"),
             Step::ShortestPathBFS(_) => write!(f, "ShortestPathBFS"),
             Step::ShortestPathAStar(_) => write!(f, "ShortestPathAStar"),
+            Step::KShortestPaths(_) => write!(f, "KShortestPaths"),
+            Step::AllPaths(_) => write!(f, "AllPaths"),
+            Step::ShortestPathsFrom(_) => write!(f, "ShortestPathsFrom"),
+            Step::GraphAlgorithm(graph_algorithm) => write!(f, "{}", graph_algorithm.algorithm),
             Step::SearchVector(_) => write!(f, "SearchVector"),
+            Step::OwnerN(_) => write!(f, "OwnerN"),
             Step::GroupBy(_) => write!(f, "GroupBy"),
             Step::AggregateBy(_) => write!(f, "AggregateBy"),
             Step::RerankRRF(_) => write!(f, "RerankRRF"),
@@ -371,12 +440,48 @@ pub struct Out {
     pub label: GenRef<String>,
     pub edge_type: EdgeType,
     pub get_vector_data: bool,
+    /// Cursor to resume after, only set for nodes
+    pub after: Option<GenRef<String>>,
 }
 impl Display for Out {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.edge_type {
-            EdgeType::Node => write!(f, "out_node({})", self.label),
-            EdgeType::Vec => write!(f, "out_vec({}, {})", self.label, self.get_vector_data),
+        match (&self.edge_type, &self.after) {
+            (EdgeType::Node, Some(after)) => write!(
+                f,
+                "out_node_after({}, decode_cursor({after})?)",
+                self.label
+            ),
+            (EdgeType::Node, None) => write!(f, "out_node({})", self.label),
+            (EdgeType::Vec, _) => write!(f, "out_vec({}, {})", self.label, self.get_vector_data),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OutN {
+    pub label: GenRef<String>,
+    pub min_depth: usize,
+    pub max_depth: usize,
+    pub emit_paths: bool,
+    pub until: Option<WhereRef>,
+}
+impl Display for OutN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.until {
+            Some(until) => {
+                write!(
+                    f,
+                    "out_n_until({}, {}, {}, {}, ",
+                    self.label, self.min_depth, self.max_depth, self.emit_paths
+                )?;
+                until.fmt_closure(f)?;
+                write!(f, ")")
+            }
+            None => write!(
+                f,
+                "out_n({}, {}, {}, {})",
+                self.label, self.min_depth, self.max_depth, self.emit_paths
+            ),
         }
     }
 }
@@ -386,12 +491,19 @@ pub struct In {
     pub label: GenRef<String>,
     pub edge_type: EdgeType,
     pub get_vector_data: bool,
+    /// Cursor to resume after, only set for nodes
+    pub after: Option<GenRef<String>>,
 }
 impl Display for In {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.edge_type {
-            EdgeType::Node => write!(f, "in_node({})", self.label),
-            EdgeType::Vec => write!(f, "in_vec({}, {})", self.label, self.get_vector_data),
+        match (&self.edge_type, &self.after) {
+            (EdgeType::Node, Some(after)) => write!(
+                f,
+                "in_node_after({}, decode_cursor({after})?)",
+                self.label
+            ),
+            (EdgeType::Node, None) => write!(f, "in_node({})", self.label),
+            (EdgeType::Vec, _) => write!(f, "in_vec({}, {})", self.label, self.get_vector_data),
         }
     }
 }
@@ -399,20 +511,30 @@ impl Display for In {
 #[derive(Clone)]
 pub struct OutE {
     pub label: GenRef<String>,
+    /// Cursor to resume after
+    pub after: Option<GenRef<String>>,
 }
 impl Display for OutE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "out_e({})", self.label)
+        match &self.after {
+            Some(after) => write!(f, "out_e_after({}, decode_cursor({after})?)", self.label),
+            None => write!(f, "out_e({})", self.label),
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct InE {
     pub label: GenRef<String>,
+    /// Cursor to resume after
+    pub after: Option<GenRef<String>>,
 }
 impl Display for InE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "in_e({})", self.label)
+        match &self.after {
+            Some(after) => write!(f, "in_e_after({}, decode_cursor({after})?)", self.label),
+            None => write!(f, "in_e({})", self.label),
+        }
     }
 }
 
@@ -431,8 +553,10 @@ impl Display for Where {
 pub struct WhereRef {
     pub expr: BoExp,
 }
-impl Display for WhereRef {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl WhereRef {
+    /// Writes the `|val, txn| ...` predicate closure on its own, so steps other than
+    /// `filter_ref` can take the same condition
+    pub fn fmt_closure(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Check if this is a simple property check that can be optimized
         if let BoExp::Expr(traversal) = &self.expr
             && let TraversalType::FromSingle(var) = &traversal.traversal_type
@@ -476,44 +600,41 @@ impl Display for WhereRef {
                         BoolOp::Neq(neq) => format!("{} != {}", value_expr, neq.right),
                         BoolOp::Contains(contains) => format!("{}{}", value_expr, contains),
                         BoolOp::IsIn(is_in) => format!("{}{}", value_expr, is_in),
+                        // reserved properties are always present
+                        BoolOp::IsNull(_) => "false".to_string(),
+                        // reserved properties are never arrays
+                        BoolOp::Any(_) | BoolOp::All(_) => "false".to_string(),
                     };
                     return write!(
                         f,
-                        "filter_ref(|val, txn|{{
+                        "|val, txn|{{
                 if let Ok(val) = val {{
                     Ok({})
                 }} else {{
                     Ok(false)
                 }}
-            }})",
+            }}",
                         bool_expr
                     );
                 }
 
                 // Handle PropertyFetch with BoolOp - use get_property
                 if let (Some(prop), Some(bool_op)) = (prop, bool_op) {
-                    let bool_expr = match bool_op {
-                        BoolOp::Gt(gt) => format!("{gt}"),
-                        BoolOp::Gte(gte) => format!("{gte}"),
-                        BoolOp::Lt(lt) => format!("{lt}"),
-                        BoolOp::Lte(lte) => format!("{lte}"),
-                        BoolOp::Eq(eq) => format!("{eq}"),
-                        BoolOp::Neq(neq) => format!("{neq}"),
-                        BoolOp::Contains(contains) => format!("v{contains}"),
-                        BoolOp::IsIn(is_in) => format!("v{is_in}"),
-                    };
+                    let bool_expr = bool_op.predicate();
                     return write!(
                         f,
-                        "filter_ref(|val, txn|{{
+                        "|val, txn|{{
                 if let Ok(val) = val {{
                     Ok(val
                     .get_property({})
-                    .map_or(false, |v| {}))
+                    .map_or({}, |v| {}))
                 }} else {{
                     Ok(false)
                 }}
-            }})",
-                        prop, bool_expr
+            }}",
+                        prop,
+                        bool_op.value_if_missing(),
+                        bool_expr
                     );
                 }
             }
@@ -522,18 +643,26 @@ impl Display for WhereRef {
         // Fall back to default (unoptimized) code generation
         write!(
             f,
-            "filter_ref(|val, txn|{{
+            "|val, txn|{{
                 if let Ok(val) = val {{
                     Ok({})
                 }} else {{
                     Ok(false)
                 }}
-            }})",
+            }}",
             self.expr
         )
     }
 }
 
+impl Display for WhereRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "filter_ref(")?;
+        self.fmt_closure(f)?;
+        write!(f, ")")
+    }
+}
+
 #[derive(Clone)]
 pub struct Range {
     pub start: GeneratedValue,
@@ -547,15 +676,42 @@ impl Display for Range {
 
 #[derive(Clone)]
 pub struct OrderBy {
+    pub keys: Vec<OrderKey>,
+    /// Set when the step is followed by a `RANGE`, so only the first `top_k` items are kept
+    pub top_k: Option<GeneratedValue>,
+}
+impl Display for OrderBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        // always sorted by `SortKey`s, so missing values are placed as the key asks
+        match &self.top_k {
+            None => write!(f, "order_by(&[{keys}])"),
+            Some(k) => write!(f, "order_by_top_k(&[{keys}], {k})"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OrderKey {
     pub property: GenRef<String>,
     pub order: Order,
+    pub nulls_first: bool,
 }
-impl Display for OrderBy {
+impl Display for OrderKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.order {
-            Order::Asc => write!(f, "order_by_asc({})", self.property),
-            Order::Desc => write!(f, "order_by_desc({})", self.property),
+            Order::Asc => write!(f, "SortKey::asc({})", self.property)?,
+            Order::Desc => write!(f, "SortKey::desc({})", self.property)?,
         }
+        if self.nulls_first {
+            write!(f, ".nulls_first()")?;
+        }
+        Ok(())
     }
 }
 
@@ -563,19 +719,28 @@ impl Display for OrderBy {
 pub struct GroupBy {
     pub should_count: bool,
     pub properties: Vec<GenRef<String>>,
+    pub aggregations: Vec<(String, AggregateFunction)>,
+    /// `(field, enum)` of the enum fields of the grouped items
+    pub enum_fields: Vec<(String, String)>,
 }
 impl Display for GroupBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "group_by(&[{}], {})",
-            self.properties
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(","),
-            self.should_count
-        )
+        write_decode_enums(f, &self.enum_fields)?;
+        let properties = self
+            .properties
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        match self.aggregations.is_empty() {
+            true => write!(f, "group_by(&[{}], {})", properties, self.should_count),
+            false => write!(
+                f,
+                "group_by_with(&[{}], &[{}])",
+                properties,
+                write_aggregations(&self.aggregations)
+            ),
+        }
     }
 }
 
@@ -583,22 +748,77 @@ impl Display for GroupBy {
 pub struct AggregateBy {
     pub should_count: bool,
     pub properties: Vec<GenRef<String>>,
+    pub aggregations: Vec<(String, AggregateFunction)>,
+    /// `(field, enum)` of the enum fields of the aggregated items
+    pub enum_fields: Vec<(String, String)>,
 }
 impl Display for AggregateBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "aggregate_by(&[{}], {})",
-            self.properties
-                .iter()
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>()
-                .join(","),
-            self.should_count
-        )
+        write_decode_enums(f, &self.enum_fields)?;
+        let properties = self
+            .properties
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        match (self.aggregations.is_empty(), self.should_count) {
+            (true, _) => write!(f, "aggregate_by(&[{}], {})", properties, self.should_count),
+            // Counting drops the items, which leaves exactly what GROUP_BY returns
+            (false, true) => write!(
+                f,
+                "group_by_with(&[{}], &[{}])",
+                properties,
+                write_aggregations(&self.aggregations)
+            ),
+            (false, false) => write!(
+                f,
+                "aggregate_by_with(&[{}], &[{}])",
+                properties,
+                write_aggregations(&self.aggregations)
+            ),
+        }
     }
 }
 
+/// Enums are stored as the index of their variant, so grouped items read them back as the
+/// variant names first, which keys the groups by name and returns the items with them
+fn write_decode_enums(f: &mut fmt::Formatter<'_>, enum_fields: &[(String, String)]) -> fmt::Result {
+    if enum_fields.is_empty() {
+        return Ok(());
+    }
+    let fields = enum_fields
+        .iter()
+        .map(|(field, enum_name)| format!("(\"{field}\", {enum_name}::VARIANTS)"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(
+        f,
+        "map_traversal(|item, _| Ok(enums::decode_item(item, &[{fields}], &arena))).",
+    )
+}
+
+fn write_aggregations(aggregations: &[(String, AggregateFunction)]) -> String {
+    aggregations
+        .iter()
+        .map(|(name, function)| {
+            let function = match function {
+                AggregateFunction::Count => "Count".to_string(),
+                AggregateFunction::CountDistinct(field) => format!("CountDistinct(\"{field}\")"),
+                AggregateFunction::Sum(field) => format!("Sum(\"{field}\")"),
+                AggregateFunction::Avg(field) => format!("Avg(\"{field}\")"),
+                AggregateFunction::Min(field) => format!("Min(\"{field}\")"),
+                AggregateFunction::Max(field) => format!("Max(\"{field}\")"),
+                AggregateFunction::Percentile(field, rank) => {
+                    format!("Percentile(\"{field}\", {rank:?})")
+                }
+                AggregateFunction::Collect(field) => format!("Collect(\"{field}\")"),
+            };
+            format!("Aggregation {{ name: \"{name}\", function: AggregateFunction::{function} }}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Clone)]
 pub struct ShortestPath {
     pub label: Option<GenRef<String>>,
@@ -641,6 +861,31 @@ pub struct ShortestPathAStar {
     pub heuristic_property: GenRef<String>,
 }
 
+#[derive(Clone)]
+pub struct KShortestPaths {
+    pub label: Option<GenRef<String>>,
+    pub from: Option<GenRef<String>>,
+    pub to: Option<GenRef<String>>,
+    pub k: GeneratedValue,
+    pub weight_calculation: WeightCalculation,
+}
+
+#[derive(Clone)]
+pub struct AllPaths {
+    pub label: Option<GenRef<String>>,
+    pub from: Option<GenRef<String>>,
+    pub to: Option<GenRef<String>>,
+    pub max_depth: usize,
+}
+
+#[derive(Clone)]
+pub struct ShortestPathsFrom {
+    pub label: Option<GenRef<String>>,
+    pub max_distance: Option<GeneratedValue>,
+    /// `None` counts hops instead of weighing edges
+    pub weight_calculation: Option<WeightCalculation>,
+}
+
 #[derive(Clone)]
 pub enum PathAlgorithm {
     BFS,
@@ -806,6 +1051,185 @@ impl Display for ShortestPathAStar {
     }
 }
 
+impl Display for KShortestPaths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "k_shortest_paths({}, {}, {}, {}, ",
+            self.label
+                .as_ref()
+                .map_or("None".to_string(), |label| format!("Some({label})")),
+            self.from
+                .as_ref()
+                .map_or("None".to_string(), |from| format!("Some(&{from})")),
+            self.to
+                .as_ref()
+                .map_or("None".to_string(), |to| format!("Some(&{to})")),
+            self.k
+        )?;
+
+        match &self.weight_calculation {
+            WeightCalculation::Property(prop) => {
+                write!(
+                    f,
+                    "|edge, _src_node, _dst_node| -> Result<f64, GraphError> {{ Ok(edge.get_property({})?.as_f64()?) }}",
+                    prop
+                )?;
+            }
+            WeightCalculation::Expression(expr) => {
+                write!(
+                    f,
+                    "|edge, src_node, dst_node| -> Result<f64, GraphError> {{ Ok({}) }}",
+                    expr
+                )?;
+            }
+            WeightCalculation::Default => {
+                write!(f, "helix_db::helix_engine::traversal_core::ops::util::paths::default_weight_fn")?;
+            }
+        }
+
+        write!(f, ")")
+    }
+}
+
+impl Display for AllPaths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "all_paths({}, {}, {}, {})",
+            self.label
+                .as_ref()
+                .map_or("None".to_string(), |label| format!("Some({label})")),
+            self.from
+                .as_ref()
+                .map_or("None".to_string(), |from| format!("Some(&{from})")),
+            self.to
+                .as_ref()
+                .map_or("None".to_string(), |to| format!("Some(&{to})")),
+            self.max_depth
+        )
+    }
+}
+
+impl Display for ShortestPathsFrom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "shortest_paths_from({}, {}, ",
+            self.label
+                .as_ref()
+                .map_or("None".to_string(), |label| format!("Some({label})")),
+            self.max_distance
+                .as_ref()
+                .map_or("None".to_string(), |max| format!("Some({max} as f64)"))
+        )?;
+
+        match &self.weight_calculation {
+            Some(WeightCalculation::Property(prop)) => {
+                write!(
+                    f,
+                    "|edge, _src_node, _dst_node| -> Result<f64, GraphError> {{ Ok(edge.get_property({}).ok_or(GraphError::Default)?.as_f64()) }}",
+                    prop
+                )?;
+            }
+            Some(WeightCalculation::Expression(expr)) => {
+                write!(
+                    f,
+                    "|edge, src_node, dst_node| -> Result<f64, GraphError> {{ Ok({}) }}",
+                    expr
+                )?;
+            }
+            Some(WeightCalculation::Default) => {
+                write!(f, "helix_db::helix_engine::traversal_core::ops::util::paths::default_weight_fn")?;
+            }
+            None => {
+                write!(f, "helix_db::helix_engine::traversal_core::ops::util::shortest_paths_from::hop_count_weight_fn")?;
+            }
+        }
+
+        write!(f, ")")
+    }
+}
+
+/// Runs a graph analytics algorithm over the nodes of the traversal. `args` holds
+/// every parameter of the algorithm, with defaults already filled in.
+#[derive(Clone)]
+pub struct GraphAlgorithmStep {
+    pub edge_labels: Vec<GenRef<String>>,
+    pub algorithm: GraphAlgorithmType,
+    pub args: Vec<GeneratedValue>,
+}
+impl Display for GraphAlgorithmStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "run_graph_algorithm(&[{}], ",
+            self.edge_labels
+                .iter()
+                .map(|label| label.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        match (&self.algorithm, self.args.as_slice()) {
+            (GraphAlgorithmType::PageRank, [damping, iterations]) => write!(
+                f,
+                "GraphAlgorithm::PageRank {{ damping: {damping} as f64, iterations: {iterations} as usize }}"
+            )?,
+            (GraphAlgorithmType::ConnectedComponents, _) => {
+                write!(f, "GraphAlgorithm::WeaklyConnectedComponents")?
+            }
+            (GraphAlgorithmType::StronglyConnectedComponents, _) => {
+                write!(f, "GraphAlgorithm::StronglyConnectedComponents")?
+            }
+            (GraphAlgorithmType::LabelPropagation, [iterations]) => write!(
+                f,
+                "GraphAlgorithm::LabelPropagation {{ iterations: {iterations} as usize }}"
+            )?,
+            (GraphAlgorithmType::TriangleCount, _) => write!(f, "GraphAlgorithm::TriangleCount")?,
+            (GraphAlgorithmType::Betweenness, [samples]) => write!(
+                f,
+                "GraphAlgorithm::Betweenness {{ samples: {samples} as usize }}"
+            )?,
+            (algorithm, args) => unreachable!("{algorithm} given {} arguments", args.len()),
+        }
+        write!(f, ")")
+    }
+}
+
+/// Joins searched vectors back to the nodes they are embedded on
+#[derive(Clone)]
+pub struct OwnerN {
+    /// Label of the node type embedding the vectors
+    pub label: GenRef<String>,
+    /// Field the vectors are embedded under
+    pub field: GenRef<String>,
+}
+impl Display for OwnerN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "owner_n({}, {})", self.label, self.field)
+    }
+}
+
+/// Inserts a vector for each node written by `AddN` or `UPDATE` and embeds it under `field`
+#[derive(Clone, Debug)]
+pub struct SetEmbedding {
+    /// Field the vector is embedded under
+    pub field: GenRef<String>,
+    /// Label of the vector type
+    pub label: GenRef<String>,
+    /// Data of the vector
+    pub vec: VecData,
+}
+impl Display for SetEmbedding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "set_embedding::<fn(&HVector, &RoTxn) -> bool>({}, {}, {})",
+            self.field, self.label, self.vec
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct SearchVectorStep {
     pub vec: VecData,
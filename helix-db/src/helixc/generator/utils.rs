@@ -441,27 +441,36 @@ use helix_db::{
                 in_::{in_::InAdapter, in_e::InEdgesAdapter, to_n::ToNAdapter, to_v::ToVAdapter},
                 out::{
                     from_n::FromNAdapter, from_v::FromVAdapter, out::OutAdapter, out_e::OutEdgesAdapter,
+                    out_n::OutNAdapter,
                 },
                 source::{
                     add_e::AddEAdapter,
                     add_n::AddNAdapter,
+                    e_between::EBetweenAdapter,
                     e_from_id::EFromIdAdapter,
+                    e_from_index::EFromIndexAdapter,
                     e_from_type::EFromTypeAdapter,
                     n_from_id::NFromIdAdapter,
                     n_from_index::NFromIndexAdapter,
                     n_from_type::NFromTypeAdapter,
+                    upsert_e::UpsertEAdapter,
+                    upsert_n::{UpsertNAdapter, Upserted},
                     v_from_id::VFromIdAdapter,
                     v_from_type::VFromTypeAdapter
                 },
                 util::{
                     dedup::DedupAdapter, drop::Drop, exist::Exist, filter_mut::FilterMut,
                     filter_ref::FilterRefAdapter, map::MapAdapter, paths::{PathAlgorithm, ShortestPathAdapter},
-                    range::RangeAdapter, update::UpdateAdapter, order::OrderByAdapter,
+                    range::RangeAdapter, update::UpdateAdapter, order::{OrderByAdapter, SortKey},
+                    paginate::{PaginateAdapter, decode_cursor},
+                    shortest_paths_from::ShortestPathsFromAdapter,
+                    graph_algorithms::{GraphAlgorithm, GraphAlgorithmAdapter},
                     aggregate::AggregateAdapter, group_by::GroupByAdapter, count::CountAdapter,
                 },
                 vectors::{
                     brute_force_search::BruteForceSearchVAdapter, insert::InsertVAdapter,
-                    search::SearchVAdapter,
+                    search::SearchVAdapter, owner_n::OwnerNAdapter,
+                    set_embedding::SetEmbeddingAdapter,
                 },
             },
             traversal_value::TraversalValue,
@@ -480,8 +489,11 @@ use helix_db::{
         response::Response,
         value::{casting::{cast, CastType}, Value},
         format::Format,
+        functions,
     },
     utils::{
+        enums,
+        group_by::{AggregateFunction, Aggregation},
         id::{ID, uuid_str},
         items::{Edge, Node},
         properties::ImmutablePropertiesMap,
@@ -0,0 +1,1146 @@
+//! Interpreter running HQL queries directly against the graph.
+//!
+//! Queries compiled into an instance go through the generator, so ad-hoc queries (e.g. from
+//! `helix shell`) are run here instead: the analyzed AST is walked and every step is mapped
+//! onto its `traversal_core` adapter at runtime. Only a subset of HQL is covered, anything
+//! outside of it fails with an error naming the construct.
+
+pub(crate) mod values;
+
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use heed3::{RoTxn, RwTxn, WithTls};
+use itertools::Itertools;
+
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            ops::{
+                g::G,
+                in_::{in_::InAdapter, in_e::InEdgesAdapter, to_n::ToNAdapter, to_v::ToVAdapter},
+                out::{
+                    from_n::FromNAdapter, from_v::FromVAdapter, out::OutAdapter,
+                    out_e::OutEdgesAdapter,
+                },
+                source::{
+                    add_e::AddEAdapter, add_n::AddNAdapter, e_between::EBetweenAdapter,
+                    e_from_id::EFromIdAdapter, e_from_index::EFromIndexAdapter,
+                    e_from_type::EFromTypeAdapter, n_from_id::NFromIdAdapter,
+                    n_from_index::NFromIndexAdapter, n_from_type::NFromTypeAdapter,
+                    v_from_id::VFromIdAdapter, v_from_type::VFromTypeAdapter,
+                },
+                util::{drop::Drop, update::UpdateAdapter},
+                vectors::search::SearchVAdapter,
+            },
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+        vector_core::vector::HVector,
+    },
+    helixc::parser::types::{
+        AddEdge, AddNode, BooleanOpType, EvaluatesToNumber, EvaluatesToNumberType, Expression,
+        ExpressionType, Field, FieldAddition, FieldType, FieldValueType, GraphStepType, IdType,
+        NullsOrder, OrderBy, OrderByType, Query, ReturnType, Schema, SearchVector, Source,
+        StartNode, Statement, StatementType, StepType, Traversal, Update, ValueType, VectorData,
+    },
+    protocol::value::Value,
+    utils::properties::ImmutablePropertiesMap,
+};
+use values::{
+    as_f64, as_i128, coerce, compare, default_value, equals, item_fields, item_value, parse_id,
+    property, read_item, stored,
+};
+
+/// Runs `query` against the graph, returning its values keyed by name.
+///
+/// Queries run in a read transaction unless `write` is set, in which case the write
+/// transaction is committed once every statement succeeded.
+pub fn interpret(
+    storage: &HelixGraphStorage,
+    source: &Source,
+    query: &Query,
+    params: HashMap<String, Value>,
+    write: bool,
+) -> Result<sonic_rs::Value, GraphError> {
+    let arena = Bump::new();
+    let txn = match write {
+        true => Txn::Write(storage.graph_env.write_txn()?),
+        false => Txn::Read(storage.graph_env.read_txn()?),
+    };
+    let schema = source
+        .get_latest_schema()
+        .map_err(|e| GraphError::New(e.to_string()))?;
+    let mut interpreter = Interpreter {
+        storage,
+        arena: &arena,
+        txn,
+        schema,
+        params: query_params(schema, query, params)?,
+        vars: HashMap::new(),
+    };
+
+    for statement in &query.statements {
+        interpreter.statement(statement)?;
+    }
+    let mut result = sonic_rs::Object::new();
+    for value in &query.return_values {
+        interpreter.return_value(value, &mut result)?;
+    }
+
+    if let Txn::Write(txn) = interpreter.txn {
+        txn.commit()?;
+    }
+    Ok(result.into_value())
+}
+
+/// Casts the request's parameters to the types the query declares
+fn query_params(
+    schema: &Schema,
+    query: &Query,
+    mut params: HashMap<String, Value>,
+) -> Result<HashMap<String, Value>, GraphError> {
+    let mut values = HashMap::new();
+    for param in &query.parameters {
+        let name = &param.name.1;
+        let value = match params.remove(name) {
+            Some(value) => coerce(value, &schema.resolve_structs(&param.param_type.1), name)?,
+            None if param.is_optional => Value::Empty,
+            None => return Err(GraphError::New(format!("missing parameter `{name}`"))),
+        };
+        values.insert(name.clone(), value);
+    }
+    match params.keys().next() {
+        Some(name) => Err(GraphError::New(format!("unknown parameter `{name}`"))),
+        None => Ok(values),
+    }
+}
+
+fn unsupported(what: &str) -> GraphError {
+    GraphError::New(format!("{what} is not supported by the interpreter"))
+}
+
+/// The fields declared for a node, edge or vector type
+fn fields<'s>(schema: &'s Schema, label: &str) -> &'s [Field] {
+    if let Some(node) = schema.node_schemas.iter().find(|node| node.name.1 == label) {
+        return &node.fields;
+    }
+    if let Some(edge) = schema.edge_schemas.iter().find(|edge| edge.name.1 == label) {
+        return edge.properties.as_deref().unwrap_or_default();
+    }
+    match schema
+        .vector_schemas
+        .iter()
+        .find(|vector| vector.name == label)
+    {
+        Some(vector) => &vector.fields,
+        None => &[],
+    }
+}
+
+enum Txn<'db> {
+    Read(RoTxn<'db, WithTls>),
+    Write(RwTxn<'db>),
+}
+
+/// The items a traversal or expression evaluated to.
+///
+/// `single` is set when the HQL type is a single item, e.g. `N<User>(id)` or `::FIRST`, so it
+/// is returned as an object rather than a list.
+#[derive(Clone)]
+struct Items<'arena> {
+    values: Vec<TraversalValue<'arena>>,
+    single: bool,
+}
+
+impl<'arena> Items<'arena> {
+    fn one(value: TraversalValue<'arena>) -> Self {
+        Items {
+            values: vec![value],
+            single: true,
+        }
+    }
+
+    fn many(values: Vec<TraversalValue<'arena>>) -> Self {
+        Items {
+            values,
+            single: false,
+        }
+    }
+
+    fn into_value(self, schema: &Schema) -> Value {
+        let item_value = |item| item_value(schema, item);
+        match self.single {
+            true => self
+                .values
+                .into_iter()
+                .next()
+                .map(item_value)
+                .unwrap_or_default(),
+            false => Value::Array(self.values.into_iter().map(item_value).collect()),
+        }
+    }
+
+    fn into_json(
+        self,
+        schema: &Schema,
+        arena: &'arena Bump,
+    ) -> Result<sonic_rs::Value, GraphError> {
+        let mut values = self
+            .values
+            .into_iter()
+            .map(|item| read_item(schema, item, arena));
+        let json = match self.single {
+            true => match values.next() {
+                Some(value) => sonic_rs::to_value(&value),
+                None => Ok(sonic_rs::Value::default()),
+            },
+            false => sonic_rs::to_value(&values.collect::<Vec<_>>()),
+        };
+        json.map_err(|e| GraphError::New(e.to_string()))
+    }
+}
+
+struct Interpreter<'db, 'arena, 's> {
+    storage: &'db HelixGraphStorage,
+    arena: &'arena Bump,
+    txn: Txn<'db>,
+    schema: &'s Schema,
+    params: HashMap<String, Value>,
+    vars: HashMap<String, Items<'arena>>,
+}
+
+impl<'db: 'arena, 'arena, 's> Interpreter<'db, 'arena, 's> {
+    fn ro(&self) -> &RoTxn<'db> {
+        match &self.txn {
+            Txn::Read(txn) => txn,
+            Txn::Write(txn) => txn,
+        }
+    }
+
+    fn rw(&mut self, what: &str) -> Result<&mut RwTxn<'db>, GraphError> {
+        match &mut self.txn {
+            Txn::Write(txn) => Ok(txn),
+            Txn::Read(_) => Err(GraphError::New(format!(
+                "{what} writes to the graph, which is not allowed in a read-only query"
+            ))),
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<(), GraphError> {
+        match &statement.statement {
+            StatementType::Assignment(assignment) => {
+                let items = self.expression(&assignment.value, None)?;
+                self.vars.insert(assignment.variable.clone(), items);
+            }
+            StatementType::Expression(expression) => {
+                self.expression(expression, None)?;
+            }
+            StatementType::Drop(expression) => {
+                let items = self.expression(expression, None)?;
+                self.drop(items)?;
+            }
+            StatementType::ForLoop(_) => return Err(unsupported("FOR")),
+            StatementType::IfElse(_) => return Err(unsupported("IF")),
+        }
+        Ok(())
+    }
+
+    fn return_value(
+        &mut self,
+        value: &ReturnType,
+        result: &mut sonic_rs::Object,
+    ) -> Result<(), GraphError> {
+        match value {
+            ReturnType::Expression(expression) => {
+                let name = match &expression.expr {
+                    ExpressionType::Identifier(name) => name.as_str(),
+                    ExpressionType::Traversal(traversal) => match &traversal.start {
+                        StartNode::Identifier(name) => name.as_str(),
+                        _ => "data",
+                    },
+                    _ => "data",
+                };
+                let json = self
+                    .expression(expression, None)?
+                    .into_json(self.schema, self.arena)?;
+                result.insert(name, json);
+            }
+            ReturnType::Object(fields) => {
+                for (name, value) in fields {
+                    let json = self.return_json(value)?;
+                    result.insert(name, json);
+                }
+            }
+            ReturnType::Array(_) => {
+                let json = self.return_json(value)?;
+                result.insert("data", json);
+            }
+            ReturnType::Empty => {}
+        }
+        Ok(())
+    }
+
+    fn return_json(&mut self, value: &ReturnType) -> Result<sonic_rs::Value, GraphError> {
+        match value {
+            ReturnType::Expression(expression) => self
+                .expression(expression, None)?
+                .into_json(self.schema, self.arena),
+            ReturnType::Object(fields) => {
+                let mut object = sonic_rs::Object::new();
+                for (name, value) in fields {
+                    let json = self.return_json(value)?;
+                    object.insert(name, json);
+                }
+                Ok(object.into_value())
+            }
+            ReturnType::Array(values) => {
+                let mut array = sonic_rs::Array::new();
+                for value in values {
+                    array.push(self.return_json(value)?);
+                }
+                Ok(array.into_value())
+            }
+            ReturnType::Empty => Ok(sonic_rs::Value::default()),
+        }
+    }
+
+    fn variable(&self, name: &str) -> Result<Items<'arena>, GraphError> {
+        if let Some(items) = self.vars.get(name) {
+            return Ok(items.clone());
+        }
+        match self.params.get(name) {
+            Some(value) => Ok(Items::one(TraversalValue::Value(value.clone()))),
+            None => Err(GraphError::New(format!("`{name}` is not defined"))),
+        }
+    }
+
+    fn expression(
+        &mut self,
+        expression: &Expression,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<Items<'arena>, GraphError> {
+        match &expression.expr {
+            ExpressionType::Traversal(traversal) => self.traversal(traversal, current),
+            ExpressionType::Identifier(name) => self.variable(name),
+            ExpressionType::AddNode(add) => self.add_node(add).map(Items::one),
+            ExpressionType::AddEdge(add) => self.add_edge(add),
+            ExpressionType::SearchVector(search) => self.search_vector(search),
+            ExpressionType::StringLiteral(_)
+            | ExpressionType::IntegerLiteral(_)
+            | ExpressionType::FloatLiteral(_)
+            | ExpressionType::BooleanLiteral(_)
+            | ExpressionType::ArrayLiteral(_)
+            | ExpressionType::Exists(_)
+            | ExpressionType::Not(_)
+            | ExpressionType::And(_)
+            | ExpressionType::Or(_)
+            | ExpressionType::Empty => {
+                let value = self.value(expression, current)?;
+                Ok(Items::one(TraversalValue::Value(value)))
+            }
+            ExpressionType::AddVector(_) => Err(unsupported("AddV")),
+            ExpressionType::UpsertNode(_) => Err(unsupported("UpsertN")),
+            ExpressionType::UpsertEdge(_) => Err(unsupported("UpsertE")),
+            ExpressionType::BM25Search(_) => Err(unsupported("SearchBM25")),
+            ExpressionType::MathFunctionCall(call) => Err(unsupported(call.function.name())),
+            ExpressionType::ScalarFunctionCall(call) => Err(unsupported(call.function.name())),
+            ExpressionType::Coalesce(_) => Err(unsupported("COALESCE")),
+        }
+    }
+
+    /// Evaluates an expression to a single value
+    fn value(
+        &mut self,
+        expression: &Expression,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<Value, GraphError> {
+        match &expression.expr {
+            ExpressionType::StringLiteral(s) => Ok(Value::String(s.clone())),
+            ExpressionType::IntegerLiteral(i) => Ok(Value::I32(*i)),
+            ExpressionType::FloatLiteral(f) => Ok(Value::F64(*f)),
+            ExpressionType::BooleanLiteral(b) => Ok(Value::Boolean(*b)),
+            ExpressionType::ArrayLiteral(items) => Ok(Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.value(item, current))
+                    .collect::<Result<_, _>>()?,
+            )),
+            ExpressionType::Exists(_)
+            | ExpressionType::Not(_)
+            | ExpressionType::And(_)
+            | ExpressionType::Or(_) => self.condition(expression, current).map(Value::Boolean),
+            ExpressionType::Empty => Ok(Value::Empty),
+            _ => Ok(self
+                .expression(expression, current)?
+                .into_value(self.schema)),
+        }
+    }
+
+    fn condition(
+        &mut self,
+        expression: &Expression,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<bool, GraphError> {
+        match &expression.expr {
+            ExpressionType::BooleanLiteral(b) => Ok(*b),
+            ExpressionType::Not(expression) => Ok(!self.condition(expression, current)?),
+            ExpressionType::And(expressions) => {
+                for expression in expressions {
+                    if !self.condition(expression, current)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            ExpressionType::Or(expressions) => {
+                for expression in expressions {
+                    if self.condition(expression, current)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            ExpressionType::Exists(exists) => {
+                let items = self.expression(&exists.expr, current)?;
+                Ok(!items.values.is_empty())
+            }
+            _ => match self.value(expression, current)? {
+                Value::Boolean(b) => Ok(b),
+                value => Err(GraphError::New(format!(
+                    "expected a boolean condition, got {}",
+                    value.inner_stringify()
+                ))),
+            },
+        }
+    }
+
+    fn value_type(&self, value: &ValueType) -> Result<Value, GraphError> {
+        match value {
+            ValueType::Literal { value, .. } => Ok(value.clone()),
+            ValueType::Identifier { value, .. } => {
+                Ok(self.variable(value)?.into_value(self.schema))
+            }
+            ValueType::Object { fields, .. } => Ok(Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), self.value_type(value)?)))
+                    .collect::<Result<_, GraphError>>()?,
+            )),
+        }
+    }
+
+    /// The ids an id argument refers to, e.g. every node held by a variable
+    fn ids(&self, id: &IdType) -> Result<Vec<u128>, GraphError> {
+        match id {
+            IdType::Literal { value, .. } => Ok(vec![parse_id(value.trim_matches('"'))?]),
+            IdType::Identifier { value, .. } => self
+                .variable(value)?
+                .values
+                .iter()
+                .map(|item| match item {
+                    TraversalValue::Value(Value::Id(id)) => Ok(**id),
+                    TraversalValue::Value(Value::String(id)) => parse_id(id),
+                    TraversalValue::Value(_) | TraversalValue::Empty => {
+                        Err(GraphError::New(format!("`{value}` is not an id")))
+                    }
+                    item => Ok(item.id()),
+                })
+                .collect(),
+            IdType::ByIndex { .. } => Err(GraphError::New(
+                "index lookups cannot be used as ids".to_string(),
+            )),
+        }
+    }
+
+    fn id(&self, id: &IdType) -> Result<u128, GraphError> {
+        match self.ids(id)?.as_slice() {
+            [id] => Ok(*id),
+            _ => Err(GraphError::New(format!("`{id}` must be a single id"))),
+        }
+    }
+
+    /// The value an indexed field is looked up by, cast to the field's type
+    fn index_key(&self, label: &str, field: &str, value: &ValueType) -> Result<Value, GraphError> {
+        let value = self.value_type(value)?;
+        match fields(self.schema, label).iter().find(|f| f.name == field) {
+            Some(f) => stored(self.schema, value, &f.field_type, field),
+            None => Ok(value),
+        }
+    }
+
+    fn number(&self, number: &EvaluatesToNumber) -> Result<usize, GraphError> {
+        let value = match &number.value {
+            EvaluatesToNumberType::Identifier(name) => self.variable(name)?.into_value(self.schema),
+            EvaluatesToNumberType::I8(v) => Value::I8(*v),
+            EvaluatesToNumberType::I16(v) => Value::I16(*v),
+            EvaluatesToNumberType::I32(v) => Value::I32(*v),
+            EvaluatesToNumberType::I64(v) => Value::I64(*v),
+            EvaluatesToNumberType::U8(v) => Value::U8(*v),
+            EvaluatesToNumberType::U16(v) => Value::U16(*v),
+            EvaluatesToNumberType::U32(v) => Value::U32(*v),
+            EvaluatesToNumberType::U64(v) => Value::U64(*v),
+            EvaluatesToNumberType::U128(v) => Value::U128(*v),
+            EvaluatesToNumberType::F32(v) => Value::F32(*v),
+            EvaluatesToNumberType::F64(v) => Value::F64(*v),
+        };
+        to_usize(&value)
+    }
+
+    fn traversal(
+        &mut self,
+        traversal: &Traversal,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<Items<'arena>, GraphError> {
+        let mut items = self.start(&traversal.start, current)?;
+        let anonymous = matches!(traversal.start, StartNode::Anonymous);
+        for (i, step) in traversal.steps.iter().enumerate() {
+            // `_::{age}` and `user::{age}::GT(..)` read the property rather than projecting it
+            let as_value = match traversal.steps.get(i + 1) {
+                Some(next) => matches!(next.step, StepType::BooleanOperation(_)),
+                None => anonymous,
+            };
+            items = self.step(&step.step, items, as_value, current)?;
+        }
+        Ok(items)
+    }
+
+    fn start(
+        &mut self,
+        start: &StartNode,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<Items<'arena>, GraphError> {
+        let (storage, arena) = (self.storage, self.arena);
+        match start {
+            StartNode::Node { node_type, ids } => {
+                let Some(ids) = ids else {
+                    let values = G::new(storage, self.ro(), arena)
+                        .n_from_type(node_type)
+                        .collect::<Result<_, _>>()?;
+                    return Ok(Items::many(values));
+                };
+                let mut values = Vec::new();
+                for id in ids {
+                    match id {
+                        IdType::ByIndex { index, value, .. } => {
+                            let field = index.to_string();
+                            let key = self.index_key(node_type, &field, value)?;
+                            let nodes = G::new(storage, self.ro(), arena)
+                                .n_from_index(node_type, &field, &key)
+                                .collect::<Result<Vec<_>, _>>()?;
+                            values.extend(nodes);
+                        }
+                        id => {
+                            let id = self.id(id)?;
+                            let node = G::new(storage, self.ro(), arena)
+                                .n_from_id(&id)
+                                .collect_to_obj()?;
+                            values.push(node);
+                        }
+                    }
+                }
+                Ok(Items {
+                    values,
+                    single: ids.len() == 1,
+                })
+            }
+            StartNode::Edge { edge_type, ids } => {
+                let Some(ids) = ids else {
+                    let values = G::new(storage, self.ro(), arena)
+                        .e_from_type(edge_type)
+                        .collect::<Result<_, _>>()?;
+                    return Ok(Items::many(values));
+                };
+                let mut values = Vec::new();
+                for id in ids {
+                    match id {
+                        IdType::ByIndex { index, value, .. } => {
+                            let field = index.to_string();
+                            let key = self.index_key(edge_type, &field, value)?;
+                            let edges = G::new(storage, self.ro(), arena)
+                                .e_from_index(edge_type, &field, &key)
+                                .collect::<Result<Vec<_>, _>>()?;
+                            values.extend(edges);
+                        }
+                        id => {
+                            let id = self.id(id)?;
+                            let edge = G::new(storage, self.ro(), arena)
+                                .e_from_id(&id)
+                                .next()
+                                .unwrap_or(Err(GraphError::EdgeNotFound))?;
+                            values.push(edge);
+                        }
+                    }
+                }
+                // edge indices are not unique, so an index lookup can match several edges
+                let single =
+                    matches!(ids.as_slice(), [id] if !matches!(id, IdType::ByIndex { .. }));
+                Ok(Items { values, single })
+            }
+            StartNode::EdgesBetween {
+                edge_type,
+                from,
+                to,
+            } => {
+                let (from, to) = (self.id(from)?, self.id(to)?);
+                let values = G::new(storage, self.ro(), arena)
+                    .e_between(edge_type, from, to)
+                    .collect::<Result<_, _>>()?;
+                Ok(Items::many(values))
+            }
+            StartNode::Vector { vector_type, ids } => {
+                let Some(ids) = ids else {
+                    let values = G::new(storage, self.ro(), arena)
+                        .v_from_type(arena.alloc_str(vector_type), true)
+                        .collect::<Result<_, _>>()?;
+                    return Ok(Items::many(values));
+                };
+                let mut values = Vec::new();
+                for id in ids {
+                    let id = self.id(id)?;
+                    let vector = G::new(storage, self.ro(), arena)
+                        .v_from_id(&id, true)
+                        .collect_to_obj()?;
+                    values.push(vector);
+                }
+                Ok(Items {
+                    values,
+                    single: ids.len() == 1,
+                })
+            }
+            StartNode::SearchVector(search) => self.search_vector(search),
+            StartNode::Identifier(name) => self.variable(name),
+            StartNode::Anonymous => match current {
+                Some(item) => Ok(Items::one(item.clone())),
+                None => Err(GraphError::New(
+                    "`_` can only be used inside a traversal step".to_string(),
+                )),
+            },
+        }
+    }
+
+    fn step(
+        &mut self,
+        step: &StepType,
+        items: Items<'arena>,
+        as_value: bool,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<Items<'arena>, GraphError> {
+        let (storage, arena) = (self.storage, self.arena);
+        match step {
+            StepType::Node(graph_step) | StepType::Edge(graph_step) => {
+                let single = items.single
+                    && matches!(
+                        graph_step.step,
+                        GraphStepType::FromN
+                            | GraphStepType::ToN
+                            | GraphStepType::FromV
+                            | GraphStepType::ToV
+                    );
+                let g = G::from_iter(storage, self.ro(), items.values.into_iter(), arena);
+                let values = match &graph_step.step {
+                    GraphStepType::Out(label) => g.out_node(label).collect::<Result<_, _>>()?,
+                    GraphStepType::In(label) => g.in_node(label).collect::<Result<_, _>>()?,
+                    GraphStepType::OutE(label) => g.out_e(label).collect::<Result<_, _>>()?,
+                    GraphStepType::InE(label) => g.in_e(label).collect::<Result<_, _>>()?,
+                    GraphStepType::FromN => g.from_n().collect::<Result<_, _>>()?,
+                    GraphStepType::ToN => g.to_n().collect::<Result<_, _>>()?,
+                    GraphStepType::FromV => g.from_v(true).collect::<Result<_, _>>()?,
+                    GraphStepType::ToV => g.to_v(true).collect::<Result<_, _>>()?,
+                    GraphStepType::OutN(_) => return Err(unsupported("OutN")),
+                    GraphStepType::SearchVector(_) => return Err(unsupported("SearchV as a step")),
+                    _ => return Err(unsupported("path and graph algorithm steps")),
+                };
+                Ok(Items { values, single })
+            }
+            StepType::Where(condition) => {
+                let mut values = Vec::new();
+                for item in items.values {
+                    if self.condition(condition, Some(&item))? {
+                        values.push(item);
+                    }
+                }
+                Ok(Items {
+                    values,
+                    single: items.single,
+                })
+            }
+            StepType::BooleanOperation(op) => {
+                let values = items
+                    .values
+                    .into_iter()
+                    .map(|item| {
+                        let result =
+                            self.boolean_op(&op.op, &item_value(self.schema, item), current)?;
+                        Ok(TraversalValue::Value(Value::Boolean(result)))
+                    })
+                    .collect::<Result<_, GraphError>>()?;
+                Ok(Items {
+                    values,
+                    single: items.single,
+                })
+            }
+            StepType::Count => Ok(Items::one(TraversalValue::Value(Value::from(
+                items.values.len(),
+            )))),
+            StepType::Update(update) => self.update(update, items),
+            StepType::Object(object) => {
+                let values = items
+                    .values
+                    .iter()
+                    .map(|item| {
+                        let value = match object.fields.as_slice() {
+                            [field] if as_value && !object.should_spread => {
+                                self.field_value(item, field)?
+                            }
+                            fields => {
+                                Value::Object(self.project(item, fields, object.should_spread)?)
+                            }
+                        };
+                        Ok(TraversalValue::Value(value))
+                    })
+                    .collect::<Result<_, GraphError>>()?;
+                Ok(Items {
+                    values,
+                    single: items.single,
+                })
+            }
+            StepType::Exclude(exclude) => {
+                let values = items
+                    .values
+                    .iter()
+                    .map(|item| {
+                        let mut fields = item_fields(self.schema, item);
+                        for (_, field) in &exclude.fields {
+                            fields.remove(field);
+                        }
+                        TraversalValue::Value(Value::Object(fields))
+                    })
+                    .collect();
+                Ok(Items {
+                    values,
+                    single: items.single,
+                })
+            }
+            StepType::Range((start, end)) => {
+                let start = to_usize(&self.value(start, current)?)?;
+                let end = to_usize(&self.value(end, current)?)?;
+                let values = items
+                    .values
+                    .into_iter()
+                    .skip(start)
+                    .take(end.saturating_sub(start))
+                    .collect();
+                Ok(Items::many(values))
+            }
+            StepType::First => Ok(Items {
+                values: items.values.into_iter().take(1).collect(),
+                single: true,
+            }),
+            StepType::OrderBy(order) => self.order(order, items),
+            StepType::Closure(_) => Err(unsupported("closures")),
+            StepType::After(_) | StepType::Cursor => Err(unsupported("pagination cursors")),
+            StepType::Aggregate(_) => Err(unsupported("AGGREGATE_BY")),
+            StepType::GroupBy(_) => Err(unsupported("GROUP_BY")),
+            StepType::AddEdge(_) => Err(unsupported("AddE as a step")),
+            StepType::RerankRRF(_) => Err(unsupported("RerankRRF")),
+            StepType::RerankMMR(_) => Err(unsupported("RerankMMR")),
+        }
+    }
+
+    fn boolean_op(
+        &mut self,
+        op: &BooleanOpType,
+        value: &Value,
+        current: Option<&TraversalValue<'arena>>,
+    ) -> Result<bool, GraphError> {
+        use std::cmp::Ordering;
+
+        // a missing property is neither greater nor less than anything
+        let ordering =
+            |this: &mut Self, other: &Expression| -> Result<Option<Ordering>, GraphError> {
+                let other = this.value(other, current)?;
+                Ok(match (value, &other) {
+                    (Value::Empty, _) | (_, Value::Empty) => None,
+                    _ => Some(compare(value, &other)),
+                })
+            };
+        match op {
+            BooleanOpType::GreaterThan(other) => {
+                Ok(ordering(self, other)? == Some(Ordering::Greater))
+            }
+            BooleanOpType::GreaterThanOrEqual(other) => Ok(matches!(
+                ordering(self, other)?,
+                Some(Ordering::Greater | Ordering::Equal)
+            )),
+            BooleanOpType::LessThan(other) => Ok(ordering(self, other)? == Some(Ordering::Less)),
+            BooleanOpType::LessThanOrEqual(other) => Ok(matches!(
+                ordering(self, other)?,
+                Some(Ordering::Less | Ordering::Equal)
+            )),
+            BooleanOpType::Equal(other) => Ok(equals(value, &self.value(other, current)?)),
+            BooleanOpType::NotEqual(other) => Ok(!equals(value, &self.value(other, current)?)),
+            BooleanOpType::Contains(other) => {
+                let needle = self.value(other, current)?;
+                Ok(match value {
+                    Value::Array(items) => items.iter().any(|item| equals(item, &needle)),
+                    value => value.contains(&needle.inner_stringify()),
+                })
+            }
+            BooleanOpType::IsIn(other) => match self.value(other, current)? {
+                Value::Array(items) => Ok(items.iter().any(|item| equals(value, item))),
+                _ => Err(GraphError::New("IS_IN expects an array".to_string())),
+            },
+            BooleanOpType::IsNull => Ok(matches!(value, Value::Empty)),
+            // the operands are evaluated against the value, e.g. `::AND(_::GT(1), _::LT(5))`
+            BooleanOpType::And(conditions) => {
+                let value = TraversalValue::Value(value.clone());
+                for condition in conditions {
+                    if !self.condition(condition, Some(&value))? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            BooleanOpType::Or(conditions) => {
+                let value = TraversalValue::Value(value.clone());
+                for condition in conditions {
+                    if self.condition(condition, Some(&value))? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            BooleanOpType::Any(op) | BooleanOpType::All(op) => {
+                let Value::Array(items) = value else {
+                    return Err(GraphError::New("ANY and ALL expect an array".to_string()));
+                };
+                let any = matches!(op.op, BooleanOpType::Any(_));
+                for item in items {
+                    if self.boolean_op(&op.op, item, current)? == any {
+                        return Ok(any);
+                    }
+                }
+                Ok(!any)
+            }
+        }
+    }
+
+    fn field_value(
+        &mut self,
+        item: &TraversalValue<'arena>,
+        field: &FieldAddition,
+    ) -> Result<Value, GraphError> {
+        match &field.value.value {
+            FieldValueType::Empty => Ok(property(self.schema, item, &field.key)),
+            FieldValueType::Identifier(name) => self.identifier_value(item, name),
+            FieldValueType::Expression(Expression {
+                expr: ExpressionType::Identifier(name),
+                ..
+            }) => self.identifier_value(item, name),
+            FieldValueType::Expression(expression) => self.value(expression, Some(item)),
+            FieldValueType::Traversal(traversal) => Ok(self
+                .traversal(traversal, Some(item))?
+                .into_value(self.schema)),
+            FieldValueType::Fields(fields) => Ok(Value::Object(self.project(item, fields, false)?)),
+            FieldValueType::Literal(value) => Ok(value.clone()),
+        }
+    }
+
+    /// A name in a projection reads the item's property, falling back to a variable of that name
+    fn identifier_value(
+        &self,
+        item: &TraversalValue<'arena>,
+        name: &str,
+    ) -> Result<Value, GraphError> {
+        match property(self.schema, item, name) {
+            Value::Empty if self.vars.contains_key(name) || self.params.contains_key(name) => {
+                Ok(self.variable(name)?.into_value(self.schema))
+            }
+            value => Ok(value),
+        }
+    }
+
+    fn project(
+        &mut self,
+        item: &TraversalValue<'arena>,
+        fields: &[FieldAddition],
+        spread: bool,
+    ) -> Result<HashMap<String, Value>, GraphError> {
+        let mut object = match spread {
+            true => item_fields(self.schema, item),
+            false => HashMap::new(),
+        };
+        for field in fields {
+            let value = self.field_value(item, field)?;
+            object.insert(field.key.clone(), value);
+        }
+        Ok(object)
+    }
+
+    fn order(
+        &mut self,
+        order: &OrderBy,
+        items: Items<'arena>,
+    ) -> Result<Items<'arena>, GraphError> {
+        use std::cmp::Ordering;
+
+        let mut keyed = items
+            .values
+            .into_iter()
+            .map(|item| {
+                let keys = order
+                    .keys
+                    .iter()
+                    .map(|key| self.value(&key.expression, Some(&item)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((keys, item))
+            })
+            .collect::<Result<Vec<_>, GraphError>>()?;
+        keyed.sort_by(|(a, _), (b, _)| {
+            order
+                .keys
+                .iter()
+                .zip(a.iter().zip(b))
+                .map(|(key, (a, b))| {
+                    let nulls = match key.nulls {
+                        Some(NullsOrder::First) => Ordering::Less,
+                        _ => Ordering::Greater,
+                    };
+                    match (a, b, &key.order_by_type) {
+                        (Value::Empty, Value::Empty, _) => Ordering::Equal,
+                        (Value::Empty, _, _) => nulls,
+                        (_, Value::Empty, _) => nulls.reverse(),
+                        (a, b, OrderByType::Asc) => compare(a, b),
+                        (a, b, OrderByType::Desc) => compare(b, a),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(Items {
+            values: keyed.into_iter().map(|(_, item)| item).collect(),
+            single: items.single,
+        })
+    }
+
+    fn search_vector(&mut self, search: &SearchVector) -> Result<Items<'arena>, GraphError> {
+        let label = search.vector_type.as_deref().unwrap_or_default();
+        if search.pre_filter.is_some() {
+            return Err(unsupported("SearchV pre-filtering"));
+        }
+        if self
+            .schema
+            .node_schemas
+            .iter()
+            .any(|node| node.name.1 == label)
+        {
+            return Err(unsupported("searching the vectors embedded on nodes"));
+        }
+        let data = match &search.data {
+            Some(VectorData::Vector(data)) => data.clone(),
+            Some(VectorData::Identifier(name)) => {
+                match self.variable(name)?.into_value(self.schema) {
+                    Value::Array(values) => values
+                        .iter()
+                        .map(|value| {
+                            as_f64(value)
+                                .ok_or_else(|| GraphError::New(format!("`{name}` is not a vector")))
+                        })
+                        .collect::<Result<_, _>>()?,
+                    _ => return Err(GraphError::New(format!("`{name}` is not a vector"))),
+                }
+            }
+            Some(VectorData::Embed(_)) => return Err(unsupported("Embed")),
+            None => {
+                return Err(GraphError::New(
+                    "SearchV needs a vector to search with".to_string(),
+                ));
+            }
+        };
+        let k = match &search.k {
+            Some(k) => self.number(k)?,
+            None => return Err(GraphError::New("SearchV needs a result count".to_string())),
+        };
+
+        let (storage, arena) = (self.storage, self.arena);
+        let values = G::new(storage, self.ro(), arena)
+            .search_v::<fn(&HVector, &RoTxn) -> bool, _>(
+                arena.alloc_slice_copy(&data),
+                k,
+                arena.alloc_str(label),
+                None,
+            )
+            .collect::<Result<_, _>>()?;
+        Ok(Items::many(values))
+    }
+
+    /// Casts the values written to an item to the types its schema declares, allocating the
+    /// keys in the arena. `defaults` fills in the defaults of missing fields, as on insert.
+    fn properties(
+        &self,
+        label: &str,
+        values: Vec<(&str, Value)>,
+        defaults: bool,
+    ) -> Result<Vec<(&'arena str, Value)>, GraphError> {
+        let fields = fields(self.schema, label);
+        let mut props = Vec::with_capacity(values.len());
+        for (key, value) in values {
+            let value = match fields.iter().find(|field| field.name == key) {
+                Some(Field {
+                    field_type: FieldType::Embedding(_),
+                    ..
+                }) => return Err(unsupported("writing embedded vectors")),
+                Some(field) => stored(self.schema, value, &field.field_type, key)?,
+                None => value,
+            };
+            props.push((&*self.arena.alloc_str(key), value));
+        }
+        if defaults {
+            for field in fields {
+                if let Some(default) = &field.defaults
+                    && !props.iter().any(|(key, _)| *key == field.name)
+                {
+                    let value = stored(
+                        self.schema,
+                        default_value(default),
+                        &field.field_type,
+                        &field.name,
+                    )?;
+                    props.push((&*self.arena.alloc_str(&field.name), value));
+                }
+            }
+        }
+        Ok(props)
+    }
+
+    fn field_values<'f>(
+        &self,
+        fields: Option<&'f HashMap<String, ValueType>>,
+    ) -> Result<Vec<(&'f str, Value)>, GraphError> {
+        fields
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| Ok((key.as_str(), self.value_type(value)?)))
+            .collect()
+    }
+
+    fn properties_map(
+        &self,
+        props: Vec<(&'arena str, Value)>,
+    ) -> Option<ImmutablePropertiesMap<'arena>> {
+        (!props.is_empty())
+            .then(|| ImmutablePropertiesMap::new(props.len(), props.into_iter(), self.arena))
+    }
+
+    fn add_node(&mut self, add: &AddNode) -> Result<TraversalValue<'arena>, GraphError> {
+        let label = add.node_type.as_deref().unwrap_or_default();
+        let values = self.field_values(add.fields.as_ref())?;
+        let properties = self.properties_map(self.properties(label, values, true)?);
+        let indices = fields(self.schema, label)
+            .iter()
+            .filter(|field| field.is_indexed())
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+
+        let (storage, arena) = (self.storage, self.arena);
+        let txn = self.rw("AddN")?;
+        G::new_mut(storage, arena, txn)
+            .add_n(arena.alloc_str(label), properties, Some(indices.as_slice()))
+            .collect_to_obj()
+    }
+
+    fn add_edge(&mut self, add: &AddEdge) -> Result<Items<'arena>, GraphError> {
+        let label = add.edge_type.as_deref().unwrap_or_default();
+        let (Some(from), Some(to)) = (&add.connection.from_id, &add.connection.to_id) else {
+            return Err(GraphError::New("AddE needs both From and To".to_string()));
+        };
+        let (from, to) = (self.ids(from)?, self.ids(to)?);
+        let values = self.field_values(add.fields.as_ref())?;
+        let properties = self.properties_map(self.properties(label, values, true)?);
+
+        let (storage, arena) = (self.storage, self.arena);
+        let label = arena.alloc_str(label);
+        let txn = self.rw("AddE")?;
+        let mut edges = Vec::new();
+        for (from, to) in from.iter().cartesian_product(&to) {
+            let edge = G::new_mut(storage, arena, txn)
+                .add_edge(label, properties, *from, *to, false)
+                .collect_to_obj()?;
+            edges.push(edge);
+        }
+        Ok(Items {
+            single: edges.len() == 1,
+            values: edges,
+        })
+    }
+
+    fn update(
+        &mut self,
+        update: &Update,
+        items: Items<'arena>,
+    ) -> Result<Items<'arena>, GraphError> {
+        let mut values = Vec::with_capacity(update.fields.len());
+        for field in &update.fields {
+            let value = match &field.value.value {
+                FieldValueType::Literal(value) => value.clone(),
+                FieldValueType::Identifier(name) => self.variable(name)?.into_value(self.schema),
+                FieldValueType::Expression(expression) => self.value(expression, None)?,
+                _ => return Err(unsupported("this UPDATE value")),
+            };
+            values.push((field.key.as_str(), value));
+        }
+        // the values are cast once per type of the updated items
+        let mut props = HashMap::new();
+        for item in &items.values {
+            if !props.contains_key(item.label()) {
+                props.insert(
+                    item.label(),
+                    self.properties(item.label(), values.clone(), false)?,
+                );
+            }
+        }
+
+        let (storage, arena) = (self.storage, self.arena);
+        let txn = self.rw("UPDATE")?;
+        let values = G::new_mut_from_iter(storage, txn, items.values.into_iter(), arena)
+            .update_with(|item| props.get(item.label()).cloned().unwrap_or_default())
+            .collect::<Result<_, _>>()?;
+        Ok(Items {
+            values,
+            single: items.single,
+        })
+    }
+
+    fn drop(&mut self, items: Items<'arena>) -> Result<(), GraphError> {
+        let (storage, schema) = (self.storage, self.schema);
+        let txn = self.rw("DROP")?;
+        for item in items.values {
+            // vectors embedded on a node are dropped along with it
+            let embeddings = fields(schema, item.label())
+                .iter()
+                .filter(|field| matches!(field.field_type, FieldType::Embedding(_)))
+                .map(|field| field.name.as_str())
+                .collect::<Vec<_>>();
+            Drop::drop_traversal_with_embeddings(
+                std::iter::once(Ok(item)),
+                &embeddings,
+                storage,
+                txn,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn to_usize(value: &Value) -> Result<usize, GraphError> {
+    as_i128(value)
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| {
+            GraphError::New(format!(
+                "expected a non-negative integer, got {}",
+                value.inner_stringify()
+            ))
+        })
+}
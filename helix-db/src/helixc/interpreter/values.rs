@@ -0,0 +1,260 @@
+//! Conversions between HQL values, traversal items and schema field types.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use bumpalo::Bump;
+
+use crate::{
+    helix_engine::{traversal_core::traversal_value::TraversalValue, types::GraphError},
+    helixc::parser::types::{DefaultValue, EnumSchema, FieldType, Schema},
+    protocol::{date::Date, value::Value},
+    utils::{enums, id::ID},
+};
+
+/// Casts `value` to the field type it is written to, checking that it fits.
+///
+/// JSON numbers and literals are parsed as the widest numeric type, so they are narrowed here
+/// the same way the generated structs would deserialize them.
+pub(crate) fn coerce(value: Value, ty: &FieldType, name: &str) -> Result<Value, GraphError> {
+    let int = as_i128(&value);
+    let coerced = match (ty, &value) {
+        (_, Value::Empty)
+        | (FieldType::String, Value::String(_))
+        | (FieldType::Boolean, Value::Boolean(_))
+        | (FieldType::Uuid, Value::Id(_))
+        | (FieldType::Date, Value::Date(_))
+        // enum variants are checked by the analyzer
+        | (FieldType::Identifier(_), _) => Some(value.clone()),
+        (FieldType::F32, _) => as_f64(&value).map(|f| Value::F32(f as f32)),
+        (FieldType::F64, _) => as_f64(&value).map(Value::F64),
+        (FieldType::I8, _) => int.and_then(|n| i8::try_from(n).ok()).map(Value::I8),
+        (FieldType::I16, _) => int.and_then(|n| i16::try_from(n).ok()).map(Value::I16),
+        (FieldType::I32, _) => int.and_then(|n| i32::try_from(n).ok()).map(Value::I32),
+        (FieldType::I64, _) => int.and_then(|n| i64::try_from(n).ok()).map(Value::I64),
+        (FieldType::U8, _) => int.and_then(|n| u8::try_from(n).ok()).map(Value::U8),
+        (FieldType::U16, _) => int.and_then(|n| u16::try_from(n).ok()).map(Value::U16),
+        (FieldType::U32, _) => int.and_then(|n| u32::try_from(n).ok()).map(Value::U32),
+        (FieldType::U64, _) => int.and_then(|n| u64::try_from(n).ok()).map(Value::U64),
+        (FieldType::U128, _) => int.and_then(|n| u128::try_from(n).ok()).map(Value::U128),
+        (FieldType::Uuid, Value::String(id)) => parse_id(id).ok().map(|id| Value::Id(ID::from(id))),
+        (FieldType::Date, Value::String(_) | Value::I64(_) | Value::U64(_)) => {
+            Date::new(&value).ok().map(Value::Date)
+        }
+        (FieldType::Array(inner), Value::Array(items)) => Some(Value::Array(
+            items
+                .iter()
+                .map(|item| coerce(item.clone(), inner, name))
+                .collect::<Result<_, _>>()?,
+        )),
+        (FieldType::Object(fields), Value::Object(map)) => Some(Value::Object(
+            map.iter()
+                .map(|(key, item)| {
+                    let item = match fields.get(key) {
+                        Some(ty) => coerce(item.clone(), ty, name)?,
+                        None => item.clone(),
+                    };
+                    Ok((key.clone(), item))
+                })
+                .collect::<Result<_, GraphError>>()?,
+        )),
+        _ => None,
+    };
+    coerced.ok_or_else(|| {
+        GraphError::New(format!(
+            "`{name}` expects {ty}, got {}",
+            value.inner_stringify()
+        ))
+    })
+}
+
+/// Casts `value` to the field type it is written to, the way it is stored.
+///
+/// Enums are stored as the index of their variant, structs as the object of their fields and
+/// everything else as [`coerce`] casts it.
+pub(crate) fn stored(
+    schema: &Schema,
+    value: Value,
+    ty: &FieldType,
+    name: &str,
+) -> Result<Value, GraphError> {
+    let Some(enum_schema) = enum_schema(schema, ty) else {
+        return coerce(value, &schema.resolve_structs(ty), name);
+    };
+    let index = match &value {
+        Value::Empty => return Ok(value),
+        Value::String(variant) => enum_schema.variants.iter().position(|(_, v)| v == variant),
+        _ => None,
+    };
+    index
+        .map(|index| enums::variant_value(index, enum_schema.variants.len()))
+        .ok_or_else(|| {
+            GraphError::New(format!(
+                "`{name}` expects a variant of {ty}, got {}",
+                value.inner_stringify()
+            ))
+        })
+}
+
+/// Reads a property the way it was written, with enum variants read back as their names
+fn read(schema: &Schema, label: &str, key: &str, value: &Value) -> Value {
+    let variant = super::fields(schema, label)
+        .iter()
+        .find(|field| field.name == key)
+        .and_then(|field| enum_schema(schema, &field.field_type))
+        .zip(enums::variant_index(value))
+        .and_then(|(enum_schema, index)| enum_schema.variants.get(index));
+    match variant {
+        Some((_, name)) => Value::String(name.clone()),
+        None => value.clone(),
+    }
+}
+
+/// Reads back the enum variants held by the properties of an item as their names
+pub(super) fn read_item<'arena>(
+    schema: &Schema,
+    item: TraversalValue<'arena>,
+    arena: &'arena Bump,
+) -> TraversalValue<'arena> {
+    if schema.enum_schemas.is_empty() {
+        return item;
+    }
+    enums::map_properties(item, arena, |label, key, value| {
+        Some(read(schema, label, key, value)).filter(|read| read != value)
+    })
+}
+
+fn enum_schema<'s>(schema: &'s Schema, ty: &FieldType) -> Option<&'s EnumSchema> {
+    match ty {
+        FieldType::Identifier(name) => schema.enum_schemas.iter().find(|e| e.name.1 == *name),
+        _ => None,
+    }
+}
+
+pub(crate) fn default_value(default: &DefaultValue) -> Value {
+    match default {
+        DefaultValue::Now => Value::String(chrono::Utc::now().to_rfc3339()),
+        DefaultValue::String(s) => Value::String(s.clone()),
+        DefaultValue::F32(f) => Value::F32(*f),
+        DefaultValue::F64(f) => Value::F64(*f),
+        DefaultValue::I8(i) => Value::I8(*i),
+        DefaultValue::I16(i) => Value::I16(*i),
+        DefaultValue::I32(i) => Value::I32(*i),
+        DefaultValue::I64(i) => Value::I64(*i),
+        DefaultValue::U8(i) => Value::U8(*i),
+        DefaultValue::U16(i) => Value::U16(*i),
+        DefaultValue::U32(i) => Value::U32(*i),
+        DefaultValue::U64(i) => Value::U64(*i),
+        DefaultValue::U128(i) => Value::U128(*i),
+        DefaultValue::Boolean(b) => Value::Boolean(*b),
+        DefaultValue::Empty => Value::Empty,
+    }
+}
+
+pub(crate) fn parse_id(id: &str) -> Result<u128, GraphError> {
+    uuid::Uuid::parse_str(id)
+        .map(|id| id.as_u128())
+        .map_err(|_| GraphError::New(format!("`{id}` is not a valid id")))
+}
+
+pub(super) fn as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::I8(v) => Some(*v as i128),
+        Value::I16(v) => Some(*v as i128),
+        Value::I32(v) => Some(*v as i128),
+        Value::I64(v) => Some(*v as i128),
+        Value::U8(v) => Some(*v as i128),
+        Value::U16(v) => Some(*v as i128),
+        Value::U32(v) => Some(*v as i128),
+        Value::U64(v) => Some(*v as i128),
+        Value::U128(v) => i128::try_from(*v).ok(),
+        _ => None,
+    }
+}
+
+pub(super) fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::F32(v) => Some(*v as f64),
+        Value::F64(v) => Some(*v),
+        value => as_i128(value).map(|v| v as f64),
+    }
+}
+
+/// Orders two values, comparing mixed integers and floats as `f64`.
+pub(super) fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::F32(_) | Value::F64(_), _) | (_, Value::F32(_) | Value::F64(_)) => {
+            match (as_f64(a), as_f64(b)) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => a.cmp(b),
+            }
+        }
+        _ => a.cmp(b),
+    }
+}
+
+/// Compares two values, matching ids against their string form.
+pub(super) fn equals(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Id(id), Value::String(s)) | (Value::String(s), Value::Id(id)) => {
+            parse_id(s).is_ok_and(|s| s == **id)
+        }
+        _ => a == b,
+    }
+}
+
+/// Reads a property of an item, including its `id` and `label`.
+pub(super) fn property(schema: &Schema, item: &TraversalValue, key: &str) -> Value {
+    match (item, key) {
+        (TraversalValue::Value(value), _) => value.get_path(key).cloned().unwrap_or_default(),
+        (_, "id") => Value::Id(ID::from(item.id())),
+        (_, "label") => Value::String(item.label().to_string()),
+        (TraversalValue::Edge(edge), "from_node") => Value::Id(ID::from(edge.from_node)),
+        (TraversalValue::Edge(edge), "to_node") => Value::Id(ID::from(edge.to_node)),
+        (TraversalValue::Vector(vector), "score") => Value::F64(vector.score()),
+        _ => match item.get_property(key) {
+            Some(value) => read(schema, item.label(), key, value),
+            None => Value::Empty,
+        },
+    }
+}
+
+/// The fields of an item as an object, the way it is returned
+pub(super) fn item_fields(schema: &Schema, item: &TraversalValue) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+    let properties = match item {
+        TraversalValue::Node(node) => node.properties,
+        TraversalValue::Edge(edge) => {
+            fields.insert("from_node".to_string(), Value::Id(ID::from(edge.from_node)));
+            fields.insert("to_node".to_string(), Value::Id(ID::from(edge.to_node)));
+            edge.properties
+        }
+        TraversalValue::Vector(vector) => {
+            fields.insert(
+                "data".to_string(),
+                Value::Array(vector.data.iter().copied().map(Value::F64).collect()),
+            );
+            fields.insert("score".to_string(), Value::F64(vector.score()));
+            vector.properties
+        }
+        TraversalValue::VectorNodeWithoutVectorData(vector) => vector.properties,
+        TraversalValue::Value(Value::Object(object)) => return object.clone(),
+        _ => return fields,
+    };
+    fields.insert("id".to_string(), Value::Id(ID::from(item.id())));
+    fields.insert("label".to_string(), Value::String(item.label().to_string()));
+    if let Some(properties) = properties {
+        for (key, value) in properties.iter() {
+            fields.insert(key.to_string(), read(schema, item.label(), key, value));
+        }
+    }
+    fields
+}
+
+/// Turns an item into a plain value, with graph items becoming objects of their fields
+pub(super) fn item_value(schema: &Schema, item: TraversalValue) -> Value {
+    match item {
+        TraversalValue::Value(value) => value,
+        TraversalValue::Empty => Value::Empty,
+        item => Value::Object(item_fields(schema, &item)),
+    }
+}
@@ -4,5 +4,8 @@
 //! HelixQL compiler, analyzer, and transpiler.
 
 pub mod analyzer;
+pub mod formatter;
 pub mod generator;
+pub mod interpreter;
+pub mod migration;
 pub mod parser;
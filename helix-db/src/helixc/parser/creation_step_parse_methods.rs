@@ -1,8 +1,11 @@
 use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
     location::HasLoc,
-    types::{AddEdge, AddNode, AddVector, Embed, EvaluatesToString, VectorData},
-    utils::PairTools,
+    types::{
+        AddEdge, AddNode, AddVector, Embed, EvaluatesToString, Expression, ExpressionType,
+        UpsertEdge, UpsertNode, VectorData,
+    },
+    utils::{PairTools, PairsTools},
 };
 use pest::iterators::Pair;
 
@@ -148,6 +151,57 @@ impl HelixParser {
             loc: pair.loc(),
         })
     }
+
+    pub(super) fn parse_upsert_node(&self, pair: Pair<Rule>) -> Result<UpsertNode, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let node_type = pairs.try_next()?.as_str().to_string();
+
+        let match_pair = pairs.try_next()?;
+        let match_loc = match_pair.loc();
+        let mut fields = self.parse_property_assignments(match_pair)?;
+        if fields.len() != 1 {
+            return Err(ParserError::from(format!(
+                "UpsertN<{node_type}> must match on exactly one indexed field"
+            )));
+        }
+        let match_field = fields.keys().next().cloned().unwrap_or_default();
+
+        let update_fields = match pairs.next() {
+            Some(update_pair) => {
+                let update = self.parse_property_assignments(update_pair)?;
+                let names = update.keys().cloned().collect();
+                fields.extend(update);
+                names
+            }
+            None => vec![],
+        };
+
+        Ok(UpsertNode {
+            match_field: (match_loc, match_field),
+            insert: Box::new(Expression {
+                loc: loc.clone(),
+                expr: ExpressionType::AddNode(AddNode {
+                    node_type: Some(node_type),
+                    fields: Some(fields),
+                    loc: loc.clone(),
+                }),
+            }),
+            update_fields,
+            loc,
+        })
+    }
+
+    pub(super) fn parse_upsert_edge(&self, pair: Pair<Rule>) -> Result<UpsertEdge, ParserError> {
+        let loc = pair.loc();
+        Ok(UpsertEdge {
+            insert: Box::new(Expression {
+                loc: loc.clone(),
+                expr: ExpressionType::AddEdge(self.parse_add_edge(pair, false)?),
+            }),
+            loc,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -411,6 +465,58 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // ============================================================================
+    // Upsert Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_upsert_node_with_created_binding() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+
+            QUERY upsertUser(email: String, name: String) =>
+                user, created <- UpsertN<User>({email: email}, {name: name})
+                RETURN user, created
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_upsert_node_multiple_match_fields_fails() {
+        let source = r#"
+            N::User { INDEX email: String, name: String }
+
+            QUERY upsertUser(email: String, name: String) =>
+                user <- UpsertN<User>({email: email, name: name})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_upsert_edge() {
+        let source = r#"
+            N::User { name: String }
+            E::Follows { From: User, To: User, Properties: { weight: F64 } }
+
+            QUERY follow(from: ID, to: ID, weight: F64) =>
+                a <- N<User>(from)
+                b <- N<User>(to)
+                edge <- UpsertE<Follows>({weight: weight})::From(a)::To(b)
+                RETURN edge
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
     // ============================================================================
     // Edge Cases
     // ============================================================================
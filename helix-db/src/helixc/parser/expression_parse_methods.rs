@@ -3,9 +3,10 @@ use crate::{
         HelixParser, ParserError, Rule,
         location::{HasLoc, Loc},
         types::{
-            Assignment, BM25Search, Embed, EvaluatesToNumber, EvaluatesToNumberType,
+            Assignment, BM25Search, Coalesce, Embed, EvaluatesToNumber, EvaluatesToNumberType,
             EvaluatesToString, ExistsExpression, Expression, ExpressionType, ForLoop, ForLoopVars,
-            MathFunction, MathFunctionCall, SearchVector, ValueType, VectorData,
+            IfElse, MathFunction, MathFunctionCall, ScalarFunction, ScalarFunctionCall,
+            SearchVector, Statement, StatementType, ValueType, VectorData,
         },
         utils::{PairTools, PairsTools},
     },
@@ -17,10 +18,20 @@ impl HelixParser {
     pub(super) fn parse_assignment(&self, pair: Pair<Rule>) -> Result<Assignment, ParserError> {
         let mut pairs = pair.clone().into_inner();
         let variable = pairs.try_next()?.as_str().to_string();
-        let value = self.parse_expression(pairs.try_next()?)?;
+        let mut value_pair = pairs.try_next()?;
+        let created_variable = match value_pair.as_rule() {
+            Rule::identifier => {
+                let created = (value_pair.loc(), value_pair.as_str().to_string());
+                value_pair = pairs.try_next()?;
+                Some(created)
+            }
+            _ => None,
+        };
+        let value = self.parse_expression(value_pair)?;
 
         Ok(Assignment {
             variable,
+            created_variable,
             value,
             loc: pair.loc(),
         })
@@ -125,6 +136,14 @@ impl HelixParser {
                 loc: pair.loc(),
                 expr: ExpressionType::AddEdge(self.parse_add_edge(pair, false)?),
             }),
+            Rule::UpsertN => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::UpsertNode(self.parse_upsert_node(pair)?),
+            }),
+            Rule::UpsertE => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::UpsertEdge(self.parse_upsert_edge(pair)?),
+            }),
             Rule::search_vector => Ok(Expression {
                 loc: pair.loc(),
                 expr: ExpressionType::SearchVector(self.parse_search_vector(pair)?),
@@ -141,6 +160,14 @@ impl HelixParser {
                 loc: pair.loc(),
                 expr: ExpressionType::MathFunctionCall(self.parse_math_function_call(pair)?),
             }),
+            Rule::scalar_function_call => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::ScalarFunctionCall(self.parse_scalar_function_call(pair)?),
+            }),
+            Rule::coalesce => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::Coalesce(self.parse_coalesce(pair)?),
+            }),
             _ => Err(ParserError::from(format!(
                 "Unexpected expression type: {:?}",
                 pair.as_rule()
@@ -238,6 +265,20 @@ impl HelixParser {
                     },
                 })
             }
+            Rule::traversal | Rule::id_traversal => Ok(Expression {
+                loc: expression.loc(),
+                expr: ExpressionType::Traversal(Box::new(self.parse_traversal(expression)?)),
+            }),
+            Rule::identifier => Ok(Expression {
+                loc: expression.loc(),
+                expr: ExpressionType::Identifier(expression.as_str().to_string()),
+            }),
+            Rule::scalar_function_call => Ok(Expression {
+                loc: expression.loc(),
+                expr: ExpressionType::ScalarFunctionCall(
+                    self.parse_scalar_function_call(expression)?,
+                ),
+            }),
 
             _ => unreachable!(),
         }
@@ -401,6 +442,53 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_if_else(&self, pair: Pair<Rule>) -> Result<IfElse, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let condition = self.parse_boolean_expression(pairs.try_next()?)?;
+        let then_statements = self.parse_query_body(pairs.try_next()?)?;
+
+        // the else branch is either a nested if statement (ELSE IF) or a block
+        let else_statements = match pairs.next() {
+            Some(else_stmt) => {
+                let branch = else_stmt.try_inner_next()?;
+                match branch.as_rule() {
+                    Rule::if_stmt => Some(vec![Statement {
+                        loc: branch.loc(),
+                        statement: StatementType::IfElse(self.parse_if_else(branch)?),
+                    }]),
+                    Rule::query_body => Some(self.parse_query_body(branch)?),
+                    _ => {
+                        return Err(ParserError::from(format!(
+                            "Unexpected rule in ELSE: {:?}",
+                            branch.as_rule()
+                        )));
+                    }
+                }
+            }
+            None => None,
+        };
+
+        Ok(IfElse {
+            condition,
+            then_statements,
+            else_statements,
+            loc,
+        })
+    }
+
+    pub(super) fn parse_coalesce(&self, pair: Pair<Rule>) -> Result<Coalesce, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let value = self.parse_expression(pairs.try_next()?)?;
+        let fallback = self.parse_expression(pairs.try_next()?)?;
+        Ok(Coalesce {
+            value: Box::new(value),
+            fallback: Box::new(fallback),
+            loc,
+        })
+    }
+
     pub(super) fn parse_search_vector(
         &self,
         pair: Pair<Rule>,
@@ -572,6 +660,57 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_scalar_function_call(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<ScalarFunctionCall, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+
+        let function_name = inner
+            .next()
+            .ok_or_else(|| ParserError::from("Missing function name"))?
+            .as_str();
+        let function = ScalarFunction::from_name(function_name)
+            .ok_or_else(|| ParserError::from(format!("Unknown function: {function_name}")))?;
+
+        // each scalar_function_arg is either an anonymous traversal or any expression
+        let mut args = Vec::new();
+        if let Some(args_pair) = inner.next() {
+            for arg_pair in args_pair.into_inner() {
+                let arg = arg_pair.try_inner_next()?;
+                args.push(match arg.as_rule() {
+                    Rule::anonymous_traversal => Expression {
+                        loc: arg.loc(),
+                        expr: ExpressionType::Traversal(Box::new(self.parse_anon_traversal(arg)?)),
+                    },
+                    _ => self.parse_expression(arg)?,
+                });
+            }
+        }
+
+        let (min_arity, max_arity) = function.arity();
+        if args.len() < min_arity || args.len() > max_arity {
+            let expected = match (min_arity, max_arity) {
+                (min, max) if min == max => min.to_string(),
+                (min, usize::MAX) => format!("at least {min}"),
+                (min, max) => format!("{min} to {max}"),
+            };
+            return Err(ParserError::from(format!(
+                "Function {} expects {} argument(s), but got {}",
+                function_name,
+                expected,
+                args.len()
+            )));
+        }
+
+        Ok(ScalarFunctionCall {
+            function,
+            args,
+            loc,
+        })
+    }
+
     pub(super) fn parse_math_expression(
         &self,
         pair: Pair<Rule>,
@@ -592,6 +731,10 @@ impl HelixParser {
                         loc: inner_inner.loc(),
                         expr: ExpressionType::MathFunctionCall(self.parse_math_function_call(inner_inner)?),
                     }),
+                    Rule::scalar_function_call => Ok(Expression {
+                        loc: inner_inner.loc(),
+                        expr: ExpressionType::ScalarFunctionCall(self.parse_scalar_function_call(inner_inner)?),
+                    }),
                     Rule::float => inner_inner
                         .as_str()
                         .parse()
@@ -668,7 +811,10 @@ impl HelixParser {
 
 #[cfg(test)]
 mod tests {
-    use crate::helixc::parser::{write_to_temp_file, HelixParser};
+    use crate::helixc::parser::{
+        types::{ExpressionType, IfElse, ScalarFunction, ScalarFunctionCall, StatementType},
+        write_to_temp_file, HelixParser,
+    };
 
     // ============================================================================
     // Literal Expression Tests
@@ -920,6 +1066,135 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // ============================================================================
+    // If / Coalesce Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_if_else_if_else() {
+        let source = r#"
+            N::Person { name: String, age: U32 }
+
+            QUERY testQuery(id: ID, flag: Boolean) =>
+                person <- N<Person>(id)
+                IF flag {
+                    a <- person
+                } ELSE IF person::{age}::GT(18) {
+                    b <- person
+                } ELSE {
+                    c <- person
+                }
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content).unwrap();
+        let query = &result.queries[0];
+        let StatementType::IfElse(if_else) = &query.statements[1].statement else {
+            panic!("expected IF statement");
+        };
+        assert!(matches!(if_else.condition.expr, ExpressionType::Identifier(ref name) if name == "flag"));
+        assert_eq!(if_else.then_statements.len(), 1);
+
+        // ELSE IF is stored as an else branch holding the nested IF
+        let else_statements = if_else.else_statements.as_ref().unwrap();
+        assert_eq!(else_statements.len(), 1);
+        let StatementType::IfElse(nested) = &else_statements[0].statement else {
+            panic!("expected nested IF statement");
+        };
+        assert!(matches!(nested.condition.expr, ExpressionType::Traversal(_)));
+        assert_eq!(nested.else_statements.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_if_inside_for_loop() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY testQuery(ids: [ID]) =>
+                FOR id IN ids {
+                    IF !EXISTS(N<Person>(id)) {
+                        AddN<Person>({name: "new"})
+                    }
+                }
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content).unwrap();
+        let StatementType::ForLoop(for_loop) = &result.queries[0].statements[0].statement else {
+            panic!("expected FOR statement");
+        };
+        assert!(matches!(
+            for_loop.statements[0].statement,
+            StatementType::IfElse(IfElse { else_statements: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_coalesce() {
+        let source = r#"
+            QUERY testQuery(nickname?: String) =>
+                name <- COALESCE(nickname, "anonymous")
+                RETURN name
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content).unwrap();
+        let StatementType::Assignment(assignment) = &result.queries[0].statements[0].statement else {
+            panic!("expected assignment");
+        };
+        let ExpressionType::Coalesce(coalesce) = &assignment.value.expr else {
+            panic!("expected COALESCE");
+        };
+        assert!(matches!(coalesce.value.expr, ExpressionType::Identifier(ref name) if name == "nickname"));
+        assert!(matches!(coalesce.fallback.expr, ExpressionType::StringLiteral(ref s) if s == "anonymous"));
+    }
+
+    #[test]
+    fn test_parse_scalar_function_call() {
+        let source = r#"
+            N::User { name: String, created_at: Date }
+
+            QUERY testQuery(prefix: String) =>
+                users <- N<User>::WHERE(STARTS_WITH(LOWER(_::{name}), prefix))
+                cutoff <- DATE_ADD(NOW(), 7, "day")
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content).unwrap();
+        let StatementType::Assignment(assignment) = &result.queries[0].statements[1].statement
+        else {
+            panic!("expected assignment");
+        };
+        let ExpressionType::ScalarFunctionCall(call) = &assignment.value.expr else {
+            panic!("expected DATE_ADD");
+        };
+        assert_eq!(call.function, ScalarFunction::DateAdd);
+        assert_eq!(call.args.len(), 3);
+        assert!(matches!(
+            call.args[0].expr,
+            ExpressionType::ScalarFunctionCall(ScalarFunctionCall {
+                function: ScalarFunction::Now,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_scalar_function_wrong_arity() {
+        let source = r#"
+            QUERY testQuery(name: String) =>
+                short <- SUBSTRING(name)
+                RETURN short
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
     // ============================================================================
     // BM25 Search Tests
     // ============================================================================
@@ -1094,6 +1369,27 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_array_predicates_and_field_paths() {
+        let source = r#"
+            N::Person { name: String, tags: [String], address: { city: String } }
+
+            QUERY testQuery(tag: String, city: String) =>
+                people <- N<Person>::WHERE(
+                    AND(
+                        _::{tags}::ANY(EQ(tag)),
+                        _::{tags}::ALL(NEQ("")),
+                        _::{address.city}::EQ(city)
+                    )
+                )::{name, address.city, town: address.city}
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_parse_multiple_assignments() {
         let source = r#"
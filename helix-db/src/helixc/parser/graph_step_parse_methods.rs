@@ -2,10 +2,12 @@ use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
     location::HasLoc,
     types::{
-        Aggregate, BooleanOp, BooleanOpType, Closure, Exclude, Expression, ExpressionType, FieldAddition,
-        FieldValue, FieldValueType, GraphStep, GraphStepType, GroupBy, IdType, MMRDistance, Object, OrderBy,
+        Aggregate, AggregateField, AggregateFunction, AllPaths, BooleanOp, BooleanOpType, Closure, Exclude, Expression, ExpressionType, FieldAddition,
+        EvaluatesToNumber, EvaluatesToNumberType, FieldValue, FieldValueType, GraphAlgorithm,
+        GraphAlgorithmType, GraphStep, GraphStepType, GroupBy, IdType,
+        KShortestPaths, MMRDistance, NullsOrder, Object, OrderBy, OrderKey, OutN,
         OrderByType, RerankMMR, RerankRRF, ShortestPath, ShortestPathAStar, ShortestPathBFS,
-        ShortestPathDijkstras, Step, StepType, Update,
+        ShortestPathDijkstras, ShortestPathsFrom, Step, StepType, Update, WeightExpression,
     },
     utils::{PairTools, PairsTools},
 };
@@ -17,19 +19,57 @@ impl HelixParser {
     /// #### Example
     /// ```rs
     /// ::ORDER<Asc>(_::{age})
+    /// ::ORDER<Asc>(_::{last}, Desc: _::{age} NULLS FIRST)
     /// ```
     pub(super) fn parse_order_by(&self, pair: Pair<Rule>) -> Result<OrderBy, ParserError> {
-        let mut inner = pair.clone().into_inner();
-        let order_by_type = match inner.try_next_inner().try_next()?.as_rule() {
-            Rule::asc => OrderByType::Asc,
-            Rule::desc => OrderByType::Desc,
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let default_order = self.parse_order_by_type(inner.try_next()?)?;
+        let keys = inner
+            .map(|key| self.parse_order_key(key, default_order.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(OrderBy { loc, keys })
+    }
+
+    fn parse_order_by_type(&self, pair: Pair<Rule>) -> Result<OrderByType, ParserError> {
+        match pair.try_inner_next()?.as_rule() {
+            Rule::asc => Ok(OrderByType::Asc),
+            Rule::desc => Ok(OrderByType::Desc),
             _ => unreachable!(),
-        };
-        let expression = self.parse_expression(inner.try_next()?)?;
-        Ok(OrderBy {
-            loc: pair.loc(),
+        }
+    }
+
+    /// Parses a single key of an order by step, which falls back to the direction
+    /// given in `ORDER<..>` unless it names its own
+    fn parse_order_key(
+        &self,
+        pair: Pair<Rule>,
+        default_order: OrderByType,
+    ) -> Result<OrderKey, ParserError> {
+        let loc = pair.loc();
+        let mut order_by_type = default_order;
+        let mut expression = None;
+        let mut nulls = None;
+        for p in pair.into_inner() {
+            match p.as_rule() {
+                Rule::order_by_type => order_by_type = self.parse_order_by_type(p)?,
+                Rule::to_order => expression = Some(self.parse_expression(p)?),
+                Rule::nulls_order => {
+                    nulls = Some(match p.try_inner_next()?.as_rule() {
+                        Rule::nulls_first => NullsOrder::First,
+                        _ => NullsOrder::Last,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(OrderKey {
+            loc,
             order_by_type,
-            expression: Box::new(expression),
+            expression: Box::new(expression.ok_or_else(|| {
+                ParserError::from("Missing expression in order by step".to_string())
+            })?),
+            nulls,
         })
     }
 
@@ -56,6 +96,7 @@ impl HelixParser {
     /// #### Example
     /// ```rs
     /// ::GT(1)
+    /// ::ANY(EQ("admin"))
     /// ```
     pub(super) fn parse_bool_operation(&self, pair: Pair<Rule>) -> Result<BooleanOp, ParserError> {
         let inner = pair.clone().try_inner_next()?;
@@ -104,6 +145,22 @@ impl HelixParser {
                 loc: pair.loc(),
                 op: BooleanOpType::IsIn(Box::new(self.parse_expression(inner)?)),
             },
+            Rule::IS_NULL => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::IsNull,
+            },
+            Rule::ANY_ELEMENT => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::Any(Box::new(
+                    self.parse_bool_operation(inner.try_inner_next()?)?,
+                )),
+            },
+            Rule::ALL_ELEMENTS => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::All(Box::new(
+                    self.parse_bool_operation(inner.try_inner_next()?)?,
+                )),
+            },
             _ => return Err(ParserError::from("Invalid boolean operation")),
         };
         Ok(expr)
@@ -128,6 +185,7 @@ impl HelixParser {
     /// #### Example
     /// ```rs
     /// ::{username: name}
+    /// ::{city: address.city}
     /// ```
     pub(super) fn parse_object_step(&self, pair: Pair<Rule>) -> Result<Object, ParserError> {
         let mut fields = Vec::new();
@@ -138,9 +196,13 @@ impl HelixParser {
                 continue;
             }
             let mut pairs = p.clone().into_inner();
-            let prop_key = pairs.try_next()?.as_str().to_string();
+            let mut prop_key = pairs.try_next()?.as_str().to_string();
             let field_addition = match pairs.next() {
                 Some(p) => match p.as_rule() {
+                    Rule::field_path => FieldValue {
+                        loc: p.loc(),
+                        value: FieldValueType::Identifier(p.as_str().to_string()),
+                    },
                     Rule::evaluates_to_anything => FieldValue {
                         loc: p.loc(),
                         value: FieldValueType::Expression(self.parse_expression(p)?),
@@ -163,10 +225,17 @@ impl HelixParser {
                     },
                     _ => self.parse_new_field_value(p)?,
                 },
-                None if !prop_key.is_empty() => FieldValue {
-                    loc: p.loc(),
-                    value: FieldValueType::Identifier(prop_key.clone()),
-                },
+                None if !prop_key.is_empty() => {
+                    let value = FieldValueType::Identifier(prop_key.clone());
+                    // `{address.city}` is returned under the name of the last field in the path
+                    if let Some((_, last)) = prop_key.rsplit_once('.') {
+                        prop_key = last.to_string();
+                    }
+                    FieldValue {
+                        loc: p.loc(),
+                        value,
+                    }
+                }
                 None => FieldValue {
                     loc: p.loc(),
                     value: FieldValueType::Empty,
@@ -221,27 +290,93 @@ impl HelixParser {
 
     pub(super) fn parse_aggregate(&self, pair: Pair<Rule>) -> Result<Aggregate, ParserError> {
         let loc = pair.loc();
-        let identifiers = pair
-            .into_inner()
-            .map(|i| i.as_str().to_string())
-            .collect::<Vec<_>>();
+        let (properties, aggregations) = self.parse_group_args(pair)?;
 
         Ok(Aggregate {
             loc,
-            properties: identifiers,
+            properties,
+            aggregations,
         })
     }
 
     pub(super) fn parse_group_by(&self, pair: Pair<Rule>) -> Result<GroupBy, ParserError> {
         let loc = pair.loc();
-        let identifiers = pair
-            .into_inner()
-            .map(|i| i.as_str().to_string())
-            .collect::<Vec<_>>();
+        let (properties, aggregations) = self.parse_group_args(pair)?;
 
         Ok(GroupBy {
             loc,
-            properties: identifiers,
+            properties,
+            aggregations,
+        })
+    }
+
+    /// Splits the arguments of `GROUP_BY`/`AGGREGATE_BY` into the properties to group
+    /// by and the named aggregates to compute per group
+    fn parse_group_args(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<(Vec<String>, Vec<AggregateField>), ParserError> {
+        let mut properties = Vec::new();
+        let mut aggregations = Vec::new();
+        for p in pair.into_inner() {
+            match p.as_rule() {
+                Rule::identifier => properties.push(p.as_str().to_string()),
+                Rule::aggregate_field => aggregations.push(self.parse_aggregate_field(p)?),
+                other => {
+                    return Err(ParserError::from(format!(
+                        "Unexpected rule in group arguments: {other:?}"
+                    )));
+                }
+            }
+        }
+        Ok((properties, aggregations))
+    }
+
+    fn parse_aggregate_field(&self, pair: Pair<Rule>) -> Result<AggregateField, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let name = inner.try_next()?.as_str().to_string();
+        let function_pair = inner.try_next()?;
+        let function = match function_pair.as_rule() {
+            Rule::count => AggregateFunction::Count,
+            Rule::count_distinct => {
+                AggregateFunction::CountDistinct(function_pair.try_inner_next()?.as_str().to_string())
+            }
+            Rule::percentile => {
+                let mut args = function_pair.into_inner();
+                let field = args.try_next()?.as_str().to_string();
+                let rank = args.try_next()?.as_str().parse::<f64>().map_err(|_| {
+                    ParserError::from("Invalid percentile rank".to_string())
+                })?;
+                AggregateFunction::Percentile(field, rank)
+            }
+            Rule::aggregate_call => {
+                let mut args = function_pair.into_inner();
+                let function = args.try_next()?.as_str().to_string();
+                let field = args.try_next()?.as_str().to_string();
+                match function.as_str() {
+                    "SUM" => AggregateFunction::Sum(field),
+                    "AVG" => AggregateFunction::Avg(field),
+                    "MIN" => AggregateFunction::Min(field),
+                    "MAX" => AggregateFunction::Max(field),
+                    "COLLECT" => AggregateFunction::Collect(field),
+                    other => {
+                        return Err(ParserError::from(format!(
+                            "Unknown aggregate function: {other}"
+                        )));
+                    }
+                }
+            }
+            other => {
+                return Err(ParserError::from(format!(
+                    "Unexpected aggregate function: {other:?}"
+                )));
+            }
+        };
+        Ok(AggregateField {
+            loc,
+            name,
+            function,
         })
     }
 
@@ -268,6 +403,14 @@ impl HelixParser {
                 loc: step_pair.loc(),
                 step: StepType::Range(self.parse_range(step_pair)?),
             }),
+            Rule::after_step => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::After(Box::new(self.parse_expression(step_pair)?)),
+            }),
+            Rule::cursor => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::Cursor,
+            }),
 
             Rule::bool_operations => Ok(Step {
                 loc: step_pair.loc(),
@@ -382,6 +525,10 @@ impl HelixParser {
                     step: GraphStepType::Out(types),
                 }
             }
+            Rule::out_n => GraphStep {
+                loc: pair.loc(),
+                step: GraphStepType::OutN(self.parse_out_n(pair)?),
+            },
             Rule::in_nodes => {
                 let types = types(&pair)?;
                 GraphStep {
@@ -641,6 +788,22 @@ impl HelixParser {
                     }),
                 }
             }
+            Rule::k_shortest_paths => GraphStep {
+                loc: pair.loc(),
+                step: GraphStepType::KShortestPaths(self.parse_k_shortest_paths(pair)?),
+            },
+            Rule::all_paths => GraphStep {
+                loc: pair.loc(),
+                step: GraphStepType::AllPaths(self.parse_all_paths(pair)?),
+            },
+            Rule::shortest_paths_from => GraphStep {
+                loc: pair.loc(),
+                step: GraphStepType::ShortestPathsFrom(self.parse_shortest_paths_from(pair)?),
+            },
+            Rule::graph_algorithm => GraphStep {
+                loc: pair.loc(),
+                step: GraphStepType::GraphAlgorithm(self.parse_graph_algorithm(pair)?),
+            },
 
             Rule::search_vector => GraphStep {
                 loc: pair.loc(),
@@ -656,6 +819,226 @@ impl HelixParser {
         Ok(step)
     }
 
+    /// Parses a variable-length out step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::OutN<Knows>(1..3, UNTIL(_::{name}::EQ("Bob")), PATHS)
+    /// ```
+    pub(super) fn parse_out_n(&self, pair: Pair<Rule>) -> Result<OutN, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let label = inner.try_next_inner()?.try_next()?.as_str().to_string();
+        let mut range = inner.try_next_inner()?;
+        let mut depth = || -> Result<usize, ParserError> {
+            let p = range.try_next()?;
+            p.as_str()
+                .parse::<usize>()
+                .map_err(|_| ParserError::from(format!("Invalid depth `{}`", p.as_str())))
+        };
+        let (min_depth, max_depth) = (depth()?, depth()?);
+
+        let mut until = None;
+        let mut emit_paths = false;
+        for p in inner {
+            match p.as_rule() {
+                Rule::until => until = Some(Box::new(self.parse_expression(p)?)),
+                Rule::emit_paths => emit_paths = true,
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(OutN {
+            loc,
+            label,
+            min_depth,
+            max_depth,
+            until,
+            emit_paths,
+        })
+    }
+
+    /// Parses a k shortest paths step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::KShortestPaths<Road>(3, _::{distance})::To(end)
+    /// ```
+    pub(super) fn parse_k_shortest_paths(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<KShortestPaths, ParserError> {
+        let loc = pair.loc();
+        let mut type_arg = None;
+        let mut k = None;
+        let mut weight_expr = None;
+        let (mut from, mut to) = (None, None);
+        for p in pair.into_inner() {
+            match p.as_rule() {
+                Rule::type_args => type_arg = Some(p.try_inner_next()?.as_str().to_string()),
+                Rule::integer => {
+                    k = Some(EvaluatesToNumber {
+                        loc: p.loc(),
+                        value: EvaluatesToNumberType::I32(
+                            p.as_str()
+                                .parse::<i32>()
+                                .map_err(|_| ParserError::from("Invalid integer value"))?,
+                        ),
+                    })
+                }
+                Rule::identifier => {
+                    k = Some(EvaluatesToNumber {
+                        loc: p.loc(),
+                        value: EvaluatesToNumberType::Identifier(p.as_str().to_string()),
+                    })
+                }
+                Rule::math_expression => {
+                    weight_expr = Some(WeightExpression::Expression(Box::new(
+                        self.parse_math_expression(p)?,
+                    )))
+                }
+                Rule::to_from => (from, to) = self.parse_path_endpoints(p)?,
+                _ => {}
+            }
+        }
+        Ok(KShortestPaths {
+            loc,
+            from,
+            to,
+            type_arg,
+            k: k.ok_or_else(|| ParserError::from("KShortestPaths expects a number of paths"))?,
+            weight_expr: Some(weight_expr.unwrap_or(WeightExpression::Default)),
+        })
+    }
+
+    /// Parses an all paths step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::AllPaths<Transfer>(4)::To(account)
+    /// ```
+    pub(super) fn parse_all_paths(&self, pair: Pair<Rule>) -> Result<AllPaths, ParserError> {
+        let loc = pair.loc();
+        let mut type_arg = None;
+        let mut max_depth = 0;
+        let (mut from, mut to) = (None, None);
+        for p in pair.into_inner() {
+            match p.as_rule() {
+                Rule::type_args => type_arg = Some(p.try_inner_next()?.as_str().to_string()),
+                Rule::integer => {
+                    max_depth = p
+                        .as_str()
+                        .parse::<usize>()
+                        .map_err(|_| ParserError::from("Invalid integer value"))?
+                }
+                Rule::to_from => (from, to) = self.parse_path_endpoints(p)?,
+                _ => {}
+            }
+        }
+        Ok(AllPaths {
+            loc,
+            from,
+            to,
+            type_arg,
+            max_depth,
+        })
+    }
+
+    /// Parses a single source shortest paths step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::ShortestPathsFrom<Road>(_::{distance})::WITHIN(10.0)
+    /// ```
+    pub(super) fn parse_shortest_paths_from(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<ShortestPathsFrom, ParserError> {
+        let loc = pair.loc();
+        let mut type_arg = None;
+        let mut weight_expr = None;
+        let mut max_distance = None;
+        for p in pair.into_inner() {
+            match p.as_rule() {
+                Rule::type_args => type_arg = Some(p.try_inner_next()?.as_str().to_string()),
+                Rule::math_expression => {
+                    weight_expr = Some(WeightExpression::Expression(Box::new(
+                        self.parse_math_expression(p)?,
+                    )))
+                }
+                Rule::within => max_distance = Some(self.parse_expression(p.try_inner_next()?)?),
+                _ => {}
+            }
+        }
+        Ok(ShortestPathsFrom {
+            loc,
+            type_arg,
+            weight_expr,
+            max_distance,
+        })
+    }
+
+    /// Parses a graph analytics step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::PageRank<Follows>(0.85, 20)
+    /// ```
+    pub(super) fn parse_graph_algorithm(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<GraphAlgorithm, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let algorithm = match inner.try_next()?.as_str() {
+            "PageRank" => GraphAlgorithmType::PageRank,
+            "ConnectedComponents" => GraphAlgorithmType::ConnectedComponents,
+            "StronglyConnectedComponents" => GraphAlgorithmType::StronglyConnectedComponents,
+            "LabelPropagation" => GraphAlgorithmType::LabelPropagation,
+            "TriangleCount" => GraphAlgorithmType::TriangleCount,
+            "Betweenness" => GraphAlgorithmType::Betweenness,
+            other => return Err(ParserError::from(format!("Unknown graph algorithm: {other}"))),
+        };
+        let mut edge_types = Vec::new();
+        let mut args = Vec::new();
+        for p in inner {
+            match p.as_rule() {
+                Rule::type_args => {
+                    edge_types = p.into_inner().map(|t| t.as_str().to_string()).collect()
+                }
+                Rule::evaluates_to_number => args.push(self.parse_expression(p)?),
+                _ => {}
+            }
+        }
+        Ok(GraphAlgorithm {
+            loc,
+            algorithm,
+            edge_types,
+            args,
+        })
+    }
+
+    /// Parses the `::From(..)` and `::To(..)` ids of a path step
+    fn parse_path_endpoints(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<(Option<IdType>, Option<IdType>), ParserError> {
+        let loc = pair.loc();
+        let (mut from, mut to) = (None, None);
+        for p in pair.into_inner() {
+            let id = IdType::Identifier {
+                value: p.clone().try_inner_next()?.as_str().to_string(),
+                loc: loc.clone(),
+            };
+            match p.as_rule() {
+                Rule::from => from = Some(id),
+                Rule::to => to = Some(id),
+                _ => unreachable!(),
+            }
+        }
+        Ok((from, to))
+    }
+
     /// Parses a RerankRRF step
     ///
     /// #### Example
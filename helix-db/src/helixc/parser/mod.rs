@@ -10,11 +10,7 @@ use crate::helixc::parser::types::{Content, HxFile, Schema, Source};
 use location::HasLoc;
 use pest::Parser as PestParser;
 use pest_derive::Parser;
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Debug,
-    io::Write,
-};
+use std::{collections::HashMap, fmt::Debug, io::Write};
 
 pub mod creation_step_parse_methods;
 pub mod errors;
@@ -60,8 +56,8 @@ impl HelixParser {
             };
 
             let pairs = pair.into_inner();
-            let mut remaining_queries = HashSet::new();
-            let mut remaining_migrations = HashSet::new();
+            let mut remaining_queries = Vec::new();
+            let mut remaining_migrations = Vec::new();
             for pair in pairs {
                 match pair.as_rule() {
                     Rule::schema_def => {
@@ -110,6 +106,8 @@ impl HelixParser {
                                             node_schemas: vec![node_schema],
                                             edge_schemas: vec![],
                                             vector_schemas: vec![],
+                                            enum_schemas: vec![],
+                                            struct_schemas: vec![],
                                         });
                                 }
                                 Rule::edge_def => {
@@ -128,6 +126,8 @@ impl HelixParser {
                                             node_schemas: vec![],
                                             edge_schemas: vec![edge_schema],
                                             vector_schemas: vec![],
+                                            enum_schemas: vec![],
+                                            struct_schemas: vec![],
                                         });
                                 }
                                 Rule::vector_def => {
@@ -146,6 +146,48 @@ impl HelixParser {
                                             node_schemas: vec![],
                                             edge_schemas: vec![],
                                             vector_schemas: vec![vector_schema],
+                                            enum_schemas: vec![],
+                                            struct_schemas: vec![],
+                                        });
+                                }
+                                Rule::enum_def => {
+                                    let enum_schema =
+                                        parser.parse_enum_def(pair.clone(), file.name.clone())?;
+                                    parser
+                                        .source
+                                        .schema
+                                        .entry(schema_version)
+                                        .and_modify(|schema| {
+                                            schema.enum_schemas.push(enum_schema.clone())
+                                        })
+                                        .or_insert(Schema {
+                                            loc: pair.loc(),
+                                            version: (pair.loc(), schema_version),
+                                            node_schemas: vec![],
+                                            edge_schemas: vec![],
+                                            vector_schemas: vec![],
+                                            enum_schemas: vec![enum_schema],
+                                            struct_schemas: vec![],
+                                        });
+                                }
+                                Rule::struct_def => {
+                                    let struct_schema =
+                                        parser.parse_struct_def(pair.clone(), file.name.clone())?;
+                                    parser
+                                        .source
+                                        .schema
+                                        .entry(schema_version)
+                                        .and_modify(|schema| {
+                                            schema.struct_schemas.push(struct_schema.clone())
+                                        })
+                                        .or_insert(Schema {
+                                            loc: pair.loc(),
+                                            version: (pair.loc(), schema_version),
+                                            node_schemas: vec![],
+                                            edge_schemas: vec![],
+                                            vector_schemas: vec![],
+                                            enum_schemas: vec![],
+                                            struct_schemas: vec![struct_schema],
                                         });
                                 }
                                 _ => return Err(ParserError::from("Unexpected rule encountered")),
@@ -153,10 +195,10 @@ impl HelixParser {
                         }
                     }
                     Rule::migration_def => {
-                        remaining_migrations.insert(pair);
+                        remaining_migrations.push(pair);
                     }
                     Rule::query_def => {
-                        remaining_queries.insert(pair);
+                        remaining_queries.push(pair);
                     }
                     Rule::EOI => (),
                     _ => return Err(ParserError::from("Unexpected rule encountered")),
@@ -148,6 +148,10 @@ impl HelixParser {
                     loc: p.loc(),
                     statement: StatementType::ForLoop(self.parse_for_loop(p)?),
                 }),
+                Rule::if_stmt => Ok(Statement {
+                    loc: p.loc(),
+                    statement: StatementType::IfElse(self.parse_if_else(p)?),
+                }),
                 _ => Err(ParserError::from(format!(
                     "Unexpected statement type in query body: {:?}",
                     p.as_rule()
@@ -535,4 +539,48 @@ mod tests {
             crate::helixc::parser::types::FieldType::Object(_)
         ));
     }
+
+    #[test]
+    fn test_parse_edge_index_and_between_lookups() {
+        use crate::helixc::parser::types::{ExpressionType, IdType, StartNode, StatementType};
+
+        let source = r#"
+            N::Person { name: String }
+            E::Follows { From: Person, To: Person, Properties: { INDEX since: I64 } }
+
+            QUERY lookup(a: ID, b: ID, since: I64) =>
+                indexed <- E<Follows>({since: since})
+                between <- E<Follows>::Between(a, b)
+                RETURN indexed, between
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let starts = parsed.queries[0]
+            .statements
+            .iter()
+            .map(|statement| match &statement.statement {
+                StatementType::Assignment(assignment) => match &assignment.value.expr {
+                    ExpressionType::Traversal(traversal) => traversal.start.clone(),
+                    _ => panic!("expected traversal"),
+                },
+                _ => panic!("expected assignment"),
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            &starts[0],
+            StartNode::Edge { ids: Some(ids), .. } if matches!(ids[0], IdType::ByIndex { .. })
+        ));
+        assert!(matches!(
+            &starts[1],
+            StartNode::EdgesBetween {
+                from: IdType::Identifier { value: from, .. },
+                to: IdType::Identifier { value: to, .. },
+                ..
+            } if from == "a" && to == "b"
+        ));
+    }
 }
@@ -4,9 +4,9 @@ use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
     location::HasLoc,
     types::{
-        DefaultValue, EdgeSchema, Field, FieldPrefix, FieldType, Migration, MigrationItem,
-        MigrationItemMapping, MigrationPropertyMapping, NodeSchema, Source, ValueCast,
-        VectorSchema,
+        DefaultValue, EdgeSchema, EnumSchema, Field, FieldPrefix, FieldType, Migration, MigrationItem,
+        MigrationItemMapping, MigrationPropertyMapping, NodeSchema, Source, StructSchema,
+        ValueCast, VectorSchema,
     },
     utils::{PairTools, PairsTools},
 };
@@ -43,6 +43,38 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_enum_def(
+        &self,
+        pair: Pair<Rule>,
+        filepath: String,
+    ) -> Result<EnumSchema, ParserError> {
+        let mut pairs = pair.clone().into_inner();
+        let name = pairs.try_next()?.as_str().to_string();
+        let variants = pairs
+            .map(|p| (p.loc(), p.as_str().to_string()))
+            .collect();
+        Ok(EnumSchema {
+            name: (pair.loc(), name),
+            variants,
+            loc: pair.loc_with_filepath(filepath),
+        })
+    }
+
+    pub(super) fn parse_struct_def(
+        &self,
+        pair: Pair<Rule>,
+        filepath: String,
+    ) -> Result<StructSchema, ParserError> {
+        let mut pairs = pair.clone().into_inner();
+        let name = pairs.try_next()?.as_str().to_string();
+        let fields = self.parse_node_body(pairs.try_next()?, filepath.clone())?;
+        Ok(StructSchema {
+            name: (pair.loc(), name),
+            fields,
+            loc: pair.loc_with_filepath(filepath),
+        })
+    }
+
     pub(super) fn parse_node_body(
         &self,
         pair: Pair<Rule>,
@@ -423,10 +455,10 @@ impl HelixParser {
                         let mut field_pair = field.clone().into_inner();
                         (
                             field_pair.try_next()?.as_str().to_string(),
-                            field_pair.try_next_inner().try_next()?,
+                            field_pair.try_next()?,
                         )
                     };
-                    let field_type = self.parse_field_type(field_type, Some(&self.source))?;
+                    let field_type = self.parse_field_def_type(field_type)?;
                     fields.insert(field_name, field_type);
                 }
                 Ok(FieldType::Object(fields))
@@ -440,28 +472,45 @@ impl HelixParser {
         }
     }
 
+    /// Parses the type of a field definition, either a `param_type` or a `V::Type` embedding.
+    fn parse_field_def_type(&self, pair: Pair<Rule>) -> Result<FieldType, ParserError> {
+        match pair.as_rule() {
+            Rule::embedding_type => Ok(FieldType::Embedding(
+                pair.try_inner_next()?.as_str().to_string(),
+            )),
+            _ => self.parse_field_type(pair.try_inner_next()?, Some(&self.source)),
+        }
+    }
+
     pub(super) fn parse_field_def(
         &self,
         pair: Pair<Rule>,
         filepath: String,
     ) -> Result<Field, ParserError> {
         let mut pairs = pair.clone().into_inner();
-        // structure is index? ~ identifier ~ ":" ~ param_type
-        let prefix: FieldPrefix = match pairs.clone().try_next()?.as_rule() {
+        // structure is index? ~ identifier ~ ":" ~ param_type ~ optional? ~ default?
+        let mut prefix: FieldPrefix = match pairs.clone().try_next()?.as_rule() {
             Rule::index => {
                 pairs.try_next()?;
                 FieldPrefix::Index
             }
-            // Rule::optional => {
-            //     pairs.next().unwrap();
-            //     FieldPrefix::Optional
-            // }
             _ => FieldPrefix::Empty,
         };
         let name = pairs.try_next()?.as_str().to_string();
 
-        let field_type =
-            self.parse_field_type(pairs.try_next_inner().try_next()?, Some(&self.source))?;
+        let field_type = self.parse_field_def_type(pairs.try_next()?)?;
+        if matches!(field_type, FieldType::Embedding(_)) {
+            // the node is indexed by the id of its vector so that vector search
+            // results can be joined back to it
+            prefix = FieldPrefix::Index;
+        }
+
+        let is_optional = pairs
+            .peek()
+            .is_some_and(|p| p.as_rule() == Rule::optional);
+        if is_optional {
+            pairs.next();
+        }
 
         let defaults = self.parse_default_value(&mut pairs, &field_type)?;
 
@@ -470,6 +519,7 @@ impl HelixParser {
             defaults,
             name,
             field_type,
+            is_optional,
             loc: pair.loc_with_filepath(filepath),
         })
     }
@@ -572,6 +622,61 @@ mod tests {
         assert!(matches!(schema.node_schemas[0].fields[1].prefix, FieldPrefix::Empty));
     }
 
+    #[test]
+    fn test_parse_node_definition_with_embedding() {
+        let source = r#"
+            V::DocEmb {}
+
+            N::Doc {
+                title: String,
+                embedding: V::DocEmb
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        let fields = &schema.node_schemas[0].fields;
+        assert!(matches!(fields[0].prefix, FieldPrefix::Empty));
+        assert_eq!(
+            fields[1].field_type,
+            FieldType::Embedding("DocEmb".to_string())
+        );
+        // embedded vectors are indexed so search results can be joined back to the node
+        assert!(matches!(fields[1].prefix, FieldPrefix::Index));
+    }
+
+    #[test]
+    fn test_parse_node_definition_with_optional_fields() {
+        let source = r#"
+            N::Person {
+                name: String,
+                nickname: String?,
+                INDEX email: String?,
+                age: U32? DEFAULT 0
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        let fields = &schema.node_schemas[0].fields;
+        assert!(!fields[0].is_optional);
+        assert!(fields[1].is_optional);
+        assert!(fields[1].is_nullable());
+        assert!(fields[2].is_optional);
+        assert!(matches!(fields[2].prefix, FieldPrefix::Index));
+        assert!(fields[3].is_optional);
+        assert!(!fields[3].is_nullable());
+        assert!(fields[3].defaults.is_some());
+    }
+
     #[test]
     fn test_parse_node_definition_all_types() {
         let source = r#"
@@ -833,6 +938,100 @@ mod tests {
         assert_eq!(schema.vector_schemas[0].fields.len(), 2);
     }
 
+    // ============================================================================
+    // Enum Definition Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_enum_definition() {
+        let source = r#"
+            ENUM Status { Active, Suspended, }
+
+            N::User {
+                status: Status
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        assert_eq!(schema.enum_schemas.len(), 1);
+        assert_eq!(schema.enum_schemas[0].name.1, "Status");
+        let variants: Vec<_> = schema.enum_schemas[0]
+            .variants
+            .iter()
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(variants, vec!["Active", "Suspended"]);
+        assert!(matches!(
+            &schema.node_schemas[0].fields[0].field_type,
+            FieldType::Identifier(name) if name == "Status"
+        ));
+    }
+
+    #[test]
+    fn test_parse_enum_definition_invalid_lowercase_variant() {
+        let source = r#"
+            ENUM Status { active }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_enum_definition_keeps_duplicate_variants() {
+        let source = r#"
+            ENUM Status { Active, Active }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        // duplicates are reported by the analyzer
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        assert_eq!(schema.enum_schemas[0].variants.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_struct_definition() {
+        let source = r#"
+            STRUCT Address { city: String, zip: U32? }
+
+            N::User {
+                home: Address
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        assert_eq!(schema.struct_schemas.len(), 1);
+        assert_eq!(schema.struct_schemas[0].name.1, "Address");
+        assert_eq!(schema.struct_schemas[0].fields.len(), 2);
+        assert!(schema.struct_schemas[0].fields[1].is_optional);
+
+        // structs stay named in the schema, and resolve to the object of their fields
+        let home = &schema.node_schemas[0].fields[0].field_type;
+        assert!(matches!(home, FieldType::Identifier(name) if name == "Address"));
+        match schema.resolve_structs(home) {
+            FieldType::Object(fields) => {
+                assert_eq!(fields.get("city"), Some(&FieldType::String));
+                assert_eq!(fields.get("zip"), Some(&FieldType::U32));
+            }
+            other => panic!("expected an object, got {other:?}"),
+        }
+    }
+
     // ============================================================================
     // Multiple Schemas Test
     // ============================================================================
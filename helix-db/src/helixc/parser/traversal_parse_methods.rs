@@ -78,74 +78,7 @@ impl HelixParser {
                             }
                             ids = Some(new_ids);
                         }
-                        Rule::by_index => {
-                            ids = Some({
-                                let mut pairs: Pairs<'_, Rule> = p.clone().into_inner();
-                                let index = pairs.try_next_inner().try_next()?;
-                                let index = match index.as_rule() {
-                                    Rule::identifier => IdType::Identifier {
-                                        value: index.as_str().to_string(),
-                                        loc: index.loc(),
-                                    },
-                                    Rule::string_literal => IdType::Literal {
-                                        value: index.as_str().to_string(),
-                                        loc: index.loc(),
-                                    },
-                                    other => {
-                                        return Err(ParserError::from(format!(
-                                            "Should be identifier or string literal: {other:?}"
-                                        )));
-                                    }
-                                };
-                                let value = match pairs.try_next_inner()?.next() {
-                                    Some(val) => match val.as_rule() {
-                                        Rule::identifier => ValueType::Identifier {
-                                            value: val.as_str().to_string(),
-                                            loc: val.loc(),
-                                        },
-                                        Rule::string_literal => ValueType::Literal {
-                                            value: Value::from(val.as_str()),
-                                            loc: val.loc(),
-                                        },
-                                        Rule::integer => ValueType::Literal {
-                                            value: Value::from(
-                                                val.as_str().parse::<i64>().map_err(|_| {
-                                                    ParserError::from("Invalid integer value")
-                                                })?,
-                                            ),
-                                            loc: val.loc(),
-                                        },
-                                        Rule::float => ValueType::Literal {
-                                            value: Value::from(
-                                                val.as_str().parse::<f64>().map_err(|_| {
-                                                    ParserError::from("Invalid float value")
-                                                })?,
-                                            ),
-                                            loc: val.loc(),
-                                        },
-                                        Rule::boolean => ValueType::Literal {
-                                            value: Value::from(
-                                                val.as_str().parse::<bool>().map_err(|_| {
-                                                    ParserError::from("Invalid boolean value")
-                                                })?,
-                                            ),
-                                            loc: val.loc(),
-                                        },
-                                        _ => {
-                                            return Err(ParserError::from(
-                                                "Should be identifier or string literal",
-                                            ));
-                                        }
-                                    },
-                                    _ => unreachable!(),
-                                };
-                                vec![IdType::ByIndex {
-                                    index: Box::new(index),
-                                    value: Box::new(value),
-                                    loc: p.loc(),
-                                }]
-                            })
-                        }
+                        Rule::by_index => ids = Some(vec![self.parse_by_index(p)?]),
                         _ => unreachable!(),
                     }
                 }
@@ -155,6 +88,7 @@ impl HelixParser {
                 let pairs = pair.into_inner();
                 let mut edge_type = String::new();
                 let mut ids = None;
+                let mut between = None;
 
                 for p in pairs {
                     match p.as_rule() {
@@ -185,10 +119,24 @@ impl HelixParser {
                             }
                             ids = Some(new_ids);
                         }
+                        Rule::by_index => ids = Some(vec![self.parse_by_index(p)?]),
+                        Rule::edge_between => {
+                            let mut endpoints = p.into_inner();
+                            let from = self.parse_id_args(endpoints.try_next()?)?;
+                            let to = self.parse_id_args(endpoints.try_next()?)?;
+                            between = from.zip(to);
+                        }
                         _ => unreachable!(),
                     }
                 }
-                Ok(StartNode::Edge { edge_type, ids })
+                match between {
+                    Some((from, to)) => Ok(StartNode::EdgesBetween {
+                        edge_type,
+                        from,
+                        to,
+                    }),
+                    None => Ok(StartNode::Edge { edge_type, ids }),
+                }
             }
             Rule::identifier => Ok(StartNode::Identifier(pair.as_str().to_string())),
             Rule::search_vector => Ok(StartNode::SearchVector(self.parse_search_vector(pair)?)),
@@ -300,4 +248,70 @@ impl HelixParser {
             _ => Ok(StartNode::Anonymous),
         }
     }
+
+    /// Parses an index lookup such as `N<User>({email: email})`.
+    fn parse_by_index(&self, pair: Pair<Rule>) -> Result<IdType, ParserError> {
+        let mut pairs: Pairs<'_, Rule> = pair.clone().into_inner();
+        let index = pairs.try_next_inner().try_next()?;
+        let index = match index.as_rule() {
+            Rule::identifier => IdType::Identifier {
+                value: index.as_str().to_string(),
+                loc: index.loc(),
+            },
+            Rule::string_literal => IdType::Literal {
+                value: index.as_str().to_string(),
+                loc: index.loc(),
+            },
+            other => {
+                return Err(ParserError::from(format!(
+                    "Should be identifier or string literal: {other:?}"
+                )));
+            }
+        };
+        let value = match pairs.try_next_inner()?.next() {
+            Some(val) => match val.as_rule() {
+                Rule::identifier => ValueType::Identifier {
+                    value: val.as_str().to_string(),
+                    loc: val.loc(),
+                },
+                Rule::string_literal => ValueType::Literal {
+                    value: Value::from(val.as_str()),
+                    loc: val.loc(),
+                },
+                Rule::integer => ValueType::Literal {
+                    value: Value::from(
+                        val.as_str()
+                            .parse::<i64>()
+                            .map_err(|_| ParserError::from("Invalid integer value"))?,
+                    ),
+                    loc: val.loc(),
+                },
+                Rule::float => ValueType::Literal {
+                    value: Value::from(
+                        val.as_str()
+                            .parse::<f64>()
+                            .map_err(|_| ParserError::from("Invalid float value"))?,
+                    ),
+                    loc: val.loc(),
+                },
+                Rule::boolean => ValueType::Literal {
+                    value: Value::from(
+                        val.as_str()
+                            .parse::<bool>()
+                            .map_err(|_| ParserError::from("Invalid boolean value"))?,
+                    ),
+                    loc: val.loc(),
+                },
+                _ => {
+                    return Err(ParserError::from("Should be identifier or string literal"));
+                }
+            },
+            _ => unreachable!(),
+        };
+        Ok(IdType::ByIndex {
+            index: Box::new(index),
+            value: Box::new(value),
+            loc: pair.loc(),
+        })
+    }
 }
@@ -64,6 +64,44 @@ impl Source {
             .map(|(_, schema)| schema)
             .collect()
     }
+
+    /// Reconstructs the definitions of the latest schema from their source, without
+    /// the queries and migrations around them.
+    pub fn schema_source(&self) -> String {
+        let Ok(schema) = self.get_latest_schema() else {
+            return String::new();
+        };
+        schema
+            .enum_schemas
+            .iter()
+            .map(|item| item.loc.span.as_str())
+            .chain(
+                schema
+                    .struct_schemas
+                    .iter()
+                    .map(|item| item.loc.span.as_str()),
+            )
+            .chain(
+                schema
+                    .node_schemas
+                    .iter()
+                    .map(|item| item.loc.span.as_str()),
+            )
+            .chain(
+                schema
+                    .vector_schemas
+                    .iter()
+                    .map(|item| item.loc.span.as_str()),
+            )
+            .chain(
+                schema
+                    .edge_schemas
+                    .iter()
+                    .map(|item| item.loc.span.as_str()),
+            )
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +111,8 @@ pub struct Schema {
     pub node_schemas: Vec<NodeSchema>,
     pub edge_schemas: Vec<EdgeSchema>,
     pub vector_schemas: Vec<VectorSchema>,
+    pub enum_schemas: Vec<EnumSchema>,
+    pub struct_schemas: Vec<StructSchema>,
 }
 
 #[derive(Debug, Clone)]
@@ -98,6 +138,75 @@ pub struct EdgeSchema {
     pub loc: Loc,
 }
 
+#[derive(Debug, Clone)]
+pub struct EnumSchema {
+    pub name: (Loc, String),
+    pub variants: Vec<(Loc, String)>,
+    pub loc: Loc,
+}
+
+impl EnumSchema {
+    pub fn has_variant(&self, variant: &str) -> bool {
+        self.variants.iter().any(|(_, v)| v == variant)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StructSchema {
+    pub name: (Loc, String),
+    pub fields: Vec<Field>,
+    pub loc: Loc,
+}
+
+impl Schema {
+    /// Returns the struct declaration when `field_type` names one.
+    pub fn get_struct(&self, field_type: &FieldType) -> Option<&StructSchema> {
+        match field_type {
+            FieldType::Identifier(name) => self.struct_schemas.iter().find(|s| s.name.1 == *name),
+            _ => None,
+        }
+    }
+
+    /// Replaces the structs named by `field_type`, at any depth, with the objects they declare,
+    /// which is how their values are stored.
+    ///
+    /// A struct containing itself is left as its name.
+    pub fn resolve_structs(&self, field_type: &FieldType) -> FieldType {
+        self.resolve_structs_in(field_type, &mut Vec::new())
+    }
+
+    fn resolve_structs_in<'s>(
+        &'s self,
+        field_type: &FieldType,
+        seen: &mut Vec<&'s str>,
+    ) -> FieldType {
+        match field_type {
+            FieldType::Array(inner) => {
+                FieldType::Array(Box::new(self.resolve_structs_in(inner, seen)))
+            }
+            FieldType::Object(fields) => FieldType::Object(
+                fields
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), self.resolve_structs_in(ty, seen)))
+                    .collect(),
+            ),
+            _ => match self.get_struct(field_type) {
+                Some(struct_schema) if !seen.contains(&struct_schema.name.1.as_str()) => {
+                    seen.push(&struct_schema.name.1);
+                    let fields = struct_schema
+                        .fields
+                        .iter()
+                        .map(|f| (f.name.clone(), self.resolve_structs_in(&f.field_type, seen)))
+                        .collect();
+                    seen.pop();
+                    FieldType::Object(fields)
+                }
+                _ => field_type.clone(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Migration {
     pub from_version: (Loc, usize),
@@ -163,12 +272,18 @@ pub struct Field {
     pub defaults: Option<DefaultValue>,
     pub name: String,
     pub field_type: FieldType,
+    pub is_optional: bool,
     pub loc: Loc,
 }
 impl Field {
     pub fn is_indexed(&self) -> bool {
         self.prefix.is_indexed()
     }
+
+    /// Whether the field may be missing on a stored item (declared with `?` and no `DEFAULT`).
+    pub fn is_nullable(&self) -> bool {
+        self.is_optional && self.defaults.is_none()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -222,6 +337,8 @@ pub enum FieldType {
     Array(Box<FieldType>),
     Identifier(String),
     Object(HashMap<String, FieldType>),
+    /// A vector of the given type embedded on a node, `V::Type`
+    Embedding(String),
     // Closure(String, HashMap<String, FieldType>),
 }
 
@@ -257,6 +374,7 @@ impl PartialEq for FieldType {
             (FieldType::Array(a), FieldType::Array(b)) => a == b,
             (FieldType::Identifier(a), FieldType::Identifier(b)) => a == b,
             (FieldType::Object(a), FieldType::Object(b)) => a == b,
+            (FieldType::Embedding(a), FieldType::Embedding(b)) => a == b,
             // (FieldType::Closure(a, b), FieldType::Closure(c, d)) => a == c && b == d,
             _ => false,
         }
@@ -285,11 +403,16 @@ impl Display for FieldType {
             FieldType::Identifier(s) => write!(f, "{s}"),
             FieldType::Object(m) => {
                 write!(f, "{{")?;
-                for (k, v) in m {
+                for (i, (k, v)) in m.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
                     write!(f, "{k}: {v}")?;
                 }
                 write!(f, "}}")
-            } // FieldType::Closure(a, b) => write!(f, "Closure({})", a),
+            }
+            FieldType::Embedding(v) => write!(f, "V::{v}"),
+            // FieldType::Closure(a, b) => write!(f, "Closure({})", a),
         }
     }
 }
@@ -415,11 +538,14 @@ pub enum StatementType {
     Expression(Expression),
     Drop(Expression),
     ForLoop(ForLoop),
+    IfElse(IfElse),
 }
 
 #[derive(Debug, Clone)]
 pub struct Assignment {
     pub variable: String,
+    /// Second variable of `a, created <- UpsertN<...>`, bound to whether the upsert inserted
+    pub created_variable: Option<(Loc, String)>,
     pub value: Expression,
     pub loc: Loc,
 }
@@ -432,6 +558,15 @@ pub struct ForLoop {
     pub loc: Loc,
 }
 
+#[derive(Debug, Clone)]
+pub struct IfElse {
+    pub condition: Expression,
+    pub then_statements: Vec<Statement>,
+    /// `ELSE IF` chains are stored as an else branch holding a single nested `IfElse`.
+    pub else_statements: Option<Vec<Statement>>,
+    pub loc: Loc,
+}
+
 #[derive(Debug, Clone)]
 pub enum ForLoopVars {
     Identifier {
@@ -566,6 +701,96 @@ pub struct MathFunctionCall {
     pub loc: Loc,
 }
 
+/// String and date function types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScalarFunction {
+    // Strings
+    Lower,
+    Upper,
+    Concat,
+    Substring, // SUBSTRING(s, start[, len])
+    StartsWith,
+    EndsWith,
+    RegexMatch,
+    Length,
+
+    // Dates
+    DateTrunc, // DATE_TRUNC(date, unit)
+    DateAdd,   // DATE_ADD(date, amount, unit)
+    DateDiff,  // DATE_DIFF(start, end, unit)
+    Now,
+
+    // Arrays
+    Append, // APPEND([array, ]value), the array defaults to the updated field
+    Remove, // REMOVE([array, ]value), the array defaults to the updated field
+}
+
+impl ScalarFunction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "LOWER" => ScalarFunction::Lower,
+            "UPPER" => ScalarFunction::Upper,
+            "CONCAT" => ScalarFunction::Concat,
+            "SUBSTRING" => ScalarFunction::Substring,
+            "STARTS_WITH" => ScalarFunction::StartsWith,
+            "ENDS_WITH" => ScalarFunction::EndsWith,
+            "REGEX_MATCH" => ScalarFunction::RegexMatch,
+            "LENGTH" => ScalarFunction::Length,
+            "DATE_TRUNC" => ScalarFunction::DateTrunc,
+            "DATE_ADD" => ScalarFunction::DateAdd,
+            "DATE_DIFF" => ScalarFunction::DateDiff,
+            "NOW" => ScalarFunction::Now,
+            "APPEND" => ScalarFunction::Append,
+            "REMOVE" => ScalarFunction::Remove,
+            _ => return None,
+        })
+    }
+
+    /// Returns the minimum and maximum number of arguments for this function
+    pub fn arity(&self) -> (usize, usize) {
+        match self {
+            ScalarFunction::Now => (0, 0),
+            ScalarFunction::Lower | ScalarFunction::Upper | ScalarFunction::Length => (1, 1),
+            ScalarFunction::StartsWith
+            | ScalarFunction::EndsWith
+            | ScalarFunction::RegexMatch
+            | ScalarFunction::DateTrunc => (2, 2),
+            ScalarFunction::Append | ScalarFunction::Remove => (1, 2),
+            ScalarFunction::Substring => (2, 3),
+            ScalarFunction::DateAdd | ScalarFunction::DateDiff => (3, 3),
+            ScalarFunction::Concat => (1, usize::MAX),
+        }
+    }
+
+    /// Returns the function name as a string
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScalarFunction::Lower => "LOWER",
+            ScalarFunction::Upper => "UPPER",
+            ScalarFunction::Concat => "CONCAT",
+            ScalarFunction::Substring => "SUBSTRING",
+            ScalarFunction::StartsWith => "STARTS_WITH",
+            ScalarFunction::EndsWith => "ENDS_WITH",
+            ScalarFunction::RegexMatch => "REGEX_MATCH",
+            ScalarFunction::Length => "LENGTH",
+            ScalarFunction::DateTrunc => "DATE_TRUNC",
+            ScalarFunction::DateAdd => "DATE_ADD",
+            ScalarFunction::DateDiff => "DATE_DIFF",
+            ScalarFunction::Now => "NOW",
+            ScalarFunction::Append => "APPEND",
+            ScalarFunction::Remove => "REMOVE",
+        }
+    }
+}
+
+/// String or date function call AST node
+#[derive(Debug, Clone)]
+pub struct ScalarFunctionCall {
+    pub function: ScalarFunction,
+    pub args: Vec<Expression>,
+    pub loc: Loc,
+}
+
 #[derive(Clone)]
 pub enum ExpressionType {
     Traversal(Box<Traversal>),
@@ -579,12 +804,16 @@ pub enum ExpressionType {
     AddVector(AddVector),
     AddNode(AddNode),
     AddEdge(AddEdge),
+    UpsertNode(UpsertNode),
+    UpsertEdge(UpsertEdge),
     Not(Box<Expression>),
     And(Vec<Expression>),
     Or(Vec<Expression>),
     SearchVector(SearchVector),
     BM25Search(BM25Search),
     MathFunctionCall(MathFunctionCall),
+    ScalarFunctionCall(ScalarFunctionCall),
+    Coalesce(Coalesce),
     Empty,
 }
 
@@ -610,12 +839,16 @@ impl Debug for ExpressionType {
             ExpressionType::AddVector(av) => write!(f, "AddVector({av:?})"),
             ExpressionType::AddNode(an) => write!(f, "AddNode({an:?})"),
             ExpressionType::AddEdge(ae) => write!(f, "AddEdge({ae:?})"),
+            ExpressionType::UpsertNode(un) => write!(f, "UpsertNode({un:?})"),
+            ExpressionType::UpsertEdge(ue) => write!(f, "UpsertEdge({ue:?})"),
             ExpressionType::Not(expr) => write!(f, "Not({expr:?})"),
             ExpressionType::And(exprs) => write!(f, "And({exprs:?})"),
             ExpressionType::Or(exprs) => write!(f, "Or({exprs:?})"),
             ExpressionType::SearchVector(sv) => write!(f, "SearchVector({sv:?})"),
             ExpressionType::BM25Search(bm25) => write!(f, "BM25Search({bm25:?})"),
             ExpressionType::MathFunctionCall(mfc) => write!(f, "MathFunctionCall({mfc:?})"),
+            ExpressionType::ScalarFunctionCall(sfc) => write!(f, "ScalarFunctionCall({sfc:?})"),
+            ExpressionType::Coalesce(c) => write!(f, "Coalesce({:?}, {:?})", c.value, c.fallback),
             ExpressionType::Empty => write!(f, "Empty"),
         }
     }
@@ -634,17 +867,28 @@ impl Display for ExpressionType {
             ExpressionType::AddVector(av) => write!(f, "AddVector({av:?})"),
             ExpressionType::AddNode(an) => write!(f, "AddNode({an:?})"),
             ExpressionType::AddEdge(ae) => write!(f, "AddEdge({ae:?})"),
+            ExpressionType::UpsertNode(un) => write!(f, "UpsertNode({un:?})"),
+            ExpressionType::UpsertEdge(ue) => write!(f, "UpsertEdge({ue:?})"),
             ExpressionType::Not(expr) => write!(f, "Not({expr:?})"),
             ExpressionType::And(exprs) => write!(f, "And({exprs:?})"),
             ExpressionType::Or(exprs) => write!(f, "Or({exprs:?})"),
             ExpressionType::SearchVector(sv) => write!(f, "SearchVector({sv:?})"),
             ExpressionType::BM25Search(bm25) => write!(f, "BM25Search({bm25:?})"),
             ExpressionType::MathFunctionCall(mfc) => write!(f, "{}({:?})", mfc.function.name(), mfc.args),
+            ExpressionType::ScalarFunctionCall(sfc) => write!(f, "{}({:?})", sfc.function.name(), sfc.args),
+            ExpressionType::Coalesce(c) => write!(f, "COALESCE({}, {})", c.value.expr, c.fallback.expr),
             ExpressionType::Empty => write!(f, "Empty"),
         }
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Coalesce {
+    pub value: Box<Expression>,
+    pub fallback: Box<Expression>,
+    pub loc: Loc,
+}
+
 #[derive(Debug, Clone)]
 pub struct Traversal {
     pub start: StartNode,
@@ -670,6 +914,12 @@ pub enum StartNode {
         edge_type: String,
         ids: Option<Vec<IdType>>,
     },
+    /// `E<Type>::Between(from, to)`
+    EdgesBetween {
+        edge_type: String,
+        from: IdType,
+        to: IdType,
+    },
     Vector {
         vector_type: String,
         ids: Option<Vec<IdType>>,
@@ -693,21 +943,87 @@ pub enum OrderByType {
 
 #[derive(Debug, Clone)]
 pub struct OrderBy {
+    pub loc: Loc,
+    pub keys: Vec<OrderKey>,
+}
+
+/// One key of an `ORDER` step, e.g. `Desc: _::{age} NULLS FIRST`
+#[derive(Debug, Clone)]
+pub struct OrderKey {
     pub loc: Loc,
     pub order_by_type: OrderByType,
     pub expression: Box<Expression>,
+    pub nulls: Option<NullsOrder>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NullsOrder {
+    First,
+    Last,
 }
 
 #[derive(Debug, Clone)]
 pub struct Aggregate {
     pub loc: Loc,
-    pub properties: Vec<String>
+    pub properties: Vec<String>,
+    pub aggregations: Vec<AggregateField>,
 }
 
 #[derive(Debug, Clone)]
 pub struct GroupBy {
     pub loc: Loc,
-    pub properties: Vec<String>
+    pub properties: Vec<String>,
+    pub aggregations: Vec<AggregateField>,
+}
+
+/// A named aggregate in `GROUP_BY`/`AGGREGATE_BY`, e.g. `total: SUM(amount)`
+#[derive(Debug, Clone)]
+pub struct AggregateField {
+    pub loc: Loc,
+    pub name: String,
+    pub function: AggregateFunction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateFunction {
+    Count,
+    CountDistinct(String),
+    Sum(String),
+    Avg(String),
+    Min(String),
+    Max(String),
+    Percentile(String, f64),
+    Collect(String),
+}
+
+impl AggregateFunction {
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AggregateFunction::Count => None,
+            AggregateFunction::CountDistinct(field)
+            | AggregateFunction::Sum(field)
+            | AggregateFunction::Avg(field)
+            | AggregateFunction::Min(field)
+            | AggregateFunction::Max(field)
+            | AggregateFunction::Percentile(field, _)
+            | AggregateFunction::Collect(field) => Some(field),
+        }
+    }
+}
+
+impl std::fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateFunction::Count => write!(f, "COUNT"),
+            AggregateFunction::CountDistinct(field) => write!(f, "COUNT(DISTINCT {field})"),
+            AggregateFunction::Sum(field) => write!(f, "SUM({field})"),
+            AggregateFunction::Avg(field) => write!(f, "AVG({field})"),
+            AggregateFunction::Min(field) => write!(f, "MIN({field})"),
+            AggregateFunction::Max(field) => write!(f, "MAX({field})"),
+            AggregateFunction::Percentile(field, rank) => write!(f, "PERCENTILE({field}, {rank})"),
+            AggregateFunction::Collect(field) => write!(f, "COLLECT({field})"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -743,6 +1059,10 @@ pub enum StepType {
     Exclude(Exclude),
     Closure(Closure),
     Range((Expression, Expression)),
+    /// Resumes the traversal after the item a cursor points to
+    After(Box<Expression>),
+    /// Cursor pointing past the last item of the traversal
+    Cursor,
     OrderBy(OrderBy),
     Aggregate(Aggregate),
     GroupBy(GroupBy),
@@ -768,6 +1088,8 @@ impl PartialEq<StepType> for StepType {
                 | (&StepType::Exclude(_), &StepType::Exclude(_))
                 | (&StepType::Closure(_), &StepType::Closure(_))
                 | (&StepType::Range(_), &StepType::Range(_))
+                | (&StepType::After(_), &StepType::After(_))
+                | (&StepType::Cursor, &StepType::Cursor)
                 | (&StepType::OrderBy(_), &StepType::OrderBy(_))
                 | (&StepType::AddEdge(_), &StepType::AddEdge(_))
                 | (&StepType::Aggregate(_), &StepType::Aggregate(_))
@@ -810,6 +1132,7 @@ pub struct GraphStep {
 pub enum GraphStepType {
     Out(String),
     In(String),
+    OutN(OutN),
 
     FromN,
     ToN,
@@ -823,6 +1146,10 @@ pub enum GraphStepType {
     ShortestPathDijkstras(ShortestPathDijkstras),
     ShortestPathBFS(ShortestPathBFS),
     ShortestPathAStar(ShortestPathAStar),
+    KShortestPaths(KShortestPaths),
+    AllPaths(AllPaths),
+    ShortestPathsFrom(ShortestPathsFrom),
+    GraphAlgorithm(GraphAlgorithm),
     SearchVector(SearchVector),
 }
 impl GraphStep {
@@ -830,6 +1157,7 @@ impl GraphStep {
         match &self.step {
             GraphStepType::Out(s) => Some(s.clone()),
             GraphStepType::In(s) => Some(s.clone()),
+            GraphStepType::OutN(s) => Some(s.label.clone()),
             GraphStepType::OutE(s) => Some(s.clone()),
             GraphStepType::InE(s) => Some(s.clone()),
             GraphStepType::SearchVector(s) => Some(s.vector_type.clone().unwrap()),
@@ -838,6 +1166,17 @@ impl GraphStep {
     }
 }
 
+/// Variable-length traversal over outgoing edges, e.g. `OutN<Knows>(1..3, UNTIL(...), PATHS)`
+#[derive(Debug, Clone)]
+pub struct OutN {
+    pub loc: Loc,
+    pub label: String,
+    pub min_depth: usize,
+    pub max_depth: usize,
+    pub until: Option<Box<Expression>>,
+    pub emit_paths: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct ShortestPath {
     pub loc: Loc,
@@ -887,6 +1226,67 @@ pub struct ShortestPathAStar {
     pub heuristic_property: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct KShortestPaths {
+    pub loc: Loc,
+    pub from: Option<IdType>,
+    pub to: Option<IdType>,
+    pub type_arg: Option<String>,
+    pub k: EvaluatesToNumber,
+    pub weight_expr: Option<WeightExpression>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AllPaths {
+    pub loc: Loc,
+    pub from: Option<IdType>,
+    pub to: Option<IdType>,
+    pub type_arg: Option<String>,
+    pub max_depth: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShortestPathsFrom {
+    pub loc: Loc,
+    pub type_arg: Option<String>,
+    pub weight_expr: Option<WeightExpression>,
+    pub max_distance: Option<Expression>,
+}
+
+/// Graph analytics step, e.g. `PageRank<Follows>(0.85, 20)`
+#[derive(Debug, Clone)]
+pub struct GraphAlgorithm {
+    pub loc: Loc,
+    pub algorithm: GraphAlgorithmType,
+    pub edge_types: Vec<String>,
+    pub args: Vec<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphAlgorithmType {
+    PageRank,
+    ConnectedComponents,
+    StronglyConnectedComponents,
+    LabelPropagation,
+    TriangleCount,
+    Betweenness,
+}
+
+impl std::fmt::Display for GraphAlgorithmType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphAlgorithmType::PageRank => write!(f, "PageRank"),
+            GraphAlgorithmType::ConnectedComponents => write!(f, "ConnectedComponents"),
+            GraphAlgorithmType::StronglyConnectedComponents => {
+                write!(f, "StronglyConnectedComponents")
+            }
+            GraphAlgorithmType::LabelPropagation => write!(f, "LabelPropagation"),
+            GraphAlgorithmType::TriangleCount => write!(f, "TriangleCount"),
+            GraphAlgorithmType::Betweenness => write!(f, "Betweenness"),
+        }
+    }
+}
+
 // PathAlgorithm enum removed - now using distinct function names
 
 #[derive(Debug, Clone)]
@@ -907,6 +1307,9 @@ pub enum BooleanOpType {
     NotEqual(Box<Expression>),
     Contains(Box<Expression>),
     IsIn(Box<Expression>),
+    IsNull,
+    Any(Box<BooleanOp>),
+    All(Box<BooleanOp>),
 }
 
 #[derive(Debug, Clone)]
@@ -991,6 +1394,28 @@ pub struct AddEdge {
     pub from_identifier: bool,
 }
 
+/// `UpsertN<T>({key: value}, {..fields})`: finds the node by the indexed `key`, updating
+/// it with `fields`, or inserts a node with both.
+#[derive(Debug, Clone)]
+pub struct UpsertNode {
+    pub loc: Loc,
+    /// The indexed field used to look up an existing node
+    pub match_field: (Loc, String),
+    /// `AddN` of the node inserted when there is no match, holding the match field and `fields`
+    pub insert: Box<Expression>,
+    /// Fields written to an existing node
+    pub update_fields: Vec<String>,
+}
+
+/// `UpsertE<T>({..fields})::From(a)::To(b)`: updates the `T` edge from `a` to `b` with
+/// `fields`, or inserts it.
+#[derive(Debug, Clone)]
+pub struct UpsertEdge {
+    pub loc: Loc,
+    /// `AddE` of the edge inserted when there is no match
+    pub insert: Box<Expression>,
+}
+
 #[derive(Debug, Clone)]
 pub struct EdgeConnection {
     pub loc: Loc,
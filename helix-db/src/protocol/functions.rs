@@ -0,0 +1,374 @@
+//! Scalar string, date and array functions callable from HQL expressions.
+//!
+//! Every function takes and returns a `Value` so it can be used inside `WHERE`
+//! closures, remappings and `UPDATE` values alike. Invalid or missing input
+//! yields `Value::Empty` rather than an error, so a single malformed property
+//! never aborts a whole traversal.
+//!
+//! Dates are returned in the same RFC3339 string form they are stored in.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, Months, TimeZone, Utc};
+use regex::Regex;
+
+use crate::protocol::{date::Date, value::Value};
+
+/// The units accepted by `DATE_TRUNC`, `DATE_ADD` and `DATE_DIFF`.
+pub const DATE_UNITS: [&str; 7] = ["second", "minute", "hour", "day", "week", "month", "year"];
+
+/// Maximum number of compiled patterns kept per thread by `REGEX_MATCH`.
+const REGEX_CACHE_SIZE: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: RefCell<HashMap<String, Option<Regex>>> = RefCell::new(HashMap::new());
+}
+
+fn as_str(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(s) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::I8(i) => Some(*i as i64),
+        Value::I16(i) => Some(*i as i64),
+        Value::I32(i) => Some(*i as i64),
+        Value::I64(i) => Some(*i),
+        Value::U8(u) => Some(*u as i64),
+        Value::U16(u) => Some(*u as i64),
+        Value::U32(u) => Some(*u as i64),
+        Value::U64(u) => i64::try_from(*u).ok(),
+        Value::U128(u) => i64::try_from(*u).ok(),
+        _ => None,
+    }
+}
+
+fn as_date(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Date(date) => Some(*date.inner()),
+        value => Date::new(value).ok().map(|date| *date.inner()),
+    }
+}
+
+fn map_str(value: &Value, f: impl FnOnce(&str) -> Value) -> Value {
+    as_str(value).map_or(Value::Empty, f)
+}
+
+/// Lowercases a string.
+pub fn lower(value: &Value) -> Value {
+    map_str(value, |s| Value::String(s.to_lowercase()))
+}
+
+/// Uppercases a string.
+pub fn upper(value: &Value) -> Value {
+    map_str(value, |s| Value::String(s.to_uppercase()))
+}
+
+/// Concatenates the string form of every value, skipping empty ones.
+pub fn concat(values: &[&Value]) -> Value {
+    Value::String(
+        values
+            .iter()
+            .filter(|value| !matches!(value, Value::Empty))
+            .map(|value| value.inner_str())
+            .collect(),
+    )
+}
+
+/// Returns `len` characters starting at character `start`, or the rest of the
+/// string when `len` is empty.
+pub fn substring(value: &Value, start: &Value, len: &Value) -> Value {
+    let (Some(s), Some(start)) = (as_str(value), as_i64(start)) else {
+        return Value::Empty;
+    };
+    let chars = s.chars().skip(start.max(0) as usize);
+    Value::String(match len {
+        Value::Empty => chars.collect(),
+        len => match as_i64(len) {
+            Some(len) => chars.take(len.max(0) as usize).collect(),
+            None => return Value::Empty,
+        },
+    })
+}
+
+/// Whether a string starts with the given prefix.
+pub fn starts_with(value: &Value, prefix: &Value) -> Value {
+    match (as_str(value), as_str(prefix)) {
+        (Some(s), Some(prefix)) => Value::Boolean(s.starts_with(prefix)),
+        _ => Value::Empty,
+    }
+}
+
+/// Whether a string ends with the given suffix.
+pub fn ends_with(value: &Value, suffix: &Value) -> Value {
+    match (as_str(value), as_str(suffix)) {
+        (Some(s), Some(suffix)) => Value::Boolean(s.ends_with(suffix)),
+        _ => Value::Empty,
+    }
+}
+
+/// Whether a string matches the given regular expression.
+///
+/// Compiled patterns are cached per thread; an invalid pattern never matches.
+pub fn regex_match(value: &Value, pattern: &Value) -> Value {
+    let (Some(s), Some(pattern)) = (as_str(value), as_str(pattern)) else {
+        return Value::Empty;
+    };
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if !cache.contains_key(pattern) {
+            if cache.len() >= REGEX_CACHE_SIZE {
+                cache.clear();
+            }
+            cache.insert(pattern.to_string(), Regex::new(pattern).ok());
+        }
+        match &cache[pattern] {
+            Some(regex) => Value::Boolean(regex.is_match(s)),
+            None => Value::Empty,
+        }
+    })
+}
+
+/// The number of characters in a string or elements in an array.
+pub fn length(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::I64(s.chars().count() as i64),
+        Value::Array(arr) => Value::I64(arr.len() as i64),
+        _ => Value::Empty,
+    }
+}
+
+/// Appends a value to an array, or every element of an array value.
+/// A missing array starts a new one.
+pub fn append(array: &Value, value: &Value) -> Value {
+    let mut items = match array {
+        Value::Array(items) => items.clone(),
+        Value::Empty => Vec::new(),
+        _ => return Value::Empty,
+    };
+    match value {
+        Value::Array(values) => items.extend(values.iter().cloned()),
+        Value::Empty => {}
+        value => items.push(value.clone()),
+    }
+    Value::Array(items)
+}
+
+/// Removes every occurrence of a value, or of every element of an array value, from an array.
+pub fn remove(array: &Value, value: &Value) -> Value {
+    let Value::Array(items) = array else {
+        return Value::Empty;
+    };
+    let values = match value {
+        Value::Array(values) => values.as_slice(),
+        value => std::slice::from_ref(value),
+    };
+    Value::Array(
+        items
+            .iter()
+            .filter(|item| !values.contains(item))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// The current time.
+pub fn now() -> Value {
+    Value::from(Utc::now())
+}
+
+/// Truncates a date down to the start of the given unit.
+pub fn date_trunc(date: &Value, unit: &Value) -> Value {
+    let (Some(date), Some(unit)) = (as_date(date), as_str(unit)) else {
+        return Value::Empty;
+    };
+    let truncated = match unit {
+        "second" => date.duration_trunc(Duration::seconds(1)).ok(),
+        "minute" => date.duration_trunc(Duration::minutes(1)).ok(),
+        "hour" => date.duration_trunc(Duration::hours(1)).ok(),
+        "day" => date.duration_trunc(Duration::days(1)).ok(),
+        "week" => date
+            .duration_trunc(Duration::days(1))
+            .ok()
+            .map(|day| day - Duration::days(day.weekday().num_days_from_monday() as i64)),
+        "month" => Utc
+            .with_ymd_and_hms(date.year(), date.month(), 1, 0, 0, 0)
+            .single(),
+        "year" => Utc.with_ymd_and_hms(date.year(), 1, 1, 0, 0, 0).single(),
+        _ => None,
+    };
+    truncated.map_or(Value::Empty, Value::from)
+}
+
+/// Adds `amount` of the given unit to a date; negative amounts subtract.
+pub fn date_add(date: &Value, amount: &Value, unit: &Value) -> Value {
+    let (Some(date), Some(amount), Some(unit)) = (as_date(date), as_i64(amount), as_str(unit))
+    else {
+        return Value::Empty;
+    };
+    let months = |months: i64| {
+        let delta = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+        match months < 0 {
+            true => date.checked_sub_months(delta),
+            false => date.checked_add_months(delta),
+        }
+    };
+    let added = match unit {
+        "second" => Duration::try_seconds(amount).and_then(|d| date.checked_add_signed(d)),
+        "minute" => Duration::try_minutes(amount).and_then(|d| date.checked_add_signed(d)),
+        "hour" => Duration::try_hours(amount).and_then(|d| date.checked_add_signed(d)),
+        "day" => Duration::try_days(amount).and_then(|d| date.checked_add_signed(d)),
+        "week" => Duration::try_weeks(amount).and_then(|d| date.checked_add_signed(d)),
+        "month" => months(amount),
+        "year" => amount.checked_mul(12).and_then(months),
+        _ => None,
+    };
+    added.map_or(Value::Empty, Value::from)
+}
+
+/// The number of whole units between `start` and `end`, negative when `end` is earlier.
+pub fn date_diff(start: &Value, end: &Value, unit: &Value) -> Value {
+    let (Some(start), Some(end), Some(unit)) = (as_date(start), as_date(end), as_str(unit)) else {
+        return Value::Empty;
+    };
+    let delta = end - start;
+    let months = || {
+        let months = (end.year() as i64 - start.year() as i64) * 12 + end.month() as i64
+            - start.month() as i64;
+        // only count a month once its day and time have been reached
+        match months.signum() {
+            1 if start.checked_add_months(Months::new(months as u32))? > end => Some(months - 1),
+            -1 if start.checked_sub_months(Months::new((-months) as u32))? < end => {
+                Some(months + 1)
+            }
+            _ => Some(months),
+        }
+    };
+    let diff = match unit {
+        "second" => Some(delta.num_seconds()),
+        "minute" => Some(delta.num_minutes()),
+        "hour" => Some(delta.num_hours()),
+        "day" => Some(delta.num_days()),
+        "week" => Some(delta.num_weeks()),
+        "month" => months(),
+        "year" => months().map(|months| months / 12),
+        _ => None,
+    };
+    diff.map_or(Value::Empty, Value::I64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    #[test]
+    fn test_string_functions() {
+        assert_eq!(lower(&s("HeLLo")), s("hello"));
+        assert_eq!(upper(&s("HeLLo")), s("HELLO"));
+        assert_eq!(
+            concat(&[&s("a"), &Value::I32(1), &Value::Empty, &s("b")]),
+            s("a1b")
+        );
+        assert_eq!(
+            substring(&s("héllo"), &Value::I64(1), &Value::I64(3)),
+            s("éll")
+        );
+        assert_eq!(
+            substring(&s("héllo"), &Value::I64(2), &Value::Empty),
+            s("llo")
+        );
+        assert_eq!(starts_with(&s("alice"), &s("al")), Value::Boolean(true));
+        assert_eq!(ends_with(&s("alice"), &s("al")), Value::Boolean(false));
+        assert_eq!(length(&s("héllo")), Value::I64(5));
+        assert_eq!(length(&Value::Array(vec![Value::I32(1)])), Value::I64(1));
+        assert!(matches!(lower(&Value::I32(1)), Value::Empty));
+        assert!(matches!(length(&Value::Empty), Value::Empty));
+    }
+
+    #[test]
+    fn test_array_functions() {
+        let tags = Value::Array(vec![s("a"), s("b"), s("a")]);
+        assert_eq!(
+            append(&tags, &s("c")),
+            Value::Array(vec![s("a"), s("b"), s("a"), s("c")])
+        );
+        assert_eq!(
+            append(&Value::Empty, &Value::Array(vec![s("a"), s("b")])),
+            Value::Array(vec![s("a"), s("b")])
+        );
+        assert_eq!(remove(&tags, &s("a")), Value::Array(vec![s("b")]));
+        assert_eq!(
+            remove(&tags, &Value::Array(vec![s("a"), s("b")])),
+            Value::Array(vec![])
+        );
+        assert!(matches!(append(&s("a"), &s("b")), Value::Empty));
+        assert!(matches!(remove(&Value::Empty, &s("a")), Value::Empty));
+    }
+
+    #[test]
+    fn test_regex_match() {
+        assert_eq!(
+            regex_match(&s("user-42"), &s(r"^user-\d+$")),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            regex_match(&s("admin"), &s(r"^user-\d+$")),
+            Value::Boolean(false)
+        );
+        assert!(matches!(regex_match(&s("admin"), &s("(")), Value::Empty));
+    }
+
+    #[test]
+    fn test_date_functions() {
+        let date = s("2024-03-14T15:09:26Z");
+        assert_eq!(date_trunc(&date, &s("day")), s("2024-03-14T00:00:00+00:00"));
+        assert_eq!(
+            date_trunc(&date, &s("week")),
+            s("2024-03-11T00:00:00+00:00")
+        );
+        assert_eq!(
+            date_trunc(&date, &s("month")),
+            s("2024-03-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            date_trunc(&date, &s("year")),
+            s("2024-01-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            date_add(&date, &Value::I64(-7), &s("day")),
+            s("2024-03-07T15:09:26+00:00")
+        );
+        assert_eq!(
+            date_add(&s("2024-01-31"), &Value::I32(1), &s("month")),
+            s("2024-02-29T00:00:00+00:00")
+        );
+        assert_eq!(
+            date_diff(&s("2024-01-31"), &s("2024-03-01"), &s("day")),
+            Value::I64(30)
+        );
+        assert_eq!(
+            date_diff(&s("2024-01-31"), &s("2024-02-28"), &s("month")),
+            Value::I64(0)
+        );
+        assert_eq!(
+            date_diff(&s("2024-01-31"), &s("2024-02-29"), &s("month")),
+            Value::I64(1)
+        );
+        assert_eq!(
+            date_diff(&s("2025-03-01"), &s("2024-01-01"), &s("year")),
+            Value::I64(-1)
+        );
+        assert!(matches!(date_trunc(&date, &s("fortnight")), Value::Empty));
+        assert!(matches!(
+            date_add(&s("nope"), &Value::I64(1), &s("day")),
+            Value::Empty
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod date;
 pub mod error;
 pub mod format;
+pub mod functions;
 pub mod request;
 pub mod response;
 pub mod custom_serde;
@@ -129,6 +129,15 @@ impl Value {
         self.inner_str().contains(needle)
     }
 
+    /// Follows a dotted path (e.g. `address.city`) through nested objects.
+    /// Returns `None` if a segment is missing or a non-object value is reached.
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        path.split('.').try_fold(self, |value, key| match value {
+            Value::Object(obj) => obj.get(key),
+            _ => None,
+        })
+    }
+
     #[inline]
     #[allow(unused_variables)] // default is not used but needed for function signature
     pub fn map_value_or(
@@ -263,6 +272,7 @@ impl PartialEq<Value> for Value {
             (Value::String(s), Value::String(o)) => s == o,
             (Value::Date(s), Value::Date(o)) => s == o,
             (Value::Boolean(s), Value::Boolean(o)) => s == o,
+            (Value::Id(s), Value::Id(o)) => s == o,
             (Value::Array(s), Value::Array(o)) => s == o,
             (Value::Empty, Value::Empty) => true,
             (Value::Empty, _) => false,
@@ -710,7 +720,8 @@ impl<'de> Deserialize<'de> for Value {
                     11 => Ok(Value::U128(variant_data.newtype_variant()?)),
                     12 => Ok(Value::Date(variant_data.newtype_variant()?)),
                     13 => Ok(Value::Boolean(variant_data.newtype_variant()?)),
-                    14 => Ok(Value::Id(variant_data.newtype_variant()?)),
+                    // `ID` is written as its raw u128 but only deserializes from a UUID string
+                    14 => Ok(Value::Id(ID::from(variant_data.newtype_variant::<u128>()?))),
                     15 => Ok(Value::Array(variant_data.newtype_variant()?)),
                     16 => Ok(Value::Object(variant_data.newtype_variant()?)),
                     17 => {
@@ -1017,6 +1028,17 @@ impl From<usize> for Value {
     }
 }
 
+/// A missing optional value is stored as `Value::Empty`.
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    #[inline]
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Empty, Into::into)
+    }
+}
+
 impl From<Value> for String {
     #[inline]
     fn from(v: Value) -> Self {
@@ -1704,6 +1726,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_value_from_option() {
+        let val = Value::from(Some(String::from("nick")));
+        assert!(matches!(val, Value::String(ref s) if s == "nick"));
+
+        let val = Value::from(None::<String>);
+        assert!(matches!(val, Value::Empty));
+
+        let opt = Some(7u32);
+        let val = Value::from(&opt);
+        assert!(matches!(val, Value::U32(7)));
+    }
+
     #[test]
     #[ignore]
     fn test_value_from_datetime() {
@@ -1965,6 +2000,7 @@ mod tests {
             Value::F64(3.14),
             Value::Boolean(true),
             Value::U128(u128::MAX),
+            Value::Id(ID::from(u128::MAX)),
             Value::Empty,
             Value::Array(vec![Value::I32(1), Value::I32(2)]),
         ];
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use serde::Serialize;
+use serde::{Serialize, Serializer, ser::SerializeMap};
 
-use crate::helix_engine::traversal_core::traversal_value::TraversalValue;
+use crate::{
+    helix_engine::traversal_core::traversal_value::TraversalValue, utils::group_by::GroupRow,
+};
 
 #[derive(Clone, Default, Serialize)]
 pub struct AggregateItem<'arena> {
@@ -24,3 +26,24 @@ impl<'arena> Aggregate<'arena> {
         }
     }
 }
+
+/// A group along with the items that fell into it.
+///
+/// Serializes as the flattened `GroupRow` plus an `items` array.
+#[derive(Clone, Default)]
+pub struct AggregateRow<'arena> {
+    pub row: GroupRow,
+    pub items: Vec<TraversalValue<'arena>>,
+}
+
+impl<'arena> Serialize for AggregateRow<'arena> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        self.row.serialize_entries(&mut map)?;
+        map.serialize_entry("items", &self.items)?;
+        map.end()
+    }
+}
@@ -0,0 +1,185 @@
+//! Values of schema enums.
+//!
+//! A variant is stored as its index in the enum declaration, as a `U8` or, for enums with more
+//! than 256 variants, a `U16`. It is read back as the variant name.
+
+use bumpalo::Bump;
+use serde::Serialize;
+pub use serde::Serializer;
+
+use crate::{
+    helix_engine::traversal_core::traversal_value::TraversalValue,
+    protocol::value::Value,
+    utils::{
+        items::{Edge, Node},
+        properties::ImmutablePropertiesMap,
+    },
+};
+
+/// The value the variant at `index` of an enum with `variant_count` variants is stored as
+#[inline]
+pub fn variant_value(index: usize, variant_count: usize) -> Value {
+    match variant_count <= u8::MAX as usize + 1 {
+        true => Value::U8(index as u8),
+        false => Value::U16(index as u16),
+    }
+}
+
+/// The index of the variant a stored value holds
+#[inline]
+pub fn variant_index(value: &Value) -> Option<usize> {
+    match value {
+        Value::U8(index) => Some(*index as usize),
+        Value::U16(index) => Some(*index as usize),
+        _ => None,
+    }
+}
+
+/// The name of the variant a stored value holds
+pub fn variant_name<'v>(value: &Value, variants: &[&'v str]) -> Option<&'v str> {
+    variant_index(value).and_then(|index| variants.get(index).copied())
+}
+
+/// Serializes a property holding a variant as the variant name.
+///
+/// Values that are not a variant index, e.g. ones already read back as names, are serialized
+/// as they are.
+pub fn serialize_variant<S: Serializer>(
+    value: &Option<&Value>,
+    variants: &[&str],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value.and_then(|value| variant_name(value, variants)) {
+        Some(name) => serializer.serialize_str(name),
+        None => value.serialize(serializer),
+    }
+}
+
+/// Replaces the variant indices held by the enum `fields` of an item with the variant names,
+/// so the item can be grouped or returned as is
+pub fn decode_item<'arena>(
+    item: TraversalValue<'arena>,
+    fields: &[(&str, &[&str])],
+    arena: &'arena Bump,
+) -> TraversalValue<'arena> {
+    map_properties(item, arena, |_, key, value| {
+        let (_, variants) = fields.iter().find(|(field, _)| *field == key)?;
+        variant_name(value, variants).map(Value::from)
+    })
+}
+
+/// Rebuilds the properties of an item, and of the nodes and edges of a path, replacing every
+/// value `f` maps given the label of its item and its key
+pub fn map_properties<'arena>(
+    item: TraversalValue<'arena>,
+    arena: &'arena Bump,
+    f: impl Fn(&str, &str, &Value) -> Option<Value>,
+) -> TraversalValue<'arena> {
+    let map = |label: &str, properties: Option<ImmutablePropertiesMap<'arena>>| {
+        let properties = properties?;
+        if !properties
+            .iter()
+            .any(|(key, value)| f(label, key, value).is_some())
+        {
+            return Some(properties);
+        }
+        Some(ImmutablePropertiesMap::new(
+            properties.len(),
+            properties.iter().map(|(key, value)| {
+                let value = f(label, key, value).unwrap_or_else(|| value.clone());
+                (key, value)
+            }),
+            arena,
+        ))
+    };
+    let node = |node: Node<'arena>| Node {
+        properties: map(node.label, node.properties),
+        ..node
+    };
+    let edge = |edge: Edge<'arena>| Edge {
+        properties: map(edge.label, edge.properties),
+        ..edge
+    };
+    match item {
+        TraversalValue::Node(item) => TraversalValue::Node(node(item)),
+        TraversalValue::Edge(item) => TraversalValue::Edge(edge(item)),
+        TraversalValue::NodeWithScore { node: item, score } => TraversalValue::NodeWithScore {
+            node: node(item),
+            score,
+        },
+        TraversalValue::Vector(mut vector) => {
+            vector.properties = map(vector.label, vector.properties);
+            TraversalValue::Vector(vector)
+        }
+        TraversalValue::VectorNodeWithoutVectorData(mut vector) => {
+            vector.properties = map(vector.label, vector.properties);
+            TraversalValue::VectorNodeWithoutVectorData(vector)
+        }
+        TraversalValue::Path((nodes, edges)) => TraversalValue::Path((
+            nodes.into_iter().map(node).collect(),
+            edges.into_iter().map(edge).collect(),
+        )),
+        item => item,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARIANTS: &[&str] = &["Active", "Suspended"];
+
+    #[test]
+    fn test_variant_value_width() {
+        assert_eq!(variant_value(1, 2), Value::U8(1));
+        assert_eq!(variant_value(255, 256), Value::U8(255));
+        assert_eq!(variant_value(256, 257), Value::U16(256));
+    }
+
+    #[test]
+    fn test_variant_name() {
+        assert_eq!(variant_name(&Value::U8(1), VARIANTS), Some("Suspended"));
+        assert_eq!(variant_name(&Value::U16(0), VARIANTS), Some("Active"));
+        assert_eq!(variant_name(&Value::U8(2), VARIANTS), None);
+        assert_eq!(
+            variant_name(&Value::String("Active".to_string()), VARIANTS),
+            None
+        );
+    }
+
+    #[test]
+    fn test_decode_item() {
+        let arena = Bump::new();
+        let properties = ImmutablePropertiesMap::new(
+            2,
+            vec![("name", Value::from("alice")), ("status", Value::U8(1))].into_iter(),
+            &arena,
+        );
+        let node = TraversalValue::Node(Node {
+            id: 1,
+            label: "User",
+            version: 0,
+            properties: Some(properties),
+        });
+
+        let node = decode_item(node, &[("status", VARIANTS)], &arena);
+        assert_eq!(node.get_property("status"), Some(&Value::from("Suspended")));
+        assert_eq!(node.get_property("name"), Some(&Value::from("alice")));
+    }
+
+    #[test]
+    fn test_serialize_variant() {
+        let mut json = Vec::new();
+        serialize_variant(
+            &Some(&Value::U8(0)),
+            VARIANTS,
+            &mut sonic_rs::Serializer::new(&mut json),
+        )
+        .unwrap();
+        assert_eq!(json, b"\"Active\"");
+
+        let mut json = Vec::new();
+        serialize_variant(&None, VARIANTS, &mut sonic_rs::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, b"null");
+    }
+}
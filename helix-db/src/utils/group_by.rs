@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::Serialize;
+use serde::{Serialize, Serializer, ser::SerializeMap};
 
 use crate::protocol::value::Value;
 
@@ -24,3 +24,72 @@ impl GroupBy {
         }
     }
 }
+
+/// Function computed over a property of every item in a group
+#[derive(Debug, Clone, Copy)]
+pub enum AggregateFunction<'a> {
+    /// Number of items in the group
+    Count,
+    /// Number of distinct values of the property
+    CountDistinct(&'a str),
+    Sum(&'a str),
+    Avg(&'a str),
+    Min(&'a str),
+    Max(&'a str),
+    /// Linearly interpolated percentile of the property, with the rank between 0 and 1
+    Percentile(&'a str, f64),
+    /// All values of the property, in the order the items were traversed
+    Collect(&'a str),
+}
+
+impl<'a> AggregateFunction<'a> {
+    pub fn property(&self) -> Option<&'a str> {
+        match self {
+            Self::Count => None,
+            Self::CountDistinct(property)
+            | Self::Sum(property)
+            | Self::Avg(property)
+            | Self::Min(property)
+            | Self::Max(property)
+            | Self::Percentile(property, _)
+            | Self::Collect(property) => Some(property),
+        }
+    }
+}
+
+/// An aggregate function whose result is returned under `name` in each group
+#[derive(Debug, Clone, Copy)]
+pub struct Aggregation<'a> {
+    pub name: &'a str,
+    pub function: AggregateFunction<'a>,
+}
+
+/// A single group, holding the values it was grouped by and the result of each aggregation.
+///
+/// Serializes as one flat object, e.g. `{ "region": "eu", "total": 12.5, "count": 3 }`.
+#[derive(Debug, Clone, Default)]
+pub struct GroupRow {
+    pub keys: Vec<(String, Value)>,
+    pub aggregates: Vec<(String, Value)>,
+    pub count: i32,
+}
+
+impl GroupRow {
+    pub(crate) fn serialize_entries<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        for (key, value) in self.keys.iter().chain(self.aggregates.iter()) {
+            map.serialize_entry(key, value)?;
+        }
+        map.serialize_entry("count", &self.count)
+    }
+}
+
+impl Serialize for GroupRow {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        self.serialize_entries(&mut map)?;
+        map.end()
+    }
+}
@@ -77,6 +77,28 @@ impl<'arena> Node<'arena> {
         self.properties.and_then(|value| value.get(prop))
     }
 
+    /// Returns a copy of the node with `key` set to `value`, allocating the new
+    /// property map in the arena. The stored node is left untouched.
+    pub fn with_property(
+        self,
+        key: &'arena str,
+        value: Value,
+        arena: &'arena bumpalo::Bump,
+    ) -> Self {
+        let items = self
+            .properties
+            .iter()
+            .flat_map(|props| props.iter())
+            .filter(|(k, _)| *k != key)
+            .map(|(k, v)| (k, v.clone()))
+            .chain(std::iter::once((key, value)))
+            .collect::<Vec<_>>();
+        Node {
+            properties: Some(ImmutablePropertiesMap::new(items.len(), items.into_iter(), arena)),
+            ..self
+        }
+    }
+
     /// Deserializes bytes into a node using a custom deserializer that allocates into the provided arena
     ///
     /// NOTE: in this method, fixint encoding is used
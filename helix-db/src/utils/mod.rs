@@ -1,4 +1,5 @@
 pub mod aggregate;
+pub mod enums;
 pub mod group_by;
 pub mod id;
 pub mod items;
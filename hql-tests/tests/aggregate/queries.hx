@@ -22,6 +22,10 @@ QUERY GroupUsersByAge () =>
     users <- N<User>::GROUP_BY(age)
     RETURN users
 
+QUERY UserStatsByName () =>
+    stats <- N<User>::GROUP_BY(name, avg_age: AVG(age), oldest: MAX(age), p50: PERCENTILE(age, 0.5), emails: COUNT(DISTINCT email))
+    RETURN stats
+
 QUERY CreateUser (name: String, age: U8, email: String) =>
     user <- AddN<User>({
         name: name,